@@ -27,6 +27,7 @@ fn main() {
         Layer {
             z_level: 0,
             visible: true,
+            ..Layer::default()
         },
     );
 
@@ -40,6 +41,7 @@ fn main() {
         .with(PointStyle {
             radius: Dimension::Pixels(50.0),
             colour: Color::rgb8(0, 0xff, 0),
+            ..PointStyle::default()
         })
         .build();
     // and a red hexagon
@@ -0,0 +1,324 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    components::{
+        annotation::{apply_override, dimension_text},
+        AnnotationGraphics, DimensionStyle, Geometry,
+    },
+    Angle, Arc, BoundingBox, DrawingSpace, Line, Point, Vector,
+};
+use specs::Entity;
+
+/// A dimension showing the angle between two lines (e.g. a DXF `DIMENSION`
+/// entity with the angular type).
+///
+/// The angle is measured anticlockwise from [`AngularDimension::first`] to
+/// [`AngularDimension::second`], around the [`AngularDimension::vertex`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AngularDimension {
+    /// The point the angle is measured around.
+    pub vertex: Point,
+    /// A point on the first leg of the angle.
+    pub first: Point,
+    /// A point on the second leg of the angle.
+    pub second: Point,
+    /// How far the dimension arc is from the [`AngularDimension::vertex`].
+    pub radius: f64,
+    /// The two lines being measured.
+    ///
+    /// The [`crate::systems::SyncAnnotations`] system will re-measure the
+    /// dimension whenever either line changes.
+    pub associated_with: Option<(Entity, Entity)>,
+    /// Text to show instead of the measured value, where `<>` will be
+    /// replaced with the measurement.
+    pub text_override: Option<String>,
+    /// How the dimension should look.
+    pub style: DimensionStyle,
+}
+
+impl AngularDimension {
+    /// Create a new [`AngularDimension`] from three points.
+    pub fn new(
+        vertex: Point,
+        first: Point,
+        second: Point,
+        radius: f64,
+    ) -> Self {
+        AngularDimension {
+            vertex,
+            first,
+            second,
+            radius,
+            associated_with: None,
+            text_override: None,
+            style: DimensionStyle::default(),
+        }
+    }
+
+    /// Create an [`AngularDimension`] measuring the angle between two lines,
+    /// where each leg goes from the point the lines intersect to whichever
+    /// end of the line is furthest away.
+    ///
+    /// Parallel lines don't form an angle, so this returns [`None`].
+    pub fn between_lines(
+        first: Line,
+        second: Line,
+        radius: f64,
+    ) -> Option<Self> {
+        let vertex = infinite_line_intersection(first, second)?;
+        let furthest = |line: Line| {
+            if (line.start - vertex).square_length()
+                > (line.end - vertex).square_length()
+            {
+                line.start
+            } else {
+                line.end
+            }
+        };
+
+        Some(AngularDimension::new(
+            vertex,
+            furthest(first),
+            furthest(second),
+            radius,
+        ))
+    }
+
+    /// Re-measure the angle between two lines, returning `false` if they
+    /// aren't lines or they no longer form an angle.
+    pub fn update_from(&mut self, first: &Geometry, second: &Geometry) -> bool {
+        let measured = match (first, second) {
+            (Geometry::Line(first), Geometry::Line(second)) => {
+                AngularDimension::between_lines(*first, *second, self.radius)
+            },
+            _ => None,
+        };
+
+        match measured {
+            Some(measured) => {
+                self.vertex = measured.vertex;
+                self.first = measured.first;
+                self.second = measured.second;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// The direction of the first leg.
+    pub fn start_angle(&self) -> Angle { angle_of(self.first - self.vertex) }
+
+    /// The measured angle, in the range `0 <= angle < 2π`.
+    pub fn measurement(&self) -> Angle {
+        let end = angle_of(self.second - self.vertex);
+        (end - self.start_angle()).positive()
+    }
+
+    /// The text to display.
+    pub fn text(&self) -> String {
+        let measurement = format!(
+            "{:.*}°",
+            self.style.precision,
+            self.measurement().to_degrees()
+        );
+
+        apply_override(measurement, self.text_override.as_deref())
+    }
+
+    /// The arc-shaped dimension line.
+    pub fn dimension_arc(&self) -> Arc {
+        Arc::from_centre_radius(
+            self.vertex,
+            self.radius,
+            self.start_angle(),
+            self.measurement(),
+        )
+    }
+
+    /// Break the [`AngularDimension`] down into lines, arrowheads and text.
+    pub fn graphics(&self) -> AnnotationGraphics {
+        let mut graphics = AnnotationGraphics::new();
+        let style = &self.style;
+        let arc = self.dimension_arc();
+
+        for &point in &[self.first, self.second] {
+            let delta = point - self.vertex;
+            let distance = delta.length();
+
+            if distance == 0.0 {
+                continue;
+            }
+
+            let direction = delta / distance;
+
+            if distance + style.extension_offset < self.radius {
+                graphics.lines.push(Line::new(
+                    point + direction * style.extension_offset,
+                    self.vertex
+                        + direction * (self.radius + style.extension_overshoot),
+                ));
+            } else if distance - style.extension_offset > self.radius {
+                graphics.lines.push(Line::new(
+                    point - direction * style.extension_offset,
+                    self.vertex
+                        + direction * (self.radius - style.extension_overshoot),
+                ));
+            }
+        }
+
+        graphics.arcs.push(arc);
+
+        let tangent = |angle: Angle| {
+            let (sin, cos) = angle.sin_cos();
+            Vector::new(-sin, cos)
+        };
+
+        if arc.sweep_angle() != Angle::zero() {
+            graphics.push_arrowhead(
+                arc.start(),
+                -tangent(arc.start_angle()),
+                style.arrow_size,
+            );
+            graphics.push_arrowhead(
+                arc.end(),
+                tangent(arc.end_angle()),
+                style.arrow_size,
+            );
+        }
+
+        let middle = arc.start_angle() + arc.sweep_angle() / 2.0;
+        graphics.text.push(dimension_text(
+            arc.point_at(arc.sweep_angle() / 2.0),
+            tangent(middle),
+            self.text(),
+            style,
+        ));
+
+        graphics
+    }
+}
+
+/// The direction of a vector.
+///
+/// [`Vector::angle_from_x_axis()`] uses a fast approximation of `atan2()`,
+/// which isn't accurate enough for displaying measurements.
+fn angle_of(vector: Vector) -> Angle {
+    Angle::radians(vector.y.atan2(vector.x))
+}
+
+/// Where two infinitely long lines cross.
+fn infinite_line_intersection(first: Line, second: Line) -> Option<Point> {
+    let a = first.displacement();
+    let b = second.displacement();
+    let denominator = a.cross(b);
+
+    if denominator.abs() <= std::f64::EPSILON {
+        return None;
+    }
+
+    let t = (second.start - first.start).cross(b) / denominator;
+    Some(first.start + a * t)
+}
+
+impl Bounded<DrawingSpace> for AngularDimension {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        self.graphics().bounding_box()
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for AngularDimension {
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        self.graphics().closest_point(target)
+    }
+}
+
+impl Translate<DrawingSpace> for AngularDimension {
+    fn translate(&mut self, displacement: Vector) {
+        self.vertex += displacement;
+        self.first += displacement;
+        self.second += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for AngularDimension {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        self.vertex = transform.transform_point(self.vertex);
+        self.first = transform.transform_point(self.first);
+        self.second = transform.transform_point(self.second);
+        self.radius *= scale;
+
+        // the angle is measured anticlockwise, so mirroring would otherwise
+        // measure the other side
+        if transform.is_reflection() {
+            std::mem::swap(&mut self.first, &mut self.second);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    #[test]
+    fn angle_between_two_lines() {
+        let horizontal = Line::new(Point::new(1.0, 1.0), Point::new(5.0, 1.0));
+        let diagonal = Line::new(Point::new(3.0, 3.0), Point::new(2.0, 2.0));
+
+        let dimension =
+            AngularDimension::between_lines(horizontal, diagonal, 2.0).unwrap();
+
+        assert_eq!(dimension.vertex, Point::new(1.0, 1.0));
+        assert!(dimension
+            .measurement()
+            .radians
+            .approx_eq(&Angle::frac_pi_4().radians));
+        assert_eq!(dimension.text(), "45.00°");
+        assert!(AngularDimension::between_lines(horizontal, horizontal, 1.0)
+            .is_none());
+    }
+
+    #[test]
+    fn reflex_angles_are_measured_anticlockwise() {
+        let dimension = AngularDimension::new(
+            Point::zero(),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 0.0),
+            5.0,
+        );
+
+        let graphics = dimension.graphics();
+
+        assert!(dimension.measurement().to_degrees().approx_eq(&270.0));
+        // both legs are inside the arc, so their extension lines go outwards
+        assert_eq!(graphics.lines.len(), 2);
+        assert!(graphics.lines[0].end.y > 5.0);
+        assert_eq!(graphics.arcs[0].radius(), 5.0);
+        assert_eq!(graphics.arrowheads.len(), 2);
+    }
+
+    #[test]
+    fn bounds_follow_the_dimension_arc() {
+        let dimension = AngularDimension::new(
+            Point::zero(),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 0.0),
+            5.0,
+        );
+
+        let bounds = dimension.bounding_box();
+
+        // the reflex arc sweeps through 180° and 270°
+        assert!(bounds.min_x() <= -5.0);
+        assert!(bounds.min_y() <= -5.0);
+    }
+}
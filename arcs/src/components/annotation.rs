@@ -0,0 +1,257 @@
+use crate::{
+    algorithms::{Bounded, Closest, ClosestPoint},
+    components::{
+        Geometry, Handle, HorizontalAlignment, Text, VerticalAlignment,
+    },
+    Angle, Arc, BoundingBox, DrawingSpace, Line, Point, Vector,
+};
+use specs::Entity;
+
+/// Settings shared by all dimensions.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DimensionStyle {
+    /// The height of the measurement text.
+    pub text_height: f64,
+    /// How long each arrowhead is.
+    pub arrow_size: f64,
+    /// How far extension lines go past the dimension line.
+    pub extension_overshoot: f64,
+    /// The gap between a definition point and the start of its extension
+    /// line.
+    pub extension_offset: f64,
+    /// The gap between the dimension line and its text.
+    pub text_gap: f64,
+    /// The number of decimal places to show in the measurement text.
+    pub precision: usize,
+}
+
+impl Default for DimensionStyle {
+    fn default() -> DimensionStyle {
+        DimensionStyle {
+            text_height: 2.5,
+            arrow_size: 2.5,
+            extension_overshoot: 1.25,
+            extension_offset: 0.625,
+            text_gap: 0.625,
+            precision: 2,
+        }
+    }
+}
+
+/// The things used to draw an annotation (e.g. a dimension), expressed in
+/// terms of simpler geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationGraphics {
+    /// Straight lines (extension lines, dimension lines, etc.).
+    pub lines: Vec<Line>,
+    /// Curved lines (e.g. the dimension line for an angle).
+    pub arcs: Vec<Arc>,
+    /// Filled triangles, as `[tip, left, right]`.
+    pub arrowheads: Vec<[Point; 3]>,
+    /// Any text to display.
+    pub text: Vec<Text>,
+}
+
+impl AnnotationGraphics {
+    /// Create an empty [`AnnotationGraphics`].
+    pub fn new() -> Self {
+        AnnotationGraphics {
+            lines: Vec::new(),
+            arcs: Vec::new(),
+            arrowheads: Vec::new(),
+            text: Vec::new(),
+        }
+    }
+
+    /// Add an arrowhead which points in `direction` and ends at `tip`.
+    pub fn push_arrowhead(&mut self, tip: Point, direction: Vector, size: f64) {
+        let direction = direction.normalize();
+        let back = tip - direction * size;
+        let half_width = Vector::new(-direction.y, direction.x) * (size / 6.0);
+
+        self.arrowheads
+            .push([tip, back + half_width, back - half_width]);
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let lines = self.lines.iter().flat_map(|l| vec![l.start, l.end]);
+        let arrowheads = self.arrowheads.iter().flat_map(|a| a.to_vec());
+        let text = self.text.iter().flat_map(|t| t.corners().to_vec());
+
+        let arcs = self.arcs.iter().flat_map(|a| {
+            let bounds = a.bounding_box();
+            vec![bounds.bottom_left(), bounds.top_right()]
+        });
+
+        lines.chain(arcs).chain(arrowheads).chain(text)
+    }
+}
+
+impl Default for AnnotationGraphics {
+    fn default() -> AnnotationGraphics { AnnotationGraphics::new() }
+}
+
+impl Bounded<DrawingSpace> for AnnotationGraphics {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        let mut points = self.points();
+        let first = points.next().unwrap_or_else(Point::zero);
+        let (bottom_left, top_right) =
+            points.fold((first, first), |(bottom_left, top_right), point| {
+                (bottom_left.min(point), top_right.max(point))
+            });
+
+        BoundingBox::new_unchecked(bottom_left, top_right)
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for AnnotationGraphics {
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        let lines = self.lines.iter().map(|l| l.closest_point(target));
+        // arrowheads are small enough that their edges are good enough
+        let arrowheads = self.arrowheads.iter().flat_map(|&[a, b, c]| {
+            vec![
+                Line::new(a, b).closest_point(target),
+                Line::new(b, c).closest_point(target),
+                Line::new(c, a).closest_point(target),
+            ]
+        });
+        let arcs = self.arcs.iter().map(|a| a.closest_point(target));
+        let text = self.text.iter().map(|t| t.closest_point(target));
+
+        lines
+            .chain(arcs)
+            .chain(arrowheads)
+            .chain(text)
+            .flat_map(|closest| closest.points().to_vec())
+            .min_by(|a, b| {
+                let a = (*a - target).square_length();
+                let b = (*b - target).square_length();
+                a.partial_cmp(&b).unwrap()
+            })
+            .map(Closest::One)
+            .unwrap_or(Closest::Infinite)
+    }
+}
+
+/// A point on another entity's geometry which an annotation is attached to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Anchor {
+    pub entity: Entity,
+    pub handle: Handle,
+}
+
+impl Anchor {
+    pub const fn new(entity: Entity, handle: Handle) -> Self {
+        Anchor { entity, handle }
+    }
+
+    /// Find where the [`Anchor`] is on some [`Geometry`], returning [`None`]
+    /// if it doesn't have that [`Handle`].
+    ///
+    /// The centre of a line is its midpoint, and every handle on a point
+    /// refers to the point itself.
+    pub fn locate(&self, geometry: &Geometry) -> Option<Point> {
+        match (geometry, self.handle) {
+            (Geometry::Point(p), _) => Some(*p),
+            (Geometry::Line(line), Handle::Start) => Some(line.start),
+            (Geometry::Line(line), Handle::End) => Some(line.end),
+            (Geometry::Line(line), Handle::Centre) => {
+                Some(line.start.lerp(line.end, 0.5))
+            },
+            (Geometry::Arc(arc), Handle::Start) => Some(arc.start()),
+            (Geometry::Arc(arc), Handle::End) => Some(arc.end()),
+            (Geometry::Arc(arc), Handle::Centre) => Some(arc.centre()),
+            _ => None,
+        }
+    }
+}
+
+/// Generate the text for a dimension.
+///
+/// Following the DXF convention, any `<>` in the `text_override` will be
+/// replaced with the measured value.
+pub fn measurement_text(
+    value: f64,
+    precision: usize,
+    text_override: Option<&str>,
+) -> String {
+    apply_override(format!("{:.*}", precision, value), text_override)
+}
+
+/// Substitute a formatted measurement into the `text_override`, if there is
+/// one.
+pub(crate) fn apply_override(
+    measurement: String,
+    text_override: Option<&str>,
+) -> String {
+    match text_override {
+        Some(text) => text.replace("<>", &measurement),
+        None => measurement,
+    }
+}
+
+/// Place text centred above a point on a dimension line, rotated so it
+/// always reads from left to right.
+pub(crate) fn dimension_text(
+    midpoint: Point,
+    direction: Vector,
+    content: String,
+    style: &DimensionStyle,
+) -> Text {
+    let mut rotation = Angle::radians(direction.y.atan2(direction.x));
+
+    if rotation.radians > Angle::frac_pi_2().radians + 1e-9
+        || rotation.radians <= -Angle::frac_pi_2().radians
+    {
+        rotation += Angle::pi();
+    }
+    let rotation = rotation.signed();
+
+    let (sin, cos) = rotation.sin_cos();
+    let up = Vector::new(-sin, cos);
+
+    Text {
+        insertion_point: midpoint + up * style.text_gap,
+        height: style.text_height,
+        rotation,
+        horizontal_alignment: HorizontalAlignment::Centre,
+        vertical_alignment: VerticalAlignment::Bottom,
+        content,
+    }
+}
+
+/// Place horizontal text just past the end of a leader going from `start`
+/// to `end`, on the same side as the leader points.
+pub(crate) fn leader_text(
+    start: Point,
+    end: Point,
+    content: String,
+    style: &DimensionStyle,
+) -> Text {
+    let (horizontal_alignment, gap) = if end.x >= start.x {
+        (HorizontalAlignment::Left, style.text_gap)
+    } else {
+        (HorizontalAlignment::Right, -style.text_gap)
+    };
+
+    Text {
+        insertion_point: end + Vector::new(gap, 0.0),
+        height: style.text_height,
+        rotation: Angle::zero(),
+        horizontal_alignment,
+        vertical_alignment: VerticalAlignment::Middle,
+        content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overridden_measurement_text() {
+        assert_eq!(measurement_text(1.23456, 2, None), "1.23");
+        assert_eq!(measurement_text(1.23456, 0, Some("<> mm")), "1 mm");
+        assert_eq!(measurement_text(1.23456, 2, Some("TYP.")), "TYP.");
+    }
+}
@@ -0,0 +1,507 @@
+//! Checking a drawing for common problems, and optionally fixing them.
+
+use crate::{
+    algorithms::Bounded,
+    components::{
+        soft_delete, Deleted, DrawingObject, Geometry, Group, GroupMember,
+        Name, NameTable, Parent,
+    },
+    Point,
+};
+use specs::prelude::*;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+/// Something which is wrong with a drawing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditIssue {
+    /// A [`crate::Line`] whose start and end are the same.
+    ZeroLengthLine(Entity),
+    /// A [`crate::Arc`] with no radius.
+    ZeroRadiusArc(Entity),
+    /// Geometry containing a `NaN` or infinite coordinate.
+    NonFiniteCoordinates(Entity),
+    /// A component which refers to an entity that doesn't exist.
+    DanglingReference {
+        entity: Entity,
+        target: Entity,
+        kind: ReferenceKind,
+    },
+    /// An entry in the [`NameTable`] which doesn't match the [`Name`]
+    /// components.
+    StaleName { name: Name, entity: Entity },
+    /// A [`DrawingObject`] which is identical to another one on the same
+    /// layer.
+    Duplicate { original: Entity, duplicate: Entity },
+}
+
+impl AuditIssue {
+    /// Can [`repair()`] fix this issue?
+    pub fn is_fixable(&self) -> bool {
+        match self {
+            AuditIssue::DanglingReference { kind, .. } => {
+                *kind != ReferenceKind::Layer
+            },
+            _ => true,
+        }
+    }
+}
+
+impl Display for AuditIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditIssue::ZeroLengthLine(entity) => {
+                write!(f, "Entity {} is a line with no length", entity.id())
+            },
+            AuditIssue::ZeroRadiusArc(entity) => {
+                write!(f, "Entity {} is an arc with no radius", entity.id())
+            },
+            AuditIssue::NonFiniteCoordinates(entity) => write!(
+                f,
+                "Entity {} has NaN or infinite coordinates",
+                entity.id()
+            ),
+            AuditIssue::DanglingReference {
+                entity,
+                target,
+                kind,
+            } => write!(
+                f,
+                "Entity {} refers to a {} ({}) which doesn't exist",
+                entity.id(),
+                kind,
+                target.id()
+            ),
+            AuditIssue::StaleName { name, entity } => write!(
+                f,
+                "The name table says \"{}\" is entity {}, but it isn't",
+                name.as_str(),
+                entity.id()
+            ),
+            AuditIssue::Duplicate {
+                original,
+                duplicate,
+            } => write!(
+                f,
+                "Entity {} is a duplicate of entity {}",
+                duplicate.id(),
+                original.id()
+            ),
+        }
+    }
+}
+
+/// The different ways one entity can refer to another.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ReferenceKind {
+    /// A [`DrawingObject::layer`].
+    Layer,
+    /// An entity's [`Parent`].
+    Parent,
+    /// The [`Group`] a [`GroupMember`] belongs to.
+    Group,
+    /// One of a [`Group`]'s members.
+    GroupMember,
+    /// The entity a hatch boundary or dimension is associated with.
+    Association,
+}
+
+impl Display for ReferenceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReferenceKind::Layer => "layer",
+            ReferenceKind::Parent => "parent",
+            ReferenceKind::Group => "group",
+            ReferenceKind::GroupMember => "group member",
+            ReferenceKind::Association => "associated entity",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// The result of [`audit()`]ing a drawing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditReport {
+    pub issues: Vec<AuditIssue>,
+}
+
+impl AuditReport {
+    /// Did the audit find nothing wrong?
+    pub fn is_clean(&self) -> bool { self.issues.is_empty() }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AuditIssue> + '_ {
+        self.issues.iter()
+    }
+
+    pub fn len(&self) -> usize { self.issues.len() }
+
+    pub fn is_empty(&self) -> bool { self.issues.is_empty() }
+}
+
+/// Check a drawing for degenerate geometry, references to entities which
+/// don't exist, an out-of-date [`NameTable`], and duplicated objects.
+///
+/// Objects which have been soft-deleted (see [`Deleted`]) are skipped.
+pub fn audit(world: &World) -> AuditReport {
+    let entities = world.entities();
+    let objects = world.read_storage::<DrawingObject>();
+    let parents = world.read_storage::<Parent>();
+    let groups = world.read_storage::<Group>();
+    let members = world.read_storage::<GroupMember>();
+    // repair() soft-deletes things, so skip them or they'd be reported again
+    // until they're purged
+    let deleted = world.read_storage::<Deleted>();
+    let mut issues = Vec::new();
+    let mut dangling = |entity, target, kind| {
+        if !entities.is_alive(target) {
+            issues.push(AuditIssue::DanglingReference {
+                entity,
+                target,
+                kind,
+            });
+        }
+    };
+
+    for (entity, object, _) in (&entities, &objects, !&deleted).join() {
+        dangling(entity, object.layer, ReferenceKind::Layer);
+
+        for target in object.geometry.associations() {
+            dangling(entity, target, ReferenceKind::Association);
+        }
+    }
+
+    for (entity, Parent(parent), _) in (&entities, &parents, !&deleted).join() {
+        dangling(entity, *parent, ReferenceKind::Parent);
+    }
+
+    for (entity, GroupMember(group)) in (&entities, &members).join() {
+        dangling(entity, *group, ReferenceKind::Group);
+    }
+
+    for (entity, group) in (&entities, &groups).join() {
+        for &member in group.members() {
+            dangling(entity, member, ReferenceKind::GroupMember);
+        }
+    }
+
+    let mut duplicates = Duplicates::default();
+
+    for (entity, object, _) in (&entities, &objects, !&deleted).join() {
+        match object.geometry {
+            Geometry::Line(line) if line.length() == 0.0 => {
+                issues.push(AuditIssue::ZeroLengthLine(entity));
+            },
+            Geometry::Arc(arc) if arc.radius() == 0.0 => {
+                issues.push(AuditIssue::ZeroRadiusArc(entity));
+            },
+            ref geometry if !is_finite(geometry) => {
+                issues.push(AuditIssue::NonFiniteCoordinates(entity));
+            },
+            _ => {
+                if let Some(original) = duplicates.check(entity, object) {
+                    issues.push(AuditIssue::Duplicate {
+                        original,
+                        duplicate: entity,
+                    });
+                }
+            },
+        }
+    }
+
+    if let Some(table) = world.try_fetch::<NameTable>() {
+        let mut copy = NameTable::clone(&table);
+
+        for (name, entity) in copy.validate(world) {
+            issues.push(AuditIssue::StaleName { name, entity });
+        }
+    }
+
+    AuditReport { issues }
+}
+
+/// Fix every [`AuditIssue::is_fixable()`] issue in a report, returning how
+/// many were fixed.
+///
+/// - Degenerate, non-finite, and duplicate objects are soft-deleted (see
+///   [`crate::components::Deleted`])
+/// - Entities whose [`Parent`] no longer exists are soft-deleted
+/// - Missing group members and [`GroupMember`]s pointing at missing groups are
+///   removed
+/// - Associations with missing entities are cleared
+/// - The [`NameTable`] is revalidated
+///
+/// Objects on a missing layer are left for the user to deal with.
+pub fn repair(world: &World, report: &AuditReport) -> usize {
+    let mut fixed = 0;
+
+    for issue in report.iter().filter(|issue| issue.is_fixable()) {
+        match *issue {
+            AuditIssue::ZeroLengthLine(entity)
+            | AuditIssue::ZeroRadiusArc(entity)
+            | AuditIssue::NonFiniteCoordinates(entity)
+            | AuditIssue::Duplicate {
+                duplicate: entity, ..
+            } => soft_delete(world, entity),
+            AuditIssue::DanglingReference { entity, kind, .. } => {
+                repair_reference(world, entity, kind)
+            },
+            AuditIssue::StaleName { .. } => {
+                if let Some(mut table) = world.try_fetch_mut::<NameTable>() {
+                    table.validate(world);
+                }
+            },
+        }
+
+        fixed += 1;
+    }
+
+    fixed
+}
+
+fn repair_reference(world: &World, entity: Entity, kind: ReferenceKind) {
+    let entities = world.entities();
+
+    match kind {
+        ReferenceKind::Layer => {},
+        ReferenceKind::Parent => soft_delete(world, entity),
+        ReferenceKind::Group => {
+            world.write_storage::<GroupMember>().remove(entity);
+        },
+        ReferenceKind::GroupMember => {
+            if let Some(group) = world.write_storage::<Group>().get_mut(entity)
+            {
+                group.retain(|&member| entities.is_alive(member));
+            }
+        },
+        ReferenceKind::Association => {
+            let mut objects = world.write_storage::<DrawingObject>();
+            let alive = |e: &Option<Entity>| match e {
+                Some(e) if !entities.is_alive(*e) => None,
+                other => *other,
+            };
+
+            if let Some(object) = objects.get_mut(entity) {
+                let dead = object
+                    .geometry
+                    .associations()
+                    .into_iter()
+                    .any(|e| !entities.is_alive(e));
+
+                match object.geometry {
+                    Geometry::Hatch(ref mut hatch) => {
+                        for hatch_loop in &mut hatch.loops {
+                            hatch_loop.associated_with =
+                                alive(&hatch_loop.associated_with);
+                        }
+                    },
+                    Geometry::RadialDimension(ref mut dim) => {
+                        dim.associated_with = alive(&dim.associated_with);
+                    },
+                    Geometry::DiameterDimension(ref mut dim) => {
+                        dim.associated_with = alive(&dim.associated_with);
+                    },
+                    // these need all their associations to be measured
+                    Geometry::LinearDimension(ref mut dim) if dead => {
+                        dim.associated_with = None;
+                    },
+                    Geometry::AngularDimension(ref mut dim) if dead => {
+                        dim.associated_with = None;
+                    },
+                    Geometry::Leader(ref mut leader) if dead => {
+                        leader.associated_with = None;
+                    },
+                    _ => {},
+                }
+            }
+        },
+    }
+}
+
+fn is_finite(geometry: &Geometry) -> bool {
+    let points = |points: &[Point]| {
+        points.iter().all(|p| p.x.is_finite() && p.y.is_finite())
+    };
+
+    match geometry {
+        Geometry::Line(line) => points(&[line.start, line.end]),
+        Geometry::Point(point) => points(&[*point]),
+        Geometry::Arc(arc) => {
+            points(&[arc.centre()])
+                && arc.radius().is_finite()
+                && arc.start_angle().radians.is_finite()
+                && arc.sweep_angle().radians.is_finite()
+        },
+        Geometry::Ellipse(ellipse) => {
+            points(&[ellipse.centre()])
+                && ellipse.semi_major().is_finite()
+                && ellipse.semi_minor().is_finite()
+                && ellipse.rotation().radians.is_finite()
+        },
+        Geometry::Polyline(polyline) => polyline
+            .vertices()
+            .iter()
+            .all(|v| points(&[v.point]) && v.bulge.is_finite()),
+        Geometry::CubicBezier(curve) => points(&[
+            curve.start,
+            curve.first_control_point,
+            curve.second_control_point,
+            curve.end,
+        ]),
+        Geometry::QuadraticBezier(curve) => {
+            points(&[curve.start, curve.control_point, curve.end])
+        },
+        Geometry::Spline(spline) => points(spline.control_points()),
+        Geometry::XLine(xline) => {
+            points(&[xline.base_point, xline.direction.to_point()])
+        },
+        Geometry::Ray(ray) => points(&[ray.start, ray.direction.to_point()]),
+        other => {
+            let bounds = other.bounding_box();
+            points(&[bounds.bottom_left(), bounds.top_right()])
+        },
+    }
+}
+
+/// Finds identical [`DrawingObject`]s, using their bounding boxes to avoid
+/// comparing everything with everything else.
+#[derive(Default)]
+struct Duplicates<'a> {
+    seen: HashMap<DuplicateKey, Vec<(Entity, &'a Geometry)>>,
+}
+
+/// The layer and bounding box (as raw bits) of an object.
+type DuplicateKey = (Entity, [u64; 4]);
+
+impl<'a> Duplicates<'a> {
+    /// Returns the entity `object` duplicates, if any.
+    fn check(
+        &mut self,
+        entity: Entity,
+        object: &'a DrawingObject,
+    ) -> Option<Entity> {
+        let bounds = object.geometry.bounding_box();
+        let key = [
+            bounds.min_x().to_bits(),
+            bounds.min_y().to_bits(),
+            bounds.max_x().to_bits(),
+            bounds.max_y().to_bits(),
+        ];
+        let candidates = self.seen.entry((object.layer, key)).or_default();
+
+        if let Some(&(original, _)) =
+            candidates.iter().find(|(_, g)| **g == object.geometry)
+        {
+            return Some(original);
+        }
+
+        candidates.push((entity, &object.geometry));
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{Deleted, Layer},
+        Line,
+    };
+
+    fn object(world: &mut World, layer: Entity, geometry: Geometry) -> Entity {
+        world
+            .create_entity()
+            .with(DrawingObject { geometry, layer })
+            .build()
+    }
+
+    #[test]
+    fn find_degenerate_and_duplicate_geometry() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().with(Layer::default()).build();
+        let line = Line::new(Point::zero(), Point::new(1.0, 1.0));
+        let original = object(&mut world, layer, Geometry::Line(line));
+        let copy = object(&mut world, layer, Geometry::Line(line));
+        let dot = Line::new(Point::new(2.0, 2.0), Point::new(2.0, 2.0));
+        let zero_length = object(&mut world, layer, Geometry::Line(dot));
+        let nan = object(
+            &mut world,
+            layer,
+            Geometry::Point(Point::new(std::f64::NAN, 0.0)),
+        );
+
+        let report = audit(&world);
+
+        assert_eq!(
+            report.issues,
+            vec![
+                AuditIssue::Duplicate {
+                    original,
+                    duplicate: copy
+                },
+                AuditIssue::ZeroLengthLine(zero_length),
+                AuditIssue::NonFiniteCoordinates(nan),
+            ]
+        );
+
+        assert_eq!(repair(&world, &report), 3);
+        {
+            let deleted = world.read_storage::<Deleted>();
+            assert!(!deleted.contains(original));
+            assert!(deleted.contains(copy));
+            assert!(deleted.contains(nan));
+        }
+        assert!(audit(&world).is_clean());
+
+        // a soft-deleted original doesn't make its copies duplicates
+        soft_delete(&world, original);
+        let _another_copy = object(&mut world, layer, Geometry::Line(line));
+        assert!(audit(&world).is_clean());
+    }
+
+    #[test]
+    fn find_dangling_references() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let group = world.create_entity().build();
+        let member = world.create_entity().with(GroupMember(group)).build();
+        let orphan = object(&mut world, layer, Geometry::Point(Point::zero()));
+        world.delete_entity(layer).unwrap();
+        world.delete_entity(group).unwrap();
+        world.maintain();
+        let mut table = NameTable::default();
+        table.insert(Name::new("ghost"), layer);
+        world.insert(table);
+
+        let report = audit(&world);
+
+        assert_eq!(
+            report.issues,
+            vec![
+                AuditIssue::DanglingReference {
+                    entity: orphan,
+                    target: layer,
+                    kind: ReferenceKind::Layer,
+                },
+                AuditIssue::DanglingReference {
+                    entity: member,
+                    target: group,
+                    kind: ReferenceKind::Group,
+                },
+                AuditIssue::StaleName {
+                    name: Name::new("ghost"),
+                    entity: layer,
+                },
+            ]
+        );
+
+        assert_eq!(repair(&world, &report), 2);
+        assert!(world.read_storage::<GroupMember>().get(member).is_none());
+        assert!(world.fetch::<NameTable>().is_empty());
+        assert_eq!(audit(&world).len(), 1);
+    }
+}
@@ -0,0 +1,379 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    components::Geometry,
+    Angle, BoundingBox, DrawingSpace, Point, Vector,
+};
+use std::collections::BTreeMap;
+
+/// A named collection of geometry which can be placed in a drawing many
+/// times using an [`Insert`] (e.g. a DXF `BLOCK`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockDefinition {
+    /// The name [`Insert`]s use to refer to this [`BlockDefinition`].
+    pub name: String,
+    /// The point in the block's geometry which is placed at
+    /// [`Insert::position`].
+    pub base_point: Point,
+    /// The objects making up the block, which may include [`Insert`]s of
+    /// other blocks.
+    pub geometry: Vec<Geometry>,
+}
+
+impl BlockDefinition {
+    /// Create a new [`BlockDefinition`].
+    pub fn new<S: Into<String>>(
+        name: S,
+        base_point: Point,
+        geometry: Vec<Geometry>,
+    ) -> Self {
+        BlockDefinition {
+            name: name.into(),
+            base_point,
+            geometry,
+        }
+    }
+}
+
+/// A global [`specs::prelude::Resource`] containing every
+/// [`BlockDefinition`] in a drawing, looked up by name.
+///
+/// Like DXF, names are case-insensitive.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockTable {
+    blocks: BTreeMap<String, BlockDefinition>,
+    generation: u64,
+}
+
+impl BlockTable {
+    fn key(name: &str) -> String { name.to_uppercase() }
+
+    /// Look up a [`BlockDefinition`] by name.
+    pub fn get(&self, name: &str) -> Option<&BlockDefinition> {
+        self.blocks.get(&BlockTable::key(name))
+    }
+
+    /// Add a [`BlockDefinition`], returning the one it replaced (if any).
+    pub fn insert(
+        &mut self,
+        block: BlockDefinition,
+    ) -> Option<BlockDefinition> {
+        self.generation += 1;
+        self.blocks.insert(BlockTable::key(&block.name), block)
+    }
+
+    /// Remove a [`BlockDefinition`].
+    pub fn remove(&mut self, name: &str) -> Option<BlockDefinition> {
+        self.generation += 1;
+        self.blocks.remove(&BlockTable::key(name))
+    }
+
+    /// Iterate over every [`BlockDefinition`], in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = &BlockDefinition> + '_ {
+        self.blocks.values()
+    }
+
+    pub fn len(&self) -> usize { self.blocks.len() }
+
+    pub fn is_empty(&self) -> bool { self.blocks.is_empty() }
+
+    /// A counter which changes whenever a [`BlockDefinition`] is added or
+    /// removed, so systems know when to re-expand their [`Insert`]s.
+    pub fn generation(&self) -> u64 { self.generation }
+}
+
+/// A reference to a [`BlockDefinition`], placing a copy of its geometry in
+/// the drawing (e.g. a DXF `INSERT` entity).
+///
+/// On its own an [`Insert`] only knows where it is, so the
+/// [`crate::components::Geometry`] traits treat it as a single point at
+/// [`Insert::position`]. Use [`Insert::expand()`] with the [`BlockTable`] to
+/// get the actual geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Insert {
+    /// The name of the [`BlockDefinition`] being inserted.
+    pub block: String,
+    /// Where the block's [`BlockDefinition::base_point`] is placed.
+    pub position: Point,
+    /// How much the block is stretched horizontally (negative values mirror
+    /// it).
+    pub scale_x: f64,
+    /// How much the block is stretched vertically (negative values mirror
+    /// it).
+    pub scale_y: f64,
+    /// How much the block is rotated (anticlockwise) around
+    /// [`Insert::position`].
+    pub rotation: Angle,
+}
+
+/// How deeply [`Insert`]s can be nested inside [`BlockDefinition`]s before
+/// we assume the blocks refer to each other in a cycle.
+const MAX_NESTING: usize = 32;
+
+impl Insert {
+    /// Create a new [`Insert`] which places a block without scaling or
+    /// rotating it.
+    pub fn new<S: Into<String>>(block: S, position: Point) -> Self {
+        Insert {
+            block: block.into(),
+            position,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotation: Angle::zero(),
+        }
+    }
+
+    /// The transform which moves a block's geometry from its definition to
+    /// where it appears in the drawing.
+    pub fn transform(
+        &self,
+        base_point: Point,
+    ) -> AffineTransform<DrawingSpace> {
+        AffineTransform::translation(-base_point.to_vector())
+            .then(AffineTransform::scale(self.scale_x, self.scale_y))
+            .then(AffineTransform::rotation(self.rotation))
+            .then(AffineTransform::translation(self.position.to_vector()))
+    }
+
+    /// Get a copy of the block's geometry, positioned where the [`Insert`]
+    /// places it.
+    ///
+    /// Nested [`Insert`]s are expanded too, so the result never contains an
+    /// [`Insert`]. Blocks which are missing from the [`BlockTable`] or
+    /// which contain themselves are skipped, as is any geometry that can't
+    /// be scaled non-uniformly (e.g. text in a stretched block).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use arcs::{components::*, Angle, Line, Point};
+    /// # use euclid::approxeq::ApproxEq;
+    /// let mut blocks = BlockTable::default();
+    /// blocks.insert(BlockDefinition::new(
+    ///     "Bolt",
+    ///     Point::zero(),
+    ///     vec![Geometry::Line(Line::new(Point::zero(), Point::new(1.0, 0.0)))],
+    /// ));
+    /// let insert = Insert {
+    ///     rotation: Angle::frac_pi_2(),
+    ///     scale_x: 2.0,
+    ///     ..Insert::new("bolt", Point::new(5.0, 5.0))
+    /// };
+    ///
+    /// let got = insert.expand(&blocks);
+    ///
+    /// match got.as_slice() {
+    ///     [Geometry::Line(line)] => {
+    ///         assert!(line.start.approx_eq(&Point::new(5.0, 5.0)));
+    ///         assert!(line.end.approx_eq(&Point::new(5.0, 7.0)));
+    ///     },
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn expand(&self, blocks: &BlockTable) -> Vec<Geometry> {
+        let mut geometry = Vec::new();
+        let mut stack = Vec::new();
+        self.expand_into(
+            blocks,
+            AffineTransform::identity(),
+            &mut stack,
+            &mut geometry,
+        );
+
+        geometry
+    }
+
+    fn expand_into<'a>(
+        &'a self,
+        blocks: &'a BlockTable,
+        parent: AffineTransform<DrawingSpace>,
+        stack: &mut Vec<&'a str>,
+        geometry: &mut Vec<Geometry>,
+    ) {
+        let definition = match blocks.get(&self.block) {
+            Some(definition) => definition,
+            None => {
+                log::warn!("Unable to find the \"{}\" block", self.block);
+                return;
+            },
+        };
+
+        if stack.len() >= MAX_NESTING
+            || stack.iter().any(|name| {
+                BlockTable::key(name) == BlockTable::key(&definition.name)
+            })
+        {
+            log::warn!("The \"{}\" block contains itself", definition.name);
+            return;
+        }
+
+        let transform = self.transform(definition.base_point).then(parent);
+        stack.push(&definition.name);
+
+        for item in &definition.geometry {
+            match item {
+                Geometry::Insert(nested) => {
+                    nested.expand_into(blocks, transform, stack, geometry)
+                },
+                other => match other.transformed_by(transform) {
+                    Ok(transformed) => geometry.push(transformed),
+                    Err(e) => log::warn!(
+                        "Unable to place {:?} from the \"{}\" block: {}",
+                        other,
+                        definition.name,
+                        e
+                    ),
+                },
+            }
+        }
+
+        stack.pop();
+    }
+
+    /// The area covered by the block's geometry, or just the
+    /// [`Insert::position`] if the block is empty or missing.
+    pub fn bounding_box_within(
+        &self,
+        blocks: &BlockTable,
+    ) -> BoundingBox<DrawingSpace> {
+        BoundingBox::around(self.expand(blocks))
+            .unwrap_or_else(|| self.bounding_box())
+    }
+}
+
+impl Bounded<DrawingSpace> for Insert {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        self.position.bounding_box()
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for Insert {
+    fn closest_point(&self, _target: Point) -> Closest<DrawingSpace> {
+        Closest::One(self.position)
+    }
+}
+
+impl Translate<DrawingSpace> for Insert {
+    fn translate(&mut self, displacement: Vector) {
+        self.position += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for Insert {
+    /// Inserts can be scaled along their own axes, so anything except
+    /// shearing the block is allowed.
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let (sin, cos) = self.rotation.sin_cos();
+        let x_axis =
+            transform.transform_vector(Vector::new(cos, sin) * self.scale_x);
+        let y_axis =
+            transform.transform_vector(Vector::new(-sin, cos) * self.scale_y);
+        let (scale_x, y_length) = (x_axis.length(), y_axis.length());
+
+        if scale_x == 0.0 || y_length == 0.0 {
+            return Err(TransformError::Degenerate);
+        }
+
+        // the block's axes need to stay perpendicular
+        let tolerance = crate::algorithms::DEFAULT_TOLERANCE;
+        if x_axis.dot(y_axis).abs() > tolerance * scale_x * y_length {
+            return Err(TransformError::NonUniform);
+        }
+
+        let rotation = Angle::radians(x_axis.y.atan2(x_axis.x));
+        let mirrored = x_axis.cross(y_axis) < 0.0;
+
+        self.position = transform.transform_point(self.position);
+        self.rotation = rotation;
+        self.scale_x = scale_x;
+        self.scale_y = if mirrored { -y_length } else { y_length };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::mirror, Line};
+    use euclid::approxeq::ApproxEq;
+
+    fn square(name: &str) -> BlockDefinition {
+        BlockDefinition::new(
+            name,
+            Point::new(1.0, 1.0),
+            vec![
+                Geometry::Line(Line::new(
+                    Point::new(1.0, 1.0),
+                    Point::new(2.0, 1.0),
+                )),
+                Geometry::Line(Line::new(
+                    Point::new(2.0, 1.0),
+                    Point::new(2.0, 2.0),
+                )),
+            ],
+        )
+    }
+
+    #[test]
+    fn nested_blocks_are_expanded() {
+        let mut blocks = BlockTable::default();
+        blocks.insert(square("Square"));
+        blocks.insert(BlockDefinition::new(
+            "Pair",
+            Point::zero(),
+            vec![
+                Geometry::Insert(Insert::new("Square", Point::zero())),
+                Geometry::Insert(Insert::new("Square", Point::new(5.0, 0.0))),
+            ],
+        ));
+        let insert = Insert::new("Pair", Point::new(0.0, 10.0));
+
+        let got = insert.bounding_box_within(&blocks);
+
+        assert_eq!(insert.expand(&blocks).len(), 4);
+        assert_eq!(
+            got,
+            BoundingBox::new(Point::new(0.0, 10.0), Point::new(6.0, 11.0))
+        );
+    }
+
+    #[test]
+    fn blocks_cant_contain_themselves() {
+        let mut blocks = BlockTable::default();
+        let mut recursive = square("Recursive");
+        recursive
+            .geometry
+            .push(Geometry::Insert(Insert::new("recursive", Point::zero())));
+        blocks.insert(recursive);
+
+        let got = Insert::new("Recursive", Point::zero()).expand(&blocks);
+
+        assert_eq!(got.len(), 2);
+    }
+
+    #[test]
+    fn mirroring_an_insert_flips_its_scale() {
+        let mut blocks = BlockTable::default();
+        blocks.insert(square("Square"));
+        let insert = Insert::new("Square", Point::new(3.0, 0.0));
+        let axis = Line::new(Point::zero(), Point::new(0.0, 1.0));
+
+        let got = mirror(&insert, axis).unwrap();
+
+        assert!(got.position.approx_eq(&Point::new(-3.0, 0.0)));
+        assert!(got.scale_x.approx_eq(&1.0));
+        assert!(got.scale_y.approx_eq(&-1.0));
+        assert!(got.rotation.radians.abs().approx_eq(&std::f64::consts::PI));
+        let corner = match got.expand(&blocks)[0] {
+            Geometry::Line(line) => line.end,
+            _ => unreachable!(),
+        };
+        assert!(corner.approx_eq(&Point::new(-4.0, 0.0)));
+    }
+}
@@ -0,0 +1,131 @@
+use piet::Color;
+use specs::prelude::*;
+use specs_derive::Component;
+
+/// The colour an entity should be drawn with.
+///
+/// Entities without a [`Colour`] use the stroke from their
+/// [`crate::components::LineStyle`] (if they have one) or their layer's
+/// colour. See [`crate::components::resolved_style()`] for the full set of
+/// rules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component)]
+#[storage(DenseVecStorage)]
+pub enum Colour {
+    /// Use the layer's colour.
+    ByLayer,
+    /// Use the colour of the block this entity is part of. Entities which
+    /// aren't part of a block are drawn using the foreground colour (see
+    /// [`crate::components::StyleDefaults`]).
+    ByBlock,
+    /// A colour from the standard 256-colour palette used by AutoCAD (see
+    /// [`Colour::palette()`]).
+    Indexed(u8),
+    /// A specific colour.
+    Rgb(u8, u8, u8),
+}
+
+impl Default for Colour {
+    fn default() -> Colour { Colour::ByLayer }
+}
+
+impl Colour {
+    /// The palette index for the foreground colour (white on a dark
+    /// background, black on a light one).
+    pub const FOREGROUND: u8 = 7;
+
+    /// Look up an entry in the AutoCAD Colour Index.
+    ///
+    /// This returns [`None`] for [`Colour::FOREGROUND`] and `0` (which DXF
+    /// uses to mean "by block"), leaving the choice of foreground colour to
+    /// the caller.
+    ///
+    /// ```rust
+    /// # use arcs::components::Colour;
+    /// let red = Colour::palette(1).unwrap();
+    /// assert_eq!(red.as_rgba_u32(), 0xff0000ff);
+    ///
+    /// // the darker, desaturated variant of orange-red
+    /// let brown = Colour::palette(23).unwrap();
+    /// assert_eq!(brown.as_rgba_u32(), 0xa56752ff);
+    ///
+    /// assert!(Colour::palette(Colour::FOREGROUND).is_none());
+    /// ```
+    pub fn palette(index: u8) -> Option<Color> {
+        let (r, g, b) = match index {
+            0 | Colour::FOREGROUND => return None,
+            1 => (255, 0, 0),
+            2 => (255, 255, 0),
+            3 => (0, 255, 0),
+            4 => (0, 255, 255),
+            5 => (0, 0, 255),
+            6 => (255, 0, 255),
+            8 => (128, 128, 128),
+            9 => (192, 192, 192),
+            10..=249 => {
+                // 24 hues, each with 5 shades and a paler variant of each
+                let hue = f64::from(index / 10 - 1) * 15.0;
+                let shade = usize::from(index % 10 / 2);
+                let value = [1.0, 0.65, 0.5, 0.3, 0.15][shade];
+                let saturation = if index % 2 == 0 { 1.0 } else { 0.5 };
+                hsv_to_rgb(hue, saturation, value)
+            },
+            250..=255 => {
+                let grey =
+                    [51, 91, 132, 173, 214, 255][usize::from(index - 250)];
+                (grey, grey, grey)
+            },
+        };
+
+        Some(Color::rgb8(r, g, b))
+    }
+}
+
+fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> (u8, u8, u8) {
+    let chroma = value * saturation;
+    let h = hue / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    // truncating matches the published palette
+    let channel = |c: f64| ((c + m) * 255.0) as u8;
+
+    (channel(r), channel(g), channel(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(index: u8) -> u32 {
+        Colour::palette(index).unwrap().as_rgba_u32() >> 8
+    }
+
+    #[test]
+    fn shades_of_a_hue() {
+        let got: Vec<_> = (10..20).map(rgb).collect();
+
+        assert_eq!(
+            got,
+            vec![
+                0xff0000, 0xff7f7f, 0xa50000, 0xa55252, 0x7f0000, 0x7f3f3f,
+                0x4c0000, 0x4c2626, 0x260000, 0x261313,
+            ]
+        );
+    }
+
+    #[test]
+    fn greys_at_the_end_of_the_palette() {
+        assert_eq!(rgb(250), 0x333333);
+        assert_eq!(rgb(255), 0xffffff);
+        assert_eq!(rgb(90), 0x00ff00);
+        assert_eq!(rgb(170), 0x0000ff);
+    }
+}
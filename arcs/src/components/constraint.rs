@@ -0,0 +1,115 @@
+use crate::components::{EntityMap, MapEntities};
+use specs::prelude::*;
+
+#[allow(unused_imports)] // for rustdoc links
+use specs::prelude::Resource;
+
+/// A geometric relationship which the
+/// [`crate::systems::SolveConstraints`] system maintains between
+/// [`crate::components::DrawingObject`]s.
+///
+/// Constraints are attached to their own [`Entity`] (much like a dimension)
+/// and refer to the geometry they constrain. Lines, arcs, and points can be
+/// constrained.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    /// Two points are in the same place.
+    Coincident(ConstraintPoint, ConstraintPoint),
+    /// Two lines point in the same (or opposite) direction.
+    Parallel(Entity, Entity),
+    /// Two lines are at right angles.
+    Perpendicular(Entity, Entity),
+    /// A line touches an arc, or two arcs touch each other.
+    Tangent(Entity, Entity),
+    /// A line is parallel to the x axis.
+    Horizontal(Entity),
+    /// A line is parallel to the y axis.
+    Vertical(Entity),
+    /// Two lines have the same length, or two arcs have the same radius.
+    Equal(Entity, Entity),
+    /// The geometry can't be moved by the solver.
+    Fixed(Entity),
+}
+
+impl Constraint {
+    /// The entities this [`Constraint`] refers to.
+    pub fn entities(&self) -> Vec<Entity> {
+        match *self {
+            Constraint::Coincident(first, second) => {
+                vec![first.entity, second.entity]
+            },
+            Constraint::Parallel(a, b)
+            | Constraint::Perpendicular(a, b)
+            | Constraint::Tangent(a, b)
+            | Constraint::Equal(a, b) => vec![a, b],
+            Constraint::Horizontal(e)
+            | Constraint::Vertical(e)
+            | Constraint::Fixed(e) => vec![e],
+        }
+    }
+}
+
+impl Component for Constraint {
+    type Storage = FlaggedStorage<Self, DenseVecStorage<Self>>;
+}
+
+impl MapEntities for Constraint {
+    fn map_entities(&mut self, map: &EntityMap) {
+        match self {
+            Constraint::Coincident(first, second) => {
+                first.entity = map.get(first.entity);
+                second.entity = map.get(second.entity);
+            },
+            Constraint::Parallel(a, b)
+            | Constraint::Perpendicular(a, b)
+            | Constraint::Tangent(a, b)
+            | Constraint::Equal(a, b) => {
+                *a = map.get(*a);
+                *b = map.get(*b);
+            },
+            Constraint::Horizontal(e)
+            | Constraint::Vertical(e)
+            | Constraint::Fixed(e) => *e = map.get(*e),
+        }
+    }
+}
+
+/// A particular point on some constrained geometry.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ConstraintPoint {
+    pub entity: Entity,
+    pub handle: Handle,
+}
+
+impl ConstraintPoint {
+    pub const fn new(entity: Entity, handle: Handle) -> Self {
+        ConstraintPoint { entity, handle }
+    }
+}
+
+/// Which point on a piece of geometry a [`ConstraintPoint`] refers to.
+///
+/// Every handle on a [`crate::Point`] refers to the point itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Handle {
+    Start,
+    End,
+    /// The centre of an arc.
+    Centre,
+}
+
+/// A global [`Resource`] describing how the last attempt to satisfy the
+/// [`Constraint`]s went.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConstraintStatus {
+    /// Were all the constraints satisfied?
+    pub converged: bool,
+    /// How many iterations the solver needed.
+    pub iterations: usize,
+    /// How far from being satisfied the constraints were when the solver
+    /// stopped.
+    pub residual: f64,
+    /// Constraints which refer to missing or unsupported geometry, and were
+    /// ignored.
+    pub invalid: Vec<Entity>,
+}
@@ -0,0 +1,13 @@
+use specs::prelude::*;
+use specs_derive::Component;
+
+/// An empty [`Component`] marking helper geometry (e.g. construction lines
+/// used while laying out a drawing) which isn't part of the final output.
+///
+/// Construction geometry is drawn using the
+/// [`crate::components::StyleDefaults`] construction style, ignored when
+/// calculating the drawing's extents, and should be skipped by exporters
+/// unless the caller explicitly asks for it.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Component)]
+#[storage(NullStorage)]
+pub struct ConstructionGeometry;
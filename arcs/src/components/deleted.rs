@@ -0,0 +1,21 @@
+use specs::prelude::*;
+
+/// A tombstone marking an [`Entity`] which is about to be deleted.
+///
+/// Soft-deleting an entity means the change is visible to the
+/// [`crate::components::UndoLog`] and gives other systems (e.g.
+/// [`crate::systems::HierarchyBookkeeping`]) a chance to react while the
+/// entity's components are still around. The
+/// [`crate::systems::PurgeDeleted`] system physically deletes tombstoned
+/// entities once everyone else has run.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Deleted;
+
+impl Component for Deleted {
+    type Storage = FlaggedStorage<Self, NullStorage<Self>>;
+}
+
+/// Mark an [`Entity`] as [`Deleted`].
+pub fn soft_delete(world: &World, entity: Entity) {
+    let _ = world.write_storage::<Deleted>().insert(entity, Deleted);
+}
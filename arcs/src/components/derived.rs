@@ -0,0 +1,289 @@
+use crate::{
+    algorithms::Side,
+    components::{EntityMap, MapEntities},
+};
+use specs::{prelude::*, world::Index};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+#[allow(unused_imports)] // for rustdoc links
+use specs::prelude::Resource;
+
+/// Marks a [`crate::components::DrawingObject`] as an offset of another
+/// entity's geometry, so it is kept parallel to its source.
+///
+/// The [`crate::systems::RecomputeDerived`] system re-creates the offset
+/// whenever the source changes. If the source is deleted or can no longer be
+/// offset this component is removed and the geometry is left as-is.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AssociativeOffset {
+    pub source: Entity,
+    pub distance: f64,
+    pub side: Side,
+}
+
+impl AssociativeOffset {
+    pub const fn new(source: Entity, distance: f64, side: Side) -> Self {
+        AssociativeOffset {
+            source,
+            distance,
+            side,
+        }
+    }
+}
+
+impl Component for AssociativeOffset {
+    type Storage = FlaggedStorage<Self, DenseVecStorage<Self>>;
+}
+
+impl MapEntities for AssociativeOffset {
+    fn map_entities(&mut self, map: &EntityMap) {
+        self.source = map.get(self.source);
+    }
+}
+
+/// A global [`Resource`] recording which entities are derived from which,
+/// so they can be updated in the right order.
+///
+/// This is maintained by the [`crate::systems::RecomputeDerived`] system.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    /// The entities each derived entity depends on, keyed by
+    /// [`Entity::id()`] so deleted entities can be removed using
+    /// [`ComponentEvent::Removed`].
+    sources: HashMap<Index, (Entity, Vec<Entity>)>,
+    /// The entities derived from each source, also keyed by [`Entity::id()`].
+    dependents: HashMap<Index, (Entity, BTreeSet<Entity>)>,
+}
+
+impl DependencyGraph {
+    /// The entities `dependent` is derived from.
+    pub fn sources_of(&self, dependent: Entity) -> &[Entity] {
+        match self.sources.get(&dependent.id()) {
+            Some((ent, sources)) if *ent == dependent => sources,
+            _ => &[],
+        }
+    }
+
+    /// The entities derived directly from `source`.
+    pub fn dependents_of(
+        &self,
+        source: Entity,
+    ) -> impl Iterator<Item = Entity> + '_ {
+        self.dependents
+            .get(&source.id())
+            .filter(move |(ent, _)| *ent == source)
+            .into_iter()
+            .flat_map(|(_, dependents)| dependents.iter().copied())
+    }
+
+    /// Every entity which (directly or indirectly) depends on the `changed`
+    /// entities, ordered so each entity comes after everything it depends
+    /// on.
+    pub fn update_order<I>(
+        &self,
+        changed: I,
+    ) -> Result<Vec<Entity>, DependencyCycle>
+    where
+        I: IntoIterator<Item = Entity>,
+    {
+        // find everything affected by the change
+        let mut affected = BTreeSet::new();
+        let mut to_visit: Vec<Entity> = changed.into_iter().collect();
+
+        while let Some(ent) = to_visit.pop() {
+            for dependent in self.dependents_of(ent) {
+                if affected.insert(dependent) {
+                    to_visit.push(dependent);
+                }
+            }
+        }
+
+        // then do a topological sort (Kahn's algorithm) of those entities
+        let mut remaining: BTreeMap<Entity, usize> = affected
+            .iter()
+            .map(|&ent| {
+                let blocked_by = self
+                    .sources_of(ent)
+                    .iter()
+                    .filter(|source| affected.contains(source))
+                    .count();
+                (ent, blocked_by)
+            })
+            .collect();
+        let mut ready: Vec<Entity> = remaining
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&ent, _)| ent)
+            .collect();
+        let mut order = Vec::with_capacity(affected.len());
+
+        while let Some(ent) = ready.pop() {
+            remaining.remove(&ent);
+            order.push(ent);
+
+            for dependent in self.dependents_of(ent) {
+                if let Some(count) = remaining.get_mut(&dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.push(dependent);
+                    }
+                }
+            }
+        }
+
+        if remaining.is_empty() {
+            Ok(order)
+        } else {
+            Err(DependencyCycle(remaining.keys().copied().collect()))
+        }
+    }
+
+    /// How many derived entities there are.
+    pub fn len(&self) -> usize { self.sources.len() }
+
+    pub fn is_empty(&self) -> bool { self.sources.is_empty() }
+
+    /// Record the entities `dependent` is derived from, replacing any
+    /// previous sources.
+    pub(crate) fn set_sources(
+        &mut self,
+        dependent: Entity,
+        mut sources: Vec<Entity>,
+    ) {
+        self.remove_by_id(dependent.id());
+        // e.g. a hatch may have several loops associated with one entity
+        sources.sort();
+        sources.dedup();
+
+        if sources.is_empty() {
+            return;
+        }
+
+        for &source in &sources {
+            let entry = self
+                .dependents
+                .entry(source.id())
+                .or_insert_with(|| (source, BTreeSet::new()));
+
+            if entry.0 != source {
+                // the id was reused, so the old source must be dead
+                *entry = (source, BTreeSet::new());
+            }
+            entry.1.insert(dependent);
+        }
+        self.sources.insert(dependent.id(), (dependent, sources));
+    }
+
+    /// Forget that the entity with this [`Entity::id()`] was derived from
+    /// anything (e.g. because it was deleted).
+    pub(crate) fn remove_by_id(&mut self, id: Index) {
+        let (dependent, sources) = match self.sources.remove(&id) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        for source in sources {
+            if let Some((ent, dependents)) =
+                self.dependents.get_mut(&source.id())
+            {
+                if *ent != source {
+                    continue;
+                }
+
+                dependents.remove(&dependent);
+                if dependents.is_empty() {
+                    self.dependents.remove(&source.id());
+                }
+            }
+        }
+    }
+
+    /// The sources which were deleted, given the [`Entity::id()`]s of
+    /// entities which might have been.
+    pub(crate) fn dead_sources<I>(
+        &self,
+        removed: I,
+        entities: &Entities<'_>,
+    ) -> Vec<Entity>
+    where
+        I: IntoIterator<Item = Index>,
+    {
+        removed
+            .into_iter()
+            .filter_map(|id| self.dependents.get(&id))
+            .map(|&(source, _)| source)
+            .filter(|&source| !entities.is_alive(source))
+            .collect()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.sources.clear();
+        self.dependents.clear();
+    }
+}
+
+/// The entities which couldn't be updated because they (indirectly) depend
+/// on themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyCycle(pub Vec<Entity>);
+
+impl Display for DependencyCycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} entities depend on themselves", self.0.len())
+    }
+}
+
+impl Error for DependencyCycle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_are_updated_before_their_dependents() {
+        let mut world = World::new();
+        let line = world.create_entity().build();
+        let offset = world.create_entity().build();
+        let offset_of_offset = world.create_entity().build();
+        let hatch = world.create_entity().build();
+        let mut graph = DependencyGraph::default();
+        graph.set_sources(hatch, vec![offset_of_offset, offset]);
+        graph.set_sources(offset_of_offset, vec![offset]);
+        graph.set_sources(offset, vec![line]);
+
+        let got = graph.update_order(vec![line]).unwrap();
+
+        assert_eq!(got, vec![offset, offset_of_offset, hatch]);
+        assert_eq!(graph.update_order(vec![hatch]).unwrap(), Vec::new());
+
+        graph.set_sources(offset, vec![line, hatch]);
+        let err = graph.update_order(vec![line]).unwrap_err();
+        assert_eq!(err.0.len(), 3);
+    }
+
+    #[test]
+    fn only_deleted_sources_are_dead() {
+        let mut world = World::new();
+        let line = world.create_entity().build();
+        let arc = world.create_entity().build();
+        let offset = world.create_entity().build();
+        let mut graph = DependencyGraph::default();
+        graph.set_sources(offset, vec![line, arc]);
+        world.delete_entity(line).unwrap();
+        world.maintain();
+
+        let got = graph.dead_sources(
+            vec![line.id(), arc.id(), offset.id()],
+            &world.entities(),
+        );
+
+        assert_eq!(got, vec![line]);
+        graph.remove_by_id(offset.id());
+        assert!(graph.is_empty());
+        assert_eq!(graph.dependents_of(arc).count(), 0);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{BoundingBox, DrawingSpace};
+
+#[allow(unused_imports)] // for rustdoc links
+use specs::prelude::Resource;
+
+/// A global [`Resource`] containing the parts of the drawing which have
+/// changed since a renderer last redrew them.
+///
+/// The [`crate::systems::TrackDirtyRegions`] system adds both the old and
+/// new [`BoundingBox`] of every entity which changes. Overlapping regions
+/// are merged, so a renderer can [`DirtyRegions::take()`] the regions each
+/// frame and only redraw those parts of the screen (use
+/// [`crate::window::transform_to_canvas_space()`] to convert them to
+/// pixels).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirtyRegions {
+    regions: Vec<BoundingBox<DrawingSpace>>,
+}
+
+impl DirtyRegions {
+    /// Mark part of the drawing as needing to be redrawn.
+    pub fn add(&mut self, region: BoundingBox<DrawingSpace>) {
+        let mut region = region;
+
+        // merging two regions may make the result overlap with a region we
+        // already checked, so keep going until nothing overlaps
+        while let Some(ix) = self
+            .regions
+            .iter()
+            .position(|existing| existing.intersects_with(region))
+        {
+            let existing = self.regions.swap_remove(ix);
+            region = BoundingBox::merge(existing, region);
+        }
+
+        self.regions.push(region);
+    }
+
+    /// The regions which need to be redrawn. No two regions overlap.
+    pub fn regions(&self) -> &[BoundingBox<DrawingSpace>] { &self.regions }
+
+    /// A single [`BoundingBox`] containing every dirty region.
+    pub fn extents(&self) -> Option<BoundingBox<DrawingSpace>> {
+        BoundingBox::around(self.regions.iter().copied())
+    }
+
+    /// Remove all the dirty regions, typically after they've been redrawn.
+    pub fn take(&mut self) -> Vec<BoundingBox<DrawingSpace>> {
+        std::mem::take(&mut self.regions)
+    }
+
+    pub fn clear(&mut self) { self.regions.clear(); }
+
+    pub fn len(&self) -> usize { self.regions.len() }
+
+    pub fn is_empty(&self) -> bool { self.regions.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    fn square(x: f64, y: f64) -> BoundingBox<DrawingSpace> {
+        BoundingBox::new(Point::new(x, y), Point::new(x + 1.0, y + 1.0))
+    }
+
+    #[test]
+    fn overlapping_regions_are_merged() {
+        let mut dirty = DirtyRegions::default();
+
+        dirty.add(square(0.0, 0.0));
+        dirty.add(square(5.0, 5.0));
+        assert_eq!(dirty.len(), 2);
+
+        // bridges the gap between the first two
+        dirty.add(BoundingBox::new(Point::new(0.5, 0.5), Point::new(5.5, 5.5)));
+
+        assert_eq!(
+            dirty.regions(),
+            &[BoundingBox::new(Point::zero(), Point::new(6.0, 6.0))]
+        );
+        assert_eq!(dirty.take().len(), 1);
+        assert!(dirty.is_empty());
+    }
+}
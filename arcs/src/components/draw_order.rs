@@ -0,0 +1,81 @@
+use crate::components::DrawingObject;
+use specs::prelude::*;
+use specs_derive::Component;
+
+/// Controls the order entities on the same [`crate::components::Layer`] are
+/// drawn in, with higher values drawn on top of lower ones.
+///
+/// Entities without a [`DrawOrder`] use `DrawOrder(0)`, and entities with
+/// equal values are drawn in the order they were created. This makes it
+/// possible to draw a hatch underneath its outline, for example.
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Component,
+)]
+#[storage(DenseVecStorage)]
+pub struct DrawOrder(pub i32);
+
+/// Draw an entity on top of every other [`DrawingObject`].
+pub fn bring_to_front(world: &World, entity: Entity) {
+    let mut orders = world.write_storage::<DrawOrder>();
+    let top = (
+        &world.entities(),
+        &world.read_storage::<DrawingObject>(),
+        orders.maybe(),
+    )
+        .join()
+        .filter(|(ent, _, _)| *ent != entity)
+        .map(|(_, _, order)| order.copied().unwrap_or_default().0)
+        .max()
+        .unwrap_or(0);
+
+    let _ = orders.insert(entity, DrawOrder(top.saturating_add(1)));
+}
+
+/// Draw an entity underneath every other [`DrawingObject`].
+pub fn send_to_back(world: &World, entity: Entity) {
+    let mut orders = world.write_storage::<DrawOrder>();
+    let bottom = (
+        &world.entities(),
+        &world.read_storage::<DrawingObject>(),
+        orders.maybe(),
+    )
+        .join()
+        .filter(|(ent, _, _)| *ent != entity)
+        .map(|(_, _, order)| order.copied().unwrap_or_default().0)
+        .min()
+        .unwrap_or(0);
+
+    let _ = orders.insert(entity, DrawOrder(bottom.saturating_sub(1)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_entities_to_the_front_and_back() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let object = || DrawingObject {
+            geometry: crate::components::Geometry::Point(crate::Point::zero()),
+            layer,
+        };
+        let outline = world
+            .create_entity()
+            .with(object())
+            .with(DrawOrder(3))
+            .build();
+        let hatch = world.create_entity().with(object()).build();
+        let label = world.create_entity().with(object()).build();
+
+        send_to_back(&world, hatch);
+        bring_to_front(&world, label);
+        bring_to_front(&world, label);
+
+        let orders = world.read_storage::<DrawOrder>();
+        assert_eq!(orders.get(hatch), Some(&DrawOrder(-1)));
+        assert_eq!(orders.get(outline), Some(&DrawOrder(3)));
+        assert_eq!(orders.get(label), Some(&DrawOrder(4)));
+    }
+}
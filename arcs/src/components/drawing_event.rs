@@ -0,0 +1,48 @@
+use specs::Entity;
+
+#[allow(unused_imports)] // for rustdoc links
+use specs::shrev::EventChannel;
+
+/// A high-level notification that something in the drawing has changed.
+///
+/// The bookkeeping systems publish these to an
+/// [`EventChannel<DrawingEvent>`] resource, so UIs and plugins can subscribe
+/// to a single channel instead of registering readers on each storage.
+///
+/// ```rust
+/// # use arcs::components::DrawingEvent;
+/// # use specs::{prelude::*, shrev::EventChannel};
+/// let mut world = World::new();
+/// arcs::components::register(&mut world);
+/// let mut dispatcher = arcs::systems::default_dispatcher(&mut world);
+/// let mut reader = world
+///     .fetch_mut::<EventChannel<DrawingEvent>>()
+///     .register_reader();
+///
+/// let layer = arcs::components::get_or_create_layer(&world, "0");
+/// dispatcher.dispatch(&world);
+///
+/// let events = world.fetch::<EventChannel<DrawingEvent>>();
+/// let got: Vec<_> = events.read(&mut reader).cloned().collect();
+/// assert_eq!(got, vec![DrawingEvent::LayerChanged(layer)]);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DrawingEvent {
+    /// A [`crate::components::DrawingObject`] was added.
+    EntityCreated(Entity),
+    /// A [`crate::components::DrawingObject`] was removed (typically because
+    /// its entity was deleted).
+    EntityDeleted(Entity),
+    /// A [`crate::components::DrawingObject`] was modified.
+    GeometryChanged(Entity),
+    /// A [`crate::components::DrawingObject`] was moved to another layer.
+    ///
+    /// This is published in addition to [`DrawingEvent::GeometryChanged`].
+    MovedToLayer { entity: Entity, layer: Entity },
+    /// A [`crate::components::Layer`] was added, removed, renamed, or had
+    /// its properties changed.
+    LayerChanged(Entity),
+    /// Something was added to or removed from the
+    /// [`crate::components::SelectionSet`].
+    SelectionChanged,
+}
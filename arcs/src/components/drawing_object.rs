@@ -1,12 +1,28 @@
 use crate::{
-    algorithms::{Bounded, Closest, ClosestPoint, Translate},
-    Arc, BoundingBox, DrawingSpace, Line, Point, Vector,
+    algorithms::{
+        array, distance, extend_arc, extend_line, mirror, scale_about,
+        AffineTransform, Approximate, Bounded, Closest, ClosestLocation,
+        ClosestParameter, ClosestPoint, Endpoint, ExtendError, Intersect,
+        IntersectionSet, Length, Offset, Project, Projection, Rotate,
+        Separation, Side, Tessellate, TransformError, Transformable, Translate,
+        Trim,
+    },
+    components::{
+        AngularDimension, DiameterDimension, Group, GroupMember, Hatch,
+        ImageRef, Insert, Leader, LinearDimension, MText, Name, NameGenerator,
+        NameTable, RadialDimension, Selected, Text,
+    },
+    Angle, Arc, BoundingBox, CubicBezier, DrawingSpace, Ellipse, EllipticalArc,
+    Line, Point, Polyline, QuadraticBezier, Ray, Region, Spline, Tolerance,
+    Vector, XLine,
 };
+use arcs_core::primitives::Segment;
 use specs::prelude::*;
 
-// for rustdoc links
-#[allow(unused_imports)]
-use crate::components::Layer;
+use crate::components::{
+    ensure_unlocked, is_locked, EditError, EntityMap, Layer, Locked,
+    MapEntities,
+};
 
 /// Something which can be drawn on the screen.
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +36,44 @@ impl Component for DrawingObject {
     type Storage = FlaggedStorage<Self, DenseVecStorage<Self>>;
 }
 
+impl MapEntities for DrawingObject {
+    fn map_entities(&mut self, map: &EntityMap) {
+        self.layer = map.get(self.layer);
+
+        match self.geometry {
+            Geometry::Hatch(ref mut hatch) => {
+                for hatch_loop in &mut hatch.loops {
+                    hatch_loop.associated_with =
+                        hatch_loop.associated_with.map(|e| map.get(e));
+                }
+            },
+            Geometry::RadialDimension(ref mut dim) => {
+                dim.associated_with = dim.associated_with.map(|e| map.get(e));
+            },
+            Geometry::DiameterDimension(ref mut dim) => {
+                dim.associated_with = dim.associated_with.map(|e| map.get(e));
+            },
+            Geometry::LinearDimension(ref mut dim) => {
+                if let Some((from, to)) = &mut dim.associated_with {
+                    from.entity = map.get(from.entity);
+                    to.entity = map.get(to.entity);
+                }
+            },
+            Geometry::AngularDimension(ref mut dim) => {
+                dim.associated_with = dim
+                    .associated_with
+                    .map(|(first, second)| (map.get(first), map.get(second)));
+            },
+            Geometry::Leader(ref mut leader) => {
+                if let Some(anchor) = &mut leader.associated_with {
+                    anchor.entity = map.get(anchor.entity);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
 /// The geometry of a [`DrawingObject`].
 #[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
@@ -27,6 +81,25 @@ pub enum Geometry {
     Line(Line),
     Arc(Arc),
     Point(Point),
+    Ellipse(Ellipse),
+    EllipticalArc(EllipticalArc),
+    Polyline(Polyline),
+    CubicBezier(CubicBezier),
+    QuadraticBezier(QuadraticBezier),
+    Spline(Spline),
+    Text(Text),
+    MText(MText),
+    Hatch(Hatch),
+    LinearDimension(LinearDimension),
+    AngularDimension(AngularDimension),
+    RadialDimension(RadialDimension),
+    DiameterDimension(DiameterDimension),
+    Leader(Leader),
+    XLine(XLine),
+    Ray(Ray),
+    ImageRef(ImageRef),
+    Region(Region),
+    Insert(Insert),
 }
 
 impl ClosestPoint<DrawingSpace> for Geometry {
@@ -35,6 +108,83 @@ impl ClosestPoint<DrawingSpace> for Geometry {
             Geometry::Point(p) => p.closest_point(target),
             Geometry::Line(l) => l.closest_point(target),
             Geometry::Arc(a) => a.closest_point(target),
+            Geometry::Ellipse(e) => e.closest_point(target),
+            Geometry::EllipticalArc(e) => e.closest_point(target),
+            Geometry::Polyline(p) => p.closest_point(target),
+            Geometry::CubicBezier(c) => c.closest_point(target),
+            Geometry::QuadraticBezier(q) => q.closest_point(target),
+            Geometry::Spline(s) => s.closest_point(target),
+            Geometry::Text(t) => t.closest_point(target),
+            Geometry::MText(t) => t.closest_point(target),
+            Geometry::Hatch(h) => h.closest_point(target),
+            Geometry::LinearDimension(d) => d.closest_point(target),
+            Geometry::AngularDimension(dimension) => {
+                dimension.closest_point(target)
+            },
+            Geometry::RadialDimension(dimension) => {
+                dimension.closest_point(target)
+            },
+            Geometry::DiameterDimension(dimension) => {
+                dimension.closest_point(target)
+            },
+            Geometry::Leader(leader) => leader.closest_point(target),
+            Geometry::XLine(xline) => xline.closest_point(target),
+            Geometry::Ray(ray) => ray.closest_point(target),
+            Geometry::ImageRef(image) => image.closest_point(target),
+            Geometry::Region(region) => region.closest_point(target),
+            Geometry::Insert(insert) => insert.closest_point(target),
+        }
+    }
+}
+
+impl ClosestParameter<DrawingSpace> for Geometry {
+    /// Only curves have a parameter, so everything else (e.g. text,
+    /// annotations and regions) just uses `0`.
+    fn parameter_of(&self, point: Point) -> f64 {
+        match self {
+            Geometry::Point(p) => p.parameter_of(point),
+            Geometry::Line(line) => line.parameter_of(point),
+            Geometry::Arc(arc) => arc.parameter_of(point),
+            Geometry::Ellipse(ellipse) => ellipse.parameter_of(point),
+            Geometry::EllipticalArc(arc) => arc.parameter_of(point),
+            Geometry::Polyline(polyline) => polyline.parameter_of(point),
+            Geometry::CubicBezier(c) => c.parameter_of(point),
+            Geometry::QuadraticBezier(q) => q.parameter_of(point),
+            Geometry::Spline(s) => s.parameter_of(point),
+            Geometry::XLine(xline) => xline.parameter_of(point),
+            Geometry::Ray(ray) => ray.parameter_of(point),
+            Geometry::Text(_)
+            | Geometry::MText(_)
+            | Geometry::Hatch(_)
+            | Geometry::LinearDimension(_)
+            | Geometry::AngularDimension(_)
+            | Geometry::RadialDimension(_)
+            | Geometry::DiameterDimension(_)
+            | Geometry::Leader(_)
+            | Geometry::ImageRef(_)
+            | Geometry::Region(_)
+            | Geometry::Insert(_) => 0.0,
+        }
+    }
+
+    fn closest_location(
+        &self,
+        target: Point,
+    ) -> Option<ClosestLocation<DrawingSpace>> {
+        // some curves can find the parameter directly
+        match self {
+            Geometry::CubicBezier(c) => c.closest_location(target),
+            Geometry::QuadraticBezier(q) => q.closest_location(target),
+            Geometry::Spline(s) => s.closest_location(target),
+            _ => {
+                let point = *self.closest_point(target).points().first()?;
+
+                Some(ClosestLocation {
+                    point,
+                    parameter: self.parameter_of(point),
+                    distance: (point - target).length(),
+                })
+            },
         }
     }
 }
@@ -45,12 +195,146 @@ impl ClosestPoint<DrawingSpace> for DrawingObject {
     }
 }
 
+impl ClosestParameter<DrawingSpace> for DrawingObject {
+    fn parameter_of(&self, point: Point) -> f64 {
+        self.geometry.parameter_of(point)
+    }
+
+    fn closest_location(
+        &self,
+        target: Point,
+    ) -> Option<ClosestLocation<DrawingSpace>> {
+        self.geometry.closest_location(target)
+    }
+}
+
 impl Bounded<DrawingSpace> for Geometry {
     fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
         match self {
             Geometry::Line(line) => line.bounding_box(),
             Geometry::Arc(arc) => arc.bounding_box(),
             Geometry::Point(point) => point.bounding_box(),
+            Geometry::Ellipse(ellipse) => ellipse.bounding_box(),
+            Geometry::EllipticalArc(arc) => arc.bounding_box(),
+            Geometry::Polyline(polyline) => polyline.bounding_box(),
+            Geometry::CubicBezier(curve) => curve.bounding_box(),
+            Geometry::QuadraticBezier(curve) => curve.bounding_box(),
+            Geometry::Spline(spline) => spline.bounding_box(),
+            Geometry::Text(text) => text.bounding_box(),
+            Geometry::MText(text) => text.bounding_box(),
+            Geometry::Hatch(hatch) => hatch.bounding_box(),
+            Geometry::LinearDimension(d) => d.bounding_box(),
+            Geometry::AngularDimension(dimension) => dimension.bounding_box(),
+            Geometry::RadialDimension(dimension) => dimension.bounding_box(),
+            Geometry::DiameterDimension(dimension) => dimension.bounding_box(),
+            Geometry::Leader(leader) => leader.bounding_box(),
+            Geometry::XLine(xline) => xline.bounding_box(),
+            Geometry::Ray(ray) => ray.bounding_box(),
+            Geometry::ImageRef(image) => image.bounding_box(),
+            Geometry::Region(region) => region.bounding_box(),
+            Geometry::Insert(insert) => insert.bounding_box(),
+        }
+    }
+}
+
+impl Length for Geometry {
+    /// The length of the curve, or the perimeter of a [`Region`].
+    ///
+    /// Text, annotations, hatches, images and inserts aren't curves so they
+    /// have no length, while [`XLine`]s and [`Ray`]s are infinitely long.
+    fn length(&self) -> f64 {
+        match self {
+            Geometry::Point(_) => 0.0,
+            Geometry::Line(line) => line.length(),
+            Geometry::Arc(arc) => arc.length(),
+            Geometry::Ellipse(ellipse) => ellipse.length(),
+            Geometry::EllipticalArc(arc) => arc.length(),
+            Geometry::Polyline(polyline) => polyline.length(),
+            Geometry::CubicBezier(curve) => curve.length(),
+            Geometry::QuadraticBezier(curve) => curve.length(),
+            Geometry::Spline(spline) => spline.length(),
+            Geometry::Region(region) => {
+                region.loops().map(Length::length).sum()
+            },
+            Geometry::XLine(_) | Geometry::Ray(_) => std::f64::INFINITY,
+            Geometry::Text(_)
+            | Geometry::MText(_)
+            | Geometry::Hatch(_)
+            | Geometry::LinearDimension(_)
+            | Geometry::AngularDimension(_)
+            | Geometry::RadialDimension(_)
+            | Geometry::DiameterDimension(_)
+            | Geometry::Leader(_)
+            | Geometry::ImageRef(_)
+            | Geometry::Insert(_) => 0.0,
+        }
+    }
+}
+
+impl Project<DrawingSpace> for Geometry {
+    /// Project onto the curve, or the outer boundary of a [`Region`].
+    ///
+    /// Points, text, annotations, hatches, images and inserts don't have
+    /// anything to project onto so they always give [`None`].
+    fn project(&self, point: Point) -> Option<Projection<DrawingSpace>> {
+        match self {
+            Geometry::Line(line) => line.project(point),
+            Geometry::Arc(arc) => arc.project(point),
+            Geometry::Ellipse(ellipse) => ellipse.project(point),
+            Geometry::EllipticalArc(arc) => arc.project(point),
+            Geometry::Polyline(polyline) => polyline.project(point),
+            Geometry::CubicBezier(curve) => curve.project(point),
+            Geometry::QuadraticBezier(curve) => curve.project(point),
+            Geometry::Spline(spline) => spline.project(point),
+            Geometry::XLine(xline) => xline.project(point),
+            Geometry::Ray(ray) => ray.project(point),
+            Geometry::Region(region) => region.outer().project(point),
+            Geometry::Point(_)
+            | Geometry::Text(_)
+            | Geometry::MText(_)
+            | Geometry::Hatch(_)
+            | Geometry::LinearDimension(_)
+            | Geometry::AngularDimension(_)
+            | Geometry::RadialDimension(_)
+            | Geometry::DiameterDimension(_)
+            | Geometry::Leader(_)
+            | Geometry::ImageRef(_)
+            | Geometry::Insert(_) => None,
+        }
+    }
+}
+
+impl Tessellate<DrawingSpace> for Geometry {
+    /// Tessellate the curve, or the outer boundary of a [`Region`].
+    ///
+    /// A [`Point`] becomes a [`Polyline`] with a single vertex, while
+    /// anything which isn't a finite curve (text, annotations, hatches,
+    /// images, inserts, [`XLine`]s and [`Ray`]s) gives an empty
+    /// [`Polyline`].
+    fn tessellate(&self, tolerance: f64) -> Polyline {
+        match self {
+            Geometry::Point(point) => Polyline::from_points(vec![*point]),
+            Geometry::Line(line) => line.tessellate(tolerance),
+            Geometry::Arc(arc) => arc.tessellate(tolerance),
+            Geometry::Ellipse(ellipse) => ellipse.tessellate(tolerance),
+            Geometry::EllipticalArc(arc) => arc.tessellate(tolerance),
+            Geometry::Polyline(polyline) => polyline.tessellate(tolerance),
+            Geometry::CubicBezier(curve) => curve.tessellate(tolerance),
+            Geometry::QuadraticBezier(curve) => curve.tessellate(tolerance),
+            Geometry::Spline(spline) => spline.tessellate(tolerance),
+            Geometry::Region(region) => region.outer().tessellate(tolerance),
+            Geometry::Text(_)
+            | Geometry::MText(_)
+            | Geometry::Hatch(_)
+            | Geometry::LinearDimension(_)
+            | Geometry::AngularDimension(_)
+            | Geometry::RadialDimension(_)
+            | Geometry::DiameterDimension(_)
+            | Geometry::Leader(_)
+            | Geometry::XLine(_)
+            | Geometry::Ray(_)
+            | Geometry::ImageRef(_)
+            | Geometry::Insert(_) => Polyline::new(Vec::new(), false),
         }
     }
 }
@@ -61,12 +345,1396 @@ impl Translate<DrawingSpace> for Geometry {
             Geometry::Point(ref mut point) => point.translate(displacement),
             Geometry::Line(ref mut line) => line.translate(displacement),
             Geometry::Arc(ref mut arc) => arc.translate(displacement),
+            Geometry::Ellipse(ref mut ellipse) => {
+                ellipse.translate(displacement)
+            },
+            Geometry::EllipticalArc(ref mut arc) => arc.translate(displacement),
+            Geometry::Polyline(ref mut polyline) => {
+                polyline.translate(displacement)
+            },
+            Geometry::CubicBezier(ref mut curve) => {
+                curve.translate(displacement)
+            },
+            Geometry::QuadraticBezier(ref mut curve) => {
+                curve.translate(displacement)
+            },
+            Geometry::Spline(ref mut spline) => spline.translate(displacement),
+            Geometry::Text(ref mut text) => text.translate(displacement),
+            Geometry::MText(ref mut text) => text.translate(displacement),
+            Geometry::Hatch(ref mut hatch) => hatch.translate(displacement),
+            Geometry::LinearDimension(ref mut d) => d.translate(displacement),
+            Geometry::AngularDimension(ref mut dimension) => {
+                dimension.translate(displacement)
+            },
+            Geometry::RadialDimension(ref mut dimension) => {
+                dimension.translate(displacement)
+            },
+            Geometry::DiameterDimension(ref mut dimension) => {
+                dimension.translate(displacement)
+            },
+            Geometry::Leader(ref mut leader) => leader.translate(displacement),
+            Geometry::XLine(ref mut xline) => xline.translate(displacement),
+            Geometry::Ray(ref mut ray) => ray.translate(displacement),
+            Geometry::ImageRef(ref mut image) => image.translate(displacement),
+            Geometry::Region(ref mut region) => region.translate(displacement),
+            Geometry::Insert(ref mut insert) => insert.translate(displacement),
+        }
+    }
+}
+
+impl Transformable<DrawingSpace> for Geometry {
+    /// Apply a transform to any [`Geometry`].
+    ///
+    /// An [`Arc`] which is squashed or sheared becomes an
+    /// [`EllipticalArc`], while everything else keeps its type (returning a
+    /// [`TransformError`] if that isn't possible).
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        match self {
+            Geometry::Point(point) => point.transform_by(transform),
+            Geometry::Line(line) => line.transform_by(transform),
+            Geometry::Arc(arc) => match arc.transform_by(transform) {
+                Err(TransformError::NonUniform) => {
+                    let elliptical =
+                        EllipticalArc::from(*arc).transformed_by(transform)?;
+                    *self = Geometry::EllipticalArc(elliptical);
+                    Ok(())
+                },
+                other => other,
+            },
+            Geometry::Ellipse(ellipse) => ellipse.transform_by(transform),
+            Geometry::EllipticalArc(arc) => arc.transform_by(transform),
+            Geometry::Polyline(polyline) => polyline.transform_by(transform),
+            Geometry::CubicBezier(curve) => curve.transform_by(transform),
+            Geometry::QuadraticBezier(curve) => curve.transform_by(transform),
+            Geometry::Spline(spline) => spline.transform_by(transform),
+            Geometry::Text(text) => text.transform_by(transform),
+            Geometry::MText(text) => text.transform_by(transform),
+            Geometry::Hatch(hatch) => hatch.transform_by(transform),
+            Geometry::LinearDimension(d) => d.transform_by(transform),
+            Geometry::AngularDimension(d) => d.transform_by(transform),
+            Geometry::RadialDimension(d) => d.transform_by(transform),
+            Geometry::DiameterDimension(d) => d.transform_by(transform),
+            Geometry::Leader(leader) => leader.transform_by(transform),
+            Geometry::XLine(xline) => xline.transform_by(transform),
+            Geometry::Ray(ray) => ray.transform_by(transform),
+            Geometry::ImageRef(image) => image.transform_by(transform),
+            Geometry::Region(region) => region.transform_by(transform),
+            Geometry::Insert(insert) => insert.transform_by(transform),
         }
     }
 }
 
+/// How accurately curves without an exact intersection algorithm (e.g.
+/// ellipses and splines) are approximated when intersecting them.
+const INTERSECTION_TOLERANCE: f64 = 1e-3;
+
+impl Geometry {
+    /// A human-friendly name for this kind of [`Geometry`] (e.g. `"Line"`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Geometry::Line(_) => "Line",
+            Geometry::Arc(_) => "Arc",
+            Geometry::Point(_) => "Point",
+            Geometry::Ellipse(_) => "Ellipse",
+            Geometry::EllipticalArc(_) => "EllipticalArc",
+            Geometry::Polyline(_) => "Polyline",
+            Geometry::CubicBezier(_) => "CubicBezier",
+            Geometry::QuadraticBezier(_) => "QuadraticBezier",
+            Geometry::Spline(_) => "Spline",
+            Geometry::Text(_) => "Text",
+            Geometry::MText(_) => "MText",
+            Geometry::Hatch(_) => "Hatch",
+            Geometry::LinearDimension(_) => "LinearDimension",
+            Geometry::AngularDimension(_) => "AngularDimension",
+            Geometry::RadialDimension(_) => "RadialDimension",
+            Geometry::DiameterDimension(_) => "DiameterDimension",
+            Geometry::Leader(_) => "Leader",
+            Geometry::XLine(_) => "XLine",
+            Geometry::Ray(_) => "Ray",
+            Geometry::ImageRef(_) => "ImageRef",
+            Geometry::Region(_) => "Region",
+            Geometry::Insert(_) => "Insert",
+        }
+    }
+
+    /// The other entities this [`Geometry`] is associated with (e.g. a
+    /// hatch's boundaries or the arc a radial dimension measures).
+    pub fn associations(&self) -> Vec<Entity> {
+        match self {
+            Geometry::Hatch(hatch) => hatch
+                .loops
+                .iter()
+                .filter_map(|hatch_loop| hatch_loop.associated_with)
+                .collect(),
+            Geometry::RadialDimension(dim) => {
+                dim.associated_with.into_iter().collect()
+            },
+            Geometry::DiameterDimension(dim) => {
+                dim.associated_with.into_iter().collect()
+            },
+            Geometry::LinearDimension(dim) => dim
+                .associated_with
+                .iter()
+                .flat_map(|(from, to)| vec![from.entity, to.entity])
+                .collect(),
+            Geometry::AngularDimension(dim) => dim
+                .associated_with
+                .iter()
+                .flat_map(|&(first, second)| vec![first, second])
+                .collect(),
+            Geometry::Leader(leader) => leader
+                .associated_with
+                .map(|a| a.entity)
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Geometry {
+    /// Break this [`Geometry`] into the [`Line`]s and [`Arc`]s used for
+    /// intersection tests, clipping any infinite lines to `bounds`.
+    fn intersection_segments(
+        &self,
+        bounds: BoundingBox<DrawingSpace>,
+    ) -> Vec<Segment<DrawingSpace>> {
+        fn approximated<A>(curve: &A) -> Vec<Segment<DrawingSpace>>
+        where
+            A: Approximate<DrawingSpace>,
+        {
+            let points: Vec<Point> =
+                curve.approximate(INTERSECTION_TOLERANCE).collect();
+
+            points
+                .windows(2)
+                .map(|pair| Segment::Line(Line::new(pair[0], pair[1])))
+                .collect()
+        }
+
+        fn annotation(
+            graphics: crate::components::AnnotationGraphics,
+        ) -> Vec<Segment<DrawingSpace>> {
+            graphics
+                .lines
+                .into_iter()
+                .map(Segment::Line)
+                .chain(graphics.arcs.into_iter().map(Segment::Arc))
+                .collect()
+        }
+
+        match self {
+            Geometry::Point(point) => {
+                vec![Segment::Line(Line::new(*point, *point))]
+            },
+            Geometry::Line(line) => vec![Segment::Line(*line)],
+            Geometry::Arc(arc) => vec![Segment::Arc(*arc)],
+            Geometry::Ellipse(ellipse) => approximated(ellipse),
+            Geometry::EllipticalArc(arc) => approximated(arc),
+            Geometry::Polyline(polyline) => polyline.segments().collect(),
+            Geometry::CubicBezier(curve) => approximated(curve),
+            Geometry::QuadraticBezier(curve) => approximated(curve),
+            Geometry::Spline(spline) => approximated(spline),
+            // text doesn't have any linework to intersect with
+            Geometry::Text(_) | Geometry::MText(_) => Vec::new(),
+            Geometry::Hatch(hatch) => hatch
+                .loops
+                .iter()
+                .flat_map(|l| l.boundary.segments())
+                .collect(),
+            Geometry::LinearDimension(d) => annotation(d.graphics()),
+            Geometry::AngularDimension(d) => annotation(d.graphics()),
+            Geometry::RadialDimension(d) => annotation(d.graphics()),
+            Geometry::DiameterDimension(d) => annotation(d.graphics()),
+            Geometry::Leader(leader) => annotation(leader.graphics()),
+            Geometry::XLine(xline) => xline
+                .clip_to(bounds)
+                .map(Segment::Line)
+                .into_iter()
+                .collect(),
+            Geometry::Ray(ray) => {
+                ray.clip_to(bounds).map(Segment::Line).into_iter().collect()
+            },
+            Geometry::ImageRef(image) => image
+                .outline()
+                .edges()
+                .iter()
+                .copied()
+                .map(Segment::Line)
+                .collect(),
+            Geometry::Region(region) => {
+                region.loops().flat_map(Polyline::segments).collect()
+            },
+            // a block's contents aren't known without the BlockTable
+            Geometry::Insert(_) => Vec::new(),
+        }
+    }
+}
+
+impl Geometry {
+    /// Points on this [`Geometry`] which can be used to find the convex hull
+    /// around it (see [`crate::algorithms::convex_hull()`]).
+    ///
+    /// This is the endpoints of each line, with curves sampled along their
+    /// length. Text uses the corners of its bounding box, while infinite
+    /// lines can't be surrounded by a hull and have no points.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arcs::{
+    ///     algorithms::convex_hull, components::Geometry, Line, Point,
+    /// };
+    ///
+    /// let objects = vec![
+    ///     Geometry::Point(Point::new(0.0, 5.0)),
+    ///     Geometry::Line(Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0))),
+    /// ];
+    ///
+    /// let hull = convex_hull(objects.iter().flat_map(Geometry::hull_points));
+    ///
+    /// assert_eq!(hull.area(), 10.0);
+    /// ```
+    pub fn hull_points(&self) -> Vec<Point> {
+        match self {
+            Geometry::XLine(_) | Geometry::Ray(_) => Vec::new(),
+            Geometry::Text(_) | Geometry::MText(_) => {
+                let bounds = self.bounding_box();
+                vec![
+                    bounds.bottom_left(),
+                    bounds.bottom_right(),
+                    bounds.top_right(),
+                    bounds.top_left(),
+                ]
+            },
+            _ => self
+                .intersection_segments(self.bounding_box())
+                .into_iter()
+                .flat_map(|segment| match segment {
+                    Segment::Line(line) => vec![line.start, line.end],
+                    Segment::Arc(arc) => {
+                        arc.approximate(INTERSECTION_TOLERANCE).collect()
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Geometry {
+    /// Remove the part of this [`Geometry`] which was picked, cutting it
+    /// back to wherever it crosses the `cutting_edges`.
+    ///
+    /// Only lines, arcs and polylines can be trimmed. This returns the
+    /// leftover pieces, or [`None`] when there wasn't anything to trim (see
+    /// [`Trim::trim()`]).
+    pub fn trim(
+        &self,
+        cutting_edges: &[Geometry],
+        pick: Point,
+    ) -> Option<Vec<Geometry>> {
+        let cutting_points: Vec<Point> = cutting_edges
+            .iter()
+            .flat_map(|edge| self.intersect(edge))
+            .collect();
+
+        match self {
+            Geometry::Line(line) => line
+                .trim(&cutting_points, pick)
+                .map(|pieces| pieces.into_iter().map(Geometry::Line).collect()),
+            Geometry::Arc(arc) => arc
+                .trim(&cutting_points, pick)
+                .map(|pieces| pieces.into_iter().map(Geometry::Arc).collect()),
+            Geometry::Polyline(polyline) => {
+                polyline.trim(&cutting_points, pick).map(|pieces| {
+                    pieces.into_iter().map(Geometry::Polyline).collect()
+                })
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Geometry {
+    /// Lengthen a line or arc until it reaches the nearest of the
+    /// `boundaries`, extending whichever end is closest to `pick`.
+    pub fn extend(
+        &self,
+        boundaries: &[Geometry],
+        pick: Point,
+    ) -> Result<Geometry, ExtendError> {
+        let nearest_end = |start: Point, end: Point| {
+            if (pick - start).length() < (pick - end).length() {
+                Endpoint::Start
+            } else {
+                Endpoint::End
+            }
+        };
+
+        // the extended line or arc can only ever reach points on its own
+        // infinite line or circle
+        let mut bounds = match self {
+            Geometry::Arc(arc) => {
+                let radius = Vector::new(arc.radius(), arc.radius());
+                BoundingBox::new_unchecked(
+                    arc.centre() - radius,
+                    arc.centre() + radius,
+                )
+            },
+            _ => self.bounding_box(),
+        };
+
+        // infinite boundaries get clipped, so make sure the clipped lines
+        // still reach wherever a line would cross them
+        for boundary in boundaries {
+            let crossing = match (self, boundary) {
+                (Geometry::Line(line), Geometry::XLine(xline)) => {
+                    XLine::new(line.start, line.displacement())
+                        .intersect_xline(*xline)
+                },
+                (Geometry::Line(line), Geometry::Ray(ray)) => {
+                    XLine::new(line.start, line.displacement())
+                        .intersect_xline(ray.to_xline())
+                },
+                _ => None,
+            };
+
+            if let Some(crossing) = crossing {
+                bounds = BoundingBox::new_unchecked(
+                    bounds.bottom_left().min(crossing),
+                    bounds.top_right().max(crossing),
+                );
+            }
+        }
+
+        let padding = Vector::new(1.0, 1.0);
+        let bounds = BoundingBox::new_unchecked(
+            bounds.bottom_left() - padding,
+            bounds.top_right() + padding,
+        );
+
+        let segments: Vec<_> = boundaries
+            .iter()
+            .flat_map(|boundary| boundary.intersection_segments(bounds))
+            .collect();
+
+        match self {
+            Geometry::Line(line) => {
+                let end = nearest_end(line.start, line.end);
+                extend_line(*line, end, &segments).map(Geometry::Line)
+            },
+            Geometry::Arc(arc) => {
+                let end = nearest_end(arc.start(), arc.end());
+                extend_arc(*arc, end, &segments).map(Geometry::Arc)
+            },
+            _ => Err(ExtendError::Unsupported),
+        }
+    }
+
+    /// Find the minimum distance between two pieces of [`Geometry`], and the
+    /// closest pair of points.
+    ///
+    /// This uses the same lines and arcs as [`Geometry::intersect()`], so
+    /// curves without an exact representation are approximated and [`Text`]
+    /// (which has no linework) returns [`None`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arcs::{components::Geometry, Line, Point, Vector, XLine};
+    /// use euclid::approxeq::ApproxEq;
+    ///
+    /// let line = Geometry::Line(Line::new(
+    ///     Point::new(0.0, 5.0),
+    ///     Point::new(10.0, 5.0),
+    /// ));
+    /// let axis = Geometry::XLine(XLine::new(
+    ///     Point::new(100.0, 0.0),
+    ///     Vector::new(1.0, 0.0),
+    /// ));
+    ///
+    /// let got = line.distance_to(&axis).unwrap();
+    ///
+    /// assert!(got.distance.approx_eq(&5.0));
+    /// assert!(got.second.y.approx_eq(&0.0));
+    /// ```
+    pub fn distance_to(
+        &self,
+        other: &Geometry,
+    ) -> Option<Separation<DrawingSpace>> {
+        let bounds = distance_bounds(self, other);
+
+        distance(
+            self.intersection_segments(bounds),
+            other.intersection_segments(bounds),
+        )
+    }
+}
+
+/// Get an area which is big enough to contain the closest points between
+/// two pieces of [`Geometry`], so infinite lines can be clipped to it.
+fn distance_bounds(
+    first: &Geometry,
+    second: &Geometry,
+) -> BoundingBox<DrawingSpace> {
+    let infinite = |geometry: &Geometry| match geometry {
+        Geometry::XLine(xline) => Some(*xline),
+        Geometry::Ray(ray) => Some(ray.to_xline()),
+        _ => None,
+    };
+    let anchors = |geometry: &Geometry| match infinite(geometry) {
+        Some(xline) => vec![xline.base_point],
+        None => {
+            let bounds = geometry.bounding_box();
+            vec![
+                bounds.bottom_left(),
+                bounds.bottom_right(),
+                bounds.top_right(),
+                bounds.top_left(),
+            ]
+        },
+    };
+
+    let mut points = anchors(first);
+    points.extend(anchors(second));
+
+    // the closest point on an infinite line is always where the anchors of
+    // everything else get projected onto it, or where it crosses another
+    // infinite line
+    let xlines: Vec<XLine> = [first, second]
+        .iter()
+        .filter_map(|geometry| infinite(geometry))
+        .collect();
+    let mut extra = Vec::new();
+    for xline in &xlines {
+        extra.extend(points.iter().map(|&point| {
+            let t = (point - xline.base_point).dot(xline.direction)
+                / xline.direction.square_length();
+            xline.point_at(t)
+        }));
+    }
+    if let [a, b] = xlines[..] {
+        extra.extend(a.intersect_xline(b));
+    }
+    points.extend(extra);
+
+    let (min, max) = points
+        .iter()
+        .fold((points[0], points[0]), |(min, max), &point| {
+            (min.min(point), max.max(point))
+        });
+    let padding = Vector::new(1.0, 1.0);
+
+    BoundingBox::new_unchecked(min - padding, max + padding)
+}
+
+impl Intersect<DrawingSpace> for Geometry {
+    /// Intersect any two pieces of [`Geometry`].
+    ///
+    /// Lines and arcs (including polyline segments) are intersected exactly,
+    /// while other curves are approximated. Annotations are intersected
+    /// using their lines and arcs, and [`Text`] never intersects with
+    /// anything.
+    fn intersect_within(
+        &self,
+        other: &Geometry,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<DrawingSpace> {
+        // infinite lines can't be clipped to each other's bounds
+        let crossing = match (self, other) {
+            (Geometry::XLine(a), Geometry::XLine(b)) => {
+                Some(a.intersect_xline(*b))
+            },
+            (Geometry::XLine(a), Geometry::Ray(b))
+            | (Geometry::Ray(b), Geometry::XLine(a)) => {
+                Some(b.intersect_xline(*a))
+            },
+            (Geometry::Ray(a), Geometry::Ray(b)) => Some(a.intersect_ray(*b)),
+            _ => None,
+        };
+        if let Some(point) = crossing {
+            let mut set = IntersectionSet::with_tolerance(tolerance);
+            set.extend(point);
+            return set;
+        }
+
+        // give infinite lines enough room to cross the other geometry
+        let padded = |bounds: BoundingBox<DrawingSpace>| {
+            let padding = Vector::new(1.0, 1.0);
+            BoundingBox::new_unchecked(
+                bounds.bottom_left() - padding,
+                bounds.top_right() + padding,
+            )
+        };
+
+        let first = self.intersection_segments(padded(other.bounding_box()));
+        let second = other.intersection_segments(padded(self.bounding_box()));
+
+        first[..].intersect_within(&second[..], tolerance)
+    }
+}
+
+impl Offset<DrawingSpace> for Geometry {
+    /// Only lines, arcs and polylines can be offset, other types of
+    /// [`Geometry`] will return [`None`].
+    fn offset(&self, distance: f64, side: Side) -> Option<Self> {
+        match self {
+            Geometry::Line(line) => {
+                line.offset(distance, side).map(Geometry::Line)
+            },
+            Geometry::Arc(arc) => arc.offset(distance, side).map(Geometry::Arc),
+            Geometry::Polyline(polyline) => {
+                polyline.offset(distance, side).map(Geometry::Polyline)
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Intersect<DrawingSpace> for DrawingObject {
+    fn intersect_within(
+        &self,
+        other: &DrawingObject,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<DrawingSpace> {
+        self.geometry.intersect_within(&other.geometry, tolerance)
+    }
+}
+
 impl Translate<DrawingSpace> for DrawingObject {
     fn translate(&mut self, displacement: Vector) {
         self.geometry.translate(displacement);
     }
 }
+
+impl Transformable<DrawingSpace> for DrawingObject {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        self.geometry.transform_by(transform)
+    }
+}
+
+/// Mirror every [`Selected`] [`DrawingObject`] across the infinite line
+/// through `axis`, returning the mirrored entities.
+///
+/// When `keep_originals` is `true` the mirrored objects are added as new
+/// entities (on the same [`Layer`] and with the same styles) which aren't
+/// selected, otherwise the originals are mirrored in-place. Objects on a
+/// locked [`Layer`] are skipped.
+///
+/// Nothing is changed if any of the objects can't be mirrored, or if mirroring
+/// in-place would modify a [`Locked`] entity.
+pub fn mirror_selected(
+    world: &mut World,
+    axis: Line,
+    keep_originals: bool,
+) -> Result<Vec<Entity>, EditError> {
+    let mirrored = transformed_selection(world, |object| mirror(object, axis))?;
+
+    if !keep_originals {
+        ensure_all_unlocked(world, mirrored.iter().map(|(entity, _)| *entity))?;
+        return Ok(overwrite(world, mirrored));
+    }
+
+    Ok(mirrored
+        .into_iter()
+        .map(|(original, object)| duplicate(world, original, object))
+        .collect())
+}
+
+/// Create a new entity for `object`, a modified copy of the `original`
+/// entity's [`DrawingObject`].
+///
+/// The copy gets the original's styles, [`crate::components::Metadata`], group
+/// membership, and other properties. If the original has a [`Name`] the copy
+/// gets a unique name based on it (e.g. `Wall` becomes `Wall-1`) from the
+/// [`NameGenerator`]. The copy is never [`Selected`], and doesn't inherit
+/// anything tying the original to the rest of the drawing (e.g. its
+/// [`crate::components::Constraint`]s or place in the hierarchy).
+pub fn duplicate(
+    world: &mut World,
+    original: Entity,
+    object: DrawingObject,
+) -> Entity {
+    let original_name = world.read_storage::<Name>().get(original).cloned();
+
+    let name = original_name.map(|original_name| {
+        world
+            .entry::<NameGenerator>()
+            .or_insert_with(Default::default);
+        let table = world.try_fetch::<NameTable>();
+        let empty = NameTable::default();
+
+        world.fetch_mut::<NameGenerator>().next_available(
+            original_name.as_str(),
+            table.as_deref().unwrap_or(&empty),
+        )
+    });
+
+    let mut builder = world.create_entity().with(object);
+    if let Some(name) = name {
+        builder = builder.with(name);
+    }
+    let copy = builder.build();
+
+    for component in crate::components::known_components() {
+        component.duplicate(world, original, copy);
+    }
+
+    // the copy is in the same group as the original, so the group needs to
+    // know about it too
+    if let Some(&GroupMember(group)) =
+        world.read_storage::<GroupMember>().get(copy)
+    {
+        if let Some(group) = world.write_storage::<Group>().get_mut(group) {
+            group.push(copy);
+        }
+    }
+
+    copy
+}
+
+/// Copy an entity into a grid with `rows` rows and `columns` columns (see
+/// [`array::rectangular()`]), returning the newly created copies.
+///
+/// Nothing is created if `original` has no [`DrawingObject`].
+pub fn rectangular_array(
+    world: &mut World,
+    original: Entity,
+    rows: usize,
+    columns: usize,
+    spacing: Vector,
+) -> Vec<Entity> {
+    let copies = match world.read_storage::<DrawingObject>().get(original) {
+        Some(object) => array::rectangular(object, rows, columns, spacing),
+        None => return Vec::new(),
+    };
+
+    duplicate_all(world, original, copies)
+}
+
+/// Copy an entity `count` times around `centre` (see [`array::polar()`]),
+/// returning the newly created copies.
+///
+/// Nothing is created if `original` has no [`DrawingObject`].
+pub fn polar_array(
+    world: &mut World,
+    original: Entity,
+    base_point: Point,
+    centre: Point,
+    count: usize,
+    span: Angle,
+    rotate_items: bool,
+) -> Vec<Entity> {
+    let copies = match world.read_storage::<DrawingObject>().get(original) {
+        Some(object) => {
+            array::polar(object, base_point, centre, count, span, rotate_items)
+        },
+        None => return Vec::new(),
+    };
+
+    duplicate_all(world, original, copies)
+}
+
+/// Create entities for everything except the first item in an array (that's
+/// the original).
+fn duplicate_all(
+    world: &mut World,
+    original: Entity,
+    copies: Vec<DrawingObject>,
+) -> Vec<Entity> {
+    copies
+        .into_iter()
+        .skip(1)
+        .map(|object| duplicate(world, original, object))
+        .collect()
+}
+
+/// Stretch every [`Selected`] [`DrawingObject`] in-place by independent
+/// factors along the x and y axes, relative to `base_point`.
+///
+/// Squashed [`Arc`]s become [`EllipticalArc`]s (see [`scale_about()`]), and
+/// nothing is changed if any of the objects can't be scaled or are
+/// [`Locked`]. Objects on a locked [`Layer`] are skipped.
+pub fn scale_selected(
+    world: &mut World,
+    base_point: Point,
+    factor_x: f64,
+    factor_y: f64,
+) -> Result<Vec<Entity>, EditError> {
+    let scaled = transformed_selection(world, |object| {
+        scale_about(object, base_point, factor_x, factor_y)
+    })?;
+    ensure_all_unlocked(world, scaled.iter().map(|(entity, _)| *entity))?;
+
+    Ok(overwrite(world, scaled))
+}
+
+/// Rotate every [`Selected`] [`DrawingObject`] anticlockwise about
+/// `base_point`, returning the rotated entities.
+///
+/// Objects on a locked [`Layer`] are skipped, and nothing is changed if any
+/// of the objects are [`Locked`].
+pub fn rotate_selected(
+    world: &World,
+    base_point: Point,
+    angle: Angle,
+) -> Result<Vec<Entity>, EditError> {
+    let targets = editable_selection(world)?;
+    let mut objects = world.write_storage::<DrawingObject>();
+
+    for &entity in &targets {
+        if let Some(object) = objects.get_mut(entity) {
+            object.rotate(base_point, angle);
+        }
+    }
+
+    Ok(targets)
+}
+
+/// Move every [`Selected`] [`DrawingObject`] by `displacement`, returning the
+/// moved entities.
+///
+/// Objects on a locked [`Layer`] are skipped, and nothing is changed if any
+/// of the objects are [`Locked`].
+pub fn translate_selected(
+    world: &World,
+    displacement: Vector,
+) -> Result<Vec<Entity>, EditError> {
+    let targets = editable_selection(world)?;
+    let mut objects = world.write_storage::<DrawingObject>();
+
+    for &entity in &targets {
+        if let Some(object) = objects.get_mut(entity) {
+            object.translate(displacement);
+        }
+    }
+
+    Ok(targets)
+}
+
+/// Delete every [`Selected`] [`DrawingObject`], returning the deleted
+/// entities.
+///
+/// Objects on a locked [`Layer`] are skipped, and nothing is deleted if any
+/// of the objects are [`Locked`]. The entities are removed the next time
+/// [`World::maintain()`] is called.
+pub fn delete_selected(world: &World) -> Result<Vec<Entity>, EditError> {
+    let targets = editable_selection(world)?;
+    let entities = world.entities();
+
+    for &entity in &targets {
+        let _ = entities.delete(entity);
+    }
+
+    Ok(targets)
+}
+
+/// Every [`Selected`] [`DrawingObject`] which isn't on a locked [`Layer`],
+/// failing if any of them are [`Locked`].
+fn editable_selection(world: &World) -> Result<Vec<Entity>, EditError> {
+    let entities = world.entities();
+    let objects = world.read_storage::<DrawingObject>();
+    let selected = world.read_storage::<Selected>();
+    let layers = world.read_storage::<Layer>();
+    let locked = world.read_storage::<Locked>();
+
+    (&entities, &objects, &selected)
+        .join()
+        .filter(|(_, object, _)| !is_locked(&layers, object.layer))
+        .map(|(entity, _, _)| {
+            ensure_unlocked(&locked, entity)?;
+            Ok(entity)
+        })
+        .collect()
+}
+
+fn ensure_all_unlocked<I>(world: &World, entities: I) -> Result<(), EditError>
+where
+    I: IntoIterator<Item = Entity>,
+{
+    let locked = world.read_storage::<Locked>();
+
+    entities
+        .into_iter()
+        .try_for_each(|entity| ensure_unlocked(&locked, entity))
+}
+
+/// Transform a copy of every [`Selected`] [`DrawingObject`] which isn't on a
+/// locked [`Layer`], bailing out at the first failure.
+fn transformed_selection<F>(
+    world: &World,
+    mut transform: F,
+) -> Result<Vec<(Entity, DrawingObject)>, EditError>
+where
+    F: FnMut(&DrawingObject) -> Result<DrawingObject, TransformError>,
+{
+    let entities = world.entities();
+    let objects = world.read_storage::<DrawingObject>();
+    let selected = world.read_storage::<Selected>();
+    let layers = world.read_storage::<Layer>();
+
+    (&entities, &objects, &selected)
+        .join()
+        .filter(|(_, object, _)| !is_locked(&layers, object.layer))
+        .map(|(entity, object, _)| Ok((entity, transform(object)?)))
+        .collect()
+}
+
+fn overwrite(
+    world: &mut World,
+    transformed: Vec<(Entity, DrawingObject)>,
+) -> Vec<Entity> {
+    let mut objects = world.write_storage::<DrawingObject>();
+
+    transformed
+        .into_iter()
+        .map(|(entity, object)| {
+            if let Some(original) = objects.get_mut(entity) {
+                *original = object;
+            }
+            entity
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{
+            create_group, Colour, ConstructionGeometry, DrawOrder, Hyperlink,
+            ImageSource, LineStyle, Metadata,
+        },
+        Angle,
+    };
+    use euclid::approxeq::ApproxEq;
+
+    #[test]
+    fn intersect_different_kinds_of_geometry() {
+        let line = Geometry::Line(Line::new(
+            Point::new(-10.0, 0.0),
+            Point::new(10.0, 0.0),
+        ));
+        let circle = Geometry::Arc(Arc::from_centre_radius(
+            Point::zero(),
+            5.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        ));
+        let xline = Geometry::XLine(XLine::new(
+            Point::new(1.0, 100.0),
+            Vector::new(0.0, 1.0),
+        ));
+        let image = Geometry::ImageRef(ImageRef::new(
+            ImageSource::Handle(0),
+            Point::new(-2.0, -2.0),
+            4,
+            4,
+        ));
+
+        assert_eq!(line.intersect(&circle).len(), 2);
+        assert_eq!(line.intersect(&xline).points(), &[Point::new(1.0, 0.0)]);
+        assert_eq!(xline.intersect(&image).len(), 2);
+        assert_eq!(circle.intersect(&image).len(), 0);
+    }
+
+    #[test]
+    fn extend_a_line_to_a_construction_line() {
+        let line = Geometry::Line(Line::new(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+        ));
+        let xline = Geometry::XLine(XLine::new(
+            Point::new(50.0, 0.0),
+            Vector::new(1.0, 1.0),
+        ));
+
+        let got = line.extend(&[xline], Point::new(0.9, 0.0)).unwrap();
+
+        assert_eq!(
+            got,
+            Geometry::Line(Line::new(
+                Point::new(0.0, 0.0),
+                Point::new(50.0, 0.0)
+            ))
+        );
+        assert_eq!(
+            line.extend(&[], Point::zero()),
+            Err(ExtendError::NoBoundary)
+        );
+    }
+
+    #[test]
+    fn closest_location_on_geometry() {
+        let arc = Geometry::Arc(Arc::from_centre_radius(
+            Point::zero(),
+            2.0,
+            Angle::zero(),
+            Angle::pi(),
+        ));
+
+        let got = arc.closest_location(Point::new(0.0, 5.0)).unwrap();
+
+        assert!((got.point - Point::new(0.0, 2.0)).length() < 1e-10);
+        assert!((got.parameter - 0.5).abs() < 1e-10);
+        assert!((got.distance - 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn total_length_of_mixed_geometry() {
+        let objects = [
+            Geometry::Line(Line::new(
+                Point::new(0.0, 0.0),
+                Point::new(3.0, 4.0),
+            )),
+            Geometry::Arc(Arc::from_centre_radius(
+                Point::zero(),
+                2.0,
+                Angle::zero(),
+                Angle::pi(),
+            )),
+            Geometry::Point(Point::new(1.0, 1.0)),
+        ];
+
+        let got: f64 = objects.iter().map(Length::length).sum();
+
+        assert!((got - (5.0 + 2.0 * std::f64::consts::PI)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn hull_around_a_circle() {
+        let circle = Geometry::Arc(Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        ));
+
+        let points = circle.hull_points();
+
+        assert!(points.len() > 4);
+        let hull = crate::algorithms::convex_hull(points);
+        assert!((hull.area() - std::f64::consts::PI).abs() < 0.01);
+        assert!(Geometry::XLine(XLine::new(
+            Point::zero(),
+            Vector::new(1.0, 0.0)
+        ))
+        .hull_points()
+        .is_empty());
+    }
+
+    #[test]
+    fn trim_a_line_back_to_a_circle() {
+        let line = Geometry::Line(Line::new(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+        ));
+        let circle = Geometry::Arc(Arc::from_centre_radius(
+            Point::new(10.0, 0.0),
+            4.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        ));
+
+        let got = line.trim(&[circle], Point::new(9.0, 0.0)).unwrap();
+
+        assert_eq!(
+            got,
+            vec![Geometry::Line(Line::new(
+                Point::new(0.0, 0.0),
+                Point::new(6.0, 0.0)
+            ))]
+        );
+    }
+
+    #[test]
+    fn distance_between_infinite_lines() {
+        let first = Geometry::XLine(XLine::new(
+            Point::new(0.0, 0.0),
+            Vector::new(1.0, 1.0),
+        ));
+        let parallel = Geometry::Ray(Ray::new(
+            Point::new(50.0, 48.0),
+            Vector::new(-1.0, -1.0),
+        ));
+        let crossing = Geometry::XLine(XLine::new(
+            Point::new(1000.0, 0.0),
+            Vector::new(0.0, 1.0),
+        ));
+
+        let got = first.distance_to(&parallel).unwrap();
+        assert!((got.distance - 2.0_f64.sqrt()).abs() < 1e-9);
+
+        let got = first.distance_to(&crossing).unwrap();
+        assert_eq!(got.distance, 0.0);
+        assert!((got.first - Point::new(1000.0, 1000.0)).length() < 1e-9);
+    }
+
+    #[test]
+    fn squashed_arcs_become_elliptical_arcs() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            2.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+        let squash = AffineTransform::scale(1.0, 0.5);
+        let rotate = AffineTransform::rotation(Angle::frac_pi_2());
+
+        let mut squashed = Geometry::Arc(arc);
+        squashed.transform_by(squash).unwrap();
+        let mut rotated = Geometry::Arc(arc);
+        rotated.transform_by(rotate).unwrap();
+
+        match squashed {
+            Geometry::EllipticalArc(got) => {
+                assert_eq!(got.ellipse().semi_major(), 2.0);
+                assert_eq!(got.ellipse().semi_minor(), 1.0);
+                assert!(got.end().x.abs() < 1e-10);
+                assert!((got.end().y - 1.0).abs() < 1e-10);
+            },
+            other => panic!("Expected an elliptical arc, got {:?}", other),
+        }
+        match rotated {
+            Geometry::Arc(_) => {},
+            other => panic!("Expected an arc, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn mirror_the_selection() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let line = Line::new(Point::new(1.0, 0.0), Point::new(2.0, 1.0));
+        let selected = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer,
+            })
+            .with(Selected)
+            .build();
+        let unselected = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer,
+            })
+            .build();
+        let y_axis = Line::new(Point::zero(), Point::new(0.0, 1.0));
+        let mirrored = Line::new(Point::new(-1.0, 0.0), Point::new(-2.0, 1.0));
+
+        let copies = mirror_selected(&mut world, y_axis, true).unwrap();
+
+        assert_eq!(copies.len(), 1);
+        assert_ne!(copies[0], selected);
+        let objects = world.read_storage::<DrawingObject>();
+        assert_eq!(
+            objects.get(copies[0]).unwrap().geometry,
+            Geometry::Line(mirrored)
+        );
+        assert_eq!(
+            objects.get(selected).unwrap().geometry,
+            Geometry::Line(line)
+        );
+        drop(objects);
+
+        let moved = mirror_selected(&mut world, y_axis, false).unwrap();
+
+        assert_eq!(moved, vec![selected]);
+        let objects = world.read_storage::<DrawingObject>();
+        assert_eq!(
+            objects.get(selected).unwrap().geometry,
+            Geometry::Line(mirrored)
+        );
+        assert_eq!(
+            objects.get(unselected).unwrap().geometry,
+            Geometry::Line(line)
+        );
+    }
+
+    #[test]
+    fn stretch_the_selection() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let arc = Arc::from_centre_radius(
+            Point::new(1.0, 0.0),
+            1.0,
+            Angle::zero(),
+            Angle::pi(),
+        );
+        let line = Line::new(Point::new(1.0, 1.0), Point::new(2.0, 3.0));
+        let mut selected = Vec::new();
+        for geometry in vec![Geometry::Arc(arc), Geometry::Line(line)] {
+            selected.push(
+                world
+                    .create_entity()
+                    .with(DrawingObject { geometry, layer })
+                    .with(Selected)
+                    .build(),
+            );
+        }
+
+        let got = scale_selected(&mut world, Point::zero(), 2.0, 1.0).unwrap();
+
+        assert_eq!(got, selected);
+        let objects = world.read_storage::<DrawingObject>();
+        match objects.get(selected[0]).unwrap().geometry {
+            Geometry::EllipticalArc(elliptical) => {
+                assert!(elliptical.start().approx_eq(&Point::new(4.0, 0.0)));
+                assert!(elliptical.end().approx_eq(&Point::zero()));
+            },
+            ref other => panic!("Expected an elliptical arc, got {:?}", other),
+        }
+        assert_eq!(
+            objects.get(selected[1]).unwrap().geometry,
+            Geometry::Line(Line::new(
+                Point::new(2.0, 1.0),
+                Point::new(4.0, 3.0)
+            ))
+        );
+    }
+
+    #[test]
+    fn rotate_the_selection() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let line = Line::new(Point::new(1.0, 0.0), Point::new(2.0, 0.0));
+        let selected = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer,
+            })
+            .with(Selected)
+            .build();
+        let unselected = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer,
+            })
+            .build();
+
+        let got =
+            rotate_selected(&world, Point::zero(), Angle::frac_pi_2()).unwrap();
+
+        assert_eq!(got, vec![selected]);
+        let objects = world.read_storage::<DrawingObject>();
+        match objects.get(selected).unwrap().geometry {
+            Geometry::Line(rotated) => {
+                assert!(rotated.start.approx_eq(&Point::new(0.0, 1.0)));
+                assert!(rotated.end.approx_eq(&Point::new(0.0, 2.0)));
+            },
+            ref other => panic!("Expected a line, got {:?}", other),
+        }
+        assert_eq!(
+            objects.get(unselected).unwrap().geometry,
+            Geometry::Line(line)
+        );
+    }
+
+    #[test]
+    fn arrays_duplicate_names_layers_and_styles() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let original = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::new(1.0, 1.0)),
+                layer,
+            })
+            .with(LineStyle::default())
+            .with(Name::new("Bolt"))
+            .with(Selected)
+            .with(
+                vec![("part-number", "M8")]
+                    .into_iter()
+                    .collect::<Metadata>(),
+            )
+            .with(Hyperlink::new("https://example.com/m8.pdf"))
+            .with(Colour::Indexed(3))
+            .with(DrawOrder(7))
+            .with(ConstructionGeometry)
+            .build();
+        let group = create_group(&world, None, vec![original]);
+
+        let copies = rectangular_array(
+            &mut world,
+            original,
+            2,
+            2,
+            Vector::new(5.0, 3.0),
+        );
+
+        assert_eq!(copies.len(), 3);
+        let objects = world.read_storage::<DrawingObject>();
+        let names = world.read_storage::<Name>();
+        let styles = world.read_storage::<LineStyle>();
+        let selected = world.read_storage::<Selected>();
+        let metadata = world.read_storage::<Metadata>();
+        let hyperlinks = world.read_storage::<Hyperlink>();
+        let colours = world.read_storage::<Colour>();
+        let orders = world.read_storage::<DrawOrder>();
+        let construction = world.read_storage::<ConstructionGeometry>();
+        let membership = world.read_storage::<GroupMember>();
+        let groups = world.read_storage::<Group>();
+        let mut seen = Vec::new();
+        for &copy in &copies {
+            assert_eq!(colours.get(copy), Some(&Colour::Indexed(3)));
+            assert_eq!(orders.get(copy), Some(&DrawOrder(7)));
+            assert!(construction.get(copy).is_some());
+            assert_eq!(membership.get(copy), Some(&GroupMember(group)));
+            assert!(groups.get(group).unwrap().contains(copy));
+            assert_eq!(objects.get(copy).unwrap().layer, layer);
+            assert!(styles.get(copy).is_some());
+            assert_eq!(
+                metadata.get(copy).unwrap().get_str("part-number"),
+                Some("M8")
+            );
+            assert!(hyperlinks.get(copy).is_some());
+            assert!(selected.get(copy).is_none());
+            seen.push(names.get(copy).unwrap().as_str().to_string());
+        }
+        assert_eq!(seen, vec!["Bolt-1", "Bolt-2", "Bolt-3"]);
+        assert_eq!(
+            objects.get(copies[2]).unwrap().geometry,
+            Geometry::Point(Point::new(6.0, 4.0))
+        );
+    }
+
+    #[test]
+    fn polar_arrays_of_entities() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let original = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::new(1.0, 0.0)),
+                layer,
+            })
+            .build();
+
+        let copies = polar_array(
+            &mut world,
+            original,
+            Point::new(1.0, 0.0),
+            Point::zero(),
+            4,
+            Angle::two_pi(),
+            true,
+        );
+
+        assert_eq!(copies.len(), 3);
+        let objects = world.read_storage::<DrawingObject>();
+        match objects.get(copies[1]).unwrap().geometry {
+            Geometry::Point(point) => {
+                assert!(point.approx_eq(&Point::new(-1.0, 0.0)))
+            },
+            ref other => panic!("Expected a point, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn locked_layers_are_left_alone() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let locked = world
+            .create_entity()
+            .with(Layer {
+                locked: true,
+                ..Default::default()
+            })
+            .build();
+        let line = Line::new(Point::new(1.0, 0.0), Point::new(2.0, 0.0));
+        world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer: locked,
+            })
+            .with(Selected)
+            .build();
+
+        let rotated =
+            rotate_selected(&world, Point::zero(), Angle::frac_pi_2()).unwrap();
+        let scaled = scale_selected(&mut world, Point::zero(), 2.0, 2.0);
+
+        assert!(rotated.is_empty());
+        assert_eq!(scaled, Ok(Vec::new()));
+    }
+
+    #[test]
+    fn locked_entities_refuse_to_be_modified() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let line = Line::new(Point::new(1.0, 0.0), Point::new(2.0, 0.0));
+        let free = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer,
+            })
+            .with(Selected)
+            .build();
+        let reference = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer,
+            })
+            .with(Selected)
+            .with(Locked)
+            .build();
+
+        let locked = Err(EditError::Locked(reference));
+        assert_eq!(translate_selected(&world, Vector::new(1.0, 1.0)), locked);
+        assert_eq!(
+            rotate_selected(&world, Point::zero(), Angle::frac_pi_2()),
+            locked
+        );
+        assert_eq!(delete_selected(&world), locked);
+        world.maintain();
+
+        let objects = world.read_storage::<DrawingObject>();
+        for entity in vec![free, reference] {
+            match objects.get(entity).unwrap().geometry {
+                Geometry::Line(got) => assert_eq!(got, line),
+                ref other => panic!("Expected a line, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn delete_the_selection() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let point = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::zero()),
+                layer,
+            })
+            .with(Selected)
+            .build();
+
+        let deleted = delete_selected(&world).unwrap();
+        world.maintain();
+
+        assert_eq!(deleted, vec![point]);
+        assert!(!world.is_alive(point));
+    }
+}
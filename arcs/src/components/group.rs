@@ -0,0 +1,221 @@
+use crate::components::{select, EntityMap, MapEntities, Name};
+use specs::prelude::*;
+
+/// A collection of entities which are selected and edited as a unit.
+///
+/// Each member has a [`GroupMember`] pointing back at the group's
+/// [`Entity`]. Use [`create_group()`] and [`dissolve_group()`] so both sides
+/// stay in sync, and register the [`crate::systems::GroupBookkeeping`]
+/// system so deleted members are dropped automatically.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Group {
+    members: Vec<Entity>,
+}
+
+impl Group {
+    /// The entities in this [`Group`], in the order they were added.
+    pub fn members(&self) -> &[Entity] { &self.members }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.members.contains(&entity)
+    }
+
+    pub fn len(&self) -> usize { self.members.len() }
+
+    pub fn is_empty(&self) -> bool { self.members.is_empty() }
+
+    pub(crate) fn push(&mut self, entity: Entity) {
+        if !self.contains(entity) {
+            self.members.push(entity);
+        }
+    }
+
+    pub(crate) fn retain<F>(&mut self, predicate: F)
+    where
+        F: FnMut(&Entity) -> bool,
+    {
+        self.members.retain(predicate);
+    }
+}
+
+impl Component for Group {
+    type Storage = FlaggedStorage<Self, HashMapStorage<Self>>;
+}
+
+impl MapEntities for Group {
+    fn map_entities(&mut self, map: &EntityMap) {
+        for member in &mut self.members {
+            *member = map.get(*member);
+        }
+    }
+}
+
+/// Marks an [`Entity`] as belonging to a [`Group`].
+///
+/// An entity can only be in one [`Group`] at a time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GroupMember(pub Entity);
+
+impl Component for GroupMember {
+    type Storage = FlaggedStorage<Self, DenseVecStorage<Self>>;
+}
+
+impl MapEntities for GroupMember {
+    fn map_entities(&mut self, map: &EntityMap) { self.0 = map.get(self.0); }
+}
+
+/// Create a new [`Group`] containing `members`, optionally giving it a
+/// [`Name`].
+///
+/// Members which were already part of another [`Group`] are moved into the
+/// new one, and members which have been deleted are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// # use arcs::components::*;
+/// # use specs::prelude::*;
+/// let mut world = World::new();
+/// arcs::components::register(&mut world);
+/// let door = world.create_entity().build();
+/// let handle = world.create_entity().build();
+///
+/// let group =
+///     create_group(&world, Some(Name::new("Door")), vec![door, handle]);
+///
+/// let groups = world.read_storage::<Group>();
+/// assert_eq!(groups.get(group).unwrap().members(), &[door, handle]);
+/// let membership = world.read_storage::<GroupMember>();
+/// assert_eq!(membership.get(handle), Some(&GroupMember(group)));
+/// ```
+pub fn create_group<I>(world: &World, name: Option<Name>, members: I) -> Entity
+where
+    I: IntoIterator<Item = Entity>,
+{
+    let entities = world.entities();
+    let mut groups = world.write_storage::<Group>();
+    let mut membership = world.write_storage::<GroupMember>();
+    let group_entity = entities.create();
+    let mut group = Group::default();
+
+    for member in members {
+        if !entities.is_alive(member) || group.contains(member) {
+            continue;
+        }
+
+        if let Some(&GroupMember(previous)) = membership.get(member) {
+            if let Some(previous) = groups.get_mut(previous) {
+                previous.retain(|&m| m != member);
+            }
+        }
+
+        membership
+            .insert(member, GroupMember(group_entity))
+            .expect("The member is alive");
+        group.members.push(member);
+    }
+
+    groups
+        .insert(group_entity, group)
+        .expect("The group was just created");
+
+    if let Some(name) = name {
+        world
+            .write_storage::<Name>()
+            .insert(group_entity, name)
+            .expect("The group was just created");
+    }
+
+    group_entity
+}
+
+/// Delete a [`Group`], leaving its members untouched apart from removing
+/// their [`GroupMember`].
+///
+/// The former members are returned.
+pub fn dissolve_group(world: &World, group: Entity) -> Vec<Entity> {
+    let removed = world.write_storage::<Group>().remove(group);
+    let members = match removed {
+        Some(group) => group.members,
+        None => return Vec::new(),
+    };
+
+    let mut membership = world.write_storage::<GroupMember>();
+
+    for &member in &members {
+        if membership.get(member) == Some(&GroupMember(group)) {
+            membership.remove(member);
+        }
+    }
+
+    if let Err(e) = world.entities().delete(group) {
+        log::warn!("Unable to delete the group: {}", e);
+    }
+
+    members
+}
+
+/// Mark every member of a [`Group`] as selected (see [`select()`]).
+pub fn select_group(world: &World, group: Entity) {
+    let members = match world.read_storage::<Group>().get(group) {
+        Some(group) => group.members().to_vec(),
+        None => return,
+    };
+
+    for member in members {
+        select(world, member);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Selected;
+
+    fn world_with_entities(count: usize) -> (World, Vec<Entity>) {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let entities =
+            (0..count).map(|_| world.create_entity().build()).collect();
+
+        (world, entities)
+    }
+
+    #[test]
+    fn entities_move_between_groups() {
+        let (world, ents) = world_with_entities(3);
+        let first = create_group(&world, None, vec![ents[0], ents[1]]);
+
+        let second =
+            create_group(&world, None, vec![ents[1], ents[2], ents[2]]);
+
+        let groups = world.read_storage::<Group>();
+        assert_eq!(groups.get(first).unwrap().members(), &[ents[0]]);
+        assert_eq!(groups.get(second).unwrap().members(), &[ents[1], ents[2]]);
+        let membership = world.read_storage::<GroupMember>();
+        assert_eq!(membership.get(ents[1]), Some(&GroupMember(second)));
+    }
+
+    #[test]
+    fn dissolve_and_select_groups() {
+        let (mut world, ents) = world_with_entities(3);
+        let group = create_group(&world, None, vec![ents[0], ents[2]]);
+
+        select_group(&world, group);
+
+        {
+            let selected = world.read_storage::<Selected>();
+            let got: Vec<_> =
+                ents.iter().map(|&e| selected.contains(e)).collect();
+            assert_eq!(got, vec![true, false, true]);
+        }
+
+        let members = dissolve_group(&world, group);
+        world.maintain();
+
+        assert_eq!(members, vec![ents[0], ents[2]]);
+        assert!(!world.is_alive(group));
+        assert!(world.read_storage::<GroupMember>().is_empty());
+        assert!(world.is_alive(ents[0]));
+    }
+}
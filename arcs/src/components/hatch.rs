@@ -0,0 +1,567 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Approximate, Bounded, Closest, ClosestPoint,
+        SelfIntersect, SelfIntersection, TransformError, Transformable,
+        Translate,
+    },
+    components::Geometry,
+    primitives::{Region, Vertex},
+    Angle, BoundingBox, DrawingSpace, Line, Point, Polyline, Tolerance, Vector,
+};
+use specs::Entity;
+
+/// An area filled in with a solid colour or a pattern of lines (e.g. a DXF
+/// `HATCH` entity).
+///
+/// The filled area is everything inside an odd number of
+/// [`Hatch::loops`], so a loop inside another loop will create a hole.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hatch {
+    /// The closed loops bounding the area to fill.
+    pub loops: Vec<HatchLoop>,
+    /// How the area should be filled.
+    pub pattern: HatchPattern,
+}
+
+impl Hatch {
+    /// Create a new [`Hatch`] without any boundaries.
+    pub fn new(pattern: HatchPattern) -> Self {
+        Hatch {
+            loops: Vec::new(),
+            pattern,
+        }
+    }
+
+    /// Add a boundary loop which isn't associated with any other entity.
+    pub fn with_loop(mut self, boundary: Polyline) -> Self {
+        self.loops.push(HatchLoop::new(boundary));
+        self
+    }
+
+    /// Approximate each loop with a closed sequence of points.
+    pub fn approximate_loops(&self, tolerance: f64) -> Vec<Vec<Point>> {
+        self.loops
+            .iter()
+            .map(|l| l.boundary.approximate(tolerance).collect())
+            .collect()
+    }
+
+    /// Is this point inside the filled area?
+    pub fn contains(&self, point: Point, tolerance: f64) -> bool {
+        let crossings: usize = self
+            .approximate_loops(tolerance)
+            .iter()
+            .map(|points| {
+                points
+                    .windows(2)
+                    .filter(|edge| {
+                        let (a, b) = (edge[0], edge[1]);
+
+                        if (a.y > point.y) == (b.y > point.y) {
+                            return false;
+                        }
+
+                        let t = (point.y - a.y) / (b.y - a.y);
+                        point.x < a.x + t * (b.x - a.x)
+                    })
+                    .count()
+            })
+            .sum();
+
+        crossings % 2 == 1
+    }
+
+    /// Calculate the lines used to draw the [`HatchPattern`], clipped to the
+    /// filled area.
+    ///
+    /// A [`HatchPattern::Solid`] fill doesn't have any lines.
+    pub fn pattern_lines(&self, tolerance: f64) -> Vec<Line> {
+        let families = match self.pattern {
+            HatchPattern::Solid => return Vec::new(),
+            HatchPattern::Lines(ref families) => families,
+        };
+        let loops = self.approximate_loops(tolerance);
+
+        families
+            .iter()
+            .flat_map(|family| family.clip_to(&loops))
+            .collect()
+    }
+
+    /// A tolerance which is appropriate for approximating this [`Hatch`]'s
+    /// boundaries.
+    fn default_tolerance(&self) -> f64 {
+        self.bounding_box().diagonal().length() * 1e-3
+    }
+}
+
+impl Bounded<DrawingSpace> for Hatch {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        let mut loops = self
+            .loops
+            .iter()
+            .filter(|l| !l.boundary.is_empty())
+            .map(|l| l.boundary.bounding_box());
+
+        let first = match loops.next() {
+            Some(first) => first,
+            None => return BoundingBox::new(Point::zero(), Point::zero()),
+        };
+
+        let (bottom_left, top_right) = loops.fold(
+            (first.bottom_left(), first.top_right()),
+            |(bottom_left, top_right), bounds| {
+                (
+                    bottom_left.min(bounds.bottom_left()),
+                    top_right.max(bounds.top_right()),
+                )
+            },
+        );
+
+        BoundingBox::new_unchecked(bottom_left, top_right)
+    }
+}
+
+impl SelfIntersect<DrawingSpace> for Hatch {
+    /// A valid [`Hatch`] boundary never crosses itself, although loops may
+    /// be nested inside each other.
+    ///
+    /// Segments are numbered consecutively across every loop, in the same
+    /// order as [`Hatch::loops`].
+    fn self_intersections_within(
+        &self,
+        tolerance: Tolerance,
+    ) -> Vec<SelfIntersection<DrawingSpace>> {
+        let mut boundaries = self.loops.iter().map(|l| l.boundary.clone());
+
+        match boundaries.next() {
+            Some(first) => Region::new(first, boundaries.collect())
+                .self_intersections_within(tolerance),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for Hatch {
+    /// The filled area is treated as solid, so any point inside it is closest
+    /// to itself.
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        if self.contains(target, self.default_tolerance()) {
+            return Closest::One(target);
+        }
+
+        self.loops
+            .iter()
+            .flat_map(|l| l.boundary.closest_point(target).points().to_vec())
+            .min_by(|a, b| {
+                let a = (*a - target).square_length();
+                let b = (*b - target).square_length();
+                a.partial_cmp(&b).unwrap()
+            })
+            .map(Closest::One)
+            .unwrap_or(Closest::Infinite)
+    }
+}
+
+impl Translate<DrawingSpace> for Hatch {
+    fn translate(&mut self, displacement: Vector) {
+        for l in &mut self.loops {
+            l.boundary.translate(displacement);
+        }
+
+        if let HatchPattern::Lines(ref mut families) = self.pattern {
+            for family in families {
+                family.origin += displacement;
+            }
+        }
+    }
+}
+
+impl Transformable<DrawingSpace> for Hatch {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        // transform a copy so we're left untouched if part of it fails
+        let mut transformed = self.clone();
+
+        for l in &mut transformed.loops {
+            l.boundary.transform_by(transform)?;
+        }
+
+        if let HatchPattern::Lines(ref mut families) = transformed.pattern {
+            let scale = transform.similarity()?;
+
+            for family in families {
+                family.angle = transform.transform_angle(family.angle);
+                family.origin = transform.transform_point(family.origin);
+                family.spacing *= scale;
+                for dash in &mut family.dashes {
+                    *dash *= scale;
+                }
+            }
+        }
+
+        *self = transformed;
+
+        Ok(())
+    }
+}
+
+/// A single closed boundary for a [`Hatch`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HatchLoop {
+    /// The loop's shape.
+    pub boundary: Polyline,
+    /// The entity this loop was created from, if any.
+    ///
+    /// The [`crate::systems::RecomputeDerived`] system will update the
+    /// [`HatchLoop::boundary`] whenever this entity's [`Geometry`] changes.
+    pub associated_with: Option<Entity>,
+}
+
+impl HatchLoop {
+    /// Create a [`HatchLoop`] which isn't associated with any other entity.
+    pub fn new(mut boundary: Polyline) -> Self {
+        boundary.set_closed(true);
+
+        HatchLoop {
+            boundary,
+            associated_with: None,
+        }
+    }
+
+    /// Create a [`HatchLoop`] which follows the shape of another entity.
+    ///
+    /// This will fail if the [`Geometry`] isn't closed (see
+    /// [`boundary_from_geometry()`]).
+    pub fn associated(entity: Entity, geometry: &Geometry) -> Option<Self> {
+        boundary_from_geometry(geometry).map(|boundary| HatchLoop {
+            boundary,
+            associated_with: Some(entity),
+        })
+    }
+}
+
+/// Try to turn some closed [`Geometry`] into a [`Polyline`] which can be used
+/// as a [`HatchLoop::boundary`].
+///
+/// Closed polylines and full circles are converted exactly, while ellipses
+/// and closed curves are approximated.
+pub fn boundary_from_geometry(geometry: &Geometry) -> Option<Polyline> {
+    fn approximated<A: Approximate<DrawingSpace> + Bounded<DrawingSpace>>(
+        curve: &A,
+    ) -> Polyline {
+        let tolerance = curve.bounding_box().diagonal().length() * 1e-3;
+        let mut points: Vec<_> = curve.approximate(tolerance).collect();
+        // the loop will be closed anyway
+        points.pop();
+
+        Polyline::new(points.into_iter().map(Vertex::straight).collect(), true)
+    }
+
+    fn is_closed(start: Point, end: Point) -> bool {
+        (start - end).length() <= std::f64::EPSILON * 1e3
+    }
+
+    match geometry {
+        Geometry::Polyline(polyline) if polyline.is_closed() => {
+            Some(polyline.clone())
+        },
+        Geometry::Arc(arc)
+            if arc.sweep_angle().radians.abs() >= Angle::two_pi().radians =>
+        {
+            // two semicircles
+            let bulge = arc.sweep_angle().radians.signum();
+            let opposite = arc.centre() + (arc.centre() - arc.start());
+
+            Some(Polyline::new(
+                vec![
+                    Vertex::new(arc.start(), bulge),
+                    Vertex::new(opposite, bulge),
+                ],
+                true,
+            ))
+        },
+        Geometry::Ellipse(ellipse) => Some(approximated(ellipse)),
+        Geometry::CubicBezier(curve) if is_closed(curve.start, curve.end) => {
+            Some(approximated(curve))
+        },
+        Geometry::QuadraticBezier(curve)
+            if is_closed(curve.start, curve.end) =>
+        {
+            Some(approximated(curve))
+        },
+        Geometry::Spline(spline) if is_closed(spline.start(), spline.end()) => {
+            Some(approximated(spline))
+        },
+        _ => None,
+    }
+}
+
+/// How a [`Hatch`] should be filled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HatchPattern {
+    /// Fill the entire area with a solid colour.
+    Solid,
+    /// Fill the area with one or more families of (possibly dashed) parallel
+    /// lines.
+    Lines(Vec<HatchLine>),
+}
+
+impl HatchPattern {
+    /// The ANSI31 pattern (iron, brick and stone masonry) - lines at 45°.
+    pub fn ansi31(scale: f64) -> Self {
+        HatchPattern::Lines(vec![HatchLine::new(
+            Angle::frac_pi_4(),
+            3.175 * scale,
+        )])
+    }
+
+    /// The ANSI32 pattern (steel) - pairs of lines at 45°.
+    pub fn ansi32(scale: f64) -> Self {
+        HatchPattern::Lines(vec![
+            HatchLine::new(Angle::frac_pi_4(), 9.525 * scale),
+            HatchLine {
+                origin: Point::new(4.490128 * scale, 0.0),
+                ..HatchLine::new(Angle::frac_pi_4(), 9.525 * scale)
+            },
+        ])
+    }
+
+    /// The ANSI37 pattern (lead, zinc, magnesium, sound/heat/electrical
+    /// insulation) - a 45° cross-hatch.
+    pub fn ansi37(scale: f64) -> Self {
+        HatchPattern::Lines(vec![
+            HatchLine::new(Angle::frac_pi_4(), 3.175 * scale),
+            HatchLine::new(Angle::frac_pi_4() * 3.0, 3.175 * scale),
+        ])
+    }
+}
+
+/// A family of parallel lines used in a [`HatchPattern`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HatchLine {
+    /// The direction the lines run in.
+    pub angle: Angle,
+    /// A point one of the lines passes through.
+    pub origin: Point,
+    /// The perpendicular distance between consecutive lines.
+    pub spacing: f64,
+    /// The dash pattern, using the DXF convention where positive numbers are
+    /// dashes, negative numbers are gaps and zero is a dot.
+    ///
+    /// An empty dash pattern means the lines are continuous.
+    pub dashes: Vec<f64>,
+}
+
+impl HatchLine {
+    /// The most lines a single [`HatchLine`] will generate, to stop a tiny
+    /// spacing from locking up the application.
+    pub const MAX_LINES: usize = 10_000;
+
+    /// Create a family of continuous lines passing through the origin.
+    pub fn new(angle: Angle, spacing: f64) -> Self {
+        HatchLine {
+            angle,
+            origin: Point::zero(),
+            spacing,
+            dashes: Vec::new(),
+        }
+    }
+
+    /// Generate the lines which lie inside the loops, using the even-odd
+    /// rule.
+    fn clip_to(&self, loops: &[Vec<Point>]) -> Vec<Line> {
+        if self.spacing.is_nan()
+            || self.spacing <= 0.0
+            || loops.iter().all(|l| l.is_empty())
+        {
+            return Vec::new();
+        }
+
+        let (sin, cos) = self.angle.sin_cos();
+        let direction = Vector::new(cos, sin);
+        let normal = Vector::new(-sin, cos);
+        let offset_of = |p: Point| Vector::dot(p - self.origin, normal);
+
+        let (min, max) = loops.iter().flatten().fold(
+            (std::f64::INFINITY, std::f64::NEG_INFINITY),
+            |(min, max), &p| (min.min(offset_of(p)), max.max(offset_of(p))),
+        );
+        // lines which only touch the very edge of the area are skipped
+        let first = (min / self.spacing).floor() as i64 + 1;
+        let last = (max / self.spacing).ceil() as i64 - 1;
+
+        if last - first > HatchLine::MAX_LINES as i64 {
+            log::warn!(
+                "Skipping a hatch line family which would generate {} lines",
+                last - first
+            );
+            return Vec::new();
+        }
+
+        let mut lines = Vec::new();
+
+        for k in first..=last {
+            let offset = k as f64 * self.spacing;
+            let mut crossings: Vec<f64> = loops
+                .iter()
+                .flat_map(|points| points.windows(2))
+                .filter_map(|edge| {
+                    let a = offset_of(edge[0]) - offset;
+                    let b = offset_of(edge[1]) - offset;
+
+                    if (a > 0.0) == (b > 0.0) {
+                        return None;
+                    }
+
+                    let crossing = edge[0].lerp(edge[1], a / (a - b));
+                    Some(Vector::dot(crossing - self.origin, direction))
+                })
+                .collect();
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let start_of_line = self.origin + normal * offset;
+
+            for pair in crossings.chunks_exact(2) {
+                for (start, end) in self.dashes_between(pair[0], pair[1]) {
+                    lines.push(Line::new(
+                        start_of_line + direction * start,
+                        start_of_line + direction * end,
+                    ));
+                }
+            }
+        }
+
+        lines
+    }
+
+    /// Break the section of a line between `start` and `end` (measured along
+    /// the line) into dashes.
+    fn dashes_between(&self, start: f64, end: f64) -> Vec<(f64, f64)> {
+        let period: f64 = self.dashes.iter().map(|d| d.abs()).sum();
+
+        if period.is_nan() || period <= 0.0 {
+            return vec![(start, end)];
+        }
+
+        let mut dashes = Vec::new();
+        let mut position = (start / period).floor() * period;
+
+        while position < end {
+            for &dash in &self.dashes {
+                if dash >= 0.0 {
+                    let dash_start = position.max(start);
+                    let dash_end = (position + dash).min(end);
+
+                    if dash_start <= dash_end {
+                        dashes.push((dash_start, dash_end));
+                    }
+                }
+
+                position += dash.abs();
+            }
+        }
+
+        dashes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Arc;
+    use euclid::approxeq::ApproxEq;
+
+    fn square(size: f64) -> Polyline {
+        Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(size, 0.0),
+            Point::new(size, size),
+            Point::new(0.0, size),
+        ])
+    }
+
+    #[test]
+    fn holes_are_not_filled() {
+        let mut hole = square(2.0);
+        hole.translate(Vector::new(4.0, 4.0));
+        let hatch = Hatch::new(HatchPattern::Solid)
+            .with_loop(square(10.0))
+            .with_loop(hole);
+
+        assert!(hatch.contains(Point::new(1.0, 1.0), 0.1));
+        assert!(!hatch.contains(Point::new(5.0, 5.0), 0.1));
+        assert!(!hatch.contains(Point::new(15.0, 5.0), 0.1));
+        assert_eq!(
+            hatch.closest_point(Point::new(5.0, 5.4)),
+            Closest::One(Point::new(5.0, 6.0))
+        );
+    }
+
+    #[test]
+    fn horizontal_lines_are_clipped_to_the_boundary() {
+        let pattern =
+            HatchPattern::Lines(vec![HatchLine::new(Angle::zero(), 3.0)]);
+        let hatch = Hatch::new(pattern).with_loop(square(10.0));
+
+        let lines = hatch.pattern_lines(0.1);
+
+        assert_eq!(
+            lines,
+            vec![
+                Line::new(Point::new(0.0, 3.0), Point::new(10.0, 3.0)),
+                Line::new(Point::new(0.0, 6.0), Point::new(10.0, 6.0)),
+                Line::new(Point::new(0.0, 9.0), Point::new(10.0, 9.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dashed_lines() {
+        let line = HatchLine {
+            dashes: vec![2.0, -1.0, 0.0, -1.0],
+            ..HatchLine::new(Angle::zero(), 1.0)
+        };
+
+        let got = line.dashes_between(1.0, 9.0);
+
+        assert_eq!(
+            got,
+            vec![(1.0, 2.0), (3.0, 3.0), (4.0, 6.0), (7.0, 7.0), (8.0, 9.0)]
+        );
+    }
+
+    #[test]
+    fn circles_make_a_valid_boundary() {
+        let circle = Arc::from_centre_radius(
+            Point::new(1.0, 1.0),
+            2.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        );
+
+        let got = boundary_from_geometry(&Geometry::Arc(circle)).unwrap();
+
+        assert!(got.area().approx_eq(&(std::f64::consts::PI * 4.0)));
+        assert!(boundary_from_geometry(&Geometry::Line(Line::new(
+            Point::zero(),
+            Point::new(1.0, 1.0)
+        )))
+        .is_none());
+    }
+
+    #[test]
+    fn crossing_loops_make_an_invalid_boundary() {
+        let nested = Hatch::new(HatchPattern::Solid)
+            .with_loop(square(4.0))
+            .with_loop(square(2.0).translated(Vector::new(1.0, 1.0)));
+        let crossing = Hatch::new(HatchPattern::Solid)
+            .with_loop(square(4.0))
+            .with_loop(square(2.0).translated(Vector::new(3.0, 1.0)));
+
+        assert!(nested.is_simple());
+        assert_eq!(crossing.self_intersections().len(), 2);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    algorithms::Bounded,
+    components::{ConstructionGeometry, DrawingObject, Layer},
+    BoundingBox, DrawingSpace,
+};
+use specs::prelude::*;
+use specs_derive::Component;
+
+/// An empty [`Component`] used to temporarily hide an [`Entity`], regardless
+/// of whether its [`Layer`] is visible.
+///
+/// Hidden entities aren't drawn, picked, or included in the drawing's
+/// extents.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Component)]
+#[storage(NullStorage)]
+pub struct Hidden;
+
+/// Should an entity on `layer` be displayed?
+///
+/// Entities are shown when they aren't [`Hidden`] and their [`Layer`] is
+/// visible. Entities which aren't on a [`Layer`] are treated as visible.
+pub(crate) fn is_visible(
+    hidden: &ReadStorage<'_, Hidden>,
+    layers: &ReadStorage<'_, Layer>,
+    entity: Entity,
+    layer: Entity,
+) -> bool {
+    !hidden.contains(entity)
+        && layers.get(layer).map(|l| l.visible).unwrap_or(true)
+}
+
+/// The area covered by every visible [`DrawingObject`] (e.g. for "zoom
+/// extents"), or [`None`] if there is nothing to see.
+///
+/// Objects which go on forever (e.g. [`crate::XLine`]s) and
+/// [`ConstructionGeometry`] are ignored.
+pub fn visible_extents(world: &World) -> Option<BoundingBox<DrawingSpace>> {
+    let entities = world.entities();
+    let drawing_objects = world.read_storage::<DrawingObject>();
+    let bounding_boxes = world.read_storage::<BoundingBox<DrawingSpace>>();
+    let hidden = world.read_storage::<Hidden>();
+    let layers = world.read_storage::<Layer>();
+    let construction = world.read_storage::<ConstructionGeometry>();
+
+    let bounds = (&entities, &drawing_objects, !&construction)
+        .join()
+        .map(|(ent, obj, _)| (ent, obj))
+        .filter(|(ent, obj)| is_visible(&hidden, &layers, *ent, obj.layer))
+        .map(|(ent, obj)| {
+            bounding_boxes
+                .get(ent)
+                .copied()
+                .unwrap_or_else(|| obj.geometry.bounding_box())
+        })
+        .filter(|b| {
+            b.width().get().is_finite() && b.height().get().is_finite()
+        });
+
+    BoundingBox::around(bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::Geometry, Point, Vector, XLine};
+
+    #[test]
+    fn hidden_entities_dont_count_towards_the_extents() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().with(Layer::default()).build();
+        let hidden_layer = world
+            .create_entity()
+            .with(Layer {
+                visible: false,
+                ..Default::default()
+            })
+            .build();
+        let point = |x, y| DrawingObject {
+            geometry: Geometry::Point(Point::new(x, y)),
+            layer,
+        };
+        world.create_entity().with(point(0.0, 0.0)).build();
+        world.create_entity().with(point(1.0, 2.0)).build();
+        world
+            .create_entity()
+            .with(point(50.0, 50.0))
+            .with(Hidden)
+            .build();
+        world
+            .create_entity()
+            .with(point(10.0, -10.0))
+            .with(ConstructionGeometry)
+            .build();
+        world
+            .create_entity()
+            .with(DrawingObject {
+                layer: hidden_layer,
+                ..point(-50.0, 0.0)
+            })
+            .build();
+        world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::XLine(XLine::new(
+                    Point::zero(),
+                    Vector::new(1.0, 1.0),
+                )),
+                layer,
+            })
+            .build();
+
+        let got = visible_extents(&world).unwrap();
+
+        assert_eq!(got, BoundingBox::new(Point::zero(), Point::new(1.0, 2.0)));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::components::{EntityMap, MapEntities};
+use specs::prelude::*;
+
+/// Attaches an [`Entity`] to a parent (e.g. a dimension to the geometry it
+/// measures), so it is deleted when the parent is.
+///
+/// The [`crate::systems::HierarchyBookkeeping`] system keeps the parent's
+/// [`Children`] up to date and performs the cascading delete.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Parent(pub Entity);
+
+impl Component for Parent {
+    type Storage = FlaggedStorage<Self, DenseVecStorage<Self>>;
+}
+
+impl MapEntities for Parent {
+    fn map_entities(&mut self, map: &EntityMap) { self.0 = map.get(self.0); }
+}
+
+/// The entities which have this [`Entity`] as their [`Parent`], in the order
+/// they were attached.
+///
+/// This is maintained by the [`crate::systems::HierarchyBookkeeping`]
+/// system, so change an entity's [`Parent`] instead of editing it directly.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Children {
+    entities: Vec<Entity>,
+}
+
+impl Children {
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    pub fn len(&self) -> usize { self.entities.len() }
+
+    pub fn is_empty(&self) -> bool { self.entities.is_empty() }
+}
+
+impl From<Vec<Entity>> for Children {
+    fn from(entities: Vec<Entity>) -> Children { Children { entities } }
+}
+
+impl Component for Children {
+    type Storage = FlaggedStorage<Self, HashMapStorage<Self>>;
+}
+
+impl MapEntities for Children {
+    fn map_entities(&mut self, map: &EntityMap) {
+        for child in &mut self.entities {
+            *child = map.get(*child);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use specs::prelude::*;
+use specs_derive::Component;
+use std::fmt::{self, Display, Formatter};
+
+/// A link from an entity to an external document (e.g. a datasheet or
+/// specification).
+///
+/// The URL isn't validated or opened by the library, so it can be anything
+/// the application understands (a web address, a file path, etc.).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Component)]
+#[storage(HashMapStorage)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hyperlink {
+    /// Where the link points.
+    pub url: String,
+    /// Text to show instead of the URL.
+    pub description: Option<String>,
+}
+
+impl Hyperlink {
+    /// Create a new [`Hyperlink`] without a description.
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Hyperlink {
+            url: url.into(),
+            description: None,
+        }
+    }
+
+    /// Add a description.
+    pub fn with_description<S: Into<String>>(self, description: S) -> Self {
+        Hyperlink {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+}
+
+impl Display for Hyperlink {
+    /// Show the description, falling back to the URL.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.description {
+            Some(ref description) => write!(f, "{}", description),
+            None => write!(f, "{}", self.url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{duplicate, DrawingObject, Geometry},
+        Point,
+    };
+
+    #[test]
+    fn display_falls_back_to_the_url() {
+        let link = Hyperlink::new("https://example.com/m8.pdf");
+        assert_eq!(link.url, "https://example.com/m8.pdf");
+        assert_eq!(link.description, None);
+        assert_eq!(link.to_string(), "https://example.com/m8.pdf");
+
+        let link = link.with_description("M8 bolt datasheet");
+        assert_eq!(link.url, "https://example.com/m8.pdf");
+        assert_eq!(link.to_string(), "M8 bolt datasheet");
+    }
+
+    #[test]
+    fn copies_keep_the_hyperlink() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let object = DrawingObject {
+            geometry: Geometry::Point(Point::new(1.0, 1.0)),
+            layer,
+        };
+        let link = Hyperlink::new("https://example.com/m8.pdf")
+            .with_description("Datasheet");
+        let original = world
+            .create_entity()
+            .with(object.clone())
+            .with(link.clone())
+            .build();
+
+        let copy = duplicate(&mut world, original, object);
+
+        let links = world.read_storage::<Hyperlink>();
+        assert_eq!(links.get(copy), Some(&link));
+        assert_eq!(links.get(original), Some(&link));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trip_through_serde() {
+        let links = vec![
+            Hyperlink::new("https://example.com/m8.pdf"),
+            Hyperlink::new("specs/m8.pdf").with_description("Datasheet"),
+        ];
+
+        for link in links {
+            let json = serde_json::to_string(&link).unwrap();
+            let got: Hyperlink = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(got, link);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    Angle, BoundingBox, DrawingSpace, Point, Rectangle, Vector,
+};
+use std::path::PathBuf;
+
+/// A reference to a raster image which is displayed in the drawing (e.g. a
+/// DXF `IMAGE` entity), typically used as an underlay for tracing.
+///
+/// The image's pixels aren't stored in the drawing, it is up to the renderer
+/// to load them from the [`ImageRef::source`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageRef {
+    /// Where the image comes from.
+    pub source: ImageSource,
+    /// Where the image's bottom-left corner is placed.
+    pub insertion_point: Point,
+    /// The image's width in pixels.
+    pub pixel_width: u32,
+    /// The image's height in pixels.
+    pub pixel_height: u32,
+    /// How many drawing units each pixel takes up.
+    pub scale: f64,
+    /// How much the image is rotated (anticlockwise) around the
+    /// [`ImageRef::insertion_point`].
+    pub rotation: Angle,
+}
+
+impl ImageRef {
+    /// Create a new [`ImageRef`] where each pixel is one drawing unit
+    /// across.
+    pub fn new(
+        source: ImageSource,
+        insertion_point: Point,
+        pixel_width: u32,
+        pixel_height: u32,
+    ) -> Self {
+        ImageRef {
+            source,
+            insertion_point,
+            pixel_width,
+            pixel_height,
+            scale: 1.0,
+            rotation: Angle::zero(),
+        }
+    }
+
+    /// How wide the image is in drawing units.
+    pub fn width(&self) -> f64 { self.pixel_width as f64 * self.scale }
+
+    /// How tall the image is in drawing units.
+    pub fn height(&self) -> f64 { self.pixel_height as f64 * self.scale }
+
+    /// The area covered by the image.
+    pub fn outline(&self) -> Rectangle {
+        let (sin, cos) = self.rotation.sin_cos();
+        let half_width = Vector::new(cos, sin) * (self.width() / 2.0);
+        let half_height = Vector::new(-sin, cos) * (self.height() / 2.0);
+
+        Rectangle::new(
+            self.insertion_point + half_width + half_height,
+            self.width(),
+            self.height(),
+            self.rotation,
+        )
+    }
+}
+
+impl Bounded<DrawingSpace> for ImageRef {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        self.outline().bounding_box()
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for ImageRef {
+    /// Images are treated as solid, so any point inside one is closest to
+    /// itself.
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        let outline = self.outline();
+
+        if outline.contains(target) {
+            Closest::One(target)
+        } else {
+            outline.closest_point(target)
+        }
+    }
+}
+
+impl Translate<DrawingSpace> for ImageRef {
+    fn translate(&mut self, displacement: Vector) {
+        self.insertion_point += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for ImageRef {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        if transform.is_reflection() {
+            return Err(TransformError::Reflection);
+        }
+
+        self.insertion_point = transform.transform_point(self.insertion_point);
+        self.scale *= scale;
+        self.rotation = transform.transform_angle(self.rotation);
+
+        Ok(())
+    }
+}
+
+/// Where to find the pixels for an [`ImageRef`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ImageSource {
+    /// An image file on disk.
+    Path(PathBuf),
+    /// Some opaque handle which the renderer knows how to resolve (e.g. an
+    /// index into a texture cache).
+    Handle(u64),
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(path: PathBuf) -> ImageSource { ImageSource::Path(path) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    #[test]
+    fn scaled_and_rotated_image_bounds() {
+        let mut image = ImageRef::new(
+            PathBuf::from("scan.png").into(),
+            Point::new(10.0, 10.0),
+            200,
+            100,
+        );
+        image.scale = 0.1;
+        image.rotation = Angle::frac_pi_2();
+
+        let bounds = image.bounding_box();
+
+        assert!(bounds.min_x().approx_eq(&0.0));
+        assert!(bounds.max_x().approx_eq(&10.0));
+        assert!(bounds.min_y().approx_eq(&10.0));
+        assert!(bounds.max_y().approx_eq(&30.0));
+        assert_eq!(
+            image.closest_point(Point::new(5.0, 20.0)),
+            Closest::One(Point::new(5.0, 20.0))
+        );
+    }
+}
@@ -1,22 +1,47 @@
-use crate::components::Name;
-use specs::prelude::*;
-use specs_derive::Component;
+use crate::components::{Dimension, Name};
+use piet::Color;
+use specs::{prelude::*, world::Index};
+use std::collections::{BTreeMap, HashMap};
 
 /// A logical grouping of data, assembled as though each [`Layer`] were laid out
 /// on transparent acetate overlays.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Component)]
-#[storage(HashMapStorage)]
+///
+/// Every [`crate::components::DrawingObject`] belongs to a [`Layer`], so a
+/// whole class of geometry can be hidden or locked at once.
+#[derive(Debug, Clone)]
 pub struct Layer {
     /// The z-coordinate. Lower z-levels will be drawn above higher z-levels.
     pub z_level: usize,
     /// Should entities on this layer be displayed?
     pub visible: bool,
+    /// Are entities on this layer protected from being modified?
+    pub locked: bool,
+    /// The colour used by entities on this layer which don't specify their
+    /// own.
+    pub colour: Color,
+    /// The name of the [`crate::components::LineType`] used by entities on
+    /// this layer which don't have their own
+    /// [`crate::components::LineTypeRef`].
+    pub line_type: String,
+    /// How thick lines on this layer should be, unless an entity specifies its
+    /// own width. Layers without a line weight use the drawing's default.
+    pub line_weight: Option<Dimension>,
 }
 
 impl Layer {
+    /// The line type used for solid, unbroken lines.
+    pub const CONTINUOUS: &'static str = "Continuous";
+
     pub fn create(builder: EntityBuilder, name: Name, layer: Layer) -> Entity {
         builder.with(layer).with(name).build()
     }
+
+    /// Can entities on this layer be edited?
+    pub fn is_editable(&self) -> bool { !self.locked }
+}
+
+impl Component for Layer {
+    type Storage = FlaggedStorage<Self, HashMapStorage<Self>>;
 }
 
 impl Default for Layer {
@@ -24,6 +49,110 @@ impl Default for Layer {
         Layer {
             z_level: 0,
             visible: true,
+            locked: false,
+            colour: Color::BLACK,
+            line_type: String::from(Layer::CONTINUOUS),
+            line_weight: None,
         }
     }
 }
+
+/// Is `layer` a [`Layer`] which is protected from being modified?
+///
+/// Entities which aren't layers are treated as unlocked.
+pub(crate) fn is_locked(
+    layers: &ReadStorage<'_, Layer>,
+    layer: Entity,
+) -> bool {
+    layers.get(layer).map(|l| l.locked).unwrap_or(false)
+}
+
+/// A global [`Resource`] for looking up a [`Layer`] entity by its [`Name`].
+///
+/// Like DXF, layer names are case-insensitive. The table is kept up to date
+/// by the [`crate::systems::LayerTableBookkeeping`] system.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayerTable {
+    layers: BTreeMap<String, Entity>,
+    names: HashMap<Index, String>,
+}
+
+impl LayerTable {
+    fn key(name: &str) -> String { name.to_uppercase() }
+
+    /// Find the layer with a particular name.
+    pub fn get(&self, name: &str) -> Option<Entity> {
+        self.layers.get(&LayerTable::key(name)).copied()
+    }
+
+    /// Iterate over every layer, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.layers.values().copied()
+    }
+
+    pub fn len(&self) -> usize { self.layers.len() }
+
+    pub fn is_empty(&self) -> bool { self.layers.is_empty() }
+
+    pub(crate) fn insert(&mut self, name: &Name, layer: Entity) {
+        self.remove_by_id(layer.id());
+        let key = LayerTable::key(name.as_str());
+        self.names.insert(layer.id(), key.clone());
+        self.layers.insert(key, layer);
+    }
+
+    pub(crate) fn remove_by_id(&mut self, id: Index) -> Option<Entity> {
+        let key = self.names.remove(&id)?;
+
+        match self.layers.get(&key) {
+            // two layers with the same name may have been added, so only
+            // remove the entry when it's actually for this layer
+            Some(layer) if layer.id() == id => self.layers.remove(&key),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.layers.clear();
+        self.names.clear();
+    }
+}
+
+/// Find the [`Layer`] called `name`, creating a new [`Layer::default()`] if
+/// there isn't one.
+///
+/// ```rust
+/// # use arcs::components::{get_or_create_layer, LayerTable};
+/// # use specs::prelude::*;
+/// let mut world = World::new();
+/// arcs::components::register(&mut world);
+/// world.insert(LayerTable::default());
+///
+/// let dim = get_or_create_layer(&world, "DIM");
+///
+/// assert_eq!(get_or_create_layer(&world, "dim"), dim);
+/// assert_eq!(world.fetch::<LayerTable>().get("Dim"), Some(dim));
+/// ```
+pub fn get_or_create_layer(world: &World, name: &str) -> Entity {
+    let mut table = world.write_resource::<LayerTable>();
+
+    if let Some(layer) = table.get(name) {
+        if world.entities().is_alive(layer) {
+            return layer;
+        }
+    }
+
+    let name = Name::new(name);
+    let layer = world.entities().create();
+    world
+        .write_storage::<Layer>()
+        .insert(layer, Layer::default())
+        .expect("The entity was just created");
+    world
+        .write_storage::<Name>()
+        .insert(layer, name.clone())
+        .expect("The entity was just created");
+    table.insert(&name, layer);
+
+    layer
+}
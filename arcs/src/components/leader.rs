@@ -0,0 +1,184 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    components::{
+        annotation::leader_text, Anchor, AnnotationGraphics, DimensionStyle,
+        Geometry,
+    },
+    BoundingBox, DrawingSpace, Line, Point, Vector,
+};
+
+/// A callout which points at something using a line with an arrowhead (e.g.
+/// a DXF `LEADER` entity).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leader {
+    /// The path the leader follows, starting at the arrowhead.
+    pub vertices: Vec<Point>,
+    /// Text attached to the end of the leader.
+    pub text: Option<String>,
+    /// The point the arrowhead should stay attached to.
+    ///
+    /// The [`crate::systems::SyncAnnotations`] system will move the tip of
+    /// the leader whenever that geometry changes.
+    pub associated_with: Option<Anchor>,
+    /// How the leader should look.
+    pub style: DimensionStyle,
+}
+
+impl Leader {
+    /// Create a new [`Leader`] without any text.
+    pub fn new(vertices: Vec<Point>) -> Self {
+        Leader {
+            vertices,
+            text: None,
+            associated_with: None,
+            style: DimensionStyle::default(),
+        }
+    }
+
+    /// Attach some text to the end of the [`Leader`].
+    pub fn with_text<S: Into<String>>(self, text: S) -> Self {
+        Leader {
+            text: Some(text.into()),
+            ..self
+        }
+    }
+
+    /// Move the tip to follow the geometry it is associated with, returning
+    /// `false` if the point can't be found.
+    pub fn update_from(&mut self, geometry: &Geometry) -> bool {
+        let tip = self
+            .associated_with
+            .and_then(|anchor| anchor.locate(geometry));
+
+        match (tip, self.vertices.first_mut()) {
+            (Some(tip), Some(first)) => {
+                *first = tip;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Where the arrowhead is.
+    pub fn tip(&self) -> Option<Point> { self.vertices.first().copied() }
+
+    /// The end of the [`Leader`] that the text is attached to.
+    pub fn tail(&self) -> Option<Point> { self.vertices.last().copied() }
+
+    /// Break the [`Leader`] down into lines, arrowheads and text.
+    pub fn graphics(&self) -> AnnotationGraphics {
+        let mut graphics = AnnotationGraphics::new();
+
+        graphics.lines.extend(
+            self.vertices
+                .windows(2)
+                .map(|pair| Line::new(pair[0], pair[1])),
+        );
+
+        // the arrow points along the first segment which has a direction
+        let first_direction = self
+            .vertices
+            .windows(2)
+            .map(|pair| pair[0] - pair[1])
+            .find(|direction| direction.square_length() > 0.0);
+
+        if let (Some(tip), Some(direction)) = (self.tip(), first_direction) {
+            graphics.push_arrowhead(tip, direction, self.style.arrow_size);
+        }
+
+        if let (Some(text), Some(tail)) = (&self.text, self.tail()) {
+            let before_tail = self
+                .vertices
+                .iter()
+                .rev()
+                .find(|&&p| p != tail)
+                .copied()
+                .unwrap_or(tail);
+
+            graphics.text.push(leader_text(
+                before_tail,
+                tail,
+                text.clone(),
+                &self.style,
+            ));
+        }
+
+        graphics
+    }
+}
+
+impl Bounded<DrawingSpace> for Leader {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        self.graphics().bounding_box()
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for Leader {
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        self.graphics().closest_point(target)
+    }
+}
+
+impl Translate<DrawingSpace> for Leader {
+    fn translate(&mut self, displacement: Vector) {
+        for vertex in &mut self.vertices {
+            *vertex += displacement;
+        }
+    }
+}
+
+impl Transformable<DrawingSpace> for Leader {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        for vertex in &mut self.vertices {
+            vertex.transform_by(transform)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::HorizontalAlignment;
+
+    #[test]
+    fn arrowhead_points_at_the_first_vertex() {
+        let leader = Leader::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(-5.0, 5.0),
+            Point::new(-10.0, 5.0),
+        ])
+        .with_text("Note 1");
+
+        let graphics = leader.graphics();
+
+        assert_eq!(graphics.lines.len(), 2);
+        assert_eq!(graphics.arrowheads.len(), 1);
+        assert_eq!(graphics.arrowheads[0][0], Point::zero());
+        // the arrowhead's base is back along the first segment
+        assert!(graphics.arrowheads[0][1].x < 0.0);
+        assert_eq!(graphics.text[0].content, "Note 1");
+        assert_eq!(
+            graphics.text[0].horizontal_alignment,
+            HorizontalAlignment::Right
+        );
+    }
+
+    #[test]
+    fn degenerate_leaders_have_no_arrowhead() {
+        let leader = Leader::new(vec![Point::new(1.0, 1.0)]);
+
+        let graphics = leader.graphics();
+
+        assert!(graphics.lines.is_empty());
+        assert!(graphics.arrowheads.is_empty());
+        assert_eq!(leader.bounding_box().area(), 0.0);
+    }
+}
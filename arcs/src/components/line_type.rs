@@ -0,0 +1,276 @@
+use crate::components::Layer;
+use specs::prelude::*;
+use specs_derive::Component;
+use std::collections::BTreeMap;
+
+/// One element in a [`LineType`]'s repeating pattern.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DashElement {
+    /// A visible stroke, this many drawing units long.
+    Dash(f64),
+    /// A break in the line, this many drawing units long.
+    Gap(f64),
+    /// A single dot.
+    Dot,
+}
+
+/// A named dash pattern (e.g. a DXF `LTYPE` table entry).
+///
+/// The pattern repeats along the length of each line, with every element
+/// multiplied by [`LineType::scale`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineType {
+    /// The name entities and layers use to refer to this [`LineType`].
+    pub name: String,
+    /// The dashes, gaps and dots which make up a single repetition.
+    pub pattern: Vec<DashElement>,
+    /// How much to stretch the pattern by.
+    pub scale: f64,
+}
+
+impl LineType {
+    /// Create a new [`LineType`] with a scale of `1.0`.
+    pub fn new<S: Into<String>>(name: S, pattern: Vec<DashElement>) -> Self {
+        LineType {
+            name: name.into(),
+            pattern,
+            scale: 1.0,
+        }
+    }
+
+    /// A solid, unbroken line.
+    pub fn continuous() -> Self { LineType::new(Layer::CONTINUOUS, Vec::new()) }
+
+    /// Use a different [`LineType::scale`].
+    pub fn with_scale(self, scale: f64) -> Self { LineType { scale, ..self } }
+
+    /// Does this [`LineType`] draw an unbroken line?
+    pub fn is_continuous(&self) -> bool {
+        self.pattern.iter().all(|element| match *element {
+            DashElement::Gap(_) => false,
+            _ => true,
+        })
+    }
+
+    /// The length of a single repetition of the pattern, in drawing units.
+    pub fn pattern_length(&self) -> f64 {
+        self.pattern
+            .iter()
+            .map(|element| match *element {
+                DashElement::Dash(length) | DashElement::Gap(length) => length,
+                DashElement::Dot => 0.0,
+            })
+            .sum::<f64>()
+            * self.scale
+    }
+
+    /// The scaled pattern as alternating "on" and "off" lengths, in drawing
+    /// units, starting with an "on" length (like the dash arrays used by SVG,
+    /// PDF, and most 2D graphics libraries).
+    ///
+    /// Dots have no length, so renderers should use round line caps to make
+    /// them visible. Continuous line types have no dash array.
+    ///
+    /// ```rust
+    /// # use arcs::components::{DashElement, LineType};
+    /// let dash_dot = LineType::new(
+    ///     "DashDot",
+    ///     vec![
+    ///         DashElement::Dash(2.0),
+    ///         DashElement::Gap(0.5),
+    ///         DashElement::Dot,
+    ///         DashElement::Gap(0.5),
+    ///     ],
+    /// )
+    /// .with_scale(2.0);
+    ///
+    /// assert_eq!(dash_dot.dash_array(), Some(vec![4.0, 1.0, 0.0, 1.0]));
+    /// ```
+    pub fn dash_array(&self) -> Option<Vec<f64>> {
+        if self.is_continuous() {
+            return None;
+        }
+
+        // (is_on, length) pairs, merging neighbours of the same kind
+        let mut runs: Vec<(bool, f64)> = Vec::new();
+
+        for element in &self.pattern {
+            let (on, length) = match *element {
+                DashElement::Dash(length) => (true, length),
+                DashElement::Gap(length) => (false, length),
+                DashElement::Dot => (true, 0.0),
+            };
+
+            match runs.last_mut() {
+                Some(last) if last.0 == on => last.1 += length,
+                _ => runs.push((on, length)),
+            }
+        }
+
+        if runs.first().map(|r| !r.0).unwrap_or(false) {
+            runs.insert(0, (true, 0.0));
+        }
+        if runs.len() % 2 == 1 {
+            runs.push((false, 0.0));
+        }
+
+        Some(runs.into_iter().map(|(_, len)| len * self.scale).collect())
+    }
+}
+
+/// A global [`Resource`] containing every [`LineType`] in a drawing, looked
+/// up by name.
+///
+/// Like DXF, names are case-insensitive. The default table contains a handful
+/// of common line types, including [`Layer::CONTINUOUS`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineTypeTable {
+    line_types: BTreeMap<String, LineType>,
+}
+
+impl LineTypeTable {
+    /// Create a [`LineTypeTable`] containing only the continuous line type.
+    pub fn empty() -> Self {
+        let mut table = LineTypeTable {
+            line_types: BTreeMap::new(),
+        };
+        table.insert(LineType::continuous());
+
+        table
+    }
+
+    fn key(name: &str) -> String { name.to_uppercase() }
+
+    /// Look up a [`LineType`] by name.
+    pub fn get(&self, name: &str) -> Option<&LineType> {
+        self.line_types.get(&LineTypeTable::key(name))
+    }
+
+    /// Add a [`LineType`], returning the one it replaced (if any).
+    pub fn insert(&mut self, line_type: LineType) -> Option<LineType> {
+        self.line_types
+            .insert(LineTypeTable::key(&line_type.name), line_type)
+    }
+
+    /// Remove a [`LineType`].
+    pub fn remove(&mut self, name: &str) -> Option<LineType> {
+        self.line_types.remove(&LineTypeTable::key(name))
+    }
+
+    /// Iterate over every [`LineType`], in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = &LineType> + '_ {
+        self.line_types.values()
+    }
+
+    pub fn len(&self) -> usize { self.line_types.len() }
+
+    pub fn is_empty(&self) -> bool { self.line_types.is_empty() }
+
+    /// Find the [`LineType`] an entity should be drawn with, falling back to
+    /// its [`Layer`]'s [`Layer::line_type`] when the entity doesn't have a
+    /// [`LineTypeRef`].
+    ///
+    /// This returns [`None`] when the line type isn't in the table, in which
+    /// case the entity should be drawn using a continuous line.
+    pub fn resolve(
+        &self,
+        line_type: Option<&LineTypeRef>,
+        layer: Option<&Layer>,
+    ) -> Option<&LineType> {
+        let name = line_type
+            .map(|r| r.0.as_str())
+            .or_else(|| layer.map(|l| l.line_type.as_str()))
+            .unwrap_or(Layer::CONTINUOUS);
+
+        self.get(name)
+    }
+}
+
+impl Default for LineTypeTable {
+    fn default() -> Self {
+        let mut table = LineTypeTable::empty();
+
+        table.insert(LineType::new(
+            "Dashed",
+            vec![DashElement::Dash(0.5), DashElement::Gap(0.25)],
+        ));
+        table.insert(LineType::new(
+            "Hidden",
+            vec![DashElement::Dash(0.25), DashElement::Gap(0.125)],
+        ));
+        table.insert(LineType::new(
+            "Center",
+            vec![
+                DashElement::Dash(1.25),
+                DashElement::Gap(0.25),
+                DashElement::Dash(0.25),
+                DashElement::Gap(0.25),
+            ],
+        ));
+        table.insert(LineType::new(
+            "Dot",
+            vec![DashElement::Dot, DashElement::Gap(0.25)],
+        ));
+
+        table
+    }
+}
+
+/// Draw an entity using a particular [`LineType`] from the [`LineTypeTable`]
+/// instead of its [`Layer`]'s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Component)]
+#[storage(HashMapStorage)]
+pub struct LineTypeRef(pub String);
+
+impl LineTypeRef {
+    pub fn new<S: Into<String>>(name: S) -> Self { LineTypeRef(name.into()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leading_gaps_start_with_an_empty_dash() {
+        let line_type = LineType::new(
+            "Gappy",
+            vec![
+                DashElement::Gap(1.0),
+                DashElement::Dash(2.0),
+                DashElement::Dash(1.0),
+            ],
+        );
+
+        assert_eq!(line_type.dash_array(), Some(vec![0.0, 1.0, 3.0, 0.0]));
+        assert_eq!(line_type.pattern_length(), 4.0);
+    }
+
+    #[test]
+    fn lines_without_gaps_are_continuous() {
+        let line_type = LineType::new("Solid", vec![DashElement::Dash(1.0)]);
+
+        assert!(line_type.is_continuous());
+        assert_eq!(line_type.dash_array(), None);
+    }
+
+    #[test]
+    fn entities_override_their_layer() {
+        let table = LineTypeTable::default();
+        let layer = Layer {
+            line_type: String::from("dashed"),
+            ..Default::default()
+        };
+
+        let from_layer = table.resolve(None, Some(&layer)).unwrap();
+        let overridden = table
+            .resolve(Some(&LineTypeRef::new("HIDDEN")), Some(&layer))
+            .unwrap();
+
+        assert_eq!(from_layer.name, "Dashed");
+        assert_eq!(overridden.name, "Hidden");
+        assert!(table.resolve(None, None).unwrap().is_continuous());
+        assert!(table
+            .resolve(Some(&LineTypeRef::new("Wavy")), None)
+            .is_none());
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    components::{
+        annotation::dimension_text, measurement_text, Anchor,
+        AnnotationGraphics, DimensionStyle, Geometry,
+    },
+    Angle, BoundingBox, DrawingSpace, Line, Point, Vector,
+};
+
+/// A dimension showing the distance between two points (e.g. a DXF
+/// `DIMENSION` entity with the aligned or rotated type).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearDimension {
+    /// The first definition point.
+    pub from: Point,
+    /// The second definition point.
+    pub to: Point,
+    /// How far the dimension line is from [`LinearDimension::from`],
+    /// measured perpendicular to the dimension line. Positive offsets are to
+    /// the left when looking along the dimension line.
+    pub offset: f64,
+    /// Which direction the distance is measured in.
+    pub direction: MeasurementDirection,
+    /// The points [`LinearDimension::from`] and [`LinearDimension::to`]
+    /// should follow.
+    ///
+    /// The [`crate::systems::SyncAnnotations`] system will re-measure the
+    /// dimension whenever that geometry changes.
+    pub associated_with: Option<(Anchor, Anchor)>,
+    /// Text to show instead of the measured value, where `<>` will be
+    /// replaced with the measurement.
+    pub text_override: Option<String>,
+    /// How the dimension should look.
+    pub style: DimensionStyle,
+}
+
+impl LinearDimension {
+    /// Create a new [`LinearDimension`] which measures the direct distance
+    /// between two points.
+    pub fn aligned(from: Point, to: Point, offset: f64) -> Self {
+        LinearDimension {
+            from,
+            to,
+            offset,
+            direction: MeasurementDirection::Aligned,
+            associated_with: None,
+            text_override: None,
+            style: DimensionStyle::default(),
+        }
+    }
+
+    /// Create a new [`LinearDimension`] which only measures distance along
+    /// a particular angle (e.g. [`Angle::zero()`] for a horizontal
+    /// dimension).
+    pub fn rotated(from: Point, to: Point, offset: f64, angle: Angle) -> Self {
+        LinearDimension {
+            direction: MeasurementDirection::Fixed(angle),
+            ..LinearDimension::aligned(from, to, offset)
+        }
+    }
+
+    /// Move the definition points to match the geometry they are associated
+    /// with, returning `false` if either point can't be found.
+    pub fn update_from(&mut self, from: &Geometry, to: &Geometry) -> bool {
+        let (from_anchor, to_anchor) = match self.associated_with {
+            Some(anchors) => anchors,
+            None => return false,
+        };
+
+        match (from_anchor.locate(from), to_anchor.locate(to)) {
+            (Some(from), Some(to)) => {
+                self.from = from;
+                self.to = to;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// A unit vector pointing along the dimension line.
+    pub fn dimension_direction(&self) -> Vector {
+        match self.direction {
+            MeasurementDirection::Aligned => {
+                let delta = self.to - self.from;
+
+                if delta.square_length() == 0.0 {
+                    Vector::new(1.0, 0.0)
+                } else {
+                    delta.normalize()
+                }
+            },
+            MeasurementDirection::Fixed(angle) => {
+                let (sin, cos) = angle.sin_cos();
+                Vector::new(cos, sin)
+            },
+        }
+    }
+
+    /// The measured distance.
+    pub fn measurement(&self) -> f64 {
+        Vector::dot(self.to - self.from, self.dimension_direction()).abs()
+    }
+
+    /// The text to display.
+    pub fn text(&self) -> String {
+        measurement_text(
+            self.measurement(),
+            self.style.precision,
+            self.text_override.as_deref(),
+        )
+    }
+
+    /// The dimension line, running between the feet of the two extension
+    /// lines.
+    pub fn dimension_line(&self) -> Line {
+        let direction = self.dimension_direction();
+        let normal = Vector::new(-direction.y, direction.x);
+
+        let start = self.from + normal * self.offset;
+        let along = Vector::dot(self.to - self.from, direction);
+
+        Line::new(start, start + direction * along)
+    }
+
+    /// Break the [`LinearDimension`] down into lines, arrowheads and text.
+    pub fn graphics(&self) -> AnnotationGraphics {
+        let mut graphics = AnnotationGraphics::new();
+        let style = &self.style;
+        let dimension_line = self.dimension_line();
+
+        for &(point, foot) in &[
+            (self.from, dimension_line.start),
+            (self.to, dimension_line.end),
+        ] {
+            let delta = foot - point;
+
+            if delta.length() > style.extension_offset {
+                let direction = delta.normalize();
+                graphics.lines.push(Line::new(
+                    point + direction * style.extension_offset,
+                    foot + direction * style.extension_overshoot,
+                ));
+            }
+        }
+
+        graphics.lines.push(dimension_line);
+
+        let direction = dimension_line.end - dimension_line.start;
+        if direction.square_length() > 0.0 {
+            graphics.push_arrowhead(
+                dimension_line.start,
+                -direction,
+                style.arrow_size,
+            );
+            graphics.push_arrowhead(
+                dimension_line.end,
+                direction,
+                style.arrow_size,
+            );
+        }
+
+        graphics.text.push(dimension_text(
+            dimension_line.start.lerp(dimension_line.end, 0.5),
+            self.dimension_direction(),
+            self.text(),
+            style,
+        ));
+
+        graphics
+    }
+}
+
+impl Bounded<DrawingSpace> for LinearDimension {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        self.graphics().bounding_box()
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for LinearDimension {
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        self.graphics().closest_point(target)
+    }
+}
+
+impl Translate<DrawingSpace> for LinearDimension {
+    fn translate(&mut self, displacement: Vector) {
+        self.from += displacement;
+        self.to += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for LinearDimension {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        self.from = transform.transform_point(self.from);
+        self.to = transform.transform_point(self.to);
+        // mirroring swaps left and right
+        self.offset *= if transform.is_reflection() {
+            -scale
+        } else {
+            scale
+        };
+        if let MeasurementDirection::Fixed(ref mut angle) = self.direction {
+            *angle = transform.transform_angle(*angle);
+        }
+
+        Ok(())
+    }
+}
+
+/// The direction a [`LinearDimension`] measures along.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MeasurementDirection {
+    /// Measure the direct distance between the two definition points.
+    Aligned,
+    /// Only measure the distance along a particular direction.
+    Fixed(Angle),
+}
+
+impl Default for MeasurementDirection {
+    fn default() -> Self { MeasurementDirection::Aligned }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    #[test]
+    fn horizontal_dimension_of_a_diagonal() {
+        let dimension = LinearDimension::rotated(
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 4.0),
+            -2.0,
+            Angle::zero(),
+        );
+
+        assert_eq!(dimension.measurement(), 3.0);
+        assert_eq!(dimension.text(), "3.00");
+        assert_eq!(
+            dimension.dimension_line(),
+            Line::new(Point::new(0.0, -2.0), Point::new(3.0, -2.0))
+        );
+    }
+
+    #[test]
+    fn aligned_dimension_graphics() {
+        let mut dimension = LinearDimension::aligned(
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 0.0),
+            -5.0,
+        );
+        dimension.text_override = Some("<> mm".to_string());
+
+        let graphics = dimension.graphics();
+
+        // measuring right-to-left, so "right" of the line is up the page
+        assert_eq!(
+            dimension.dimension_line(),
+            Line::new(Point::new(10.0, 5.0), Point::new(0.0, 5.0))
+        );
+        assert_eq!(graphics.lines.len(), 3);
+        assert_eq!(graphics.arrowheads.len(), 2);
+        assert_eq!(graphics.arrowheads[0][0], Point::new(10.0, 5.0));
+
+        // the text still reads left-to-right, above the dimension line
+        let text = &graphics.text[0];
+        assert_eq!(text.content, "10.00 mm");
+        assert!(text.rotation.radians.approx_eq(&0.0));
+        assert!(text.insertion_point.y > 5.0);
+    }
+}
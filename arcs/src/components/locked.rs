@@ -0,0 +1,72 @@
+use crate::algorithms::TransformError;
+use specs::prelude::*;
+use specs_derive::Component;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// An empty [`Component`] used to protect a single [`Entity`] (e.g. a piece
+/// of reference geometry) from being modified, regardless of whether its
+/// [`crate::components::Layer`] is locked.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Component)]
+#[storage(NullStorage)]
+pub struct Locked;
+
+/// Why an edit was refused.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EditError {
+    /// The [`Entity`] is [`Locked`].
+    Locked(Entity),
+    /// The geometry couldn't be transformed.
+    Transform(TransformError),
+}
+
+impl From<TransformError> for EditError {
+    fn from(other: TransformError) -> Self { EditError::Transform(other) }
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::Locked(entity) => {
+                write!(f, "Entity {} is locked", entity.id())
+            },
+            EditError::Transform(_) => write!(f, "Unable to transform"),
+        }
+    }
+}
+
+impl Error for EditError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EditError::Locked(_) => None,
+            EditError::Transform(inner) => Some(inner),
+        }
+    }
+}
+
+/// Make sure `entity` may be modified.
+pub(crate) fn ensure_unlocked(
+    locked: &ReadStorage<'_, Locked>,
+    entity: Entity,
+) -> Result<(), EditError> {
+    if locked.contains(entity) {
+        Err(EditError::Locked(entity))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_errors_are_the_source() {
+        let err = EditError::from(TransformError::Reflection);
+
+        assert_eq!(err, EditError::Transform(TransformError::Reflection));
+        assert!(err.source().is_some());
+    }
+}
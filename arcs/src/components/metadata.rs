@@ -0,0 +1,267 @@
+use crate::Point;
+use specs::prelude::*;
+use specs_derive::Component;
+use std::collections::{btree_map, BTreeMap};
+
+/// Arbitrary application data attached to an entity (e.g. DXF extended
+/// data), so things like part numbers and costs can be kept alongside the
+/// geometry.
+///
+/// Values are looked up by key. The library itself never reads or writes
+/// [`Metadata`], it only makes sure copies of an entity keep their metadata
+/// and (with the `serde` feature) that it can be saved and loaded.
+///
+/// # Examples
+///
+/// ```rust
+/// # use arcs::components::{Metadata, MetadataValue};
+/// let mut metadata = Metadata::default();
+/// metadata.insert("part-number", "M8-BOLT-40");
+/// metadata.insert("cost", 0.35);
+/// metadata.insert("in-stock", true);
+///
+/// assert_eq!(metadata.get_str("part-number"), Some("M8-BOLT-40"));
+/// assert_eq!(metadata.get("cost"), Some(&MetadataValue::Real(0.35)));
+/// assert_eq!(metadata.get("in-stock").and_then(MetadataValue::as_bool), Some(true));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Component)]
+#[storage(HashMapStorage)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Metadata {
+    values: BTreeMap<String, MetadataValue>,
+}
+
+impl Metadata {
+    pub fn get(&self, key: &str) -> Option<&MetadataValue> {
+        self.values.get(key)
+    }
+
+    /// Get a value, but only if it is a string.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(MetadataValue::as_str)
+    }
+
+    /// Set a value, returning the previous one (if any).
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Option<MetadataValue>
+    where
+        K: Into<String>,
+        V: Into<MetadataValue>,
+    {
+        self.values.insert(key.into(), value.into())
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<MetadataValue> {
+        self.values.remove(key)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Iterate over every key and its value, in alphabetical order.
+    pub fn iter(&self) -> btree_map::Iter<'_, String, MetadataValue> {
+        self.values.iter()
+    }
+
+    pub fn len(&self) -> usize { self.values.len() }
+
+    pub fn is_empty(&self) -> bool { self.values.is_empty() }
+}
+
+impl<K, V> std::iter::FromIterator<(K, V)> for Metadata
+where
+    K: Into<String>,
+    V: Into<MetadataValue>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Metadata {
+            values: iter
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+}
+
+/// A single value in an entity's [`Metadata`], using the same types as DXF
+/// extended data.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetadataValue {
+    String(String),
+    Integer(i64),
+    Real(f64),
+    Boolean(bool),
+    Point(#[cfg_attr(feature = "serde", serde(with = "point_as_tuple"))] Point),
+}
+
+impl MetadataValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MetadataValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match *self {
+            MetadataValue::Integer(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a number, converting integers if necessary.
+    pub fn as_real(&self) -> Option<f64> {
+        match *self {
+            MetadataValue::Real(r) => Some(r),
+            MetadataValue::Integer(i) => Some(i as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            MetadataValue::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_point(&self) -> Option<Point> {
+        match *self {
+            MetadataValue::Point(p) => Some(p),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for MetadataValue {
+    fn from(other: String) -> MetadataValue { MetadataValue::String(other) }
+}
+
+impl<'a> From<&'a str> for MetadataValue {
+    fn from(other: &'a str) -> MetadataValue {
+        MetadataValue::String(other.to_string())
+    }
+}
+
+impl From<i64> for MetadataValue {
+    fn from(other: i64) -> MetadataValue { MetadataValue::Integer(other) }
+}
+
+impl From<f64> for MetadataValue {
+    fn from(other: f64) -> MetadataValue { MetadataValue::Real(other) }
+}
+
+impl From<bool> for MetadataValue {
+    fn from(other: bool) -> MetadataValue { MetadataValue::Boolean(other) }
+}
+
+impl From<Point> for MetadataValue {
+    fn from(other: Point) -> MetadataValue { MetadataValue::Point(other) }
+}
+
+/// Save a [`Point`] as an `(x, y)` tuple, the same way `euclid` does when its
+/// `serde` feature is enabled.
+#[cfg(feature = "serde")]
+mod point_as_tuple {
+    use crate::Point;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) fn serialize<S>(
+        point: &Point,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (point.x, point.y).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (x, y) = Deserialize::deserialize(deserializer)?;
+        Ok(Point::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_remove_values() {
+        let mut metadata = Metadata::default();
+        assert!(metadata.is_empty());
+
+        assert_eq!(metadata.insert("part-number", "M8"), None);
+        assert_eq!(
+            metadata.insert("part-number", "M10"),
+            Some(MetadataValue::from("M8"))
+        );
+        metadata.insert("cost", 0.35);
+        assert_eq!(metadata.len(), 2);
+        assert!(metadata.contains_key("cost"));
+
+        assert_eq!(metadata.remove("cost"), Some(MetadataValue::Real(0.35)));
+        assert_eq!(metadata.remove("cost"), None);
+        assert!(!metadata.contains_key("cost"));
+        let keys: Vec<_> = metadata.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["part-number"]);
+    }
+
+    #[test]
+    fn typed_getters() {
+        let metadata: Metadata = vec![
+            ("name", MetadataValue::from("bolt")),
+            ("count", MetadataValue::from(3_i64)),
+            ("cost", MetadataValue::from(0.35)),
+            ("in-stock", MetadataValue::from(true)),
+            ("anchor", MetadataValue::from(Point::new(1.0, 2.0))),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(metadata.get_str("name"), Some("bolt"));
+        assert_eq!(metadata.get_str("count"), None);
+        let count = metadata.get("count").unwrap();
+        assert_eq!(count.as_integer(), Some(3));
+        // integers are converted, but nothing else is
+        assert_eq!(count.as_real(), Some(3.0));
+        assert_eq!(metadata.get("cost").unwrap().as_real(), Some(0.35));
+        assert_eq!(metadata.get("cost").unwrap().as_integer(), None);
+        assert_eq!(metadata.get("in-stock").unwrap().as_bool(), Some(true));
+        assert_eq!(metadata.get("name").unwrap().as_bool(), None);
+        assert_eq!(
+            metadata.get("anchor").unwrap().as_point(),
+            Some(Point::new(1.0, 2.0))
+        );
+        assert_eq!(metadata.get("missing"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trip_through_serde() {
+        let metadata: Metadata = vec![
+            ("name", MetadataValue::from("bolt")),
+            ("count", MetadataValue::from(3_i64)),
+            ("cost", MetadataValue::from(0.35)),
+            ("in-stock", MetadataValue::from(true)),
+            ("anchor", MetadataValue::from(Point::new(1.0, 2.0))),
+        ]
+        .into_iter()
+        .collect();
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let got: Metadata = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(got, metadata);
+    }
+}
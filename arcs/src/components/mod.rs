@@ -1,11 +1,43 @@
 //! Common components used by the `arcs` CAD library.
 
+mod angular_dimension;
+mod annotation;
+mod audit;
+mod block;
+mod colour;
+mod constraint;
+mod construction;
+mod deleted;
+mod derived;
 mod dimension;
+mod dirty_regions;
+mod draw_order;
+mod drawing_event;
 mod drawing_object;
+mod group;
+mod hatch;
+mod hidden;
+mod hierarchy;
+mod hyperlink;
+mod image_ref;
 mod layer;
+mod leader;
+mod line_type;
+mod linear_dimension;
+mod locked;
+mod metadata;
+mod mtext;
 mod name;
+mod radial_dimension;
 mod selected;
+mod snapshot;
+mod spatial_index;
+mod statistics;
+mod style_resolution;
 mod styles;
+mod text;
+mod text_style;
+mod undo;
 mod viewport;
 mod vtable;
 
@@ -13,17 +45,70 @@ mod vtable;
 // mod spatial_entity;
 // pub use spatial_entity::{Space, SpatialEntity};
 
+pub use angular_dimension::AngularDimension;
+pub use annotation::{
+    measurement_text, Anchor, AnnotationGraphics, DimensionStyle,
+};
+pub use audit::{audit, repair, AuditIssue, AuditReport, ReferenceKind};
+pub use block::{BlockDefinition, BlockTable, Insert};
+pub use colour::Colour;
+pub use constraint::{Constraint, ConstraintPoint, ConstraintStatus, Handle};
+pub use construction::ConstructionGeometry;
+pub use deleted::{soft_delete, Deleted};
+pub use derived::{AssociativeOffset, DependencyCycle, DependencyGraph};
 pub use dimension::Dimension;
-pub use drawing_object::{DrawingObject, Geometry};
-pub use layer::Layer;
-pub use name::{Name, NameTable};
-pub use selected::Selected;
-pub use styles::{LineStyle, PointStyle, WindowStyle};
+pub use dirty_regions::DirtyRegions;
+pub use draw_order::{bring_to_front, send_to_back, DrawOrder};
+pub use drawing_event::DrawingEvent;
+pub use drawing_object::{
+    delete_selected, duplicate, mirror_selected, polar_array,
+    rectangular_array, rotate_selected, scale_selected, translate_selected,
+    DrawingObject, Geometry,
+};
+pub use group::{
+    create_group, dissolve_group, select_group, Group, GroupMember,
+};
+pub use hatch::{
+    boundary_from_geometry, Hatch, HatchLine, HatchLoop, HatchPattern,
+};
+pub(crate) use hidden::is_visible;
+pub use hidden::{visible_extents, Hidden};
+pub use hierarchy::{Children, Parent};
+pub use hyperlink::Hyperlink;
+pub use image_ref::{ImageRef, ImageSource};
+pub(crate) use layer::is_locked;
+pub use layer::{get_or_create_layer, Layer, LayerTable};
+pub use leader::Leader;
+pub use line_type::{DashElement, LineType, LineTypeRef, LineTypeTable};
+pub use linear_dimension::{LinearDimension, MeasurementDirection};
+pub(crate) use locked::ensure_unlocked;
+pub use locked::{EditError, Locked};
+pub use metadata::{Metadata, MetadataValue};
+pub use mtext::{parse_paragraphs, MText, MTextLine, TextFormatting, TextSpan};
+#[cfg(feature = "serde")]
+pub use name::SavedNameTable;
+pub use name::{
+    rename, DuplicateNamePolicy, InvalidName, MissingNames, Name, NameConflict,
+    NameEvent, NameGenerator, NameRules, NameTable, RenameError,
+};
+pub use radial_dimension::{DiameterDimension, RadialDimension};
+pub use selected::{clear_selection, deselect, select, Selected, SelectionSet};
+pub use snapshot::{restore, snapshot, DrawingSnapshot, SnapshotVisitor};
+pub(crate) use snapshot::{EntityMap, MapEntities};
+pub use spatial_index::SpatialIndex;
+pub use statistics::Statistics;
+pub use style_resolution::{
+    resolved_style, ResolvedStyle, StyleDefaults, StyleSources,
+};
+pub use styles::{LineStyle, LineWeight, PointShape, PointStyle, WindowStyle};
+pub use text::{HorizontalAlignment, Text, VerticalAlignment};
+pub use text_style::{TextStyle, TextStyleRef, TextStyleTable};
+pub use undo::UndoLog;
 pub use viewport::Viewport;
 pub(crate) use vtable::ComponentVtable;
 
-use specs::World;
 use crate::DrawingSpace;
+use specs::World;
 
 /// Get an iterator over the [`ComponentVtable`] for all known
 /// [`specs::Component`] types.
@@ -31,15 +116,37 @@ pub(crate) fn known_components(
 ) -> impl Iterator<Item = &'static ComponentVtable> + 'static {
     lazy_static::lazy_static! {
         static ref VTABLES: Vec<ComponentVtable> = vec![
-            ComponentVtable::for_type::<arcs_core::BoundingBox<DrawingSpace>>(),
-            ComponentVtable::for_type::<DrawingObject>(),
-            ComponentVtable::for_type::<Layer>(),
-            ComponentVtable::for_type::<Name>(),
+            ComponentVtable::for_type::<arcs_core::BoundingBox<DrawingSpace>>()
+                .not_duplicated(),
+            ComponentVtable::for_type_with_entities::<DrawingObject>()
+                .not_duplicated(),
+            ComponentVtable::for_type::<Layer>().not_duplicated(),
+            ComponentVtable::for_type::<Name>().not_duplicated(),
             ComponentVtable::for_type::<LineStyle>(),
+            ComponentVtable::for_type::<LineTypeRef>(),
+            ComponentVtable::for_type::<LineWeight>(),
+            ComponentVtable::for_type::<Colour>(),
+            ComponentVtable::for_type::<TextStyleRef>(),
+            ComponentVtable::for_type_with_entities::<Group>().not_duplicated(),
+            ComponentVtable::for_type_with_entities::<GroupMember>(),
+            ComponentVtable::for_type::<Metadata>(),
+            ComponentVtable::for_type::<Hidden>(),
+            ComponentVtable::for_type::<DrawOrder>(),
+            ComponentVtable::for_type_with_entities::<Parent>().not_duplicated(),
+            ComponentVtable::for_type_with_entities::<Children>()
+                .not_duplicated(),
+            ComponentVtable::for_type::<Hyperlink>(),
+            ComponentVtable::for_type::<Locked>(),
+            ComponentVtable::for_type::<ConstructionGeometry>(),
+            ComponentVtable::for_type::<Deleted>().not_duplicated(),
             ComponentVtable::for_type::<PointStyle>(),
-            ComponentVtable::for_type::<Selected>(),
-            ComponentVtable::for_type::<WindowStyle>(),
-            ComponentVtable::for_type::<Viewport>(),
+            ComponentVtable::for_type::<Selected>().not_duplicated(),
+            ComponentVtable::for_type::<WindowStyle>().not_duplicated(),
+            ComponentVtable::for_type::<Viewport>().not_duplicated(),
+            ComponentVtable::for_type_with_entities::<Constraint>()
+                .not_duplicated(),
+            ComponentVtable::for_type_with_entities::<AssociativeOffset>()
+                .not_duplicated(),
         ];
     }
 
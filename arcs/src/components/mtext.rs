@@ -0,0 +1,483 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    components::{
+        text::readable_orientation, HorizontalAlignment, Text,
+        VerticalAlignment,
+    },
+    Angle, BoundingBox, DrawingSpace, Point, Vector,
+};
+
+/// A block of text which may span multiple lines (e.g. a DXF `MTEXT`
+/// entity).
+///
+/// The [`MText::content`] may contain a subset of the DXF inline formatting
+/// codes, see [`parse_paragraphs()`] for the ones which are understood.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MText {
+    /// The point the text is anchored to.
+    pub insertion_point: Point,
+    /// The height of a capital letter.
+    pub height: f64,
+    /// How much the text is rotated (anticlockwise) around
+    /// [`MText::insertion_point`].
+    pub rotation: Angle,
+    /// The width lines will be wrapped at, if any.
+    pub width: Option<f64>,
+    /// A multiplier for the default distance between lines (`5/3` of
+    /// [`MText::height`]).
+    pub line_spacing: f64,
+    /// How each line is aligned horizontally relative to the
+    /// [`MText::insertion_point`].
+    pub horizontal_alignment: HorizontalAlignment,
+    /// How the block of text is aligned vertically relative to the
+    /// [`MText::insertion_point`].
+    ///
+    /// [`VerticalAlignment::Baseline`] and [`VerticalAlignment::Bottom`]
+    /// both place the last line's baseline on the insertion point.
+    pub vertical_alignment: VerticalAlignment,
+    /// The text to display, possibly containing formatting codes.
+    pub content: String,
+}
+
+impl MText {
+    /// Create a new, unwrapped [`MText`] hanging down from its insertion
+    /// point.
+    pub fn new<S: Into<String>>(
+        insertion_point: Point,
+        height: f64,
+        content: S,
+    ) -> Self {
+        MText {
+            insertion_point,
+            height,
+            rotation: Angle::zero(),
+            width: None,
+            line_spacing: 1.0,
+            horizontal_alignment: HorizontalAlignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
+            content: content.into(),
+        }
+    }
+
+    /// The distance between the baselines of consecutive lines.
+    pub fn line_pitch(&self) -> f64 {
+        self.height * 5.0 / 3.0 * self.line_spacing
+    }
+
+    /// Break the text into lines and figure out where each one goes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arcs::{components::MText, Point};
+    ///
+    /// let mut text = MText::new(Point::zero(), 1.0, "The quick brown fox");
+    /// text.width = Some(6.0);
+    ///
+    /// let lines: Vec<_> = text.layout().iter().map(|l| l.text()).collect();
+    ///
+    /// assert_eq!(lines, vec!["The quick", "brown fox"]);
+    /// ```
+    pub fn layout(&self) -> Vec<MTextLine> {
+        let max_chars = self
+            .width
+            .map(|w| (w / (self.height * Text::WIDTH_FACTOR)).floor() as usize);
+
+        let lines: Vec<Vec<TextSpan>> = parse_paragraphs(&self.content)
+            .into_iter()
+            .flat_map(|paragraph| wrap(paragraph, max_chars))
+            .collect();
+
+        let pitch = self.line_pitch();
+        let block_height = (lines.len().max(1) - 1) as f64 * pitch;
+        let first_baseline = match self.vertical_alignment {
+            VerticalAlignment::Top => -self.height,
+            VerticalAlignment::Middle => (block_height - self.height) / 2.0,
+            VerticalAlignment::Bottom | VerticalAlignment::Baseline => {
+                block_height
+            },
+        };
+
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, spans)| {
+                let baseline = first_baseline - i as f64 * pitch;
+                self.lay_out_line(spans, baseline)
+            })
+            .collect()
+    }
+
+    fn lay_out_line(&self, spans: Vec<TextSpan>, baseline: f64) -> MTextLine {
+        let chars: usize = spans.iter().map(|s| s.text.chars().count()).sum();
+        let width = chars as f64 * self.height * Text::WIDTH_FACTOR;
+        let left = match self.horizontal_alignment {
+            HorizontalAlignment::Left => 0.0,
+            HorizontalAlignment::Centre => -width / 2.0,
+            HorizontalAlignment::Right => -width,
+        };
+        let bottom = baseline - self.height * Text::DESCENT_FACTOR;
+        let top = baseline + self.height;
+
+        let corners = [
+            self.to_drawing_space(left, bottom),
+            self.to_drawing_space(left + width, bottom),
+            self.to_drawing_space(left + width, top),
+            self.to_drawing_space(left, top),
+        ];
+
+        MTextLine {
+            spans,
+            baseline_start: self.to_drawing_space(left, baseline),
+            anchor: self.to_drawing_space(0.0, baseline),
+            width,
+            bounds: bounds_around(&corners),
+        }
+    }
+
+    /// Convert from the text's local coordinate system, where the
+    /// [`MText::insertion_point`] is the origin and the baselines run along
+    /// the x-axis.
+    fn to_drawing_space(&self, x: f64, y: f64) -> Point {
+        let (sin, cos) = self.rotation.sin_cos();
+        self.insertion_point + Vector::new(x * cos - y * sin, x * sin + y * cos)
+    }
+}
+
+fn bounds_around(points: &[Point]) -> BoundingBox<DrawingSpace> {
+    let (bottom_left, top_right) = points.iter().fold(
+        (points[0], points[0]),
+        |(bottom_left, top_right), &point| {
+            (bottom_left.min(point), top_right.max(point))
+        },
+    );
+
+    BoundingBox::new_unchecked(bottom_left, top_right)
+}
+
+impl Bounded<DrawingSpace> for MText {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        let lines = self.layout();
+
+        if lines.is_empty() {
+            return BoundingBox::new(
+                self.insertion_point,
+                self.insertion_point,
+            );
+        }
+
+        let corners: Vec<_> = lines
+            .iter()
+            .flat_map(|line| {
+                vec![line.bounds.bottom_left(), line.bounds.top_right()]
+            })
+            .collect();
+
+        bounds_around(&corners)
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for MText {
+    /// Each line's bounding box is treated as a solid rectangle, so any point
+    /// on a line is closest to itself.
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        self.layout()
+            .iter()
+            .map(|line| {
+                let bounds = line.bounds;
+                Point::new(
+                    target.x.max(bounds.min_x()).min(bounds.max_x()),
+                    target.y.max(bounds.min_y()).min(bounds.max_y()),
+                )
+            })
+            .min_by(|a, b| {
+                let a = (*a - target).square_length();
+                let b = (*b - target).square_length();
+                a.partial_cmp(&b).unwrap()
+            })
+            .map(Closest::One)
+            .unwrap_or(Closest::One(self.insertion_point))
+    }
+}
+
+impl Translate<DrawingSpace> for MText {
+    fn translate(&mut self, displacement: Vector) {
+        self.insertion_point += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for MText {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        self.insertion_point = transform.transform_point(self.insertion_point);
+        self.height *= scale;
+        self.width = self.width.map(|width| width * scale);
+        let (rotation, horizontal, vertical) = readable_orientation(
+            transform,
+            self.rotation,
+            self.horizontal_alignment,
+            self.vertical_alignment,
+        );
+        self.rotation = rotation;
+        self.horizontal_alignment = horizontal;
+        self.vertical_alignment = vertical;
+
+        Ok(())
+    }
+}
+
+/// A single line of an [`MText`] after it has been laid out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MTextLine {
+    /// The pieces of text making up this line.
+    pub spans: Vec<TextSpan>,
+    /// Where the text starts, on the baseline.
+    pub baseline_start: Point,
+    /// The point on the baseline this line is aligned to (see
+    /// [`MText::horizontal_alignment`]).
+    pub anchor: Point,
+    /// The estimated width of this line.
+    pub width: f64,
+    /// The area this line covers.
+    pub bounds: BoundingBox<DrawingSpace>,
+}
+
+impl MTextLine {
+    /// The line's text, without any formatting.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+/// A run of text which all has the same [`TextFormatting`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    /// The text itself.
+    pub text: String,
+    /// How this text should be decorated.
+    pub formatting: TextFormatting,
+}
+
+/// Inline formatting which may be applied to part of an [`MText`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TextFormatting {
+    /// Draw a line under the text.
+    pub underline: bool,
+    /// Draw a line over the text.
+    pub overline: bool,
+    /// Draw a line through the text.
+    pub strikethrough: bool,
+}
+
+/// Split [`MText::content`] into paragraphs, interpreting the basic DXF
+/// formatting codes.
+///
+/// The following codes are understood:
+///
+/// - `\P` (or a newline) starts a new paragraph
+/// - `\~` is a non-breaking space
+/// - `\L` and `\l` turn underlining on and off
+/// - `\O` and `\o` turn overlining on and off
+/// - `\K` and `\k` turn strikethrough on and off
+/// - `\\`, `\{` and `\}` are escaped characters
+/// - `{` and `}` save and restore the current formatting
+///
+/// Codes with parameters (e.g. `\H2.5x;` or `\fArial|b0;`) are recognised
+/// but ignored.
+pub fn parse_paragraphs(content: &str) -> Vec<Vec<TextSpan>> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<(char, TextFormatting)> = Vec::new();
+    let mut formatting = TextFormatting::default();
+    let mut saved = Vec::new();
+    let mut chars = content.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('P') => paragraphs.push(std::mem::take(&mut current)),
+                Some('~') => current.push(('\u{a0}', formatting)),
+                Some('L') => formatting.underline = true,
+                Some('l') => formatting.underline = false,
+                Some('O') => formatting.overline = true,
+                Some('o') => formatting.overline = false,
+                Some('K') => formatting.strikethrough = true,
+                Some('k') => formatting.strikethrough = false,
+                Some(escaped @ '\\') | Some(escaped @ '{')
+                | Some(escaped @ '}') => current.push((escaped, formatting)),
+                Some('A') | Some('C') | Some('c') | Some('F') | Some('f')
+                | Some('H') | Some('Q') | Some('T') | Some('W') | Some('S') => {
+                    // skip the parameter
+                    chars.by_ref().take_while(|&c| c != ';').for_each(drop);
+                },
+                Some(other) => {
+                    current.push(('\\', formatting));
+                    current.push((other, formatting));
+                },
+                None => current.push(('\\', formatting)),
+            },
+            '{' => saved.push(formatting),
+            '}' => formatting = saved.pop().unwrap_or_default(),
+            '\n' => paragraphs.push(std::mem::take(&mut current)),
+            '\r' => {},
+            other => current.push((other, formatting)),
+        }
+    }
+
+    paragraphs.push(current);
+
+    paragraphs.into_iter().map(|p| to_spans(&p)).collect()
+}
+
+/// Merge consecutive characters with the same formatting into [`TextSpan`]s.
+fn to_spans(chars: &[(char, TextFormatting)]) -> Vec<TextSpan> {
+    let mut spans: Vec<TextSpan> = Vec::new();
+
+    for &(c, formatting) in chars {
+        match spans.last_mut() {
+            Some(span) if span.formatting == formatting => span.text.push(c),
+            _ => spans.push(TextSpan {
+                text: c.to_string(),
+                formatting,
+            }),
+        }
+    }
+
+    spans
+}
+
+/// Greedily wrap a paragraph so each line has at most `max_chars`
+/// characters, only breaking at spaces.
+fn wrap(
+    paragraph: Vec<TextSpan>,
+    max_chars: Option<usize>,
+) -> Vec<Vec<TextSpan>> {
+    let max_chars = match max_chars {
+        Some(max) => max,
+        None => return vec![paragraph],
+    };
+
+    let chars: Vec<(char, TextFormatting)> = paragraph
+        .iter()
+        .flat_map(|span| span.text.chars().map(move |c| (c, span.formatting)))
+        .collect();
+
+    let mut lines = Vec::new();
+    let mut line: Vec<(char, TextFormatting)> = Vec::new();
+
+    for word in chars.split(|&(c, _)| c == ' ').filter(|w| !w.is_empty()) {
+        if !line.is_empty() && line.len() + 1 + word.len() > max_chars {
+            lines.push(to_spans(&line));
+            line.clear();
+        }
+
+        if let Some(&(_, formatting)) = line.last() {
+            line.push((' ', formatting));
+        }
+        line.extend_from_slice(word);
+    }
+
+    lines.push(to_spans(&line));
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    #[test]
+    fn parse_formatting_codes() {
+        let got = parse_paragraphs(r"Hello \LWorld\l!\Pa{\Ob\~c}d\H2x;\\");
+
+        let underlined = TextFormatting {
+            underline: true,
+            ..Default::default()
+        };
+        let overlined = TextFormatting {
+            overline: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            got,
+            vec![
+                vec![
+                    TextSpan {
+                        text: String::from("Hello "),
+                        formatting: TextFormatting::default()
+                    },
+                    TextSpan {
+                        text: String::from("World"),
+                        formatting: underlined
+                    },
+                    TextSpan {
+                        text: String::from("!"),
+                        formatting: TextFormatting::default()
+                    },
+                ],
+                vec![
+                    TextSpan {
+                        text: String::from("a"),
+                        formatting: TextFormatting::default()
+                    },
+                    TextSpan {
+                        text: String::from("b\u{a0}c"),
+                        formatting: overlined
+                    },
+                    TextSpan {
+                        text: String::from("d\\"),
+                        formatting: TextFormatting::default()
+                    },
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn paragraphs_and_wrapping() {
+        let mut text =
+            MText::new(Point::zero(), 1.0, "one two three\nfour five six");
+        text.width = Some(8.0 * Text::WIDTH_FACTOR);
+
+        let got: Vec<_> = text.layout().iter().map(|l| l.text()).collect();
+
+        assert_eq!(got, vec!["one two", "three", "four", "five six"]);
+    }
+
+    #[test]
+    fn lines_are_stacked_downwards() {
+        let text = MText::new(Point::new(10.0, 10.0), 3.0, "first\\Psecond");
+
+        let lines = text.layout();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].baseline_start, Point::new(10.0, 7.0));
+        assert!(lines[1].baseline_start.y.approx_eq(&2.0));
+        assert_eq!(lines[0].bounds.max_y(), 10.0);
+
+        let bounds = text.bounding_box();
+        assert_eq!(bounds.top_left(), Point::new(10.0, 10.0));
+        assert_eq!(bounds.max_x(), lines[1].bounds.max_x());
+        assert_eq!(bounds.min_y(), lines[1].bounds.min_y());
+    }
+
+    #[test]
+    fn bottom_aligned_text_ends_on_the_insertion_point() {
+        let mut text = MText::new(Point::zero(), 1.0, "a\nb\nc");
+        text.vertical_alignment = VerticalAlignment::Bottom;
+        text.horizontal_alignment = HorizontalAlignment::Right;
+
+        let lines = text.layout();
+
+        let last = lines.last().unwrap();
+        assert!(last.baseline_start.y.approx_eq(&0.0));
+        assert!(last.bounds.max_x().approx_eq(&0.0));
+    }
+}
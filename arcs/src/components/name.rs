@@ -1,17 +1,78 @@
-use specs::{prelude::*, world::Index};
-use std::{borrow::Borrow, collections::HashMap};
+use specs::{prelude::*, shrev::EventChannel, world::Index};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ops::Bound,
+};
 
 /// A name that can be looked up later in the [`NameTable`].
 ///
 /// Each [`Name`] should be unique within a [`World`]. Conflicts may mess up the
 /// [`NameTable`] bookkeeping and lead to bad lookups.
-#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+///
+/// Names can be organised into namespaces by separating each level with a
+/// [`Name::SEPARATOR`] (e.g. `"floor1/walls/w-102"`).
+#[derive(Debug, Clone, PartialEq, Hash, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Name(String);
 
 impl Name {
+    /// The character used to separate each level in a hierarchical [`Name`].
+    pub const SEPARATOR: char = '/';
+
     pub fn new<S: Into<String>>(name: S) -> Self { Name(name.into()) }
 
+    /// Create a new [`Name`], making sure it satisfies a set of [`NameRules`].
+    ///
+    /// ```rust
+    /// # use arcs::components::{InvalidName, Name, NameRules};
+    /// let rules = NameRules::dxf();
+    ///
+    /// assert!(Name::try_new("WALL-1", &rules).is_ok());
+    /// assert_eq!(
+    ///     Name::try_new("WALL*1", &rules),
+    ///     Err(InvalidName::ForbiddenCharacter('*'))
+    /// );
+    /// ```
+    pub fn try_new<S: Into<String>>(
+        name: S,
+        rules: &NameRules,
+    ) -> Result<Self, InvalidName> {
+        let name = name.into();
+        rules.validate(&name)?;
+
+        Ok(Name(name))
+    }
+
     pub fn as_str(&self) -> &str { &self.0 }
+
+    /// Iterate over each level in this [`Name`]'s hierarchy.
+    pub fn segments(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.split(Name::SEPARATOR)
+    }
+
+    /// The namespace this [`Name`] belongs to, if any.
+    ///
+    /// ```rust
+    /// # use arcs::components::Name;
+    /// let name = Name::new("floor1/walls/w-102");
+    /// assert_eq!(name.parent(), Some("floor1/walls"));
+    ///
+    /// assert_eq!(Name::new("floor1").parent(), None);
+    /// ```
+    pub fn parent(&self) -> Option<&str> {
+        self.0.rfind(Name::SEPARATOR).map(|ix| &self.0[..ix])
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
 }
 
 impl AsRef<str> for Name {
@@ -39,40 +100,1099 @@ impl Component for Name {
 }
 
 /// A global [`Resource`] for looking up an [`Entity`] using its [`Name`].
+///
+/// Lookups are case-sensitive by default. Interchange formats like DXF treat
+/// names case-insensitively, so you can opt into that behaviour with
+/// [`NameTable::case_insensitive()`].
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct NameTable {
-    pub(crate) names: HashMap<Name, Entity>,
+    /// Entities, keyed by the [`NameTable::key()`] for their [`Name`].
+    names: BTreeMap<Name, Entity>,
+    /// Each [`Entity`] and its original [`Name`], keyed by the entity's
+    /// [`Index`] so they can be removed by id.
+    entities: HashMap<Index, (Entity, Name)>,
+    case_insensitive: bool,
 }
 
 impl NameTable {
+    /// Create an empty [`NameTable`] where `get("WALL-1")` and `get("wall-1")`
+    /// resolve to the same [`Entity`].
+    ///
+    /// ```rust
+    /// # use arcs::components::{Name, NameTable};
+    /// # use specs::prelude::*;
+    /// let mut world = World::new();
+    /// arcs::components::register(&mut world);
+    /// world.insert(NameTable::case_insensitive());
+    /// let mut system = arcs::systems::NameTableBookkeeping::new(&world);
+    /// let wall = world.create_entity().with(Name::new("Wall-1")).build();
+    /// System::setup(&mut system, &mut world);
+    ///
+    /// let table = world.read_resource::<NameTable>();
+    /// assert_eq!(table.get("WALL-1"), Some(wall));
+    /// assert_eq!(table.get("wall-1"), Some(wall));
+    /// assert_eq!(table.name_of(wall).unwrap().as_str(), "Wall-1");
+    /// ```
+    pub fn case_insensitive() -> NameTable {
+        NameTable {
+            case_insensitive: true,
+            ..Default::default()
+        }
+    }
+
+    /// Rebuild this [`NameTable`] from the [`Name`] components in a
+    /// [`World`], keeping its case sensitivity.
+    ///
+    /// The [`NameTable`] is entirely derived from the [`Name`] storage, so
+    /// use this after loading a drawing to recreate the lookup table from
+    /// scratch.
+    ///
+    /// ```rust
+    /// # use arcs::components::{Name, NameTable};
+    /// # use specs::prelude::*;
+    /// let mut world = World::new();
+    /// arcs::components::register(&mut world);
+    /// let wall = world.create_entity().with(Name::new("Wall")).build();
+    ///
+    /// let mut table = NameTable::case_insensitive();
+    /// table.rebuild(&world);
+    ///
+    /// assert_eq!(table.get("WALL"), Some(wall));
+    /// ```
+    pub fn rebuild(&mut self, world: &World) {
+        let entities = world.entities();
+        let names = world.read_storage::<Name>();
+        self.clear();
+
+        for (entity, name) in (&entities, &names).join() {
+            self.insert(name.clone(), entity);
+        }
+    }
+
+    /// Get a copy of this [`NameTable`] which can be serialized, using
+    /// `marker_of` to replace each [`Entity`] with something that survives a
+    /// save and load (e.g. a `specs::saveload` marker).
+    ///
+    /// Entities without a marker are skipped.
+    #[cfg(feature = "serde")]
+    pub fn save<M, F>(&self, mut marker_of: F) -> SavedNameTable<M>
+    where
+        F: FnMut(Entity) -> Option<M>,
+    {
+        let names = self
+            .names
+            .values()
+            .filter_map(|&ent| {
+                let marker = marker_of(ent)?;
+                Some((marker, self.entities[&ent.id()].1.clone()))
+            })
+            .collect();
+
+        SavedNameTable {
+            case_insensitive: self.case_insensitive,
+            names,
+        }
+    }
+
+    /// Recreate a [`NameTable`] from a [`SavedNameTable`], using `entity_of`
+    /// to find the [`Entity`] each marker now refers to.
+    ///
+    /// Markers without an [`Entity`] are skipped.
+    #[cfg(feature = "serde")]
+    pub fn load<M, F>(saved: SavedNameTable<M>, mut entity_of: F) -> NameTable
+    where
+        F: FnMut(M) -> Option<Entity>,
+    {
+        let mut table = NameTable {
+            case_insensitive: saved.case_insensitive,
+            ..Default::default()
+        };
+
+        for (marker, name) in saved.names {
+            if let Some(ent) = entity_of(marker) {
+                table.insert(name, ent);
+            }
+        }
+
+        table
+    }
+
+    /// Does this [`NameTable`] ignore case when comparing names?
+    pub fn is_case_insensitive(&self) -> bool { self.case_insensitive }
+
+    /// The key used when storing `name` in [`NameTable::names`].
+    fn key(&self, name: &str) -> Name {
+        if self.case_insensitive {
+            Name::new(name.to_lowercase())
+        } else {
+            Name::new(name)
+        }
+    }
+
     pub fn get(&self, name: &str) -> Option<Entity> {
-        self.names.get(name).copied()
+        if self.case_insensitive {
+            self.names.get(&self.key(name)).copied()
+        } else {
+            self.names.get(name).copied()
+        }
+    }
+
+    /// Look up several names at once, returning their entities in the same
+    /// order or a [`MissingNames`] error listing every name which couldn't be
+    /// found.
+    ///
+    /// ```rust
+    /// # use arcs::components::{Name, NameTable};
+    /// # use specs::prelude::*;
+    /// let mut world = World::new();
+    /// arcs::components::register(&mut world);
+    /// let first = world.create_entity().with(Name::new("first")).build();
+    /// let second = world.create_entity().with(Name::new("second")).build();
+    /// let mut table = NameTable::default();
+    /// table.rebuild(&world);
+    ///
+    /// assert_eq!(table.get_many(vec!["second", "first"]), Ok(vec![second, first]));
+    ///
+    /// let err = table.get_many(vec!["first", "third", "fourth"]).unwrap_err();
+    /// assert_eq!(err.names, vec!["third", "fourth"]);
+    /// ```
+    pub fn get_many<'a, I>(&self, names: I) -> Result<Vec<Entity>, MissingNames>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+
+        for name in names {
+            match self.get(name) {
+                Some(entity) => found.push(entity),
+                None => missing.push(name.to_string()),
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(found)
+        } else {
+            Err(MissingNames { names: missing })
+        }
+    }
+
+    /// Find the [`Name`] associated with an [`Entity`], if it has one.
+    pub fn name_of(&self, entity: Entity) -> Option<&Name> {
+        match self.entities.get(&entity.id()) {
+            Some((ent, name)) if *ent == entity => Some(name),
+            _ => None,
+        }
+    }
+
+    /// The original [`Name`] for an entry in [`NameTable::names`].
+    fn original_name(&self, entity: Entity) -> &str {
+        self.entities[&entity.id()].1.as_str()
     }
 
     pub fn iter<'this>(
         &'this self,
-    ) -> impl Iterator<Item = (&str, Entity)> + 'this {
-        self.names.iter().map(|(name, ent)| (name.as_ref(), *ent))
+    ) -> impl Iterator<Item = (&'this str, Entity)> + 'this {
+        self.names
+            .values()
+            .map(move |&ent| (self.original_name(ent), ent))
+    }
+
+    /// Iterate over all names starting with `prefix`, in sorted order.
+    pub fn iter_prefix<'this>(
+        &'this self,
+        prefix: &str,
+    ) -> impl Iterator<Item = (&'this str, Entity)> + 'this {
+        self.entries_with_prefix(self.key(prefix))
+            .map(move |(_, ent)| (self.original_name(ent), ent))
+    }
+
+    /// Iterate over the entries in [`NameTable::names`] which start with a
+    /// particular key.
+    fn entries_with_prefix(
+        &self,
+        prefix: Name,
+    ) -> impl Iterator<Item = (&Name, Entity)> + '_ {
+        self.names
+            .range((Bound::Included(prefix.clone()), Bound::Unbounded))
+            .map(|(key, ent)| (key, *ent))
+            .take_while(move |(key, _)| {
+                key.as_str().starts_with(prefix.as_str())
+            })
+    }
+
+    /// Iterate over the names directly inside the `parent` namespace.
+    ///
+    /// ```rust
+    /// # use arcs::components::{Name, NameTable};
+    /// # use specs::prelude::*;
+    /// # let mut world = World::new();
+    /// # arcs::components::register(&mut world);
+    /// # let mut system = arcs::systems::NameTableBookkeeping::new(&world);
+    /// for name in &["floor1/walls/w-1", "floor1/walls/w-2/door", "floor1/desk"] {
+    ///     world.create_entity().with(Name::new(*name)).build();
+    /// }
+    /// System::setup(&mut system, &mut world);
+    /// let table = world.read_resource::<NameTable>();
+    ///
+    /// let children: Vec<_> =
+    ///     table.children_of("floor1/walls").map(|(name, _)| name).collect();
+    ///
+    /// assert_eq!(children, vec!["floor1/walls/w-1"]);
+    /// ```
+    pub fn children_of<'this>(
+        &'this self,
+        parent: &str,
+    ) -> impl Iterator<Item = (&'this str, Entity)> + 'this {
+        let parent = self.key(parent);
+        let parent_len = parent.as_str().len();
+
+        self.entries_with_prefix(parent)
+            .filter(move |(key, _)| {
+                let rest = &key.as_str()[parent_len..];
+
+                rest.starts_with(Name::SEPARATOR)
+                    && rest.len() > 1
+                    && !rest[1..].contains(Name::SEPARATOR)
+            })
+            .map(move |(_, ent)| (self.original_name(ent), ent))
     }
 
-    pub fn clear(&mut self) { self.names.clear(); }
+    /// Find all names matching a glob-style pattern, where `*` matches any
+    /// sequence of characters and `?` matches exactly one character.
+    ///
+    /// ```rust
+    /// # use arcs::components::{Name, NameTable};
+    /// # use specs::prelude::*;
+    /// # let mut world = World::new();
+    /// # arcs::components::register(&mut world);
+    /// # let mut system = arcs::systems::NameTableBookkeeping::new(&world);
+    /// for name in &["Wall-1", "Wall-2", "Door-1", "A-copy", "AB-copy"] {
+    ///     world.create_entity().with(Name::new(*name)).build();
+    /// }
+    /// System::setup(&mut system, &mut world);
+    /// let table = world.read_resource::<NameTable>();
+    ///
+    /// let walls: Vec<_> =
+    ///     table.find_matching("Wall-*").map(|(name, _)| name).collect();
+    /// assert_eq!(walls, vec!["Wall-1", "Wall-2"]);
+    ///
+    /// let copies: Vec<_> =
+    ///     table.find_matching("?-copy").map(|(name, _)| name).collect();
+    /// assert_eq!(copies, vec!["A-copy"]);
+    /// ```
+    pub fn find_matching<'this>(
+        &'this self,
+        pattern: &str,
+    ) -> impl Iterator<Item = (&'this str, Entity)> + 'this {
+        let pattern = self.key(pattern);
+
+        self.names
+            .iter()
+            .filter(move |(key, _)| {
+                glob_matches(pattern.as_str(), key.as_str())
+            })
+            .map(move |(_, &ent)| (self.original_name(ent), ent))
+    }
+
+    /// Find all names matching a [`regex::Regex`].
+    ///
+    /// # Note
+    ///
+    /// The [`NameTable`]'s case sensitivity has no effect here. Use the
+    /// regex's `(?i)` flag if you want case-insensitive matching.
+    #[cfg(feature = "regex")]
+    pub fn find_matching_regex<'this>(
+        &'this self,
+        pattern: &'this regex::Regex,
+    ) -> impl Iterator<Item = (&'this str, Entity)> + 'this {
+        self.iter().filter(move |(name, _)| pattern.is_match(name))
+    }
+
+    pub fn clear(&mut self) {
+        self.names.clear();
+        self.entities.clear();
+    }
 
     pub fn len(&self) -> usize { self.names.len() }
 
     pub fn is_empty(&self) -> bool { self.names.is_empty() }
 
-    pub fn remove_by_id(&mut self, id: Index) {
-        let filter = move |(name, ent): (&Name, &Entity)| {
-            if ent.id() == id {
-                Some(name.clone())
-            } else {
-                None
+    /// Associate a [`Name`] with an [`Entity`], returning the [`Entity`] which
+    /// previously had that [`Name`] (if any).
+    pub(crate) fn insert(
+        &mut self,
+        name: Name,
+        entity: Entity,
+    ) -> Option<Entity> {
+        self.remove_by_id(entity.id());
+
+        let previous = self.names.insert(self.key(name.as_str()), entity);
+
+        if let Some(previous) = previous {
+            self.entities.remove(&previous.id());
+        }
+        self.entities.insert(entity.id(), (entity, name));
+
+        previous
+    }
+
+    /// Repair the [`NameTable`] by removing any entries for entities which have
+    /// been deleted or whose [`Name`] component no longer matches, returning
+    /// the entries which were removed.
+    ///
+    /// [`crate::systems::NameTableBookkeeping`] only reacts to changes in the
+    /// [`Name`] storage, so use this after populating the table by hand.
+    pub fn validate(&mut self, world: &World) -> Vec<(Name, Entity)> {
+        let entities = world.entities();
+        let names = world.read_storage::<Name>();
+
+        self.retain(|name, entity| {
+            entities.is_alive(entity) && names.get(entity) == Some(name)
+        })
+    }
+
+    fn retain<F>(&mut self, mut predicate: F) -> Vec<(Name, Entity)>
+    where
+        F: FnMut(&Name, Entity) -> bool,
+    {
+        let to_remove: Vec<_> = self
+            .entities
+            .values()
+            .filter(|(entity, name)| !predicate(name, *entity))
+            .map(|(entity, name)| (name.clone(), *entity))
+            .collect();
+
+        for (_, entity) in &to_remove {
+            self.remove_by_id(entity.id());
+        }
+
+        to_remove
+    }
+
+    /// Remove the [`Entity`] with a particular [`Index`] from the table,
+    /// returning the [`Entity`] and its [`Name`] if it was present.
+    pub fn remove_by_id(&mut self, id: Index) -> Option<(Entity, Name)> {
+        let (entity, name) = self.entities.remove(&id)?;
+        let key = self.key(name.as_str());
+        self.names.remove(&key);
+
+        Some((entity, name))
+    }
+}
+
+/// A [`NameTable`] where each [`Entity`] has been replaced by a marker, so it
+/// can be serialized.
+///
+/// Use [`NameTable::save()`] and [`NameTable::load()`] to convert to and from
+/// a [`NameTable`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SavedNameTable<M> {
+    case_insensitive: bool,
+    names: Vec<(M, Name)>,
+}
+
+/// The error returned by [`NameTable::get_many()`] when some names couldn't
+/// be found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingNames {
+    /// The names which weren't in the [`NameTable`], in the order they were
+    /// requested.
+    pub names: Vec<String>,
+}
+
+impl Display for MissingNames {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Unable to find ")?;
+
+        for (i, name) in self.names.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
             }
+            write!(f, "\"{}\"", name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for MissingNames {}
+
+/// A change to the [`NameTable`], published via an [`EventChannel`] resource
+/// by [`crate::systems::NameTableBookkeeping`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NameEvent {
+    /// An [`Entity`] was added to the [`NameTable`].
+    Added { entity: Entity, name: Name },
+    /// An [`Entity`] was removed from the [`NameTable`].
+    Removed { entity: Entity, name: Name },
+    /// An [`Entity`]'s [`Name`] was changed.
+    Renamed {
+        entity: Entity,
+        old_name: Name,
+        new_name: Name,
+    },
+}
+
+/// What [`crate::systems::NameTableBookkeeping`] should do when it finds two
+/// entities with the same [`Name`].
+///
+/// Regardless of the policy, every conflict is reported as a [`NameConflict`]
+/// via an [`EventChannel`] resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DuplicateNamePolicy {
+    /// Treat the conflict as an error, removing the [`Name`] component from
+    /// the newer entity so the [`World`] never contains duplicate names.
+    Error,
+    /// Give the newer entity a unique name generated by the [`NameGenerator`].
+    AutoRename,
+    /// The newer entity takes over the name in the [`NameTable`].
+    ReplaceOld,
+    /// The [`NameTable`] keeps pointing at the original entity, and the newer
+    /// entity won't be accessible by name.
+    KeepOld,
+}
+
+impl Default for DuplicateNamePolicy {
+    fn default() -> Self { DuplicateNamePolicy::ReplaceOld }
+}
+
+/// A notification that two entities were found with the same [`Name`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameConflict {
+    /// The [`Name`] both entities tried to use.
+    pub name: Name,
+    /// The [`Entity`] which already had this [`Name`].
+    pub existing: Entity,
+    /// The [`Entity`] which was found to be using the same [`Name`].
+    pub duplicate: Entity,
+    /// The name given to [`NameConflict::duplicate`] when using
+    /// [`DuplicateNamePolicy::AutoRename`].
+    pub renamed_to: Option<Name>,
+}
+
+/// Check whether `text` matches a glob-style `pattern`.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // where to resume from if we need to backtrack to the most recent `*`
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            },
+            Some('?') => {
+                p += 1;
+                t += 1;
+            },
+            Some(&c) if c == text[t] => {
+                p += 1;
+                t += 1;
+            },
+            _ => match backtrack {
+                // let the `*` swallow one more character and try again
+                Some((star, star_t)) => {
+                    backtrack = Some((star, star_t + 1));
+                    p = star + 1;
+                    t = star_t + 1;
+                },
+                None => return false,
+            },
+        }
+    }
+
+    // any trailing `*`s can match the empty string
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A global [`Resource`] which hands out unique [`Name`]s (e.g. `Line-1`,
+/// `Line-2`) for entities which weren't given an explicit [`Name`].
+///
+/// Generated names are checked against the [`NameTable`], and the generator
+/// remembers the names it has already handed out so they stay unique even
+/// before [`crate::systems::NameTableBookkeeping`] gets a chance to run.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NameGenerator {
+    counters: HashMap<String, usize>,
+}
+
+impl NameGenerator {
+    /// Generate a new [`Name`] starting with `prefix` which isn't already in
+    /// use.
+    pub fn next_available(&mut self, prefix: &str, table: &NameTable) -> Name {
+        let counter = self.counters.entry(prefix.to_string()).or_insert(0);
+
+        loop {
+            *counter += 1;
+            let candidate = format!("{}-{}", prefix, counter);
+
+            if table.get(&candidate).is_none() {
+                return Name::new(candidate);
+            }
+        }
+    }
+}
+
+/// A global [`Resource`] containing the rules a [`Name`] must follow.
+///
+/// The default rules only require a [`Name`] to be non-empty.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NameRules {
+    /// The maximum number of characters allowed in a [`Name`].
+    pub max_length: Option<usize>,
+    /// Only allow 7-bit ASCII characters.
+    pub ascii_only: bool,
+    /// Characters which may not appear in a [`Name`].
+    pub forbidden_characters: Vec<char>,
+    /// Prefixes reserved for internal use (e.g. `"*"` for anonymous blocks).
+    pub reserved_prefixes: Vec<String>,
+}
+
+impl NameRules {
+    /// Rules compatible with the DXF naming conventions for layers, blocks,
+    /// and other symbol table entries.
+    ///
+    /// DXF doesn't allow `'/'` in names, but it is still accepted here so
+    /// hierarchical names (see [`Name::SEPARATOR`]) keep working. Anything
+    /// writing a DXF file needs to replace the separator when exporting.
+    pub fn dxf() -> NameRules {
+        NameRules {
+            max_length: Some(255),
+            ascii_only: false,
+            forbidden_characters: "<>\\\":;?*|=`".chars().collect(),
+            reserved_prefixes: vec![String::from("*")],
+        }
+    }
+
+    /// Check whether a name satisfies these rules.
+    pub fn validate(&self, name: &str) -> Result<(), InvalidName> {
+        if name.is_empty() {
+            return Err(InvalidName::Empty);
+        }
+
+        let length = name.chars().count();
+        if let Some(max_length) = self.max_length {
+            if length > max_length {
+                return Err(InvalidName::TooLong { max_length, length });
+            }
+        }
+
+        let bad_char = name.chars().find(|c| {
+            (self.ascii_only && !c.is_ascii())
+                || self.forbidden_characters.contains(c)
+        });
+        if let Some(c) = bad_char {
+            return Err(InvalidName::ForbiddenCharacter(c));
+        }
+
+        if let Some(prefix) = self
+            .reserved_prefixes
+            .iter()
+            .find(|prefix| name.starts_with(prefix.as_str()))
+        {
+            return Err(InvalidName::ReservedPrefix(prefix.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// The reason a name was rejected by [`NameRules`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidName {
+    /// Names can't be empty.
+    Empty,
+    /// The name was longer than [`NameRules::max_length`].
+    TooLong { max_length: usize, length: usize },
+    /// The name contained a forbidden character.
+    ForbiddenCharacter(char),
+    /// The name started with a reserved prefix.
+    ReservedPrefix(String),
+}
+
+impl Display for InvalidName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidName::Empty => write!(f, "Names can't be empty"),
+            InvalidName::TooLong { max_length, length } => write!(
+                f,
+                "The name is {} characters long, but the maximum is {}",
+                length, max_length
+            ),
+            InvalidName::ForbiddenCharacter(c) => {
+                write!(f, "Names can't contain {:?}", c)
+            },
+            InvalidName::ReservedPrefix(prefix) => {
+                write!(f, "The \"{}\" prefix is reserved", prefix)
+            },
+        }
+    }
+}
+
+impl Error for InvalidName {}
+
+/// Give an [`Entity`] a new [`Name`], updating the [`NameTable`] immediately
+/// so lookups never see a stale [`Entity`].
+///
+/// This fails if `new_name` is already used by another [`Entity`] or doesn't
+/// satisfy the [`NameRules`] (if the [`World`] has them). When there is no
+/// [`NameTable`] every [`Name`] component is checked instead.
+pub fn rename(
+    world: &World,
+    entity: Entity,
+    new_name: Name,
+) -> Result<(), RenameError> {
+    if !world.is_alive(entity) {
+        return Err(RenameError::DeadEntity(entity));
+    }
+
+    if let Some(rules) = world.try_fetch::<NameRules>() {
+        rules
+            .validate(new_name.as_str())
+            .map_err(RenameError::InvalidName)?;
+    }
+
+    let mut names = world.write_storage::<Name>();
+    let mut table = world.try_fetch_mut::<NameTable>();
+
+    if let Some(ref mut table) = table {
+        match table.get(new_name.as_str()) {
+            Some(existing) if existing != entity => {
+                return Err(RenameError::NameAlreadyTaken {
+                    name: new_name,
+                    existing,
+                });
+            },
+            _ => {
+                let old_name = table.name_of(entity).cloned();
+                table.insert(new_name.clone(), entity);
+
+                // the bookkeeping system won't see a difference between the
+                // table and the Name storage, so we need to publish the event
+                if let Some(mut events) =
+                    world.try_fetch_mut::<EventChannel<NameEvent>>()
+                {
+                    let event = match old_name {
+                        Some(old_name) => NameEvent::Renamed {
+                            entity,
+                            old_name,
+                            new_name: new_name.clone(),
+                        },
+                        None => NameEvent::Added {
+                            entity,
+                            name: new_name.clone(),
+                        },
+                    };
+                    events.single_write(event);
+                }
+            },
+        }
+    } else {
+        // without a table we need to check every name the slow way
+        let existing = (&world.entities(), &names)
+            .join()
+            .find(|&(ent, name)| ent != entity && *name == new_name)
+            .map(|(ent, _)| ent);
+
+        if let Some(existing) = existing {
+            return Err(RenameError::NameAlreadyTaken {
+                name: new_name,
+                existing,
+            });
+        }
+    }
+
+    names
+        .insert(entity, new_name)
+        .map_err(|_| RenameError::DeadEntity(entity))?;
+
+    Ok(())
+}
+
+/// The error returned when [`rename()`] fails.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenameError {
+    /// Another [`Entity`] is already using this [`Name`].
+    NameAlreadyTaken { name: Name, existing: Entity },
+    /// The [`Entity`] being renamed has been deleted.
+    DeadEntity(Entity),
+    /// The new name doesn't satisfy the [`NameRules`].
+    InvalidName(InvalidName),
+}
+
+impl Display for RenameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RenameError::NameAlreadyTaken { name, existing } => write!(
+                f,
+                "The name \"{}\" is already used by {:?}",
+                name.as_str(),
+                existing
+            ),
+            RenameError::DeadEntity(entity) => {
+                write!(f, "{:?} has been deleted", entity)
+            },
+            RenameError::InvalidName(_) => write!(f, "Invalid name"),
+        }
+    }
+}
+
+impl Error for RenameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RenameError::InvalidName(inner) => Some(inner),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_names_are_always_unique() {
+        let mut world = World::new();
+        let mut table = NameTable::default();
+        table.insert(Name::new("Line-2"), world.create_entity().build());
+        let mut generator = NameGenerator::default();
+
+        let got: Vec<_> = (0..3)
+            .map(|_| generator.next_available("Line", &table))
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                Name::new("Line-1"),
+                Name::new("Line-3"),
+                Name::new("Line-4")
+            ]
+        );
+        assert_eq!(generator.next_available("Arc", &table), Name::new("Arc-1"));
+    }
+
+    #[test]
+    fn remove_entries_by_id() {
+        let mut world = World::new();
+        let mut table = NameTable::default();
+        let wall = world.create_entity().build();
+        table.insert(Name::new("wall"), wall);
+        world.delete_entity(wall).unwrap();
+        world.maintain();
+        // reuses the deleted entity's id
+        let door = world.create_entity().build();
+        assert_eq!(door.id(), wall.id());
+
+        assert!(table.name_of(door).is_none());
+        assert_eq!(
+            table.remove_by_id(wall.id()),
+            Some((wall, Name::new("wall")))
+        );
+        assert!(table.get("wall").is_none());
+        assert!(table.is_empty());
+        assert_eq!(table.remove_by_id(wall.id()), None);
+    }
+
+    #[test]
+    fn rebuilding_keeps_the_case_sensitivity() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let wall = world.create_entity().with(Name::new("Wall")).build();
+        let mut table = NameTable::case_insensitive();
+        table.insert(Name::new("stale"), world.create_entity().build());
+
+        table.rebuild(&world);
+
+        assert!(table.is_case_insensitive());
+        assert_eq!(table.get("WALL"), Some(wall));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trip_the_name_table() {
+        let mut world = World::new();
+        let wall = world.create_entity().build();
+        let door = world.create_entity().build();
+        let mut table = NameTable::case_insensitive();
+        table.insert(Name::new("Wall"), wall);
+        table.insert(Name::new("Door"), door);
+
+        // use the entity's position in the save file as its marker
+        let markers = [wall, door];
+        let saved = table.save(|ent| markers.iter().position(|&m| m == ent));
+        let json = serde_json::to_string(&saved).unwrap();
+        assert_eq!(
+            json,
+            r#"{"case_insensitive":true,"names":[[1,"Door"],[0,"Wall"]]}"#
+        );
+
+        let mut other_world = World::new();
+        let loaded: Vec<Entity> = (0..2)
+            .map(|_| other_world.create_entity().build())
+            .collect();
+        let got = NameTable::load(
+            serde_json::from_str(&json).unwrap(),
+            |marker: usize| loaded.get(marker).copied(),
+        );
+
+        assert!(got.is_case_insensitive());
+        assert_eq!(got.get("wall"), Some(loaded[0]));
+        assert_eq!(got.name_of(loaded[1]), Some(&Name::new("Door")));
+    }
+
+    #[test]
+    fn iterate_over_names_with_a_prefix() {
+        let mut world = World::new();
+        let mut table = NameTable::default();
+        for name in &["a/b", "a/c/d", "a", "ab", "b/a"] {
+            table.insert(Name::new(*name), world.create_entity().build());
+        }
+
+        let got: Vec<_> = table.iter_prefix("a/").map(|(n, _)| n).collect();
+
+        assert_eq!(got, vec!["a/b", "a/c/d"]);
+    }
+
+    #[test]
+    fn glob_patterns() {
+        let inputs = vec![
+            ("Wall-*", "Wall-1", true),
+            ("Wall-*", "Wall-", true),
+            ("Wall-*", "Door-1", false),
+            ("?-copy", "A-copy", true),
+            ("?-copy", "AB-copy", false),
+            ("*", "", true),
+            ("", "", true),
+            ("", "a", false),
+            ("a*b*c", "aXXbYYc", true),
+            ("a*b*c", "aXXbYY", false),
+            ("*.dxf", "drawing.dxf.bak", false),
+        ];
+
+        for (pattern, text, should_be) in inputs {
+            assert_eq!(
+                glob_matches(pattern, text),
+                should_be,
+                "{:?} ~ {:?}",
+                pattern,
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn validate_names() {
+        let rules = NameRules {
+            max_length: Some(5),
+            ascii_only: true,
+            forbidden_characters: vec!['*'],
+            reserved_prefixes: vec![String::from("$")],
         };
+        let inputs = vec![
+            ("", Err(InvalidName::Empty)),
+            ("abcde", Ok(())),
+            (
+                "abcdef",
+                Err(InvalidName::TooLong {
+                    max_length: 5,
+                    length: 6,
+                }),
+            ),
+            ("a*", Err(InvalidName::ForbiddenCharacter('*'))),
+            ("Ø", Err(InvalidName::ForbiddenCharacter('Ø'))),
+            ("$abc", Err(InvalidName::ReservedPrefix(String::from("$")))),
+        ];
 
-        // OPT: This is super inefficient...
-        if let Some(name) = self.names.iter().filter_map(filter).next() {
-            self.names.remove(&name);
+        for (name, should_be) in inputs {
+            assert_eq!(rules.validate(name), should_be, "{:?}", name);
         }
     }
+
+    #[test]
+    fn dxf_rules_allow_hierarchical_names() {
+        let rules = NameRules::dxf();
+
+        assert!(Name::try_new("floor1/walls/w-102", &rules).is_ok());
+        assert_eq!(
+            Name::try_new("floor1\\walls", &rules),
+            Err(InvalidName::ForbiddenCharacter('\\'))
+        );
+    }
+
+    #[test]
+    fn renames_are_validated() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let first = world.create_entity().with(Name::new("first")).build();
+        world.insert(NameRules::dxf());
+
+        let got = rename(&world, first, Name::new("a:b"));
+
+        assert_eq!(
+            got,
+            Err(RenameError::InvalidName(InvalidName::ForbiddenCharacter(
+                ':'
+            )))
+        );
+    }
+
+    #[test]
+    fn validate_removes_stale_entries() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let deleted = world.create_entity().build();
+        let renamed = world.create_entity().with(Name::new("new")).build();
+        let ok = world.create_entity().with(Name::new("ok")).build();
+        let mut table = NameTable::default();
+        table.insert(Name::new("deleted"), deleted);
+        table.insert(Name::new("old"), renamed);
+        table.insert(Name::new("ok"), ok);
+        world.delete_entity(deleted).unwrap();
+
+        let mut removed = table.validate(&world);
+        removed.sort();
+
+        assert_eq!(
+            removed,
+            vec![(Name::new("deleted"), deleted), (Name::new("old"), renamed)]
+        );
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get("ok"), Some(ok));
+    }
+
+    #[test]
+    fn case_insensitive_queries() {
+        let mut world = World::new();
+        let mut table = NameTable::case_insensitive();
+        let wall = world.create_entity().build();
+        table.insert(Name::new("Floor1/Wall-1"), wall);
+        table.insert(Name::new("floor1/Door"), world.create_entity().build());
+
+        assert_eq!(table.get("FLOOR1/WALL-1"), Some(wall));
+        let children: Vec<_> =
+            table.children_of("FLOOR1").map(|(n, _)| n).collect();
+        assert_eq!(children, vec!["floor1/Door", "Floor1/Wall-1"]);
+        let matches: Vec<_> =
+            table.find_matching("*wall*").map(|(n, _)| n).collect();
+        assert_eq!(matches, vec!["Floor1/Wall-1"]);
+
+        // a duplicate which only differs by case replaces the original
+        let duplicate = world.create_entity().build();
+        assert_eq!(
+            table.insert(Name::new("FLOOR1/wall-1"), duplicate),
+            Some(wall)
+        );
+        assert!(table.name_of(wall).is_none());
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn look_up_many_names() {
+        let mut world = World::new();
+        let mut table = NameTable::default();
+        let first = world.create_entity().build();
+        let second = world.create_entity().build();
+        table.insert(Name::new("first"), first);
+        table.insert(Name::new("second"), second);
+
+        assert_eq!(table.get_many(Vec::new()), Ok(Vec::new()));
+        assert_eq!(
+            table.get_many(vec!["second", "first", "second"]),
+            Ok(vec![second, first, second])
+        );
+
+        let err = table
+            .get_many(vec!["fourth", "first", "third", "Second"])
+            .unwrap_err();
+        assert_eq!(err.names, vec!["fourth", "third", "Second"]);
+    }
+
+    #[test]
+    fn look_up_many_names_ignoring_case() {
+        let mut world = World::new();
+        let mut table = NameTable::case_insensitive();
+        let wall = world.create_entity().build();
+        let door = world.create_entity().build();
+        table.insert(Name::new("Wall"), wall);
+        table.insert(Name::new("door"), door);
+
+        assert_eq!(table.get_many(vec!["DOOR", "wall"]), Ok(vec![door, wall]));
+        let err = table.get_many(vec!["WINDOW", "Door"]).unwrap_err();
+        assert_eq!(err.names, vec!["WINDOW"]);
+    }
+
+    #[test]
+    fn rename_updates_the_table_immediately() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let first = world.create_entity().with(Name::new("first")).build();
+        let second = world.create_entity().with(Name::new("second")).build();
+        let mut table = NameTable::default();
+        table.insert(Name::new("first"), first);
+        table.insert(Name::new("second"), second);
+        world.insert(table);
+
+        rename(&world, first, Name::new("renamed")).unwrap();
+
+        let table = world.read_resource::<NameTable>();
+        assert_eq!(table.get("renamed"), Some(first));
+        assert!(table.get("first").is_none());
+        assert_eq!(
+            world.read_storage::<Name>().get(first),
+            Some(&Name::new("renamed"))
+        );
+    }
+
+    #[test]
+    fn renaming_to_an_existing_name_fails_without_a_table() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let first = world.create_entity().with(Name::new("first")).build();
+        let second = world.create_entity().with(Name::new("second")).build();
+
+        let got = rename(&world, first, Name::new("second"));
+
+        assert_eq!(
+            got,
+            Err(RenameError::NameAlreadyTaken {
+                name: Name::new("second"),
+                existing: second,
+            })
+        );
+        assert!(rename(&world, first, Name::new("first")).is_ok());
+    }
+
+    #[test]
+    fn renaming_to_an_existing_name_fails() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let first = world.create_entity().with(Name::new("first")).build();
+        let second = world.create_entity().with(Name::new("second")).build();
+        let mut table = NameTable::default();
+        table.insert(Name::new("first"), first);
+        table.insert(Name::new("second"), second);
+        world.insert(table);
+
+        let got = rename(&world, first, Name::new("second"));
+
+        assert_eq!(
+            got,
+            Err(RenameError::NameAlreadyTaken {
+                name: Name::new("second"),
+                existing: second,
+            })
+        );
+        assert_eq!(
+            world.read_resource::<NameTable>().get("first"),
+            Some(first)
+        );
+    }
 }
@@ -0,0 +1,368 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    components::{
+        annotation::{apply_override, leader_text},
+        AnnotationGraphics, DimensionStyle, Geometry,
+    },
+    Angle, BoundingBox, DrawingSpace, Line, Point, Vector,
+};
+use specs::Entity;
+
+/// A dimension showing the radius of an arc or circle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialDimension {
+    /// The centre of the arc being measured.
+    pub centre: Point,
+    /// The radius of the arc being measured.
+    pub radius: f64,
+    /// The direction the leader points, as seen from the
+    /// [`RadialDimension::centre`].
+    pub angle: Angle,
+    /// How far the leader extends past the arc before reaching the text.
+    pub leader_length: f64,
+    /// The entity being measured, if any.
+    ///
+    /// The [`crate::systems::SyncRadialDimensions`] system will update
+    /// the [`RadialDimension::centre`] and [`RadialDimension::radius`]
+    /// whenever this entity's [`Geometry`] changes.
+    pub associated_with: Option<Entity>,
+    /// Text to show instead of the measured value, where `<>` will be
+    /// replaced with the measurement.
+    pub text_override: Option<String>,
+    /// How the dimension should look.
+    pub style: DimensionStyle,
+}
+
+impl RadialDimension {
+    /// The prefix added to the measured radius.
+    pub const PREFIX: &'static str = "R";
+
+    /// Create a new [`RadialDimension`].
+    pub fn new(centre: Point, radius: f64, angle: Angle) -> Self {
+        RadialDimension {
+            centre,
+            radius,
+            angle,
+            leader_length: DimensionStyle::default().arrow_size * 2.0,
+            associated_with: None,
+            text_override: None,
+            style: DimensionStyle::default(),
+        }
+    }
+
+    /// Create a [`RadialDimension`] which measures some other entity's
+    /// [`Geometry`], returning [`None`] if it isn't an arc or circle.
+    pub fn associated(
+        entity: Entity,
+        geometry: &Geometry,
+        angle: Angle,
+    ) -> Option<Self> {
+        let mut dimension = RadialDimension::new(Point::zero(), 0.0, angle);
+        dimension.associated_with = Some(entity);
+
+        if dimension.update_from(geometry) {
+            Some(dimension)
+        } else {
+            None
+        }
+    }
+
+    /// Re-measure some [`Geometry`], returning `false` if it can't be
+    /// measured.
+    pub fn update_from(&mut self, geometry: &Geometry) -> bool {
+        match arc_parameters(geometry) {
+            Some((centre, radius)) => {
+                self.centre = centre;
+                self.radius = radius;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// The measured radius.
+    pub fn measurement(&self) -> f64 { self.radius }
+
+    /// The text to display.
+    pub fn text(&self) -> String {
+        let measurement = format!(
+            "{}{:.*}",
+            RadialDimension::PREFIX,
+            self.style.precision,
+            self.measurement()
+        );
+
+        apply_override(measurement, self.text_override.as_deref())
+    }
+
+    /// Break the [`RadialDimension`] down into lines, arrowheads and text.
+    pub fn graphics(&self) -> AnnotationGraphics {
+        let direction = unit_vector(self.angle);
+        let tip = self.centre + direction * self.radius;
+        let mut graphics = AnnotationGraphics::new();
+
+        graphics.push_arrowhead(tip, direction, self.style.arrow_size);
+        leader_with_text(
+            &mut graphics,
+            self.centre,
+            tip + direction * self.leader_length,
+            self.text(),
+            &self.style,
+        );
+
+        graphics
+    }
+}
+
+impl Bounded<DrawingSpace> for RadialDimension {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        self.graphics().bounding_box()
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for RadialDimension {
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        self.graphics().closest_point(target)
+    }
+}
+
+impl Translate<DrawingSpace> for RadialDimension {
+    fn translate(&mut self, displacement: Vector) {
+        self.centre += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for RadialDimension {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        self.centre = transform.transform_point(self.centre);
+        self.radius *= scale;
+        self.angle = transform.transform_angle(self.angle);
+        self.leader_length *= scale;
+
+        Ok(())
+    }
+}
+
+/// A dimension showing the diameter of an arc or circle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiameterDimension {
+    /// The centre of the arc being measured.
+    pub centre: Point,
+    /// The radius of the arc being measured.
+    pub radius: f64,
+    /// The direction the leader points, as seen from the
+    /// [`DiameterDimension::centre`].
+    pub angle: Angle,
+    /// How far the leader extends past the arc before reaching the text.
+    pub leader_length: f64,
+    /// The entity being measured, if any.
+    ///
+    /// The [`crate::systems::SyncRadialDimensions`] system will update
+    /// the [`DiameterDimension::centre`] and [`DiameterDimension::radius`]
+    /// whenever this entity's [`Geometry`] changes.
+    pub associated_with: Option<Entity>,
+    /// Text to show instead of the measured value, where `<>` will be
+    /// replaced with the measurement.
+    pub text_override: Option<String>,
+    /// How the dimension should look.
+    pub style: DimensionStyle,
+}
+
+impl DiameterDimension {
+    /// The prefix added to the measured diameter.
+    pub const PREFIX: &'static str = "Ø";
+
+    /// Create a new [`DiameterDimension`].
+    pub fn new(centre: Point, radius: f64, angle: Angle) -> Self {
+        DiameterDimension {
+            centre,
+            radius,
+            angle,
+            leader_length: DimensionStyle::default().arrow_size * 2.0,
+            associated_with: None,
+            text_override: None,
+            style: DimensionStyle::default(),
+        }
+    }
+
+    /// Create a [`DiameterDimension`] which measures some other entity's
+    /// [`Geometry`], returning [`None`] if it isn't an arc or circle.
+    pub fn associated(
+        entity: Entity,
+        geometry: &Geometry,
+        angle: Angle,
+    ) -> Option<Self> {
+        let mut dimension = DiameterDimension::new(Point::zero(), 0.0, angle);
+        dimension.associated_with = Some(entity);
+
+        if dimension.update_from(geometry) {
+            Some(dimension)
+        } else {
+            None
+        }
+    }
+
+    /// Re-measure some [`Geometry`], returning `false` if it can't be
+    /// measured.
+    pub fn update_from(&mut self, geometry: &Geometry) -> bool {
+        match arc_parameters(geometry) {
+            Some((centre, radius)) => {
+                self.centre = centre;
+                self.radius = radius;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// The measured diameter.
+    pub fn measurement(&self) -> f64 { self.radius * 2.0 }
+
+    /// The text to display.
+    pub fn text(&self) -> String {
+        let measurement = format!(
+            "{}{:.*}",
+            DiameterDimension::PREFIX,
+            self.style.precision,
+            self.measurement()
+        );
+
+        apply_override(measurement, self.text_override.as_deref())
+    }
+
+    /// Break the [`DiameterDimension`] down into lines, arrowheads and text.
+    pub fn graphics(&self) -> AnnotationGraphics {
+        let direction = unit_vector(self.angle);
+        let tip = self.centre + direction * self.radius;
+        let tail = self.centre - direction * self.radius;
+        let mut graphics = AnnotationGraphics::new();
+
+        graphics.push_arrowhead(tip, direction, self.style.arrow_size);
+        graphics.push_arrowhead(tail, -direction, self.style.arrow_size);
+        leader_with_text(
+            &mut graphics,
+            tail,
+            tip + direction * self.leader_length,
+            self.text(),
+            &self.style,
+        );
+
+        graphics
+    }
+}
+
+impl Bounded<DrawingSpace> for DiameterDimension {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        self.graphics().bounding_box()
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for DiameterDimension {
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        self.graphics().closest_point(target)
+    }
+}
+
+impl Translate<DrawingSpace> for DiameterDimension {
+    fn translate(&mut self, displacement: Vector) {
+        self.centre += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for DiameterDimension {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        self.centre = transform.transform_point(self.centre);
+        self.radius *= scale;
+        self.angle = transform.transform_angle(self.angle);
+        self.leader_length *= scale;
+
+        Ok(())
+    }
+}
+
+/// The centre and radius of an arc or circle.
+fn arc_parameters(geometry: &Geometry) -> Option<(Point, f64)> {
+    match geometry {
+        Geometry::Arc(arc) => Some((arc.centre(), arc.radius())),
+        _ => None,
+    }
+}
+
+fn unit_vector(angle: Angle) -> Vector {
+    let (sin, cos) = angle.sin_cos();
+    Vector::new(cos, sin)
+}
+
+/// Add a straight leader from `start` to `end`, with horizontal text just
+/// past the `end`.
+fn leader_with_text(
+    graphics: &mut AnnotationGraphics,
+    start: Point,
+    end: Point,
+    content: String,
+    style: &DimensionStyle,
+) {
+    graphics.lines.push(Line::new(start, end));
+    graphics.text.push(leader_text(start, end, content, style));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::HorizontalAlignment, Arc};
+    use specs::prelude::*;
+
+    #[test]
+    fn radius_of_an_arc() {
+        let arc = Geometry::Arc(Arc::from_centre_radius(
+            Point::new(1.0, 2.0),
+            5.0,
+            Angle::zero(),
+            Angle::pi(),
+        ));
+        let entity = World::new().create_entity().build();
+
+        let dimension =
+            RadialDimension::associated(entity, &arc, Angle::zero()).unwrap();
+        let graphics = dimension.graphics();
+
+        assert_eq!(dimension.text(), "R5.00");
+        assert_eq!(graphics.arrowheads[0][0], Point::new(6.0, 2.0));
+        assert_eq!(graphics.lines[0].start, Point::new(1.0, 2.0));
+        assert_eq!(
+            graphics.text[0].horizontal_alignment,
+            HorizontalAlignment::Left
+        );
+    }
+
+    #[test]
+    fn diameter_goes_across_the_circle() {
+        let mut dimension =
+            DiameterDimension::new(Point::zero(), 2.0, Angle::pi());
+        dimension.style.precision = 1;
+
+        let graphics = dimension.graphics();
+
+        assert_eq!(dimension.text(), "Ø4.0");
+        assert_eq!(graphics.arrowheads.len(), 2);
+        assert_eq!(graphics.lines[0].start.x.round(), 2.0);
+        assert_eq!(
+            graphics.text[0].horizontal_alignment,
+            HorizontalAlignment::Right
+        );
+        assert!(!dimension.update_from(&Geometry::Point(Point::zero())));
+    }
+}
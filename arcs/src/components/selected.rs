@@ -1,7 +1,129 @@
 use specs::prelude::*;
-use specs_derive::Component;
 
 /// An empty [`Component`] used to mark an [`Entity`] as selected.
-#[derive(Debug, Copy, Clone, Default, PartialEq, Component)]
-#[storage(NullStorage)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct Selected;
+
+impl Component for Selected {
+    type Storage = FlaggedStorage<Self, NullStorage<Self>>;
+}
+
+/// A global [`Resource`] listing every [`Selected`] entity, in the order they
+/// were selected.
+///
+/// The [`Selected`] component is the source of truth. The
+/// [`crate::systems::SelectionBookkeeping`] system keeps this list in sync
+/// with it, while [`select()`], [`deselect()`] and [`clear_selection()`]
+/// update both at once.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectionSet {
+    entities: Vec<Entity>,
+}
+
+impl SelectionSet {
+    /// Add an [`Entity`] to the end of the selection, returning `false` if it
+    /// was already selected.
+    pub fn add(&mut self, entity: Entity) -> bool {
+        if self.contains(entity) {
+            false
+        } else {
+            self.entities.push(entity);
+            true
+        }
+    }
+
+    /// Remove an [`Entity`] from the selection, returning `false` if it
+    /// wasn't selected.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let len = self.entities.len();
+        self.entities.retain(|&e| e != entity);
+        self.entities.len() != len
+    }
+
+    pub fn clear(&mut self) { self.entities.clear(); }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// Iterate over the selected entities, in the order they were selected.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+
+    pub fn len(&self) -> usize { self.entities.len() }
+
+    pub fn is_empty(&self) -> bool { self.entities.is_empty() }
+
+    pub(crate) fn retain<F>(&mut self, predicate: F)
+    where
+        F: FnMut(&Entity) -> bool,
+    {
+        self.entities.retain(predicate);
+    }
+}
+
+/// Mark an [`Entity`] as [`Selected`], updating the [`SelectionSet`]
+/// immediately.
+pub fn select(world: &World, entity: Entity) {
+    if !world.is_alive(entity) {
+        return;
+    }
+
+    let _ = world.write_storage::<Selected>().insert(entity, Selected);
+
+    if let Some(mut selection) = world.try_fetch_mut::<SelectionSet>() {
+        selection.add(entity);
+    }
+}
+
+/// Stop an [`Entity`] from being [`Selected`], updating the [`SelectionSet`]
+/// immediately.
+pub fn deselect(world: &World, entity: Entity) {
+    world.write_storage::<Selected>().remove(entity);
+
+    if let Some(mut selection) = world.try_fetch_mut::<SelectionSet>() {
+        selection.remove(entity);
+    }
+}
+
+/// Deselect everything.
+pub fn clear_selection(world: &World) {
+    world.write_storage::<Selected>().clear();
+
+    if let Some(mut selection) = world.try_fetch_mut::<SelectionSet>() {
+        selection.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_order_is_kept() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        world.insert(SelectionSet::default());
+        let ents: Vec<_> =
+            (0..3).map(|_| world.create_entity().build()).collect();
+
+        select(&world, ents[2]);
+        select(&world, ents[0]);
+        select(&world, ents[2]);
+
+        let got: Vec<_> = world.fetch::<SelectionSet>().iter().collect();
+        assert_eq!(got, vec![ents[2], ents[0]]);
+
+        deselect(&world, ents[2]);
+
+        let got: Vec<_> = world.fetch::<SelectionSet>().iter().collect();
+        assert_eq!(got, vec![ents[0]]);
+        assert!(!world.read_storage::<Selected>().contains(ents[2]));
+
+        clear_selection(&world);
+
+        assert!(world.fetch::<SelectionSet>().is_empty());
+        assert!(world.read_storage::<Selected>().is_empty());
+    }
+}
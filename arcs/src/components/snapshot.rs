@@ -0,0 +1,362 @@
+use crate::components::{
+    known_components, BlockTable, LineTypeTable, StyleDefaults, TextStyleTable,
+};
+use specs::prelude::*;
+use std::{any::Any, collections::HashMap, fmt::Debug};
+
+/// A copy of every entity and every known component in a [`World`], taken
+/// with [`snapshot()`] and put back with [`restore()`].
+///
+/// The drawing-wide tables ([`LineTypeTable`], [`TextStyleTable`],
+/// [`BlockTable`], and [`StyleDefaults`]) are captured as well.
+#[derive(Debug)]
+pub struct DrawingSnapshot {
+    /// The entities at the time of the snapshot, used to work out which
+    /// components belong together.
+    entities: Vec<Entity>,
+    components: Vec<Box<dyn StoredComponents>>,
+    line_types: Option<LineTypeTable>,
+    text_styles: Option<TextStyleTable>,
+    blocks: Option<BlockTable>,
+    style_defaults: Option<StyleDefaults>,
+}
+
+impl DrawingSnapshot {
+    /// How many entities were captured.
+    pub fn len(&self) -> usize { self.entities.len() }
+
+    pub fn is_empty(&self) -> bool { self.entities.is_empty() }
+
+    /// The entities at the time of the snapshot.
+    ///
+    /// These are only identifiers, and any components referring to other
+    /// entities (e.g. a [`crate::components::DrawingObject`]'s layer) use the
+    /// same identifiers.
+    pub fn entities(&self) -> &[Entity] { &self.entities }
+
+    /// Show every captured component to a [`SnapshotVisitor`] (e.g. to
+    /// serialise the drawing).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use arcs::components::*;
+    /// # use specs::prelude::*;
+    /// # use std::any::Any;
+    /// /// Collect the name of every entity.
+    /// #[derive(Default)]
+    /// struct Names(Vec<(Entity, String)>);
+    ///
+    /// impl SnapshotVisitor for Names {
+    ///     fn visit_component(&mut self, entity: Entity, component: &dyn Any) {
+    ///         if let Some(name) = component.downcast_ref::<Name>() {
+    ///             self.0.push((entity, name.as_str().to_string()));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// arcs::components::register(&mut world);
+    /// let layer = world.create_entity().with(Name::new("base")).build();
+    ///
+    /// let mut names = Names::default();
+    /// snapshot(&world).visit(&mut names);
+    ///
+    /// assert_eq!(names.0, vec![(layer, String::from("base"))]);
+    /// ```
+    pub fn visit<V: SnapshotVisitor>(&self, visitor: &mut V) {
+        for &entity in &self.entities {
+            visitor.visit_entity(entity);
+        }
+
+        for components in &self.components {
+            components.visit(&mut |entity, component| {
+                visitor.visit_component(entity, component)
+            });
+        }
+    }
+
+    /// The [`LineTypeTable`], if the [`World`] had one.
+    pub fn line_types(&self) -> Option<&LineTypeTable> {
+        self.line_types.as_ref()
+    }
+
+    /// The [`TextStyleTable`], if the [`World`] had one.
+    pub fn text_styles(&self) -> Option<&TextStyleTable> {
+        self.text_styles.as_ref()
+    }
+
+    /// The [`BlockTable`], if the [`World`] had one.
+    pub fn blocks(&self) -> Option<&BlockTable> { self.blocks.as_ref() }
+
+    /// The [`StyleDefaults`], if the [`World`] had them.
+    pub fn style_defaults(&self) -> Option<&StyleDefaults> {
+        self.style_defaults.as_ref()
+    }
+}
+
+/// Something which looks at the contents of a [`DrawingSnapshot`] using
+/// [`DrawingSnapshot::visit()`].
+///
+/// Every entity is visited first, followed by the components grouped by
+/// type.
+pub trait SnapshotVisitor {
+    /// Called once for each entity in the snapshot.
+    fn visit_entity(&mut self, _entity: Entity) {}
+
+    /// Called once for each component, which can be inspected with
+    /// [`Any::downcast_ref()`].
+    fn visit_component(&mut self, entity: Entity, component: &dyn Any);
+}
+
+impl Clone for DrawingSnapshot {
+    fn clone(&self) -> Self {
+        DrawingSnapshot {
+            entities: self.entities.clone(),
+            components: self
+                .components
+                .iter()
+                .map(|c| c.boxed_clone())
+                .collect(),
+            line_types: self.line_types.clone(),
+            text_styles: self.text_styles.clone(),
+            blocks: self.blocks.clone(),
+            style_defaults: self.style_defaults.clone(),
+        }
+    }
+}
+
+/// Take a copy of everything in the [`World`] (e.g. for a checkpoint or
+/// "revert to saved").
+pub fn snapshot(world: &World) -> DrawingSnapshot {
+    DrawingSnapshot {
+        entities: world.entities().join().collect(),
+        components: known_components().map(|c| c.snapshot(world)).collect(),
+        line_types: world.try_fetch::<LineTypeTable>().map(|t| (*t).clone()),
+        text_styles: world.try_fetch::<TextStyleTable>().map(|t| (*t).clone()),
+        blocks: world.try_fetch::<BlockTable>().map(|t| (*t).clone()),
+        style_defaults: world
+            .try_fetch::<StyleDefaults>()
+            .map(|t| (*t).clone()),
+    }
+}
+
+/// Replace everything in the [`World`] with the contents of a
+/// [`DrawingSnapshot`].
+///
+/// Every existing entity is deleted and the snapshot's entities are
+/// recreated, so the returned map says which new [`Entity`] each of the
+/// snapshot's entities became. References between components (e.g. a
+/// [`crate::components::DrawingObject`]'s layer) are updated to match.
+///
+/// Resources derived from components (e.g. the
+/// [`crate::components::NameTable`]) are brought up to date the next time
+/// their bookkeeping systems run.
+pub fn restore(
+    world: &mut World,
+    snapshot: &DrawingSnapshot,
+) -> HashMap<Entity, Entity> {
+    world.delete_all();
+    world.maintain();
+
+    let mapping = snapshot
+        .entities
+        .iter()
+        .map(|&original| (original, world.create_entity().build()))
+        .collect();
+    let map = EntityMap { mapping };
+
+    for components in &snapshot.components {
+        components.restore(world, &map);
+    }
+
+    if let Some(ref line_types) = snapshot.line_types {
+        world.insert(line_types.clone());
+    }
+    if let Some(ref text_styles) = snapshot.text_styles {
+        world.insert(text_styles.clone());
+    }
+    if let Some(ref blocks) = snapshot.blocks {
+        world.insert(blocks.clone());
+    }
+    if let Some(ref style_defaults) = snapshot.style_defaults {
+        world.insert(style_defaults.clone());
+    }
+
+    map.mapping
+}
+
+/// Which new [`Entity`] each entity in a [`DrawingSnapshot`] became.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EntityMap {
+    mapping: HashMap<Entity, Entity>,
+}
+
+impl EntityMap {
+    /// Look up what an [`Entity`] became, leaving entities which weren't in
+    /// the snapshot unchanged.
+    pub(crate) fn get(&self, entity: Entity) -> Entity {
+        self.mapping.get(&entity).copied().unwrap_or(entity)
+    }
+}
+
+/// A [`Component`] which refers to other entities.
+pub(crate) trait MapEntities {
+    /// Update every [`Entity`] this component refers to.
+    fn map_entities(&mut self, map: &EntityMap);
+}
+
+/// The copies of one type of component in a [`DrawingSnapshot`].
+pub(crate) trait StoredComponents: Debug + Send + Sync {
+    fn restore(&self, world: &World, map: &EntityMap);
+    fn visit(&self, visitor: &mut dyn FnMut(Entity, &dyn Any));
+    fn boxed_clone(&self) -> Box<dyn StoredComponents>;
+}
+
+#[derive(Debug)]
+pub(crate) struct Stored<T> {
+    values: Vec<(Entity, T)>,
+    map_entities: fn(&mut T, &EntityMap),
+}
+
+impl<T> Stored<T>
+where
+    T: Component + Clone,
+{
+    pub(crate) fn capture(
+        world: &World,
+        map_entities: fn(&mut T, &EntityMap),
+    ) -> Self {
+        let values = (&world.entities(), &world.read_storage::<T>())
+            .join()
+            .map(|(entity, value)| (entity, value.clone()))
+            .collect();
+
+        Stored {
+            values,
+            map_entities,
+        }
+    }
+}
+
+impl<T> StoredComponents for Stored<T>
+where
+    T: Component + Clone + Debug + Send + Sync,
+{
+    fn restore(&self, world: &World, map: &EntityMap) {
+        let mut storage = world.write_storage::<T>();
+
+        for (entity, value) in &self.values {
+            let mut value = value.clone();
+            (self.map_entities)(&mut value, map);
+            let _ = storage.insert(map.get(*entity), value);
+        }
+    }
+
+    fn visit(&self, visitor: &mut dyn FnMut(Entity, &dyn Any)) {
+        for (entity, value) in &self.values {
+            visitor(*entity, value);
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn StoredComponents> {
+        Box::new(Stored {
+            values: self.values.clone(),
+            map_entities: self.map_entities,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{DrawingObject, Geometry, Layer, Name, Parent},
+        Point,
+    };
+
+    #[test]
+    fn restore_entities_and_their_references() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world
+            .create_entity()
+            .with(Layer::default())
+            .with(Name::new("base"))
+            .build();
+        let point = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::new(1.0, 2.0)),
+                layer,
+            })
+            .build();
+        let label = world.create_entity().with(Parent(point)).build();
+        let saved = snapshot(&world);
+
+        world.delete_entity(point).unwrap();
+        world.create_entity().with(Layer::default()).build();
+        world.maintain();
+        let mapping = restore(&mut world, &saved.clone());
+
+        assert_eq!(world.entities().join().count(), 3);
+        let names = world.read_storage::<Name>();
+        assert_eq!(names.get(mapping[&layer]), Some(&Name::new("base")));
+        let objects = world.read_storage::<DrawingObject>();
+        let restored = objects.get(mapping[&point]).unwrap();
+        assert_eq!(restored.layer, mapping[&layer]);
+        assert_eq!(restored.geometry, Geometry::Point(Point::new(1.0, 2.0)));
+        let parents = world.read_storage::<Parent>();
+        assert_eq!(
+            parents.get(mapping[&label]),
+            Some(&Parent(mapping[&point]))
+        );
+    }
+
+    #[test]
+    fn visit_everything_in_a_snapshot() {
+        #[derive(Default)]
+        struct Collector {
+            entities: Vec<Entity>,
+            objects: Vec<(Entity, DrawingObject)>,
+            components: usize,
+        }
+
+        impl SnapshotVisitor for Collector {
+            fn visit_entity(&mut self, entity: Entity) {
+                self.entities.push(entity);
+            }
+
+            fn visit_component(&mut self, entity: Entity, component: &dyn Any) {
+                self.components += 1;
+                if let Some(object) = component.downcast_ref::<DrawingObject>()
+                {
+                    self.objects.push((entity, object.clone()));
+                }
+            }
+        }
+
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world
+            .create_entity()
+            .with(Layer::default())
+            .with(Name::new("base"))
+            .build();
+        let object = DrawingObject {
+            geometry: Geometry::Point(Point::new(1.0, 2.0)),
+            layer,
+        };
+        let point = world.create_entity().with(object.clone()).build();
+        let saved = snapshot(&world);
+        let mut collector = Collector::default();
+
+        saved.visit(&mut collector);
+
+        assert_eq!(collector.entities, saved.entities());
+        assert_eq!(collector.entities, vec![layer, point]);
+        assert_eq!(collector.objects, vec![(point, object)]);
+        assert_eq!(collector.components, 3);
+        assert!(saved.line_types().is_none());
+    }
+}
@@ -0,0 +1,449 @@
+use crate::{BoundingBox, DrawingSpace, Point};
+use specs::{world::Index, Entity};
+use std::collections::HashMap;
+
+#[allow(unused_imports)] // for rustdoc links
+use specs::prelude::Resource;
+
+type Bounds = BoundingBox<DrawingSpace>;
+
+/// How many items a node can hold before it is split into quadrants.
+const MAX_ITEMS_PER_NODE: usize = 8;
+/// Stop splitting nodes after this many levels, so lots of items stacked on
+/// top of each other don't create an infinitely deep tree.
+const MAX_DEPTH: usize = 16;
+
+/// A global [`Resource`] for quickly finding which entities are in (or near)
+/// part of the drawing, based on their [`BoundingBox`].
+///
+/// Entities are stored in a quadtree which grows to fit whatever is added to
+/// it. Objects which go on forever (e.g. [`crate::XLine`]s) can't be placed
+/// in the tree, so they are kept to one side and checked every time.
+///
+/// The [`crate::systems::SpatialIndexBookkeeping`] system keeps the index in
+/// sync with the [`BoundingBox`] components calculated by
+/// [`crate::systems::SyncBounds`].
+#[derive(Debug, Clone, Default)]
+pub struct SpatialIndex {
+    root: Option<Node>,
+    unbounded: Vec<(Entity, Bounds)>,
+    locations: HashMap<Index, (Entity, Bounds)>,
+}
+
+impl SpatialIndex {
+    /// Add an [`Entity`] to the index, replacing any previous location.
+    pub fn insert(&mut self, entity: Entity, bounds: Bounds) {
+        self.remove_by_id(entity.id());
+        self.locations.insert(entity.id(), (entity, bounds));
+
+        if !is_finite(bounds) {
+            self.unbounded.push((entity, bounds));
+            return;
+        }
+
+        match self.root {
+            Some(ref root) if root.bounds.fully_contains(bounds) => {},
+            _ => self.grow_to_fit(bounds),
+        }
+
+        if let Some(ref mut root) = self.root {
+            root.insert(entity, bounds, 0);
+        }
+    }
+
+    /// Remove an [`Entity`] from the index, returning where it was.
+    pub fn remove(&mut self, entity: Entity) -> Option<Bounds> {
+        match self.locations.get(&entity.id()) {
+            Some(&(existing, _)) if existing == entity => {
+                self.remove_by_id(entity.id())
+            },
+            _ => None,
+        }
+    }
+
+    /// Remove whichever [`Entity`] has a particular [`Index`] (e.g. from a
+    /// [`specs::storage::ComponentEvent`]).
+    pub(crate) fn remove_by_id(&mut self, id: Index) -> Option<Bounds> {
+        let (entity, bounds) = self.locations.remove(&id)?;
+
+        if is_finite(bounds) {
+            if let Some(ref mut root) = self.root {
+                root.remove(entity, bounds);
+            }
+        } else {
+            self.unbounded.retain(|&(ent, _)| ent != entity);
+        }
+
+        Some(bounds)
+    }
+
+    /// Where was an [`Entity`] when it was added to the index?
+    pub fn get(&self, entity: Entity) -> Option<Bounds> {
+        self.locations
+            .get(&entity.id())
+            .filter(|(existing, _)| *existing == entity)
+            .map(|&(_, bounds)| bounds)
+    }
+
+    /// Iterate over every [`Entity`] in the index, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Bounds)> + '_ {
+        self.locations.values().copied()
+    }
+
+    pub fn len(&self) -> usize { self.locations.len() }
+
+    pub fn is_empty(&self) -> bool { self.locations.is_empty() }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.unbounded.clear();
+        self.locations.clear();
+    }
+
+    /// Find every [`Entity`] whose [`BoundingBox`] overlaps with `region`.
+    pub fn query_region(&self, region: Bounds) -> Vec<Entity> {
+        let mut found: Vec<Entity> = self
+            .unbounded
+            .iter()
+            .filter(|(_, bounds)| bounds.intersects_with(region))
+            .map(|&(entity, _)| entity)
+            .collect();
+
+        if let Some(ref root) = self.root {
+            root.query(region, &mut found);
+        }
+
+        found
+    }
+
+    /// Find every [`Entity`] whose [`BoundingBox`] contains `point`.
+    pub fn query_point(&self, point: Point) -> Vec<Entity> {
+        self.query_region(BoundingBox::new(point, point))
+    }
+
+    /// Find the [`Entity`] with the closest [`BoundingBox`] to `point`.
+    pub fn nearest(&self, point: Point) -> Option<Entity> {
+        self.nearest_by(point, |_, bounds| Some(distance_to(bounds, point)))
+    }
+
+    /// Find the [`Entity`] which is closest to `point`, according to
+    /// `distance`.
+    ///
+    /// The `distance` function is only called for entities which could
+    /// possibly be closer than the best one found so far, so it can be
+    /// relatively expensive (e.g. the distance to the entity's actual
+    /// geometry). It should never be less than the distance to the entity's
+    /// [`BoundingBox`], and may return [`None`] to skip an entity.
+    pub fn nearest_by<F>(&self, point: Point, mut distance: F) -> Option<Entity>
+    where
+        F: FnMut(Entity, Bounds) -> Option<f64>,
+    {
+        let mut best: Option<(Entity, f64)> = None;
+
+        for &(entity, bounds) in &self.unbounded {
+            consider(&mut best, entity, distance(entity, bounds));
+        }
+
+        if let Some(ref root) = self.root {
+            root.nearest(point, &mut distance, &mut best);
+        }
+
+        best.map(|(entity, _)| entity)
+    }
+
+    /// Replace the root with a bigger one which also contains `bounds`,
+    /// adding everything back in.
+    fn grow_to_fit(&mut self, bounds: Bounds) {
+        let mut items = Vec::new();
+        let area = match self.root.take() {
+            Some(root) => {
+                let area = BoundingBox::merge(root.bounds, bounds);
+                root.drain_into(&mut items);
+                area
+            },
+            None => bounds,
+        };
+
+        // leave plenty of room so the tree doesn't need to grow often
+        let side = area.width().get().max(area.height().get()).max(1.0) * 2.0;
+        let centre = area.bottom_left() + area.diagonal() / 2.0;
+        let mut root = Node::new(BoundingBox::from_centre_and_dimensions(
+            centre,
+            euclid::Length::new(side),
+            euclid::Length::new(side),
+        ));
+
+        for (entity, bounds) in items {
+            root.insert(entity, bounds, 0);
+        }
+
+        self.root = Some(root);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: Bounds,
+    items: Vec<(Entity, Bounds)>,
+    children: Option<Box<[Node; 4]>>,
+}
+
+impl Node {
+    fn new(bounds: Bounds) -> Self {
+        Node {
+            bounds,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    fn insert(&mut self, entity: Entity, bounds: Bounds, depth: usize) {
+        if let Some(child) = self.child_containing(bounds) {
+            child.insert(entity, bounds, depth + 1);
+            return;
+        }
+
+        self.items.push((entity, bounds));
+
+        if self.children.is_none()
+            && self.items.len() > MAX_ITEMS_PER_NODE
+            && depth < MAX_DEPTH
+        {
+            self.split(depth);
+        }
+    }
+
+    fn split(&mut self, depth: usize) {
+        let bl = self.bounds.bottom_left();
+        let tr = self.bounds.top_right();
+        let centre = bl + self.bounds.diagonal() / 2.0;
+
+        self.children = Some(Box::new([
+            Node::new(BoundingBox::new(bl, centre)),
+            Node::new(BoundingBox::new(centre, tr)),
+            Node::new(BoundingBox::new(
+                Point::new(centre.x, bl.y),
+                Point::new(tr.x, centre.y),
+            )),
+            Node::new(BoundingBox::new(
+                Point::new(bl.x, centre.y),
+                Point::new(centre.x, tr.y),
+            )),
+        ]));
+
+        for (entity, bounds) in std::mem::take(&mut self.items) {
+            self.insert(entity, bounds, depth);
+        }
+    }
+
+    fn child_containing(&mut self, bounds: Bounds) -> Option<&mut Node> {
+        self.children
+            .as_mut()?
+            .iter_mut()
+            .find(|child| child.bounds.fully_contains(bounds))
+    }
+
+    fn remove(&mut self, entity: Entity, bounds: Bounds) -> bool {
+        if let Some(ix) = self.items.iter().position(|&(e, _)| e == entity) {
+            self.items.swap_remove(ix);
+            return true;
+        }
+
+        match self.child_containing(bounds) {
+            Some(child) => child.remove(entity, bounds),
+            None => false,
+        }
+    }
+
+    fn query(&self, region: Bounds, found: &mut Vec<Entity>) {
+        if !self.bounds.intersects_with(region) {
+            return;
+        }
+
+        found.extend(
+            self.items
+                .iter()
+                .filter(|(_, bounds)| bounds.intersects_with(region))
+                .map(|&(entity, _)| entity),
+        );
+
+        for child in self.children.iter().flat_map(|c| c.iter()) {
+            child.query(region, found);
+        }
+    }
+
+    fn nearest<F>(
+        &self,
+        point: Point,
+        distance: &mut F,
+        best: &mut Option<(Entity, f64)>,
+    ) where
+        F: FnMut(Entity, Bounds) -> Option<f64>,
+    {
+        let could_be_closer = |bounds: Bounds, best: &Option<(Entity, f64)>| {
+            best.map_or(true, |(_, d)| distance_to(bounds, point) < d)
+        };
+
+        if !could_be_closer(self.bounds, best) {
+            return;
+        }
+
+        for &(entity, bounds) in &self.items {
+            if could_be_closer(bounds, best) {
+                consider(best, entity, distance(entity, bounds));
+            }
+        }
+
+        if let Some(ref children) = self.children {
+            // visit the closest quadrants first so we can skip the others
+            let mut children: Vec<_> = children.iter().collect();
+            children.sort_by(|a, b| {
+                distance_to(a.bounds, point)
+                    .partial_cmp(&distance_to(b.bounds, point))
+                    .unwrap()
+            });
+
+            for child in children {
+                child.nearest(point, distance, best);
+            }
+        }
+    }
+
+    fn drain_into(self, items: &mut Vec<(Entity, Bounds)>) {
+        items.extend(self.items);
+
+        if let Some(children) = self.children {
+            let children: [Node; 4] = *children;
+
+            for child in children {
+                child.drain_into(items);
+            }
+        }
+    }
+}
+
+fn consider(
+    best: &mut Option<(Entity, f64)>,
+    entity: Entity,
+    distance: Option<f64>,
+) {
+    match (distance, *best) {
+        (Some(d), Some((_, best_so_far))) if d >= best_so_far => {},
+        (Some(d), _) if !d.is_nan() => *best = Some((entity, d)),
+        _ => {},
+    }
+}
+
+fn is_finite(bounds: Bounds) -> bool {
+    bounds.bottom_left().to_vector().square_length().is_finite()
+        && bounds.top_right().to_vector().square_length().is_finite()
+}
+
+/// How far `point` is from the closest part of `bounds`.
+fn distance_to(bounds: Bounds, point: Point) -> f64 {
+    let dx = (bounds.min_x() - point.x)
+        .max(point.x - bounds.max_x())
+        .max(0.0);
+    let dy = (bounds.min_y() - point.y)
+        .max(point.y - bounds.max_y())
+        .max(0.0);
+
+    dx.hypot(dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::prelude::*;
+
+    fn bounds(x: f64, y: f64, width: f64, height: f64) -> Bounds {
+        BoundingBox::new(Point::new(x, y), Point::new(x + width, y + height))
+    }
+
+    fn index_with_grid(world: &mut World, n: usize) -> SpatialIndex {
+        let mut index = SpatialIndex::default();
+
+        for i in 0..n {
+            for j in 0..n {
+                let entity = world.create_entity().build();
+                index.insert(
+                    entity,
+                    bounds(i as f64 * 10.0, j as f64 * 10.0, 1.0, 1.0),
+                );
+            }
+        }
+
+        index
+    }
+
+    #[test]
+    fn query_a_region() {
+        let mut world = World::new();
+        let index = index_with_grid(&mut world, 20);
+
+        let got = index.query_region(bounds(15.0, 15.0, 20.0, 10.0));
+
+        // x in {20, 30}, y in {20}
+        assert_eq!(got.len(), 2);
+        for entity in got {
+            let b = index.get(entity).unwrap();
+            assert!(b.intersects_with(bounds(15.0, 15.0, 20.0, 10.0)));
+        }
+        assert_eq!(index.len(), 400);
+    }
+
+    #[test]
+    fn removed_entities_are_forgotten() {
+        let mut world = World::new();
+        let mut index = index_with_grid(&mut world, 10);
+        let point = Point::new(50.5, 50.5);
+        let target = index.query_point(point)[0];
+
+        assert!(index.remove(target).is_some());
+
+        assert!(index.query_point(point).is_empty());
+        assert!(index.get(target).is_none());
+        assert_eq!(index.len(), 99);
+    }
+
+    #[test]
+    fn the_tree_grows_to_fit() {
+        let mut world = World::new();
+        let mut index = index_with_grid(&mut world, 4);
+        let far_away = world.create_entity().build();
+
+        index.insert(far_away, bounds(1e6, -1e6, 5.0, 5.0));
+
+        assert_eq!(
+            index.query_point(Point::new(1e6 + 1.0, -1e6)),
+            vec![far_away]
+        );
+        assert_eq!(index.query_region(bounds(0.0, 0.0, 31.0, 31.0)).len(), 16);
+    }
+
+    #[test]
+    fn find_the_nearest_entity() {
+        let mut world = World::new();
+        let mut index = index_with_grid(&mut world, 10);
+        let expected = index.query_point(Point::new(40.5, 60.5))[0];
+        let everywhere = world.create_entity().build();
+        index.insert(
+            everywhere,
+            BoundingBox::new(
+                Point::new(std::f64::NEG_INFINITY, std::f64::NEG_INFINITY),
+                Point::new(std::f64::INFINITY, std::f64::INFINITY),
+            ),
+        );
+
+        let got = index.nearest_by(Point::new(43.0, 62.0), |entity, b| {
+            if entity == everywhere {
+                None
+            } else {
+                Some(distance_to(b, Point::new(43.0, 62.0)))
+            }
+        });
+
+        assert_eq!(got, Some(expected));
+        assert_eq!(index.nearest(Point::new(43.0, 62.0)), Some(everywhere));
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{BoundingBox, DrawingSpace};
+use specs::{world::Index, Entity};
+use std::collections::{BTreeMap, HashMap};
+
+#[allow(unused_imports)] // for rustdoc links
+use specs::prelude::Resource;
+
+type Bounds = BoundingBox<DrawingSpace>;
+
+/// A global [`Resource`] with summary information about the drawing, useful
+/// for status bars and sanity checks after an import.
+///
+/// The [`crate::systems::UpdateStatistics`] system keeps this up to date as
+/// [`crate::components::DrawingObject`]s are added, changed, and removed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Statistics {
+    objects: HashMap<Index, Summary>,
+    by_kind: BTreeMap<&'static str, usize>,
+    by_layer: HashMap<Entity, usize>,
+    extents: Option<Bounds>,
+    /// Set when an object on the edge of the [`Statistics::extents()`] was
+    /// removed, so they need to be recalculated.
+    extents_need_updating: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Summary {
+    kind: &'static str,
+    layer: Entity,
+    bounds: Option<Bounds>,
+}
+
+impl Statistics {
+    /// The total number of [`crate::components::DrawingObject`]s.
+    pub fn total(&self) -> usize { self.objects.len() }
+
+    /// How many objects there are of a particular
+    /// [`crate::components::Geometry::kind()`].
+    pub fn count_of_kind(&self, kind: &str) -> usize {
+        self.by_kind.get(kind).copied().unwrap_or(0)
+    }
+
+    /// Iterate over the number of objects of each kind, ordered by kind.
+    pub fn by_kind(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.by_kind.iter().map(|(&kind, &count)| (kind, count))
+    }
+
+    /// How many objects are on a particular layer.
+    pub fn count_on_layer(&self, layer: Entity) -> usize {
+        self.by_layer.get(&layer).copied().unwrap_or(0)
+    }
+
+    /// Iterate over the number of objects on each layer.
+    pub fn by_layer(&self) -> impl Iterator<Item = (Entity, usize)> + '_ {
+        self.by_layer.iter().map(|(&layer, &count)| (layer, count))
+    }
+
+    /// A [`BoundingBox`] around every object, ignoring objects which go on
+    /// forever.
+    pub fn extents(&self) -> Option<Bounds> { self.extents }
+
+    /// Record an object, replacing anything previously recorded for it.
+    pub(crate) fn insert(
+        &mut self,
+        entity: Entity,
+        kind: &'static str,
+        layer: Entity,
+        bounds: Bounds,
+    ) {
+        self.remove_by_id(entity.id());
+
+        let bounds = if is_finite(bounds) {
+            Some(bounds)
+        } else {
+            None
+        };
+        *self.by_kind.entry(kind).or_default() += 1;
+        *self.by_layer.entry(layer).or_default() += 1;
+        if let Some(bounds) = bounds {
+            self.extents = Some(match self.extents {
+                Some(extents) => BoundingBox::merge(extents, bounds),
+                None => bounds,
+            });
+        }
+
+        self.objects.insert(
+            entity.id(),
+            Summary {
+                kind,
+                layer,
+                bounds,
+            },
+        );
+    }
+
+    pub(crate) fn remove_by_id(&mut self, id: Index) {
+        let summary = match self.objects.remove(&id) {
+            Some(s) => s,
+            None => return,
+        };
+
+        if let Some(count) = self.by_kind.get_mut(summary.kind) {
+            *count -= 1;
+            if *count == 0 {
+                self.by_kind.remove(summary.kind);
+            }
+        }
+        if let Some(count) = self.by_layer.get_mut(&summary.layer) {
+            *count -= 1;
+            if *count == 0 {
+                self.by_layer.remove(&summary.layer);
+            }
+        }
+
+        // the extents only shrink if the object was touching the edge
+        if let (Some(bounds), Some(extents)) = (summary.bounds, self.extents) {
+            if !strictly_inside(bounds, extents) {
+                self.extents_need_updating = true;
+            }
+        }
+    }
+
+    /// Recalculate the extents if an object on the edge was removed.
+    ///
+    /// This needs to look at every object, so it's done once after a batch
+    /// of changes instead of in [`Statistics::remove_by_id()`].
+    pub(crate) fn update_extents(&mut self) {
+        if self.extents_need_updating {
+            self.extents = BoundingBox::around(
+                self.objects.values().filter_map(|s| s.bounds),
+            );
+            self.extents_need_updating = false;
+        }
+    }
+
+    pub(crate) fn clear(&mut self) { *self = Statistics::default(); }
+}
+
+fn is_finite(bounds: Bounds) -> bool {
+    bounds.width().get().is_finite() && bounds.height().get().is_finite()
+}
+
+fn strictly_inside(inner: Bounds, outer: Bounds) -> bool {
+    inner.min_x() > outer.min_x()
+        && inner.min_y() > outer.min_y()
+        && inner.max_x() < outer.max_x()
+        && inner.max_y() < outer.max_y()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+    use specs::prelude::*;
+
+    #[test]
+    fn extents_shrink_when_an_outer_object_is_removed() {
+        let mut world = World::new();
+        let layer = world.create_entity().build();
+        let small = world.create_entity().build();
+        let big = world.create_entity().build();
+        let mut stats = Statistics::default();
+
+        stats.insert(
+            small,
+            "Line",
+            layer,
+            BoundingBox::new(Point::new(1.0, 1.0), Point::new(2.0, 2.0)),
+        );
+        stats.insert(
+            big,
+            "Arc",
+            layer,
+            BoundingBox::new(Point::zero(), Point::new(10.0, 10.0)),
+        );
+        assert_eq!(stats.total(), 2);
+        assert_eq!(stats.count_on_layer(layer), 2);
+        assert_eq!(stats.count_of_kind("Arc"), 1);
+
+        stats.remove_by_id(big.id());
+        stats.update_extents();
+
+        assert_eq!(stats.count_of_kind("Arc"), 0);
+        assert_eq!(stats.by_kind().collect::<Vec<_>>(), vec![("Line", 1)]);
+        assert_eq!(
+            stats.extents(),
+            Some(BoundingBox::new(Point::new(1.0, 1.0), Point::new(2.0, 2.0)))
+        );
+    }
+
+    #[test]
+    fn extents_are_only_recalculated_once_per_batch() {
+        let mut world = World::new();
+        let layer = world.create_entity().build();
+        let mut stats = Statistics::default();
+        let mut add = |x: f64| {
+            let ent = world.create_entity().build();
+            stats.insert(
+                ent,
+                "Point",
+                layer,
+                BoundingBox::new(Point::new(x, x), Point::new(x, x)),
+            );
+            ent
+        };
+        let _first = add(0.0);
+        let second = add(1.0);
+        let third = add(2.0);
+
+        stats.remove_by_id(third.id());
+        stats.remove_by_id(second.id());
+        assert!(stats.extents_need_updating);
+        stats.update_extents();
+
+        assert!(!stats.extents_need_updating);
+        assert_eq!(
+            stats.extents(),
+            Some(BoundingBox::new(Point::zero(), Point::zero()))
+        );
+    }
+}
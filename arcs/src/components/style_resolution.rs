@@ -0,0 +1,369 @@
+//! Working out how an entity should be drawn.
+//!
+//! Styles can be set on the entity itself, inherited from its [`Layer`], or
+//! taken from the drawing-wide [`StyleDefaults`]. The rules are implemented
+//! once here so every renderer and exporter draws things the same way.
+
+use crate::components::{
+    Colour, ConstructionGeometry, Dimension, DrawingObject, Layer, LineStyle,
+    LineType, LineTypeRef, LineTypeTable, LineWeight,
+};
+use piet::Color;
+use shred_derive::SystemData;
+use specs::prelude::*;
+
+/// A global [`Resource`] with the styles used when neither an entity nor its
+/// [`Layer`] specify one.
+#[derive(Debug, Clone)]
+pub struct StyleDefaults {
+    /// The width used for lines without a [`LineWeight`].
+    pub line_weight: Dimension,
+    /// The foreground colour, used for [`Colour::ByBlock`] entities outside
+    /// a block and for [`Colour::FOREGROUND`].
+    pub colour: Color,
+    /// The colour [`ConstructionGeometry`] is drawn with, regardless of its
+    /// own [`Colour`] or its [`Layer`]'s.
+    pub construction_colour: Color,
+    /// The name of the [`LineType`] [`ConstructionGeometry`] is drawn with.
+    pub construction_line_type: String,
+}
+
+impl Default for StyleDefaults {
+    fn default() -> StyleDefaults {
+        StyleDefaults {
+            line_weight: Dimension::default(),
+            colour: Color::BLACK,
+            construction_colour: Color::rgb8(0x80, 0x80, 0x80),
+            construction_line_type: String::from("Dashed"),
+        }
+    }
+}
+
+/// The final style an entity should be drawn with.
+#[derive(Debug, Clone)]
+pub struct ResolvedStyle {
+    /// The colour to draw with.
+    pub colour: Color,
+    /// How thick the entity's lines are.
+    pub line_weight: Dimension,
+    /// The dash pattern to use, or [`None`] for continuous lines.
+    pub line_type: Option<LineType>,
+}
+
+/// Everything needed to resolve an entity's style.
+#[derive(SystemData)]
+#[allow(missing_debug_implementations)]
+pub struct StyleSources<'world> {
+    layers: ReadStorage<'world, Layer>,
+    line_styles: ReadStorage<'world, LineStyle>,
+    line_weights: ReadStorage<'world, LineWeight>,
+    colours: ReadStorage<'world, Colour>,
+    line_type_refs: ReadStorage<'world, LineTypeRef>,
+    construction: ReadStorage<'world, ConstructionGeometry>,
+    line_types: Option<Read<'world, LineTypeTable>>,
+    defaults: Option<Read<'world, StyleDefaults>>,
+}
+
+impl<'world> StyleSources<'world> {
+    /// Work out the style for an entity on a particular `layer`.
+    ///
+    /// [`ConstructionGeometry`] always uses the construction colour and line
+    /// type from [`StyleDefaults`] so it stands out from the real drawing.
+    pub fn resolve(&self, entity: Entity, layer: Entity) -> ResolvedStyle {
+        if self.construction.contains(entity) {
+            let defaults =
+                self.defaults.as_deref().cloned().unwrap_or_default();
+
+            let line_type = self.line_types.as_ref().and_then(|table| {
+                table.get(&defaults.construction_line_type).cloned()
+            });
+
+            return ResolvedStyle {
+                colour: defaults.construction_colour,
+                line_weight: self.line_weight(entity, layer),
+                line_type,
+            };
+        }
+
+        ResolvedStyle {
+            colour: self.colour(entity, layer),
+            line_weight: self.line_weight(entity, layer),
+            line_type: self.line_type(entity, layer).cloned(),
+        }
+    }
+
+    /// An entity's line weight comes from (in order of priority):
+    ///
+    /// 1. The entity's [`LineWeight`]
+    /// 2. The width of the entity's [`LineStyle`]
+    /// 3. The layer's [`Layer::line_weight`]
+    /// 4. The width of the layer's [`LineStyle`]
+    /// 5. [`StyleDefaults::line_weight`]
+    fn line_weight(&self, entity: Entity, layer: Entity) -> Dimension {
+        let by_layer = || {
+            self.layers
+                .get(layer)
+                .and_then(|l| l.line_weight)
+                .or_else(|| self.line_styles.get(layer).map(|s| s.width))
+                .unwrap_or_else(|| self.default_line_weight())
+        };
+
+        match self.line_weights.get(entity) {
+            Some(LineWeight::Width(width)) => *width,
+            Some(LineWeight::Default) => self.default_line_weight(),
+            Some(LineWeight::ByLayer) => by_layer(),
+            None => self
+                .line_styles
+                .get(entity)
+                .map(|s| s.width)
+                .unwrap_or_else(by_layer),
+        }
+    }
+
+    fn default_line_weight(&self) -> Dimension {
+        self.defaults
+            .as_ref()
+            .map(|d| d.line_weight)
+            .unwrap_or_else(|| StyleDefaults::default().line_weight)
+    }
+
+    /// An entity's colour comes from (in order of priority):
+    ///
+    /// 1. The entity's [`Colour`]
+    /// 2. The stroke of the entity's [`LineStyle`]
+    /// 3. The stroke of the layer's [`LineStyle`]
+    /// 4. The layer's [`Layer::colour`]
+    /// 5. [`StyleDefaults::colour`]
+    fn colour(&self, entity: Entity, layer: Entity) -> Color {
+        let by_layer = || {
+            self.line_styles
+                .get(layer)
+                .map(|s| s.stroke.clone())
+                .or_else(|| self.layers.get(layer).map(|l| l.colour.clone()))
+                .unwrap_or_else(|| self.foreground())
+        };
+
+        match self.colours.get(entity) {
+            Some(Colour::Rgb(r, g, b)) => Color::rgb8(*r, *g, *b),
+            Some(Colour::Indexed(index)) => {
+                Colour::palette(*index).unwrap_or_else(|| self.foreground())
+            },
+            Some(Colour::ByBlock) => self.foreground(),
+            Some(Colour::ByLayer) => by_layer(),
+            None => self
+                .line_styles
+                .get(entity)
+                .map(|s| s.stroke.clone())
+                .unwrap_or_else(by_layer),
+        }
+    }
+
+    fn foreground(&self) -> Color {
+        self.defaults
+            .as_ref()
+            .map(|d| d.colour.clone())
+            .unwrap_or_else(|| StyleDefaults::default().colour)
+    }
+
+    fn line_type(&self, entity: Entity, layer: Entity) -> Option<&LineType> {
+        self.line_types
+            .as_ref()?
+            .resolve(self.line_type_refs.get(entity), self.layers.get(layer))
+    }
+}
+
+/// Work out how an entity's [`DrawingObject`] should be drawn, returning
+/// [`None`] if it has no [`DrawingObject`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use arcs::{components::*, Point};
+/// # use specs::prelude::*;
+/// let mut world = World::new();
+/// arcs::components::register(&mut world);
+/// let layer = world
+///     .create_entity()
+///     .with(Layer {
+///         line_weight: Some(Dimension::Pixels(3.0)),
+///         ..Default::default()
+///     })
+///     .build();
+/// let point = Geometry::Point(Point::zero());
+/// let by_layer = world
+///     .create_entity()
+///     .with(DrawingObject { geometry: point.clone(), layer })
+///     .build();
+/// let thin = world
+///     .create_entity()
+///     .with(DrawingObject { geometry: point, layer })
+///     .with(LineWeight::Width(Dimension::Pixels(0.5)))
+///     .build();
+///
+/// let got = resolved_style(&world, by_layer).unwrap();
+/// assert_eq!(got.line_weight, Dimension::Pixels(3.0));
+///
+/// let got = resolved_style(&world, thin).unwrap();
+/// assert_eq!(got.line_weight, Dimension::Pixels(0.5));
+/// ```
+pub fn resolved_style(world: &World, entity: Entity) -> Option<ResolvedStyle> {
+    let layer = world.read_storage::<DrawingObject>().get(entity)?.layer;
+    let sources = world.system_data::<StyleSources<'_>>();
+
+    Some(sources.resolve(entity, layer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::Geometry, Point};
+
+    fn world_with_layer(layer: Layer) -> (World, Entity) {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().with(layer).build();
+
+        (world, layer)
+    }
+
+    fn object(world: &mut World, layer: Entity) -> EntityBuilder<'_> {
+        world.create_entity().with(DrawingObject {
+            geometry: Geometry::Point(Point::zero()),
+            layer,
+        })
+    }
+
+    #[test]
+    fn fall_back_to_the_global_default() {
+        let (mut world, layer) = world_with_layer(Layer::default());
+        world.insert(StyleDefaults {
+            line_weight: Dimension::Pixels(2.0),
+            ..Default::default()
+        });
+        let entity = object(&mut world, layer).build();
+
+        let got = resolved_style(&world, entity).unwrap();
+
+        assert_eq!(got.line_weight, Dimension::Pixels(2.0));
+    }
+
+    #[test]
+    fn explicitly_use_the_default() {
+        let (mut world, layer) = world_with_layer(Layer {
+            line_weight: Some(Dimension::Pixels(4.0)),
+            ..Default::default()
+        });
+        let entity = object(&mut world, layer)
+            .with(LineWeight::Default)
+            .with(LineStyle {
+                width: Dimension::Pixels(8.0),
+                ..Default::default()
+            })
+            .build();
+
+        let got = resolved_style(&world, entity).unwrap();
+
+        assert_eq!(got.line_weight, StyleDefaults::default().line_weight);
+    }
+
+    #[test]
+    fn line_styles_still_override_the_layer() {
+        let (mut world, layer) = world_with_layer(Layer {
+            line_weight: Some(Dimension::Pixels(4.0)),
+            ..Default::default()
+        });
+        let styled = object(&mut world, layer)
+            .with(LineStyle {
+                width: Dimension::Pixels(8.0),
+                ..Default::default()
+            })
+            .build();
+        let by_layer = object(&mut world, layer)
+            .with(LineStyle {
+                width: Dimension::Pixels(8.0),
+                ..Default::default()
+            })
+            .with(LineWeight::ByLayer)
+            .build();
+
+        assert_eq!(
+            resolved_style(&world, styled).unwrap().line_weight,
+            Dimension::Pixels(8.0)
+        );
+        assert_eq!(
+            resolved_style(&world, by_layer).unwrap().line_weight,
+            Dimension::Pixels(4.0)
+        );
+    }
+
+    #[test]
+    fn resolve_the_layers_line_type() {
+        let (mut world, layer) = world_with_layer(Layer {
+            line_type: String::from("Dashed"),
+            ..Default::default()
+        });
+        world.insert(LineTypeTable::default());
+        let entity = object(&mut world, layer).build();
+
+        let got = resolved_style(&world, entity).unwrap();
+
+        assert_eq!(got.line_type.unwrap().name, "Dashed");
+    }
+
+    #[test]
+    fn entities_need_a_drawing_object() {
+        let (mut world, _) = world_with_layer(Layer::default());
+        let entity = world.create_entity().build();
+
+        assert!(resolved_style(&world, entity).is_none());
+    }
+
+    #[test]
+    fn colours_by_layer_and_by_block() {
+        let (mut world, layer) = world_with_layer(Layer {
+            colour: Color::rgb8(0, 0, 255),
+            ..Default::default()
+        });
+        world.insert(StyleDefaults {
+            colour: Color::WHITE,
+            ..Default::default()
+        });
+        let implicit = object(&mut world, layer).build();
+        let by_block = object(&mut world, layer).with(Colour::ByBlock).build();
+        let foreground = object(&mut world, layer)
+            .with(Colour::Indexed(Colour::FOREGROUND))
+            .build();
+        let explicit = object(&mut world, layer)
+            .with(Colour::Rgb(0x12, 0x34, 0x56))
+            .build();
+        let rgba = |entity| {
+            resolved_style(&world, entity).unwrap().colour.as_rgba_u32()
+        };
+
+        assert_eq!(rgba(implicit), 0x0000ffff);
+        assert_eq!(rgba(by_block), 0xffffffff);
+        assert_eq!(rgba(foreground), 0xffffffff);
+        assert_eq!(rgba(explicit), 0x123456ff);
+    }
+
+    #[test]
+    fn construction_geometry_stands_out() {
+        let (mut world, layer) = world_with_layer(Layer {
+            colour: Color::rgb8(0, 0, 255),
+            ..Default::default()
+        });
+        world.insert(LineTypeTable::default());
+        let entity = object(&mut world, layer)
+            .with(Colour::Rgb(0xff, 0, 0))
+            .with(ConstructionGeometry)
+            .build();
+
+        let got = resolved_style(&world, entity).unwrap();
+
+        assert_eq!(
+            got.colour.as_rgba_u32(),
+            StyleDefaults::default().construction_colour.as_rgba_u32()
+        );
+        assert_eq!(got.line_type.unwrap().name, "Dashed");
+    }
+}
@@ -7,18 +7,42 @@ use specs_derive::Component;
 #[storage(DenseVecStorage)]
 pub struct PointStyle {
     pub colour: Color,
+    /// How big the marker is, either in pixels or drawing units.
     pub radius: Dimension,
+    /// The marker used to draw the point.
+    pub shape: PointShape,
 }
 
 impl Default for PointStyle {
     fn default() -> PointStyle {
         PointStyle {
             colour: Color::BLACK,
-            radius: Dimension::default(),
+            radius: Dimension::Pixels(3.0),
+            shape: PointShape::default(),
         }
     }
 }
 
+/// The different markers a [`crate::Point`] can be displayed with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PointShape {
+    /// A filled circle.
+    Dot,
+    /// An empty circle.
+    Circle,
+    /// A horizontal and vertical line ("+").
+    Plus,
+    /// Two diagonal lines ("×").
+    X,
+    /// A [`PointShape::Plus`] inside a [`PointShape::Circle`], like a
+    /// crosshair.
+    Cross,
+}
+
+impl Default for PointShape {
+    fn default() -> Self { PointShape::Dot }
+}
+
 #[derive(Debug, Clone, Component)]
 #[storage(DenseVecStorage)]
 pub struct LineStyle {
@@ -35,6 +59,27 @@ impl Default for LineStyle {
     }
 }
 
+/// How thick an entity's lines should be.
+///
+/// Entities without a [`LineWeight`] use the width from their [`LineStyle`]
+/// (if they have one) or their layer's line weight. See
+/// [`crate::components::resolved_style()`] for the full set of rules.
+#[derive(Debug, Copy, Clone, PartialEq, Component)]
+#[storage(DenseVecStorage)]
+pub enum LineWeight {
+    /// Use the layer's line weight.
+    ByLayer,
+    /// Use the drawing's default line weight (see
+    /// [`crate::components::StyleDefaults`]).
+    Default,
+    /// A specific width.
+    Width(Dimension),
+}
+
+impl Default for LineWeight {
+    fn default() -> LineWeight { LineWeight::ByLayer }
+}
+
 #[derive(Debug, Clone, Component)]
 #[storage(HashMapStorage)]
 pub struct WindowStyle {
@@ -0,0 +1,353 @@
+use crate::{
+    algorithms::{
+        AffineTransform, Bounded, Closest, ClosestPoint, TransformError,
+        Transformable, Translate,
+    },
+    Angle, BoundingBox, DrawingSpace, Point, Vector,
+};
+
+/// A single line of text (e.g. a DXF `TEXT` entity).
+///
+/// The [`Text::height`] is the height of a capital letter in drawing units.
+/// Without access to font metrics we can only estimate how wide the text will
+/// be, so [`Text::estimated_width()`] assumes characters are roughly
+/// [`Text::WIDTH_FACTOR`] times as wide as they are tall.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    /// The point the text is anchored to.
+    pub insertion_point: Point,
+    /// The height of a capital letter.
+    pub height: f64,
+    /// How much the text is rotated (anticlockwise) around
+    /// [`Text::insertion_point`].
+    pub rotation: Angle,
+    /// How the text is aligned horizontally relative to the
+    /// [`Text::insertion_point`].
+    pub horizontal_alignment: HorizontalAlignment,
+    /// How the text is aligned vertically relative to the
+    /// [`Text::insertion_point`].
+    pub vertical_alignment: VerticalAlignment,
+    /// The string to display.
+    pub content: String,
+}
+
+impl Text {
+    /// How far below the baseline descenders (e.g. on a "g") go, as a
+    /// fraction of [`Text::height`].
+    pub const DESCENT_FACTOR: f64 = 0.25;
+    /// The approximate ratio of a character's width to its height.
+    pub const WIDTH_FACTOR: f64 = 0.6;
+
+    /// Create a new left-aligned [`Text`] sitting on its baseline.
+    pub fn new<S: Into<String>>(
+        insertion_point: Point,
+        height: f64,
+        content: S,
+    ) -> Self {
+        Text {
+            insertion_point,
+            height,
+            rotation: Angle::zero(),
+            horizontal_alignment: HorizontalAlignment::default(),
+            vertical_alignment: VerticalAlignment::default(),
+            content: content.into(),
+        }
+    }
+
+    /// Roughly how wide the [`Text`] will be.
+    pub fn estimated_width(&self) -> f64 {
+        self.content.chars().count() as f64 * self.height * Text::WIDTH_FACTOR
+    }
+
+    /// The vertical offset from the [`Text::insertion_point`] to the baseline,
+    /// in the text's local (unrotated) coordinate system.
+    pub fn baseline_offset(&self) -> f64 {
+        match self.vertical_alignment {
+            VerticalAlignment::Baseline => 0.0,
+            VerticalAlignment::Bottom => self.height * Text::DESCENT_FACTOR,
+            VerticalAlignment::Middle => -self.height / 2.0,
+            VerticalAlignment::Top => -self.height,
+        }
+    }
+
+    /// The point on the baseline which is level with the
+    /// [`Text::insertion_point`].
+    pub fn baseline_anchor(&self) -> Point {
+        self.to_drawing_space(0.0, self.baseline_offset())
+    }
+
+    /// The horizontal offset from the [`Text::insertion_point`] to the start
+    /// of the text, in the text's local (unrotated) coordinate system.
+    pub fn start_offset(&self, width: f64) -> f64 {
+        match self.horizontal_alignment {
+            HorizontalAlignment::Left => 0.0,
+            HorizontalAlignment::Centre => -width / 2.0,
+            HorizontalAlignment::Right => -width,
+        }
+    }
+
+    /// The four corners of the area covered by this [`Text`], starting at
+    /// the bottom-left and going anticlockwise.
+    pub fn corners(&self) -> [Point; 4] {
+        let width = self.estimated_width();
+        let left = self.start_offset(width);
+        let right = left + width;
+        let baseline = self.baseline_offset();
+        let bottom = baseline - self.height * Text::DESCENT_FACTOR;
+        let top = baseline + self.height;
+
+        [
+            self.to_drawing_space(left, bottom),
+            self.to_drawing_space(right, bottom),
+            self.to_drawing_space(right, top),
+            self.to_drawing_space(left, top),
+        ]
+    }
+
+    /// Convert from the text's local coordinate system, where the
+    /// [`Text::insertion_point`] is the origin and the baseline runs along
+    /// the x-axis.
+    fn to_drawing_space(&self, x: f64, y: f64) -> Point {
+        let (sin, cos) = self.rotation.sin_cos();
+        self.insertion_point + Vector::new(x * cos - y * sin, x * sin + y * cos)
+    }
+
+    /// The inverse of [`Text::to_drawing_space()`].
+    fn to_local_space(&self, point: Point) -> (f64, f64) {
+        let (sin, cos) = self.rotation.sin_cos();
+        let delta = point - self.insertion_point;
+
+        (
+            delta.x * cos + delta.y * sin,
+            -delta.x * sin + delta.y * cos,
+        )
+    }
+}
+
+impl Bounded<DrawingSpace> for Text {
+    fn bounding_box(&self) -> BoundingBox<DrawingSpace> {
+        let [a, b, c, d] = self.corners();
+
+        BoundingBox::new_unchecked(
+            a.min(b).min(c).min(d),
+            a.max(b).max(c).max(d),
+        )
+    }
+}
+
+impl ClosestPoint<DrawingSpace> for Text {
+    /// Text is treated as a solid rectangle, so any point inside it is
+    /// closest to itself.
+    fn closest_point(&self, target: Point) -> Closest<DrawingSpace> {
+        let [bottom_left, _, top_right, _] = self.corners();
+        let (min_x, min_y) = self.to_local_space(bottom_left);
+        let (max_x, max_y) = self.to_local_space(top_right);
+        let (x, y) = self.to_local_space(target);
+
+        Closest::One(
+            self.to_drawing_space(
+                x.max(min_x).min(max_x),
+                y.max(min_y).min(max_y),
+            ),
+        )
+    }
+}
+
+impl Translate<DrawingSpace> for Text {
+    fn translate(&mut self, displacement: Vector) {
+        self.insertion_point += displacement;
+    }
+}
+
+impl Transformable<DrawingSpace> for Text {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<DrawingSpace>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        self.insertion_point = transform.transform_point(self.insertion_point);
+        self.height *= scale;
+        let (rotation, horizontal, vertical) = readable_orientation(
+            transform,
+            self.rotation,
+            self.horizontal_alignment,
+            self.vertical_alignment,
+        );
+        self.rotation = rotation;
+        self.horizontal_alignment = horizontal;
+        self.vertical_alignment = vertical;
+
+        Ok(())
+    }
+}
+
+/// Work out how text should be oriented after a transform.
+///
+/// Mirroring text would normally leave it reading backwards, so instead it
+/// is kept readable and the alignment is flipped so the text still covers
+/// the mirrored area.
+pub(crate) fn readable_orientation(
+    transform: AffineTransform<DrawingSpace>,
+    rotation: Angle,
+    horizontal: HorizontalAlignment,
+    vertical: VerticalAlignment,
+) -> (Angle, HorizontalAlignment, VerticalAlignment) {
+    let baseline = transform.transform_angle(rotation);
+
+    if !transform.is_reflection() {
+        return (baseline, horizontal, vertical);
+    }
+
+    let up = transform.transform_angle(rotation + Angle::frac_pi_2());
+
+    if up.radians.sin() >= 0.0 {
+        // the text is still the right way up, but reads right to left
+        (
+            (baseline + Angle::pi()).positive(),
+            horizontal.mirrored(),
+            vertical,
+        )
+    } else {
+        // the text reads left to right, but is upside down
+        (baseline, horizontal, vertical.mirrored())
+    }
+}
+
+/// Where a [`Text`] is placed horizontally relative to its insertion point.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HorizontalAlignment {
+    /// The text starts at the insertion point.
+    Left,
+    /// The text is centred on the insertion point.
+    Centre,
+    /// The text ends at the insertion point.
+    Right,
+}
+
+impl Default for HorizontalAlignment {
+    fn default() -> Self { HorizontalAlignment::Left }
+}
+
+impl HorizontalAlignment {
+    fn mirrored(self) -> Self {
+        match self {
+            HorizontalAlignment::Left => HorizontalAlignment::Right,
+            HorizontalAlignment::Centre => HorizontalAlignment::Centre,
+            HorizontalAlignment::Right => HorizontalAlignment::Left,
+        }
+    }
+}
+
+/// Where a [`Text`] is placed vertically relative to its insertion point.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VerticalAlignment {
+    /// The text sits on the insertion point.
+    Baseline,
+    /// The bottom of any descenders are level with the insertion point.
+    Bottom,
+    /// The insertion point is halfway up a capital letter.
+    Middle,
+    /// The top of a capital letter is level with the insertion point.
+    Top,
+}
+
+impl Default for VerticalAlignment {
+    fn default() -> Self { VerticalAlignment::Baseline }
+}
+
+impl VerticalAlignment {
+    fn mirrored(self) -> Self {
+        match self {
+            VerticalAlignment::Baseline | VerticalAlignment::Bottom => {
+                VerticalAlignment::Top
+            },
+            VerticalAlignment::Middle => VerticalAlignment::Middle,
+            VerticalAlignment::Top => VerticalAlignment::Bottom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Line;
+    use euclid::approxeq::ApproxEq;
+
+    #[test]
+    fn bounds_of_left_aligned_text() {
+        let text = Text::new(Point::new(10.0, 20.0), 2.0, "Hello");
+
+        let bounds = text.bounding_box();
+
+        let width = 5.0 * 2.0 * Text::WIDTH_FACTOR;
+        assert_eq!(bounds.min_x(), 10.0);
+        assert_eq!(bounds.max_x(), 10.0 + width);
+        assert_eq!(bounds.min_y(), 20.0 - 2.0 * Text::DESCENT_FACTOR);
+        assert_eq!(bounds.max_y(), 22.0);
+    }
+
+    #[test]
+    fn bounds_of_rotated_centred_text() {
+        let mut text = Text::new(Point::zero(), 1.0, "ab");
+        text.rotation = Angle::frac_pi_2();
+        text.horizontal_alignment = HorizontalAlignment::Centre;
+        text.vertical_alignment = VerticalAlignment::Middle;
+
+        let bounds = text.bounding_box();
+
+        // the text now runs up the page, centred on the origin
+        let half_width = text.estimated_width() / 2.0;
+        assert!(bounds.min_y().approx_eq(&-half_width));
+        assert!(bounds.max_y().approx_eq(&half_width));
+        assert!(bounds.max_x().approx_eq(&(0.5 + Text::DESCENT_FACTOR)));
+        assert!(bounds.min_x().approx_eq(&-0.5));
+    }
+
+    #[test]
+    fn points_inside_text_are_closest_to_themselves() {
+        let text = Text::new(Point::zero(), 10.0, "Hello, World!");
+        let inside = Point::new(5.0, 5.0);
+        let outside = Point::new(5.0, 100.0);
+
+        assert_eq!(text.closest_point(inside), Closest::One(inside));
+        assert_eq!(
+            text.closest_point(outside),
+            Closest::One(Point::new(5.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn text_can_be_rotated_and_scaled_but_not_squashed() {
+        let text = Text::new(Point::new(1.0, 0.0), 2.0, "Hello");
+        let transform = AffineTransform::rotation(Angle::frac_pi_2())
+            .then(AffineTransform::scale(3.0, 3.0));
+
+        let got = text.transformed_by(transform).unwrap();
+
+        assert!(got.insertion_point.approx_eq(&Point::new(0.0, 3.0)));
+        assert!(got.height.approx_eq(&6.0));
+        assert!(got.rotation.radians.approx_eq(&Angle::frac_pi_2().radians));
+        assert_eq!(
+            text.transformed_by(AffineTransform::scale(1.0, 2.0)),
+            Err(TransformError::NonUniform)
+        );
+    }
+
+    #[test]
+    fn mirrored_text_stays_readable() {
+        let text = Text::new(Point::new(1.0, 1.0), 2.0, "Hello");
+        let y_axis = Line::new(Point::zero(), Point::new(0.0, 1.0));
+        let x_axis = Line::new(Point::zero(), Point::new(1.0, 0.0));
+
+        let across_y = crate::algorithms::mirror(&text, y_axis).unwrap();
+        let across_x = crate::algorithms::mirror(&text, x_axis).unwrap();
+
+        assert!(across_y.insertion_point.approx_eq(&Point::new(-1.0, 1.0)));
+        assert!(across_y.rotation.radians.approx_eq(&0.0));
+        assert_eq!(across_y.horizontal_alignment, HorizontalAlignment::Right);
+        assert!(across_x.insertion_point.approx_eq(&Point::new(1.0, -1.0)));
+        assert!(across_x.rotation.radians.approx_eq(&0.0));
+        assert_eq!(across_x.vertical_alignment, VerticalAlignment::Top);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::Angle;
+use specs::prelude::*;
+use specs_derive::Component;
+use std::collections::BTreeMap;
+
+/// A named set of font settings (e.g. a DXF `STYLE` table entry) which can
+/// be shared by many pieces of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    /// The name text entities use to refer to this [`TextStyle`].
+    pub name: String,
+    /// The name of the font family to draw with.
+    pub font: String,
+    /// A multiplier for the height of any text using this style.
+    pub height_factor: f64,
+    /// How much to stretch characters horizontally (`1.0` is normal, less
+    /// than `1.0` is condensed).
+    pub width_factor: f64,
+    /// How far characters lean to the right, measured from the vertical.
+    pub oblique_angle: Angle,
+}
+
+impl TextStyle {
+    /// The name of the style used when text doesn't specify one.
+    pub const STANDARD: &'static str = "Standard";
+
+    /// Create a new [`TextStyle`] using the font as-is.
+    pub fn new<N, F>(name: N, font: F) -> Self
+    where
+        N: Into<String>,
+        F: Into<String>,
+    {
+        TextStyle {
+            name: name.into(),
+            font: font.into(),
+            height_factor: 1.0,
+            width_factor: 1.0,
+            oblique_angle: Angle::zero(),
+        }
+    }
+
+    /// The [`TextStyle::STANDARD`] text style.
+    pub fn standard() -> Self {
+        TextStyle::new(TextStyle::STANDARD, "sans-serif")
+    }
+}
+
+/// A global [`Resource`] containing every [`TextStyle`] in a drawing, looked
+/// up by name.
+///
+/// Like DXF, names are case-insensitive. The table always starts with a
+/// [`TextStyle::STANDARD`] style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyleTable {
+    text_styles: BTreeMap<String, TextStyle>,
+}
+
+impl TextStyleTable {
+    fn key(name: &str) -> String { name.to_uppercase() }
+
+    /// Look up a [`TextStyle`] by name.
+    pub fn get(&self, name: &str) -> Option<&TextStyle> {
+        self.text_styles.get(&TextStyleTable::key(name))
+    }
+
+    /// Add a [`TextStyle`], returning the one it replaced (if any).
+    pub fn insert(&mut self, text_style: TextStyle) -> Option<TextStyle> {
+        self.text_styles
+            .insert(TextStyleTable::key(&text_style.name), text_style)
+    }
+
+    /// Remove a [`TextStyle`].
+    pub fn remove(&mut self, name: &str) -> Option<TextStyle> {
+        self.text_styles.remove(&TextStyleTable::key(name))
+    }
+
+    /// Iterate over every [`TextStyle`], in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = &TextStyle> + '_ {
+        self.text_styles.values()
+    }
+
+    pub fn len(&self) -> usize { self.text_styles.len() }
+
+    pub fn is_empty(&self) -> bool { self.text_styles.is_empty() }
+
+    /// Find the [`TextStyle`] a piece of text should be drawn with, using
+    /// [`TextStyle::STANDARD`] when it doesn't have a [`TextStyleRef`].
+    ///
+    /// This returns [`None`] when the style isn't in the table, in which case
+    /// the text should be drawn using [`TextStyle::standard()`].
+    pub fn resolve(
+        &self,
+        text_style: Option<&TextStyleRef>,
+    ) -> Option<&TextStyle> {
+        let name = text_style
+            .map(|r| r.0.as_str())
+            .unwrap_or(TextStyle::STANDARD);
+
+        self.get(name)
+    }
+}
+
+impl Default for TextStyleTable {
+    fn default() -> Self {
+        let mut table = TextStyleTable {
+            text_styles: BTreeMap::new(),
+        };
+        table.insert(TextStyle::standard());
+
+        table
+    }
+}
+
+/// Draw a [`crate::components::Text`] or [`crate::components::MText`] using
+/// a particular [`TextStyle`] from the [`TextStyleTable`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Component)]
+#[storage(HashMapStorage)]
+pub struct TextStyleRef(pub String);
+
+impl TextStyleRef {
+    pub fn new<S: Into<String>>(name: S) -> Self { TextStyleRef(name.into()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_uses_the_standard_style_by_default() {
+        let mut table = TextStyleTable::default();
+        table.insert(TextStyle {
+            width_factor: 0.8,
+            ..TextStyle::new("Narrow", "monospace")
+        });
+
+        let standard = table.resolve(None).unwrap();
+        let narrow = table.resolve(Some(&TextStyleRef::new("NARROW"))).unwrap();
+
+        assert_eq!(standard, &TextStyle::standard());
+        assert_eq!(narrow.font, "monospace");
+        assert_eq!(narrow.width_factor, 0.8);
+        assert!(table.resolve(Some(&TextStyleRef::new("Fancy"))).is_none());
+    }
+}
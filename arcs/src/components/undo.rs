@@ -0,0 +1,461 @@
+use crate::components::{Deleted, DrawingObject, Name, Parent};
+use specs::{prelude::*, storage::Tracked, world::Index};
+use std::{collections::HashMap, fmt::Debug};
+
+#[allow(unused_imports)] // for rustdoc links
+use specs::prelude::Resource;
+
+/// A global [`Resource`] which records changes to the [`World`] so they can
+/// be undone and redone.
+///
+/// The [`UndoLog`] listens to the events from each tracked component's
+/// [`FlaggedStorage`] and keeps a copy of the last known value for every
+/// entity. Calling [`UndoLog::commit()`] turns everything which happened
+/// since the previous commit into a named transaction, which
+/// [`UndoLog::undo()`] and [`UndoLog::redo()`] can then roll back and replay.
+///
+/// Entities can't be brought back to life once they've been deleted, so
+/// undoing a deletion restores the components onto a new [`Entity`]. Later
+/// transactions which refer to the deleted entity are redirected to its
+/// replacement, but other components which mention the old [`Entity`] (e.g.
+/// a [`DrawingObject`]'s layer) aren't updated.
+///
+/// # Examples
+///
+/// ```rust
+/// # use arcs::{components::*, Point};
+/// # use specs::prelude::*;
+/// let mut world = World::new();
+/// arcs::components::register(&mut world);
+/// let log = UndoLog::new(&world);
+/// world.insert(log);
+///
+/// let layer = world.create_entity().build();
+/// let point = world
+///     .create_entity()
+///     .with(DrawingObject {
+///         geometry: Geometry::Point(Point::zero()),
+///         layer,
+///     })
+///     .build();
+/// world.fetch_mut::<UndoLog>().commit(&world, "Add Point");
+///
+/// assert_eq!(world.fetch::<UndoLog>().undo_name(), Some("Add Point"));
+/// world.fetch_mut::<UndoLog>().undo(&world);
+/// assert!(world.read_storage::<DrawingObject>().get(point).is_none());
+///
+/// world.fetch_mut::<UndoLog>().redo(&world);
+/// assert!(world.read_storage::<DrawingObject>().get(point).is_some());
+/// ```
+#[derive(Debug, Default)]
+pub struct UndoLog {
+    journals: Vec<Box<dyn Journal>>,
+    undo_stack: Vec<Transaction>,
+    redo_stack: Vec<Transaction>,
+    /// Entities which were recreated after being deleted.
+    replacements: HashMap<Entity, Entity>,
+}
+
+impl UndoLog {
+    /// The name given to changes which were made before calling
+    /// [`UndoLog::undo()`] without being committed.
+    pub const UNCOMMITTED: &'static str = "Uncommitted changes";
+
+    /// Create an [`UndoLog`] which tracks [`DrawingObject`]s, [`Name`]s,
+    /// [`Parent`]s, and [`Deleted`] tombstones.
+    pub fn new(world: &World) -> Self {
+        let mut log = UndoLog::default();
+        log.track::<DrawingObject>(world);
+        log.track::<Name>(world);
+        log.track::<Parent>(world);
+        log.track::<Deleted>(world);
+
+        log
+    }
+
+    /// Start recording changes to another type of [`Component`].
+    pub fn track<T>(&mut self, world: &World)
+    where
+        T: Component + Clone + Debug + Send + Sync,
+        T::Storage: Tracked,
+    {
+        self.journals
+            .push(Box::new(ComponentJournal::<T>::new(world)));
+    }
+
+    /// Group every change since the last commit into a transaction which can
+    /// be undone, returning `false` if nothing changed.
+    ///
+    /// Committing something new means the transactions which were undone can
+    /// no longer be redone.
+    pub fn commit<S: Into<String>>(&mut self, world: &World, name: S) -> bool {
+        let changes = self.pending_changes(world);
+
+        if changes.is_empty() {
+            return false;
+        }
+
+        self.undo_stack.push(Transaction {
+            name: name.into(),
+            changes,
+        });
+        self.redo_stack.clear();
+
+        true
+    }
+
+    /// Roll back the most recent transaction, returning its name.
+    ///
+    /// Any changes which haven't been committed yet are committed first (see
+    /// [`UndoLog::UNCOMMITTED`]), so they are the ones which get undone.
+    pub fn undo(&mut self, world: &World) -> Option<String> {
+        self.commit(world, UndoLog::UNCOMMITTED);
+        let transaction = self.undo_stack.pop()?;
+
+        for change in transaction.changes.iter().rev() {
+            let entity = self.target(world, change.entity(), change.before());
+            change.revert(world, entity);
+        }
+        self.forget_pending_changes(world);
+
+        let name = transaction.name.clone();
+        self.redo_stack.push(transaction);
+        Some(name)
+    }
+
+    /// Replay the most recently undone transaction, returning its name.
+    ///
+    /// Changes which haven't been committed are committed first, which
+    /// clears the redo history.
+    pub fn redo(&mut self, world: &World) -> Option<String> {
+        self.commit(world, UndoLog::UNCOMMITTED);
+        let transaction = self.redo_stack.pop()?;
+
+        for change in &transaction.changes {
+            let entity = self.target(world, change.entity(), change.after());
+            change.reapply(world, entity);
+        }
+        self.forget_pending_changes(world);
+
+        let name = transaction.name.clone();
+        self.undo_stack.push(transaction);
+        Some(name)
+    }
+
+    /// The name of the transaction [`UndoLog::undo()`] would roll back.
+    pub fn undo_name(&self) -> Option<&str> {
+        self.undo_stack.last().map(|t| t.name.as_str())
+    }
+
+    /// The name of the transaction [`UndoLog::redo()`] would replay.
+    pub fn redo_name(&self) -> Option<&str> {
+        self.redo_stack.last().map(|t| t.name.as_str())
+    }
+
+    pub fn can_undo(&self) -> bool { !self.undo_stack.is_empty() }
+
+    pub fn can_redo(&self) -> bool { !self.redo_stack.is_empty() }
+
+    /// Forget every transaction, without changing the [`World`].
+    pub fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.replacements.clear();
+    }
+
+    fn pending_changes(&mut self, world: &World) -> Vec<Box<dyn Change>> {
+        let mut changes = Vec::new();
+
+        for journal in &mut self.journals {
+            journal.record(world, &mut changes);
+        }
+
+        changes
+    }
+
+    /// Catch up on the events caused by applying a transaction so they don't
+    /// get recorded a second time.
+    fn forget_pending_changes(&mut self, world: &World) {
+        let _ = self.pending_changes(world);
+    }
+
+    /// Find the [`Entity`] a change should be applied to, creating a
+    /// replacement if the original was deleted and `needs_value` is set.
+    fn target(
+        &mut self,
+        world: &World,
+        original: Entity,
+        needs_value: bool,
+    ) -> Entity {
+        let mut entity = original;
+        while let Some(&replacement) = self.replacements.get(&entity) {
+            entity = replacement;
+        }
+
+        let entities = world.entities();
+        if needs_value && !entities.is_alive(entity) {
+            let replacement = entities.create();
+            self.replacements.insert(entity, replacement);
+            entity = replacement;
+        }
+
+        entity
+    }
+}
+
+#[derive(Debug)]
+struct Transaction {
+    name: String,
+    changes: Vec<Box<dyn Change>>,
+}
+
+/// Something which records the changes to a single type of component.
+trait Journal: Debug + Send + Sync {
+    fn record(&mut self, world: &World, changes: &mut Vec<Box<dyn Change>>);
+}
+
+#[derive(Debug)]
+struct ComponentJournal<T: Component> {
+    events: ReaderId<ComponentEvent>,
+    touched: BitSet,
+    last_known: HashMap<Index, (Entity, T)>,
+}
+
+impl<T> ComponentJournal<T>
+where
+    T: Component + Clone,
+    T::Storage: Tracked,
+{
+    fn new(world: &World) -> Self {
+        let events = world.write_storage::<T>().register_reader();
+        let last_known = (&world.entities(), &world.read_storage::<T>())
+            .join()
+            .map(|(entity, value)| (entity.id(), (entity, value.clone())))
+            .collect();
+
+        ComponentJournal {
+            events,
+            touched: BitSet::new(),
+            last_known,
+        }
+    }
+}
+
+impl<T> Journal for ComponentJournal<T>
+where
+    T: Component + Clone + Debug + Send + Sync,
+    T::Storage: Tracked,
+{
+    fn record(&mut self, world: &World, changes: &mut Vec<Box<dyn Change>>) {
+        let entities = world.entities();
+        let storage = world.read_storage::<T>();
+        self.touched.clear();
+
+        for event in storage.channel().read(&mut self.events) {
+            match *event {
+                ComponentEvent::Inserted(id)
+                | ComponentEvent::Modified(id)
+                | ComponentEvent::Removed(id) => {
+                    self.touched.add(id);
+                },
+            }
+        }
+
+        for id in (&self.touched).join() {
+            let entity = entities.entity(id);
+            let after = storage.get(entity).cloned();
+
+            match self.last_known.remove(&id) {
+                // the index was reused by a new entity
+                Some((previous, before)) if previous != entity => {
+                    changes.push(ComponentChange::boxed(
+                        previous,
+                        Some(before),
+                        None,
+                    ));
+                    if after.is_some() {
+                        changes.push(ComponentChange::boxed(
+                            entity,
+                            None,
+                            after.clone(),
+                        ));
+                    }
+                },
+                before => {
+                    let before = before.map(|(_, value)| value);
+                    if before.is_some() || after.is_some() {
+                        changes.push(ComponentChange::boxed(
+                            entity,
+                            before,
+                            after.clone(),
+                        ));
+                    }
+                },
+            }
+
+            if let Some(value) = after {
+                self.last_known.insert(id, (entity, value));
+            }
+        }
+    }
+}
+
+/// A single component being added, removed, or modified.
+trait Change: Debug + Send + Sync {
+    fn entity(&self) -> Entity;
+    /// Did the entity have this component before the change?
+    fn before(&self) -> bool;
+    /// Did the entity have this component after the change?
+    fn after(&self) -> bool;
+    fn revert(&self, world: &World, entity: Entity);
+    fn reapply(&self, world: &World, entity: Entity);
+}
+
+#[derive(Debug)]
+struct ComponentChange<T> {
+    entity: Entity,
+    before: Option<T>,
+    after: Option<T>,
+}
+
+impl<T> ComponentChange<T>
+where
+    T: Component + Clone + Debug + Send + Sync,
+{
+    fn boxed(
+        entity: Entity,
+        before: Option<T>,
+        after: Option<T>,
+    ) -> Box<dyn Change> {
+        Box::new(ComponentChange {
+            entity,
+            before,
+            after,
+        })
+    }
+}
+
+fn set<T: Component + Clone>(world: &World, entity: Entity, value: &Option<T>) {
+    let mut storage = world.write_storage::<T>();
+
+    match value {
+        Some(value) => {
+            let _ = storage.insert(entity, value.clone());
+        },
+        None => {
+            storage.remove(entity);
+        },
+    }
+}
+
+impl<T> Change for ComponentChange<T>
+where
+    T: Component + Clone + Debug + Send + Sync,
+{
+    fn entity(&self) -> Entity { self.entity }
+
+    fn before(&self) -> bool { self.before.is_some() }
+
+    fn after(&self) -> bool { self.after.is_some() }
+
+    fn revert(&self, world: &World, entity: Entity) {
+        set(world, entity, &self.before);
+    }
+
+    fn reapply(&self, world: &World, entity: Entity) {
+        set(world, entity, &self.after);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::Geometry, Point};
+
+    fn point(x: f64, layer: Entity) -> DrawingObject {
+        DrawingObject {
+            geometry: Geometry::Point(Point::new(x, 0.0)),
+            layer,
+        }
+    }
+
+    fn x_of(world: &World, entity: Entity) -> Option<f64> {
+        match world.read_storage::<DrawingObject>().get(entity)?.geometry {
+            Geometry::Point(p) => Some(p.x),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_a_modification() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let entity = world.create_entity().with(point(1.0, layer)).build();
+        let mut log = UndoLog::new(&world);
+
+        world
+            .write_storage::<DrawingObject>()
+            .insert(entity, point(2.0, layer))
+            .unwrap();
+        assert!(log.commit(&world, "Move"));
+        assert!(!log.commit(&world, "Nothing"));
+
+        assert_eq!(log.undo(&world).as_deref(), Some("Move"));
+        assert_eq!(x_of(&world, entity), Some(1.0));
+        assert!(!log.can_undo());
+
+        assert_eq!(log.redo(&world).as_deref(), Some("Move"));
+        assert_eq!(x_of(&world, entity), Some(2.0));
+        assert!(!log.can_redo());
+        // applying a transaction isn't recorded as a new change
+        assert!(!log.commit(&world, "Nothing"));
+    }
+
+    #[test]
+    fn deleted_entities_come_back() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let entity = world.create_entity().with(point(1.0, layer)).build();
+        let mut log = UndoLog::new(&world);
+
+        world.delete_entity(entity).unwrap();
+        world.maintain();
+        log.commit(&world, "Delete");
+        log.undo(&world);
+        world.maintain();
+
+        let restored: Vec<_> =
+            (&world.entities(), &world.read_storage::<DrawingObject>())
+                .join()
+                .map(|(ent, _)| ent)
+                .collect();
+        assert_eq!(restored.len(), 1);
+        let replacement = restored[0];
+        assert_ne!(replacement, entity);
+        assert_eq!(x_of(&world, replacement), Some(1.0));
+
+        // later transactions follow the replacement
+        log.redo(&world);
+        assert_eq!(x_of(&world, replacement), None);
+    }
+
+    #[test]
+    fn new_commits_clear_the_redo_stack() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let mut log = UndoLog::new(&world);
+        world.create_entity().with(point(1.0, layer)).build();
+        log.commit(&world, "First");
+        log.undo(&world);
+        assert_eq!(log.redo_name(), Some("First"));
+
+        world.create_entity().with(point(2.0, layer)).build();
+        log.commit(&world, "Second");
+
+        assert!(!log.can_redo());
+        assert_eq!(log.undo_name(), Some("Second"));
+    }
+}
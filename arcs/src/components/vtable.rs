@@ -1,5 +1,6 @@
-use specs::{Component, World, WorldExt};
-use std::any;
+use crate::components::snapshot::{MapEntities, Stored, StoredComponents};
+use specs::{Component, Entity, World, WorldExt};
+use std::{any, fmt::Debug};
 
 /// Functions for working with generic [`Component`]s without needing to drag a
 /// type variable around.
@@ -7,13 +8,17 @@ use std::any;
 pub(crate) struct ComponentVtable {
     name: &'static str,
     register: fn(world: &mut World),
+    snapshot: fn(world: &World) -> Box<dyn StoredComponents>,
+    /// Copy the component from one entity to another, if copies of an entity
+    /// should get this component.
+    duplicate: Option<fn(world: &World, original: Entity, copy: Entity)>,
 }
 
 impl ComponentVtable {
     /// Create the [`ComponentVtable`] corresponding to a particular type.
     pub fn for_type<T>() -> Self
     where
-        T: Component,
+        T: Component + Clone + Debug + Send + Sync,
         T::Storage: Default,
     {
         ComponentVtable {
@@ -21,6 +26,39 @@ impl ComponentVtable {
             register: |world| {
                 world.register::<T>();
             },
+            snapshot: |world| Box::new(Stored::<T>::capture(world, |_, _| {})),
+            duplicate: Some(|world, original, copy| {
+                let mut storage = world.write_storage::<T>();
+
+                if let Some(value) = storage.get(original).cloned() {
+                    let _ = storage.insert(copy, value);
+                }
+            }),
+        }
+    }
+
+    /// Create the [`ComponentVtable`] for a type which refers to other
+    /// entities.
+    pub fn for_type_with_entities<T>() -> Self
+    where
+        T: Component + Clone + Debug + Send + Sync + MapEntities,
+        T::Storage: Default,
+    {
+        ComponentVtable {
+            snapshot: |world| {
+                Box::new(Stored::<T>::capture(world, T::map_entities))
+            },
+            ..ComponentVtable::for_type::<T>()
+        }
+    }
+
+    /// Don't give copies made by [`crate::components::duplicate()`] this
+    /// component (e.g. because it is derived from the geometry or only makes
+    /// sense on the original).
+    pub fn not_duplicated(self) -> Self {
+        ComponentVtable {
+            duplicate: None,
+            ..self
         }
     }
 
@@ -29,4 +67,22 @@ impl ComponentVtable {
 
     /// Register this component with the [`World`].
     pub(crate) fn register(&self, world: &mut World) { (self.register)(world); }
+
+    /// Take a copy of every instance of this component.
+    pub(crate) fn snapshot(&self, world: &World) -> Box<dyn StoredComponents> {
+        (self.snapshot)(world)
+    }
+
+    /// Copy this component from `original` to `copy`, if copies should have
+    /// it.
+    pub(crate) fn duplicate(
+        &self,
+        world: &World,
+        original: Entity,
+        copy: Entity,
+    ) {
+        if let Some(duplicate) = self.duplicate {
+            duplicate(world, original, copy);
+        }
+    }
 }
@@ -21,6 +21,7 @@
 #![deny(missing_debug_implementations, intra_doc_link_resolution_failure)]
 
 pub mod components;
+pub mod query;
 pub mod systems;
 mod types;
 pub mod window;
@@ -31,5 +32,27 @@ pub use types::{CanvasSpace, DrawingSpace, Length, Point, Transform, Vector};
 
 /// An [`primitives::Arc`] in [`DrawingSpace`].
 pub type Arc = primitives::Arc<DrawingSpace>;
+/// A [`primitives::Circle`] in [`DrawingSpace`].
+pub type Circle = primitives::Circle<DrawingSpace>;
+/// A [`primitives::CubicBezier`] in [`DrawingSpace`].
+pub type CubicBezier = primitives::CubicBezier<DrawingSpace>;
+/// An [`primitives::Ellipse`] in [`DrawingSpace`].
+pub type Ellipse = primitives::Ellipse<DrawingSpace>;
+/// An [`primitives::EllipticalArc`] in [`DrawingSpace`].
+pub type EllipticalArc = primitives::EllipticalArc<DrawingSpace>;
 /// A [`primitives::Line`] in [`DrawingSpace`].
 pub type Line = primitives::Line<DrawingSpace>;
+/// A [`primitives::Polyline`] in [`DrawingSpace`].
+pub type Polyline = primitives::Polyline<DrawingSpace>;
+/// A [`primitives::QuadraticBezier`] in [`DrawingSpace`].
+pub type QuadraticBezier = primitives::QuadraticBezier<DrawingSpace>;
+/// A [`primitives::Ray`] in [`DrawingSpace`].
+pub type Ray = primitives::Ray<DrawingSpace>;
+/// A [`primitives::Rectangle`] in [`DrawingSpace`].
+pub type Rectangle = primitives::Rectangle<DrawingSpace>;
+/// A [`primitives::Region`] in [`DrawingSpace`].
+pub type Region = primitives::Region<DrawingSpace>;
+/// A [`primitives::Spline`] in [`DrawingSpace`].
+pub type Spline = primitives::Spline<DrawingSpace>;
+/// A [`primitives::XLine`] in [`DrawingSpace`].
+pub type XLine = primitives::XLine<DrawingSpace>;
@@ -0,0 +1,139 @@
+use crate::{
+    components::{DrawOrder, Layer, Viewport},
+    query::{closest_to, Candidates},
+    window::to_drawing_coordinates,
+    BoundingBox, CanvasSpace, Point, Vector,
+};
+use euclid::{Point2D, Size2D};
+use specs::prelude::*;
+use std::cmp::{Ordering, Reverse};
+
+/// Something found by [`hit_test()`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Hit {
+    pub entity: Entity,
+    /// How far the entity is from the point being tested, in drawing units.
+    pub distance: f64,
+    /// The point on the entity which is closest to the point being tested.
+    pub closest_point: Point,
+}
+
+/// Find everything within `tolerance_px` pixels of a point on the screen.
+///
+/// The closest hits come first, with ties broken by whichever entity is
+/// drawn on top (see [`Layer::z_level`] and [`DrawOrder`]).
+pub fn hit_test(
+    world: &World,
+    viewport: &Viewport,
+    window: Size2D<f64, CanvasSpace>,
+    screen_point: Point2D<f64, CanvasSpace>,
+    tolerance_px: f64,
+) -> Vec<Hit> {
+    let target = to_drawing_coordinates(screen_point, viewport, window);
+    let tolerance = tolerance_px / viewport.pixels_per_drawing_unit.get();
+    let region = BoundingBox::new(
+        target - Vector::new(tolerance, tolerance),
+        target + Vector::new(tolerance, tolerance),
+    );
+
+    let candidates = Candidates::fetch(world);
+    let mut hits: Vec<Hit> = candidates
+        .in_region(region)
+        .into_iter()
+        .filter_map(|(entity, geometry)| {
+            let (distance, closest_point) = closest_to(geometry, target);
+
+            if distance <= tolerance {
+                Some(Hit {
+                    entity,
+                    distance,
+                    closest_point,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let layers = world.read_storage::<Layer>();
+    let draw_orders = world.read_storage::<DrawOrder>();
+    // lower z-levels and higher draw orders are drawn on top
+    let stacking = |hit: &Hit| {
+        let z_level = candidates
+            .objects
+            .get(hit.entity)
+            .and_then(|obj| layers.get(obj.layer))
+            .map(|layer| layer.z_level)
+            .unwrap_or_default();
+        let order = draw_orders.get(hit.entity).copied().unwrap_or_default();
+
+        (z_level, Reverse(order), Reverse(hit.entity.id()))
+    };
+
+    hits.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| stacking(a).cmp(&stacking(b)))
+    });
+
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{DrawingObject, Geometry, Hidden},
+        Line,
+    };
+    use euclid::Scale;
+
+    #[test]
+    fn closest_and_topmost_hits_come_first() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut dispatcher = crate::systems::default_dispatcher(&mut world);
+        let layer = world.create_entity().with(Layer::default()).build();
+        let line = |world: &mut World, y: f64| {
+            world
+                .create_entity()
+                .with(DrawingObject {
+                    geometry: Geometry::Line(Line::new(
+                        Point::new(-10.0, y),
+                        Point::new(10.0, y),
+                    )),
+                    layer,
+                })
+                .build()
+        };
+        let bottom = line(&mut world, 0.0);
+        let top = line(&mut world, 0.0);
+        world
+            .write_storage::<DrawOrder>()
+            .insert(top, DrawOrder(1))
+            .unwrap();
+        let nearby = line(&mut world, 0.5);
+        let hidden = line(&mut world, 0.0);
+        world
+            .write_storage::<Hidden>()
+            .insert(hidden, Hidden)
+            .unwrap();
+        let _far_away = line(&mut world, 50.0);
+        dispatcher.dispatch(&world);
+
+        // 10 pixels per unit, centred on the origin
+        let viewport = Viewport {
+            centre: Point::zero(),
+            pixels_per_drawing_unit: Scale::new(10.0),
+        };
+        let window = Size2D::new(200.0, 200.0);
+        let cursor = Point2D::new(100.0, 99.0);
+
+        let got = hit_test(&world, &viewport, window, cursor, 8.0);
+
+        let entities: Vec<_> = got.iter().map(|hit| hit.entity).collect();
+        assert_eq!(entities, vec![top, bottom, nearby]);
+        assert!((got[0].distance - 0.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,88 @@
+//! Spatial queries for finding the entities under the cursor or inside part
+//! of the drawing.
+//!
+//! These use the [`SpatialIndex`] to find candidates, so they rely on the
+//! [`crate::systems::SpatialIndexBookkeeping`] system having run. Hidden and
+//! [`crate::components::Deleted`] entities are never returned.
+
+mod hit_test;
+mod nearest;
+mod selection;
+
+pub use hit_test::{hit_test, Hit};
+pub use nearest::nearest;
+pub use selection::{
+    crossing_polygon_select, crossing_select, fence_select,
+    window_polygon_select, window_select,
+};
+
+use crate::{
+    algorithms::ClosestPoint,
+    components::{
+        is_visible, Deleted, DrawingObject, Geometry, Hidden, Layer,
+        SpatialIndex,
+    },
+    BoundingBox, DrawingSpace, Point,
+};
+use specs::prelude::*;
+use std::cmp::Ordering;
+
+/// The storages every query needs.
+struct Candidates<'world> {
+    objects: ReadStorage<'world, DrawingObject>,
+    hidden: ReadStorage<'world, Hidden>,
+    layers: ReadStorage<'world, Layer>,
+    deleted: ReadStorage<'world, Deleted>,
+    index: Read<'world, SpatialIndex>,
+}
+
+impl<'world> Candidates<'world> {
+    fn fetch(world: &'world World) -> Self {
+        Candidates {
+            objects: world.read_storage(),
+            hidden: world.read_storage(),
+            layers: world.read_storage(),
+            deleted: world.read_storage(),
+            index: world.system_data(),
+        }
+    }
+
+    /// Every selectable entity whose [`BoundingBox`] touches `region`, along
+    /// with its [`Geometry`].
+    fn in_region(
+        &self,
+        region: BoundingBox<DrawingSpace>,
+    ) -> Vec<(Entity, &Geometry)> {
+        self.index
+            .query_region(region)
+            .into_iter()
+            .filter_map(|ent| self.selectable(ent))
+            .collect()
+    }
+
+    /// Get an entity's [`Geometry`] if it can be selected.
+    fn selectable(&self, ent: Entity) -> Option<(Entity, &Geometry)> {
+        let object = self.objects.get(ent)?;
+
+        if self.deleted.contains(ent)
+            || !is_visible(&self.hidden, &self.layers, ent, object.layer)
+        {
+            None
+        } else {
+            Some((ent, &object.geometry))
+        }
+    }
+}
+
+/// The distance from `target` to the closest point on some [`Geometry`],
+/// and that point.
+fn closest_to(geometry: &Geometry, target: Point) -> (f64, Point) {
+    geometry
+        .closest_point(target)
+        .points()
+        .iter()
+        .map(|&p| ((p - target).length(), p))
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+        // infinitely many closest points means we're right on top
+        .unwrap_or((0.0, target))
+}
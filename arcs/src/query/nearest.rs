@@ -0,0 +1,109 @@
+use crate::{
+    components::DrawingObject,
+    query::{closest_to, Candidates},
+    Point,
+};
+use specs::prelude::*;
+
+/// Find the entity closest to `point` and how far away it is, only
+/// considering entities which pass the `filter`.
+///
+/// The `filter` can be used to restrict the search to a particular layer,
+/// [`crate::components::Geometry::kind()`], or entities with some
+/// component. Use `|_, _| true` to consider everything.
+///
+/// # Examples
+///
+/// ```rust
+/// use arcs::{components::Geometry, query};
+/// # use specs::prelude::*;
+/// # let mut world = World::new();
+/// # arcs::components::register(&mut world);
+/// # let _ = arcs::systems::default_dispatcher(&mut world);
+/// # let point = arcs::Point::zero();
+///
+/// // snap to the nearest line
+/// let nearest_line = query::nearest(&world, point, |_, object| {
+///     match object.geometry {
+///         Geometry::Line(_) => true,
+///         _ => false,
+///     }
+/// });
+/// # assert!(nearest_line.is_none());
+/// ```
+pub fn nearest<F>(
+    world: &World,
+    point: Point,
+    mut filter: F,
+) -> Option<(Entity, f64)>
+where
+    F: FnMut(Entity, &DrawingObject) -> bool,
+{
+    let candidates = Candidates::fetch(world);
+    let distance_to = |ent: Entity| {
+        let (_, geometry) = candidates.selectable(ent)?;
+        Some(closest_to(geometry, point).0)
+    };
+
+    let found = candidates.index.nearest_by(point, |ent, _| {
+        let object = candidates.objects.get(ent)?;
+
+        if filter(ent, object) {
+            distance_to(ent)
+        } else {
+            None
+        }
+    })?;
+
+    distance_to(found).map(|distance| (found, distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{Geometry, Layer},
+        Line,
+    };
+
+    #[test]
+    fn find_the_nearest_entity_passing_the_filter() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut dispatcher = crate::systems::default_dispatcher(&mut world);
+        let first_layer = world.create_entity().with(Layer::default()).build();
+        let second_layer = world.create_entity().with(Layer::default()).build();
+        let close = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::new(1.0, 0.0)),
+                layer: first_layer,
+            })
+            .build();
+        let further = world
+            .create_entity()
+            .with(DrawingObject {
+                // the bounding box is closer than the point
+                geometry: Geometry::Line(Line::new(
+                    Point::new(0.0, 2.0),
+                    Point::new(2.0, 0.0),
+                )),
+                layer: second_layer,
+            })
+            .build();
+        dispatcher.dispatch(&world);
+
+        assert_eq!(
+            nearest(&world, Point::zero(), |_, _| true),
+            Some((close, 1.0))
+        );
+
+        let (got, distance) =
+            nearest(&world, Point::zero(), |_, obj| obj.layer == second_layer)
+                .unwrap();
+        assert_eq!(got, further);
+        assert!((distance - 2.0_f64.sqrt()).abs() < 1e-9);
+
+        assert_eq!(nearest(&world, Point::zero(), |_, _| false), None);
+    }
+}
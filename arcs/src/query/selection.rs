@@ -0,0 +1,267 @@
+use crate::{
+    algorithms::{Bounded, ClosestPoint, Intersect},
+    components::Geometry,
+    query::Candidates,
+    BoundingBox, DrawingSpace, Point, Polyline,
+};
+use specs::prelude::*;
+
+/// Find every entity which is entirely inside `region`.
+///
+/// This is the "window" selection you get when dragging a selection
+/// rectangle from left to right.
+pub fn window_select(
+    world: &World,
+    region: BoundingBox<DrawingSpace>,
+) -> Vec<Entity> {
+    Candidates::fetch(world)
+        .in_region(region)
+        .into_iter()
+        .filter(|(_, geometry)| region.fully_contains(geometry.bounding_box()))
+        .map(|(ent, _)| ent)
+        .collect()
+}
+
+/// Find every entity which is inside `region` or crosses one of its edges.
+///
+/// This is the "crossing" selection you get when dragging a selection
+/// rectangle from right to left. Unlike a simple [`BoundingBox`] check, an
+/// entity is only selected when its actual [`Geometry`] touches the
+/// rectangle (e.g. an arc curving around a corner isn't selected).
+pub fn crossing_select(
+    world: &World,
+    region: BoundingBox<DrawingSpace>,
+) -> Vec<Entity> {
+    let outline = outline(region);
+
+    Candidates::fetch(world)
+        .in_region(region)
+        .into_iter()
+        .filter(|(_, geometry)| {
+            region.fully_contains(geometry.bounding_box())
+                || crosses(geometry, &outline)
+        })
+        .map(|(ent, _)| ent)
+        .collect()
+}
+
+/// Find every entity which is entirely inside a polygon.
+///
+/// This is the "window" version of a lasso selection. The `polygon` is
+/// always treated as closed.
+pub fn window_polygon_select(world: &World, polygon: &Polyline) -> Vec<Entity> {
+    polygon_select(world, polygon, false)
+}
+
+/// Find every entity which is inside a polygon or crosses one of its edges.
+///
+/// This is the "crossing" version of a lasso selection. The `polygon` is
+/// always treated as closed.
+pub fn crossing_polygon_select(
+    world: &World,
+    polygon: &Polyline,
+) -> Vec<Entity> {
+    polygon_select(world, polygon, true)
+}
+
+fn polygon_select(
+    world: &World,
+    polygon: &Polyline,
+    include_crossing: bool,
+) -> Vec<Entity> {
+    if polygon.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut polygon = polygon.clone();
+    polygon.set_closed(true);
+    let region = polygon.bounding_box();
+    let outline = Geometry::Polyline(polygon.clone());
+
+    Candidates::fetch(world)
+        .in_region(region)
+        .into_iter()
+        .filter(|(_, geometry)| {
+            if crosses(geometry, &outline) {
+                include_crossing
+            } else {
+                region.fully_contains(geometry.bounding_box())
+                    && point_on(geometry).map_or(false, |p| polygon.contains(p))
+            }
+        })
+        .map(|(ent, _)| ent)
+        .collect()
+}
+
+/// Find every entity crossed by a "fence".
+///
+/// The `fence` is always treated as an open [`Polyline`], making this handy
+/// for picking a bunch of parallel lines to trim or extend at once.
+pub fn fence_select(world: &World, fence: &Polyline) -> Vec<Entity> {
+    if fence.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut fence = fence.clone();
+    fence.set_closed(false);
+    let region = fence.bounding_box();
+    let fence = Geometry::Polyline(fence);
+
+    Candidates::fetch(world)
+        .in_region(region)
+        .into_iter()
+        .filter(|(_, geometry)| crosses(geometry, &fence))
+        .map(|(ent, _)| ent)
+        .collect()
+}
+
+/// Any point on the [`Geometry`].
+///
+/// If a piece of geometry doesn't cross a polygon's edges, checking whether
+/// this point is inside tells us whether the whole thing is inside.
+fn point_on(geometry: &Geometry) -> Option<Point> {
+    // the corner of the bounding box is never the centre of an arc
+    let corner = geometry.bounding_box().bottom_left();
+    geometry.closest_point(corner).points().first().copied()
+}
+
+/// Does `geometry` touch the `boundary` anywhere?
+pub(crate) fn crosses(geometry: &Geometry, boundary: &Geometry) -> bool {
+    !geometry.intersect(boundary).is_empty()
+}
+
+/// The edges of a [`BoundingBox`] as a closed [`Polyline`].
+fn outline(region: BoundingBox<DrawingSpace>) -> Geometry {
+    let mut polyline = Polyline::from_points(vec![
+        region.bottom_left(),
+        region.bottom_right(),
+        region.top_right(),
+        region.top_left(),
+    ]);
+    polyline.set_closed(true);
+
+    Geometry::Polyline(polyline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{DrawingObject, Layer},
+        Arc, Line, Point,
+    };
+    use euclid::Angle;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn window_and_crossing_selections() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut dispatcher = crate::systems::default_dispatcher(&mut world);
+        let layer = world.create_entity().with(Layer::default()).build();
+        let mut add = |geometry| {
+            world
+                .create_entity()
+                .with(DrawingObject { geometry, layer })
+                .build()
+        };
+        let inside = add(Geometry::Line(Line::new(
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+        )));
+        let crossing = add(Geometry::Line(Line::new(
+            Point::new(5.0, 5.0),
+            Point::new(15.0, 5.0),
+        )));
+        // the bounding box overlaps the region's corner, the arc doesn't
+        let around_the_corner = add(Geometry::Arc(Arc::from_centre_radius(
+            Point::new(12.0, 12.0),
+            2.5,
+            Angle::radians(PI),
+            Angle::radians(PI / 2.0),
+        )));
+        let _outside = add(Geometry::Point(Point::new(20.0, 20.0)));
+        dispatcher.dispatch(&world);
+        let region = BoundingBox::new(Point::zero(), Point::new(10.0, 10.0));
+
+        assert_eq!(window_select(&world, region), vec![inside]);
+
+        let mut got = crossing_select(&world, region);
+        got.sort();
+        assert_eq!(got, vec![inside, crossing]);
+        assert!(!got.contains(&around_the_corner));
+    }
+
+    #[test]
+    fn lasso_selections() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut dispatcher = crate::systems::default_dispatcher(&mut world);
+        let layer = world.create_entity().with(Layer::default()).build();
+        let mut add = |geometry| {
+            world
+                .create_entity()
+                .with(DrawingObject { geometry, layer })
+                .build()
+        };
+        let inside = add(Geometry::Point(Point::new(1.0, 1.0)));
+        let crossing = add(Geometry::Line(Line::new(
+            Point::new(1.0, 3.0),
+            Point::new(3.0, 3.0),
+        )));
+        // inside the polygon's bounding box, but in the notch of the "L"
+        let in_the_notch = add(Geometry::Point(Point::new(3.0, 3.0)));
+        dispatcher.dispatch(&world);
+        let l_shape = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 4.0),
+            Point::new(0.0, 4.0),
+        ]);
+
+        assert_eq!(window_polygon_select(&world, &l_shape), vec![inside]);
+
+        let mut got = crossing_polygon_select(&world, &l_shape);
+        got.sort();
+        assert_eq!(got, vec![inside, crossing]);
+        assert!(!got.contains(&in_the_notch));
+    }
+
+    #[test]
+    fn fence_selection() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut dispatcher = crate::systems::default_dispatcher(&mut world);
+        let layer = world.create_entity().with(Layer::default()).build();
+        let mut add = |x| {
+            world
+                .create_entity()
+                .with(DrawingObject {
+                    geometry: Geometry::Line(Line::new(
+                        Point::new(x, 0.0),
+                        Point::new(x, 10.0),
+                    )),
+                    layer,
+                })
+                .build()
+        };
+        let first = add(1.0);
+        let second = add(2.0);
+        let _past_the_end = add(5.0);
+        let fence = Polyline::from_points(vec![
+            Point::new(0.0, 5.0),
+            Point::new(3.0, 5.0),
+            Point::new(3.0, 20.0),
+            // closing the fence would also cross the last line
+            Point::new(10.0, 8.0),
+        ]);
+        dispatcher.dispatch(&world);
+
+        let mut got = fence_select(&world, &fence);
+        got.sort();
+
+        assert_eq!(got, vec![first, second]);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::components::{snapshot, DrawingEvent, DrawingSnapshot};
+use specs::{prelude::*, shrev::EventChannel};
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+/// Somewhere an [`Autosave`] can send its [`DrawingSnapshot`]s.
+///
+/// The sink decides how the snapshot is serialised and where it ends up
+/// (e.g. a file on disk or browser storage), typically by walking over its
+/// contents with [`DrawingSnapshot::visit()`]. Any closure accepting a
+/// [`DrawingSnapshot`] can be used as a sink.
+pub trait AutosaveSink {
+    type Error: Display;
+
+    fn save(&mut self, snapshot: &DrawingSnapshot) -> Result<(), Self::Error>;
+}
+
+impl<F, E> AutosaveSink for F
+where
+    F: FnMut(&DrawingSnapshot) -> Result<(), E>,
+    E: Display,
+{
+    type Error = E;
+
+    fn save(&mut self, snapshot: &DrawingSnapshot) -> Result<(), E> {
+        self(snapshot)
+    }
+}
+
+/// Periodically takes a [`snapshot()`] of the [`World`] and sends it to an
+/// [`AutosaveSink`].
+///
+/// A save happens every [`Autosave::every_dispatches()`] dispatches (100 by
+/// default), or whenever the [`Autosave::every()`] interval elapses, but
+/// only if a [`DrawingEvent`] (other than a
+/// [`DrawingEvent::SelectionChanged`]) was published since the last save.
+/// Failed saves are logged and retried next time.
+///
+/// The snapshot needs access to the entire [`World`], so this is a [`RunNow`]
+/// which should be added to the dispatcher with
+/// [`DispatcherBuilder::with_thread_local()`].
+#[derive(Debug)]
+pub struct Autosave<S> {
+    sink: S,
+    changes: ReaderId<DrawingEvent>,
+    dirty: bool,
+    dispatches_between_saves: Option<usize>,
+    interval: Option<Duration>,
+    dispatches_since_save: usize,
+    last_save: Instant,
+}
+
+impl<S: AutosaveSink> Autosave<S> {
+    pub const NAME: &'static str = module_path!();
+
+    /// Create an [`Autosave`] which saves every 100 dispatches.
+    pub fn new(world: &mut World, sink: S) -> Self {
+        let changes = world
+            .entry::<EventChannel<DrawingEvent>>()
+            .or_insert_with(EventChannel::default)
+            .register_reader();
+
+        Autosave {
+            sink,
+            changes,
+            dirty: false,
+            dispatches_between_saves: Some(100),
+            interval: None,
+            dispatches_since_save: 0,
+            last_save: Instant::now(),
+        }
+    }
+
+    /// Save every `dispatches` dispatches.
+    pub fn every_dispatches(self, dispatches: usize) -> Self {
+        Autosave {
+            dispatches_between_saves: Some(dispatches),
+            ..self
+        }
+    }
+
+    /// Save whenever `interval` has elapsed since the last save, instead of
+    /// counting dispatches.
+    pub fn every(self, interval: Duration) -> Self {
+        Autosave {
+            dispatches_between_saves: None,
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Save immediately, regardless of whether anything has changed.
+    pub fn save_now(&mut self, world: &World) -> Result<(), S::Error> {
+        self.sink.save(&snapshot(world))?;
+
+        self.dirty = false;
+        self.dispatches_since_save = 0;
+        self.last_save = Instant::now();
+
+        Ok(())
+    }
+
+    /// Get a reference to the [`AutosaveSink`].
+    pub fn sink(&self) -> &S { &self.sink }
+
+    fn is_due(&self) -> bool {
+        let dispatches = self
+            .dispatches_between_saves
+            .map_or(false, |n| self.dispatches_since_save >= n);
+        let elapsed = self
+            .interval
+            .map_or(false, |interval| self.last_save.elapsed() >= interval);
+
+        dispatches || elapsed
+    }
+}
+
+impl<'world, S: AutosaveSink> RunNow<'world> for Autosave<S> {
+    fn run_now(&mut self, world: &'world World) {
+        self.dispatches_since_save += 1;
+
+        let changed = world
+            .fetch::<EventChannel<DrawingEvent>>()
+            .read(&mut self.changes)
+            .any(|event| *event != DrawingEvent::SelectionChanged);
+        self.dirty |= changed;
+
+        if self.dirty && self.is_due() {
+            if let Err(e) = self.save_now(world) {
+                log::warn!("Autosave failed: {}", e);
+            }
+        }
+    }
+
+    fn setup(&mut self, _world: &mut World) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{DrawingObject, Geometry},
+        systems::DrawingEventBookkeeping,
+        Point,
+    };
+    use std::convert::Infallible;
+
+    /// Records how many entities were in each snapshot.
+    #[derive(Debug, Default)]
+    struct Recorder(Vec<usize>);
+
+    impl AutosaveSink for Recorder {
+        type Error = Infallible;
+
+        fn save(
+            &mut self,
+            snapshot: &DrawingSnapshot,
+        ) -> Result<(), Infallible> {
+            self.0.push(snapshot.len());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn only_save_when_something_changed() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut events = DrawingEventBookkeeping::new(&world);
+        System::setup(&mut events, &mut world);
+        let mut autosave =
+            Autosave::new(&mut world, Recorder::default()).every_dispatches(2);
+        let layer = world.create_entity().build();
+
+        let mut dispatch = |world: &World| {
+            events.run_now(world);
+            autosave.run_now(world);
+            autosave.sink().0.clone()
+        };
+
+        assert!(dispatch(&world).is_empty());
+        assert!(dispatch(&world).is_empty());
+
+        world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::zero()),
+                layer,
+            })
+            .build();
+
+        assert_eq!(dispatch(&world), vec![2]);
+        assert_eq!(dispatch(&world), vec![2]);
+        assert_eq!(dispatch(&world), vec![2]);
+    }
+}
@@ -1,23 +1,39 @@
-use crate::{algorithms::Bounded, components::DrawingObject, BoundingBox, DrawingSpace};
+use crate::{
+    algorithms::Bounded,
+    components::{BlockTable, DrawingObject, Geometry},
+    BoundingBox, DrawingSpace,
+};
 use specs::prelude::*;
 
 /// Lets us keep track of a [`DrawingObject`]'s rough location in *Drawing
 /// Space*.
+///
+/// Every [`DrawingObject`] gets a [`BoundingBox`] which is kept up to date as
+/// its geometry changes, including objects which existed before the system
+/// was created. The bounds of a [`crate::components::Insert`] cover its
+/// block's geometry, and are recalculated whenever the [`BlockTable`]
+/// changes.
 #[derive(Debug)]
 pub struct SyncBounds {
     changes: ReaderId<ComponentEvent>,
     to_update: BitSet,
     removed: BitSet,
+    block_generation: Option<u64>,
 }
 
 impl SyncBounds {
     pub const NAME: &'static str = module_path!();
 
     pub fn new(world: &World) -> SyncBounds {
+        let mut drawing_objects = world.write_storage::<DrawingObject>();
+        let changes = drawing_objects.register_reader();
+
         SyncBounds {
-            changes: world.write_storage::<DrawingObject>().register_reader(),
-            to_update: BitSet::new(),
+            changes,
+            // anything created before we started listening needs bounds too
+            to_update: drawing_objects.mask().clone(),
             removed: BitSet::new(),
+            block_generation: None,
         }
     }
 }
@@ -27,14 +43,11 @@ impl<'world> System<'world> for SyncBounds {
         WriteStorage<'world, BoundingBox<DrawingSpace>>,
         ReadStorage<'world, DrawingObject>,
         Entities<'world>,
+        Option<Read<'world, BlockTable>>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        // clear any left-over flags
-        self.to_update.clear();
-        self.removed.clear();
-
-        let (mut bounds, drawing_objects, entities) = data;
+        let (mut bounds, drawing_objects, entities, blocks) = data;
 
         // find out which items have changed since we were last polled
         for event in drawing_objects.channel().read(&mut self.changes) {
@@ -48,16 +61,110 @@ impl<'world> System<'world> for SyncBounds {
             }
         }
 
+        // every insert needs updating when its block definition changes
+        let generation = blocks.as_ref().map(|b| b.generation());
+        if generation != self.block_generation {
+            self.block_generation = generation;
+
+            for (ent, drawing_object) in (&entities, &drawing_objects).join() {
+                if let Geometry::Insert(_) = drawing_object.geometry {
+                    self.to_update.add(ent.id());
+                }
+            }
+        }
+
         for (ent, drawing_object, _) in
             (&entities, &drawing_objects, &self.to_update).join()
         {
-            bounds
-                .insert(ent, drawing_object.geometry.bounding_box())
-                .unwrap();
+            let bounding_box = match (&drawing_object.geometry, &blocks) {
+                (Geometry::Insert(insert), Some(blocks)) => {
+                    insert.bounding_box_within(blocks)
+                },
+                (geometry, _) => geometry.bounding_box(),
+            };
+
+            bounds.insert(ent, bounding_box).unwrap();
         }
 
         for (ent, _) in (&entities, &self.removed).join() {
             bounds.remove(ent);
         }
+
+        self.to_update.clear();
+        self.removed.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{BlockDefinition, Insert},
+        Line, Point,
+    };
+
+    #[test]
+    fn insert_bounds_follow_the_block() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut blocks = BlockTable::default();
+        blocks.insert(BlockDefinition::new("Door", Point::zero(), Vec::new()));
+        world.insert(blocks);
+        let mut system = SyncBounds::new(&world);
+        let layer = world.create_entity().build();
+        let door = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Insert(Insert::new(
+                    "Door",
+                    Point::new(10.0, 0.0),
+                )),
+                layer,
+            })
+            .build();
+        system.run_now(&world);
+
+        world.fetch_mut::<BlockTable>().insert(BlockDefinition::new(
+            "Door",
+            Point::zero(),
+            vec![Geometry::Line(Line::new(
+                Point::zero(),
+                Point::new(1.0, 2.0),
+            ))],
+        ));
+        system.run_now(&world);
+
+        let got = *world
+            .read_storage::<BoundingBox<DrawingSpace>>()
+            .get(door)
+            .unwrap();
+        assert_eq!(
+            got,
+            BoundingBox::new(Point::new(10.0, 0.0), Point::new(11.0, 2.0))
+        );
+    }
+
+    #[test]
+    fn existing_objects_get_bounds() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let layer = world.create_entity().build();
+        let line = Line::new(Point::zero(), Point::new(3.0, 4.0));
+        let existing = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(line),
+                layer,
+            })
+            .build();
+
+        let mut system = SyncBounds::new(&world);
+        system.run_now(&world);
+
+        let got = world
+            .read_storage::<BoundingBox<DrawingSpace>>()
+            .get(existing)
+            .copied();
+        assert_eq!(got, Some(line.bounding_box()));
     }
 }
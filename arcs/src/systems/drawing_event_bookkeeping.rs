@@ -0,0 +1,125 @@
+use crate::components::{DrawingEvent, DrawingObject};
+use specs::{prelude::*, shrev::EventChannel, world::Index};
+use std::collections::HashMap;
+
+/// Publishes a [`DrawingEvent`] whenever a [`DrawingObject`] is created,
+/// deleted, or changed.
+#[derive(Debug)]
+pub struct DrawingEventBookkeeping {
+    changes: ReaderId<ComponentEvent>,
+    /// Each known [`DrawingObject`] and the layer it was on.
+    known: HashMap<Index, (Entity, Entity)>,
+}
+
+impl DrawingEventBookkeeping {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        let mut objects = world.write_storage::<DrawingObject>();
+        let changes = objects.register_reader();
+        let known = (&world.entities(), &objects)
+            .join()
+            .map(|(ent, object)| (ent.id(), (ent, object.layer)))
+            .collect();
+
+        DrawingEventBookkeeping { changes, known }
+    }
+}
+
+impl<'world> System<'world> for DrawingEventBookkeeping {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, DrawingObject>,
+        Write<'world, EventChannel<DrawingEvent>>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, objects, mut events) = data;
+
+        for event in objects.channel().read(&mut self.changes) {
+            match *event {
+                ComponentEvent::Inserted(id) => {
+                    let ent = entities.entity(id);
+
+                    if let Some(object) = objects.get(ent) {
+                        self.known.insert(id, (ent, object.layer));
+                        events.single_write(DrawingEvent::EntityCreated(ent));
+                    }
+                },
+                ComponentEvent::Modified(id) => {
+                    let ent = entities.entity(id);
+                    let object = match objects.get(ent) {
+                        Some(object) => object,
+                        None => continue,
+                    };
+
+                    events.single_write(DrawingEvent::GeometryChanged(ent));
+
+                    let previous = self.known.insert(id, (ent, object.layer));
+                    if previous.map(|(_, layer)| layer) != Some(object.layer) {
+                        events.single_write(DrawingEvent::MovedToLayer {
+                            entity: ent,
+                            layer: object.layer,
+                        });
+                    }
+                },
+                ComponentEvent::Removed(id) => {
+                    if let Some((ent, _)) = self.known.remove(&id) {
+                        events.single_write(DrawingEvent::EntityDeleted(ent));
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::Geometry, Point};
+
+    #[test]
+    fn publish_object_lifecycle() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = DrawingEventBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .fetch_mut::<EventChannel<DrawingEvent>>()
+            .register_reader();
+        let first_layer = world.create_entity().build();
+        let second_layer = world.create_entity().build();
+
+        let point = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::zero()),
+                layer: first_layer,
+            })
+            .build();
+        system.run_now(&world);
+        world
+            .write_storage::<DrawingObject>()
+            .get_mut(point)
+            .unwrap()
+            .layer = second_layer;
+        system.run_now(&world);
+        world.delete_entity(point).unwrap();
+        system.run_now(&world);
+
+        let events = world.fetch::<EventChannel<DrawingEvent>>();
+        let got: Vec<_> = events.read(&mut reader).copied().collect();
+        assert_eq!(
+            got,
+            vec![
+                DrawingEvent::EntityCreated(point),
+                DrawingEvent::GeometryChanged(point),
+                DrawingEvent::MovedToLayer {
+                    entity: point,
+                    layer: second_layer
+                },
+                DrawingEvent::EntityDeleted(point),
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::components::{Group, GroupMember};
+use specs::prelude::*;
+
+/// Keeps each [`Group`] consistent with its members.
+///
+/// Deleted entities are dropped from their [`Group`], and entities whose
+/// [`Group`] has been deleted lose their [`GroupMember`].
+#[derive(Debug)]
+pub struct GroupBookkeeping {
+    group_changes: ReaderId<ComponentEvent>,
+    member_changes: ReaderId<ComponentEvent>,
+}
+
+impl GroupBookkeeping {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> GroupBookkeeping {
+        GroupBookkeeping {
+            group_changes: world.write_storage::<Group>().register_reader(),
+            member_changes: world
+                .write_storage::<GroupMember>()
+                .register_reader(),
+        }
+    }
+}
+
+impl<'world> System<'world> for GroupBookkeeping {
+    type SystemData = (
+        Entities<'world>,
+        WriteStorage<'world, Group>,
+        WriteStorage<'world, GroupMember>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, mut groups, mut membership) = data;
+
+        let group_events =
+            groups.channel().read(&mut self.group_changes).count();
+        let member_events =
+            membership.channel().read(&mut self.member_changes).count();
+
+        if group_events == 0 && member_events == 0 {
+            return;
+        }
+
+        // members of a group which no longer exists
+        let orphans: Vec<Entity> = (&entities, &membership)
+            .join()
+            .filter(|(_, GroupMember(group))| !groups.contains(*group))
+            .map(|(ent, _)| ent)
+            .collect();
+
+        for orphan in orphans {
+            membership.remove(orphan);
+        }
+
+        // members which were deleted or have been moved to another group
+        let stale: Vec<Entity> = (&entities, &groups)
+            .join()
+            .filter(|(ent, group)| {
+                group
+                    .members()
+                    .iter()
+                    .any(|&m| membership.get(m) != Some(&GroupMember(*ent)))
+            })
+            .map(|(ent, _)| ent)
+            .collect();
+
+        for ent in stale {
+            if let Some(group) = groups.get_mut(ent) {
+                group.retain(|&m| {
+                    entities.is_alive(m)
+                        && membership.get(m) == Some(&GroupMember(ent))
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::create_group;
+
+    #[test]
+    fn deleted_members_and_groups_are_cleaned_up() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = GroupBookkeeping::new(&world);
+        let ents: Vec<_> =
+            (0..3).map(|_| world.create_entity().build()).collect();
+        let first = create_group(&world, None, vec![ents[0], ents[1]]);
+        let second = create_group(&world, None, vec![ents[2]]);
+        system.run_now(&world);
+
+        world.delete_entity(ents[1]).unwrap();
+        world.delete_entity(second).unwrap();
+        world.maintain();
+        system.run_now(&world);
+
+        let groups = world.read_storage::<Group>();
+        assert_eq!(groups.get(first).unwrap().members(), &[ents[0]]);
+        let membership = world.read_storage::<GroupMember>();
+        assert!(membership.get(ents[2]).is_none());
+    }
+}
@@ -0,0 +1,219 @@
+use crate::components::{Children, Deleted, Parent};
+use specs::prelude::*;
+use std::collections::HashMap;
+
+/// Keeps each entity's [`Children`] consistent with the [`Parent`]
+/// components pointing at it, deleting children (and their children) when
+/// their [`Parent`] is deleted.
+///
+/// Children of a soft-deleted parent are marked as [`Deleted`] too, so the
+/// whole family can be restored together.
+#[derive(Debug)]
+pub struct HierarchyBookkeeping {
+    parent_changes: ReaderId<ComponentEvent>,
+    children_changes: ReaderId<ComponentEvent>,
+    deleted_changes: ReaderId<ComponentEvent>,
+}
+
+impl HierarchyBookkeeping {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        HierarchyBookkeeping {
+            parent_changes: world.write_storage::<Parent>().register_reader(),
+            children_changes: world
+                .write_storage::<Children>()
+                .register_reader(),
+            deleted_changes: world.write_storage::<Deleted>().register_reader(),
+        }
+    }
+}
+
+impl<'world> System<'world> for HierarchyBookkeeping {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, Parent>,
+        WriteStorage<'world, Children>,
+        WriteStorage<'world, Deleted>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, parents, mut children, mut deleted) = data;
+
+        let parent_events =
+            parents.channel().read(&mut self.parent_changes).count();
+        let children_events =
+            children.channel().read(&mut self.children_changes).count();
+        let deleted_events =
+            deleted.channel().read(&mut self.deleted_changes).count();
+
+        if parent_events == 0 && children_events == 0 && deleted_events == 0 {
+            return;
+        }
+
+        // tombstone everything descended from a tombstoned entity
+        loop {
+            let orphans: Vec<Entity> = (&entities, &parents, !&deleted)
+                .join()
+                .filter(|(_, Parent(parent), _)| deleted.contains(*parent))
+                .map(|(ent, _, _)| ent)
+                .collect();
+
+            if orphans.is_empty() {
+                break;
+            }
+
+            for orphan in orphans {
+                let _ = deleted.insert(orphan, Deleted);
+            }
+        }
+
+        // we caused these events, so don't wake up for them next time
+        deleted.channel().read(&mut self.deleted_changes).count();
+
+        // keep going until we've found every descendant of a deleted entity
+        let mut doomed = BitSet::new();
+        loop {
+            let orphans: Vec<Entity> = (&entities, &parents, !&doomed)
+                .join()
+                .filter(|(_, Parent(parent), _)| {
+                    !entities.is_alive(*parent) || doomed.contains(parent.id())
+                })
+                .map(|(ent, _, _)| ent)
+                .collect();
+
+            if orphans.is_empty() {
+                break;
+            }
+
+            for orphan in orphans {
+                doomed.add(orphan.id());
+            }
+        }
+
+        for (ent, _) in (&entities, &doomed).join() {
+            if let Err(e) = entities.delete(ent) {
+                log::warn!("Unable to delete {:?}: {}", ent, e);
+            }
+        }
+
+        // work out who everyone's children should be, keeping the existing
+        // order where possible
+        let mut expected: HashMap<Entity, Vec<Entity>> = HashMap::new();
+
+        for (ent, existing) in (&entities, &children).join() {
+            let still_attached = existing
+                .iter()
+                .filter(|&child| {
+                    entities.is_alive(child)
+                        && !doomed.contains(child.id())
+                        && parents.get(child) == Some(&Parent(ent))
+                })
+                .collect();
+            expected.insert(ent, still_attached);
+        }
+
+        for (child, &Parent(parent), _) in
+            (&entities, &parents, !&doomed).join()
+        {
+            let siblings = expected.entry(parent).or_default();
+            if !siblings.contains(&child) {
+                siblings.push(child);
+            }
+        }
+
+        for (parent, kids) in expected {
+            if doomed.contains(parent.id()) || !entities.is_alive(parent) {
+                continue;
+            }
+
+            if kids.is_empty() {
+                children.remove(parent);
+            } else {
+                let unchanged = children
+                    .get(parent)
+                    .map(|c| c.iter().eq(kids.iter().copied()))
+                    .unwrap_or(false);
+
+                // only touch the storage when something changed, otherwise
+                // we'd be triggered again next time
+                if !unchanged {
+                    let _ = children.insert(parent, Children::from(kids));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn children_follow_their_parents() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = HierarchyBookkeeping::new(&world);
+        let line = world.create_entity().build();
+        let dimension = world.create_entity().with(Parent(line)).build();
+        let label = world.create_entity().with(Parent(dimension)).build();
+        let other = world.create_entity().build();
+        system.run_now(&world);
+
+        {
+            let children = world.read_storage::<Children>();
+            let got: Vec<_> = children.get(line).unwrap().iter().collect();
+            assert_eq!(got, vec![dimension]);
+            assert!(children.get(dimension).unwrap().contains(label));
+        }
+
+        world.delete_entity(line).unwrap();
+        world.maintain();
+        system.run_now(&world);
+        world.maintain();
+
+        assert!(!world.is_alive(dimension));
+        assert!(!world.is_alive(label));
+        assert!(world.is_alive(other));
+    }
+
+    #[test]
+    fn reparenting_updates_both_sides() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = HierarchyBookkeeping::new(&world);
+        let first = world.create_entity().build();
+        let second = world.create_entity().build();
+        let child = world.create_entity().with(Parent(first)).build();
+        system.run_now(&world);
+
+        world
+            .write_storage::<Parent>()
+            .insert(child, Parent(second))
+            .unwrap();
+        system.run_now(&world);
+
+        let children = world.read_storage::<Children>();
+        assert!(children.get(first).is_none());
+        assert!(children.get(second).unwrap().contains(child));
+    }
+
+    #[test]
+    fn soft_deletes_cascade_to_children() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = HierarchyBookkeeping::new(&world);
+        let line = world.create_entity().build();
+        let dimension = world.create_entity().with(Parent(line)).build();
+        let label = world.create_entity().with(Parent(dimension)).build();
+        system.run_now(&world);
+
+        crate::components::soft_delete(&world, line);
+        system.run_now(&world);
+
+        let deleted = world.read_storage::<Deleted>();
+        assert!(deleted.contains(dimension));
+        assert!(deleted.contains(label));
+        assert!(world.is_alive(dimension));
+    }
+}
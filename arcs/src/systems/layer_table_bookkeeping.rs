@@ -0,0 +1,139 @@
+use crate::components::{DrawingEvent, Layer, LayerTable, Name};
+use specs::{prelude::*, shrev::EventChannel};
+
+/// A [`System`] which keeps the global [`LayerTable`] in sync with every
+/// entity that has both a [`Layer`] and a [`Name`].
+///
+/// Any changes to a layer are published as a [`DrawingEvent::LayerChanged`].
+#[derive(Debug)]
+pub struct LayerTableBookkeeping {
+    layer_changes: ReaderId<ComponentEvent>,
+    name_changes: ReaderId<ComponentEvent>,
+    changed: BitSet,
+}
+
+impl LayerTableBookkeeping {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        LayerTableBookkeeping {
+            layer_changes: world.write_storage::<Layer>().register_reader(),
+            name_changes: world.write_storage::<Name>().register_reader(),
+            changed: BitSet::new(),
+        }
+    }
+}
+
+impl<'world> System<'world> for LayerTableBookkeeping {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, Layer>,
+        ReadStorage<'world, Name>,
+        Write<'world, LayerTable>,
+        Write<'world, EventChannel<DrawingEvent>>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        self.changed.clear();
+
+        let (entities, layers, names, mut table, mut events) = data;
+        let mut removed = Vec::new();
+
+        let changes = layers
+            .channel()
+            .read(&mut self.layer_changes)
+            .chain(names.channel().read(&mut self.name_changes));
+
+        for event in changes {
+            let id = match *event {
+                ComponentEvent::Inserted(id)
+                | ComponentEvent::Modified(id)
+                | ComponentEvent::Removed(id) => id,
+            };
+
+            removed.extend(table.remove_by_id(id));
+            self.changed.add(id);
+        }
+
+        for (ent, _, name, _) in
+            (&entities, &layers, &names, &self.changed).join()
+        {
+            table.insert(name, ent);
+        }
+
+        for ent in removed {
+            if !entities.is_alive(ent) || !layers.contains(ent) {
+                events.single_write(DrawingEvent::LayerChanged(ent));
+            }
+        }
+
+        for (ent, _, _) in (&entities, &layers, &self.changed).join() {
+            events.single_write(DrawingEvent::LayerChanged(ent));
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        <Self::SystemData as shred::DynamicSystemData>::setup(
+            &self.accessor(),
+            world,
+        );
+
+        let (entities, layers, names, mut table, _) = world.system_data::<(
+            Entities<'_>,
+            ReadStorage<'_, Layer>,
+            ReadStorage<'_, Name>,
+            Write<'_, LayerTable>,
+            Write<'_, EventChannel<DrawingEvent>>,
+        )>();
+        table.clear();
+
+        for (ent, _, name) in (&entities, &layers, &names).join() {
+            table.insert(name, ent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follow_layers_being_added_renamed_and_deleted() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let walls = Layer::create(
+            world.create_entity(),
+            Name::new("Walls"),
+            Layer::default(),
+        );
+        let mut system = LayerTableBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        assert_eq!(world.fetch::<LayerTable>().get("WALLS"), Some(walls));
+
+        // named entities which aren't layers are ignored
+        world.create_entity().with(Name::new("Door")).build();
+        let dim = Layer::create(
+            world.create_entity(),
+            Name::new("DIM"),
+            Layer::default(),
+        );
+        world
+            .write_storage::<Name>()
+            .insert(walls, Name::new("Partitions"))
+            .unwrap();
+        system.run_now(&world);
+
+        {
+            let table = world.fetch::<LayerTable>();
+            assert_eq!(table.get("dim"), Some(dim));
+            assert_eq!(table.get("Walls"), None);
+            assert_eq!(table.get("door"), None);
+            assert_eq!(table.get("partitions"), Some(walls));
+        }
+
+        world.delete_entity(dim).unwrap();
+        system.run_now(&world);
+
+        assert_eq!(world.fetch::<LayerTable>().len(), 1);
+    }
+}
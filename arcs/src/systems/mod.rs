@@ -1,16 +1,50 @@
 //! Background tasks and useful [`specs::System`]s.
 
+mod autosave;
 mod bounds;
+mod drawing_event_bookkeeping;
+mod group_bookkeeping;
+mod hierarchy_bookkeeping;
+mod layer_table_bookkeeping;
 mod name_table_bookkeeping;
+mod purge_deleted;
+mod recompute_derived;
+mod selection_bookkeeping;
+mod solve_constraints;
+mod spatial_index_bookkeeping;
+mod sync_annotations;
+mod sync_radial_dimensions;
+mod track_dirty_regions;
+mod update_statistics;
 // mod spatial_relation;
 
+pub use autosave::{Autosave, AutosaveSink};
 pub use bounds::SyncBounds;
+pub use drawing_event_bookkeeping::DrawingEventBookkeeping;
+pub use group_bookkeeping::GroupBookkeeping;
+pub use hierarchy_bookkeeping::HierarchyBookkeeping;
+pub use layer_table_bookkeeping::LayerTableBookkeeping;
 pub use name_table_bookkeeping::NameTableBookkeeping;
+pub use purge_deleted::PurgeDeleted;
+pub use recompute_derived::RecomputeDerived;
+pub use selection_bookkeeping::SelectionBookkeeping;
+pub use solve_constraints::SolveConstraints;
+pub use spatial_index_bookkeeping::SpatialIndexBookkeeping;
+pub use sync_annotations::SyncAnnotations;
+pub use sync_radial_dimensions::SyncRadialDimensions;
+pub use track_dirty_regions::TrackDirtyRegions;
+pub use update_statistics::UpdateStatistics;
 // pub use spatial_relation::SpatialRelation;
 
-use specs::{DispatcherBuilder, World};
+use specs::{Dispatcher, DispatcherBuilder, World};
 
 /// Register any necessary background tasks with a [`DispatcherBuilder`].
+///
+/// The tasks are registered with the dependencies they need to run in the
+/// correct order (e.g. constraints are solved before bounding boxes are
+/// recalculated, and the [`SpatialIndex`] is updated afterwards).
+///
+/// [`SpatialIndex`]: crate::components::SpatialIndex
 pub fn register_background_tasks<'a, 'b>(
     builder: DispatcherBuilder<'a, 'b>,
     world: &World,
@@ -21,5 +55,136 @@ pub fn register_background_tasks<'a, 'b>(
             NameTableBookkeeping::NAME,
             &[],
         )
-        .with(SyncBounds::new(world), SyncBounds::NAME, &[])
+        .with(GroupBookkeeping::new(world), GroupBookkeeping::NAME, &[])
+        .with(
+            LayerTableBookkeeping::new(world),
+            LayerTableBookkeeping::NAME,
+            &[],
+        )
+        .with(
+            HierarchyBookkeeping::new(world),
+            HierarchyBookkeeping::NAME,
+            &[],
+        )
+        .with(
+            SelectionBookkeeping::new(world),
+            SelectionBookkeeping::NAME,
+            &[],
+        )
+        .with(SolveConstraints::new(world), SolveConstraints::NAME, &[])
+        .with(
+            RecomputeDerived::new(world),
+            RecomputeDerived::NAME,
+            &[SolveConstraints::NAME],
+        )
+        .with(
+            SyncRadialDimensions::new(world),
+            SyncRadialDimensions::NAME,
+            &[RecomputeDerived::NAME],
+        )
+        .with(
+            SyncAnnotations::new(world),
+            SyncAnnotations::NAME,
+            &[RecomputeDerived::NAME],
+        )
+        .with(
+            SyncBounds::new(world),
+            SyncBounds::NAME,
+            &[
+                RecomputeDerived::NAME,
+                SyncRadialDimensions::NAME,
+                SyncAnnotations::NAME,
+            ],
+        )
+        .with(
+            SpatialIndexBookkeeping::new(world),
+            SpatialIndexBookkeeping::NAME,
+            &[SyncBounds::NAME],
+        )
+        .with(
+            TrackDirtyRegions::new(world),
+            TrackDirtyRegions::NAME,
+            &[SyncBounds::NAME],
+        )
+        .with(
+            UpdateStatistics::new(world),
+            UpdateStatistics::NAME,
+            &[SyncBounds::NAME],
+        )
+        .with(
+            DrawingEventBookkeeping::new(world),
+            DrawingEventBookkeeping::NAME,
+            &[
+                RecomputeDerived::NAME,
+                SyncRadialDimensions::NAME,
+                SyncAnnotations::NAME,
+            ],
+        )
+        .with(
+            PurgeDeleted::new(world),
+            PurgeDeleted::NAME,
+            &[
+                NameTableBookkeeping::NAME,
+                GroupBookkeeping::NAME,
+                HierarchyBookkeeping::NAME,
+                SelectionBookkeeping::NAME,
+                SpatialIndexBookkeeping::NAME,
+                LayerTableBookkeeping::NAME,
+                DrawingEventBookkeeping::NAME,
+                UpdateStatistics::NAME,
+            ],
+        )
+}
+
+/// Create a [`Dispatcher`] which runs all the background tasks, making sure
+/// any resources they need have been added to the [`World`].
+///
+/// Use [`register_background_tasks()`] instead if the application has its
+/// own systems to add.
+pub fn default_dispatcher(world: &mut World) -> Dispatcher<'static, 'static> {
+    let mut dispatcher =
+        register_background_tasks(DispatcherBuilder::new(), world).build();
+    dispatcher.setup(world);
+
+    dispatcher
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{Constraint, DrawingObject, Geometry, SpatialIndex},
+        BoundingBox, DrawingSpace, Line, Point,
+    };
+    use specs::{Builder, WorldExt};
+
+    #[test]
+    fn solved_geometry_is_indexed_in_a_single_dispatch() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut dispatcher = default_dispatcher(&mut world);
+        let layer = world.create_entity().build();
+        let line = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(Line::new(
+                    Point::zero(),
+                    Point::new(4.0, 2.0),
+                )),
+                layer,
+            })
+            .build();
+        world
+            .create_entity()
+            .with(Constraint::Horizontal(line))
+            .build();
+
+        dispatcher.dispatch(&world);
+        world.maintain();
+
+        let bounds = world.read_storage::<BoundingBox<DrawingSpace>>();
+        let bounds = bounds.get(line).unwrap();
+        assert_eq!(bounds.height().get(), 0.0);
+        assert_eq!(world.fetch::<SpatialIndex>().get(line), Some(*bounds));
+    }
 }
@@ -1,7 +1,16 @@
-use crate::components::{Name, NameTable};
-use specs::prelude::*;
+use crate::components::{
+    DuplicateNamePolicy, Name, NameConflict, NameEvent, NameGenerator,
+    NameTable,
+};
+use shred_derive::SystemData;
+use specs::{prelude::*, shrev::EventChannel};
+use std::collections::HashMap;
 
 /// A [`System`] which makes sure the global [`NameTable`] is kept up-to-date.
+///
+/// Name collisions are resolved using the [`DuplicateNamePolicy`] resource and
+/// reported via an [`EventChannel`] of [`NameConflict`]s. Any changes to the
+/// [`NameTable`] are published as [`NameEvent`]s.
 #[derive(Debug)]
 pub struct NameTableBookkeeping {
     changes: ReaderId<ComponentEvent>,
@@ -20,72 +29,212 @@ impl NameTableBookkeeping {
 }
 
 impl<'world> System<'world> for NameTableBookkeeping {
-    type SystemData = (
-        Entities<'world>,
-        ReadStorage<'world, Name>,
-        Write<'world, NameTable>,
-    );
-
-    fn run(&mut self, data: Self::SystemData) {
-        let (entities, names, mut name_table) = data;
+    type SystemData = NameTableData<'world>;
 
+    fn run(&mut self, mut data: Self::SystemData) {
         // clear any left-over data
         self.inserted.clear();
+        let mut previous_names = HashMap::new();
 
         // record which changes have happened since we last ran
-        for event in names.channel().read(&mut self.changes) {
-            match dbg!(event) {
+        for event in data.names.channel().read(&mut self.changes) {
+            match event {
                 ComponentEvent::Inserted(id) => {
                     self.inserted.add(*id);
                 },
                 ComponentEvent::Removed(id) => {
-                    name_table.remove_by_id(*id);
+                    if let Some((entity, name)) =
+                        data.name_table.remove_by_id(*id)
+                    {
+                        data.events
+                            .single_write(NameEvent::Removed { entity, name });
+                    }
                 },
                 ComponentEvent::Modified(id) => {
-                    name_table.remove_by_id(*id);
+                    if let Some((entity, name)) =
+                        data.name_table.remove_by_id(*id)
+                    {
+                        previous_names.insert(entity, name);
+                    }
                     self.inserted.add(*id);
                 },
             }
         }
 
-        for (ent, name, _) in (&entities, &names, &self.inserted).join() {
-            use std::collections::hash_map::Entry;
+        data.associate_names(&self.inserted, previous_names);
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        <Self::SystemData as shred::DynamicSystemData>::setup(
+            &self.accessor(),
+            world,
+        );
+
+        let mut data = world.system_data::<NameTableData<'_>>();
+
+        data.name_table.clear();
+
+        let everything = (&data.entities, &data.names)
+            .join()
+            .map(|(ent, _)| ent.id())
+            .collect();
+
+        data.associate_names(&everything, HashMap::new());
+    }
+}
+
+/// The state used by [`NameTableBookkeeping`].
+#[derive(SystemData)]
+#[allow(missing_debug_implementations)]
+pub struct NameTableData<'world> {
+    entities: Entities<'world>,
+    names: WriteStorage<'world, Name>,
+    name_table: Write<'world, NameTable>,
+    policy: Read<'world, DuplicateNamePolicy>,
+    generator: Write<'world, NameGenerator>,
+    conflicts: Write<'world, EventChannel<NameConflict>>,
+    events: Write<'world, EventChannel<NameEvent>>,
+}
+
+impl<'world> NameTableData<'world> {
+    /// Add the names for each entity in `to_associate` to the [`NameTable`],
+    /// resolving any conflicts according to the [`DuplicateNamePolicy`].
+    ///
+    /// The `previous_names` are used to tell whether an entity was renamed.
+    fn associate_names(
+        &mut self,
+        to_associate: &BitSet,
+        mut previous_names: HashMap<Entity, Name>,
+    ) {
+        let mut fixups = Vec::new();
 
-            match name_table.names.entry(name.clone()) {
-                Entry::Vacant(entry) => {
-                    entry.insert(ent);
+        for (ent, name, _) in (&self.entities, &self.names, to_associate).join()
+        {
+            let previous_name = previous_names.remove(&ent);
+
+            let existing = match self.name_table.get(name.as_str()) {
+                Some(existing) if existing != ent => existing,
+                _ => {
+                    self.name_table.insert(name.clone(), ent);
+                    publish_change(
+                        &mut self.events,
+                        ent,
+                        previous_name,
+                        Some(name.clone()),
+                    );
+                    continue;
                 },
-                Entry::Occupied(mut entry) => {
+            };
+
+            let mut conflict = NameConflict {
+                name: name.clone(),
+                existing,
+                duplicate: ent,
+                renamed_to: None,
+            };
+
+            let new_name = match *self.policy {
+                DuplicateNamePolicy::ReplaceOld => {
                     log::warn!(
                         "Duplicate name found when associating {:?} with \"{}\" (previous entity: {:?})",
                         ent,
                         name.as_ref(),
-                        entry.get()
+                        existing
                     );
-                    entry.insert(ent);
+                    self.name_table.insert(name.clone(), ent);
+                    self.events.single_write(NameEvent::Removed {
+                        entity: existing,
+                        name: name.clone(),
+                    });
+                    Some(name.clone())
                 },
-            }
-        }
-    }
-
-    fn setup(&mut self, world: &mut World) {
-        <Self::SystemData as shred::DynamicSystemData>::setup(
-            &self.accessor(),
-            world,
-        );
+                DuplicateNamePolicy::KeepOld => {
+                    log::warn!(
+                        "Ignoring the duplicate name \"{}\" on {:?} (already used by {:?})",
+                        name.as_ref(),
+                        ent,
+                        existing
+                    );
+                    None
+                },
+                DuplicateNamePolicy::AutoRename => {
+                    let new_name = self
+                        .generator
+                        .next_available(name.as_str(), &self.name_table);
+                    log::debug!(
+                        "Renaming {:?} from \"{}\" to \"{}\" to avoid a conflict with {:?}",
+                        ent,
+                        name.as_ref(),
+                        new_name.as_ref(),
+                        existing
+                    );
+                    self.name_table.insert(new_name.clone(), ent);
+                    conflict.renamed_to = Some(new_name.clone());
+                    fixups.push((ent, Some(new_name.clone())));
+                    Some(new_name)
+                },
+                DuplicateNamePolicy::Error => {
+                    log::error!(
+                        "Removing the duplicate name \"{}\" from {:?} (already used by {:?})",
+                        name.as_ref(),
+                        ent,
+                        existing
+                    );
+                    fixups.push((ent, None));
+                    None
+                },
+            };
 
-        let entities = world.entities();
-        let names = world.read_storage::<Name>();
-        let mut name_table = world.write_resource::<NameTable>();
+            publish_change(&mut self.events, ent, previous_name, new_name);
+            self.conflicts.single_write(conflict);
+        }
 
-        name_table.clear();
+        // anything left over was modified but didn't make it back into the
+        // table
+        for (entity, name) in previous_names {
+            self.events
+                .single_write(NameEvent::Removed { entity, name });
+        }
 
-        for (ent, name) in (&entities, &names).join() {
-            name_table.names.insert(name.clone(), ent);
+        for (ent, new_name) in fixups {
+            match new_name {
+                Some(new_name) => {
+                    self.names
+                        .insert(ent, new_name)
+                        .expect("The entity is still alive");
+                },
+                None => {
+                    self.names.remove(ent);
+                },
+            }
         }
     }
 }
 
+/// Publish the [`NameEvent`] corresponding to an [`Entity`]'s entry in the
+/// [`NameTable`] going from `previous_name` to `new_name`.
+fn publish_change(
+    events: &mut EventChannel<NameEvent>,
+    entity: Entity,
+    previous_name: Option<Name>,
+    new_name: Option<Name>,
+) {
+    let event = match (previous_name, new_name) {
+        (Some(old_name), Some(new_name)) if old_name != new_name => {
+            NameEvent::Renamed {
+                entity,
+                old_name,
+                new_name,
+            }
+        },
+        (None, Some(name)) => NameEvent::Added { entity, name },
+        (Some(name), None) => NameEvent::Removed { entity, name },
+        _ => return,
+    };
+
+    events.single_write(event);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,6 +255,28 @@ mod tests {
         assert_eq!(names.get("second").unwrap(), second);
     }
 
+    #[test]
+    fn look_up_the_name_of_an_entity() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let first = world.create_entity().with(Name::new("first")).build();
+        let unnamed = world.create_entity().build();
+        let mut system = NameTableBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+
+        // rename the entity after the initial setup
+        world
+            .write_storage::<Name>()
+            .insert(first, Name::new("renamed"))
+            .unwrap();
+        system.run_now(&world);
+
+        let names = world.read_resource::<NameTable>();
+        assert_eq!(names.name_of(first), Some(&Name::new("renamed")));
+        assert!(names.get("first").is_none());
+        assert!(names.name_of(unnamed).is_none());
+    }
+
     #[test]
     fn run_will_keep_the_nametable_updated() {
         let mut world = World::new();
@@ -130,4 +301,175 @@ mod tests {
         assert_eq!(names.get("second").unwrap(), second);
         assert_eq!(names.get("third").unwrap(), third);
     }
+
+    /// Create two entities with the same name after the system has been set
+    /// up, returning `(original, duplicate)`.
+    fn world_with_duplicate_names(
+        policy: DuplicateNamePolicy,
+    ) -> (
+        World,
+        NameTableBookkeeping,
+        ReaderId<NameConflict>,
+        Entity,
+        Entity,
+    ) {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        world.insert(policy);
+        let original = world.create_entity().with(Name::new("wall")).build();
+        let mut system = NameTableBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        let reader = world
+            .write_resource::<EventChannel<NameConflict>>()
+            .register_reader();
+
+        let duplicate = world.create_entity().with(Name::new("wall")).build();
+        system.run_now(&world);
+
+        (world, system, reader, original, duplicate)
+    }
+
+    #[test]
+    fn keep_the_old_entity_on_conflict() {
+        let (world, _, mut reader, original, duplicate) =
+            world_with_duplicate_names(DuplicateNamePolicy::KeepOld);
+
+        let names = world.read_resource::<NameTable>();
+        assert_eq!(names.get("wall").unwrap(), original);
+        assert!(names.name_of(duplicate).is_none());
+
+        let conflicts: Vec<_> = world
+            .read_resource::<EventChannel<NameConflict>>()
+            .read(&mut reader)
+            .cloned()
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![NameConflict {
+                name: Name::new("wall"),
+                existing: original,
+                duplicate,
+                renamed_to: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn replace_the_old_entity_on_conflict() {
+        let (world, _, _, original, duplicate) =
+            world_with_duplicate_names(DuplicateNamePolicy::ReplaceOld);
+
+        let names = world.read_resource::<NameTable>();
+        assert_eq!(names.get("wall").unwrap(), duplicate);
+        assert!(names.name_of(original).is_none());
+    }
+
+    #[test]
+    fn automatically_rename_duplicates() {
+        let (world, mut system, mut reader, original, duplicate) =
+            world_with_duplicate_names(DuplicateNamePolicy::AutoRename);
+        // the rename shows up as a modification on the next run
+        system.run_now(&world);
+
+        let names = world.read_resource::<NameTable>();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.get("wall").unwrap(), original);
+        assert_eq!(names.get("wall-1").unwrap(), duplicate);
+        assert_eq!(
+            world.read_storage::<Name>().get(duplicate),
+            Some(&Name::new("wall-1"))
+        );
+        let conflicts = world.read_resource::<EventChannel<NameConflict>>();
+        let renamed: Vec<_> = conflicts
+            .read(&mut reader)
+            .map(|c| c.renamed_to.clone())
+            .collect();
+        assert_eq!(renamed, vec![Some(Name::new("wall-1"))]);
+    }
+
+    #[test]
+    fn remove_duplicate_names_when_treated_as_errors() {
+        let (world, mut system, _, original, duplicate) =
+            world_with_duplicate_names(DuplicateNamePolicy::Error);
+        system.run_now(&world);
+
+        let names = world.read_resource::<NameTable>();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names.get("wall").unwrap(), original);
+        assert!(world.read_storage::<Name>().get(duplicate).is_none());
+    }
+
+    #[test]
+    fn publish_events_when_names_change() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let first = world.create_entity().with(Name::new("first")).build();
+        let mut system = NameTableBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .write_resource::<EventChannel<NameEvent>>()
+            .register_reader();
+
+        let second = world.create_entity().with(Name::new("second")).build();
+        world
+            .write_storage::<Name>()
+            .insert(first, Name::new("renamed"))
+            .unwrap();
+        system.run_now(&world);
+        world.delete_entity(second).unwrap();
+        world.maintain();
+        system.run_now(&world);
+
+        let events: Vec<_> = world
+            .read_resource::<EventChannel<NameEvent>>()
+            .read(&mut reader)
+            .cloned()
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                NameEvent::Renamed {
+                    entity: first,
+                    old_name: Name::new("first"),
+                    new_name: Name::new("renamed"),
+                },
+                NameEvent::Added {
+                    entity: second,
+                    name: Name::new("second"),
+                },
+                NameEvent::Removed {
+                    entity: second,
+                    name: Name::new("second"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn prune_deleted_entities() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = NameTableBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        let named = world.create_entity().with(Name::new("named")).build();
+        // an entity which was added to the table by hand
+        let unnamed = world.create_entity().build();
+        world
+            .write_resource::<NameTable>()
+            .insert(Name::new("unnamed"), unnamed);
+        system.run_now(&world);
+
+        world.delete_entity(named).unwrap();
+        world.delete_entity(unnamed).unwrap();
+        world.maintain();
+        system.run_now(&world);
+
+        // only the Name storage is watched
+        assert_eq!(world.read_resource::<NameTable>().len(), 1);
+
+        // so manual entries need an explicit clean-up
+        let removed = world.write_resource::<NameTable>().validate(&world);
+        assert_eq!(removed, vec![(Name::new("unnamed"), unnamed)]);
+        assert!(world.read_resource::<NameTable>().is_empty());
+    }
 }
@@ -0,0 +1,51 @@
+use crate::components::Deleted;
+use specs::prelude::*;
+
+/// Physically removes every [`Deleted`] entity from the [`World`].
+///
+/// This should run after anything which needs to react to entities being
+/// deleted. The entities are gone after the next call to
+/// [`World::maintain()`].
+#[derive(Debug, Default)]
+pub struct PurgeDeleted;
+
+impl PurgeDeleted {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(_world: &World) -> Self { PurgeDeleted }
+}
+
+impl<'world> System<'world> for PurgeDeleted {
+    type SystemData = (Entities<'world>, ReadStorage<'world, Deleted>);
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, deleted) = data;
+
+        for (ent, _) in (&entities, &deleted).join() {
+            if let Err(e) = entities.delete(ent) {
+                log::warn!("Unable to delete {:?}: {}", ent, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::soft_delete;
+
+    #[test]
+    fn tombstoned_entities_are_removed() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let doomed = world.create_entity().build();
+        let survivor = world.create_entity().build();
+        soft_delete(&world, doomed);
+
+        PurgeDeleted::new(&world).run_now(&world);
+        world.maintain();
+
+        assert!(!world.is_alive(doomed));
+        assert!(world.is_alive(survivor));
+    }
+}
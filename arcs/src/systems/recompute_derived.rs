@@ -0,0 +1,416 @@
+use crate::{
+    algorithms::Offset,
+    components::{
+        boundary_from_geometry, AssociativeOffset, DependencyGraph,
+        DrawingObject, Geometry,
+    },
+};
+use specs::{hibitset::BitSetLike, prelude::*};
+use std::collections::HashSet;
+
+/// Recomputes geometry which is derived from other entities, namely
+/// [`crate::components::HatchLoop`]s associated with a boundary and
+/// [`AssociativeOffset`]s.
+///
+/// Derived entities are updated in the order given by the
+/// [`DependencyGraph`], so chains like a hatch bounded by an offset of a
+/// line are brought up to date in a single pass. Block
+/// [`crate::components::Insert`] bounds are kept up to date by
+/// [`crate::systems::SyncBounds`].
+///
+/// If a source is deleted or stops being usable (e.g. it is no longer a
+/// closed boundary) the association is dropped and the derived geometry
+/// keeps its last known shape.
+#[derive(Debug)]
+pub struct RecomputeDerived {
+    object_changes: ReaderId<ComponentEvent>,
+    offset_changes: ReaderId<ComponentEvent>,
+    changed: BitSet,
+    removed: BitSet,
+}
+
+impl RecomputeDerived {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        RecomputeDerived {
+            object_changes: world
+                .write_storage::<DrawingObject>()
+                .register_reader(),
+            offset_changes: world
+                .write_storage::<AssociativeOffset>()
+                .register_reader(),
+            changed: BitSet::new(),
+            removed: BitSet::new(),
+        }
+    }
+}
+
+impl<'world> System<'world> for RecomputeDerived {
+    type SystemData = (
+        Entities<'world>,
+        WriteStorage<'world, DrawingObject>,
+        WriteStorage<'world, AssociativeOffset>,
+        Write<'world, DependencyGraph>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        self.changed.clear();
+        self.removed.clear();
+
+        let (entities, mut objects, mut offsets, mut graph) = data;
+
+        let changes = objects
+            .channel()
+            .read(&mut self.object_changes)
+            .chain(offsets.channel().read(&mut self.offset_changes));
+
+        for event in changes {
+            match *event {
+                ComponentEvent::Inserted(id) | ComponentEvent::Modified(id) => {
+                    self.changed.add(id);
+                },
+                ComponentEvent::Removed(id) => {
+                    self.changed.add(id);
+                    self.removed.add(id);
+                },
+            }
+        }
+
+        if self.changed.is_empty() {
+            return;
+        }
+
+        let dead = graph.dead_sources((&self.removed).iter(), &entities);
+
+        // deleted entities aren't derived from anything any more
+        for id in (&self.removed).iter() {
+            if !entities.entity(id).gen().is_alive() {
+                graph.remove_by_id(id);
+            }
+        }
+
+        let mut dirty: HashSet<Entity> = dead.iter().copied().collect();
+
+        for (ent, _) in (&entities, &self.changed).join() {
+            graph.set_sources(ent, sources(objects.get(ent), offsets.get(ent)));
+            dirty.insert(ent);
+        }
+
+        let order = match graph.update_order(dirty.iter().copied()) {
+            Ok(order) => order,
+            Err(e) => {
+                log::warn!("Unable to recompute derived geometry: {}", e);
+                return;
+            },
+        };
+
+        for ent in order {
+            let mut modified = false;
+
+            if let Some(updated) =
+                update_hatch(ent, &dirty, &entities, &objects)
+            {
+                if let Some(object) = objects.get_mut(ent) {
+                    object.geometry = updated;
+                    modified = true;
+                }
+            }
+
+            if let Some(&offset) = offsets.get(ent) {
+                if dirty.contains(&offset.source) {
+                    let geometry = objects
+                        .get(offset.source)
+                        .filter(|_| entities.is_alive(offset.source))
+                        .and_then(|source| {
+                            source.geometry.offset(offset.distance, offset.side)
+                        });
+
+                    match geometry {
+                        Some(geometry) => {
+                            if let Some(object) = objects.get_mut(ent) {
+                                object.geometry = geometry;
+                                modified = true;
+                            }
+                        },
+                        None => {
+                            log::warn!(
+                                "Unable to offset {:?}, dissociating it from {:?}",
+                                offset.source,
+                                ent
+                            );
+                            offsets.remove(ent);
+                        },
+                    }
+                }
+            }
+
+            if modified {
+                dirty.insert(ent);
+            }
+            graph.set_sources(ent, sources(objects.get(ent), offsets.get(ent)));
+        }
+
+        // we caused these events and have already dealt with them
+        objects.channel().read(&mut self.object_changes).count();
+        offsets.channel().read(&mut self.offset_changes).count();
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        <Self::SystemData as shred::DynamicSystemData>::setup(
+            &self.accessor(),
+            world,
+        );
+
+        let (entities, objects, offsets, mut graph) = world.system_data::<(
+            Entities<'_>,
+            ReadStorage<'_, DrawingObject>,
+            ReadStorage<'_, AssociativeOffset>,
+            Write<'_, DependencyGraph>,
+        )>();
+        graph.clear();
+
+        for ent in (&entities).join() {
+            graph.set_sources(ent, sources(objects.get(ent), offsets.get(ent)));
+        }
+    }
+}
+
+/// Everything an entity is derived from.
+fn sources(
+    object: Option<&DrawingObject>,
+    offset: Option<&AssociativeOffset>,
+) -> Vec<Entity> {
+    let mut sources = object
+        .map(|object| object.geometry.associations())
+        .unwrap_or_default();
+    sources.extend(offset.map(|offset| offset.source));
+
+    sources
+}
+
+/// Get the hatch's new geometry if any of its loops are associated with a
+/// `dirty` entity.
+fn update_hatch(
+    ent: Entity,
+    dirty: &HashSet<Entity>,
+    entities: &Entities<'_>,
+    objects: &WriteStorage<'_, DrawingObject>,
+) -> Option<Geometry> {
+    let mut hatch = match objects.get(ent)?.geometry {
+        Geometry::Hatch(ref hatch) => hatch.clone(),
+        _ => return None,
+    };
+    let mut changed = false;
+
+    for hatch_loop in &mut hatch.loops {
+        let source = match hatch_loop.associated_with {
+            Some(source) if dirty.contains(&source) => source,
+            _ => continue,
+        };
+        changed = true;
+
+        if !entities.is_alive(source) {
+            hatch_loop.associated_with = None;
+            continue;
+        }
+
+        match objects
+            .get(source)
+            .and_then(|d| boundary_from_geometry(&d.geometry))
+        {
+            Some(boundary) => hatch_loop.boundary = boundary,
+            None => {
+                log::warn!(
+                    "{:?} is no longer a closed boundary, dissociating it from the hatch on {:?}",
+                    source,
+                    ent
+                );
+                hatch_loop.associated_with = None;
+            },
+        }
+    }
+
+    if changed {
+        Some(Geometry::Hatch(hatch))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::{Side, Translate},
+        components::{Hatch, HatchLoop, HatchPattern, Layer},
+        Line, Point, Polyline, Vector,
+    };
+
+    fn square() -> Geometry {
+        let mut polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ]);
+        polyline.set_closed(true);
+        Geometry::Polyline(polyline)
+    }
+
+    fn hatch_loop(world: &World, hatch: Entity) -> HatchLoop {
+        match world
+            .read_storage::<DrawingObject>()
+            .get(hatch)
+            .unwrap()
+            .geometry
+        {
+            Geometry::Hatch(ref hatch) => hatch.loops[0].clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn hatch_around(
+        world: &mut World,
+        boundary: Entity,
+        layer: Entity,
+    ) -> Entity {
+        let geometry = world
+            .read_storage::<DrawingObject>()
+            .get(boundary)
+            .unwrap()
+            .geometry
+            .clone();
+        let mut hatch = Hatch::new(HatchPattern::Solid);
+        hatch
+            .loops
+            .push(HatchLoop::associated(boundary, &geometry).unwrap());
+        world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Hatch(hatch),
+                layer,
+            })
+            .build()
+    }
+
+    #[test]
+    fn moving_the_boundary_moves_the_hatch() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = RecomputeDerived::new(&world);
+        System::setup(&mut system, &mut world);
+        let layer = Layer::create(
+            world.create_entity(),
+            crate::components::Name::new("default"),
+            Layer::default(),
+        );
+        let boundary = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: square(),
+                layer,
+            })
+            .build();
+        let hatch = hatch_around(&mut world, boundary, layer);
+        system.run_now(&world);
+
+        world
+            .write_storage::<DrawingObject>()
+            .get_mut(boundary)
+            .unwrap()
+            .translate(Vector::new(5.0, 0.0));
+        system.run_now(&world);
+
+        let got = hatch_loop(&world, hatch);
+        assert_eq!(got.associated_with, Some(boundary));
+        assert_eq!(got.boundary.vertices()[0].point, Point::new(5.0, 0.0));
+
+        world.delete_entity(boundary).unwrap();
+        world.maintain();
+        system.run_now(&world);
+
+        let got = hatch_loop(&world, hatch);
+        assert_eq!(got.associated_with, None);
+        assert_eq!(got.boundary.vertices()[0].point, Point::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn chains_of_offsets_update_in_one_pass() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = RecomputeDerived::new(&world);
+        System::setup(&mut system, &mut world);
+        let layer = world.create_entity().build();
+        let line = |y| {
+            Geometry::Line(Line::new(Point::new(0.0, y), Point::new(10.0, y)))
+        };
+        let source = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: line(0.0),
+                layer,
+            })
+            .build();
+        let first = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: line(1.0),
+                layer,
+            })
+            .with(AssociativeOffset::new(source, 1.0, Side::Left))
+            .build();
+        let second = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: line(3.0),
+                layer,
+            })
+            .with(AssociativeOffset::new(first, 2.0, Side::Left))
+            .build();
+        system.run_now(&world);
+
+        world
+            .write_storage::<DrawingObject>()
+            .get_mut(source)
+            .unwrap()
+            .geometry = line(-5.0);
+        system.run_now(&world);
+
+        let objects = world.read_storage::<DrawingObject>();
+        assert_eq!(objects.get(first).unwrap().geometry, line(-4.0));
+        assert_eq!(objects.get(second).unwrap().geometry, line(-2.0));
+        assert_eq!(
+            world.fetch::<DependencyGraph>().sources_of(second),
+            &[first]
+        );
+    }
+
+    #[test]
+    fn deleted_dependents_are_forgotten() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = RecomputeDerived::new(&world);
+        System::setup(&mut system, &mut world);
+        let layer = world.create_entity().build();
+        let source = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: square(),
+                layer,
+            })
+            .build();
+        let hatch = hatch_around(&mut world, source, layer);
+        system.run_now(&world);
+        assert_eq!(world.fetch::<DependencyGraph>().len(), 1);
+
+        world.delete_entity(hatch).unwrap();
+        world.maintain();
+        system.run_now(&world);
+
+        let graph = world.fetch::<DependencyGraph>();
+        assert!(graph.is_empty());
+        assert_eq!(graph.sources_of(hatch), &[]);
+        assert_eq!(graph.dependents_of(source).count(), 0);
+    }
+}
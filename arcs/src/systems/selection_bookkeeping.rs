@@ -0,0 +1,144 @@
+use crate::components::{DrawingEvent, Selected, SelectionSet};
+use specs::{prelude::*, shrev::EventChannel};
+
+/// A [`System`] which keeps the global [`SelectionSet`] in sync with the
+/// [`Selected`] component.
+///
+/// Entities are appended to the [`SelectionSet`] in the order they were
+/// selected, and dropped when they are deselected or deleted. A
+/// [`DrawingEvent::SelectionChanged`] is published whenever that happens.
+#[derive(Debug)]
+pub struct SelectionBookkeeping {
+    changes: ReaderId<ComponentEvent>,
+    inserted: BitSet,
+    removed: BitSet,
+}
+
+impl SelectionBookkeeping {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        SelectionBookkeeping {
+            changes: world.write_storage::<Selected>().register_reader(),
+            inserted: BitSet::new(),
+            removed: BitSet::new(),
+        }
+    }
+}
+
+impl<'world> System<'world> for SelectionBookkeeping {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, Selected>,
+        Write<'world, SelectionSet>,
+        Write<'world, EventChannel<DrawingEvent>>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        self.inserted.clear();
+        self.removed.clear();
+
+        let (entities, selected, mut selection, mut events) = data;
+        let original_len = selection.len();
+
+        for event in selected.channel().read(&mut self.changes) {
+            match *event {
+                ComponentEvent::Inserted(id) => {
+                    self.removed.remove(id);
+                    self.inserted.add(id);
+                },
+                ComponentEvent::Removed(id) => {
+                    self.inserted.remove(id);
+                    self.removed.add(id);
+                },
+                ComponentEvent::Modified(_) => {},
+            }
+        }
+
+        selection.retain(|&ent| {
+            entities.is_alive(ent)
+                && !self.removed.contains(ent.id())
+                && selected.contains(ent)
+        });
+
+        let retained = selection.len();
+
+        for (ent, _, _) in (&entities, &selected, &self.inserted).join() {
+            selection.add(ent);
+        }
+
+        if retained != original_len || selection.len() != retained {
+            events.single_write(DrawingEvent::SelectionChanged);
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        <Self::SystemData as shred::DynamicSystemData>::setup(
+            &self.accessor(),
+            world,
+        );
+
+        let (entities, selected, mut selection, _) = world.system_data::<(
+            Entities<'_>,
+            ReadStorage<'_, Selected>,
+            Write<'_, SelectionSet>,
+            Write<'_, EventChannel<DrawingEvent>>,
+        )>();
+        selection.clear();
+
+        for (ent, _) in (&entities, &selected).join() {
+            selection.add(ent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_the_selected_component() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = SelectionBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        let first = world.create_entity().with(Selected).build();
+        let second = world.create_entity().build();
+        world
+            .write_storage::<Selected>()
+            .insert(second, Selected)
+            .unwrap();
+        system.run_now(&world);
+
+        let got: Vec<_> = world.fetch::<SelectionSet>().iter().collect();
+        assert_eq!(got, vec![first, second]);
+
+        world.write_storage::<Selected>().remove(second);
+        world.delete_entity(first).unwrap();
+        world.maintain();
+        system.run_now(&world);
+
+        assert!(world.fetch::<SelectionSet>().is_empty());
+    }
+
+    #[test]
+    fn publish_selection_changes() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = SelectionBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .fetch_mut::<EventChannel<DrawingEvent>>()
+            .register_reader();
+
+        let ent = world.create_entity().with(Selected).build();
+        system.run_now(&world);
+        // nothing changed
+        system.run_now(&world);
+        world.write_storage::<Selected>().remove(ent);
+        system.run_now(&world);
+
+        let events = world.fetch::<EventChannel<DrawingEvent>>();
+        assert_eq!(events.read(&mut reader).count(), 2);
+    }
+}
@@ -0,0 +1,624 @@
+use crate::{
+    components::{
+        is_locked, Constraint, ConstraintPoint, ConstraintStatus,
+        DrawingObject, Geometry, Handle, Layer, Locked,
+    },
+    Angle, Arc, Line, Point,
+};
+use specs::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Give up if the constraints still aren't satisfied after this many
+/// iterations.
+const MAX_ITERATIONS: usize = 50;
+/// How close to zero every residual needs to be before the constraints are
+/// considered satisfied.
+const TOLERANCE: f64 = 1e-9;
+/// Added to the diagonal so redundant constraints don't make the system
+/// singular.
+const DAMPING: f64 = 1e-12;
+
+/// Moves [`DrawingObject`]s so their [`Constraint`]s are satisfied, changing
+/// the geometry as little as possible.
+///
+/// The solver only runs when a [`Constraint`] or [`DrawingObject`] changes.
+/// [`Constraint::Fixed`] and [`Locked`] geometry (or geometry on a locked
+/// [`Layer`]) is never moved. If the constraints can't be satisfied the
+/// geometry is left alone and the failure is recorded in the
+/// [`ConstraintStatus`].
+#[derive(Debug)]
+pub struct SolveConstraints {
+    constraint_changes: ReaderId<ComponentEvent>,
+    geometry_changes: ReaderId<ComponentEvent>,
+}
+
+impl SolveConstraints {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        SolveConstraints {
+            constraint_changes: world
+                .write_storage::<Constraint>()
+                .register_reader(),
+            geometry_changes: world
+                .write_storage::<DrawingObject>()
+                .register_reader(),
+        }
+    }
+}
+
+impl<'world> System<'world> for SolveConstraints {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, Constraint>,
+        WriteStorage<'world, DrawingObject>,
+        ReadStorage<'world, Locked>,
+        ReadStorage<'world, Layer>,
+        Write<'world, ConstraintStatus>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, constraints, mut objects, locked, layers, mut status) =
+            data;
+
+        let constraint_events = constraints
+            .channel()
+            .read(&mut self.constraint_changes)
+            .count();
+        let geometry_events =
+            objects.channel().read(&mut self.geometry_changes).count();
+
+        if constraint_events == 0 && geometry_events == 0 {
+            return;
+        }
+
+        let mut problem = Problem::default();
+        let mut invalid = Vec::new();
+        let mut fixed = HashSet::new();
+
+        for (ent, constraint) in (&entities, &constraints).join() {
+            if let Constraint::Fixed(target) = *constraint {
+                fixed.insert(target);
+            }
+
+            let supported = constraint.entities().into_iter().all(|target| {
+                match objects.get(target) {
+                    Some(object) => problem.add_geometry(target, object),
+                    None => false,
+                }
+            });
+
+            if supported {
+                problem.constraints.push(constraint.clone());
+            } else {
+                invalid.push(ent);
+            }
+        }
+
+        let frozen: Vec<Entity> = problem
+            .geometry
+            .keys()
+            .copied()
+            .filter(|&ent| {
+                fixed.contains(&ent)
+                    || locked.contains(ent)
+                    || objects
+                        .get(ent)
+                        .map(|o| is_locked(&layers, o.layer))
+                        .unwrap_or(true)
+            })
+            .collect();
+
+        for ent in frozen {
+            problem.freeze(ent);
+        }
+
+        let outcome = problem.solve();
+
+        if outcome.converged {
+            for (ent, geometry) in problem.changed_geometry(&objects) {
+                if let Some(object) = objects.get_mut(ent) {
+                    object.geometry = geometry;
+                }
+            }
+            // we caused these events, so don't wake up for them next time
+            objects.channel().read(&mut self.geometry_changes).count();
+        } else {
+            log::warn!(
+                "Unable to satisfy the constraints after {} iterations \
+                 (residual: {})",
+                outcome.iterations,
+                outcome.residual
+            );
+        }
+
+        *status = ConstraintStatus { invalid, ..outcome };
+    }
+}
+
+/// How a piece of geometry is represented in the parameter vector.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Shape {
+    /// `[x, y]`
+    Point,
+    /// `[start_x, start_y, end_x, end_y]`
+    Line,
+    /// `[centre_x, centre_y, radius, start_angle, sweep_angle]`
+    Arc,
+}
+
+impl Shape {
+    fn len(self) -> usize {
+        match self {
+            Shape::Point => 2,
+            Shape::Line => 4,
+            Shape::Arc => 5,
+        }
+    }
+}
+
+/// The constraint problem as a vector of parameters and a set of residuals
+/// which should all be zero.
+#[derive(Debug, Default)]
+struct Problem {
+    parameters: Vec<f64>,
+    frozen: HashSet<usize>,
+    geometry: HashMap<Entity, (usize, Shape)>,
+    constraints: Vec<Constraint>,
+}
+
+impl Problem {
+    /// Start tracking an entity's geometry, returning `false` if it can't be
+    /// constrained.
+    fn add_geometry(&mut self, entity: Entity, object: &DrawingObject) -> bool {
+        if self.geometry.contains_key(&entity) {
+            return true;
+        }
+
+        let (shape, values) = match object.geometry {
+            Geometry::Point(p) => (Shape::Point, vec![p.x, p.y]),
+            Geometry::Line(line) => (
+                Shape::Line,
+                vec![line.start.x, line.start.y, line.end.x, line.end.y],
+            ),
+            Geometry::Arc(arc) => (
+                Shape::Arc,
+                vec![
+                    arc.centre().x,
+                    arc.centre().y,
+                    arc.radius(),
+                    arc.start_angle().radians,
+                    arc.sweep_angle().radians,
+                ],
+            ),
+            _ => return false,
+        };
+
+        self.geometry.insert(entity, (self.parameters.len(), shape));
+        self.parameters.extend(values);
+
+        true
+    }
+
+    fn freeze(&mut self, entity: Entity) {
+        if let Some(&(offset, shape)) = self.geometry.get(&entity) {
+            self.frozen.extend(offset..offset + shape.len());
+        }
+    }
+
+    fn solve(&mut self) -> ConstraintStatus {
+        let free: Vec<usize> = (0..self.parameters.len())
+            .filter(|ix| !self.frozen.contains(ix))
+            .collect();
+        let mut iterations = 0;
+        let mut residuals = self.residuals(&self.parameters);
+
+        while norm(&residuals) > TOLERANCE && iterations < MAX_ITERATIONS {
+            iterations += 1;
+
+            let jacobian = self.jacobian(&free, &residuals);
+            let step = match minimum_norm_step(&jacobian, &residuals) {
+                Some(step) => step,
+                None => break,
+            };
+
+            for (&ix, delta) in free.iter().zip(step) {
+                self.parameters[ix] -= delta;
+            }
+
+            residuals = self.residuals(&self.parameters);
+        }
+
+        let residual = norm(&residuals);
+
+        ConstraintStatus {
+            converged: residual <= TOLERANCE,
+            iterations,
+            residual,
+            invalid: Vec::new(),
+        }
+    }
+
+    /// The partial derivative of each residual with respect to each free
+    /// parameter, estimated using finite differences.
+    fn jacobian(&self, free: &[usize], residuals: &[f64]) -> Vec<Vec<f64>> {
+        let mut jacobian = vec![vec![0.0; free.len()]; residuals.len()];
+        let mut parameters = self.parameters.clone();
+
+        for (column, &ix) in free.iter().enumerate() {
+            let original = parameters[ix];
+            let h = 1e-7 * original.abs().max(1.0);
+            parameters[ix] = original + h;
+            let nudged = self.residuals(&parameters);
+            parameters[ix] = original;
+
+            for (row, value) in nudged.iter().enumerate() {
+                jacobian[row][column] = (value - residuals[row]) / h;
+            }
+        }
+
+        jacobian
+    }
+
+    fn residuals(&self, parameters: &[f64]) -> Vec<f64> {
+        let mut residuals = Vec::new();
+
+        for constraint in &self.constraints {
+            self.constraint_residuals(constraint, parameters, &mut residuals);
+        }
+
+        residuals
+    }
+
+    fn constraint_residuals(
+        &self,
+        constraint: &Constraint,
+        p: &[f64],
+        residuals: &mut Vec<f64>,
+    ) {
+        let shape = |e: Entity| self.geometry[&e].1;
+        let direction = |e: Entity| {
+            let (o, _) = self.geometry[&e];
+            (p[o + 2] - p[o], p[o + 3] - p[o + 1])
+        };
+
+        match *constraint {
+            Constraint::Coincident(a, b) => {
+                let (ax, ay) = self.point(a, p);
+                let (bx, by) = self.point(b, p);
+                residuals.push(ax - bx);
+                residuals.push(ay - by);
+            },
+            Constraint::Horizontal(line) if shape(line) == Shape::Line => {
+                residuals.push(direction(line).1);
+            },
+            Constraint::Vertical(line) if shape(line) == Shape::Line => {
+                residuals.push(direction(line).0);
+            },
+            Constraint::Parallel(a, b) | Constraint::Perpendicular(a, b)
+                if shape(a) == Shape::Line && shape(b) == Shape::Line =>
+            {
+                let (ax, ay) = direction(a);
+                let (bx, by) = direction(b);
+                let lengths = ax.hypot(ay) * bx.hypot(by);
+
+                if lengths > 0.0 {
+                    let value = match constraint {
+                        Constraint::Parallel(..) => ax * by - ay * bx,
+                        _ => ax * bx + ay * by,
+                    };
+                    residuals.push(value / lengths);
+                }
+            },
+            Constraint::Equal(a, b) => match (shape(a), shape(b)) {
+                (Shape::Line, Shape::Line) => {
+                    let (ax, ay) = direction(a);
+                    let (bx, by) = direction(b);
+                    residuals.push(ax.hypot(ay) - bx.hypot(by));
+                },
+                (Shape::Arc, Shape::Arc) => {
+                    residuals.push(self.radius(a, p) - self.radius(b, p));
+                },
+                _ => {},
+            },
+            Constraint::Tangent(a, b) => match (shape(a), shape(b)) {
+                (Shape::Line, Shape::Arc) | (Shape::Arc, Shape::Line) => {
+                    let (line, arc) = if shape(a) == Shape::Line {
+                        (a, b)
+                    } else {
+                        (b, a)
+                    };
+                    let (o, _) = self.geometry[&line];
+                    let (dx, dy) = direction(line);
+                    let (cx, cy) = self
+                        .point(ConstraintPoint::new(arc, Handle::Centre), p);
+                    let length = dx.hypot(dy);
+
+                    if length > 0.0 {
+                        let distance =
+                            ((cx - p[o]) * dy - (cy - p[o + 1]) * dx).abs()
+                                / length;
+                        residuals.push(distance - self.radius(arc, p));
+                    }
+                },
+                (Shape::Arc, Shape::Arc) => {
+                    let (ax, ay) =
+                        self.point(ConstraintPoint::new(a, Handle::Centre), p);
+                    let (bx, by) =
+                        self.point(ConstraintPoint::new(b, Handle::Centre), p);
+                    let (ra, rb) = (self.radius(a, p), self.radius(b, p));
+                    let distance = (ax - bx).hypot(ay - by);
+
+                    // stay on whichever side the arcs are already on
+                    if distance < ra.max(rb) {
+                        residuals.push(distance - (ra - rb).abs());
+                    } else {
+                        residuals.push(distance - (ra + rb));
+                    }
+                },
+                _ => {},
+            },
+            _ => {},
+        }
+    }
+
+    fn point(&self, point: ConstraintPoint, p: &[f64]) -> (f64, f64) {
+        let (o, shape) = self.geometry[&point.entity];
+
+        match (shape, point.handle) {
+            (Shape::Point, _) => (p[o], p[o + 1]),
+            (Shape::Line, Handle::End) => (p[o + 2], p[o + 3]),
+            (Shape::Line, _) => {
+                if point.handle == Handle::Centre {
+                    ((p[o] + p[o + 2]) / 2.0, (p[o + 1] + p[o + 3]) / 2.0)
+                } else {
+                    (p[o], p[o + 1])
+                }
+            },
+            (Shape::Arc, Handle::Centre) => (p[o], p[o + 1]),
+            (Shape::Arc, handle) => {
+                let angle = if handle == Handle::Start {
+                    p[o + 3]
+                } else {
+                    p[o + 3] + p[o + 4]
+                };
+                (
+                    p[o] + p[o + 2] * angle.cos(),
+                    p[o + 1] + p[o + 2] * angle.sin(),
+                )
+            },
+        }
+    }
+
+    fn radius(&self, entity: Entity, p: &[f64]) -> f64 {
+        let (o, _) = self.geometry[&entity];
+        p[o + 2]
+    }
+
+    /// The geometry which needs to be updated after solving.
+    fn changed_geometry(
+        &self,
+        objects: &WriteStorage<'_, DrawingObject>,
+    ) -> Vec<(Entity, Geometry)> {
+        let p = &self.parameters;
+        let mut changed = Vec::new();
+
+        for (&ent, &(o, shape)) in &self.geometry {
+            let geometry = match shape {
+                Shape::Point => Geometry::Point(Point::new(p[o], p[o + 1])),
+                Shape::Line => Geometry::Line(Line::new(
+                    Point::new(p[o], p[o + 1]),
+                    Point::new(p[o + 2], p[o + 3]),
+                )),
+                Shape::Arc if p[o + 2] > 0.0 => {
+                    Geometry::Arc(Arc::from_centre_radius(
+                        Point::new(p[o], p[o + 1]),
+                        p[o + 2],
+                        Angle::radians(p[o + 3]),
+                        Angle::radians(p[o + 4]),
+                    ))
+                },
+                Shape::Arc => continue,
+            };
+
+            if objects.get(ent).map(|o| o.geometry != geometry) == Some(true) {
+                changed.push((ent, geometry));
+            }
+        }
+
+        changed
+    }
+}
+
+fn norm(values: &[f64]) -> f64 {
+    values.iter().map(|v| v * v).sum::<f64>().sqrt()
+}
+
+/// Find the smallest `step` where `jacobian * step = residuals`, i.e.
+/// `step = Jᵀ (J Jᵀ)⁻¹ r`.
+fn minimum_norm_step(
+    jacobian: &[Vec<f64>],
+    residuals: &[f64],
+) -> Option<Vec<f64>> {
+    let rows = residuals.len();
+    let columns = jacobian.first().map(Vec::len).unwrap_or(0);
+
+    // J Jᵀ, with a little damping
+    let mut matrix = vec![vec![0.0; rows]; rows];
+    for i in 0..rows {
+        for j in 0..rows {
+            matrix[i][j] =
+                (0..columns).map(|k| jacobian[i][k] * jacobian[j][k]).sum();
+        }
+        matrix[i][i] += DAMPING;
+    }
+
+    let y = solve_linear(matrix, residuals.to_vec())?;
+
+    Some(
+        (0..columns)
+            .map(|k| (0..rows).map(|i| jacobian[i][k] * y[i]).sum())
+            .collect(),
+    )
+}
+
+/// Solve `a x = b` using Gaussian elimination with partial pivoting.
+fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&i, &j| {
+            a[i][column].abs().partial_cmp(&a[j][column].abs()).unwrap()
+        })?;
+
+        if a[pivot][column].abs() < std::f64::EPSILON {
+            return None;
+        }
+
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+
+        let pivot_row = a[column].clone();
+
+        for row in column + 1..n {
+            let factor = a[row][column] / pivot_row[column];
+            for (value, above) in
+                a[row][column..].iter_mut().zip(&pivot_row[column..])
+            {
+                *value -= factor * above;
+            }
+            b[row] -= factor * b[column];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let known: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - known) / a[row][row];
+    }
+
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::register;
+    use euclid::approxeq::ApproxEq;
+
+    fn line(world: &mut World, start: Point, end: Point) -> Entity {
+        let layer = world.create_entity().build();
+        world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Line(Line::new(start, end)),
+                layer,
+            })
+            .build()
+    }
+
+    fn get_line(world: &World, entity: Entity) -> Line {
+        match world
+            .read_storage::<DrawingObject>()
+            .get(entity)
+            .unwrap()
+            .geometry
+        {
+            Geometry::Line(line) => line,
+            ref other => panic!("Expected a line, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn a_fixed_line_and_a_perpendicular_neighbour() {
+        let mut world = World::new();
+        register(&mut world);
+        world.insert(ConstraintStatus::default());
+        let mut system = SolveConstraints::new(&world);
+        let base = line(&mut world, Point::zero(), Point::new(10.0, 1.0));
+        let side =
+            line(&mut world, Point::new(10.5, 1.0), Point::new(11.0, 8.0));
+        for constraint in &[
+            Constraint::Fixed(base),
+            Constraint::Coincident(
+                ConstraintPoint::new(base, Handle::End),
+                ConstraintPoint::new(side, Handle::Start),
+            ),
+            Constraint::Perpendicular(base, side),
+            Constraint::Equal(base, side),
+        ] {
+            world.create_entity().with(constraint.clone()).build();
+        }
+
+        system.run_now(&world);
+
+        let status = ConstraintStatus::clone(&world.fetch());
+        assert!(status.converged, "{:?}", status);
+        assert_eq!(get_line(&world, base).end, Point::new(10.0, 1.0));
+        let side = get_line(&world, side);
+        assert!(side.start.approx_eq(&Point::new(10.0, 1.0)));
+        assert!(side.end.approx_eq(&Point::new(9.0, 11.0)));
+    }
+
+    #[test]
+    fn a_horizontal_line_touching_an_arc() {
+        let mut world = World::new();
+        register(&mut world);
+        world.insert(ConstraintStatus::default());
+        let mut system = SolveConstraints::new(&world);
+        let layer = world.create_entity().build();
+        let arc = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Arc(Arc::from_centre_radius(
+                    Point::zero(),
+                    2.0,
+                    Angle::zero(),
+                    Angle::pi(),
+                )),
+                layer,
+            })
+            .build();
+        let tangent =
+            line(&mut world, Point::new(-5.0, 3.0), Point::new(5.0, 2.5));
+        for constraint in &[
+            Constraint::Fixed(arc),
+            Constraint::Horizontal(tangent),
+            Constraint::Tangent(tangent, arc),
+        ] {
+            world.create_entity().with(constraint.clone()).build();
+        }
+
+        system.run_now(&world);
+
+        assert!(world.fetch::<ConstraintStatus>().converged);
+        let got = get_line(&world, tangent);
+        assert!(got.start.y.approx_eq(&2.0));
+        assert!(got.end.y.approx_eq(&2.0));
+    }
+
+    #[test]
+    fn report_impossible_constraints() {
+        let mut world = World::new();
+        register(&mut world);
+        world.insert(ConstraintStatus::default());
+        let mut system = SolveConstraints::new(&world);
+        let first = line(&mut world, Point::zero(), Point::new(1.0, 0.0));
+        let original = get_line(&world, first);
+        let missing = world.create_entity().build();
+        for constraint in &[
+            Constraint::Fixed(first),
+            Constraint::Vertical(first),
+            Constraint::Horizontal(missing),
+        ] {
+            world.create_entity().with(constraint.clone()).build();
+        }
+
+        system.run_now(&world);
+
+        let status = world.fetch::<ConstraintStatus>();
+        assert!(!status.converged);
+        assert_eq!(status.invalid.len(), 1);
+        assert_eq!(get_line(&world, first), original);
+    }
+}
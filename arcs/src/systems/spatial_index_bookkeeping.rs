@@ -0,0 +1,117 @@
+use crate::{components::SpatialIndex, BoundingBox, DrawingSpace};
+use specs::prelude::*;
+
+/// Keeps the [`SpatialIndex`] in sync with every entity's [`BoundingBox`].
+#[derive(Debug)]
+pub struct SpatialIndexBookkeeping {
+    changes: ReaderId<ComponentEvent>,
+    to_update: BitSet,
+}
+
+impl SpatialIndexBookkeeping {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        SpatialIndexBookkeeping {
+            changes: world
+                .write_storage::<BoundingBox<DrawingSpace>>()
+                .register_reader(),
+            to_update: BitSet::new(),
+        }
+    }
+}
+
+impl<'world> System<'world> for SpatialIndexBookkeeping {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, BoundingBox<DrawingSpace>>,
+        Write<'world, SpatialIndex>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        self.to_update.clear();
+
+        let (entities, bounds, mut index) = data;
+
+        for event in bounds.channel().read(&mut self.changes) {
+            match *event {
+                ComponentEvent::Inserted(id) | ComponentEvent::Modified(id) => {
+                    self.to_update.add(id);
+                },
+                ComponentEvent::Removed(id) => {
+                    self.to_update.remove(id);
+                    index.remove_by_id(id);
+                },
+            }
+        }
+
+        for (ent, bounding_box, _) in
+            (&entities, &bounds, &self.to_update).join()
+        {
+            index.insert(ent, *bounding_box);
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        <Self::SystemData as shred::DynamicSystemData>::setup(
+            &self.accessor(),
+            world,
+        );
+
+        let (entities, bounds, mut index) = world.system_data::<(
+            Entities<'_>,
+            ReadStorage<'_, BoundingBox<DrawingSpace>>,
+            Write<'_, SpatialIndex>,
+        )>();
+        index.clear();
+
+        for (ent, bounding_box) in (&entities, &bounds).join() {
+            index.insert(ent, *bounding_box);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    #[test]
+    fn follow_bounding_box_changes() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let first = world
+            .create_entity()
+            .with(BoundingBox::new(Point::zero(), Point::new(1.0, 1.0)))
+            .build();
+        let mut system = SpatialIndexBookkeeping::new(&world);
+        System::setup(&mut system, &mut world);
+        let second = world
+            .create_entity()
+            .with(BoundingBox::new(Point::new(5.0, 5.0), Point::new(6.0, 6.0)))
+            .build();
+        system.run_now(&world);
+
+        assert_eq!(
+            world
+                .fetch::<SpatialIndex>()
+                .query_point(Point::new(0.5, 0.5)),
+            vec![first]
+        );
+        assert_eq!(
+            world
+                .fetch::<SpatialIndex>()
+                .query_point(Point::new(5.5, 5.5)),
+            vec![second]
+        );
+
+        world
+            .write_storage::<BoundingBox<DrawingSpace>>()
+            .remove(first);
+        world.delete_entity(second).unwrap();
+        world.maintain();
+        system.run_now(&world);
+
+        assert!(world.fetch::<SpatialIndex>().is_empty());
+    }
+}
@@ -110,6 +110,7 @@ mod tests {
             Layer {
                 z_level: 0,
                 visible: true,
+                ..Default::default()
             },
         );
 
@@ -150,6 +151,7 @@ mod tests {
             Layer {
                 z_level: 0,
                 visible: true,
+                ..Default::default()
             },
         );
 
@@ -225,6 +227,7 @@ mod tests {
             Layer {
                 z_level: 0,
                 visible: true,
+                ..Default::default()
             },
         );
 
@@ -294,6 +297,7 @@ mod tests {
             Layer {
                 z_level: 0,
                 visible: true,
+                ..Default::default()
             },
         );
 
@@ -0,0 +1,221 @@
+use crate::components::{DrawingObject, Geometry};
+use specs::{hibitset::BitSetLike, prelude::*};
+
+/// Re-measures any [`crate::components::LinearDimension`] or
+/// [`crate::components::AngularDimension`], and moves the tip of any
+/// [`crate::components::Leader`], when the geometry it is associated with
+/// changes.
+///
+/// If the associated geometry can't be measured any more (or is deleted)
+/// the association is dropped and the annotation stays where it is.
+#[derive(Debug)]
+pub struct SyncAnnotations {
+    changes: ReaderId<ComponentEvent>,
+    changed: BitSet,
+    removed: BitSet,
+}
+
+impl SyncAnnotations {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> SyncAnnotations {
+        SyncAnnotations {
+            changes: world.write_storage::<DrawingObject>().register_reader(),
+            changed: BitSet::new(),
+            removed: BitSet::new(),
+        }
+    }
+}
+
+impl<'world> System<'world> for SyncAnnotations {
+    type SystemData = (WriteStorage<'world, DrawingObject>, Entities<'world>);
+
+    fn run(&mut self, data: Self::SystemData) {
+        self.changed.clear();
+        self.removed.clear();
+
+        let (mut drawing_objects, entities) = data;
+
+        for event in drawing_objects.channel().read(&mut self.changes) {
+            match *event {
+                ComponentEvent::Inserted(id) | ComponentEvent::Modified(id) => {
+                    self.changed.add(id);
+                },
+                ComponentEvent::Removed(id) => {
+                    self.removed.add(id);
+                },
+            }
+        }
+
+        if self.changed.is_empty() && self.removed.is_empty() {
+            return;
+        }
+
+        // find the annotations which need updating first so we don't flag
+        // every annotation as modified
+        let mut updates = Vec::new();
+
+        for (ent, drawing_object) in (&entities, &drawing_objects).join() {
+            let sources = dependencies(&drawing_object.geometry);
+
+            if sources.is_empty() {
+                continue;
+            }
+
+            if sources.iter().any(|source| {
+                self.removed.contains(source.id())
+                    || !entities.is_alive(*source)
+            }) {
+                updates.push((ent, None));
+            } else if sources
+                .iter()
+                .any(|source| self.changed.contains(source.id()))
+            {
+                let geometry: Option<Vec<Geometry>> = sources
+                    .iter()
+                    .map(|&s| {
+                        drawing_objects.get(s).map(|d| d.geometry.clone())
+                    })
+                    .collect();
+                updates.push((ent, geometry));
+            }
+        }
+
+        for (ent, geometry) in updates {
+            let drawing_object = match drawing_objects.get_mut(ent) {
+                Some(d) => d,
+                None => continue,
+            };
+            let geometry = geometry.unwrap_or_default();
+
+            let updated = match (&mut drawing_object.geometry, &geometry[..]) {
+                (Geometry::LinearDimension(d), [from, to]) => {
+                    d.update_from(from, to)
+                },
+                (Geometry::AngularDimension(d), [first, second]) => {
+                    d.update_from(first, second)
+                },
+                (Geometry::Leader(l), [target]) => l.update_from(target),
+                _ => false,
+            };
+
+            if !updated {
+                log::warn!(
+                    "Unable to follow the geometry associated with {:?}, \
+                     dissociating it",
+                    ent
+                );
+
+                match drawing_object.geometry {
+                    Geometry::LinearDimension(ref mut d) => {
+                        d.associated_with = None
+                    },
+                    Geometry::AngularDimension(ref mut d) => {
+                        d.associated_with = None
+                    },
+                    Geometry::Leader(ref mut l) => l.associated_with = None,
+                    _ => {},
+                }
+            }
+        }
+    }
+}
+
+/// The entities an annotation needs to be updated from, in the order its
+/// `update_from()` method expects them.
+fn dependencies(geometry: &Geometry) -> Vec<Entity> {
+    match geometry {
+        Geometry::LinearDimension(d) => d
+            .associated_with
+            .map(|(from, to)| vec![from.entity, to.entity])
+            .unwrap_or_default(),
+        Geometry::AngularDimension(d) => d
+            .associated_with
+            .map(|(first, second)| vec![first, second])
+            .unwrap_or_default(),
+        Geometry::Leader(l) => {
+            l.associated_with.map(|a| a.entity).into_iter().collect()
+        },
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{Anchor, Handle, Leader, LinearDimension},
+        Line, Point,
+    };
+
+    fn create(world: &mut World, geometry: Geometry) -> Entity {
+        let layer = world.create_entity().build();
+        world
+            .create_entity()
+            .with(DrawingObject { geometry, layer })
+            .build()
+    }
+
+    fn geometry(world: &World, entity: Entity) -> Geometry {
+        world
+            .read_storage::<DrawingObject>()
+            .get(entity)
+            .unwrap()
+            .geometry
+            .clone()
+    }
+
+    #[test]
+    fn moving_a_line_updates_its_dimension_and_leader() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = SyncAnnotations::new(&world);
+        let line = create(
+            &mut world,
+            Geometry::Line(Line::new(Point::zero(), Point::new(5.0, 0.0))),
+        );
+        let mut dimension =
+            LinearDimension::aligned(Point::zero(), Point::new(5.0, 0.0), 1.0);
+        dimension.associated_with = Some((
+            Anchor::new(line, Handle::Start),
+            Anchor::new(line, Handle::End),
+        ));
+        let dimension =
+            create(&mut world, Geometry::LinearDimension(dimension));
+        let mut leader =
+            Leader::new(vec![Point::new(2.5, 0.0), Point::new(4.0, 4.0)]);
+        leader.associated_with = Some(Anchor::new(line, Handle::Centre));
+        let leader = create(&mut world, Geometry::Leader(leader));
+        system.run_now(&world);
+
+        world
+            .write_storage::<DrawingObject>()
+            .get_mut(line)
+            .unwrap()
+            .geometry =
+            Geometry::Line(Line::new(Point::zero(), Point::new(8.0, 0.0)));
+        system.run_now(&world);
+
+        match geometry(&world, dimension) {
+            Geometry::LinearDimension(d) => assert_eq!(d.measurement(), 8.0),
+            other => panic!("{:?}", other),
+        }
+        match geometry(&world, leader) {
+            Geometry::Leader(l) => {
+                assert_eq!(l.tip(), Some(Point::new(4.0, 0.0)))
+            },
+            other => panic!("{:?}", other),
+        }
+
+        world.delete_entity(line).unwrap();
+        system.run_now(&world);
+
+        match geometry(&world, dimension) {
+            Geometry::LinearDimension(d) => {
+                assert_eq!(d.associated_with, None);
+                assert_eq!(d.measurement(), 8.0);
+            },
+            other => panic!("{:?}", other),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crate::components::{DrawingObject, Geometry};
+use specs::{hibitset::BitSetLike, prelude::*};
+
+/// Re-measures any [`crate::components::RadialDimension`] or
+/// [`crate::components::DiameterDimension`] when the arc it is associated
+/// with changes.
+///
+/// If the associated entity stops being an arc (or is deleted) the
+/// association is dropped and the dimension keeps its last measurement.
+#[derive(Debug)]
+pub struct SyncRadialDimensions {
+    changes: ReaderId<ComponentEvent>,
+    changed: BitSet,
+    removed: BitSet,
+}
+
+impl SyncRadialDimensions {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> SyncRadialDimensions {
+        SyncRadialDimensions {
+            changes: world.write_storage::<DrawingObject>().register_reader(),
+            changed: BitSet::new(),
+            removed: BitSet::new(),
+        }
+    }
+}
+
+impl<'world> System<'world> for SyncRadialDimensions {
+    type SystemData = (WriteStorage<'world, DrawingObject>, Entities<'world>);
+
+    fn run(&mut self, data: Self::SystemData) {
+        self.changed.clear();
+        self.removed.clear();
+
+        let (mut drawing_objects, entities) = data;
+
+        for event in drawing_objects.channel().read(&mut self.changes) {
+            match *event {
+                ComponentEvent::Inserted(id) | ComponentEvent::Modified(id) => {
+                    self.changed.add(id);
+                },
+                ComponentEvent::Removed(id) => {
+                    self.removed.add(id);
+                },
+            }
+        }
+
+        if self.changed.is_empty() && self.removed.is_empty() {
+            return;
+        }
+
+        // find the dimensions which need updating first so we don't flag
+        // every dimension as modified
+        let mut updates = Vec::new();
+
+        for (ent, drawing_object) in (&entities, &drawing_objects).join() {
+            let source = match drawing_object.geometry {
+                Geometry::RadialDimension(ref d) => d.associated_with,
+                Geometry::DiameterDimension(ref d) => d.associated_with,
+                _ => None,
+            };
+            let source = match source {
+                Some(source) => source,
+                None => continue,
+            };
+
+            if self.removed.contains(source.id()) || !entities.is_alive(source)
+            {
+                updates.push((ent, None));
+            } else if self.changed.contains(source.id()) {
+                let geometry =
+                    drawing_objects.get(source).map(|d| d.geometry.clone());
+                updates.push((ent, geometry));
+            }
+        }
+
+        for (ent, geometry) in updates {
+            let drawing_object = match drawing_objects.get_mut(ent) {
+                Some(d) => d,
+                None => continue,
+            };
+            let (updated, associated_with) = match drawing_object.geometry {
+                Geometry::RadialDimension(ref mut d) => (
+                    geometry.map(|g| d.update_from(&g)).unwrap_or(false),
+                    &mut d.associated_with,
+                ),
+                Geometry::DiameterDimension(ref mut d) => (
+                    geometry.map(|g| d.update_from(&g)).unwrap_or(false),
+                    &mut d.associated_with,
+                ),
+                _ => continue,
+            };
+
+            if !updated {
+                log::warn!(
+                    "Unable to measure {:?}, dissociating it from the dimension on {:?}",
+                    associated_with,
+                    ent
+                );
+                *associated_with = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{Layer, Name, RadialDimension},
+        Angle, Arc, Point,
+    };
+
+    #[test]
+    fn resizing_the_arc_updates_the_dimension() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = SyncRadialDimensions::new(&world);
+        let layer = Layer::create(
+            world.create_entity(),
+            Name::new("default"),
+            Layer::default(),
+        );
+        let circle = |radius| {
+            Geometry::Arc(Arc::from_centre_radius(
+                Point::zero(),
+                radius,
+                Angle::zero(),
+                Angle::two_pi(),
+            ))
+        };
+        let arc = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: circle(1.0),
+                layer,
+            })
+            .build();
+        let dimension =
+            RadialDimension::associated(arc, &circle(1.0), Angle::zero())
+                .unwrap();
+        let dimension = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::RadialDimension(dimension),
+                layer,
+            })
+            .build();
+        system.run_now(&world);
+
+        world
+            .write_storage::<DrawingObject>()
+            .get_mut(arc)
+            .unwrap()
+            .geometry = circle(3.0);
+        system.run_now(&world);
+
+        let drawing_objects = world.read_storage::<DrawingObject>();
+        match drawing_objects.get(dimension).unwrap().geometry {
+            Geometry::RadialDimension(ref d) => {
+                assert_eq!(d.radius, 3.0);
+                assert_eq!(d.associated_with, Some(arc));
+            },
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{components::DirtyRegions, BoundingBox, DrawingSpace};
+use specs::{prelude::*, world::Index};
+use std::collections::HashMap;
+
+/// Records where entities were and where they are now whenever their
+/// [`BoundingBox`] changes, so the [`DirtyRegions`] can be redrawn.
+#[derive(Debug)]
+pub struct TrackDirtyRegions {
+    changes: ReaderId<ComponentEvent>,
+    previous: HashMap<Index, BoundingBox<DrawingSpace>>,
+}
+
+impl TrackDirtyRegions {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        let mut bounds = world.write_storage::<BoundingBox<DrawingSpace>>();
+        let changes = bounds.register_reader();
+        let previous = (&world.entities(), &bounds)
+            .join()
+            .map(|(ent, bounding_box)| (ent.id(), *bounding_box))
+            .collect();
+
+        TrackDirtyRegions { changes, previous }
+    }
+}
+
+impl<'world> System<'world> for TrackDirtyRegions {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, BoundingBox<DrawingSpace>>,
+        Write<'world, DirtyRegions>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, bounds, mut dirty) = data;
+
+        for event in bounds.channel().read(&mut self.changes) {
+            let id = match *event {
+                ComponentEvent::Inserted(id)
+                | ComponentEvent::Modified(id)
+                | ComponentEvent::Removed(id) => id,
+            };
+
+            if let Some(old) = self.previous.remove(&id) {
+                dirty.add(old);
+            }
+
+            if let Some(&new) = bounds.get(entities.entity(id)) {
+                dirty.add(new);
+                self.previous.insert(id, new);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    #[test]
+    fn moving_an_entity_dirties_old_and_new_locations() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        world.insert(DirtyRegions::default());
+        let first = BoundingBox::new(Point::zero(), Point::new(1.0, 1.0));
+        let ent = world.create_entity().with(first).build();
+        let mut system = TrackDirtyRegions::new(&world);
+
+        let second =
+            BoundingBox::new(Point::new(10.0, 10.0), Point::new(11.0, 11.0));
+        world
+            .write_storage::<BoundingBox<DrawingSpace>>()
+            .insert(ent, second)
+            .unwrap();
+        system.run_now(&world);
+
+        assert_eq!(
+            world.fetch_mut::<DirtyRegions>().take(),
+            vec![first, second]
+        );
+
+        world.delete_entity(ent).unwrap();
+        system.run_now(&world);
+
+        assert_eq!(world.fetch::<DirtyRegions>().regions(), &[second]);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    algorithms::Bounded,
+    components::{DrawingObject, Statistics},
+    BoundingBox, DrawingSpace,
+};
+use specs::prelude::*;
+
+/// Keeps the global [`Statistics`] up to date as [`DrawingObject`]s and
+/// their [`BoundingBox`]es change.
+#[derive(Debug)]
+pub struct UpdateStatistics {
+    object_changes: ReaderId<ComponentEvent>,
+    bounds_changes: ReaderId<ComponentEvent>,
+    to_update: BitSet,
+}
+
+impl UpdateStatistics {
+    pub const NAME: &'static str = module_path!();
+
+    pub fn new(world: &World) -> Self {
+        UpdateStatistics {
+            object_changes: world
+                .write_storage::<DrawingObject>()
+                .register_reader(),
+            bounds_changes: world
+                .write_storage::<BoundingBox<DrawingSpace>>()
+                .register_reader(),
+            to_update: BitSet::new(),
+        }
+    }
+}
+
+impl<'world> System<'world> for UpdateStatistics {
+    type SystemData = (
+        Entities<'world>,
+        ReadStorage<'world, DrawingObject>,
+        ReadStorage<'world, BoundingBox<DrawingSpace>>,
+        Write<'world, Statistics>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        self.to_update.clear();
+
+        let (entities, objects, bounds, mut stats) = data;
+
+        let changes = objects
+            .channel()
+            .read(&mut self.object_changes)
+            .chain(bounds.channel().read(&mut self.bounds_changes));
+
+        for event in changes {
+            match *event {
+                ComponentEvent::Inserted(id) | ComponentEvent::Modified(id) => {
+                    self.to_update.add(id);
+                },
+                ComponentEvent::Removed(id) => {
+                    // this may just be the bounding box being removed, the
+                    // object will be added back below if it is still there
+                    stats.remove_by_id(id);
+                    self.to_update.add(id);
+                },
+            }
+        }
+
+        for (ent, object, _) in (&entities, &objects, &self.to_update).join() {
+            record(&mut stats, ent, object, bounds.get(ent));
+        }
+
+        stats.update_extents();
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        <Self::SystemData as shred::DynamicSystemData>::setup(
+            &self.accessor(),
+            world,
+        );
+
+        let (entities, objects, bounds, mut stats) = world.system_data::<(
+            Entities<'_>,
+            ReadStorage<'_, DrawingObject>,
+            ReadStorage<'_, BoundingBox<DrawingSpace>>,
+            Write<'_, Statistics>,
+        )>();
+        stats.clear();
+
+        for (ent, object) in (&entities, &objects).join() {
+            record(&mut stats, ent, object, bounds.get(ent));
+        }
+    }
+}
+
+fn record(
+    stats: &mut Statistics,
+    ent: Entity,
+    object: &DrawingObject,
+    bounds: Option<&BoundingBox<DrawingSpace>>,
+) {
+    let bounds = bounds
+        .copied()
+        .unwrap_or_else(|| object.geometry.bounding_box());
+    stats.insert(ent, object.geometry.kind(), object.layer, bounds);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::Geometry, Line, Point};
+
+    #[test]
+    fn count_objects_as_they_change() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        let mut system = UpdateStatistics::new(&world);
+        System::setup(&mut system, &mut world);
+        let layer = world.create_entity().build();
+        let point = world
+            .create_entity()
+            .with(DrawingObject {
+                geometry: Geometry::Point(Point::new(5.0, 5.0)),
+                layer,
+            })
+            .build();
+        system.run_now(&world);
+        assert_eq!(world.fetch::<Statistics>().count_of_kind("Point"), 1);
+
+        world
+            .write_storage::<DrawingObject>()
+            .get_mut(point)
+            .unwrap()
+            .geometry =
+            Geometry::Line(Line::new(Point::zero(), Point::new(1.0, 1.0)));
+        system.run_now(&world);
+
+        {
+            let stats = world.fetch::<Statistics>();
+            assert_eq!(stats.total(), 1);
+            assert_eq!(stats.count_of_kind("Point"), 0);
+            assert_eq!(stats.count_of_kind("Line"), 1);
+            assert_eq!(
+                stats.extents(),
+                Some(BoundingBox::new(Point::zero(), Point::new(1.0, 1.0)))
+            );
+        }
+
+        world.delete_entity(point).unwrap();
+        system.run_now(&world);
+
+        let stats = world.fetch::<Statistics>();
+        assert_eq!(stats.total(), 0);
+        assert_eq!(stats.count_on_layer(layer), 0);
+        assert_eq!(stats.extents(), None);
+    }
+}
@@ -1,17 +1,23 @@
 use crate::{
-    algorithms::Bounded,
+    algorithms::{Approximate, Bounded},
     components::{
-        DrawingObject, Geometry, Layer, LineStyle, PointStyle, Viewport,
+        is_visible, AnnotationGraphics, BlockTable, DrawOrder, DrawingObject,
+        Geometry, Hatch, HatchPattern, Hidden, HorizontalAlignment, Layer,
+        LineStyle, LineType, MText, PointShape, PointStyle, ResolvedStyle,
+        StyleSources, Text, TextStyle, TextStyleRef, TextStyleTable, Viewport,
         WindowStyle,
     },
-    BoundingBox, CanvasSpace, DrawingSpace, Line, Point,
+    Angle, BoundingBox, CanvasSpace, DrawingSpace, Ellipse, Line, Point,
 };
 use euclid::{Point2D, Scale, Size2D};
-use kurbo::Circle;
-use piet::RenderContext;
+use kurbo::{Affine, Circle, Vec2};
+use piet::{
+    Color, FontBuilder, LineCap, RenderContext, StrokeStyle, Text as _,
+    TextLayout, TextLayoutBuilder,
+};
 use shred_derive::SystemData;
 use specs::{join::MaybeJoin, prelude::*};
-use std::{cmp::Reverse, collections::BTreeMap};
+use std::{cmp::Reverse, collections::BTreeMap, fmt::Debug};
 
 /// A wrapper around the "window" object.
 #[derive(Debug, Clone, PartialEq)]
@@ -93,6 +99,11 @@ impl Window {
     }
 }
 
+/// How far (in pixels) an approximated curve may deviate from the real thing.
+const APPROXIMATION_TOLERANCE: f64 = 0.25;
+/// The width (in pixels) of the lines used to draw a [`PointShape`].
+const POINT_STROKE_WIDTH: f64 = 1.0;
+
 /// The [`System`] which actually renders things.
 ///
 /// This is a temporary object "closing over" the [`Window`] and some
@@ -106,7 +117,10 @@ struct RenderSystem<'window, B> {
 
 impl<'window, B> RenderSystem<'window, B> {
     /// Calculate the area of the drawing displayed by the viewport.
-    fn viewport_dimensions(&self, viewport: &Viewport) -> BoundingBox<DrawingSpace> {
+    fn viewport_dimensions(
+        &self,
+        viewport: &Viewport,
+    ) -> BoundingBox<DrawingSpace> {
         let window_size = viewport
             .pixels_per_drawing_unit
             .inv()
@@ -143,7 +157,206 @@ impl<'window, B: RenderContext> RenderSystem<'window, B> {
                     viewport,
                 );
             },
-            _ => unimplemented!(),
+            Geometry::Arc(ref arc) => {
+                self.render_approximated(
+                    ent,
+                    arc,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::Ellipse(ref ellipse) => {
+                self.render_ellipse(
+                    ent,
+                    ellipse,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::EllipticalArc(ref arc) => {
+                self.render_approximated(
+                    ent,
+                    arc,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::Polyline(ref polyline) => {
+                self.render_approximated(
+                    ent,
+                    polyline,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::CubicBezier(ref curve) => {
+                self.render_approximated(
+                    ent,
+                    curve,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::QuadraticBezier(ref curve) => {
+                self.render_approximated(
+                    ent,
+                    curve,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::Spline(ref spline) => {
+                self.render_approximated(
+                    ent,
+                    spline,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::Text(ref text) => {
+                self.render_text(
+                    ent,
+                    text,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::MText(ref text) => {
+                self.render_mtext(
+                    ent,
+                    text,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::Hatch(ref hatch) => {
+                self.render_hatch(
+                    ent,
+                    hatch,
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::LinearDimension(ref dimension) => {
+                self.render_annotation(
+                    ent,
+                    &dimension.graphics(),
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::AngularDimension(ref dimension) => {
+                self.render_annotation(
+                    ent,
+                    &dimension.graphics(),
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::RadialDimension(ref dimension) => {
+                self.render_annotation(
+                    ent,
+                    &dimension.graphics(),
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::DiameterDimension(ref dimension) => {
+                self.render_annotation(
+                    ent,
+                    &dimension.graphics(),
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::Leader(ref leader) => {
+                self.render_annotation(
+                    ent,
+                    &leader.graphics(),
+                    drawing_object.layer,
+                    styles,
+                    viewport,
+                );
+            },
+            Geometry::XLine(xline) => {
+                let viewport_dimensions = self.viewport_dimensions(viewport);
+
+                if let Some(line) = xline.clip_to(viewport_dimensions) {
+                    self.render_line(
+                        ent,
+                        &line,
+                        drawing_object.layer,
+                        styles,
+                        viewport,
+                    );
+                }
+            },
+            Geometry::Ray(ray) => {
+                let viewport_dimensions = self.viewport_dimensions(viewport);
+
+                if let Some(line) = ray.clip_to(viewport_dimensions) {
+                    self.render_line(
+                        ent,
+                        &line,
+                        drawing_object.layer,
+                        styles,
+                        viewport,
+                    );
+                }
+            },
+            Geometry::ImageRef(ref image) => {
+                // the renderer doesn't load images, so just show where the
+                // image would go
+                for edge in image.outline().edges().iter() {
+                    self.render_line(
+                        ent,
+                        edge,
+                        drawing_object.layer,
+                        styles,
+                        viewport,
+                    );
+                }
+            },
+            Geometry::Region(ref region) => {
+                for boundary in region.loops() {
+                    self.render_approximated(
+                        ent,
+                        boundary,
+                        drawing_object.layer,
+                        styles,
+                        viewport,
+                    );
+                }
+            },
+            Geometry::Insert(ref insert) => {
+                let blocks = match styles.blocks {
+                    Some(ref blocks) => blocks,
+                    None => return,
+                };
+
+                // draw the block's contents as if they were part of the insert
+                for geometry in insert.expand(blocks) {
+                    let item = DrawingObject {
+                        geometry,
+                        layer: drawing_object.layer,
+                    };
+                    self.render(ent, &item, styles, viewport);
+                }
+            },
         }
     }
 
@@ -159,13 +372,68 @@ impl<'window, B: RenderContext> RenderSystem<'window, B> {
         let style = resolve_point_style(styles, self.window, entity, layer);
 
         let centre = self.to_canvas_coordinates(point, viewport);
-        let shape = Circle {
-            center: kurbo::Point::new(centre.x, centre.y),
-            radius: style.radius.in_pixels(viewport.pixels_per_drawing_unit),
+        let centre = kurbo::Point::new(centre.x, centre.y);
+        let radius = style.radius.in_pixels(viewport.pixels_per_drawing_unit);
+        let circle = Circle {
+            center: centre,
+            radius,
         };
-        log::trace!("Drawing {:?} as {:?} using {:?}", point, shape, style);
-
-        self.backend.fill(shape, &style.colour);
+        let line_through_centre =
+            |offset: Vec2| kurbo::Line::new(centre - offset, centre + offset);
+        let plus = [
+            line_through_centre(Vec2::new(radius, 0.0)),
+            line_through_centre(Vec2::new(0.0, radius)),
+        ];
+        // the diagonals should end on the circle
+        let offset = radius * std::f64::consts::FRAC_1_SQRT_2;
+        let x = [
+            line_through_centre(Vec2::new(offset, offset)),
+            line_through_centre(Vec2::new(offset, -offset)),
+        ];
+        log::trace!(
+            "Drawing {:?} as a {:?} using {:?}",
+            point,
+            style.shape,
+            style
+        );
+
+        match style.shape {
+            PointShape::Dot => self.backend.fill(circle, &style.colour),
+            PointShape::Circle => {
+                self.backend
+                    .stroke(circle, &style.colour, POINT_STROKE_WIDTH)
+            },
+            PointShape::Plus => {
+                for &line in &plus {
+                    self.backend.stroke(
+                        line,
+                        &style.colour,
+                        POINT_STROKE_WIDTH,
+                    );
+                }
+            },
+            PointShape::X => {
+                for &line in &x {
+                    self.backend.stroke(
+                        line,
+                        &style.colour,
+                        POINT_STROKE_WIDTH,
+                    );
+                }
+            },
+            PointShape::Cross => {
+                self.backend
+                    .stroke(circle, &style.colour, POINT_STROKE_WIDTH);
+
+                for &line in &plus {
+                    self.backend.stroke(
+                        line,
+                        &style.colour,
+                        POINT_STROKE_WIDTH,
+                    );
+                }
+            },
+        }
     }
 
     fn render_line(
@@ -176,16 +444,351 @@ impl<'window, B: RenderContext> RenderSystem<'window, B> {
         styles: &Styling,
         viewport: &Viewport,
     ) {
-        let style = resolve_line_style(styles, self.window, entity, layer);
+        let style = styles.sources.resolve(entity, layer);
 
         let start = self.to_canvas_coordinates(line.start, viewport);
         let end = self.to_canvas_coordinates(line.end, viewport);
         let shape = kurbo::Line::new(start.to_tuple(), end.to_tuple());
-        let stroke_width =
-            style.width.in_pixels(viewport.pixels_per_drawing_unit);
+        let stroke_width = style
+            .line_weight
+            .in_pixels(viewport.pixels_per_drawing_unit);
+        let stroke_style = resolve_stroke_style(&style, viewport);
         log::trace!("Drawing {:?} as {:?} using {:?}", line, shape, style);
 
-        self.backend.stroke(shape, &style.stroke, stroke_width);
+        self.backend.stroke_styled(
+            shape,
+            &style.colour,
+            stroke_width,
+            &stroke_style,
+        );
+    }
+
+    /// Draw the outline of an [`Ellipse`] using its [`ResolvedStyle`].
+    fn render_ellipse(
+        &mut self,
+        entity: Entity,
+        ellipse: &Ellipse,
+        layer: Entity,
+        styles: &Styling,
+        viewport: &Viewport,
+    ) {
+        let style = styles.sources.resolve(entity, layer);
+
+        let centre = self.to_canvas_coordinates(ellipse.centre(), viewport);
+        let scale = viewport.pixels_per_drawing_unit.get();
+        // the y-axis is flipped in canvas space, so angles go the other way
+        let shape = kurbo::Ellipse::new(
+            centre.to_tuple(),
+            (ellipse.semi_major() * scale, ellipse.semi_minor() * scale),
+            -ellipse.rotation().radians,
+        );
+        let stroke_width = style
+            .line_weight
+            .in_pixels(viewport.pixels_per_drawing_unit);
+        let stroke_style = resolve_stroke_style(&style, viewport);
+        log::trace!("Drawing {:?} as {:?} using {:?}", ellipse, shape, style);
+
+        self.backend.stroke_styled(
+            shape,
+            &style.colour,
+            stroke_width,
+            &stroke_style,
+        );
+    }
+
+    /// Draw a curve (e.g. an [`crate::EllipticalArc`]) by approximating it
+    /// with straight lines.
+    fn render_approximated<A>(
+        &mut self,
+        entity: Entity,
+        curve: &A,
+        layer: Entity,
+        styles: &Styling,
+        viewport: &Viewport,
+    ) where
+        A: Approximate<DrawingSpace> + Debug,
+    {
+        let style = styles.sources.resolve(entity, layer);
+
+        // we only need to be accurate to within a fraction of a pixel
+        let tolerance =
+            APPROXIMATION_TOLERANCE / viewport.pixels_per_drawing_unit.get();
+        let mut shape = kurbo::BezPath::new();
+
+        for (i, point) in curve.approximate(tolerance).enumerate() {
+            let point = self.to_canvas_coordinates(point, viewport).to_tuple();
+
+            if i == 0 {
+                shape.move_to(point);
+            } else {
+                shape.line_to(point);
+            }
+        }
+
+        let stroke_width = style
+            .line_weight
+            .in_pixels(viewport.pixels_per_drawing_unit);
+        let stroke_style = resolve_stroke_style(&style, viewport);
+        log::trace!("Drawing {:?} as {:?} using {:?}", curve, shape, style);
+
+        self.backend.stroke_styled(
+            shape,
+            &style.colour,
+            stroke_width,
+            &stroke_style,
+        );
+    }
+
+    /// Fill in a [`Hatch`] using its resolved colour.
+    fn render_hatch(
+        &mut self,
+        entity: Entity,
+        hatch: &Hatch,
+        layer: Entity,
+        styles: &Styling,
+        viewport: &Viewport,
+    ) {
+        let style = styles.sources.resolve(entity, layer);
+        let tolerance =
+            APPROXIMATION_TOLERANCE / viewport.pixels_per_drawing_unit.get();
+        log::trace!("Drawing {:?} using {:?}", hatch, style);
+
+        if let HatchPattern::Solid = hatch.pattern {
+            let mut shape = kurbo::BezPath::new();
+
+            for points in hatch.approximate_loops(tolerance) {
+                for (i, point) in points.into_iter().enumerate() {
+                    let point =
+                        self.to_canvas_coordinates(point, viewport).to_tuple();
+
+                    if i == 0 {
+                        shape.move_to(point);
+                    } else {
+                        shape.line_to(point);
+                    }
+                }
+
+                shape.close_path();
+            }
+
+            self.backend.fill_even_odd(shape, &style.colour);
+            return;
+        }
+
+        let stroke_width = style
+            .line_weight
+            .in_pixels(viewport.pixels_per_drawing_unit);
+
+        for line in hatch.pattern_lines(tolerance) {
+            let start = self.to_canvas_coordinates(line.start, viewport);
+            let end = self.to_canvas_coordinates(line.end, viewport);
+            let shape = kurbo::Line::new(start.to_tuple(), end.to_tuple());
+
+            self.backend.stroke(shape, &style.colour, stroke_width);
+        }
+    }
+
+    /// Draw an annotation (e.g. a [`crate::components::LinearDimension`])
+    /// using its [`ResolvedStyle`].
+    fn render_annotation(
+        &mut self,
+        entity: Entity,
+        graphics: &AnnotationGraphics,
+        layer: Entity,
+        styles: &Styling,
+        viewport: &Viewport,
+    ) {
+        let style = styles.sources.resolve(entity, layer);
+        let stroke_width = style
+            .line_weight
+            .in_pixels(viewport.pixels_per_drawing_unit);
+        log::trace!("Drawing {:?} using {:?}", graphics, style);
+
+        for line in &graphics.lines {
+            let start = self.to_canvas_coordinates(line.start, viewport);
+            let end = self.to_canvas_coordinates(line.end, viewport);
+            let shape = kurbo::Line::new(start.to_tuple(), end.to_tuple());
+
+            self.backend.stroke(shape, &style.colour, stroke_width);
+        }
+
+        // we only need to be accurate to within a fraction of a pixel
+        let tolerance =
+            APPROXIMATION_TOLERANCE / viewport.pixels_per_drawing_unit.get();
+
+        for arc in &graphics.arcs {
+            let mut shape = kurbo::BezPath::new();
+
+            for (i, point) in arc.approximate(tolerance).enumerate() {
+                let point =
+                    self.to_canvas_coordinates(point, viewport).to_tuple();
+
+                if i == 0 {
+                    shape.move_to(point);
+                } else {
+                    shape.line_to(point);
+                }
+            }
+
+            self.backend.stroke(shape, &style.colour, stroke_width);
+        }
+
+        for arrowhead in &graphics.arrowheads {
+            let mut shape = kurbo::BezPath::new();
+
+            for (i, &point) in arrowhead.iter().enumerate() {
+                let point =
+                    self.to_canvas_coordinates(point, viewport).to_tuple();
+
+                if i == 0 {
+                    shape.move_to(point);
+                } else {
+                    shape.line_to(point);
+                }
+            }
+            shape.close_path();
+
+            self.backend.fill(shape, &style.colour);
+        }
+
+        let text_style = resolve_text_style(styles, entity);
+
+        for text in &graphics.text {
+            self.draw_text_line(
+                &text.content,
+                text.baseline_anchor(),
+                text.rotation,
+                text.height,
+                text.horizontal_alignment,
+                &style.colour,
+                &text_style,
+                viewport,
+            );
+        }
+    }
+
+    /// Draw some [`Text`] using its resolved colour and [`TextStyle`].
+    fn render_text(
+        &mut self,
+        entity: Entity,
+        text: &Text,
+        layer: Entity,
+        styles: &Styling,
+        viewport: &Viewport,
+    ) {
+        let style = styles.sources.resolve(entity, layer);
+        let text_style = resolve_text_style(styles, entity);
+        log::trace!("Drawing {:?} using {:?}", text, style);
+
+        self.draw_text_line(
+            &text.content,
+            text.baseline_anchor(),
+            text.rotation,
+            text.height,
+            text.horizontal_alignment,
+            &style.colour,
+            &text_style,
+            viewport,
+        );
+    }
+
+    /// Draw each line of an [`MText`] using its resolved colour and
+    /// [`TextStyle`].
+    fn render_mtext(
+        &mut self,
+        entity: Entity,
+        text: &MText,
+        layer: Entity,
+        styles: &Styling,
+        viewport: &Viewport,
+    ) {
+        let style = styles.sources.resolve(entity, layer);
+        let text_style = resolve_text_style(styles, entity);
+        log::trace!("Drawing {:?} using {:?}", text, style);
+
+        for line in text.layout() {
+            self.draw_text_line(
+                &line.text(),
+                line.anchor,
+                text.rotation,
+                text.height,
+                text.horizontal_alignment,
+                &style.colour,
+                &text_style,
+                viewport,
+            );
+        }
+    }
+
+    /// Draw a single line of text, aligned horizontally relative to a point
+    /// on its baseline.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text_line(
+        &mut self,
+        content: &str,
+        anchor: Point,
+        rotation: Angle,
+        height: f64,
+        alignment: HorizontalAlignment,
+        colour: &Color,
+        text_style: &TextStyle,
+        viewport: &Viewport,
+    ) {
+        let font_size = height
+            * text_style.height_factor
+            * viewport.pixels_per_drawing_unit.get();
+
+        let font = match self
+            .backend
+            .text()
+            .new_font_by_name(&text_style.font, font_size)
+            .build()
+        {
+            Ok(font) => font,
+            Err(e) => {
+                log::warn!(
+                    "Unable to load the \"{}\" font: {}",
+                    text_style.font,
+                    e
+                );
+                return;
+            },
+        };
+        let layout = match self
+            .backend
+            .text()
+            .new_text_layout(&font, content, None)
+            .build()
+        {
+            Ok(layout) => layout,
+            Err(e) => {
+                log::warn!("Unable to lay out {:?}: {}", content, e);
+                return;
+            },
+        };
+
+        // the text's local coordinate system, in pixels (remembering that the
+        // y-axis is flipped in canvas space)
+        let anchor = self.to_canvas_coordinates(anchor, viewport);
+        let shear = -text_style.oblique_angle.radians.tan();
+        let transform = Affine::translate(Vec2::new(anchor.x, anchor.y))
+            * Affine::rotate(-rotation.radians)
+            * Affine::new([text_style.width_factor, 0.0, shear, 1.0, 0.0, 0.0]);
+        let start = match alignment {
+            HorizontalAlignment::Left => 0.0,
+            HorizontalAlignment::Centre => -layout.width() / 2.0,
+            HorizontalAlignment::Right => -layout.width(),
+        };
+
+        let result = self.backend.with_save(|ctx| {
+            ctx.transform(transform);
+            ctx.draw_text(&layout, (start, 0.0), colour);
+            Ok(())
+        });
+
+        if let Err(e) = result {
+            log::warn!("Unable to draw {:?}: {}", content, e);
+        }
     }
 
     /// Translates a [`crate::Point`] from drawing space to a location in
@@ -203,13 +806,13 @@ impl<'window, 'world, B: RenderContext> System<'world>
     for RenderSystem<'window, B>
 {
     type SystemData = (
-        DrawOrder<'world>,
+        DrawingQueue<'world>,
         Styling<'world>,
         ReadStorage<'world, Viewport>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (draw_order, styling, viewports) = data;
+        let (queue, styling, viewports) = data;
 
         let window_style = self.window.style(&styling.window_styles);
         let viewport = self.window.viewport(&viewports);
@@ -219,7 +822,7 @@ impl<'window, 'world, B: RenderContext> System<'world>
 
         let viewport_dimensions = self.viewport_dimensions(&viewport);
 
-        for (ent, obj) in draw_order.calculate(viewport_dimensions) {
+        for (ent, obj) in queue.calculate(viewport_dimensions) {
             self.render(ent, obj, &styling, viewport);
         }
     }
@@ -229,8 +832,11 @@ impl<'window, 'world, B: RenderContext> System<'world>
 #[derive(SystemData)]
 struct Styling<'world> {
     point_styles: ReadStorage<'world, PointStyle>,
-    line_styles: ReadStorage<'world, LineStyle>,
     window_styles: ReadStorage<'world, WindowStyle>,
+    sources: StyleSources<'world>,
+    text_style_refs: ReadStorage<'world, TextStyleRef>,
+    text_styles: Option<Read<'world, TextStyleTable>>,
+    blocks: Option<Read<'world, BlockTable>>,
 }
 
 fn resolve_point_style<'a>(
@@ -249,30 +855,49 @@ fn resolve_point_style<'a>(
             .unwrap_or_else(|| window.default_point_style(&styling.point_styles))
 }
 
-fn resolve_line_style<'a>(
-    styling: &'a Styling,
-    window: &'a Window,
-    line: Entity,
-    layer: Entity,
-) -> &'a LineStyle {
+/// Find the [`TextStyle`] some text should be drawn with.
+fn resolve_text_style(styling: &Styling, entity: Entity) -> TextStyle {
     styling
-        .line_styles
-        .get(line)
-        .or_else(|| styling.line_styles.get(layer))
-        .unwrap_or_else(|| window.default_line_style(&styling.line_styles))
+        .text_styles
+        .as_ref()
+        .and_then(|table| table.resolve(styling.text_style_refs.get(entity)))
+        .cloned()
+        .unwrap_or_else(TextStyle::standard)
+}
+
+/// Get the dash pattern (in pixels) for an entity's [`LineType`].
+fn resolve_stroke_style(
+    resolved: &ResolvedStyle,
+    viewport: &Viewport,
+) -> StrokeStyle {
+    let mut stroke_style = StrokeStyle::new();
+
+    if let Some(dashes) =
+        resolved.line_type.as_ref().and_then(LineType::dash_array)
+    {
+        let scale = viewport.pixels_per_drawing_unit.get();
+        stroke_style
+            .set_dash(dashes.into_iter().map(|d| d * scale).collect(), 0.0);
+        // dots have no length, so they need round caps to be visible
+        stroke_style.set_line_cap(LineCap::Round);
+    }
+
+    stroke_style
 }
 
 /// The state needed when calculating which order to draw things in so z-levels
-/// are implemented correctly.
+/// and [`DrawOrder`]s are implemented correctly.
 #[derive(SystemData)]
-struct DrawOrder<'world> {
+struct DrawingQueue<'world> {
     entities: Entities<'world>,
     drawing_objects: ReadStorage<'world, DrawingObject>,
     layers: ReadStorage<'world, Layer>,
+    hidden: ReadStorage<'world, Hidden>,
+    draw_orders: ReadStorage<'world, DrawOrder>,
     bounding_boxes: ReadStorage<'world, BoundingBox<DrawingSpace>>,
 }
 
-impl<'world> DrawOrder<'world> {
+impl<'world> DrawingQueue<'world> {
     fn calculate(
         &self,
         viewport_dimensions: BoundingBox<DrawingSpace>,
@@ -299,17 +924,19 @@ impl<'world> DrawOrder<'world> {
         )
             .join()
         {
-            let Layer { z_level, visible } = self
+            let Layer { z_level, .. } = self
                 .layers
                 .get(obj.layer)
                 .expect("The object's layer was deleted");
+            let visible =
+                is_visible(&self.hidden, &self.layers, ent, obj.layer);
 
             // try to use the cached bounds, otherwise re-calculate them
             let bounds = bounds
                 .copied()
                 .unwrap_or_else(|| obj.geometry.bounding_box());
 
-            if *visible && viewport_dimensions.intersects_with(bounds) {
+            if visible && viewport_dimensions.intersects_with(bounds) {
                 drawing_objects
                     .entry(Reverse(*z_level))
                     .or_default()
@@ -317,6 +944,68 @@ impl<'world> DrawOrder<'world> {
             }
         }
 
-        drawing_objects.into_iter().flat_map(|(_, items)| items)
+        let draw_orders = &self.draw_orders;
+        let draw_order = move |ent: Entity| {
+            draw_orders.get(ent).copied().unwrap_or_default()
+        };
+
+        // lower draw orders go underneath, keeping the creation order for
+        // ties (sort_by_key() is stable)
+        drawing_objects.into_iter().flat_map(move |(_, mut items)| {
+            items.sort_by_key(|(ent, _)| draw_order(*ent));
+            items
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Dimension;
+
+    #[test]
+    fn resolve_point_styles_and_sizes() {
+        let mut world = World::new();
+        crate::components::register(&mut world);
+        <Styling<'_> as SystemData<'_>>::setup(&mut world);
+        let window = Window::create(&mut world);
+        let layer = world
+            .create_entity()
+            .with(Layer::default())
+            .with(PointStyle {
+                colour: Color::rgb8(0xff, 0, 0),
+                radius: Dimension::DrawingUnits(crate::Length::new(0.5)),
+                shape: PointShape::X,
+            })
+            .build();
+        let plain_layer = world.create_entity().with(Layer::default()).build();
+        let overridden = world
+            .create_entity()
+            .with(PointStyle {
+                radius: Dimension::Pixels(7.0),
+                shape: PointShape::Cross,
+                ..PointStyle::default()
+            })
+            .build();
+        let plain = world.create_entity().build();
+        let styling = world.system_data::<Styling<'_>>();
+        let zoom = Scale::new(10.0);
+
+        // the entity's own style wins
+        let got = resolve_point_style(&styling, &window, overridden, layer);
+        assert_eq!(got.shape, PointShape::Cross);
+        assert_eq!(got.radius.in_pixels(zoom), 7.0);
+
+        // then the layer's, where drawing units scale with the zoom
+        let got = resolve_point_style(&styling, &window, plain, layer);
+        assert_eq!(got.shape, PointShape::X);
+        assert_eq!(got.radius.in_pixels(zoom), 5.0);
+        assert_eq!(got.radius.in_pixels(Scale::new(2.0)), 1.0);
+
+        // and finally the window's default
+        let got = resolve_point_style(&styling, &window, plain, plain_layer);
+        assert_eq!(got.shape, PointShape::Dot);
+        assert_eq!(got.radius.in_pixels(zoom), 3.0);
+        assert_eq!(got.radius.in_pixels(Scale::new(2.0)), 3.0);
     }
 }
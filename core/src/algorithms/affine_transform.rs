@@ -1,4 +1,6 @@
-use crate::primitives::Line;
+use crate::primitives::{
+    CubicBezier, Line, QuadraticBezier, Ray, Spline, XLine,
+};
 use euclid::default::Transform2D;
 
 /// Something which can be transformed using an arbitrary [`Transform2D`] matrix
@@ -76,3 +78,45 @@ impl<Space> AffineTransformable for Line<Space> {
         self.end.transform(transform);
     }
 }
+
+impl<Space> AffineTransformable for CubicBezier<Space> {
+    fn transform(&mut self, transform: Transform2D<f64>) {
+        // Bézier curves are affine invariant, so we only need to transform the
+        // control points
+        self.start.transform(transform);
+        self.first_control_point.transform(transform);
+        self.second_control_point.transform(transform);
+        self.end.transform(transform);
+    }
+}
+
+impl<Space> AffineTransformable for Spline<Space> {
+    fn transform(&mut self, transform: Transform2D<f64>) {
+        // like Bézier curves, NURBS are affine invariant
+        for point in self.control_points_mut() {
+            point.transform(transform);
+        }
+    }
+}
+
+impl<Space> AffineTransformable for QuadraticBezier<Space> {
+    fn transform(&mut self, transform: Transform2D<f64>) {
+        self.start.transform(transform);
+        self.control_point.transform(transform);
+        self.end.transform(transform);
+    }
+}
+
+impl<Space> AffineTransformable for XLine<Space> {
+    fn transform(&mut self, transform: Transform2D<f64>) {
+        self.base_point.transform(transform);
+        self.direction.transform(transform);
+    }
+}
+
+impl<Space> AffineTransformable for Ray<Space> {
+    fn transform(&mut self, transform: Transform2D<f64>) {
+        self.start.transform(transform);
+        self.direction.transform(transform);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    primitives::{Arc, Line},
+    primitives::{
+        Arc, CubicBezier, Ellipse, EllipticalArc, Line, Polyline,
+        QuadraticBezier, Segment, Spline,
+    },
     Angle,
 };
 use euclid::Point2D;
@@ -66,13 +69,15 @@ impl<Space> Approximate<Space> for Arc<Space> {
         } else {
             let cos_theta_on_two = 1.0 - tolerance / self.radius();
             let theta = cos_theta_on_two.acos() * 2.0;
-            let line_segment_count = self.sweep_angle().get() / theta;
+            let line_segment_count =
+                (self.sweep_angle().get().abs() / theta).ceil();
 
-            // make sure we always have at least 2 points
+            // make sure we always have at least 2 points, and that the
+            // steps are evenly spaced so the last one lands on the end
             let line_segment_count = f64::max(line_segment_count, 2.0);
             let actual_step = self.sweep_angle() / line_segment_count;
 
-            (line_segment_count.ceil().abs() as usize, actual_step)
+            (line_segment_count as usize, actual_step)
         };
 
         ApproximatedArc {
@@ -112,9 +117,201 @@ impl<Space> Iterator for ApproximatedArc<Space> {
     }
 }
 
+impl<Space> Approximate<Space> for EllipticalArc<Space> {
+    type Iter = ApproximatedEllipticalArc<Space>;
+
+    fn approximate(&self, tolerance: f64) -> Self::Iter {
+        // An ellipse is just a circle with radius `semi_major` which has been
+        // squashed along its minor axis. Squashing can only bring a chord
+        // closer to the curve, so we can reuse the same calculation as
+        // `Arc::approximate()` with parametric angles.
+        let radius = self.ellipse().semi_major();
+
+        let (steps, step_size) = if tolerance <= 0.0 || radius <= tolerance {
+            (1, self.sweep_angle())
+        } else {
+            let cos_theta_on_two = 1.0 - tolerance / radius;
+            let theta = cos_theta_on_two.acos() * 2.0;
+            let line_segment_count = self.sweep_angle().get().abs() / theta;
+
+            // make sure we always have at least 2 points
+            let line_segment_count = f64::max(line_segment_count, 2.0).ceil();
+            let actual_step = self.sweep_angle() / line_segment_count;
+
+            (line_segment_count as usize, actual_step)
+        };
+
+        ApproximatedEllipticalArc {
+            i: 0,
+            steps,
+            step_size,
+            arc: *self,
+        }
+    }
+}
+
+impl<Space> Approximate<Space> for Ellipse<Space> {
+    type Iter = ApproximatedEllipticalArc<Space>;
+
+    fn approximate(&self, tolerance: f64) -> Self::Iter {
+        EllipticalArc::new(*self, Angle::zero(), Angle::two_pi())
+            .approximate(tolerance)
+    }
+}
+
+impl<Space> Approximate<Space> for Polyline<Space> {
+    type Iter = std::vec::IntoIter<Point2D<f64, Space>>;
+
+    fn approximate(&self, tolerance: f64) -> Self::Iter {
+        let mut points: Vec<_> = self
+            .vertices()
+            .first()
+            .map(|v| v.point)
+            .into_iter()
+            .collect();
+
+        for segment in self.segments() {
+            // skip the first point because it's the end of the last segment
+            match segment {
+                Segment::Line(line) => points.push(line.end),
+                Segment::Arc(arc) => {
+                    points.extend(arc.approximate(tolerance).skip(1))
+                },
+            }
+        }
+
+        points.into_iter()
+    }
+}
+
+impl<Space> Approximate<Space> for CubicBezier<Space> {
+    type Iter = std::vec::IntoIter<Point2D<f64, Space>>;
+
+    fn approximate(&self, tolerance: f64) -> Self::Iter {
+        // stop runaway recursion when tolerance is tiny (or zero)
+        const MAX_DEPTH: usize = 16;
+
+        fn flatten<Space>(
+            curve: CubicBezier<Space>,
+            tolerance: f64,
+            depth: usize,
+            points: &mut Vec<Point2D<f64, Space>>,
+        ) {
+            // A curve always lies within the convex hull of its control
+            // points, so if they are all close enough to the chord then the
+            // chord is a good enough approximation.
+            let chord = Line::new(curve.start, curve.end);
+            let first =
+                chord.perpendicular_distance_to(curve.first_control_point);
+            let second =
+                chord.perpendicular_distance_to(curve.second_control_point);
+            let flat_enough = depth >= MAX_DEPTH
+                || f64::max(first.get(), second.get()) <= tolerance;
+
+            if flat_enough {
+                points.push(curve.end);
+            } else {
+                let (left, right) = curve.split_at(0.5);
+                flatten(left, tolerance, depth + 1, points);
+                flatten(right, tolerance, depth + 1, points);
+            }
+        }
+
+        let mut points = vec![self.start];
+        flatten(*self, tolerance, 0, &mut points);
+
+        points.into_iter()
+    }
+}
+
+impl<Space> Approximate<Space> for QuadraticBezier<Space> {
+    type Iter = std::vec::IntoIter<Point2D<f64, Space>>;
+
+    fn approximate(&self, tolerance: f64) -> Self::Iter {
+        self.to_cubic().approximate(tolerance)
+    }
+}
+
+impl<Space> Approximate<Space> for Spline<Space> {
+    type Iter = std::vec::IntoIter<Point2D<f64, Space>>;
+
+    fn approximate(&self, tolerance: f64) -> Self::Iter {
+        // unlike Bézier curves, there's no cheap flatness test for NURBS so
+        // we keep bisecting each piece until its midpoint is close enough to
+        // the chord
+        const MIN_STEPS_PER_SPAN: usize = 4;
+        const MAX_DEPTH: usize = 12;
+
+        fn flatten<Space>(
+            spline: &Spline<Space>,
+            (start, end): (f64, f64),
+            tolerance: f64,
+            depth: usize,
+            points: &mut Vec<Point2D<f64, Space>>,
+        ) {
+            let first = spline.point_at(start);
+            let last = spline.point_at(end);
+            let middle = (start + end) / 2.0;
+            let chord = Line::new(first, last);
+            let error = chord
+                .perpendicular_distance_to(spline.point_at(middle))
+                .get();
+
+            if depth >= MAX_DEPTH || error <= tolerance {
+                points.push(last);
+            } else {
+                flatten(spline, (start, middle), tolerance, depth + 1, points);
+                flatten(spline, (middle, end), tolerance, depth + 1, points);
+            }
+        }
+
+        let mut points = vec![self.start()];
+
+        for span in self.breakpoints().windows(2) {
+            let step = (span[1] - span[0]) / MIN_STEPS_PER_SPAN as f64;
+
+            for i in 0..MIN_STEPS_PER_SPAN {
+                let start = span[0] + step * i as f64;
+                flatten(self, (start, start + step), tolerance, 0, &mut points);
+            }
+        }
+
+        points.into_iter()
+    }
+}
+
+/// An iterator over the points in an elliptical arc approximation.
+///
+/// This shouldn't be used directly, you are probably looking for
+/// `EllipticalArc::approximate()`.
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)] // iterators which are Copy are a footgun
+pub struct ApproximatedEllipticalArc<Space> {
+    i: usize,
+    steps: usize,
+    step_size: Angle,
+    arc: EllipticalArc<Space>,
+}
+
+impl<Space> Iterator for ApproximatedEllipticalArc<Space> {
+    type Item = Point2D<f64, Space>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i > self.steps {
+            return None;
+        }
+
+        let angle = Angle::radians(self.i as f64 * self.step_size.radians);
+        let point = self.arc.point_at(angle);
+        self.i += 1;
+        Some(point)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::primitives::Vertex;
 
     type Point = euclid::default::Point2D<f64>;
 
@@ -137,4 +334,105 @@ mod tests {
         assert_eq!(arc.start(), *pieces.first().unwrap());
         assert_eq!(arc.end(), *pieces.last().unwrap());
     }
+
+    #[test]
+    fn approximate_elliptical_arc_with_points() {
+        let ellipse = Ellipse::new(
+            Point::new(1.0, 2.0),
+            100.0,
+            40.0,
+            Angle::degrees(30.0),
+        );
+        let arc = EllipticalArc::new(ellipse, Angle::zero(), -Angle::pi());
+        let quality = 1.0;
+
+        let pieces: Vec<_> = arc.approximate(quality).collect();
+
+        assert!(pieces.len() > 3);
+        for window in pieces.windows(2) {
+            // the midpoint of each chord should be close to the ellipse
+            let midpoint = window[0].lerp(window[1], 0.5);
+            let angle = ellipse.parametric_angle_of(midpoint);
+            let on_ellipse = ellipse.point_at(angle);
+            assert!((on_ellipse - midpoint).length() < quality);
+        }
+        assert_eq!(arc.start(), *pieces.first().unwrap());
+        assert_eq!(arc.end(), *pieces.last().unwrap());
+    }
+
+    #[test]
+    fn approximate_closed_polyline() {
+        let polyline = Polyline::new(
+            vec![
+                Vertex::new(Point::new(0.0, 0.0), 1.0),
+                Vertex::straight(Point::new(10.0, 0.0)),
+                Vertex::straight(Point::new(10.0, 10.0)),
+            ],
+            true,
+        );
+
+        let pieces: Vec<_> = polyline.approximate(0.1).collect();
+
+        assert!(pieces.len() > 4);
+        assert_eq!(pieces[0], Point::new(0.0, 0.0));
+        assert_eq!(*pieces.last().unwrap(), Point::new(0.0, 0.0));
+        assert!(pieces.contains(&Point::new(10.0, 10.0)));
+    }
+
+    #[test]
+    fn approximate_cubic_bezier() {
+        let curve = CubicBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 100.0),
+            Point::new(100.0, 100.0),
+            Point::new(100.0, 0.0),
+        );
+        let quality = 0.5;
+
+        let pieces: Vec<_> = curve.approximate(quality).collect();
+
+        assert!(pieces.len() > 3);
+        assert_eq!(pieces[0], curve.start);
+        assert_eq!(*pieces.last().unwrap(), curve.end);
+        // the curve's peak is halfway along, which should be close to a chord
+        let peak = curve.point_at(0.5);
+        let closest = pieces
+            .windows(2)
+            .map(|w| {
+                Line::new(w[0], w[1]).perpendicular_distance_to(peak).get()
+            })
+            .fold(std::f64::INFINITY, f64::min);
+        assert!(closest <= quality);
+    }
+
+    #[test]
+    fn approximate_spline() {
+        let spline = Spline::clamped(
+            3,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 100.0),
+                Point::new(50.0, -50.0),
+                Point::new(100.0, 100.0),
+                Point::new(100.0, 0.0),
+            ],
+        )
+        .unwrap();
+        let quality = 0.5;
+
+        let pieces: Vec<_> = spline.approximate(quality).collect();
+
+        assert_eq!(pieces[0], spline.start());
+        assert_eq!(*pieces.last().unwrap(), spline.end());
+        for i in 0..=20 {
+            let point = spline.point_at(i as f64 / 20.0);
+            let closest = pieces
+                .windows(2)
+                .map(|w| {
+                    Line::new(w[0], w[1]).perpendicular_distance_to(point).get()
+                })
+                .fold(std::f64::INFINITY, f64::min);
+            assert!(closest <= quality);
+        }
+    }
 }
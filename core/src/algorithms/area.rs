@@ -0,0 +1,404 @@
+use crate::primitives::{Arc, Ellipse, Polyline, Region, Segment};
+use euclid::Point2D;
+use std::{
+    f64::consts::PI,
+    ops::{Add, Neg, Sub},
+};
+
+/// Something which encloses an area.
+pub trait Area<Space> {
+    /// The integrals used to calculate this shape's area properties.
+    fn area_moments(&self) -> AreaMoments;
+
+    /// The enclosed area, where shapes going anticlockwise have a positive
+    /// area.
+    fn signed_area(&self) -> f64 { self.area_moments().area }
+
+    /// The enclosed area.
+    fn area(&self) -> f64 { self.signed_area().abs() }
+
+    /// The centre of mass, or [`None`] if there is no area.
+    fn centroid(&self) -> Option<Point2D<f64, Space>> {
+        let moments = self.area_moments();
+
+        if moments.area.abs() <= std::f64::EPSILON {
+            None
+        } else {
+            Some(Point2D::new(
+                moments.x / moments.area,
+                moments.y / moments.area,
+            ))
+        }
+    }
+
+    /// The second moments of area around the centroid, or [`None`] if there
+    /// is no area.
+    fn second_moments(&self) -> Option<SecondMoments> {
+        let moments = self.area_moments();
+        let area = moments.area;
+
+        if area.abs() <= std::f64::EPSILON {
+            return None;
+        }
+
+        // the parallel axis theorem, in reverse
+        let (x, y) = (moments.x / area, moments.y / area);
+        let sign = area.signum();
+
+        Some(SecondMoments {
+            ixx: (moments.yy - area * y * y) * sign,
+            iyy: (moments.xx - area * x * x) * sign,
+            ixy: (moments.xy - area * x * y) * sign,
+        })
+    }
+}
+
+/// The integrals of `1`, `x`, `y`, `x²`, `y²` and `xy` over an area.
+///
+/// Moments are signed, so areas on the right of an anticlockwise boundary
+/// are negative. This means the moments for complex shapes can be
+/// calculated by adding and subtracting simpler shapes.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct AreaMoments {
+    /// The signed area.
+    pub area: f64,
+    /// The first moment, `∫x dA`.
+    pub x: f64,
+    /// The first moment, `∫y dA`.
+    pub y: f64,
+    /// The second moment, `∫x² dA`.
+    pub xx: f64,
+    /// The second moment, `∫y² dA`.
+    pub yy: f64,
+    /// The product of inertia, `∫xy dA`.
+    pub xy: f64,
+}
+
+impl AreaMoments {
+    /// The moments of the triangle with corners at the origin, `a` and `b`.
+    fn triangle(a: (f64, f64), b: (f64, f64)) -> Self {
+        let (ax, ay) = a;
+        let (bx, by) = b;
+        let area = (ax * by - bx * ay) / 2.0;
+
+        AreaMoments {
+            area,
+            x: area * (ax + bx) / 3.0,
+            y: area * (ay + by) / 3.0,
+            xx: area * (ax * ax + ax * bx + bx * bx) / 6.0,
+            yy: area * (ay * ay + ay * by + by * by) / 6.0,
+            xy: area * (2.0 * ax * ay + ax * by + bx * ay + 2.0 * bx * by)
+                / 12.0,
+        }
+    }
+
+    /// The moments of the circular sector swept out by an [`Arc`].
+    fn sector<S>(arc: Arc<S>) -> Self {
+        let r = arc.radius();
+        let a = arc.start_angle().radians;
+        let b = a + arc.sweep_angle().radians;
+        let (cx, cy) = (arc.centre().x, arc.centre().y);
+
+        // relative to the centre
+        let area = r * r * (b - a) / 2.0;
+        let x = r.powi(3) * (b.sin() - a.sin()) / 3.0;
+        let y = r.powi(3) * (a.cos() - b.cos()) / 3.0;
+        let r4 = r.powi(4);
+        let xx =
+            r4 / 8.0 * ((b - a) + ((2.0 * b).sin() - (2.0 * a).sin()) / 2.0);
+        let yy =
+            r4 / 8.0 * ((b - a) - ((2.0 * b).sin() - (2.0 * a).sin()) / 2.0);
+        let xy = r4 / 16.0 * ((2.0 * a).cos() - (2.0 * b).cos());
+
+        AreaMoments {
+            area,
+            x: x + cx * area,
+            y: y + cy * area,
+            xx: xx + 2.0 * cx * x + cx * cx * area,
+            yy: yy + 2.0 * cy * y + cy * cy * area,
+            xy: xy + cx * y + cy * x + cx * cy * area,
+        }
+    }
+
+    /// The moments of the area between an [`Arc`] and its chord.
+    fn circular_segment<S>(arc: Arc<S>) -> Self {
+        let point = |p: Point2D<f64, S>| (p.x, p.y);
+        let (centre, start, end) =
+            (point(arc.centre()), point(arc.start()), point(arc.end()));
+
+        // the sector, minus the triangle between the centre and the chord
+        AreaMoments::sector(arc)
+            - AreaMoments::triangle(centre, start)
+            - AreaMoments::triangle(start, end)
+            - AreaMoments::triangle(end, centre)
+    }
+}
+
+impl Add for AreaMoments {
+    type Output = AreaMoments;
+
+    fn add(self, other: AreaMoments) -> AreaMoments {
+        AreaMoments {
+            area: self.area + other.area,
+            x: self.x + other.x,
+            y: self.y + other.y,
+            xx: self.xx + other.xx,
+            yy: self.yy + other.yy,
+            xy: self.xy + other.xy,
+        }
+    }
+}
+
+impl Neg for AreaMoments {
+    type Output = AreaMoments;
+
+    fn neg(self) -> AreaMoments {
+        AreaMoments {
+            area: -self.area,
+            x: -self.x,
+            y: -self.y,
+            xx: -self.xx,
+            yy: -self.yy,
+            xy: -self.xy,
+        }
+    }
+}
+
+impl Sub for AreaMoments {
+    type Output = AreaMoments;
+
+    fn sub(self, other: AreaMoments) -> AreaMoments { self + -other }
+}
+
+/// The second moments of area around a shape's centroid.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct SecondMoments {
+    /// The second moment around a horizontal axis, `∫(y - ȳ)² dA`.
+    pub ixx: f64,
+    /// The second moment around a vertical axis, `∫(x - x̄)² dA`.
+    pub iyy: f64,
+    /// The product of inertia, `∫(x - x̄)(y - ȳ) dA`.
+    pub ixy: f64,
+}
+
+impl SecondMoments {
+    /// The polar moment of area, `ixx + iyy`.
+    pub fn polar(self) -> f64 { self.ixx + self.iyy }
+}
+
+impl<Space, A: Area<Space> + ?Sized> Area<Space> for &A {
+    fn area_moments(&self) -> AreaMoments { (*self).area_moments() }
+}
+
+impl<Space> Area<Space> for Polyline<Space> {
+    /// Open polylines are treated as if the last vertex were joined to the
+    /// first by a straight line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{algorithms::Area, primitives::Polyline};
+    ///
+    /// let rectangle = Polyline::from_points(vec![
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(4.0, 0.0),
+    ///     Point::new(4.0, 2.0),
+    ///     Point::new(0.0, 2.0),
+    /// ]);
+    ///
+    /// assert_eq!(Area::area(&rectangle), 8.0);
+    /// assert_eq!(Area::centroid(&rectangle), Some(Point::new(2.0, 1.0)));
+    ///
+    /// let moments = rectangle.second_moments().unwrap();
+    /// assert!((moments.ixx - 4.0 * 2.0_f64.powi(3) / 12.0).abs() < 1e-10);
+    /// assert!((moments.iyy - 2.0 * 4.0_f64.powi(3) / 12.0).abs() < 1e-10);
+    /// ```
+    fn area_moments(&self) -> AreaMoments {
+        let point = |p: Point2D<f64, Space>| (p.x, p.y);
+        let mut moments = AreaMoments::default();
+
+        for segment in self.segments() {
+            moments = moments
+                + AreaMoments::triangle(
+                    point(segment.start()),
+                    point(segment.end()),
+                );
+
+            if let Segment::Arc(arc) = segment {
+                moments = moments + AreaMoments::circular_segment(arc);
+            }
+        }
+
+        if !self.is_closed() {
+            if let (Some(first), Some(last)) =
+                (self.vertices().first(), self.vertices().last())
+            {
+                moments = moments
+                    + AreaMoments::triangle(
+                        point(last.point),
+                        point(first.point),
+                    );
+            }
+        }
+
+        moments
+    }
+}
+
+impl<Space> Area<Space> for Region<Space> {
+    /// The outer loop counts as positive and the holes as negative,
+    /// regardless of which direction they go in.
+    fn area_moments(&self) -> AreaMoments {
+        let unsigned = |polyline: &Polyline<Space>| {
+            let moments = polyline.area_moments();
+
+            if moments.area < 0.0 {
+                -moments
+            } else {
+                moments
+            }
+        };
+
+        self.holes()
+            .iter()
+            .fold(unsigned(self.outer()), |moments, hole| {
+                moments - unsigned(hole)
+            })
+    }
+}
+
+impl<Space> Area<Space> for Arc<Space> {
+    /// The area between the [`Arc`] and its chord, which is the whole circle
+    /// when the arc goes all the way around.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{algorithms::Area, primitives::Arc, Angle};
+    /// use std::f64::consts::PI;
+    ///
+    /// let circle = Arc::from_centre_radius(
+    ///     Point::new(1.0, 2.0),
+    ///     3.0,
+    ///     Angle::zero(),
+    ///     Angle::two_pi(),
+    /// );
+    ///
+    /// assert!((circle.area() - 9.0 * PI).abs() < 1e-10);
+    /// let centroid = circle.centroid().unwrap();
+    /// assert!((centroid - Point::new(1.0, 2.0)).length() < 1e-10);
+    /// ```
+    fn area_moments(&self) -> AreaMoments {
+        AreaMoments::circular_segment(*self)
+    }
+}
+
+impl<Space> Area<Space> for Ellipse<Space> {
+    fn area_moments(&self) -> AreaMoments {
+        let (a, b) = (self.semi_major(), self.semi_minor());
+        let (sin, cos) = self.rotation().radians.sin_cos();
+        let (cx, cy) = (self.centre().x, self.centre().y);
+        let area = PI * a * b;
+
+        // along the major and minor axes
+        let uu = PI * a.powi(3) * b / 4.0;
+        let vv = PI * a * b.powi(3) / 4.0;
+
+        AreaMoments {
+            area,
+            x: cx * area,
+            y: cy * area,
+            xx: cos * cos * uu + sin * sin * vv + cx * cx * area,
+            yy: sin * sin * uu + cos * cos * vv + cy * cy * area,
+            xy: sin * cos * (uu - vv) + cx * cy * area,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{primitives::Vertex, Angle};
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn square(x: f64, y: f64, size: f64) -> Polyline<euclid::UnknownUnit> {
+        Polyline::from_points(vec![
+            Point::new(x, y),
+            Point::new(x + size, y),
+            Point::new(x + size, y + size),
+            Point::new(x, y + size),
+        ])
+    }
+
+    #[test]
+    fn agrees_with_the_polyline_methods() {
+        let mut rounded = Polyline::new(
+            vec![
+                Vertex::new(Point::new(0.0, 0.0), 0.0),
+                Vertex::new(Point::new(4.0, 0.0), 0.5),
+                Vertex::new(Point::new(4.0, 3.0), -0.25),
+                Vertex::new(Point::new(0.0, 3.0), 0.0),
+            ],
+            true,
+        );
+        rounded.set_bulge(3, 0.2);
+
+        let moments = rounded.area_moments();
+        let centroid = Area::centroid(&rounded).unwrap();
+
+        assert!(moments.area.approx_eq(&rounded.signed_area()));
+        assert!(centroid.approx_eq(&rounded.centroid().unwrap()));
+    }
+
+    #[test]
+    fn a_unit_disc_built_from_semicircles() {
+        let disc = Polyline::new(
+            vec![
+                Vertex::new(Point::new(-1.0, 0.0), 1.0),
+                Vertex::new(Point::new(1.0, 0.0), 1.0),
+            ],
+            true,
+        );
+
+        let moments = disc.second_moments().unwrap();
+
+        assert!(Area::area(&disc).approx_eq(&PI));
+        assert!(moments.ixx.approx_eq(&(PI / 4.0)));
+        assert!(moments.iyy.approx_eq(&(PI / 4.0)));
+        assert!(moments.ixy.approx_eq_eps(&0.0, &1e-12));
+    }
+
+    #[test]
+    fn holes_are_subtracted() {
+        let region =
+            Region::new(square(0.0, 0.0, 4.0), vec![square(1.0, 1.0, 2.0)]);
+
+        let moments = region.second_moments().unwrap();
+
+        assert!(Area::area(&region).approx_eq(&12.0));
+        assert!(Area::centroid(&region)
+            .unwrap()
+            .approx_eq(&Point::new(2.0, 2.0)));
+        let expected = (4.0_f64.powi(4) - 2.0_f64.powi(4)) / 12.0;
+        assert!(moments.ixx.approx_eq(&expected));
+        assert!(moments.polar().approx_eq(&(2.0 * expected)));
+    }
+
+    #[test]
+    fn rotated_ellipse() {
+        let ellipse =
+            Ellipse::new(Point::new(5.0, 5.0), 2.0, 1.0, Angle::frac_pi_2());
+
+        let moments = ellipse.second_moments().unwrap();
+
+        assert!(ellipse.area().approx_eq(&(2.0 * PI)));
+        // the major axis is vertical
+        assert!(moments.ixx.approx_eq(&(PI * 8.0 / 4.0)));
+        assert!(moments.iyy.approx_eq(&(PI * 2.0 / 4.0)));
+        assert!(moments.ixy.approx_eq_eps(&0.0, &1e-9));
+    }
+}
@@ -0,0 +1,175 @@
+//! Laying out copies of an object in a regular pattern.
+//!
+//! Each function returns every item in the pattern, starting with an
+//! untouched copy of the original, so callers who already have the original
+//! can skip the first item.
+
+use crate::{
+    algorithms::{Rotate, Translate},
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+use std::f64::consts::PI;
+
+/// Copy `item` into a grid with `rows` rows and `columns` columns.
+///
+/// The columns are `spacing.x` apart and the rows are `spacing.y` apart
+/// (negative spacings grow the grid to the left or downwards). Items are
+/// returned row by row.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// # type Vector = euclid::default::Vector2D<f64>;
+/// use arcs_core::algorithms::array;
+///
+/// let got = array::rectangular(&Point::new(1.0, 1.0), 2, 3, Vector::new(10.0, 5.0));
+///
+/// assert_eq!(got.len(), 6);
+/// assert_eq!(got[0], Point::new(1.0, 1.0));
+/// assert_eq!(got[2], Point::new(21.0, 1.0));
+/// assert_eq!(got[5], Point::new(21.0, 6.0));
+/// ```
+pub fn rectangular<S, T>(
+    item: &T,
+    rows: usize,
+    columns: usize,
+    spacing: Vector2D<f64, S>,
+) -> Vec<T>
+where
+    T: Translate<S> + Clone,
+{
+    let mut items = Vec::with_capacity(rows * columns);
+
+    for row in 0..rows {
+        for column in 0..columns {
+            let displacement = Vector2D::new(
+                spacing.x * column as f64,
+                spacing.y * row as f64,
+            );
+            items.push(item.translated(displacement));
+        }
+    }
+
+    items
+}
+
+/// Copy `item` `count` times around `centre`, spread evenly over `span`
+/// (anticlockwise when positive).
+///
+/// A span of a full circle places the last item one step before the first
+/// rather than on top of it. When `rotate_items` is `false` the copies are
+/// moved so `base_point` follows the circle, but they keep the original's
+/// orientation.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::array, primitives::Line, Angle};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let spoke = Line::new(Point::new(1.0, 0.0), Point::new(2.0, 0.0));
+///
+/// let got =
+///     array::polar(&spoke, spoke.start, Point::zero(), 4, Angle::two_pi(), true);
+///
+/// assert_eq!(got.len(), 4);
+/// assert!(got[1].start.approx_eq(&Point::new(0.0, 1.0)));
+/// assert!(got[1].end.approx_eq(&Point::new(0.0, 2.0)));
+/// assert!(got[3].end.approx_eq(&Point::new(0.0, -2.0)));
+/// ```
+pub fn polar<S, T>(
+    item: &T,
+    base_point: Point2D<f64, S>,
+    centre: Point2D<f64, S>,
+    count: usize,
+    span: Angle,
+    rotate_items: bool,
+) -> Vec<T>
+where
+    T: Rotate<S> + Translate<S> + Clone,
+{
+    let is_full_circle = span.radians.abs() >= 2.0 * PI - 1e-9;
+    let gaps = if is_full_circle || count < 2 {
+        count
+    } else {
+        count - 1
+    };
+    let step = if gaps == 0 {
+        Angle::zero()
+    } else {
+        span / gaps as f64
+    };
+
+    (0..count)
+        .map(|i| {
+            let angle = step * i as f64;
+
+            if rotate_items {
+                item.rotated(centre, angle)
+            } else {
+                let moved_to = base_point.rotated(centre, angle);
+                item.translated(moved_to - base_point)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Line;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+    type Vector = euclid::default::Vector2D<f64>;
+
+    #[test]
+    fn grids_can_grow_downwards() {
+        let line = Line::new(Point::zero(), Point::new(1.0, 0.0));
+
+        let got = rectangular(&line, 3, 1, Vector::new(2.0, -4.0));
+
+        assert_eq!(got.len(), 3);
+        assert_eq!(got[2].start, Point::new(0.0, -8.0));
+        assert_eq!(got[2].end, Point::new(1.0, -8.0));
+    }
+
+    #[test]
+    fn empty_grids() {
+        assert!(
+            rectangular(&Point::zero(), 0, 5, Vector::new(1.0, 1.0)).is_empty()
+        );
+    }
+
+    #[test]
+    fn partial_spans_include_both_ends() {
+        let got = polar(
+            &Point::new(1.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::zero(),
+            3,
+            Angle::pi(),
+            true,
+        );
+
+        assert_eq!(got.len(), 3);
+        assert!(got[1].approx_eq(&Point::new(0.0, 1.0)));
+        assert!(got[2].approx_eq(&Point::new(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn unrotated_items_keep_their_orientation() {
+        let line = Line::new(Point::new(2.0, 0.0), Point::new(3.0, 0.0));
+
+        let got =
+            polar(&line, line.start, Point::zero(), 4, Angle::two_pi(), false);
+
+        assert!(got[1].start.approx_eq(&Point::new(0.0, 2.0)));
+        assert!(got[1].end.approx_eq(&Point::new(1.0, 2.0)));
+        assert!(got[2].start.approx_eq(&Point::new(-2.0, 0.0)));
+        assert!(got[2].end.approx_eq(&Point::new(-1.0, 0.0)));
+    }
+}
@@ -0,0 +1,412 @@
+//! Boolean operations (union, intersection, difference and XOR) on closed
+//! [`Region`]s.
+//!
+//! Closed polylines can be used by wrapping them in [`Region::solid()`].
+
+use crate::{
+    algorithms::{
+        fillet::{direction_at_end, direction_at_start},
+        join::reversed,
+        offset::to_polyline,
+        split::{Parameterised, PARAMETER_TOLERANCE},
+        Intersect,
+    },
+    primitives::{Polyline, Region, Segment},
+};
+use euclid::{Point2D, Vector2D};
+
+/// Segment endpoints closer than this are considered to be joined when
+/// assembling the result's loops.
+const JOIN_TOLERANCE: f64 = 1e-6;
+
+/// How far either side of a boundary to look when deciding which sides of
+/// it are filled.
+const PROBE_DISTANCE: f64 = 1e-6;
+
+/// The different ways two [`Region`]s can be combined.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Everything covered by either region.
+    Union,
+    /// Only the areas covered by both regions.
+    Intersection,
+    /// The first region with the second one cut out of it.
+    Difference,
+    /// The areas covered by exactly one of the regions.
+    Xor,
+}
+
+impl Operation {
+    /// Is a point covered by the result, given whether it is in the first
+    /// and second regions?
+    fn includes(self, in_first: bool, in_second: bool) -> bool {
+        match self {
+            Operation::Union => in_first || in_second,
+            Operation::Intersection => in_first && in_second,
+            Operation::Difference => in_first && !in_second,
+            Operation::Xor => in_first != in_second,
+        }
+    }
+}
+
+/// Everything covered by either region.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::boolean,
+///     primitives::{Polyline, Region},
+/// };
+///
+/// let square = |x: f64, y: f64| {
+///     Region::solid(Polyline::from_points(vec![
+///         Point::new(x, y),
+///         Point::new(x + 2.0, y),
+///         Point::new(x + 2.0, y + 2.0),
+///         Point::new(x, y + 2.0),
+///     ]))
+/// };
+///
+/// let got = boolean::union(&square(0.0, 0.0), &square(1.0, 1.0));
+///
+/// assert_eq!(got.len(), 1);
+/// assert_eq!(got[0].area(), 7.0);
+/// ```
+pub fn union<S>(first: &Region<S>, second: &Region<S>) -> Vec<Region<S>> {
+    perform(first, second, Operation::Union)
+}
+
+/// The areas covered by both regions.
+pub fn intersection<S>(
+    first: &Region<S>,
+    second: &Region<S>,
+) -> Vec<Region<S>> {
+    perform(first, second, Operation::Intersection)
+}
+
+/// The `first` region with the `second` one cut out of it.
+pub fn difference<S>(first: &Region<S>, second: &Region<S>) -> Vec<Region<S>> {
+    perform(first, second, Operation::Difference)
+}
+
+/// The areas covered by exactly one of the regions.
+pub fn xor<S>(first: &Region<S>, second: &Region<S>) -> Vec<Region<S>> {
+    perform(first, second, Operation::Xor)
+}
+
+/// Combine two [`Region`]s, returning the separate pieces which make up the
+/// result.
+///
+/// Each region's boundary shouldn't cross itself, which can be checked using
+/// [`SelfIntersect::is_simple()`][crate::algorithms::SelfIntersect::is_simple].
+///
+/// Both regions are cut up wherever their boundaries cross, and each piece
+/// of boundary is kept if the result is filled on one side of it but not
+/// the other. That means edges which the regions share are handled the same
+/// way as any other edge, and holes can be created (or removed) as needed.
+pub fn perform<S>(
+    first: &Region<S>,
+    second: &Region<S>,
+    operation: Operation,
+) -> Vec<Region<S>> {
+    let first_segments = boundary(first);
+    let second_segments = boundary(second);
+    let filled = |point| {
+        operation.includes(first.contains(point), second.contains(point))
+    };
+
+    let mut edges = Vec::new();
+
+    for &(segments, others, is_second) in &[
+        (&first_segments, &second_segments, false),
+        (&second_segments, &first_segments, true),
+    ] {
+        for piece in segments.iter().flat_map(|&s| split_segment(s, others)) {
+            let (left, right) = match probes(piece) {
+                Some(probes) => probes,
+                None => continue,
+            };
+
+            // shared edges appear in both regions, so only keep one copy
+            if is_second && first.contains(left) != first.contains(right) {
+                continue;
+            }
+
+            match (filled(left), filled(right)) {
+                (true, false) => edges.push(piece),
+                (false, true) => edges.push(reversed(piece)),
+                _ => {},
+            }
+        }
+    }
+
+    assemble(edges)
+}
+
+fn boundary<S>(region: &Region<S>) -> Vec<Segment<S>> {
+    region.loops().flat_map(Polyline::segments).collect()
+}
+
+/// Cut a segment wherever it crosses one of the `others`.
+fn split_segment<S>(
+    segment: Segment<S>,
+    others: &[Segment<S>],
+) -> Vec<Segment<S>> {
+    let mut cuts: Vec<f64> = std::slice::from_ref(&segment)
+        .intersect(others)
+        .into_iter()
+        .map(|point| parameter_of(segment, point))
+        .filter(|&t| t > PARAMETER_TOLERANCE && t < 1.0 - PARAMETER_TOLERANCE)
+        .collect();
+    cuts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    cuts.dedup_by(|a, b| (*a - *b).abs() <= PARAMETER_TOLERANCE);
+
+    let mut boundaries = vec![0.0];
+    boundaries.extend(cuts);
+    boundaries.push(1.0);
+
+    boundaries
+        .windows(2)
+        .map(|pair| match segment {
+            Segment::Line(line) => {
+                Segment::Line(line.portion(pair[0], pair[1]))
+            },
+            Segment::Arc(arc) => Segment::Arc(arc.portion(pair[0], pair[1])),
+        })
+        .collect()
+}
+
+fn parameter_of<S>(segment: Segment<S>, point: Point2D<f64, S>) -> f64 {
+    match segment {
+        Segment::Line(line) => line.parameter_of(point),
+        Segment::Arc(arc) => arc.parameter_of(point),
+    }
+}
+
+/// Points just to the left and right of a segment's midpoint, or [`None`]
+/// if the segment is too small to have sides.
+fn probes<S>(
+    segment: Segment<S>,
+) -> Option<(Point2D<f64, S>, Point2D<f64, S>)> {
+    let (midpoint, direction) = match segment {
+        Segment::Line(line) => {
+            (line.start.lerp(line.end, 0.5), line.displacement())
+        },
+        Segment::Arc(arc) => {
+            let midpoint = arc.point_at_parameter(0.5);
+            let radial = midpoint - arc.centre();
+            let tangent = Vector2D::new(-radial.y, radial.x);
+            let direction = if arc.is_anticlockwise() {
+                tangent
+            } else {
+                -tangent
+            };
+
+            (midpoint, direction)
+        },
+    };
+
+    let chord = (segment.end() - segment.start()).length();
+    if chord <= JOIN_TOLERANCE || direction.length() == 0.0 {
+        return None;
+    }
+
+    let direction = direction.normalize();
+    let normal = Vector2D::new(-direction.y, direction.x)
+        * PROBE_DISTANCE.min(chord * 1e-3);
+
+    Some((midpoint + normal, midpoint - normal))
+}
+
+/// Chain the edges of the result together into loops, then work out which
+/// loops are holes in which outer loops.
+///
+/// Edges are oriented so the filled area is always on their left, making
+/// outer loops anticlockwise and holes clockwise.
+fn assemble<S>(mut edges: Vec<Segment<S>>) -> Vec<Region<S>> {
+    let mut loops = Vec::new();
+
+    while let Some(first) = edges.pop() {
+        let start = first.start();
+        let mut chain = vec![first];
+
+        loop {
+            let last = *chain.last().unwrap();
+            let end = last.end();
+            if chain.len() > 1 && (end - start).length() <= JOIN_TOLERANCE {
+                break;
+            }
+
+            // where several loops touch at a point, turning as far left as
+            // possible keeps them separate
+            let incoming = direction_at_end(last);
+            let turn = |edge: &Segment<S>| {
+                let outgoing = direction_at_start(*edge);
+                incoming.cross(outgoing).atan2(incoming.dot(outgoing))
+            };
+            let next = edges
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| (e.start() - end).length() <= JOIN_TOLERANCE)
+                .max_by(|(_, a), (_, b)| {
+                    turn(a)
+                        .partial_cmp(&turn(b))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .map(|(index, _)| index);
+
+            match next {
+                Some(index) => chain.push(edges.swap_remove(index)),
+                // a broken chain can't enclose anything, so drop it
+                None => {
+                    chain.clear();
+                    break;
+                },
+            }
+        }
+
+        if let Some(polyline) = to_polyline(&chain, true) {
+            loops.push(polyline);
+        }
+    }
+
+    let (outers, holes): (Vec<_>, Vec<_>) =
+        loops.into_iter().partition(|l| l.signed_area() > 0.0);
+    let mut regions: Vec<Region<S>> =
+        outers.into_iter().map(Region::solid).collect();
+
+    for hole in holes {
+        // the left of a hole's boundary is inside the region containing it
+        let material = hole.segments().find_map(probes).map(|(left, _)| left);
+
+        let parent = regions
+            .iter_mut()
+            .filter(|region| {
+                material.map_or(false, |point| region.outer().contains(point))
+            })
+            .min_by(|a, b| {
+                a.outer()
+                    .area()
+                    .partial_cmp(&b.outer().area())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+        if let Some(parent) = parent {
+            parent.add_hole(hole);
+        }
+    }
+
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Vertex;
+    use euclid::approxeq::ApproxEq;
+    use std::f64::consts::PI;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn square(x: f64, y: f64, size: f64) -> Region<euclid::UnknownUnit> {
+        Region::solid(Polyline::from_points(vec![
+            Point::new(x, y),
+            Point::new(x + size, y),
+            Point::new(x + size, y + size),
+            Point::new(x, y + size),
+        ]))
+    }
+
+    fn circle(x: f64, radius: f64) -> Region<euclid::UnknownUnit> {
+        Region::solid(Polyline::new(
+            vec![
+                Vertex::new(Point::new(x - radius, 0.0), 1.0),
+                Vertex::new(Point::new(x + radius, 0.0), 1.0),
+            ],
+            true,
+        ))
+    }
+
+    fn total_area(regions: &[Region<euclid::UnknownUnit>]) -> f64 {
+        regions.iter().map(Region::area).sum()
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let a = square(0.0, 0.0, 2.0);
+        let b = square(1.0, 1.0, 2.0);
+
+        let inside = intersection(&a, &b);
+        assert_eq!(inside.len(), 1);
+        assert!(inside[0].area().approx_eq(&1.0));
+
+        let cut = difference(&a, &b);
+        assert_eq!(cut.len(), 1);
+        assert!(cut[0].area().approx_eq(&3.0));
+
+        let either = xor(&a, &b);
+        assert_eq!(either.len(), 2);
+        assert!(total_area(&either).approx_eq(&6.0));
+    }
+
+    #[test]
+    fn squares_sharing_an_edge_are_merged() {
+        let a = square(0.0, 0.0, 2.0);
+        let b = square(2.0, 0.0, 2.0);
+
+        let got = union(&a, &b);
+
+        assert_eq!(got.len(), 1);
+        assert!(got[0].area().approx_eq(&8.0));
+        assert!(got[0].holes().is_empty());
+        assert!(intersection(&a, &b).is_empty());
+        assert!(total_area(&difference(&a, &b)).approx_eq(&4.0));
+    }
+
+    #[test]
+    fn cutting_out_the_middle_leaves_a_hole() {
+        let outer = square(0.0, 0.0, 4.0);
+        let inner = square(1.0, 1.0, 2.0);
+
+        let got = difference(&outer, &inner);
+
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].holes().len(), 1);
+        assert!(got[0].area().approx_eq(&12.0));
+        assert!(!got[0].contains(Point::new(2.0, 2.0)));
+
+        // and filling the hole back in removes it again
+        let filled = union(&got[0], &inner);
+        assert_eq!(filled.len(), 1);
+        assert!(filled[0].holes().is_empty());
+        assert!(filled[0].area().approx_eq(&16.0));
+    }
+
+    #[test]
+    fn intersecting_circles_keeps_the_arcs() {
+        let a = circle(0.0, 1.0);
+        let b = circle(1.0, 1.0);
+
+        let got = intersection(&a, &b);
+
+        // the lens between two unit circles one unit apart
+        let expected = 2.0 * PI / 3.0 - 3.0_f64.sqrt() / 2.0;
+        assert_eq!(got.len(), 1);
+        assert!(got[0].area().approx_eq_eps(&expected, &1e-9));
+        assert!(got[0].outer().vertices().iter().all(|v| !v.is_straight()));
+    }
+
+    #[test]
+    fn disjoint_regions() {
+        let a = square(0.0, 0.0, 1.0);
+        let b = square(5.0, 5.0, 1.0);
+
+        assert_eq!(union(&a, &b).len(), 2);
+        assert!(intersection(&a, &b).is_empty());
+        let got = difference(&a, &b);
+        assert_eq!(got.len(), 1);
+        assert!(got[0].area().approx_eq(&1.0));
+    }
+}
@@ -1,8 +1,12 @@
 use crate::{
-    primitives::{Arc, Line},
+    algorithms::Approximate,
+    primitives::{
+        Arc, Circle, CubicBezier, Ellipse, EllipticalArc, Line, Polyline,
+        QuadraticBezier, Ray, Rectangle, Region, Segment, Spline, XLine,
+    },
     BoundingBox,
 };
-use euclid::{Angle, Point2D};
+use euclid::{Angle, Point2D, Vector2D};
 
 /// Calculate an axis-aligned bounding box around the item.
 pub trait Bounded<S> {
@@ -29,36 +33,265 @@ impl<S> Bounded<S> for Line<S> {
 }
 
 impl<S> Bounded<S> for Arc<S> {
+    /// The tight bounds around the [`Arc`], only including the circle's
+    /// extremes at 0°, 90°, 180°, and 270° when the arc passes through them.
     fn bounding_box(&self) -> BoundingBox<S> {
         let (x, y) = self.centre().to_tuple();
         let r = self.radius();
 
-        let mut bounds = BoundingBox::new(self.start(), self.end());
+        let extremes = [
+            (Angle::zero(), Point2D::new(x + r, y)),
+            (Angle::frac_pi_2(), Point2D::new(x, y + r)),
+            (Angle::pi(), Point2D::new(x - r, y)),
+            (Angle::pi() + Angle::frac_pi_2(), Point2D::new(x, y - r)),
+        ];
 
-        if self.contains_angle(Angle::zero()) {
-            let right = Point2D::new(x + r, y);
-            bounds = BoundingBox::new(bounds.bottom_left(), right);
+        let (start, end) = (self.start(), self.end());
+        let mut bottom_left = start.min(end);
+        let mut top_right = start.max(end);
+
+        for &(angle, point) in &extremes {
+            if self.contains_angle(angle) {
+                bottom_left = bottom_left.min(point);
+                top_right = top_right.max(point);
+            }
         }
-        if self.contains_angle(Angle::frac_pi_2()) {
-            let top = Point2D::new(x, y + r);
-            bounds = BoundingBox::new(bounds.bottom_left(), top);
+
+        BoundingBox::new_unchecked(bottom_left, top_right)
+    }
+}
+
+impl<S> Bounded<S> for Circle<S> {
+    fn bounding_box(&self) -> BoundingBox<S> {
+        let half_size = Vector2D::new(self.radius, self.radius);
+
+        BoundingBox::new_unchecked(
+            self.centre - half_size,
+            self.centre + half_size,
+        )
+    }
+}
+
+impl<S> Bounded<S> for Ellipse<S> {
+    fn bounding_box(&self) -> BoundingBox<S> {
+        // the extremes of an ellipse are where the derivatives of its
+        // parametric equations are zero, which gives us half-widths of
+        //   sqrt(a² cos² θ + b² sin² θ) and sqrt(a² sin² θ + b² cos² θ)
+        let (sin, cos) = self.rotation().sin_cos();
+        let a = self.semi_major();
+        let b = self.semi_minor();
+
+        let half_width = (a * a * cos * cos + b * b * sin * sin).sqrt();
+        let half_height = (a * a * sin * sin + b * b * cos * cos).sqrt();
+        let centre = self.centre();
+
+        BoundingBox::new(
+            Point2D::new(centre.x - half_width, centre.y - half_height),
+            Point2D::new(centre.x + half_width, centre.y + half_height),
+        )
+    }
+}
+
+impl<S> Bounded<S> for EllipticalArc<S> {
+    fn bounding_box(&self) -> BoundingBox<S> {
+        let ellipse = self.ellipse();
+        let (sin, cos) = ellipse.rotation().sin_cos();
+        let a = ellipse.semi_major();
+        let b = ellipse.semi_minor();
+
+        // parametric angles where dx/dt = 0 and dy/dt = 0 respectively
+        let horizontal_extreme = Angle::radians((-b * sin).atan2(a * cos));
+        let vertical_extreme = Angle::radians((b * cos).atan2(a * sin));
+
+        let extremes = [
+            horizontal_extreme,
+            horizontal_extreme + Angle::pi(),
+            vertical_extreme,
+            vertical_extreme + Angle::pi(),
+        ];
+
+        let (start, end) = (self.start(), self.end());
+        let mut bottom_left = start.min(end);
+        let mut top_right = start.max(end);
+
+        for &angle in &extremes {
+            if self.contains_angle(angle) {
+                let point = ellipse.point_at(angle);
+                bottom_left = bottom_left.min(point);
+                top_right = top_right.max(point);
+            }
         }
-        if self.contains_angle(Angle::pi()) {
-            let left = Point2D::new(x - r, y);
-            bounds = BoundingBox::new(bounds.top_right(), left);
+
+        BoundingBox::new_unchecked(bottom_left, top_right)
+    }
+}
+
+impl<S> Bounded<S> for Segment<S> {
+    fn bounding_box(&self) -> BoundingBox<S> {
+        match self {
+            Segment::Line(line) => line.bounding_box(),
+            Segment::Arc(arc) => arc.bounding_box(),
         }
-        if self.contains_angle(Angle::pi() + Angle::frac_pi_2()) {
-            let bottom = Point2D::new(x, y - r);
-            bounds = BoundingBox::new(bounds.top_right(), bottom);
+    }
+}
+
+impl<S> Bounded<S> for Polyline<S> {
+    /// Calculate the [`BoundingBox`] around the [`Polyline`]'s segments.
+    ///
+    /// # Panics
+    ///
+    /// An empty [`Polyline`] doesn't have a location, so this will panic.
+    fn bounding_box(&self) -> BoundingBox<S> {
+        assert!(!self.is_empty(), "An empty polyline has no bounding box");
+        let first = self.vertices()[0].point;
+
+        let (bottom_left, top_right) = self.segments().fold(
+            (first, first),
+            |(bottom_left, top_right), segment| {
+                let bounds = segment.bounding_box();
+                (
+                    bottom_left.min(bounds.bottom_left()),
+                    top_right.max(bounds.top_right()),
+                )
+            },
+        );
+
+        BoundingBox::new_unchecked(bottom_left, top_right)
+    }
+}
+
+impl<S> Bounded<S> for CubicBezier<S> {
+    fn bounding_box(&self) -> BoundingBox<S> {
+        let (bottom_left, top_right) =
+            self.extremes().map(|t| self.point_at(t)).fold(
+                (self.start.min(self.end), self.start.max(self.end)),
+                |(bottom_left, top_right), point| {
+                    (bottom_left.min(point), top_right.max(point))
+                },
+            );
+
+        BoundingBox::new_unchecked(bottom_left, top_right)
+    }
+}
+
+impl<S> Bounded<S> for QuadraticBezier<S> {
+    fn bounding_box(&self) -> BoundingBox<S> {
+        let (bottom_left, top_right) =
+            self.extremes().map(|t| self.point_at(t)).fold(
+                (self.start.min(self.end), self.start.max(self.end)),
+                |(bottom_left, top_right), point| {
+                    (bottom_left.min(point), top_right.max(point))
+                },
+            );
+
+        BoundingBox::new_unchecked(bottom_left, top_right)
+    }
+}
+
+impl<S> Bounded<S> for Spline<S> {
+    /// Get the [`BoundingBox`] around the [`Spline`].
+    ///
+    /// Because all weights are positive, the curve is guaranteed to lie within
+    /// the convex hull of its control points. That hull is shrunk down to the
+    /// bounds of a fine approximation of the curve, padded by the
+    /// approximation's tolerance to cover any bulges between samples.
+    fn bounding_box(&self) -> BoundingBox<S> {
+        /// How accurate the bounds are, relative to the control polygon.
+        const RELATIVE_TOLERANCE: f64 = 1e-4;
+
+        let points = self.control_points();
+        let (hull_min, hull_max) = points.iter().fold(
+            (points[0], points[0]),
+            |(bottom_left, top_right), &point| {
+                (bottom_left.min(point), top_right.max(point))
+            },
+        );
+
+        let tolerance = (hull_max - hull_min).length() * RELATIVE_TOLERANCE;
+        if tolerance == 0.0 {
+            return BoundingBox::new_unchecked(hull_min, hull_max);
         }
 
-        bounds
+        let first = self.start();
+        let (bottom_left, top_right) = self.approximate(tolerance).fold(
+            (first, first),
+            |(bottom_left, top_right), point| {
+                (bottom_left.min(point), top_right.max(point))
+            },
+        );
+        let padding = Vector2D::new(tolerance, tolerance);
+
+        BoundingBox::new_unchecked(
+            (bottom_left - padding).max(hull_min),
+            (top_right + padding).min(hull_max),
+        )
+    }
+}
+
+impl<S> Bounded<S> for Rectangle<S> {
+    fn bounding_box(&self) -> BoundingBox<S> {
+        let [a, b, c, d] = self.corners();
+
+        BoundingBox::new_unchecked(
+            a.min(b).min(c).min(d),
+            a.max(b).max(c).max(d),
+        )
+    }
+}
+
+impl<S> Bounded<S> for Region<S> {
+    /// The holes are inside the outer loop, so they don't affect the
+    /// bounds.
+    fn bounding_box(&self) -> BoundingBox<S> { self.outer().bounding_box() }
+}
+
+impl<S> Bounded<S> for XLine<S> {
+    /// An [`XLine`] goes on forever, so its bounds will be infinite along
+    /// any axis it isn't perpendicular to.
+    fn bounding_box(&self) -> BoundingBox<S> {
+        let extent = |base: f64, direction: f64| {
+            if direction == 0.0 {
+                (base, base)
+            } else {
+                (std::f64::NEG_INFINITY, std::f64::INFINITY)
+            }
+        };
+        let (min_x, max_x) = extent(self.base_point.x, self.direction.x);
+        let (min_y, max_y) = extent(self.base_point.y, self.direction.y);
+
+        BoundingBox::new_unchecked(
+            Point2D::new(min_x, min_y),
+            Point2D::new(max_x, max_y),
+        )
+    }
+}
+
+impl<S> Bounded<S> for Ray<S> {
+    /// A [`Ray`]'s bounds will be infinite in any direction it is heading.
+    fn bounding_box(&self) -> BoundingBox<S> {
+        let extent = |start: f64, direction: f64| {
+            if direction > 0.0 {
+                (start, std::f64::INFINITY)
+            } else if direction < 0.0 {
+                (std::f64::NEG_INFINITY, start)
+            } else {
+                (start, start)
+            }
+        };
+        let (min_x, max_x) = extent(self.start.x, self.direction.x);
+        let (min_y, max_y) = extent(self.start.y, self.direction.y);
+
+        BoundingBox::new_unchecked(
+            Point2D::new(min_x, min_y),
+            Point2D::new(max_x, max_y),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::primitives::Vertex;
     use euclid::default::{Length, Point2D};
 
     #[test]
@@ -74,4 +307,183 @@ mod tests {
         assert_eq!(bounds.bottom_left(), start);
         assert_eq!(bounds.top_right(), end);
     }
+
+    #[test]
+    fn bounding_box_around_rotated_ellipse() {
+        let centre = Point2D::new(1.0, 2.0);
+        let ellipse = Ellipse::new(centre, 10.0, 5.0, Angle::frac_pi_2());
+
+        let bounds = ellipse.bounding_box();
+
+        assert_eq!(bounds.bottom_left(), Point2D::new(-4.0, -8.0));
+        assert_eq!(bounds.top_right(), Point2D::new(6.0, 12.0));
+    }
+
+    #[test]
+    fn bounding_box_around_elliptical_arc() {
+        let ellipse = Ellipse::new(Point2D::zero(), 10.0, 5.0, Angle::zero());
+        let arc = EllipticalArc::new(
+            ellipse,
+            -Angle::frac_pi_4(),
+            Angle::frac_pi_2(),
+        );
+
+        let bounds = arc.bounding_box();
+
+        assert_eq!(bounds.bottom_left(), arc.start());
+        assert_eq!(bounds.top_right(), Point2D::new(10.0, arc.end().y));
+    }
+
+    #[test]
+    fn bounding_box_around_polyline_with_bulges() {
+        let polyline = Polyline::new(
+            vec![
+                Vertex::new(Point2D::new(0.0, 0.0), 1.0),
+                Vertex::straight(Point2D::new(2.0, 0.0)),
+                Vertex::straight(Point2D::new(2.0, 3.0)),
+            ],
+            false,
+        );
+
+        let bounds = polyline.bounding_box();
+
+        assert_eq!(bounds.min_y(), -1.0);
+        assert_eq!(bounds.max_y(), 3.0);
+        assert_eq!(bounds.min_x(), 0.0);
+        assert_eq!(bounds.max_x(), 2.0);
+    }
+
+    #[test]
+    fn bounding_box_around_cubic_bezier() {
+        let curve = CubicBezier::new(
+            Point2D::new(0.0, 0.0),
+            Point2D::new(0.0, 1.0),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(1.0, 0.0),
+        );
+
+        let bounds = curve.bounding_box();
+
+        assert_eq!(bounds.bottom_left(), Point2D::new(0.0, 0.0));
+        assert_eq!(bounds.top_right(), Point2D::new(1.0, 0.75));
+    }
+
+    #[test]
+    fn bounding_box_around_quadratic_bezier() {
+        let curve = QuadraticBezier::new(
+            Point2D::new(0.0, 0.0),
+            Point2D::new(1.0, 2.0),
+            Point2D::new(2.0, 0.0),
+        );
+
+        let bounds = curve.bounding_box();
+
+        assert_eq!(bounds.bottom_left(), Point2D::new(0.0, 0.0));
+        assert_eq!(bounds.top_right(), Point2D::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn rays_have_infinite_bounds_in_the_direction_of_travel() {
+        let ray = Ray::new(Point2D::new(1.0, 2.0), euclid::vec2(-1.0, 0.0));
+
+        let bounds = ray.bounding_box();
+
+        assert_eq!(bounds.min_x(), std::f64::NEG_INFINITY);
+        assert_eq!(bounds.max_x(), 1.0);
+        assert_eq!(bounds.min_y(), 2.0);
+        assert_eq!(bounds.max_y(), 2.0);
+    }
+
+    #[test]
+    fn arc_bounds_only_include_the_quadrants_it_crosses() {
+        // from 45° to 135°, passing through the top of the circle
+        let arc = Arc::from_centre_radius(
+            Point2D::new(1.0, 1.0),
+            2.0,
+            Angle::frac_pi_4(),
+            Angle::frac_pi_2(),
+        );
+
+        let bounds = arc.bounding_box();
+
+        assert_eq!(bounds.max_y(), 3.0);
+        assert_eq!(bounds.min_y(), arc.start().y);
+        assert_eq!(bounds.min_x(), arc.end().x);
+        assert_eq!(bounds.max_x(), arc.start().x);
+    }
+
+    #[test]
+    fn arc_bounds_when_wrapping_past_zero() {
+        let arc = Arc::from_centre_radius(
+            Point2D::zero(),
+            1.0,
+            -Angle::frac_pi_4(),
+            Angle::frac_pi_2(),
+        );
+
+        let bounds = arc.bounding_box();
+
+        assert_eq!(bounds.max_x(), 1.0);
+        assert_eq!(bounds.min_x(), arc.start().x);
+        assert_eq!(bounds.min_y(), arc.start().y);
+        assert_eq!(bounds.max_y(), arc.end().y);
+    }
+
+    #[test]
+    fn clockwise_arc_bounds() {
+        // from 90° clockwise to 270°, passing through 0°
+        let arc = Arc::from_centre_radius(
+            Point2D::zero(),
+            1.0,
+            Angle::frac_pi_2(),
+            -Angle::pi(),
+        );
+
+        let bounds = arc.bounding_box();
+
+        assert!(bounds.min_x().abs() < 1e-10);
+        assert_eq!(bounds.max_x(), 1.0);
+        assert_eq!(bounds.min_y(), -1.0);
+        assert_eq!(bounds.max_y(), 1.0);
+    }
+
+    #[test]
+    fn full_circle_bounds() {
+        let centre = Point2D::new(1.0, 2.0);
+        let arc = Arc::from_centre_radius(
+            centre,
+            3.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        );
+        let circle = Circle::new(centre, 3.0);
+
+        let expected =
+            BoundingBox::new(Point2D::new(-2.0, -1.0), Point2D::new(4.0, 5.0));
+
+        assert_eq!(arc.bounding_box(), expected);
+        assert_eq!(circle.bounding_box(), expected);
+    }
+
+    #[test]
+    fn spline_bounds_are_tighter_than_the_control_points() {
+        // a clamped cubic with 4 control points is just a Bézier curve
+        let spline = Spline::clamped(
+            3,
+            vec![
+                Point2D::new(0.0, 0.0),
+                Point2D::new(0.0, 1.0),
+                Point2D::new(1.0, 1.0),
+                Point2D::new(1.0, 0.0),
+            ],
+        )
+        .unwrap();
+
+        let bounds = spline.bounding_box();
+
+        assert_eq!(bounds.bottom_left(), Point2D::new(0.0, 0.0));
+        assert_eq!(bounds.max_x(), 1.0);
+        assert!(bounds.max_y() >= 0.75);
+        assert!(bounds.max_y() < 0.751);
+    }
 }
@@ -0,0 +1,160 @@
+use crate::primitives::{Line, XLine};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The result of cutting off the corner between two lines (see
+/// [`chamfer()`]).
+#[derive(Debug, PartialEq)]
+pub struct Chamfer<S> {
+    /// The new line cutting across the corner.
+    pub line: Line<S>,
+    /// The first line, with its end moved to the start of the
+    /// [`Chamfer::line`].
+    pub first: Line<S>,
+    /// The second line, with its start moved to the end of the
+    /// [`Chamfer::line`].
+    pub second: Line<S>,
+}
+
+impl<S> Copy for Chamfer<S> {}
+
+impl<S> Clone for Chamfer<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Cut off the corner where `first` ends and `second` starts.
+///
+/// The corner is wherever the two lines would meet if they were extended,
+/// and the chamfer starts `distance_a` back along the first line and ends
+/// `distance_b` along the second line. Setting both distances to zero
+/// extends (or trims) the lines so they meet at the corner.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::chamfer, primitives::Line};
+///
+/// let horizontal = Line::new(Point::new(-10.0, 0.0), Point::new(0.0, 0.0));
+/// let vertical = Line::new(Point::new(0.0, 0.0), Point::new(0.0, 10.0));
+///
+/// let got = chamfer(horizontal, vertical, 2.0, 3.0).unwrap();
+///
+/// assert_eq!(
+///     got.line,
+///     Line::new(Point::new(-2.0, 0.0), Point::new(0.0, 3.0))
+/// );
+/// assert_eq!(got.first.end, got.line.start);
+/// assert_eq!(got.second.start, got.line.end);
+/// ```
+pub fn chamfer<S>(
+    first: Line<S>,
+    second: Line<S>,
+    distance_a: f64,
+    distance_b: f64,
+) -> Result<Chamfer<S>, ChamferError> {
+    let valid = |distance: f64| !distance.is_nan() && distance >= 0.0;
+    if !valid(distance_a) || !valid(distance_b) {
+        return Err(ChamferError::InvalidDistance);
+    }
+
+    let corner = XLine::new(first.start, first.displacement())
+        .intersect_xline(XLine::new(second.start, second.displacement()))
+        .ok_or(ChamferError::Parallel)?;
+
+    let start = corner - first.direction() * distance_a;
+    let end = corner + second.direction() * distance_b;
+
+    // make sure we haven't cut away a line entirely
+    let first_remaining = (start - first.start).dot(first.displacement());
+    let second_remaining = (second.end - end).dot(second.displacement());
+    if first_remaining <= 0.0 || second_remaining <= 0.0 {
+        return Err(ChamferError::TooLarge);
+    }
+
+    Ok(Chamfer {
+        line: Line::new(start, end),
+        first: Line::new(first.start, start),
+        second: Line::new(end, second.end),
+    })
+}
+
+/// The reasons a [`chamfer()`] may fail.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ChamferError {
+    /// The chamfer distances can't be negative.
+    InvalidDistance,
+    /// The lines are parallel, so they don't form a corner.
+    Parallel,
+    /// The chamfer would cut away all of one of the lines.
+    TooLarge,
+}
+
+impl Display for ChamferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ChamferError::InvalidDistance => {
+                write!(f, "Chamfer distances can't be negative")
+            },
+            ChamferError::Parallel => write!(f, "The lines are parallel"),
+            ChamferError::TooLarge => {
+                write!(f, "The chamfer is longer than one of the lines")
+            },
+        }
+    }
+}
+
+impl Error for ChamferError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn chamfer_lines_which_dont_meet() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        let second = Line::new(Point::new(6.0, 1.0), Point::new(6.0, 5.0));
+
+        let got = chamfer(first, second, 1.0, 1.0).unwrap();
+
+        assert!(got.line.start.approx_eq(&Point::new(5.0, 0.0)));
+        assert!(got.line.end.approx_eq(&Point::new(6.0, 1.0)));
+        assert!(got.second.start.approx_eq(&Point::new(6.0, 1.0)));
+    }
+
+    #[test]
+    fn a_zero_chamfer_meets_at_the_corner() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        let second = Line::new(Point::new(6.0, 1.0), Point::new(6.0, 5.0));
+
+        let got = chamfer(first, second, 0.0, 0.0).unwrap();
+
+        assert_eq!(got.first.end, Point::new(6.0, 0.0));
+        assert_eq!(got.second.start, Point::new(6.0, 0.0));
+    }
+
+    #[test]
+    fn report_invalid_chamfers() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        let second = Line::new(Point::new(4.0, 0.0), Point::new(4.0, 5.0));
+        let parallel = Line::new(Point::new(0.0, 1.0), Point::new(4.0, 1.0));
+
+        assert_eq!(
+            chamfer(first, second, 5.0, 1.0).unwrap_err(),
+            ChamferError::TooLarge
+        );
+        assert_eq!(
+            chamfer(first, parallel, 1.0, 1.0).unwrap_err(),
+            ChamferError::Parallel
+        );
+        assert_eq!(
+            chamfer(first, second, -1.0, 1.0).unwrap_err(),
+            ChamferError::InvalidDistance
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
-    algorithms::Length,
-    primitives::{Arc, Line},
+    algorithms::{split::Parameterised, Length},
+    primitives::{
+        Arc, CubicBezier, Ellipse, EllipticalArc, Line, Polyline,
+        QuadraticBezier, Ray, Rectangle, Region, Segment, Spline, XLine,
+    },
 };
 use euclid::{approxeq::ApproxEq, Point2D, Scale, Vector2D};
 use std::iter::FromIterator;
@@ -109,6 +112,71 @@ impl<Space> ClosestPoint<Space> for Line<Space> {
     }
 }
 
+impl<Space> ClosestPoint<Space> for Rectangle<Space> {
+    /// Only the outline of a [`Rectangle`] is considered.
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        self.to_polyline().closest_point(target)
+    }
+}
+
+impl<Space> ClosestPoint<Space> for Region<Space> {
+    /// A [`Region`] is solid, so any point inside it is closest to itself.
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        if self.contains(target) {
+            return Closest::One(target);
+        }
+
+        let mut best_distance = std::f64::INFINITY;
+        let mut candidates: Vec<Point2D<f64, Space>> = Vec::new();
+
+        for boundary in self.loops() {
+            for point in boundary.closest_point(target).points() {
+                let distance = (*point - target).length();
+
+                if distance.approx_eq(&best_distance) {
+                    candidates.push(*point);
+                } else if distance < best_distance {
+                    best_distance = distance;
+                    candidates.clear();
+                    candidates.push(*point);
+                }
+            }
+        }
+
+        candidates.into_iter().collect()
+    }
+}
+
+impl<Space> ClosestPoint<Space> for XLine<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        let length_squared = self.direction.square_length();
+
+        if length_squared == 0.0 {
+            return Closest::One(self.base_point);
+        }
+
+        let t = Vector2D::dot(target - self.base_point, self.direction)
+            / length_squared;
+
+        Closest::One(self.point_at(t))
+    }
+}
+
+impl<Space> ClosestPoint<Space> for Ray<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        let length_squared = self.direction.square_length();
+
+        if length_squared == 0.0 {
+            return Closest::One(self.start);
+        }
+
+        let t =
+            Vector2D::dot(target - self.start, self.direction) / length_squared;
+
+        Closest::One(self.point_at(t.max(0.0)))
+    }
+}
+
 impl<Space> ClosestPoint<Space> for Arc<Space> {
     fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
         let radial = target - self.centre();
@@ -138,6 +206,453 @@ impl<Space> ClosestPoint<Space> for Arc<Space> {
     }
 }
 
+impl<Space> ClosestPoint<Space> for Ellipse<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        let radial = target - self.centre();
+
+        if radial.length().approx_eq(&0.0) {
+            return if self.is_circle() {
+                Closest::Infinite
+            } else {
+                Closest::Many(vec![
+                    self.centre() + self.minor_axis(),
+                    self.centre() - self.minor_axis(),
+                ])
+            };
+        }
+
+        // work in the ellipse's local coordinate system, where the major axis
+        // lies along the x-axis
+        let (sin, cos) = self.rotation().sin_cos();
+        let local_x = radial.x * cos + radial.y * sin;
+        let local_y = -radial.x * sin + radial.y * cos;
+
+        let (x, y) = closest_point_on_axis_aligned_ellipse(
+            self.semi_major(),
+            self.semi_minor(),
+            local_x,
+            local_y,
+        );
+
+        Closest::One(
+            self.centre() + Vector2D::new(x * cos - y * sin, x * sin + y * cos),
+        )
+    }
+}
+
+impl<Space> ClosestPoint<Space> for EllipticalArc<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        let ellipse = self.ellipse();
+        let closest = ellipse.closest_point(target);
+
+        if closest.is_infinite() {
+            return Closest::Infinite;
+        }
+
+        let on_arc: Vec<_> = closest
+            .points()
+            .iter()
+            .copied()
+            .filter(|&p| self.contains_angle(ellipse.parametric_angle_of(p)))
+            .collect();
+
+        if !on_arc.is_empty() {
+            return on_arc.into_iter().collect();
+        }
+
+        let to_start = (self.start() - target).length();
+        let to_end = (self.end() - target).length();
+
+        if to_start.approx_eq(&to_end) {
+            Closest::Many(vec![self.start(), self.end()])
+        } else if to_start < to_end {
+            Closest::One(self.start())
+        } else {
+            Closest::One(self.end())
+        }
+    }
+}
+
+impl<Space> ClosestPoint<Space> for Segment<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        match self {
+            Segment::Line(line) => line.closest_point(target),
+            Segment::Arc(arc) => arc.closest_point(target),
+        }
+    }
+}
+
+impl<Space> ClosestPoint<Space> for Polyline<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        let mut best_distance = std::f64::INFINITY;
+        let mut candidates = Vec::new();
+
+        for segment in self.segments() {
+            let closest = segment.closest_point(target);
+
+            // every point on an arc is equally close to its centre, so any
+            // point will do
+            let points = if closest.is_infinite() {
+                vec![segment.start()]
+            } else {
+                closest.points().to_vec()
+            };
+
+            for point in points {
+                let distance = (point - target).length();
+
+                if distance.approx_eq(&best_distance) {
+                    if !candidates
+                        .iter()
+                        .any(|c: &Point2D<f64, Space>| c.approx_eq(&point))
+                    {
+                        candidates.push(point);
+                    }
+                } else if distance < best_distance {
+                    best_distance = distance;
+                    candidates.clear();
+                    candidates.push(point);
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            // only empty polylines don't have any candidates
+            if let Some(vertex) = self.vertices().first() {
+                return Closest::One(vertex.point);
+            }
+        }
+
+        candidates.into_iter().collect()
+    }
+}
+
+impl<Space> ClosestPoint<Space> for CubicBezier<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        Closest::One(
+            self.point_at(closest_parameter(|t| self.point_at(t), target)),
+        )
+    }
+}
+
+impl<Space> ClosestPoint<Space> for QuadraticBezier<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        Closest::One(
+            self.point_at(closest_parameter(|t| self.point_at(t), target)),
+        )
+    }
+}
+
+impl<Space> ClosestPoint<Space> for Spline<Space> {
+    fn closest_point(&self, target: Point2D<f64, Space>) -> Closest<Space> {
+        let (start, end) = self.domain();
+        let point_at = |t: f64| self.point_at(start + t * (end - start));
+
+        Closest::One(point_at(closest_parameter(point_at, target)))
+    }
+}
+
+/// Numerically find the parameter, `t`, of the point on a parametric curve
+/// (where `0 <= t <= 1`) which is closest to `target`.
+///
+/// This does a coarse search to find roughly where the closest point is, then
+/// uses a ternary search to narrow it down.
+fn closest_parameter<Space, F>(curve: F, target: Point2D<f64, Space>) -> f64
+where
+    F: Fn(f64) -> Point2D<f64, Space>,
+{
+    const SAMPLES: usize = 32;
+    const ITERATIONS: usize = 64;
+
+    let distance = |t: f64| (curve(t) - target).square_length();
+
+    let best_sample = (0..=SAMPLES)
+        .map(|i| i as f64 / SAMPLES as f64)
+        .min_by(|&a, &b| distance(a).partial_cmp(&distance(b)).unwrap())
+        .unwrap_or(0.0);
+
+    let step = 1.0 / SAMPLES as f64;
+    let mut low = f64::max(best_sample - step, 0.0);
+    let mut high = f64::min(best_sample + step, 1.0);
+
+    for _ in 0..ITERATIONS {
+        let first_third = low + (high - low) / 3.0;
+        let second_third = high - (high - low) / 3.0;
+
+        if distance(first_third) < distance(second_third) {
+            high = second_third;
+        } else {
+            low = first_third;
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+/// Find the closest point on an axis-aligned ellipse centred on the origin.
+///
+/// This iteratively refines an estimate by approximating the ellipse locally
+/// with a circle centred on the evolute, and doesn't need any trigonometry.
+fn closest_point_on_axis_aligned_ellipse(
+    a: f64,
+    b: f64,
+    x: f64,
+    y: f64,
+) -> (f64, f64) {
+    const ITERATIONS: usize = 8;
+
+    // by symmetry we only need to look at the first quadrant
+    let px = x.abs();
+    let py = y.abs();
+
+    let mut tx = std::f64::consts::FRAC_1_SQRT_2;
+    let mut ty = std::f64::consts::FRAC_1_SQRT_2;
+
+    for _ in 0..ITERATIONS {
+        let x = a * tx;
+        let y = b * ty;
+
+        // the centre of curvature for the current estimate
+        let ex = (a * a - b * b) * tx.powi(3) / a;
+        let ey = (b * b - a * a) * ty.powi(3) / b;
+
+        let r = (x - ex).hypot(y - ey);
+        let qx = px - ex;
+        let qy = py - ey;
+        let q = qx.hypot(qy);
+
+        tx = ((qx * r / q + ex) / a).max(0.0).min(1.0);
+        ty = ((qy * r / q + ey) / b).max(0.0).min(1.0);
+
+        let t = tx.hypot(ty);
+        tx /= t;
+        ty /= t;
+    }
+
+    ((a * tx).copysign(x), (b * ty).copysign(y))
+}
+
+/// Something which can say how far along itself a point is, as well as
+/// finding the closest point.
+///
+/// Each object uses its own natural parameterisation:
+///
+/// - [`Line`]s, [`Arc`]s and [`EllipticalArc`]s go from `0` at the start to `1`
+///   at the end
+/// - The `n`'th segment of a [`Polyline`] covers parameters from `n` to `n + 1`
+/// - [`XLine`]s and [`Ray`]s use multiples of their direction vector
+/// - An [`Ellipse`] uses the parametric angle, in radians
+/// - Bézier curves use `t`, and [`Spline`]s use the parameter from their
+///   [`Spline::domain()`]
+pub trait ClosestParameter<Space>: ClosestPoint<Space> {
+    /// The parameter of a point on this object.
+    ///
+    /// Points which aren't on the object give the parameter of wherever is
+    /// closest.
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64;
+
+    /// Find the closest point to `target`, along with its parameter and how
+    /// far away it is.
+    ///
+    /// If several points are equally close the first one is used, and
+    /// [`None`] is returned when there are infinitely many.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{algorithms::ClosestParameter, primitives::Line};
+    ///
+    /// let line = Line::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+    ///
+    /// let got = line.closest_location(Point::new(2.5, 3.0)).unwrap();
+    ///
+    /// assert_eq!(got.point, Point::new(2.5, 0.0));
+    /// assert_eq!(got.parameter, 0.25);
+    /// assert_eq!(got.distance, 3.0);
+    /// ```
+    fn closest_location(
+        &self,
+        target: Point2D<f64, Space>,
+    ) -> Option<ClosestLocation<Space>> {
+        let point = *self.closest_point(target).points().first()?;
+
+        Some(ClosestLocation {
+            point,
+            parameter: self.parameter_of(point),
+            distance: (point - target).length(),
+        })
+    }
+}
+
+/// The closest point on an object, as found by
+/// [`ClosestParameter::closest_location()`].
+#[derive(Debug, PartialEq)]
+pub struct ClosestLocation<Space> {
+    /// The closest point.
+    pub point: Point2D<f64, Space>,
+    /// How far along the object the point is (see [`ClosestParameter`]).
+    pub parameter: f64,
+    /// The distance from the target to the [`ClosestLocation::point`].
+    pub distance: f64,
+}
+
+impl<Space> Copy for ClosestLocation<Space> {}
+
+impl<Space> Clone for ClosestLocation<Space> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'c, Space, C: ClosestParameter<Space> + ?Sized> ClosestParameter<Space>
+    for &'c C
+{
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        (*self).parameter_of(point)
+    }
+
+    fn closest_location(
+        &self,
+        target: Point2D<f64, Space>,
+    ) -> Option<ClosestLocation<Space>> {
+        (*self).closest_location(target)
+    }
+}
+
+impl<Space> ClosestParameter<Space> for Point2D<f64, Space> {
+    fn parameter_of(&self, _point: Point2D<f64, Space>) -> f64 { 0.0 }
+}
+
+impl<Space> ClosestParameter<Space> for Line<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        Parameterised::parameter_of(self, point)
+    }
+}
+
+impl<Space> ClosestParameter<Space> for Arc<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        Parameterised::parameter_of(self, point)
+    }
+}
+
+impl<Space> ClosestParameter<Space> for Segment<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        match self {
+            Segment::Line(line) => ClosestParameter::parameter_of(line, point),
+            Segment::Arc(arc) => ClosestParameter::parameter_of(arc, point),
+        }
+    }
+}
+
+impl<Space> ClosestParameter<Space> for Polyline<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        if self.segments().next().is_none() {
+            return 0.0;
+        }
+
+        Parameterised::parameter_of(self, point)
+    }
+}
+
+impl<Space> ClosestParameter<Space> for XLine<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        let length_squared = self.direction.square_length();
+
+        if length_squared == 0.0 {
+            0.0
+        } else {
+            Vector2D::dot(point - self.base_point, self.direction)
+                / length_squared
+        }
+    }
+}
+
+impl<Space> ClosestParameter<Space> for Ray<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        self.to_xline().parameter_of(point).max(0.0)
+    }
+}
+
+impl<Space> ClosestParameter<Space> for Ellipse<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        self.parametric_angle_of(point).radians
+    }
+}
+
+impl<Space> ClosestParameter<Space> for EllipticalArc<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        let sweep = self.sweep_angle().radians;
+        if sweep == 0.0 {
+            return 0.0;
+        }
+
+        let angle = self.ellipse().parametric_angle_of(point);
+        let offset = if sweep > 0.0 {
+            angle - self.start_angle()
+        } else {
+            self.start_angle() - angle
+        };
+
+        (offset.positive().radians / sweep.abs()).min(1.0)
+    }
+}
+
+impl<Space> ClosestParameter<Space> for CubicBezier<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        closest_parameter(|t| self.point_at(t), point)
+    }
+
+    fn closest_location(
+        &self,
+        target: Point2D<f64, Space>,
+    ) -> Option<ClosestLocation<Space>> {
+        let parameter = self.parameter_of(target);
+        Some(location(self.point_at(parameter), parameter, target))
+    }
+}
+
+impl<Space> ClosestParameter<Space> for QuadraticBezier<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        closest_parameter(|t| self.point_at(t), point)
+    }
+
+    fn closest_location(
+        &self,
+        target: Point2D<f64, Space>,
+    ) -> Option<ClosestLocation<Space>> {
+        let parameter = self.parameter_of(target);
+        Some(location(self.point_at(parameter), parameter, target))
+    }
+}
+
+impl<Space> ClosestParameter<Space> for Spline<Space> {
+    fn parameter_of(&self, point: Point2D<f64, Space>) -> f64 {
+        let (start, end) = self.domain();
+        let point_at = |t: f64| self.point_at(start + t * (end - start));
+
+        start + closest_parameter(point_at, point) * (end - start)
+    }
+
+    fn closest_location(
+        &self,
+        target: Point2D<f64, Space>,
+    ) -> Option<ClosestLocation<Space>> {
+        let parameter = self.parameter_of(target);
+        Some(location(self.point_at(parameter), parameter, target))
+    }
+}
+
+fn location<Space>(
+    point: Point2D<f64, Space>,
+    parameter: f64,
+    target: Point2D<f64, Space>,
+) -> ClosestLocation<Space> {
+    ClosestLocation {
+        point,
+        parameter,
+        distance: (point - target).length(),
+    }
+}
+
 /// An enum containing the different possible solutions for
 /// [`ClosestPoint::closest_point()`].
 #[derive(Debug, Clone, PartialEq)]
@@ -191,6 +706,7 @@ impl<Space> FromIterator<Point2D<f64, Space>> for Closest<Space> {
 mod tests {
     use super::*;
     use crate::Angle;
+    use euclid::default::Rotation2D;
 
     type Point = euclid::default::Point2D<f64>;
 
@@ -294,4 +810,165 @@ mod tests {
 
         assert_eq!(got, Closest::Many(vec![arc.start(), arc.end()]));
     }
+
+    #[test]
+    fn closest_point_on_an_ellipse() {
+        let centre = Point::new(1.0, 2.0);
+        let ellipse = Ellipse::new(centre, 10.0, 5.0, Angle::frac_pi_2());
+        let inputs = vec![
+            (Point::new(1.0, 20.0), Point::new(1.0, 12.0)),
+            (Point::new(1.0, -20.0), Point::new(1.0, -8.0)),
+            (Point::new(-10.0, 2.0), Point::new(-4.0, 2.0)),
+            (Point::new(3.0, 2.0), Point::new(6.0, 2.0)),
+        ];
+
+        for (target, expected) in inputs {
+            let got = ellipse.closest_point(target);
+
+            match got {
+                Closest::One(point) => assert!(
+                    point.approx_eq(&expected),
+                    "{:?} != {:?}",
+                    point,
+                    expected
+                ),
+                other => panic!("Expected one point but got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn closest_point_is_on_the_ellipse() {
+        let ellipse =
+            Ellipse::new(Point::new(-3.0, 4.0), 8.0, 3.0, Angle::degrees(30.0));
+        let target = Point::new(7.0, 9.0);
+
+        let got = ellipse.closest_point(target).points()[0];
+
+        // a point on the ellipse satisfies (x/a)² + (y/b)² = 1 in local
+        // coordinates, and the line to the target should be normal to it
+        let radial = Rotation2D::new(-ellipse.rotation())
+            .transform_vector(got - ellipse.centre());
+        let a = ellipse.semi_major();
+        let b = ellipse.semi_minor();
+        let on_ellipse = (radial.x / a).powi(2) + (radial.y / b).powi(2);
+        assert!(on_ellipse.approx_eq(&1.0));
+
+        let tangent = Rotation2D::new(ellipse.rotation()).transform_vector(
+            Vector2D::new(-radial.y * a / b, radial.x * b / a),
+        );
+        let to_target = target - got;
+        assert!(Vector2D::dot(tangent, to_target).approx_eq(&0.0));
+    }
+
+    #[test]
+    fn closest_point_on_an_elliptical_arc() {
+        let ellipse = Ellipse::new(Point::zero(), 10.0, 5.0, Angle::zero());
+        let arc =
+            EllipticalArc::new(ellipse, Angle::zero(), Angle::frac_pi_2());
+
+        assert_eq!(
+            arc.closest_point(Point::new(20.0, 0.0)),
+            Closest::One(Point::new(10.0, 0.0))
+        );
+        // the closest point on the full ellipse isn't part of the arc
+        assert_eq!(
+            arc.closest_point(Point::new(-20.0, -1.0)),
+            Closest::One(arc.end())
+        );
+    }
+
+    #[test]
+    fn closest_point_on_a_polyline() {
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ]);
+
+        assert_eq!(
+            polyline.closest_point(Point::new(5.0, 3.0)),
+            Closest::One(Point::new(5.0, 0.0))
+        );
+        // halfway between the two legs
+        assert_eq!(
+            polyline.closest_point(Point::new(7.0, 3.0)),
+            Closest::Many(vec![Point::new(7.0, 0.0), Point::new(10.0, 3.0)])
+        );
+    }
+
+    #[test]
+    fn closest_point_on_a_cubic_bezier() {
+        let curve = CubicBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 0.0),
+        );
+
+        let got = curve.closest_point(Point::new(0.5, 2.0));
+        assert!(got.points()[0].approx_eq(&Point::new(0.5, 0.75)));
+
+        let got = curve.closest_point(Point::new(-1.0, -1.0));
+        assert!(got.points()[0].approx_eq(&curve.start));
+    }
+
+    #[test]
+    fn closest_point_on_a_spline() {
+        let spline = Spline::clamped(
+            2,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(5.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 5.0),
+                Point::new(10.0, 10.0),
+            ],
+        )
+        .unwrap();
+
+        let got = spline.closest_point(Point::new(3.0, -4.0));
+
+        assert!(got.points()[0].approx_eq(&Point::new(3.0, 0.0)));
+    }
+
+    #[test]
+    fn location_along_a_polyline() {
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ]);
+
+        let got = polyline.closest_location(Point::new(12.0, 4.0)).unwrap();
+
+        assert_eq!(got.point, Point::new(10.0, 4.0));
+        assert!((got.parameter - 1.4).abs() < 1e-10);
+        assert_eq!(got.distance, 2.0);
+    }
+
+    #[test]
+    fn location_on_a_clockwise_elliptical_arc() {
+        let ellipse = Ellipse::new(Point::zero(), 4.0, 2.0, Angle::zero());
+        let arc = EllipticalArc::new(ellipse, Angle::frac_pi_2(), -Angle::pi());
+
+        // the bottom of the ellipse is at the end of the arc
+        let got = arc.closest_location(Point::new(0.0, -5.0)).unwrap();
+
+        assert!((got.point - Point::new(0.0, -2.0)).length() < 1e-6);
+        assert!((got.parameter - 1.0).abs() < 1e-6);
+        assert!((got.distance - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn no_location_when_everything_is_equally_close() {
+        let circle = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        );
+
+        assert!(circle.closest_location(Point::zero()).is_none());
+    }
 }
@@ -0,0 +1,141 @@
+use crate::primitives::Polyline;
+use euclid::Point2D;
+
+/// Find the smallest convex shape which contains every point, using
+/// [Andrew's monotone chain algorithm][wiki].
+///
+/// The result is a closed [`Polyline`] going anticlockwise. Points which
+/// lie along the hull's edges aren't included as vertices.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::algorithms::convex_hull;
+///
+/// let points = vec![
+///     Point::new(0.0, 0.0),
+///     Point::new(1.0, 1.0),
+///     Point::new(2.0, 0.0),
+///     Point::new(2.0, 2.0),
+///     Point::new(0.0, 2.0),
+///     Point::new(1.0, 0.0),
+/// ];
+///
+/// let hull = convex_hull(points);
+///
+/// assert!(hull.is_closed());
+/// assert_eq!(hull.len(), 4);
+/// assert_eq!(hull.area(), 4.0);
+/// ```
+///
+/// [wiki]: https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain
+pub fn convex_hull<S, I>(points: I) -> Polyline<S>
+where
+    I: IntoIterator<Item = Point2D<f64, S>>,
+{
+    let mut points: Vec<_> = points
+        .into_iter()
+        .filter(|p| p.x.is_finite() && p.y.is_finite())
+        .collect();
+    points.sort_by(|a, b| {
+        (a.x, a.y)
+            .partial_cmp(&(b.x, b.y))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    points.dedup();
+
+    if points.len() < 3 {
+        let mut hull = Polyline::from_points(points);
+        hull.set_closed(true);
+        return hull;
+    }
+
+    let mut lower = half_hull(points.iter().copied());
+    let mut upper = half_hull(points.iter().rev().copied());
+
+    // the last point of each half is the first point of the other
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+
+    let mut hull = Polyline::from_points(lower);
+    hull.set_closed(true);
+    hull
+}
+
+/// Walk along the sorted points, only keeping the ones where the hull turns
+/// anticlockwise.
+fn half_hull<S, I>(points: I) -> Vec<Point2D<f64, S>>
+where
+    I: Iterator<Item = Point2D<f64, S>>,
+{
+    let mut hull: Vec<Point2D<f64, S>> = Vec::new();
+
+    for point in points {
+        while hull.len() >= 2 {
+            let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+            if (b - a).cross(point - b) > 0.0 {
+                break;
+            }
+            hull.pop();
+        }
+
+        hull.push(point);
+    }
+
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn hull_is_anticlockwise() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 3.0),
+            Point::new(2.0, 1.0),
+        ];
+
+        let hull = convex_hull(points);
+
+        let vertices: Vec<Point> =
+            hull.vertices().iter().map(|v| v.point).collect();
+        assert_eq!(
+            vertices,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(4.0, 0.0),
+                Point::new(2.0, 3.0)
+            ]
+        );
+        assert!(hull.signed_area() > 0.0);
+    }
+
+    #[test]
+    fn collinear_points_dont_have_an_area() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.0, 1.0),
+        ];
+
+        let hull = convex_hull(points);
+
+        assert_eq!(hull.len(), 2);
+        assert_eq!(hull.area(), 0.0);
+    }
+
+    #[test]
+    fn hull_of_nothing() {
+        let hull = convex_hull(Vec::<Point>::new());
+
+        assert!(hull.is_empty());
+    }
+}
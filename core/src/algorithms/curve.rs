@@ -0,0 +1,262 @@
+use crate::{
+    primitives::{
+        Arc, CubicBezier, EllipticalArc, Line, QuadraticBezier, Segment, Spline,
+    },
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+
+/// A curve which can be evaluated at any parameter between its start and
+/// end.
+///
+/// Curves use the same parameterisation as
+/// [`ClosestParameter`][crate::algorithms::ClosestParameter], so a parameter
+/// found using [`ClosestParameter::parameter_of()`][pof] can be passed
+/// straight to [`Curve::point_at()`].
+///
+/// Some primitives have an inherent `point_at()` method which takes an
+/// [`Angle`], so you may need to call these methods as `Curve::point_at(&arc,
+/// t)`.
+///
+/// [pof]: crate::algorithms::ClosestParameter::parameter_of
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::Curve, primitives::Arc, Angle};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let arc = Arc::from_centre_radius(
+///     Point::zero(),
+///     2.0,
+///     Angle::zero(),
+///     Angle::pi(),
+/// );
+///
+/// assert_eq!(arc.param_range(), (0.0, 1.0));
+/// assert!(Curve::point_at(&arc, 0.5).approx_eq(&Point::new(0.0, 2.0)));
+/// assert!(arc.tangent_at(0.5).normalize().approx_eq(&euclid::vec2(-1.0, 0.0)));
+/// ```
+pub trait Curve<Space> {
+    /// The parameters at the start and end of the curve.
+    fn param_range(&self) -> (f64, f64);
+
+    /// The point at a particular parameter.
+    fn point_at(&self, t: f64) -> Point2D<f64, Space>;
+
+    /// The derivative of [`Curve::point_at()`] with respect to the
+    /// parameter, pointing in the direction of travel.
+    ///
+    /// This isn't normalised, so its length says how quickly the curve is
+    /// covered as `t` changes.
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space>;
+}
+
+impl<Space, C: Curve<Space> + ?Sized> Curve<Space> for &C {
+    fn param_range(&self) -> (f64, f64) { (*self).param_range() }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> { (*self).point_at(t) }
+
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space> {
+        (*self).tangent_at(t)
+    }
+}
+
+impl<Space> Curve<Space> for Line<Space> {
+    fn param_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> {
+        self.start + self.displacement() * t
+    }
+
+    fn tangent_at(&self, _t: f64) -> Vector2D<f64, Space> {
+        self.displacement()
+    }
+}
+
+impl<Space> Curve<Space> for Arc<Space> {
+    fn param_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> {
+        Arc::point_at(*self, self.sweep_angle() * t)
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space> {
+        let sweep = self.sweep_angle();
+        let (sin, cos) = (self.start_angle() + sweep * t).sin_cos();
+        let speed = self.radius() * sweep.radians;
+
+        Vector2D::new(-sin, cos) * speed
+    }
+}
+
+impl<Space> Curve<Space> for EllipticalArc<Space> {
+    fn param_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> {
+        EllipticalArc::point_at(*self, self.sweep_angle() * t)
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space> {
+        let sweep = self.sweep_angle();
+        let angle: Angle = self.start_angle() + sweep * t;
+        let (sin, cos) = angle.sin_cos();
+        let ellipse = self.ellipse();
+
+        (ellipse.minor_axis() * cos - ellipse.major_axis() * sin)
+            * sweep.radians
+    }
+}
+
+impl<Space> Curve<Space> for QuadraticBezier<Space> {
+    fn param_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> {
+        QuadraticBezier::point_at(*self, t)
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space> {
+        self.derivative_at(t)
+    }
+}
+
+impl<Space> Curve<Space> for CubicBezier<Space> {
+    fn param_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> {
+        CubicBezier::point_at(*self, t)
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space> {
+        self.derivative_at(t)
+    }
+}
+
+impl<Space> Curve<Space> for Spline<Space> {
+    /// The spline's [`Spline::domain()`].
+    fn param_range(&self) -> (f64, f64) { self.domain() }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> {
+        Spline::point_at(self, t)
+    }
+
+    /// Splines don't have an analytic derivative, so this uses a one-sided
+    /// difference which stays inside the domain.
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space> {
+        let (start, end) = self.domain();
+        let step = (end - start) * 1e-6;
+
+        if t + step <= end {
+            (Spline::point_at(self, t + step) - Spline::point_at(self, t))
+                / step
+        } else {
+            (Spline::point_at(self, t) - Spline::point_at(self, t - step))
+                / step
+        }
+    }
+}
+
+impl<Space> Curve<Space> for Segment<Space> {
+    fn param_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn point_at(&self, t: f64) -> Point2D<f64, Space> {
+        match self {
+            Segment::Line(line) => Curve::point_at(line, t),
+            Segment::Arc(arc) => Curve::point_at(arc, t),
+        }
+    }
+
+    fn tangent_at(&self, t: f64) -> Vector2D<f64, Space> {
+        match self {
+            Segment::Line(line) => line.tangent_at(t),
+            Segment::Arc(arc) => arc.tangent_at(t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::ClosestParameter, primitives::Ellipse};
+    use euclid::approxeq::ApproxEq;
+    use std::f64::consts::PI;
+
+    type Point = euclid::default::Point2D<f64>;
+    type Vector = euclid::default::Vector2D<f64>;
+
+    /// Compare [`Curve::tangent_at()`] with a central difference.
+    fn assert_tangent_matches<C: Curve<euclid::UnknownUnit>>(curve: &C) {
+        let (start, end) = curve.param_range();
+        let step = (end - start) * 1e-6;
+
+        for i in 1..10 {
+            let t = start + (end - start) * i as f64 / 10.0;
+            let expected = (curve.point_at(t + step)
+                - curve.point_at(t - step))
+                / (2.0 * step);
+            let got = curve.tangent_at(t);
+
+            assert!(
+                (got - expected).length() <= 1e-4 * expected.length().max(1.0),
+                "{:?} != {:?} at t = {}",
+                got,
+                expected,
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn tangents_are_derivatives() {
+        assert_tangent_matches(&Line::new(
+            Point::new(1.0, 2.0),
+            Point::new(-3.0, 5.0),
+        ));
+        assert_tangent_matches(&Arc::from_centre_radius(
+            Point::new(1.0, 1.0),
+            3.0,
+            Angle::degrees(30.0),
+            Angle::degrees(-200.0),
+        ));
+        assert_tangent_matches(&EllipticalArc::new(
+            Ellipse::new(Point::zero(), 4.0, 1.0, Angle::degrees(20.0)),
+            Angle::degrees(10.0),
+            Angle::degrees(250.0),
+        ));
+        assert_tangent_matches(&CubicBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 3.0),
+            Point::new(4.0, -1.0),
+            Point::new(5.0, 2.0),
+        ));
+        assert_tangent_matches(
+            &Spline::clamped(
+                2,
+                vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.0, 2.0),
+                    Point::new(3.0, -1.0),
+                    Point::new(4.0, 1.0),
+                ],
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn parameters_agree_with_closest_parameter() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::frac_pi_2(),
+            Angle::pi(),
+        );
+
+        let t = arc.parameter_of(Point::new(-1.0, 0.0));
+
+        assert!(t.approx_eq(&0.5));
+        assert!(Curve::point_at(&arc, t).approx_eq(&Point::new(-1.0, 0.0)));
+        assert!(arc.tangent_at(t).approx_eq(&Vector::new(0.0, -PI)));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{
+    algorithms::{ClosestPoint, Intersect},
+    primitives::{Arc, Line, Segment},
+};
+use euclid::Point2D;
+
+/// The closest two objects get to each other.
+#[derive(Debug, PartialEq)]
+pub struct Separation<S> {
+    /// The distance between [`Separation::first`] and
+    /// [`Separation::second`].
+    pub distance: f64,
+    /// The point on the first object.
+    pub first: Point2D<f64, S>,
+    /// The point on the second object.
+    pub second: Point2D<f64, S>,
+}
+
+impl<S> Separation<S> {
+    fn between(first: Point2D<f64, S>, second: Point2D<f64, S>) -> Self {
+        Separation {
+            distance: (second - first).length(),
+            first,
+            second,
+        }
+    }
+
+    /// Swap the first and second points.
+    fn swapped(self) -> Self {
+        Separation {
+            distance: self.distance,
+            first: self.second,
+            second: self.first,
+        }
+    }
+}
+
+impl<S> Copy for Separation<S> {}
+
+impl<S> Clone for Separation<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Find the minimum distance between two objects made out of [`Line`]s and
+/// [`Arc`]s, and the pair of points where it occurs.
+///
+/// When the objects touch, the distance is zero and both points are one of
+/// the places they intersect. This returns [`None`] if either object is
+/// empty.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::distance,
+///     primitives::{Arc, Line},
+///     Angle,
+/// };
+/// use euclid::approxeq::ApproxEq;
+///
+/// let line = Line::new(Point::new(-5.0, 3.0), Point::new(5.0, 3.0));
+/// let circle = Arc::from_centre_radius(
+///     Point::zero(),
+///     1.0,
+///     Angle::zero(),
+///     Angle::two_pi(),
+/// );
+///
+/// let got = distance(vec![line], vec![circle]).unwrap();
+///
+/// assert!(got.distance.approx_eq(&2.0));
+/// assert!(got.first.approx_eq(&Point::new(0.0, 3.0)));
+/// assert!(got.second.approx_eq(&Point::new(0.0, 1.0)));
+/// ```
+pub fn distance<S, A, B, T, U>(first: A, second: B) -> Option<Separation<S>>
+where
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = U>,
+    T: Into<Segment<S>>,
+    U: Into<Segment<S>>,
+{
+    let second: Vec<Segment<S>> = second.into_iter().map(Into::into).collect();
+    let mut best: Option<Separation<S>> = None;
+
+    for a in first.into_iter().map(Into::into) {
+        for &b in &second {
+            let candidate = segment_distance(a, b);
+
+            if best.map_or(true, |best| candidate.distance < best.distance) {
+                best = Some(candidate);
+            }
+
+            if candidate.distance == 0.0 {
+                return best;
+            }
+        }
+    }
+
+    best
+}
+
+/// The minimum distance between two [`Segment`]s.
+fn segment_distance<S>(first: Segment<S>, second: Segment<S>) -> Separation<S> {
+    if let Some(&point) = first.intersect(&second).points().first() {
+        return Separation::between(point, point);
+    }
+
+    // if two segments don't intersect, the closest points are either at one
+    // of the ends or somewhere the segments are "facing" each other
+    let mut candidates = vec![
+        Separation::between(first.start(), nearest(second, first.start())),
+        Separation::between(first.end(), nearest(second, first.end())),
+        Separation::between(nearest(first, second.start()), second.start()),
+        Separation::between(nearest(first, second.end()), second.end()),
+    ];
+
+    match (first, second) {
+        (Segment::Line(line), Segment::Arc(arc)) => {
+            candidates.push(facing_line_arc(line, arc));
+        },
+        (Segment::Arc(arc), Segment::Line(line)) => {
+            candidates.push(facing_line_arc(line, arc).swapped());
+        },
+        (Segment::Arc(a), Segment::Arc(b)) => {
+            // the closest points on two circles are on the line between
+            // their centres
+            for &point in &[a.centre(), b.centre()] {
+                for &on_a in nearest_all(Segment::Arc(a), point).iter() {
+                    candidates.push(Separation::between(
+                        on_a,
+                        nearest(Segment::Arc(b), on_a),
+                    ));
+                }
+            }
+        },
+        (Segment::Line(_), Segment::Line(_)) => {},
+    }
+
+    candidates
+        .into_iter()
+        .min_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap())
+        .unwrap()
+}
+
+/// The closest a [`Line`] gets to a circle is directly between it and the
+/// circle's centre.
+fn facing_line_arc<S>(line: Line<S>, arc: Arc<S>) -> Separation<S> {
+    let foot = nearest(Segment::Line(line), arc.centre());
+    let on_arc = nearest(Segment::Arc(arc), foot);
+
+    Separation::between(nearest(Segment::Line(line), on_arc), on_arc)
+}
+
+fn nearest<S>(segment: Segment<S>, point: Point2D<f64, S>) -> Point2D<f64, S> {
+    nearest_all(segment, point)
+        .first()
+        .copied()
+        .unwrap_or_else(|| segment.start())
+}
+
+fn nearest_all<S>(
+    segment: Segment<S>,
+    point: Point2D<f64, S>,
+) -> Vec<Point2D<f64, S>> {
+    segment.closest_point(point).points().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn crossing_lines_have_no_separation() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 2.0));
+        let second = Line::new(Point::new(0.0, 2.0), Point::new(2.0, 0.0));
+
+        let got = distance(vec![first], vec![second]).unwrap();
+
+        assert_eq!(got.distance, 0.0);
+        assert!(got.first.approx_eq(&Point::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn skew_lines_are_closest_at_an_end() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+        let second = Line::new(Point::new(3.0, 1.0), Point::new(5.0, 7.0));
+
+        let got = distance(vec![first], vec![second]).unwrap();
+
+        assert!(got.distance.approx_eq(&1.0));
+        assert!(got.first.approx_eq(&Point::new(3.0, 0.0)));
+        assert!(got.second.approx_eq(&Point::new(3.0, 1.0)));
+    }
+
+    #[test]
+    fn two_separate_circles() {
+        let circle = |x: f64, y: f64, radius: f64| {
+            Arc::from_centre_radius(
+                Point::new(x, y),
+                radius,
+                Angle::zero(),
+                Angle::two_pi(),
+            )
+        };
+
+        let got =
+            distance(vec![circle(0.0, 0.0, 1.0)], vec![circle(6.0, 8.0, 2.0)])
+                .unwrap();
+
+        assert!(got.distance.approx_eq(&7.0));
+        assert!(got.first.approx_eq(&Point::new(0.6, 0.8)));
+        assert!(got.second.approx_eq(&Point::new(4.8, 6.4)));
+    }
+
+    #[test]
+    fn nothing_to_measure() {
+        let line = Line::new(Point::zero(), Point::new(1.0, 1.0));
+
+        let got = distance(Vec::<Line<_>>::new(), vec![line]);
+
+        assert!(got.is_none());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{
+    algorithms::{arc_arc_intersections, line_arc_intersections},
+    primitives::{Arc, Line, Ray, Segment},
+    Angle,
+};
+use euclid::Point2D;
+use std::{
+    error::Error,
+    f64::consts::PI,
+    fmt::{self, Display, Formatter},
+};
+
+/// Boundaries closer than this to the end being extended are ignored, so a
+/// line which already touches a boundary will be extended to the next one.
+const MINIMUM_EXTENSION: f64 = 1e-9;
+
+/// One of the two ends of a line or arc.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// The start.
+    Start,
+    /// The end.
+    End,
+}
+
+/// Lengthen a [`Line`] until it reaches the nearest `boundary`.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::{extend_line, Endpoint},
+///     primitives::{Line, Segment},
+/// };
+///
+/// let line = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0));
+/// let near = Line::new(Point::new(5.0, -1.0), Point::new(5.0, 1.0));
+/// let far = Line::new(Point::new(8.0, -1.0), Point::new(8.0, 1.0));
+/// let boundaries = [Segment::Line(far), Segment::Line(near)];
+///
+/// let got = extend_line(line, Endpoint::End, &boundaries).unwrap();
+///
+/// assert_eq!(got, Line::new(Point::new(0.0, 0.0), Point::new(5.0, 0.0)));
+/// ```
+pub fn extend_line<S>(
+    line: Line<S>,
+    end: Endpoint,
+    boundaries: &[Segment<S>],
+) -> Result<Line<S>, ExtendError> {
+    let ray = match end {
+        Endpoint::Start => Ray::new(line.start, -line.displacement()),
+        Endpoint::End => Ray::new(line.end, line.displacement()),
+    };
+    let length = ray.direction.length();
+
+    if length == 0.0 {
+        return Err(ExtendError::NoBoundary);
+    }
+
+    let target = boundaries
+        .iter()
+        .flat_map(|&boundary| ray_crossings(ray, boundary))
+        .map(|point| ((point - ray.start).length(), point))
+        .filter(|&(distance, _)| distance > MINIMUM_EXTENSION)
+        .min_by(|a, b| {
+            a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(_, point)| point)
+        .ok_or(ExtendError::NoBoundary)?;
+
+    Ok(match end {
+        Endpoint::Start => Line::new(target, line.end),
+        Endpoint::End => Line::new(line.start, target),
+    })
+}
+
+/// Lengthen an [`Arc`] around its circle until it reaches the nearest
+/// `boundary`.
+pub fn extend_arc<S>(
+    arc: Arc<S>,
+    end: Endpoint,
+    boundaries: &[Segment<S>],
+) -> Result<Arc<S>, ExtendError> {
+    let sweep = arc.sweep_angle().radians;
+    let available = 2.0 * PI - sweep.abs();
+
+    if available <= 0.0 {
+        return Err(ExtendError::AlreadyClosed);
+    }
+
+    let circle = Arc::from_centre_radius(
+        arc.centre(),
+        arc.radius(),
+        Angle::zero(),
+        Angle::two_pi(),
+    );
+    let (from, direction) = match end {
+        Endpoint::Start => (arc.start(), -sweep.signum()),
+        Endpoint::End => (arc.end(), sweep.signum()),
+    };
+    let angular_minimum = MINIMUM_EXTENSION / arc.radius();
+
+    let extra = boundaries
+        .iter()
+        .flat_map(|&boundary| match boundary {
+            Segment::Line(line) => line_arc_intersections(line, circle),
+            Segment::Arc(other) => arc_arc_intersections(circle, other),
+        })
+        .map(|point| angle_around(arc.centre(), from, point, direction))
+        .filter(|&angle| angle > angular_minimum && angle <= available)
+        .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .ok_or(ExtendError::NoBoundary)?;
+
+    let extra = Angle::radians(extra * sweep.signum());
+
+    Ok(match end {
+        Endpoint::Start => Arc::from_centre_radius(
+            arc.centre(),
+            arc.radius(),
+            arc.start_angle() - extra,
+            arc.sweep_angle() + extra,
+        ),
+        Endpoint::End => Arc::from_centre_radius(
+            arc.centre(),
+            arc.radius(),
+            arc.start_angle(),
+            arc.sweep_angle() + extra,
+        ),
+    })
+}
+
+/// The angle (between `0` and `2*PI`) swept going from `from` to `to` around
+/// `centre`, anticlockwise if `direction` is positive.
+fn angle_around<S>(
+    centre: Point2D<f64, S>,
+    from: Point2D<f64, S>,
+    to: Point2D<f64, S>,
+    direction: f64,
+) -> f64 {
+    let (a, b) = (from - centre, to - centre);
+    let angle = a.cross(b).atan2(a.dot(b)) * direction;
+
+    angle.rem_euclid(2.0 * PI)
+}
+
+fn ray_crossings<S>(ray: Ray<S>, boundary: Segment<S>) -> Vec<Point2D<f64, S>> {
+    match boundary {
+        Segment::Line(line) => ray.intersect_line(line).into_iter().collect(),
+        Segment::Arc(arc) => {
+            // a ray only needs to go far enough to pass the whole circle
+            let reach = (arc.centre() - ray.start).length() + arc.radius();
+            let direction = ray.direction.normalize();
+            let line = Line::new(ray.start, ray.start + direction * reach);
+
+            line_arc_intersections(line, arc)
+        },
+    }
+}
+
+/// The reasons extending a line or arc may fail.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ExtendError {
+    /// None of the boundaries are in the way.
+    NoBoundary,
+    /// The arc is already a full circle.
+    AlreadyClosed,
+    /// Only lines and arcs can be extended.
+    Unsupported,
+}
+
+impl Display for ExtendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtendError::NoBoundary => {
+                write!(f, "There are no boundaries to extend to")
+            },
+            ExtendError::AlreadyClosed => {
+                write!(f, "A full circle can't be extended")
+            },
+            ExtendError::Unsupported => {
+                write!(f, "Only lines and arcs can be extended")
+            },
+        }
+    }
+}
+
+impl Error for ExtendError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn extend_the_start_of_a_line_to_an_arc() {
+        let line = Line::new(Point::new(0.0, 0.0), Point::new(1.0, 0.0));
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            3.0,
+            Angle::frac_pi_2(),
+            Angle::pi(),
+        );
+
+        let got =
+            extend_line(line, Endpoint::Start, &[Segment::Arc(arc)]).unwrap();
+
+        assert!(got.start.approx_eq(&Point::new(-3.0, 0.0)));
+        assert_eq!(
+            extend_line(line, Endpoint::End, &[Segment::Arc(arc)]),
+            Err(ExtendError::NoBoundary)
+        );
+    }
+
+    #[test]
+    fn a_line_touching_a_boundary_extends_to_the_next_one() {
+        let line = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0));
+        let touching = Line::new(Point::new(2.0, -1.0), Point::new(2.0, 1.0));
+        let next = Line::new(Point::new(4.0, -1.0), Point::new(4.0, 1.0));
+
+        let got = extend_line(
+            line,
+            Endpoint::End,
+            &[Segment::Line(touching), Segment::Line(next)],
+        )
+        .unwrap();
+
+        assert_eq!(got.end, Point::new(4.0, 0.0));
+    }
+
+    #[test]
+    fn extend_an_arc_to_a_line() {
+        // a quarter circle going clockwise from the top
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::frac_pi_2(),
+            -Angle::frac_pi_2(),
+        );
+        let floor = Line::new(Point::new(-5.0, -0.5), Point::new(5.0, -0.5));
+
+        let got =
+            extend_arc(arc, Endpoint::End, &[Segment::Line(floor)]).unwrap();
+
+        assert!(got.start().approx_eq(&arc.start()));
+        let expected = Point::new(0.75_f64.sqrt(), -0.5);
+        assert!(got.end().approx_eq(&expected));
+        assert!(got.is_clockwise());
+    }
+
+    #[test]
+    fn circles_cant_be_extended() {
+        let circle = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        );
+
+        assert_eq!(
+            extend_arc(circle, Endpoint::End, &[]),
+            Err(ExtendError::AlreadyClosed)
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use crate::{
+    algorithms::{
+        arc_arc_intersections,
+        offset::{with_end, with_start},
+        Offset, Side,
+    },
+    primitives::{Arc, Segment, XLine},
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+use std::{
+    error::Error,
+    f64::consts::PI,
+    fmt::{self, Display, Formatter},
+};
+
+/// The result of rounding off the corner between two segments (see
+/// [`fillet()`]).
+#[derive(Debug, PartialEq)]
+pub struct Fillet<S> {
+    /// The arc which joins the two segments.
+    pub arc: Arc<S>,
+    /// The first segment, with its end moved to the start of the
+    /// [`Fillet::arc`].
+    pub first: Segment<S>,
+    /// The second segment, with its start moved to the end of the
+    /// [`Fillet::arc`].
+    pub second: Segment<S>,
+}
+
+impl<S> Copy for Fillet<S> {}
+
+impl<S> Clone for Fillet<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Round off the corner where `first` ends and `second` starts with an arc
+/// of the given `radius`.
+///
+/// The segments don't need to touch, lines are extended (or trimmed) as
+/// necessary so the arc is tangent to both.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::fillet, primitives::Line};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let horizontal = Line::new(Point::new(-10.0, 0.0), Point::new(0.0, 0.0));
+/// let vertical = Line::new(Point::new(0.0, 0.0), Point::new(0.0, 10.0));
+///
+/// let got = fillet(horizontal, vertical, 2.0).unwrap();
+///
+/// assert!(got.arc.centre().approx_eq(&Point::new(-2.0, 2.0)));
+/// assert!(got.first.end().approx_eq(&Point::new(-2.0, 0.0)));
+/// assert!(got.second.start().approx_eq(&Point::new(0.0, 2.0)));
+/// ```
+pub fn fillet<S, A, B>(
+    first: A,
+    second: B,
+    radius: f64,
+) -> Result<Fillet<S>, FilletError>
+where
+    A: Into<Segment<S>>,
+    B: Into<Segment<S>>,
+{
+    let (first, second) = (first.into(), second.into());
+
+    if radius.is_nan() || radius <= 0.0 {
+        return Err(FilletError::InvalidRadius);
+    }
+
+    let incoming = direction_at_end(first);
+    let outgoing = direction_at_start(second);
+    let turn = incoming.cross(outgoing);
+
+    if turn.abs() <= 1e-12 {
+        return Err(FilletError::NoCorner);
+    }
+
+    // the fillet sits on the inside of the corner
+    let side = if turn > 0.0 { Side::Left } else { Side::Right };
+    let (a, b) = (
+        first.offset(radius, side).ok_or(FilletError::TooLarge)?,
+        second.offset(radius, side).ok_or(FilletError::TooLarge)?,
+    );
+
+    let corner = first.end().lerp(second.start(), 0.5);
+    let centre = crossings(a, b)
+        .into_iter()
+        .min_by(|p, q| {
+            (*p - corner)
+                .square_length()
+                .partial_cmp(&(*q - corner).square_length())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .ok_or(FilletError::TooLarge)?;
+
+    let start = foot(first, centre);
+    let end = foot(second, centre);
+    let trimmed_first = with_end(first, start);
+    let trimmed_second = with_start(second, end);
+
+    if !same_direction(first, trimmed_first)
+        || !same_direction(second, trimmed_second)
+    {
+        return Err(FilletError::TooLarge);
+    }
+
+    let (from, to) = (start - centre, end - centre);
+    let mut sweep = from.cross(to).atan2(from.dot(to));
+    if sweep * turn < 0.0 {
+        sweep += 2.0 * PI * turn.signum();
+    }
+
+    Ok(Fillet {
+        arc: Arc::from_centre_radius(
+            centre,
+            radius,
+            Angle::radians(from.y.atan2(from.x)),
+            Angle::radians(sweep),
+        ),
+        first: trimmed_first,
+        second: trimmed_second,
+    })
+}
+
+/// The reasons a [`fillet()`] may fail.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FilletError {
+    /// The radius must be greater than zero.
+    InvalidRadius,
+    /// The segments are parallel (or tangent), so there is no corner to
+    /// round off.
+    NoCorner,
+    /// An arc with this radius won't fit between the segments.
+    TooLarge,
+}
+
+impl Display for FilletError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FilletError::InvalidRadius => {
+                write!(f, "The fillet radius must be greater than zero")
+            },
+            FilletError::NoCorner => {
+                write!(f, "The segments don't form a corner")
+            },
+            FilletError::TooLarge => {
+                write!(
+                    f,
+                    "No fillet with that radius fits between the segments"
+                )
+            },
+        }
+    }
+}
+
+impl Error for FilletError {}
+
+/// Which way is the segment travelling when it starts?
+pub(crate) fn direction_at_start<S>(segment: Segment<S>) -> Vector2D<f64, S> {
+    match segment {
+        Segment::Line(line) => line.displacement(),
+        Segment::Arc(arc) => arc_tangent(arc, arc.start()),
+    }
+}
+
+/// Which way is the segment travelling when it ends?
+pub(crate) fn direction_at_end<S>(segment: Segment<S>) -> Vector2D<f64, S> {
+    match segment {
+        Segment::Line(line) => line.displacement(),
+        Segment::Arc(arc) => arc_tangent(arc, arc.end()),
+    }
+}
+
+fn arc_tangent<S>(arc: Arc<S>, point: Point2D<f64, S>) -> Vector2D<f64, S> {
+    let radial = point - arc.centre();
+
+    if arc.is_anticlockwise() {
+        Vector2D::new(-radial.y, radial.x)
+    } else {
+        Vector2D::new(radial.y, -radial.x)
+    }
+}
+
+/// Find where two segments would cross if lines were infinitely long and
+/// arcs were full circles.
+fn crossings<S>(a: Segment<S>, b: Segment<S>) -> Vec<Point2D<f64, S>> {
+    match (a, b) {
+        (Segment::Line(a), Segment::Line(b)) => {
+            XLine::new(a.start, a.displacement())
+                .intersect_xline(XLine::new(b.start, b.displacement()))
+                .into_iter()
+                .collect()
+        },
+        (Segment::Line(line), Segment::Arc(arc))
+        | (Segment::Arc(arc), Segment::Line(line)) => {
+            xline_circle(line.start, line.displacement(), arc)
+        },
+        (Segment::Arc(a), Segment::Arc(b)) => {
+            arc_arc_intersections(full_circle(a), full_circle(b))
+        },
+    }
+}
+
+fn full_circle<S>(arc: Arc<S>) -> Arc<S> {
+    Arc::from_centre_radius(
+        arc.centre(),
+        arc.radius(),
+        Angle::zero(),
+        Angle::two_pi(),
+    )
+}
+
+fn xline_circle<S>(
+    base: Point2D<f64, S>,
+    direction: Vector2D<f64, S>,
+    circle: Arc<S>,
+) -> Vec<Point2D<f64, S>> {
+    let direction = direction.normalize();
+    let foot = base + direction * (circle.centre() - base).dot(direction);
+    let distance = (foot - circle.centre()).length();
+    let radius = circle.radius();
+
+    if distance > radius + 1e-9 {
+        Vec::new()
+    } else {
+        let half_chord =
+            (radius * radius - distance * distance).max(0.0).sqrt();
+        vec![foot - direction * half_chord, foot + direction * half_chord]
+    }
+}
+
+/// The point on a segment's line or circle which is closest to `point`.
+fn foot<S>(segment: Segment<S>, point: Point2D<f64, S>) -> Point2D<f64, S> {
+    match segment {
+        Segment::Line(line) => {
+            let displacement = line.displacement();
+            let t = (point - line.start).dot(displacement)
+                / displacement.square_length();
+            line.start + displacement * t
+        },
+        Segment::Arc(arc) => {
+            arc.centre() + (point - arc.centre()).normalize() * arc.radius()
+        },
+    }
+}
+
+/// Has trimming a segment left it going in the same direction?
+pub(crate) fn same_direction<S>(
+    original: Segment<S>,
+    trimmed: Segment<S>,
+) -> bool {
+    match (original, trimmed) {
+        (Segment::Line(original), Segment::Line(trimmed)) => {
+            original.displacement().dot(trimmed.displacement()) > 0.0
+        },
+        (Segment::Arc(original), Segment::Arc(trimmed)) => {
+            original.sweep_angle().radians * trimmed.sweep_angle().radians > 0.0
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Line;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn fillet_between_perpendicular_lines() {
+        let horizontal =
+            Line::new(Point::new(-10.0, 0.0), Point::new(0.0, 0.0));
+        let vertical = Line::new(Point::new(0.0, 0.0), Point::new(0.0, 10.0));
+
+        let got = fillet(horizontal, vertical, 2.0).unwrap();
+
+        assert!(got.arc.sweep_angle().radians.approx_eq(&(PI / 2.0)));
+        assert!(got.arc.start().approx_eq(&Point::new(-2.0, 0.0)));
+        assert!(got.arc.end().approx_eq(&Point::new(0.0, 2.0)));
+        assert!(got.second.end().approx_eq(&Point::new(0.0, 10.0)));
+    }
+
+    #[test]
+    fn lines_which_dont_meet_are_extended() {
+        let horizontal =
+            Line::new(Point::new(-10.0, 0.0), Point::new(-5.0, 0.0));
+        let vertical = Line::new(Point::new(0.0, 5.0), Point::new(0.0, 10.0));
+
+        let got = fillet(horizontal, vertical, 1.0).unwrap();
+
+        assert!(got.first.end().approx_eq(&Point::new(-1.0, 0.0)));
+        assert!(got.second.start().approx_eq(&Point::new(0.0, 1.0)));
+    }
+
+    #[test]
+    fn fillet_between_a_line_and_an_arc() {
+        let line = Line::new(Point::new(0.0, -10.0), Point::new(0.0, 0.0));
+        let arc = Arc::from_centre_radius(
+            Point::new(0.0, 5.0),
+            5.0,
+            -Angle::frac_pi_2(),
+            Angle::frac_pi_2(),
+        );
+
+        let got = fillet(line, arc, 1.0).unwrap();
+
+        assert!(got.arc.centre().x.approx_eq(&1.0));
+        assert!(got.arc.start().approx_eq(&got.first.end()));
+        assert!(got.arc.end().approx_eq(&got.second.start()));
+        let on_circle = (got.second.start() - arc.centre()).length();
+        assert!(on_circle.approx_eq(&5.0));
+        assert!(got.arc.is_clockwise());
+    }
+
+    #[test]
+    fn report_when_a_fillet_isnt_possible() {
+        let short = Line::new(Point::new(-1.0, 0.0), Point::new(0.0, 0.0));
+        let vertical = Line::new(Point::new(0.0, 0.0), Point::new(0.0, 10.0));
+        let parallel = Line::new(Point::new(0.0, 1.0), Point::new(5.0, 1.0));
+
+        assert_eq!(
+            fillet(short, vertical, 5.0).unwrap_err(),
+            FilletError::TooLarge
+        );
+        assert_eq!(
+            fillet(short, parallel, 1.0).unwrap_err(),
+            FilletError::NoCorner
+        );
+        assert_eq!(
+            fillet(short, vertical, 0.0).unwrap_err(),
+            FilletError::InvalidRadius
+        );
+    }
+}
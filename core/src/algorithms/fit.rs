@@ -0,0 +1,448 @@
+//! Finding the [`Line`], [`Circle`] or [`Arc`] which best fits a set of
+//! points (e.g. when reverse engineering a drawing from scanned or digitised
+//! data).
+//!
+//! Each fit minimises the sum of the squared distances from the points to
+//! the shape, and the [`Fit`] reports how far each point ended up from it.
+
+use crate::{
+    primitives::{Arc, Circle, Line},
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+use std::f64::consts::PI;
+
+/// The maximum number of refinement steps taken when fitting a circle.
+const MAX_ITERATIONS: usize = 50;
+
+/// The best-fit shape for a set of points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fit<T> {
+    /// The fitted shape.
+    pub shape: T,
+    /// The signed distance from each point to the shape, in the same order
+    /// as the points were provided.
+    ///
+    /// Points to the left of a [`Line`], or outside a [`Circle`] or
+    /// [`Arc`], have a positive residual.
+    pub residuals: Vec<f64>,
+}
+
+impl<T> Fit<T> {
+    /// The distance from the shape to the point furthest away from it.
+    pub fn max_error(&self) -> f64 {
+        self.residuals.iter().fold(0.0, |max, r| r.abs().max(max))
+    }
+
+    /// The root-mean-square distance from the points to the shape.
+    pub fn rms_error(&self) -> f64 {
+        if self.residuals.is_empty() {
+            return 0.0;
+        }
+
+        let sum_of_squares: f64 = self.residuals.iter().map(|r| r * r).sum();
+        (sum_of_squares / self.residuals.len() as f64).sqrt()
+    }
+}
+
+/// Find the [`Line`] which best fits a set of points, using orthogonal
+/// (total) least squares.
+///
+/// The line runs between the projections of the two outermost points, in
+/// the direction the points were provided. This returns [`None`] if there
+/// are less than two distinct points.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::algorithms::fit;
+/// use euclid::approxeq::ApproxEq;
+///
+/// let points = [
+///     Point::new(0.0, 0.1),
+///     Point::new(1.0, -0.1),
+///     Point::new(2.0, -0.1),
+///     Point::new(3.0, 0.1),
+/// ];
+///
+/// let got = fit::line(&points).unwrap();
+///
+/// assert!(got.shape.start.approx_eq(&Point::new(0.0, 0.0)));
+/// assert!(got.shape.end.approx_eq(&Point::new(3.0, 0.0)));
+/// assert!(got.max_error().approx_eq(&0.1));
+/// ```
+pub fn line<S>(points: &[Point2D<f64, S>]) -> Option<Fit<Line<S>>> {
+    let centroid = centroid(points)?;
+
+    let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+    for &point in points {
+        let d = point - centroid;
+        xx += d.x * d.x;
+        xy += d.x * d.y;
+        yy += d.y * d.y;
+    }
+
+    if xx + yy == 0.0 {
+        // all the points are in the same spot
+        return None;
+    }
+
+    // the principal axis of the points' covariance
+    let angle = 0.5 * (2.0 * xy).atan2(xx - yy);
+    let mut direction = Vector2D::new(angle.cos(), angle.sin());
+
+    let first = points[0] - centroid;
+    let last = points[points.len() - 1] - centroid;
+    if direction.dot(last - first) < 0.0 {
+        direction = -direction;
+    }
+
+    let along = |point: Point2D<f64, S>| direction.dot(point - centroid);
+    let (min, max) = points.iter().fold(
+        (std::f64::INFINITY, std::f64::NEG_INFINITY),
+        |(min, max), &point| (min.min(along(point)), max.max(along(point))),
+    );
+
+    let normal = Vector2D::new(-direction.y, direction.x);
+    let residuals = points
+        .iter()
+        .map(|&point| normal.dot(point - centroid))
+        .collect();
+
+    Some(Fit {
+        shape: Line::new(
+            centroid + direction * min,
+            centroid + direction * max,
+        ),
+        residuals,
+    })
+}
+
+/// Find the [`Circle`] which best fits a set of points.
+///
+/// An algebraic fit is used to get a first guess, which is then refined to
+/// minimise the actual distances from the points to the circle. This
+/// returns [`None`] if there are less than three points or they are all in
+/// a straight line.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::algorithms::fit;
+/// use euclid::approxeq::ApproxEq;
+///
+/// let points: Vec<Point> = (0..8)
+///     .map(|i| {
+///         let angle = i as f64 * std::f64::consts::PI / 4.0;
+///         Point::new(3.0 + 2.0 * angle.cos(), -1.0 + 2.0 * angle.sin())
+///     })
+///     .collect();
+///
+/// let got = fit::circle(&points).unwrap();
+///
+/// assert!(got.shape.centre.approx_eq(&Point::new(3.0, -1.0)));
+/// assert!(got.shape.radius.approx_eq(&2.0));
+/// assert!(got.max_error().approx_eq(&0.0));
+/// ```
+pub fn circle<S>(points: &[Point2D<f64, S>]) -> Option<Fit<Circle<S>>> {
+    if points.len() < 3 {
+        return None;
+    }
+
+    let centroid = centroid(points)?;
+    let mut circle = algebraic_circle(points, centroid)?;
+
+    // Gauss-Newton iterations on the geometric distances
+    for _ in 0..MAX_ITERATIONS {
+        let mut normal_equations = [[0.0; 3]; 3];
+        let mut rhs = [0.0; 3];
+
+        for &point in points {
+            let offset = point - circle.centre;
+            let distance = offset.length();
+            if distance == 0.0 {
+                continue;
+            }
+
+            let jacobian = [-offset.x / distance, -offset.y / distance, -1.0];
+            let residual = distance - circle.radius;
+
+            for row in 0..3 {
+                for column in 0..3 {
+                    normal_equations[row][column] +=
+                        jacobian[row] * jacobian[column];
+                }
+                rhs[row] -= jacobian[row] * residual;
+            }
+        }
+
+        let step = match solve_3x3(normal_equations, rhs) {
+            Some(step) => step,
+            None => break,
+        };
+
+        circle.centre += Vector2D::new(step[0], step[1]);
+        circle.radius += step[2];
+
+        let size = circle.radius.abs().max(1.0);
+        if step
+            .iter()
+            .all(|s| s.abs() <= std::f64::EPSILON * 16.0 * size)
+        {
+            break;
+        }
+    }
+
+    if !circle.radius.is_finite() || circle.radius <= 0.0 {
+        return None;
+    }
+
+    let residuals = points
+        .iter()
+        .map(|&point| (point - circle.centre).length() - circle.radius)
+        .collect();
+
+    Some(Fit {
+        shape: circle,
+        residuals,
+    })
+}
+
+/// Find the [`Arc`] which best fits a set of points.
+///
+/// The points are fitted to a [`Circle`] (see [`circle()`]) and the arc
+/// covers all of them, leaving out the largest gap between neighbouring
+/// points. The arc goes in the same direction as the points were provided.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::algorithms::fit;
+/// use euclid::approxeq::ApproxEq;
+///
+/// let points = [
+///     Point::new(1.0, 0.0),
+///     Point::new(0.0, 1.0),
+///     Point::new(-1.0, 0.0),
+/// ];
+///
+/// let got = fit::arc(&points).unwrap();
+///
+/// assert!(got.shape.start().approx_eq(&points[0]));
+/// assert!(got.shape.end().approx_eq(&points[2]));
+/// assert!(got.shape.is_anticlockwise());
+/// ```
+pub fn arc<S>(points: &[Point2D<f64, S>]) -> Option<Fit<Arc<S>>> {
+    let Fit { shape, residuals } = circle(points)?;
+
+    let angle_of = |point: Point2D<f64, S>| {
+        let offset = point - shape.centre;
+        offset.y.atan2(offset.x).rem_euclid(2.0 * PI)
+    };
+
+    let mut angles: Vec<f64> = points.iter().map(|&p| angle_of(p)).collect();
+    angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // the arc starts just after the biggest gap and goes around to the
+    // point just before it
+    let mut start = angles[0];
+    let mut biggest_gap = angles[0] + 2.0 * PI - angles[angles.len() - 1];
+    for pair in angles.windows(2) {
+        if pair[1] - pair[0] > biggest_gap {
+            biggest_gap = pair[1] - pair[0];
+            start = pair[1];
+        }
+    }
+    let sweep = 2.0 * PI - biggest_gap;
+
+    // work out which way the points go by looking at how far the first
+    // and last points are from the start of the anticlockwise arc
+    let progress =
+        |point: Point2D<f64, S>| (angle_of(point) - start).rem_euclid(2.0 * PI);
+    let anticlockwise =
+        progress(points[0]) <= progress(points[points.len() - 1]);
+
+    let arc = if anticlockwise {
+        Arc::from_centre_radius(
+            shape.centre,
+            shape.radius,
+            Angle::radians(start),
+            Angle::radians(sweep),
+        )
+    } else {
+        Arc::from_centre_radius(
+            shape.centre,
+            shape.radius,
+            Angle::radians(start + sweep),
+            Angle::radians(-sweep),
+        )
+    };
+
+    Some(Fit {
+        shape: arc,
+        residuals,
+    })
+}
+
+fn centroid<S>(points: &[Point2D<f64, S>]) -> Option<Point2D<f64, S>> {
+    if points.is_empty() {
+        return None;
+    }
+
+    let sum = points
+        .iter()
+        .fold(Vector2D::zero(), |sum, point| sum + point.to_vector());
+
+    Some((sum / points.len() as f64).to_point())
+}
+
+/// Fit `x² + y² + Dx + Ey + F = 0` using ordinary least squares, working
+/// relative to the centroid to keep the numbers well conditioned.
+fn algebraic_circle<S>(
+    points: &[Point2D<f64, S>],
+    centroid: Point2D<f64, S>,
+) -> Option<Circle<S>> {
+    let mut normal_equations = [[0.0; 3]; 3];
+    let mut rhs = [0.0; 3];
+
+    for &point in points {
+        let d = point - centroid;
+        let row = [d.x, d.y, 1.0];
+        let target = -(d.x * d.x + d.y * d.y);
+
+        for i in 0..3 {
+            for j in 0..3 {
+                normal_equations[i][j] += row[i] * row[j];
+            }
+            rhs[i] += row[i] * target;
+        }
+    }
+
+    let [d, e, f] = solve_3x3(normal_equations, rhs)?;
+    let offset = Vector2D::new(-d / 2.0, -e / 2.0);
+    let radius_squared = offset.square_length() - f;
+
+    if radius_squared <= 0.0 || !radius_squared.is_finite() {
+        return None;
+    }
+
+    Some(Circle::new(centroid + offset, radius_squared.sqrt()))
+}
+
+/// Solve `a · x = b` using Cramer's rule.
+fn solve_3x3(a: [[f64; 3]; 3], b: [f64; 3]) -> Option<[f64; 3]> {
+    let determinant = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+
+    let det = determinant(a);
+    let scale: f64 = a.iter().flatten().map(|v| v.abs()).fold(0.0, f64::max);
+
+    if det.abs() <= 1e-12 * scale.powi(3) || !det.is_finite() {
+        return None;
+    }
+
+    let mut solution = [0.0; 3];
+    for (column, value) in solution.iter_mut().enumerate() {
+        let mut replaced = a;
+        for row in 0..3 {
+            replaced[row][column] = b[row];
+        }
+        *value = determinant(replaced) / det;
+    }
+
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn fit_a_vertical_line() {
+        let points = [
+            Point::new(1.0, 5.0),
+            Point::new(1.0, 3.0),
+            Point::new(1.0, 4.0),
+        ];
+
+        let got = line(&points).unwrap();
+
+        assert!(got.shape.start.approx_eq(&Point::new(1.0, 5.0)));
+        assert!(got.shape.end.approx_eq(&Point::new(1.0, 3.0)));
+        assert!(got.max_error().approx_eq(&0.0));
+    }
+
+    #[test]
+    fn lines_need_two_distinct_points() {
+        assert!(line::<euclid::UnknownUnit>(&[]).is_none());
+        assert!(line(&[Point::new(1.0, 1.0), Point::new(1.0, 1.0)]).is_none());
+    }
+
+    #[test]
+    fn collinear_points_dont_fit_a_circle() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+            Point::new(3.0, 3.0),
+        ];
+
+        assert!(circle(&points).is_none());
+    }
+
+    #[test]
+    fn noisy_circle_far_from_the_origin() {
+        let noise = [0.01, -0.02, 0.015, -0.005, 0.0, 0.02, -0.01, -0.015];
+        let points: Vec<Point> = noise
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let angle = i as f64 * PI / 4.0;
+                let radius = 5.0 + n;
+                Point::new(
+                    1000.0 + radius * angle.cos(),
+                    2000.0 + radius * angle.sin(),
+                )
+            })
+            .collect();
+
+        let got = circle(&points).unwrap();
+
+        assert!(got.shape.centre.approx_eq_eps(
+            &Point::new(1000.0, 2000.0),
+            &Point::new(0.02, 0.02)
+        ));
+        assert!(got.shape.radius.approx_eq_eps(&5.0, &0.02));
+        assert!(got.rms_error() < 0.02);
+        assert_eq!(got.residuals.len(), points.len());
+    }
+
+    #[test]
+    fn clockwise_arc_across_the_x_axis() {
+        // points going clockwise from the top of the circle, through the
+        // right hand side, to the bottom
+        let points: Vec<Point> = [90.0_f64, 45.0, 0.0, -45.0, -90.0]
+            .iter()
+            .map(|degrees| {
+                let (sin, cos) = degrees.to_radians().sin_cos();
+                Point::new(cos, sin)
+            })
+            .collect();
+
+        let got = arc(&points).unwrap().shape;
+
+        assert!(got.is_clockwise());
+        assert!(got.sweep_angle().radians.approx_eq(&-PI));
+        assert!(got.start().approx_eq(&points[0]));
+        assert!(got.end().approx_eq(&points[4]));
+    }
+}
@@ -0,0 +1,879 @@
+use crate::{
+    primitives::{Arc, Line, Polyline, Segment},
+    ApproxEqWithin, Tolerance,
+};
+use euclid::{Point2D, Vector2D};
+use std::{f64::consts::PI, iter::FromIterator, vec::IntoIter};
+
+/// How close two things need to be before they are considered to be
+/// touching, when no [`Tolerance`] is given.
+pub const DEFAULT_TOLERANCE: f64 = 1e-9;
+
+/// Something which can be intersected with a `Rhs`.
+pub trait Intersect<Space, Rhs: ?Sized = Self> {
+    /// Find everywhere `self` and `other` touch, using the default
+    /// [`Tolerance`].
+    fn intersect(&self, other: &Rhs) -> IntersectionSet<Space> {
+        self.intersect_within(other, Tolerance::default())
+    }
+
+    /// Find everywhere `self` and `other` touch, treating things within
+    /// `tolerance` of each other as touching.
+    fn intersect_within(
+        &self,
+        other: &Rhs,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<Space>;
+}
+
+/// The places where two objects intersect.
+///
+/// Points within the set's [`Tolerance`] of each other are only recorded
+/// once. When two objects overlap (e.g. collinear lines or arcs on the same
+/// circle) the ends of the overlapping section are recorded as points and
+/// [`IntersectionSet::has_overlap()`] is set.
+#[derive(Debug, PartialEq)]
+pub struct IntersectionSet<S> {
+    points: Vec<Point2D<f64, S>>,
+    overlap: bool,
+    tolerance: Tolerance,
+}
+
+impl<S> IntersectionSet<S> {
+    /// Create an empty [`IntersectionSet`] which uses the default
+    /// [`Tolerance`].
+    pub fn new() -> Self {
+        IntersectionSet::with_tolerance(Tolerance::default())
+    }
+
+    /// Create an empty [`IntersectionSet`] which merges points within
+    /// `tolerance` of each other.
+    pub fn with_tolerance(tolerance: Tolerance) -> Self {
+        IntersectionSet {
+            points: Vec::new(),
+            overlap: false,
+            tolerance,
+        }
+    }
+
+    /// The intersection points.
+    pub fn points(&self) -> &[Point2D<f64, S>] { &self.points }
+
+    /// How many intersection points are there?
+    pub fn len(&self) -> usize { self.points.len() }
+
+    /// Do the objects not touch at all?
+    pub fn is_empty(&self) -> bool { self.points.is_empty() }
+
+    /// Do the objects overlap along part of their length, rather than just
+    /// crossing at points?
+    pub fn has_overlap(&self) -> bool { self.overlap }
+
+    /// Record an intersection point, unless it has already been found.
+    pub fn push(&mut self, point: Point2D<f64, S>) {
+        let duplicate = self
+            .points
+            .iter()
+            .any(|existing| existing.approx_eq_within(&point, self.tolerance));
+
+        if !duplicate {
+            self.points.push(point);
+        }
+    }
+
+    /// Merge another set of intersections into this one.
+    pub fn append(&mut self, other: IntersectionSet<S>) {
+        self.overlap |= other.overlap;
+        for point in other.points {
+            self.push(point);
+        }
+    }
+
+    /// Mark the objects as overlapping.
+    pub fn set_overlap(&mut self) { self.overlap = true; }
+
+    /// Find the intersection point closest to `target`.
+    pub fn nearest_to(
+        &self,
+        target: Point2D<f64, S>,
+    ) -> Option<Point2D<f64, S>> {
+        self.points.iter().copied().min_by(|a, b| {
+            (*a - target)
+                .square_length()
+                .partial_cmp(&(*b - target).square_length())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+}
+
+impl<S> Default for IntersectionSet<S> {
+    fn default() -> Self { IntersectionSet::new() }
+}
+
+impl<S> Clone for IntersectionSet<S> {
+    fn clone(&self) -> Self {
+        IntersectionSet {
+            points: self.points.clone(),
+            overlap: self.overlap,
+            tolerance: self.tolerance,
+        }
+    }
+}
+
+impl<S> FromIterator<Point2D<f64, S>> for IntersectionSet<S> {
+    fn from_iter<I: IntoIterator<Item = Point2D<f64, S>>>(iter: I) -> Self {
+        let mut set = IntersectionSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<S> Extend<Point2D<f64, S>> for IntersectionSet<S> {
+    fn extend<I: IntoIterator<Item = Point2D<f64, S>>>(&mut self, iter: I) {
+        for point in iter {
+            self.push(point);
+        }
+    }
+}
+
+impl<S> IntoIterator for IntersectionSet<S> {
+    type IntoIter = IntoIter<Point2D<f64, S>>;
+    type Item = Point2D<f64, S>;
+
+    fn into_iter(self) -> Self::IntoIter { self.points.into_iter() }
+}
+
+impl<S> From<LineIntersection<S>> for IntersectionSet<S> {
+    fn from(intersection: LineIntersection<S>) -> Self {
+        let mut set = IntersectionSet::new();
+
+        match intersection {
+            LineIntersection::Disjoint | LineIntersection::Parallel => {},
+            LineIntersection::Point { point, .. } => set.push(point),
+            LineIntersection::Overlap { line, .. } => {
+                set.push(line.start);
+                set.push(line.end);
+                set.set_overlap();
+            },
+        }
+
+        set
+    }
+}
+
+impl<S> Intersect<S> for Line<S> {
+    fn intersect_within(
+        &self,
+        other: &Line<S>,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<S> {
+        let mut set = IntersectionSet::with_tolerance(tolerance);
+        set.append(
+            intersection_with_tolerance(*self, *other, tolerance).into(),
+        );
+        set
+    }
+}
+
+impl<S> Intersect<S, Arc<S>> for Line<S> {
+    fn intersect_within(
+        &self,
+        other: &Arc<S>,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<S> {
+        let mut set = IntersectionSet::with_tolerance(tolerance);
+        set.extend(line_arc_intersections_with_tolerance(
+            *self, *other, tolerance,
+        ));
+        set
+    }
+}
+
+impl<S> Intersect<S, Line<S>> for Arc<S> {
+    fn intersect_within(
+        &self,
+        other: &Line<S>,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<S> {
+        other.intersect_within(self, tolerance)
+    }
+}
+
+impl<S> Intersect<S> for Arc<S> {
+    fn intersect_within(
+        &self,
+        other: &Arc<S>,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<S> {
+        let mut set = IntersectionSet::with_tolerance(tolerance);
+        set.extend(arc_arc_intersections_with_tolerance(
+            *self, *other, tolerance,
+        ));
+
+        let same_circle =
+            self.centre().approx_eq_within(&other.centre(), tolerance)
+                && self.radius().approx_eq_within(&other.radius(), tolerance);
+        if same_circle && set.len() > 1 {
+            set.set_overlap();
+        }
+
+        set
+    }
+}
+
+impl<S> Intersect<S> for Segment<S> {
+    fn intersect_within(
+        &self,
+        other: &Segment<S>,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<S> {
+        match (self, other) {
+            (Segment::Line(a), Segment::Line(b)) => {
+                a.intersect_within(b, tolerance)
+            },
+            (Segment::Line(a), Segment::Arc(b)) => {
+                a.intersect_within(b, tolerance)
+            },
+            (Segment::Arc(a), Segment::Line(b)) => {
+                a.intersect_within(b, tolerance)
+            },
+            (Segment::Arc(a), Segment::Arc(b)) => {
+                a.intersect_within(b, tolerance)
+            },
+        }
+    }
+}
+
+impl<S> Intersect<S> for [Segment<S>] {
+    fn intersect_within(
+        &self,
+        other: &[Segment<S>],
+        tolerance: Tolerance,
+    ) -> IntersectionSet<S> {
+        let mut set = IntersectionSet::with_tolerance(tolerance);
+
+        for a in self {
+            for b in other {
+                set.append(a.intersect_within(b, tolerance));
+            }
+        }
+
+        set
+    }
+}
+
+impl<S> Intersect<S> for Polyline<S> {
+    fn intersect_within(
+        &self,
+        other: &Polyline<S>,
+        tolerance: Tolerance,
+    ) -> IntersectionSet<S> {
+        let first: Vec<_> = self.segments().collect();
+        let second: Vec<_> = other.segments().collect();
+
+        first[..].intersect_within(&second[..], tolerance)
+    }
+}
+
+/// The result of intersecting two [`Line`] segments.
+#[derive(Debug, PartialEq)]
+pub enum LineIntersection<S> {
+    /// The lines aren't parallel, but they don't touch either.
+    Disjoint,
+    /// The lines are parallel and don't touch.
+    Parallel,
+    /// The lines cross (or touch) at a single point.
+    Point {
+        /// Where the lines intersect.
+        point: Point2D<f64, S>,
+        /// How far along the first line the point is, where `0.0` is the
+        /// start and `1.0` is the end.
+        first: f64,
+        /// How far along the second line the point is.
+        second: f64,
+    },
+    /// The lines are collinear and overlap.
+    Overlap {
+        /// The section both lines have in common, going in the same
+        /// direction as the first line.
+        line: Line<S>,
+        /// Where the overlap starts and ends along the first line.
+        first: (f64, f64),
+        /// Where the overlap starts and ends along the second line.
+        second: (f64, f64),
+    },
+}
+
+impl<S> LineIntersection<S> {
+    /// Do the lines touch at all?
+    pub fn is_some(&self) -> bool {
+        match self {
+            LineIntersection::Disjoint | LineIntersection::Parallel => false,
+            LineIntersection::Point { .. }
+            | LineIntersection::Overlap { .. } => true,
+        }
+    }
+}
+
+impl<S> Copy for LineIntersection<S> {}
+
+impl<S> Clone for LineIntersection<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Find where two [`Line`] segments intersect, using the default
+/// [`Tolerance`].
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::{intersection, LineIntersection},
+///     primitives::Line,
+/// };
+///
+/// let horizontal = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+/// let vertical = Line::new(Point::new(1.0, -1.0), Point::new(1.0, 3.0));
+///
+/// assert_eq!(
+///     intersection(horizontal, vertical),
+///     LineIntersection::Point {
+///         point: Point::new(1.0, 0.0),
+///         first: 0.25,
+///         second: 0.25,
+///     },
+/// );
+/// ```
+pub fn intersection<S>(first: Line<S>, second: Line<S>) -> LineIntersection<S> {
+    intersection_with_tolerance(first, second, Tolerance::default())
+}
+
+/// Find where two [`Line`] segments intersect, treating points within
+/// `tolerance` of each other as touching.
+pub fn intersection_with_tolerance<S>(
+    first: Line<S>,
+    second: Line<S>,
+    tolerance: Tolerance,
+) -> LineIntersection<S> {
+    let tolerance = tolerance.linear;
+    let d = first.displacement();
+    let e = second.displacement();
+    let (first_length, second_length) = (d.length(), e.length());
+
+    // degenerate lines are just points
+    if first_length <= tolerance {
+        return point_on_line(first.start, second, tolerance)
+            .map(|u| LineIntersection::Point {
+                point: first.start,
+                first: 0.0,
+                second: u,
+            })
+            .unwrap_or(LineIntersection::Disjoint);
+    }
+    if second_length <= tolerance {
+        return point_on_line(second.start, first, tolerance)
+            .map(|t| LineIntersection::Point {
+                point: second.start,
+                first: t,
+                second: 0.0,
+            })
+            .unwrap_or(LineIntersection::Disjoint);
+    }
+
+    let delta = second.start - first.start;
+    let denominator = d.cross(e);
+
+    // the perpendicular distance between a line and the second line's
+    // ends, used to decide whether the lines are parallel
+    let start_offset = d.cross(delta) / first_length;
+    let end_offset = d.cross(second.end - first.start) / first_length;
+
+    if (start_offset - end_offset).abs() <= tolerance {
+        if start_offset.abs() > tolerance {
+            return LineIntersection::Parallel;
+        }
+
+        return collinear_overlap(first, second, tolerance);
+    }
+
+    let t = delta.cross(e) / denominator;
+    let u = delta.cross(d) / denominator;
+
+    let t_tolerance = tolerance / first_length;
+    let u_tolerance = tolerance / second_length;
+
+    if within_unit_range(t, t_tolerance) && within_unit_range(u, u_tolerance) {
+        let (t, u) = (t.max(0.0).min(1.0), u.max(0.0).min(1.0));
+
+        LineIntersection::Point {
+            point: first.start + d * t,
+            first: t,
+            second: u,
+        }
+    } else {
+        LineIntersection::Disjoint
+    }
+}
+
+/// Find the points where a [`Line`] segment crosses an [`Arc`], using the
+/// default [`Tolerance`].
+///
+/// There will be at most 2 points, ordered by how far along the line they
+/// are. A line which is tangent to the arc touches it at a single point.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::line_arc_intersections,
+///     primitives::{Arc, Line},
+///     Angle,
+/// };
+///
+/// // the top half of a unit circle
+/// let arc =
+///     Arc::from_centre_radius(Point::zero(), 1.0, Angle::zero(), Angle::pi());
+/// let line = Line::new(Point::new(-2.0, 0.0), Point::new(2.0, 0.0));
+///
+/// let got = line_arc_intersections(line, arc);
+///
+/// assert_eq!(got, vec![Point::new(-1.0, 0.0), Point::new(1.0, 0.0)]);
+/// ```
+pub fn line_arc_intersections<S>(
+    line: Line<S>,
+    arc: Arc<S>,
+) -> Vec<Point2D<f64, S>> {
+    line_arc_intersections_with_tolerance(line, arc, Tolerance::default())
+}
+
+/// Find the points where a [`Line`] segment crosses an [`Arc`], treating
+/// points within `tolerance` of each other as touching.
+pub fn line_arc_intersections_with_tolerance<S>(
+    line: Line<S>,
+    arc: Arc<S>,
+    tolerance: Tolerance,
+) -> Vec<Point2D<f64, S>> {
+    let tolerance = tolerance.linear;
+    let d = line.displacement();
+    let length = d.length();
+
+    if length <= tolerance {
+        return if arc_parameter(arc, line.start, tolerance).is_some() {
+            vec![line.start]
+        } else {
+            Vec::new()
+        };
+    }
+
+    // solve |start - centre + t*d| = radius for t
+    let f = line.start - arc.centre();
+    let radius = arc.radius();
+    let closest_approach = -f.dot(d) / d.square_length();
+    let distance_from_centre = (f.cross(d) / length).abs();
+
+    let params = if (distance_from_centre - radius).abs() <= tolerance {
+        vec![closest_approach]
+    } else if distance_from_centre > radius {
+        Vec::new()
+    } else {
+        let half_chord = (radius * radius
+            - distance_from_centre * distance_from_centre)
+            .sqrt()
+            / length;
+        vec![closest_approach - half_chord, closest_approach + half_chord]
+    };
+
+    let t_tolerance = tolerance / length;
+
+    params
+        .into_iter()
+        .filter(|&t| within_unit_range(t, t_tolerance))
+        .map(|t| line.start + d * t.max(0.0).min(1.0))
+        .filter(|&point| arc_parameter(arc, point, tolerance).is_some())
+        .collect()
+}
+
+/// Find the points where two [`Arc`]s cross, using the default
+/// [`Tolerance`].
+///
+/// There will be at most 2 points, with arcs which touch tangentially only
+/// having 1. Arcs which lie on the same circle can overlap along a section
+/// of their length, in which case the ends of the overlapping sections are
+/// returned.
+pub fn arc_arc_intersections<S>(
+    first: Arc<S>,
+    second: Arc<S>,
+) -> Vec<Point2D<f64, S>> {
+    arc_arc_intersections_with_tolerance(first, second, Tolerance::default())
+}
+
+/// Find the points where two [`Arc`]s cross, treating points within
+/// `tolerance` of each other as touching.
+pub fn arc_arc_intersections_with_tolerance<S>(
+    first: Arc<S>,
+    second: Arc<S>,
+    tolerance: Tolerance,
+) -> Vec<Point2D<f64, S>> {
+    let tolerance = tolerance.linear;
+    let between_centres = second.centre() - first.centre();
+    let distance = between_centres.length();
+    let (r1, r2) = (first.radius(), second.radius());
+
+    let candidates = if distance <= tolerance {
+        if (r1 - r2).abs() > tolerance {
+            // concentric circles never touch
+            return Vec::new();
+        }
+
+        // the same circle, so the overlap starts and ends with the arcs'
+        // end points
+        vec![first.start(), first.end(), second.start(), second.end()]
+    } else if distance > r1 + r2 + tolerance
+        || distance < (r1 - r2).abs() - tolerance
+    {
+        Vec::new()
+    } else {
+        // how far along the line between centres the chord joining the
+        // intersections is
+        let a = (distance * distance + r1 * r1 - r2 * r2) / (2.0 * distance);
+        let half_chord = (r1 * r1 - a * a).max(0.0).sqrt();
+        let direction = between_centres / distance;
+        let chord_midpoint = first.centre() + direction * a;
+
+        if half_chord <= tolerance {
+            vec![chord_midpoint]
+        } else {
+            let perpendicular = Vector2D::new(-direction.y, direction.x);
+            vec![
+                chord_midpoint + perpendicular * half_chord,
+                chord_midpoint - perpendicular * half_chord,
+            ]
+        }
+    };
+
+    let mut points: Vec<Point2D<f64, S>> = Vec::new();
+
+    for point in candidates {
+        let on_both = arc_parameter(first, point, tolerance).is_some()
+            && arc_parameter(second, point, tolerance).is_some();
+        let duplicate = points
+            .iter()
+            .any(|&existing| (existing - point).length() <= tolerance);
+
+        if on_both && !duplicate {
+            points.push(point);
+        }
+    }
+
+    points
+}
+
+/// If a point lies on an [`Arc`], how far along is it (where `0.0` is the
+/// start and `1.0` is the end)?
+pub(crate) fn arc_parameter<S>(
+    arc: Arc<S>,
+    point: Point2D<f64, S>,
+    tolerance: f64,
+) -> Option<f64> {
+    let radial = point - arc.centre();
+
+    if (radial.length() - arc.radius()).abs() > tolerance {
+        return None;
+    }
+
+    let sweep = arc.sweep_angle().radians;
+    if sweep.abs() >= 2.0 * PI {
+        let angle = radial.y.atan2(radial.x) - arc.start_angle().radians;
+        return Some(angle.rem_euclid(2.0 * PI) / sweep.abs());
+    }
+
+    // measure the angle from the start, in the direction the arc travels
+    let mut angle = (radial.y.atan2(radial.x) - arc.start_angle().radians)
+        .rem_euclid(2.0 * PI);
+    if sweep < 0.0 {
+        angle = (2.0 * PI - angle).rem_euclid(2.0 * PI);
+    }
+
+    let angular_tolerance = tolerance / arc.radius().max(tolerance);
+    if angle >= 2.0 * PI - angular_tolerance {
+        // just before the start
+        angle -= 2.0 * PI;
+    }
+
+    if -angular_tolerance <= angle && angle <= sweep.abs() + angular_tolerance {
+        Some((angle / sweep.abs()).max(0.0).min(1.0))
+    } else {
+        None
+    }
+}
+
+fn within_unit_range(value: f64, tolerance: f64) -> bool {
+    -tolerance <= value && value <= 1.0 + tolerance
+}
+
+/// If `point` is on the `line`, how far along is it?
+fn point_on_line<S>(
+    point: Point2D<f64, S>,
+    line: Line<S>,
+    tolerance: f64,
+) -> Option<f64> {
+    let displacement = line.displacement();
+    let length_squared = displacement.square_length();
+
+    if length_squared <= tolerance * tolerance {
+        return if (point - line.start).length() <= tolerance {
+            Some(0.0)
+        } else {
+            None
+        };
+    }
+
+    let t = (point - line.start).dot(displacement) / length_squared;
+    let t = t.max(0.0).min(1.0);
+
+    if (line.start + displacement * t - point).length() <= tolerance {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+fn collinear_overlap<S>(
+    first: Line<S>,
+    second: Line<S>,
+    tolerance: f64,
+) -> LineIntersection<S> {
+    let d = first.displacement();
+    let length_squared = d.square_length();
+    let parameter_of =
+        |point: Point2D<f64, S>| (point - first.start).dot(d) / length_squared;
+
+    // where the second line's ends are, in terms of the first line
+    let (a, b) = (parameter_of(second.start), parameter_of(second.end));
+    let start = a.min(b).max(0.0);
+    let end = a.max(b).min(1.0);
+    let t_tolerance = tolerance / length_squared.sqrt();
+
+    if end - start < -t_tolerance {
+        return LineIntersection::Parallel;
+    }
+
+    let second_parameter = |t: f64| (t - a) / (b - a);
+
+    if end - start <= t_tolerance {
+        // the lines only touch end-to-end
+        let t = (start + end) / 2.0;
+
+        return LineIntersection::Point {
+            point: first.start + d * t,
+            first: t,
+            second: second_parameter(t).max(0.0).min(1.0),
+        };
+    }
+
+    LineIntersection::Overlap {
+        line: Line::new(first.start + d * start, first.start + d * end),
+        first: (start, end),
+        second: (second_parameter(start), second_parameter(end)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::{approxeq::ApproxEq, UnknownUnit};
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn lines_which_miss() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(1.0, 1.0));
+        let second = Line::new(Point::new(3.0, 0.0), Point::new(3.0, 1.0));
+
+        assert_eq!(intersection(first, second), LineIntersection::Disjoint);
+    }
+
+    #[test]
+    fn parallel_lines() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0));
+        let offset = Line::new(Point::new(0.0, 1.0), Point::new(2.0, 1.0));
+        let collinear = Line::new(Point::new(3.0, 0.0), Point::new(4.0, 0.0));
+
+        assert_eq!(intersection(first, offset), LineIntersection::Parallel);
+        assert_eq!(intersection(first, collinear), LineIntersection::Parallel);
+    }
+
+    #[test]
+    fn collinear_lines_which_overlap() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        let second = Line::new(Point::new(6.0, 0.0), Point::new(2.0, 0.0));
+
+        let got = intersection(first, second);
+
+        assert_eq!(
+            got,
+            LineIntersection::Overlap {
+                line: Line::new(Point::new(2.0, 0.0), Point::new(4.0, 0.0)),
+                first: (0.5, 1.0),
+                second: (1.0, 0.5),
+            }
+        );
+    }
+
+    #[test]
+    fn lines_which_touch_end_to_end() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0));
+        let collinear = Line::new(Point::new(2.0, 0.0), Point::new(3.0, 0.0));
+        let corner = Line::new(Point::new(2.0, 0.0), Point::new(2.0, 5.0));
+
+        let expected = LineIntersection::Point {
+            point: Point::new(2.0, 0.0),
+            first: 1.0,
+            second: 0.0,
+        };
+        assert_eq!(intersection(first, collinear), expected);
+        assert_eq!(intersection(first, corner), expected);
+    }
+
+    #[test]
+    fn nearly_touching_lines_are_within_tolerance() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0));
+        let second = Line::new(Point::new(1.0, 0.001), Point::new(1.0, 5.0));
+
+        assert_eq!(intersection(first, second), LineIntersection::Disjoint);
+        assert!(
+            intersection_with_tolerance(first, second, 0.01.into()).is_some()
+        );
+    }
+
+    fn arc(
+        x: f64,
+        y: f64,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    ) -> Arc<UnknownUnit> {
+        Arc::from_centre_radius(
+            Point::new(x, y),
+            radius,
+            Angle::degrees(start),
+            Angle::degrees(sweep),
+        )
+    }
+
+    fn assert_points_eq(got: &[Point], expected: &[Point]) {
+        assert_eq!(got.len(), expected.len(), "{:?} != {:?}", got, expected);
+        for (got, expected) in got.iter().zip(expected) {
+            assert!(got.approx_eq(expected), "{:?} != {:?}", got, expected);
+        }
+    }
+
+    #[test]
+    fn line_tangent_to_an_arc() {
+        let quarter = arc(0.0, 0.0, 1.0, 0.0, 90.0);
+        let tangent = Line::new(Point::new(-1.0, 1.0), Point::new(1.0, 1.0));
+
+        let got = line_arc_intersections(tangent, quarter);
+
+        assert_points_eq(&got, &[Point::new(0.0, 1.0)]);
+    }
+
+    #[test]
+    fn line_crossing_the_circle_but_missing_the_arc() {
+        let quarter = arc(0.0, 0.0, 1.0, 0.0, 90.0);
+        let line = Line::new(Point::new(-2.0, -0.5), Point::new(2.0, -0.5));
+        let short = Line::new(Point::new(0.0, 0.0), Point::new(0.5, 0.5));
+        let clockwise = arc(0.0, 0.0, 1.0, 0.0, -90.0);
+
+        assert!(line_arc_intersections(line, quarter).is_empty());
+        assert!(line_arc_intersections(short, quarter).is_empty());
+        let expected = Point::new(0.75_f64.sqrt(), -0.5);
+        assert_points_eq(&line_arc_intersections(line, clockwise), &[expected]);
+    }
+
+    #[test]
+    fn overlapping_circles() {
+        let first = arc(0.0, 0.0, 5.0, 0.0, 360.0);
+        let second = arc(8.0, 0.0, 5.0, 0.0, 360.0);
+
+        let got = arc_arc_intersections(first, second);
+
+        assert_points_eq(&got, &[Point::new(4.0, 3.0), Point::new(4.0, -3.0)]);
+    }
+
+    #[test]
+    fn touching_and_partial_arcs() {
+        let first = arc(0.0, 0.0, 1.0, -90.0, 180.0);
+        let touching = arc(3.0, 0.0, 2.0, 90.0, 180.0);
+        let upper = arc(1.0, 0.0, 1.0, 90.0, 90.0);
+
+        assert_points_eq(
+            &arc_arc_intersections(first, touching),
+            &[Point::new(1.0, 0.0)],
+        );
+        assert_points_eq(
+            &arc_arc_intersections(first, upper),
+            &[Point::new(0.5, 0.75_f64.sqrt())],
+        );
+    }
+
+    #[test]
+    fn arcs_on_the_same_circle() {
+        let first = arc(0.0, 0.0, 1.0, 0.0, 180.0);
+        let second = arc(0.0, 0.0, 1.0, 90.0, 180.0);
+
+        let got = arc_arc_intersections(first, second);
+
+        assert_points_eq(&got, &[Point::new(-1.0, 0.0), Point::new(0.0, 1.0)]);
+    }
+
+    #[test]
+    fn intersection_sets_merge_duplicates() {
+        // a square and a diagonal which passes through two of its corners
+        let square = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ]);
+        let diagonal = Polyline::from_points(vec![
+            Point::new(-1.0, -1.0),
+            Point::new(3.0, 3.0),
+        ]);
+        let edge = Polyline::from_points(vec![
+            Point::new(1.0, 0.0),
+            Point::new(5.0, 0.0),
+        ]);
+
+        let got = square.intersect(&diagonal);
+        let overlapping = square.intersect(&edge);
+
+        assert_points_eq(
+            got.points(),
+            &[Point::new(0.0, 0.0), Point::new(2.0, 2.0)],
+        );
+        assert!(!got.has_overlap());
+        assert!(overlapping.has_overlap());
+        assert_eq!(
+            overlapping.nearest_to(Point::zero()),
+            Some(Point::new(1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn intersection_sets_merge_points_within_their_tolerance() {
+        let first = Line::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0));
+        let second = Line::new(Point::new(1.0, 0.001), Point::new(1.0, 5.0));
+
+        assert!(first.intersect(&second).is_empty());
+        let got = first.intersect_within(&second, Tolerance::from(0.01));
+        assert_eq!(got.len(), 1);
+
+        let mut set = IntersectionSet::with_tolerance(Tolerance::from(0.1));
+        set.extend(vec![Point::new(0.0, 0.0), Point::new(0.05, 0.0)]);
+        assert_eq!(set.len(), 1);
+    }
+}
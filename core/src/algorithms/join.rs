@@ -0,0 +1,235 @@
+use crate::{
+    algorithms::offset::to_polyline,
+    primitives::{Arc, Line, Polyline, Segment},
+    ApproxEqWithin, Tolerance,
+};
+use euclid::Point2D;
+use std::collections::VecDeque;
+
+/// A [`Polyline`] created by [`join()`]ing segments together.
+#[derive(Debug, PartialEq)]
+pub struct Joined<S> {
+    /// The joined up segments.
+    pub polyline: Polyline<S>,
+    /// The indices of the segments which were used, in the order they
+    /// appear along the [`Joined::polyline`].
+    pub consumed: Vec<usize>,
+}
+
+impl<S> Clone for Joined<S> {
+    fn clone(&self) -> Self {
+        Joined {
+            polyline: self.polyline.clone(),
+            consumed: self.consumed.clone(),
+        }
+    }
+}
+
+/// Merge lines and arcs with matching endpoints into continuous
+/// [`Polyline`]s.
+///
+/// Endpoints are considered to match when they are within the `tolerance`'s
+/// linear distance of each other, and segments are reversed where necessary so
+/// each polyline travels in one direction. A polyline is closed when its last
+/// segment ends where the first one starts.
+///
+/// Every segment ends up in exactly one of the results, so segments which
+/// aren't connected to anything become a polyline by themselves.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::join, primitives::Line, Tolerance};
+///
+/// let lines = vec![
+///     Line::new(Point::new(0.0, 0.0), Point::new(1.0, 0.0)),
+///     Line::new(Point::new(5.0, 5.0), Point::new(6.0, 5.0)),
+///     // this one is going the wrong way
+///     Line::new(Point::new(1.0, 1.0), Point::new(1.0, 0.0)),
+/// ];
+///
+/// let got = join(lines, Tolerance::default());
+///
+/// assert_eq!(got.len(), 2);
+/// assert_eq!(got[0].consumed, vec![0, 2]);
+/// assert_eq!(got[0].polyline.len(), 3);
+/// assert_eq!(got[1].consumed, vec![1]);
+/// ```
+pub fn join<S, I, T>(segments: I, tolerance: Tolerance) -> Vec<Joined<S>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<Segment<S>>,
+{
+    let segments: Vec<Segment<S>> =
+        segments.into_iter().map(Into::into).collect();
+    let mut used = vec![false; segments.len()];
+    let mut joined = Vec::new();
+
+    for first in 0..segments.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+
+        let mut chain = VecDeque::new();
+        chain.push_back((first, segments[first]));
+
+        // keep adding segments to the end of the chain
+        while let Some((index, segment)) = next_segment(
+            &segments,
+            &used,
+            chain.back().unwrap().1.end(),
+            tolerance,
+        ) {
+            used[index] = true;
+            chain.push_back((index, segment));
+        }
+
+        // then work backwards from the start, reversing the connecting
+        // segments so they still lead into the chain
+        while let Some((index, segment)) = next_segment(
+            &segments,
+            &used,
+            chain.front().unwrap().1.start(),
+            tolerance,
+        ) {
+            used[index] = true;
+            chain.push_front((index, reversed(segment)));
+        }
+
+        let start = chain.front().unwrap().1.start();
+        let end = chain.back().unwrap().1.end();
+        let closed = chain.len() > 1 && end.approx_eq_within(&start, tolerance);
+
+        let (consumed, pieces): (Vec<_>, Vec<_>) = chain.into_iter().unzip();
+        joined.push(Joined {
+            polyline: to_polyline(&pieces, closed)
+                .expect("There is always at least one segment"),
+            consumed,
+        });
+    }
+
+    joined
+}
+
+/// Find an unused segment which touches `point`, oriented so it starts
+/// there.
+fn next_segment<S>(
+    segments: &[Segment<S>],
+    used: &[bool],
+    point: Point2D<f64, S>,
+    tolerance: Tolerance,
+) -> Option<(usize, Segment<S>)> {
+    let touches =
+        |other: Point2D<f64, S>| other.approx_eq_within(&point, tolerance);
+
+    segments
+        .iter()
+        .zip(used)
+        .enumerate()
+        .filter(|(_, (_, &used))| !used)
+        .find_map(|(index, (&segment, _))| {
+            if touches(segment.start()) {
+                Some((index, segment))
+            } else if touches(segment.end()) {
+                Some((index, reversed(segment)))
+            } else {
+                None
+            }
+        })
+}
+
+/// The same segment, travelling in the opposite direction.
+pub(crate) fn reversed<S>(segment: Segment<S>) -> Segment<S> {
+    match segment {
+        Segment::Line(line) => Segment::Line(Line::new(line.end, line.start)),
+        Segment::Arc(arc) => Segment::Arc(Arc::from_centre_radius(
+            arc.centre(),
+            arc.radius(),
+            arc.start_angle() + arc.sweep_angle(),
+            -arc.sweep_angle(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn join_a_closed_loop_of_lines_and_arcs() {
+        // a slot shape, with the segments jumbled up
+        let top = Line::new(Point::new(0.0, 1.0), Point::new(2.0, 1.0));
+        let bottom = Line::new(Point::new(0.0, -1.0), Point::new(2.0, -1.0));
+        let right = Arc::from_centre_radius(
+            Point::new(2.0, 0.0),
+            1.0,
+            -Angle::frac_pi_2(),
+            Angle::pi(),
+        );
+        let left = Arc::from_centre_radius(
+            Point::new(0.0, 0.0),
+            1.0,
+            Angle::frac_pi_2(),
+            Angle::pi(),
+        );
+        let segments = vec![
+            Segment::Line(top),
+            Segment::Line(bottom),
+            Segment::Arc(right),
+            Segment::Arc(left),
+        ];
+
+        let got = join(segments, Tolerance::default());
+
+        assert_eq!(got.len(), 1);
+        let slot = &got[0].polyline;
+        assert!(slot.is_closed());
+        assert_eq!(slot.len(), 4);
+        let mut consumed = got[0].consumed.clone();
+        consumed.sort();
+        assert_eq!(consumed, vec![0, 1, 2, 3]);
+        // a rectangle plus two semicircles
+        let expected_area = 4.0 + std::f64::consts::PI;
+        assert!(slot.area().approx_eq_eps(&expected_area, &1e-6));
+    }
+
+    #[test]
+    fn endpoints_only_need_to_be_within_tolerance() {
+        let lines = vec![
+            Line::new(Point::new(0.0, 0.0), Point::new(1.0, 0.0)),
+            Line::new(Point::new(1.001, 0.0), Point::new(2.0, 0.0)),
+        ];
+
+        assert_eq!(join(lines.clone(), Tolerance::default()).len(), 2);
+
+        let got = join(lines, Tolerance::from(0.01));
+        assert_eq!(got.len(), 1);
+        assert!(!got[0].polyline.is_closed());
+        assert_eq!(got[0].consumed, vec![0, 1]);
+    }
+
+    #[test]
+    fn reversed_arcs_follow_the_same_path() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+
+        let got = reversed(Segment::Arc(arc));
+
+        assert!(got.start().approx_eq(&arc.end()));
+        assert!(got.end().approx_eq(&arc.start()));
+        match got {
+            Segment::Arc(reversed) => assert!(reversed.is_clockwise()),
+            Segment::Line(_) => unreachable!(),
+        }
+    }
+}
@@ -1,6 +1,16 @@
-use crate::primitives::{Arc, Line};
+use crate::primitives::{
+    Arc, CubicBezier, Ellipse, EllipticalArc, Line, Polyline, QuadraticBezier,
+    Segment, Spline,
+};
 use euclid::Vector2D;
 
+/// The relative error allowed when a length needs to be integrated
+/// numerically.
+const QUADRATURE_TOLERANCE: f64 = 1e-10;
+
+/// How many times an interval may be halved when integrating numerically.
+const MAX_QUADRATURE_DEPTH: usize = 20;
+
 /// Something which has a finite length.
 pub trait Length {
     /// Calculate the length.
@@ -57,10 +67,170 @@ impl<Space> Length for Arc<Space> {
     fn length(&self) -> f64 { self.radius() * self.sweep_angle().radians.abs() }
 }
 
+impl<Space> Length for Segment<Space> {
+    fn length(&self) -> f64 {
+        match self {
+            Segment::Line(line) => line.length(),
+            Segment::Arc(arc) => arc.length(),
+        }
+    }
+}
+
+impl<Space> Length for Polyline<Space> {
+    /// The total length of all the [`Polyline`]'s segments.
+    fn length(&self) -> f64 { self.segments().map(|s| s.length()).sum() }
+}
+
+impl<Space> Length for CubicBezier<Space> {
+    /// Calculates the length of a [`CubicBezier`] using numerical
+    /// integration.
+    ///
+    /// ```rust
+    /// # use arcs_core::{algorithms::Length, primitives::CubicBezier};
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// // a straight line, with evenly spaced control points
+    /// let curve = CubicBezier::new(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 0.0),
+    ///     Point::new(2.0, 0.0),
+    ///     Point::new(3.0, 0.0),
+    /// );
+    ///
+    /// assert!((curve.length() - 3.0).abs() < 1e-10);
+    /// ```
+    fn length(&self) -> f64 {
+        integrate(|t| self.derivative_at(t).length(), 0.0, 1.0)
+    }
+}
+
+impl<Space> Length for QuadraticBezier<Space> {
+    /// Calculates the length of a [`QuadraticBezier`] using numerical
+    /// integration.
+    fn length(&self) -> f64 {
+        integrate(|t| self.derivative_at(t).length(), 0.0, 1.0)
+    }
+}
+
+impl<Space> Length for Spline<Space> {
+    /// Calculates the length of a [`Spline`] using numerical integration,
+    /// one polynomial piece at a time.
+    fn length(&self) -> f64 {
+        self.breakpoints()
+            .windows(2)
+            .map(|span| {
+                // splines don't know their derivatives, so use a central
+                // difference with a step much smaller than the span
+                let step = (span[1] - span[0]) * 1e-6;
+                let speed = |u: f64| {
+                    (self.point_at(u + step) - self.point_at(u - step)).length()
+                        / (2.0 * step)
+                };
+
+                integrate(speed, span[0], span[1])
+            })
+            .sum()
+    }
+}
+
+impl<Space> Length for Ellipse<Space> {
+    /// Calculates the circumference of an [`Ellipse`] using numerical
+    /// integration (there is no closed form).
+    ///
+    /// ```rust
+    /// # use arcs_core::{algorithms::Length, primitives::Ellipse, Angle};
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// # use std::f64::consts::PI;
+    /// let circle = Ellipse::new(Point::zero(), 2.0, 2.0, Angle::zero());
+    ///
+    /// assert!((circle.length() - 4.0 * PI).abs() < 1e-9);
+    /// ```
+    fn length(&self) -> f64 {
+        ellipse_length(*self, 0.0, 2.0 * std::f64::consts::PI)
+    }
+}
+
+impl<Space> Length for EllipticalArc<Space> {
+    /// Calculates the length of an [`EllipticalArc`] using numerical
+    /// integration.
+    fn length(&self) -> f64 {
+        let start = self.start_angle().radians;
+        let end = start + self.sweep_angle().radians;
+
+        ellipse_length(self.ellipse(), start.min(end), start.max(end))
+    }
+}
+
+/// The distance travelled around an ellipse between two parametric angles.
+fn ellipse_length<S>(ellipse: Ellipse<S>, start: f64, end: f64) -> f64 {
+    let (a, b) = (ellipse.semi_major(), ellipse.semi_minor());
+
+    integrate(
+        |theta| {
+            let (sin, cos) = theta.sin_cos();
+            (a * a * sin * sin + b * b * cos * cos).sqrt()
+        },
+        start,
+        end,
+    )
+}
+
+/// Integrate `f` from `a` to `b` using adaptive Gauss-Legendre quadrature.
+fn integrate<F>(f: F, a: f64, b: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let whole = gauss_legendre(&f, a, b);
+    adaptive_integrate(&f, a, b, whole, MAX_QUADRATURE_DEPTH)
+}
+
+fn adaptive_integrate<F>(f: &F, a: f64, b: f64, whole: f64, depth: usize) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let middle = (a + b) / 2.0;
+    let left = gauss_legendre(f, a, middle);
+    let right = gauss_legendre(f, middle, b);
+    let halves = left + right;
+
+    if depth == 0
+        || (halves - whole).abs()
+            <= QUADRATURE_TOLERANCE * halves.abs().max(1.0)
+    {
+        halves
+    } else {
+        adaptive_integrate(f, a, middle, left, depth - 1)
+            + adaptive_integrate(f, middle, b, right, depth - 1)
+    }
+}
+
+/// Five point Gauss-Legendre quadrature over a single interval.
+fn gauss_legendre<F>(f: &F, a: f64, b: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    const NODES: [(f64, f64); 5] = [
+        (0.0, 0.568_888_888_888_888_9),
+        (-0.538_469_310_105_683, 0.478_628_670_499_366_5),
+        (0.538_469_310_105_683, 0.478_628_670_499_366_5),
+        (-0.906_179_845_938_664, 0.236_926_885_056_189_08),
+        (0.906_179_845_938_664, 0.236_926_885_056_189_08),
+    ];
+
+    let (half_width, centre) = ((b - a) / 2.0, (a + b) / 2.0);
+
+    NODES
+        .iter()
+        .map(|&(x, weight)| weight * f(centre + half_width * x))
+        .sum::<f64>()
+        * half_width
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Angle;
+    use crate::{primitives::Vertex, Angle};
+    use euclid::approxeq::ApproxEq;
+    use std::f64::consts::PI;
 
     type Point = euclid::default::Point2D<f64>;
 
@@ -85,4 +255,64 @@ mod tests {
             arc.sweep_angle().radians.abs() * arc.radius()
         );
     }
+
+    #[test]
+    fn polyline() {
+        let polyline = Polyline::new(
+            vec![
+                Vertex::straight(Point::new(0.0, 0.0)),
+                Vertex::new(Point::new(2.0, 0.0), 1.0),
+                Vertex::straight(Point::new(2.0, 2.0)),
+            ],
+            false,
+        );
+
+        let got = polyline.length();
+
+        assert!(got.approx_eq(&(2.0 + PI)));
+    }
+
+    #[test]
+    fn quarter_circle_bezier() {
+        // the usual approximation of a unit quarter circle
+        let k = 0.552_284_749_831;
+        let curve = CubicBezier::new(
+            Point::new(1.0, 0.0),
+            Point::new(1.0, k),
+            Point::new(k, 1.0),
+            Point::new(0.0, 1.0),
+        );
+
+        let got = curve.length();
+
+        assert!((got - PI / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ellipse_circumference() {
+        let ellipse = Ellipse::new(Point::zero(), 5.0, 3.0, Angle::zero());
+
+        let got = ellipse.length();
+
+        // the exact value, from the complete elliptic integral
+        assert!(got.approx_eq_eps(&25.526_998_863_398_14, &1e-6));
+        let half = EllipticalArc::new(ellipse, Angle::zero(), -Angle::pi());
+        assert!(half.length().approx_eq_eps(&(got / 2.0), &1e-8));
+    }
+
+    #[test]
+    fn spline_through_a_straight_line() {
+        let spline = Spline::clamped(
+            2,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(3.0, 0.0),
+                Point::new(4.0, 0.0),
+            ],
+        )
+        .unwrap();
+
+        assert!(spline.length().approx_eq_eps(&4.0, &1e-8));
+    }
 }
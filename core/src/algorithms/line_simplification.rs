@@ -1,5 +1,6 @@
-use crate::primitives::Line;
+use crate::primitives::{Circle, Line, Polyline, Vertex};
 use euclid::{Length, Point2D};
+use std::f64::consts::PI;
 
 #[allow(unused_imports)] // rustdoc links
 use crate::algorithms::Approximate;
@@ -70,6 +71,208 @@ fn simplify_points<Space>(
     }
 }
 
+/// Reduce the number of vertices in a [`Polyline`] while keeping it within
+/// `tolerance` of the original.
+///
+/// Runs of short straight segments which follow a circular arc (e.g. a
+/// traced or digitised curve) are replaced by a single arc segment, any
+/// remaining straight runs are simplified using [`simplify()`], and existing
+/// arc segments are left untouched.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::simplify_polyline, primitives::Polyline};
+/// use euclid::{approxeq::ApproxEq, Length};
+///
+/// // a semicircle made out of lots of little lines, followed by a straight
+/// // edge along the x axis
+/// let mut points: Vec<Point> = (0..=50)
+///     .map(|i| {
+///         let angle = std::f64::consts::PI * i as f64 / 50.0;
+///         Point::new(angle.cos(), angle.sin())
+///     })
+///     .collect();
+/// points.extend((1..=10).map(|i| Point::new(-1.0 - i as f64, 0.0)));
+/// let polyline = Polyline::from_points(points);
+///
+/// let got = simplify_polyline(&polyline, Length::new(0.001));
+///
+/// assert_eq!(got.len(), 3);
+/// // an anticlockwise semicircle has a bulge of 1
+/// assert!(got.vertices()[0].bulge.approx_eq(&1.0));
+/// assert!(got.vertices()[1].is_straight());
+/// ```
+pub fn simplify_polyline<Space>(
+    polyline: &Polyline<Space>,
+    tolerance: Length<f64, Space>,
+) -> Polyline<Space> {
+    let mut vertices = polyline.vertices().to_vec();
+    let closed = polyline.is_closed() && vertices.len() > 1;
+
+    if closed {
+        // treat the closing segment like any other by visiting the first
+        // point again
+        vertices.push(Vertex::straight(vertices[0].point));
+    }
+
+    if vertices.len() <= 2 {
+        return polyline.clone();
+    }
+
+    let mut simplified = Vec::new();
+    let mut start = 0;
+
+    while start < vertices.len() - 1 {
+        if !vertices[start].is_straight() {
+            simplified.push(vertices[start]);
+            start += 1;
+            continue;
+        }
+
+        // find the end of this run of straight segments
+        let mut end = start + 1;
+        while end < vertices.len() - 1 && vertices[end].is_straight() {
+            end += 1;
+        }
+
+        let points: Vec<_> =
+            vertices[start..=end].iter().map(|v| v.point).collect();
+        simplify_straight_run(&points, tolerance.get(), &mut simplified);
+        start = end;
+    }
+
+    if !closed {
+        simplified.push(*vertices.last().unwrap());
+    }
+
+    Polyline::new(simplified, polyline.is_closed())
+}
+
+/// Simplify a run of straight segments, pushing a [`Vertex`] for every
+/// point except the last.
+fn simplify_straight_run<Space>(
+    points: &[Point2D<f64, Space>],
+    tolerance: f64,
+    buffer: &mut Vec<Vertex<Space>>,
+) {
+    let mut straight_from = 0;
+    let mut current = 0;
+
+    while current < points.len() - 1 {
+        match longest_arc(&points[current..], tolerance) {
+            Some((length, bulge)) => {
+                flush_straight(
+                    &points[straight_from..=current],
+                    tolerance,
+                    buffer,
+                );
+                buffer.push(Vertex::new(points[current], bulge));
+                current += length;
+                straight_from = current;
+            },
+            None => current += 1,
+        }
+    }
+
+    flush_straight(&points[straight_from..], tolerance, buffer);
+}
+
+fn flush_straight<Space>(
+    points: &[Point2D<f64, Space>],
+    tolerance: f64,
+    buffer: &mut Vec<Vertex<Space>>,
+) {
+    if points.len() < 2 {
+        return;
+    }
+
+    let simplified = simplify(points, Length::new(tolerance));
+    buffer.extend(
+        simplified[..simplified.len() - 1]
+            .iter()
+            .map(|&point| Vertex::straight(point)),
+    );
+}
+
+/// The minimum number of straight segments which may be replaced by an arc.
+const MIN_ARC_SEGMENTS: usize = 3;
+
+/// Find the most segments from the start of `points` which can be replaced
+/// by a single arc, returning the number of segments and the arc's bulge.
+fn longest_arc<Space>(
+    points: &[Point2D<f64, Space>],
+    tolerance: f64,
+) -> Option<(usize, f64)> {
+    let mut best = None;
+
+    for segments in MIN_ARC_SEGMENTS..points.len() {
+        match fit_arc(&points[..=segments], tolerance) {
+            Some(bulge) => best = Some((segments, bulge)),
+            None => break,
+        }
+    }
+
+    let (segments, bulge) = best?;
+
+    // a straight line is simpler than a very flat arc
+    let chord = Line::new(points[0], points[segments]);
+    let is_straight = points[1..segments].iter().all(|&point| {
+        chord.perpendicular_distance_to(point).get() <= tolerance
+    });
+
+    if is_straight {
+        None
+    } else {
+        Some((segments, bulge))
+    }
+}
+
+/// Check whether every point (and the straight segments between them) lies
+/// within `tolerance` of the arc passing through the first, middle and last
+/// points, returning the arc's bulge.
+fn fit_arc<Space>(
+    points: &[Point2D<f64, Space>],
+    tolerance: f64,
+) -> Option<f64> {
+    let first = points[0];
+    let middle = points[points.len() / 2];
+    let last = points[points.len() - 1];
+    let Circle { centre, radius } =
+        Circle::circumscribing(first, middle, last)?;
+
+    let direction = (middle - first).cross(last - middle).signum();
+    let start_angle = (first.y - centre.y).atan2(first.x - centre.x);
+    let progress = |point: Point2D<f64, Space>| {
+        let offset = point - centre;
+        (direction * (offset.y.atan2(offset.x) - start_angle))
+            .rem_euclid(2.0 * PI)
+    };
+
+    let mut previous = 0.0;
+
+    for pair in points.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let distance_from_arc = ((end - centre).length() - radius).abs();
+        let half_chord = (end - start).length() / 2.0;
+        let sagitta = radius
+            - (radius * radius - half_chord * half_chord).max(0.0).sqrt();
+        let angle = progress(end);
+
+        if distance_from_arc > tolerance
+            || sagitta > tolerance
+            || angle < previous
+        {
+            return None;
+        }
+
+        previous = angle;
+    }
+
+    Some((direction * previous / 4.0).tan())
+}
+
 fn max_by_key<T, F, K>(items: &[T], mut key_func: F) -> Option<(usize, K)>
 where
     F: FnMut(&T) -> K,
@@ -178,4 +381,73 @@ mod tests {
 
         assert_eq!(got, should_be);
     }
+
+    #[test]
+    fn existing_arcs_are_left_alone() {
+        let polyline = Polyline::new(
+            vec![
+                Vertex::new(Point::new(0.0, 0.0), 0.5),
+                Vertex::straight(Point::new(1.0, 0.0)),
+                Vertex::straight(Point::new(2.0, 0.0)),
+                Vertex::new(Point::new(3.0, 0.0), -0.25),
+                Vertex::straight(Point::new(4.0, 0.0)),
+            ],
+            false,
+        );
+        let should_be = Polyline::new(
+            vec![
+                Vertex::new(Point::new(0.0, 0.0), 0.5),
+                Vertex::straight(Point::new(1.0, 0.0)),
+                Vertex::new(Point::new(3.0, 0.0), -0.25),
+                Vertex::straight(Point::new(4.0, 0.0)),
+            ],
+            false,
+        );
+
+        let got = simplify_polyline(&polyline, Length::new(0.01));
+
+        assert_eq!(got, should_be);
+    }
+
+    #[test]
+    fn closed_square_with_extra_points_on_each_side() {
+        let corners = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(0.0, 4.0),
+        ];
+        let mut points = Vec::new();
+        for i in 0..4 {
+            let (start, end) = (corners[i], corners[(i + 1) % 4]);
+            for step in 0..4 {
+                points.push(start.lerp(end, step as f64 / 4.0));
+            }
+        }
+        let mut polyline = Polyline::from_points(points);
+        polyline.set_closed(true);
+
+        let got = simplify_polyline(&polyline, Length::new(0.01));
+
+        let got: Vec<Point> = got.vertices().iter().map(|v| v.point).collect();
+        assert_eq!(got, corners);
+    }
+
+    #[test]
+    fn clockwise_arcs_have_a_negative_bulge() {
+        // a quarter circle going clockwise from the top to the right
+        let points: Vec<Point> = (0..=20)
+            .map(|i| {
+                let angle = PI / 2.0 - PI / 2.0 * i as f64 / 20.0;
+                Point::new(angle.cos(), angle.sin())
+            })
+            .collect();
+        let polyline = Polyline::from_points(points);
+
+        let got = simplify_polyline(&polyline, Length::new(0.001));
+
+        assert_eq!(got.len(), 2);
+        let expected = -(PI / 8.0).tan();
+        assert!((got.vertices()[0].bulge - expected).abs() < 1e-9);
+    }
 }
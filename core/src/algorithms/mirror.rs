@@ -0,0 +1,96 @@
+use crate::{
+    algorithms::{AffineTransform, TransformError, Transformable},
+    primitives::Line,
+};
+
+/// Get a copy of `geometry` reflected across the infinite line through
+/// `axis`.
+///
+/// Mirroring reverses the direction of arcs (so a mirrored anticlockwise arc
+/// goes clockwise) and is rejected with [`TransformError::Degenerate`] if
+/// `axis` has no length.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::mirror,
+///     primitives::{Arc, Line},
+///     Angle,
+/// };
+/// use euclid::approxeq::ApproxEq;
+///
+/// let y_axis = Line::new(Point::zero(), Point::new(0.0, 1.0));
+/// let arc = Arc::from_centre_radius(
+///     Point::new(2.0, 0.0),
+///     1.0,
+///     Angle::zero(),
+///     Angle::frac_pi_2(),
+/// );
+///
+/// let got = mirror(&arc, y_axis).unwrap();
+///
+/// assert!(got.centre().approx_eq(&Point::new(-2.0, 0.0)));
+/// assert!(got.start().approx_eq(&Point::new(-3.0, 0.0)));
+/// assert!(got.is_clockwise());
+/// ```
+pub fn mirror<S, T>(geometry: &T, axis: Line<S>) -> Result<T, TransformError>
+where
+    T: Transformable<S> + Clone,
+{
+    let transform =
+        AffineTransform::mirror(axis).ok_or(TransformError::Degenerate)?;
+
+    geometry.transformed_by(transform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{Polyline, Vertex};
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn mirror_across_a_diagonal() {
+        let axis = Line::new(Point::new(1.0, 1.0), Point::new(3.0, 3.0));
+        let line = Line::new(Point::new(2.0, 0.0), Point::new(5.0, 1.0));
+
+        let got = mirror(&line, axis).unwrap();
+
+        assert!(got.start.approx_eq(&Point::new(0.0, 2.0)));
+        assert!(got.end.approx_eq(&Point::new(1.0, 5.0)));
+    }
+
+    #[test]
+    fn mirrored_polylines_keep_their_shape() {
+        let polyline = Polyline::new(
+            vec![
+                Vertex::new(Point::new(0.0, 0.0), 0.5),
+                Vertex::straight(Point::new(2.0, 0.0)),
+                Vertex::straight(Point::new(2.0, 3.0)),
+            ],
+            true,
+        );
+        let axis = Line::new(Point::new(5.0, 0.0), Point::new(5.0, 1.0));
+
+        let got = mirror(&polyline, axis).unwrap();
+
+        assert!(got.area().approx_eq(&polyline.area()));
+        assert!(got.signed_area() * polyline.signed_area() < 0.0);
+        assert_eq!(got.vertices()[0].bulge, -0.5);
+    }
+
+    #[test]
+    fn the_axis_needs_a_direction() {
+        let point = Point::new(1.0, 1.0);
+        let axis = Line::new(point, point);
+
+        assert_eq!(
+            mirror(&Point::zero(), axis),
+            Err(TransformError::Degenerate)
+        );
+    }
+}
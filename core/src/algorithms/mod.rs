@@ -2,20 +2,77 @@
 
 mod affine_transform;
 mod approximate;
+mod area;
+pub mod array;
+pub mod boolean;
 mod bounding_box;
+mod chamfer;
 mod closest_point;
+mod convex_hull;
+mod curve;
+mod distance;
+mod extend;
+mod fillet;
+pub mod fit;
+mod intersection;
+mod join;
 mod length;
 mod line_simplification;
+mod mirror;
+mod offset;
+mod projection;
+mod rotate;
 mod scale;
+mod scale_about;
 mod scale_non_uniform;
+mod self_intersection;
+mod split;
+mod tangent;
+mod tangent_circle;
+mod tessellate;
+mod transformable;
 mod translate;
+mod trim;
 
 pub use affine_transform::AffineTransformable;
-pub use approximate::{Approximate, ApproximatedArc};
+pub use approximate::{
+    Approximate, ApproximatedArc, ApproximatedEllipticalArc,
+};
+pub use area::{Area, AreaMoments, SecondMoments};
 pub use bounding_box::Bounded;
-pub use closest_point::{Closest, ClosestPoint};
+pub use chamfer::{chamfer, Chamfer, ChamferError};
+pub use closest_point::{
+    Closest, ClosestLocation, ClosestParameter, ClosestPoint,
+};
+pub use convex_hull::convex_hull;
+pub use curve::Curve;
+pub use distance::{distance, Separation};
+pub use extend::{extend_arc, extend_line, Endpoint, ExtendError};
+pub use fillet::{fillet, Fillet, FilletError};
+pub use intersection::{
+    arc_arc_intersections, arc_arc_intersections_with_tolerance, intersection,
+    intersection_with_tolerance, line_arc_intersections,
+    line_arc_intersections_with_tolerance, Intersect, IntersectionSet,
+    LineIntersection, DEFAULT_TOLERANCE,
+};
+pub use join::{join, Joined};
 pub use length::Length;
-pub use line_simplification::simplify;
+pub use line_simplification::{simplify, simplify_polyline};
+pub use mirror::mirror;
+pub use offset::{offset, Offset, Side};
+pub use projection::{project_onto, Project, Projection};
+pub use rotate::Rotate;
 pub use scale::Scale;
+pub use scale_about::scale_about;
 pub use scale_non_uniform::ScaleNonUniform;
+pub use self_intersection::{SelfIntersect, SelfIntersection};
+pub use split::{break_between, split_at, Split};
+pub use tangent::{common_tangents, tangents_from_point};
+pub use tangent_circle::{
+    circles_tangent_to_three, circles_tangent_to_two,
+    circles_through_two_points, Tangency,
+};
+pub use tessellate::Tessellate;
+pub use transformable::{AffineTransform, TransformError, Transformable};
 pub use translate::Translate;
+pub use trim::Trim;
@@ -0,0 +1,464 @@
+use crate::{
+    algorithms::Intersect,
+    primitives::{Arc, Line, Polyline, Segment, Vertex, XLine},
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+use std::f64::consts::PI;
+
+/// Which side of an object to offset towards, relative to the direction it
+/// travels in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// Towards the left (i.e. anticlockwise from the direction of travel).
+    Left,
+    /// Towards the right.
+    Right,
+}
+
+impl Side {
+    /// The other side.
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    /// Which way to move in multiples of the left-hand normal.
+    fn sign(self) -> f64 {
+        match self {
+            Side::Left => 1.0,
+            Side::Right => -1.0,
+        }
+    }
+}
+
+/// Something which can be offset to create a parallel copy of itself.
+pub trait Offset<Space>: Sized {
+    /// Create a copy of this object where every point is `distance` units
+    /// to one `side`.
+    ///
+    /// This returns [`None`] when the result would be degenerate (e.g.
+    /// offsetting a circle towards its centre by more than its radius).
+    fn offset(&self, distance: f64, side: Side) -> Option<Self>;
+}
+
+/// Create a parallel copy of some geometry (see [`Offset`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::{offset, Side},
+///     primitives::Line,
+/// };
+///
+/// let line = Line::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+///
+/// let got = offset(&line, 2.0, Side::Left).unwrap();
+///
+/// assert_eq!(got, Line::new(Point::new(0.0, 2.0), Point::new(10.0, 2.0)));
+/// ```
+pub fn offset<S, O>(geometry: &O, distance: f64, side: Side) -> Option<O>
+where
+    O: Offset<S>,
+{
+    geometry.offset(distance, side)
+}
+
+impl<S> Offset<S> for Line<S> {
+    fn offset(&self, distance: f64, side: Side) -> Option<Self> {
+        let displacement = self.displacement();
+        let length = displacement.length();
+
+        if length == 0.0 {
+            return None;
+        }
+
+        let left = Vector2D::new(-displacement.y, displacement.x) / length;
+        let shift = left * (distance * side.sign());
+
+        Some(Line::new(self.start + shift, self.end + shift))
+    }
+}
+
+impl<S> Offset<S> for Arc<S> {
+    /// Offsetting an [`Arc`] gives a concentric arc with the same start and
+    /// sweep angles.
+    fn offset(&self, distance: f64, side: Side) -> Option<Self> {
+        // the left side of an anticlockwise arc is towards the centre
+        let direction = self.sweep_angle().radians.signum();
+        let radius = self.radius() - distance * side.sign() * direction;
+
+        if radius <= 0.0 {
+            None
+        } else {
+            Some(Arc::from_centre_radius(
+                self.centre(),
+                radius,
+                self.start_angle(),
+                self.sweep_angle(),
+            ))
+        }
+    }
+}
+
+impl<S> Offset<S> for Segment<S> {
+    fn offset(&self, distance: f64, side: Side) -> Option<Self> {
+        match self {
+            Segment::Line(line) => {
+                line.offset(distance, side).map(Segment::Line)
+            },
+            Segment::Arc(arc) => arc.offset(distance, side).map(Segment::Arc),
+        }
+    }
+}
+
+impl<S> Offset<S> for Polyline<S> {
+    /// Offset each segment, then join neighbouring segments back together.
+    ///
+    /// Neighbouring lines are extended or trimmed to meet each other (a
+    /// mitred corner). If an arc is involved, the segments are trimmed back
+    /// to where they cross, or joined by a rounded corner when they no
+    /// longer touch. Segments which disappear, or which would end up
+    /// pointing backwards, are removed.
+    fn offset(&self, distance: f64, side: Side) -> Option<Self> {
+        let closed = self.is_closed();
+
+        // keep track of the original vertex each offset segment started at,
+        // so we know where to put rounded corners
+        let mut pieces: Vec<(Segment<S>, Segment<S>)> = self
+            .segments()
+            .filter(|segment| (segment.end() - segment.start()).length() > 0.0)
+            .filter_map(|original| {
+                original
+                    .offset(distance, side)
+                    .map(|offset| (original, offset))
+            })
+            .collect();
+
+        loop {
+            let joined = join_pieces(&pieces, distance, closed);
+
+            // find any lines which were trimmed so much they flipped around
+            let reversed = joined.iter().zip(&pieces).position(
+                |((joined, _), (original, _))| match (joined, original) {
+                    (Segment::Line(new), Segment::Line(old)) => {
+                        new.displacement().dot(old.displacement()) <= 0.0
+                    },
+                    _ => false,
+                },
+            );
+
+            match reversed {
+                Some(index) if pieces.len() > 1 => {
+                    pieces.remove(index);
+                },
+                _ => {
+                    let segments: Vec<_> = joined
+                        .into_iter()
+                        .flat_map(|(segment, corner)| {
+                            std::iter::once(segment).chain(corner)
+                        })
+                        .collect();
+
+                    return to_polyline(&segments, closed);
+                },
+            }
+        }
+    }
+}
+
+/// Join each offset segment to the next one, returning the trimmed segments
+/// and any corner which needs to be inserted after them.
+fn join_pieces<S>(
+    pieces: &[(Segment<S>, Segment<S>)],
+    distance: f64,
+    closed: bool,
+) -> Vec<(Segment<S>, Option<Segment<S>>)> {
+    let mut segments: Vec<Segment<S>> =
+        pieces.iter().map(|&(_, offset)| offset).collect();
+    let mut corners: Vec<Option<Segment<S>>> = vec![None; segments.len()];
+    let count = segments.len();
+
+    let joins = if closed {
+        count
+    } else {
+        count.saturating_sub(1)
+    };
+
+    for i in 0..joins {
+        let j = (i + 1) % count;
+        if i == j {
+            break;
+        }
+
+        let vertex = pieces[j].0.start();
+        let (first, second) = (segments[i], segments[j]);
+
+        if (first.end() - second.start()).length() <= 1e-9 {
+            continue;
+        }
+
+        match join(first, second, vertex, distance) {
+            Join::Meet(point) => {
+                segments[i] = with_end(first, point);
+                segments[j] = with_start(second, point);
+            },
+            Join::Corner(corner) => corners[i] = Some(corner),
+        }
+    }
+
+    segments.into_iter().zip(corners).collect()
+}
+
+enum Join<S> {
+    /// Both segments should be extended or trimmed to meet at a point.
+    Meet(Point2D<f64, S>),
+    /// Insert a corner piece between the segments.
+    Corner(Segment<S>),
+}
+
+fn join<S>(
+    first: Segment<S>,
+    second: Segment<S>,
+    vertex: Point2D<f64, S>,
+    distance: f64,
+) -> Join<S> {
+    if let (Segment::Line(a), Segment::Line(b)) = (first, second) {
+        let a = XLine::new(a.start, a.displacement());
+        let b = XLine::new(b.start, b.displacement());
+
+        if let Some(point) = a.intersect_xline(b) {
+            return Join::Meet(point);
+        }
+    }
+
+    if let Some(point) = first.intersect(&second).nearest_to(vertex) {
+        return Join::Meet(point);
+    }
+
+    let from = first.end() - vertex;
+    let to = second.start() - vertex;
+    let radius = distance.abs();
+    let on_circle = |v: Vector2D<f64, S>| (v.length() - radius).abs() <= 1e-6;
+
+    if on_circle(from) && on_circle(to) {
+        // round off the corner
+        let sweep = from.cross(to).atan2(from.dot(to));
+
+        Join::Corner(Segment::Arc(Arc::from_centre_radius(
+            vertex,
+            radius,
+            Angle::radians(from.y.atan2(from.x)),
+            Angle::radians(sweep),
+        )))
+    } else {
+        // the neighbouring segments were removed, so just bridge the gap
+        Join::Corner(Segment::Line(Line::new(first.end(), second.start())))
+    }
+}
+
+/// Move the start of a segment to another point on the same line or
+/// circle.
+pub(crate) fn with_start<S>(
+    segment: Segment<S>,
+    start: Point2D<f64, S>,
+) -> Segment<S> {
+    match segment {
+        Segment::Line(line) => Segment::Line(Line::new(start, line.end)),
+        Segment::Arc(arc) => {
+            let moved = signed_angle_from(arc, arc.start(), start);
+
+            Segment::Arc(Arc::from_centre_radius(
+                arc.centre(),
+                arc.radius(),
+                arc.start_angle() + Angle::radians(moved),
+                arc.sweep_angle() - Angle::radians(moved),
+            ))
+        },
+    }
+}
+
+/// Move the end of a segment to another point on the same line or circle.
+pub(crate) fn with_end<S>(
+    segment: Segment<S>,
+    end: Point2D<f64, S>,
+) -> Segment<S> {
+    match segment {
+        Segment::Line(line) => Segment::Line(Line::new(line.start, end)),
+        Segment::Arc(arc) => {
+            let moved = signed_angle_from(arc, arc.end(), end);
+
+            Segment::Arc(Arc::from_centre_radius(
+                arc.centre(),
+                arc.radius(),
+                arc.start_angle(),
+                arc.sweep_angle() + Angle::radians(moved),
+            ))
+        },
+    }
+}
+
+/// The angle (in radians, between `-PI` and `PI`) swept out going from
+/// `from` to `to` around the arc's centre.
+fn signed_angle_from<S>(
+    arc: Arc<S>,
+    from: Point2D<f64, S>,
+    to: Point2D<f64, S>,
+) -> f64 {
+    let (a, b) = (from - arc.centre(), to - arc.centre());
+    let angle = a.cross(b).atan2(a.dot(b));
+
+    if angle <= -PI {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+pub(crate) fn to_polyline<S>(
+    segments: &[Segment<S>],
+    closed: bool,
+) -> Option<Polyline<S>> {
+    let first = segments.first()?;
+
+    let mut vertices: Vec<Vertex<S>> = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Line(line) => Vertex::straight(line.start),
+            Segment::Arc(arc) => Vertex::new(
+                arc.start(),
+                (arc.sweep_angle().radians / 4.0).tan(),
+            ),
+        })
+        .collect();
+
+    if !closed {
+        vertices.push(Vertex::straight(
+            segments
+                .last()
+                .map(|s| s.end())
+                .unwrap_or_else(|| first.end()),
+        ));
+    }
+
+    Some(Polyline::new(vertices, closed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn offset_arcs_are_concentric() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            5.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+
+        let inside = arc.offset(2.0, Side::Left).unwrap();
+        let outside = arc.offset(2.0, Side::Right).unwrap();
+
+        assert_eq!(inside.radius(), 3.0);
+        assert_eq!(outside.radius(), 7.0);
+        assert!(arc.offset(6.0, Side::Left).is_none());
+    }
+
+    #[test]
+    fn offset_an_open_polyline_with_a_mitred_corner() {
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ]);
+
+        let inside = polyline.offset(1.0, Side::Left).unwrap();
+        let outside = polyline.offset(1.0, Side::Right).unwrap();
+
+        let points = |p: &Polyline<_>| -> Vec<Point> {
+            p.vertices().iter().map(|v| v.point).collect()
+        };
+        assert_eq!(
+            points(&inside),
+            vec![
+                Point::new(0.0, 1.0),
+                Point::new(9.0, 1.0),
+                Point::new(9.0, 10.0)
+            ]
+        );
+        assert_eq!(
+            points(&outside),
+            vec![
+                Point::new(0.0, -1.0),
+                Point::new(11.0, -1.0),
+                Point::new(11.0, 10.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn offset_a_closed_square_outwards() {
+        // an anticlockwise square, so the right-hand side is outside
+        let mut square = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ]);
+        square.set_closed(true);
+
+        let outside = square.offset(1.0, Side::Right).unwrap();
+        let inside = square.offset(0.5, Side::Left).unwrap();
+
+        assert!(outside.is_closed());
+        assert!(outside.area().approx_eq(&16.0));
+        assert!(inside.area().approx_eq(&1.0));
+    }
+
+    #[test]
+    fn short_segments_which_flip_are_removed() {
+        // a "V" with a flat bottom, which gets squeezed out when offset
+        // towards the inside
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 10.0),
+            Point::new(4.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(10.0, 10.0),
+        ]);
+
+        let got = polyline.offset(3.0, Side::Left).unwrap();
+
+        assert_eq!(got.len(), 3);
+        let bottom = got.vertices()[1].point;
+        assert!(bottom.x.approx_eq(&5.0));
+        assert!(bottom.y > 3.0);
+    }
+
+    #[test]
+    fn tangent_arcs_stay_joined() {
+        // a line followed by a quarter circle turning right
+        let polyline = Polyline::new(
+            vec![
+                Vertex::straight(Point::new(0.0, 0.0)),
+                Vertex::new(Point::new(10.0, 0.0), -(PI / 8.0).tan()),
+                Vertex::straight(Point::new(15.0, -5.0)),
+            ],
+            false,
+        );
+
+        let got = polyline.offset(1.0, Side::Left).unwrap();
+
+        // the arc is tangent to the line, so they still join up
+        assert_eq!(got.len(), 3);
+        assert!(got.vertices()[1].point.approx_eq(&Point::new(10.0, 1.0)));
+        assert!(got.vertices()[2].point.approx_eq(&Point::new(16.0, -5.0)));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{
+    algorithms::{ClosestParameter, ClosestPoint, Curve},
+    primitives::{
+        Arc, Circle, CubicBezier, Ellipse, EllipticalArc, Line, Polyline,
+        QuadraticBezier, Ray, Segment, Spline, XLine,
+    },
+};
+use euclid::{Point2D, Vector2D};
+
+/// How close a curve's parameter needs to be to one of its ends before the
+/// projection is done using the end's tangent instead.
+const END_TOLERANCE: f64 = 1e-9;
+
+/// The foot of the perpendicular dropped from a point onto something.
+#[derive(Debug, PartialEq)]
+pub struct Projection<S> {
+    /// Where the perpendicular meets the object (or the object's extension).
+    pub point: Point2D<f64, S>,
+    /// Does [`Projection::point`] lie on the object itself, rather than on
+    /// the extension of a line, the rest of an arc's circle, or the tangent
+    /// leaving the end of a curve?
+    pub within_bounds: bool,
+}
+
+impl<S> Projection<S> {
+    /// Create a new [`Projection`].
+    pub const fn new(point: Point2D<f64, S>, within_bounds: bool) -> Self {
+        Projection {
+            point,
+            within_bounds,
+        }
+    }
+}
+
+impl<S> Copy for Projection<S> {}
+
+impl<S> Clone for Projection<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Something a point can be projected onto.
+pub trait Project<Space> {
+    /// Drop a perpendicular from `point` onto this object, returning
+    /// [`None`] when there's no unique foot (e.g. projecting the centre of a
+    /// circle onto it).
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>>;
+}
+
+impl<Space, P: Project<Space> + ?Sized> Project<Space> for &P {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        (*self).project(point)
+    }
+}
+
+/// Find the foot of the perpendicular from `point` to `geometry`, and
+/// whether it lies within the geometry's bounds (see [`Project`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::project_onto, primitives::Line};
+///
+/// let line = Line::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+///
+/// let inside = project_onto(Point::new(3.0, 4.0), &line).unwrap();
+/// assert_eq!(inside.point, Point::new(3.0, 0.0));
+/// assert!(inside.within_bounds);
+///
+/// let beyond_the_end = project_onto(Point::new(12.0, 4.0), &line).unwrap();
+/// assert_eq!(beyond_the_end.point, Point::new(12.0, 0.0));
+/// assert!(!beyond_the_end.within_bounds);
+/// ```
+pub fn project_onto<S, G>(
+    point: Point2D<f64, S>,
+    geometry: &G,
+) -> Option<Projection<S>>
+where
+    G: Project<S> + ?Sized,
+{
+    geometry.project(point)
+}
+
+/// Project onto the infinite line through `base_point`, returning the foot
+/// and how far along `direction` it is.
+fn onto_line<S>(
+    point: Point2D<f64, S>,
+    base_point: Point2D<f64, S>,
+    direction: Vector2D<f64, S>,
+) -> Option<(Point2D<f64, S>, f64)> {
+    let length_squared = direction.square_length();
+
+    if length_squared == 0.0 {
+        return None;
+    }
+
+    let t = (point - base_point).dot(direction) / length_squared;
+    Some((base_point + direction * t, t))
+}
+
+impl<Space> Project<Space> for Line<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        let (foot, t) = onto_line(point, self.start, self.displacement())?;
+        Some(Projection::new(foot, (0.0..=1.0).contains(&t)))
+    }
+}
+
+impl<Space> Project<Space> for XLine<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        let (foot, _) = onto_line(point, self.base_point, self.direction)?;
+        Some(Projection::new(foot, true))
+    }
+}
+
+impl<Space> Project<Space> for Ray<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        let (foot, t) = onto_line(point, self.start, self.direction)?;
+        Some(Projection::new(foot, t >= 0.0))
+    }
+}
+
+impl<Space> Project<Space> for Circle<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        let radial = point - self.centre;
+        let length = radial.length();
+
+        if length == 0.0 {
+            return None;
+        }
+
+        Some(Projection::new(
+            self.centre + radial * (self.radius / length),
+            true,
+        ))
+    }
+}
+
+impl<Space> Project<Space> for Arc<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        let circle = Circle::new(self.centre(), self.radius());
+        let foot = circle.project(point)?.point;
+        let radial = foot - self.centre();
+        let angle = crate::Angle::radians(radial.y.atan2(radial.x));
+
+        Some(Projection::new(foot, self.contains_angle(angle)))
+    }
+}
+
+impl<Space> Project<Space> for Ellipse<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        let foot = *self.closest_point(point).points().first()?;
+        Some(Projection::new(foot, true))
+    }
+}
+
+impl<Space> Project<Space> for EllipticalArc<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        let ellipse = self.ellipse();
+        let foot = ellipse.project(point)?.point;
+        let within_bounds =
+            self.contains_angle(ellipse.parametric_angle_of(foot));
+
+        Some(Projection::new(foot, within_bounds))
+    }
+}
+
+/// Project onto a smooth curve, following the tangent off the end of the
+/// curve when the closest point is one of its ends.
+fn onto_curve<Space, C, D>(
+    curve: &C,
+    point: Point2D<f64, Space>,
+    (start, end): (f64, f64),
+    derivative_at: D,
+) -> Option<Projection<Space>>
+where
+    C: ClosestParameter<Space>,
+    D: Fn(f64) -> Vector2D<f64, Space>,
+{
+    let closest = curve.closest_location(point)?;
+    let tolerance = END_TOLERANCE * (end - start).abs().max(1.0);
+    let at_end = if (closest.parameter - start).abs() <= tolerance {
+        Some(start)
+    } else if (end - closest.parameter).abs() <= tolerance {
+        Some(end)
+    } else {
+        None
+    };
+
+    match at_end {
+        Some(t) => {
+            let (foot, along) =
+                onto_line(point, closest.point, derivative_at(t))?;
+            let within_bounds = (foot - closest.point).length() <= tolerance
+                || (t == start && along > 0.0)
+                || (t == end && along < 0.0);
+
+            if within_bounds {
+                Some(Projection::new(closest.point, true))
+            } else {
+                Some(Projection::new(foot, false))
+            }
+        },
+        None => Some(Projection::new(closest.point, true)),
+    }
+}
+
+impl<Space> Project<Space> for CubicBezier<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        onto_curve(self, point, (0.0, 1.0), |t| self.derivative_at(t))
+    }
+}
+
+impl<Space> Project<Space> for QuadraticBezier<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        onto_curve(self, point, (0.0, 1.0), |t| self.derivative_at(t))
+    }
+}
+
+impl<Space> Project<Space> for Spline<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        onto_curve(self, point, self.domain(), |u| self.tangent_at(u))
+    }
+}
+
+impl<Space> Project<Space> for Segment<Space> {
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        match self {
+            Segment::Line(line) => line.project(point),
+            Segment::Arc(arc) => arc.project(point),
+        }
+    }
+}
+
+impl<Space> Project<Space> for Polyline<Space> {
+    /// Project onto the nearest segment, preferring feet which land on a
+    /// segment over those on a segment's extension.
+    fn project(&self, point: Point2D<f64, Space>) -> Option<Projection<Space>> {
+        self.segments()
+            .filter_map(|segment| segment.project(point))
+            .min_by(|a, b| {
+                let key = |p: &Projection<Space>| {
+                    (!p.within_bounds, (p.point - point).length())
+                };
+                key(a).partial_cmp(&key(b)).unwrap()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn project_onto_the_rest_of_an_arcs_circle() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            2.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+
+        let on_arc = arc.project(Point::new(3.0, 3.0)).unwrap();
+        let off_arc = arc.project(Point::new(-3.0, -3.0)).unwrap();
+
+        assert!(on_arc.within_bounds);
+        assert!(on_arc
+            .point
+            .approx_eq(&Point::new(2.0_f64.sqrt(), 2.0_f64.sqrt())));
+        assert!(!off_arc.within_bounds);
+        assert!(off_arc
+            .point
+            .approx_eq(&Point::new(-2.0_f64.sqrt(), -2.0_f64.sqrt())));
+        assert!(arc.project(Point::zero()).is_none());
+    }
+
+    #[test]
+    fn rays_only_go_one_way() {
+        let ray = Ray::new(Point::new(1.0, 1.0), Vector2D::new(1.0, 0.0));
+
+        let got = ray.project(Point::new(-5.0, 3.0)).unwrap();
+
+        assert_eq!(got.point, Point::new(-5.0, 1.0));
+        assert!(!got.within_bounds);
+    }
+
+    #[test]
+    fn follow_the_tangent_off_the_end_of_a_curve() {
+        let curve = QuadraticBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 2.0),
+        );
+
+        // the curve leaves the start point heading along the x axis
+        let got = curve.project(Point::new(-3.0, 1.0)).unwrap();
+
+        assert!(!got.within_bounds);
+        assert!(got.point.approx_eq(&Point::new(-3.0, 0.0)));
+    }
+
+    #[test]
+    fn polylines_prefer_feet_on_a_segment() {
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ]);
+
+        let got = polyline.project(Point::new(11.0, 5.0)).unwrap();
+
+        assert!(got.within_bounds);
+        assert_eq!(got.point, Point::new(10.0, 5.0));
+    }
+}
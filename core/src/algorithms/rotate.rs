@@ -0,0 +1,105 @@
+use crate::{
+    algorithms::{AffineTransform, Transformable},
+    Angle,
+};
+use euclid::Point2D;
+
+/// Something which can be rotated "rigidly" about a point in *Drawing Space*.
+///
+/// Rotations never change an object's size or handedness, so unlike
+/// [`Transformable`] this can't fail.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::Rotate, primitives::Line, Angle};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let line = Line::new(Point::new(1.0, 1.0), Point::new(3.0, 1.0));
+///
+/// let rotated = line.rotated(line.start, Angle::frac_pi_2());
+///
+/// assert_eq!(rotated.start, line.start);
+/// assert!(rotated.end.approx_eq(&Point::new(1.0, 3.0)));
+/// ```
+pub trait Rotate<Space> {
+    /// Rotate this object anticlockwise about `base_point` in-place.
+    fn rotate(&mut self, base_point: Point2D<f64, Space>, angle: Angle);
+
+    /// A convenience method for getting a rotated copy of this object.
+    fn rotated(&self, base_point: Point2D<f64, Space>, angle: Angle) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut clone = self.clone();
+        clone.rotate(base_point, angle);
+
+        clone
+    }
+}
+
+impl<Space, T: Transformable<Space>> Rotate<Space> for T {
+    fn rotate(&mut self, base_point: Point2D<f64, Space>, angle: Angle) {
+        self.transform_by(AffineTransform::rotation_about(base_point, angle))
+            .expect("Rotations preserve shape, size and handedness");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{Arc, Ellipse, Polyline, Vertex};
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn rotate_an_arc_about_the_origin() {
+        let arc = Arc::from_centre_radius(
+            Point::new(2.0, 0.0),
+            1.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+
+        let got = arc.rotated(Point::zero(), Angle::frac_pi_2());
+
+        assert!(got.centre().approx_eq(&Point::new(0.0, 2.0)));
+        assert!(got.start().approx_eq(&Point::new(0.0, 3.0)));
+        assert!(got.end().approx_eq(&Point::new(-1.0, 2.0)));
+        assert!(got.is_anticlockwise());
+    }
+
+    #[test]
+    fn rotating_an_ellipse_about_its_centre_only_changes_its_rotation() {
+        let ellipse =
+            Ellipse::new(Point::new(1.0, 2.0), 3.0, 1.0, Angle::degrees(10.0));
+
+        let got = ellipse.rotated(ellipse.centre(), Angle::degrees(30.0));
+
+        assert!(got.centre().approx_eq(&ellipse.centre()));
+        assert!(got.semi_major().approx_eq(&3.0));
+        assert!(got
+            .rotation()
+            .radians
+            .approx_eq(&Angle::degrees(40.0).radians));
+    }
+
+    #[test]
+    fn polylines_keep_their_bulges() {
+        let polyline = Polyline::new(
+            vec![
+                Vertex::new(Point::new(0.0, 0.0), 0.5),
+                Vertex::straight(Point::new(2.0, 0.0)),
+            ],
+            false,
+        );
+
+        let got = polyline.rotated(Point::new(1.0, 1.0), Angle::pi());
+
+        assert!(got.vertices()[0].point.approx_eq(&Point::new(2.0, 2.0)));
+        assert!(got.vertices()[1].point.approx_eq(&Point::new(0.0, 2.0)));
+        assert_eq!(got.vertices()[0].bulge, 0.5);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::algorithms::{AffineTransform, TransformError, Transformable};
+use euclid::Point2D;
+
+/// Get a copy of `geometry` stretched by independent factors along the x and
+/// y axes, relative to `base_point`.
+///
+/// Rather than silently distorting them, [`Circle`][crate::primitives::Circle]s
+/// and [`Arc`][crate::primitives::Arc]s which would be squashed are rejected
+/// with [`TransformError::NonUniform`]. Convert them into an
+/// [`Ellipse`][crate::primitives::Ellipse] or
+/// [`EllipticalArc`][crate::primitives::EllipticalArc] first to stretch them.
+/// A factor of zero would flatten everything, so it is rejected with
+/// [`TransformError::Degenerate`].
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::{scale_about, TransformError},
+///     primitives::{Circle, Ellipse},
+/// };
+/// use euclid::approxeq::ApproxEq;
+///
+/// let circle = Circle::new(Point::new(2.0, 1.0), 1.0);
+/// let base_point = Point::new(1.0, 1.0);
+///
+/// assert_eq!(
+///     scale_about(&circle, base_point, 2.0, 1.0),
+///     Err(TransformError::NonUniform)
+/// );
+///
+/// let ellipse =
+///     scale_about(&Ellipse::from(circle), base_point, 2.0, 1.0).unwrap();
+///
+/// assert!(ellipse.centre().approx_eq(&Point::new(3.0, 1.0)));
+/// assert!(ellipse.semi_major().approx_eq(&2.0));
+/// assert!(ellipse.semi_minor().approx_eq(&1.0));
+/// ```
+pub fn scale_about<S, T>(
+    geometry: &T,
+    base_point: Point2D<f64, S>,
+    factor_x: f64,
+    factor_y: f64,
+) -> Result<T, TransformError>
+where
+    T: Transformable<S> + Clone,
+{
+    if factor_x == 0.0 || factor_y == 0.0 {
+        return Err(TransformError::Degenerate);
+    }
+
+    geometry.transformed_by(AffineTransform::scale_about(
+        base_point, factor_x, factor_y,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        primitives::{Arc, EllipticalArc, Line},
+        Angle,
+    };
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn stretch_a_line_about_its_start() {
+        let line = Line::new(Point::new(1.0, 1.0), Point::new(3.0, 2.0));
+
+        let got = scale_about(&line, line.start, 2.0, -3.0).unwrap();
+
+        assert_eq!(got.start, line.start);
+        assert!(got.end.approx_eq(&Point::new(5.0, -2.0)));
+    }
+
+    #[test]
+    fn uniformly_scaled_arcs_stay_arcs() {
+        let arc = Arc::from_centre_radius(
+            Point::new(1.0, 0.0),
+            1.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+
+        let got = scale_about(&arc, Point::zero(), 3.0, 3.0).unwrap();
+
+        assert!(got.centre().approx_eq(&Point::new(3.0, 0.0)));
+        assert!(got.radius().approx_eq(&3.0));
+        assert!(got
+            .sweep_angle()
+            .radians
+            .approx_eq(&arc.sweep_angle().radians));
+    }
+
+    #[test]
+    fn squashed_arcs_need_to_be_elliptical() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            2.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+
+        assert_eq!(
+            scale_about(&arc, Point::zero(), 1.0, 0.5),
+            Err(TransformError::NonUniform)
+        );
+
+        let got =
+            scale_about(&EllipticalArc::from(arc), Point::zero(), 1.0, 0.5)
+                .unwrap();
+
+        assert!(got.start().approx_eq(&Point::new(2.0, 0.0)));
+        assert!(got.end().approx_eq(&Point::new(0.0, 1.0)));
+    }
+
+    #[test]
+    fn zero_factors_are_rejected() {
+        assert_eq!(
+            scale_about(&Point::new(1.0, 1.0), Point::zero(), 0.0, 1.0),
+            Err(TransformError::Degenerate)
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{
+    algorithms::{Bounded, Intersect, Length},
+    primitives::{Polyline, Region, Segment},
+    ApproxEqWithin, BoundingBox, Tolerance,
+};
+use euclid::Point2D;
+use std::cmp::Ordering;
+
+/// Somewhere an object's boundary crosses or touches itself.
+#[derive(Debug, PartialEq)]
+pub struct SelfIntersection<S> {
+    /// Where the boundary touches itself.
+    pub point: Point2D<f64, S>,
+    /// The index of the first segment involved.
+    pub first: usize,
+    /// The index of the second segment involved (always greater than
+    /// [`SelfIntersection::first`]).
+    pub second: usize,
+}
+
+impl<S> Copy for SelfIntersection<S> {}
+
+impl<S> Clone for SelfIntersection<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Something with a boundary which may cross over itself.
+///
+/// Neighbouring segments always meet at their shared vertex, so that isn't
+/// counted, but neighbours which double back over each other are. Segments
+/// with no length are ignored.
+///
+/// Every pair of intersecting segments is reported, so a boundary passing
+/// through one of its own vertices is reported once for each segment
+/// touching that vertex.
+pub trait SelfIntersect<S> {
+    /// Find everywhere the boundary touches itself, treating things within
+    /// `tolerance` of each other as touching.
+    fn self_intersections_within(
+        &self,
+        tolerance: Tolerance,
+    ) -> Vec<SelfIntersection<S>>;
+
+    /// Find everywhere the boundary touches itself, using the default
+    /// [`Tolerance`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{algorithms::SelfIntersect, primitives::Polyline};
+    /// use euclid::approxeq::ApproxEq;
+    ///
+    /// let bow_tie = Polyline::from_points(vec![
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(2.0, 2.0),
+    ///     Point::new(2.0, 0.0),
+    ///     Point::new(0.0, 2.0),
+    /// ]);
+    ///
+    /// let got = bow_tie.self_intersections();
+    ///
+    /// assert_eq!(got.len(), 1);
+    /// assert!(got[0].point.approx_eq(&Point::new(1.0, 1.0)));
+    /// assert_eq!((got[0].first, got[0].second), (0, 2));
+    /// ```
+    fn self_intersections(&self) -> Vec<SelfIntersection<S>> {
+        self.self_intersections_within(Tolerance::default())
+    }
+
+    /// Does the boundary never touch itself?
+    fn is_simple(&self) -> bool { self.self_intersections().is_empty() }
+}
+
+impl<S, T: SelfIntersect<S> + ?Sized> SelfIntersect<S> for &T {
+    fn self_intersections_within(
+        &self,
+        tolerance: Tolerance,
+    ) -> Vec<SelfIntersection<S>> {
+        (*self).self_intersections_within(tolerance)
+    }
+}
+
+impl<S> SelfIntersect<S> for Polyline<S> {
+    /// Segments are numbered in the same order as [`Polyline::segments()`].
+    fn self_intersections_within(
+        &self,
+        tolerance: Tolerance,
+    ) -> Vec<SelfIntersection<S>> {
+        sweep(std::iter::once(self), tolerance)
+    }
+}
+
+impl<S> SelfIntersect<S> for Region<S> {
+    /// Segments are numbered consecutively across every loop, in the order
+    /// given by [`Region::loops()`], so loops crossing each other are also
+    /// reported.
+    fn self_intersections_within(
+        &self,
+        tolerance: Tolerance,
+    ) -> Vec<SelfIntersection<S>> {
+        sweep(self.loops(), tolerance)
+    }
+}
+
+struct Item<S> {
+    index: usize,
+    loop_index: usize,
+    /// The position within its loop, ignoring zero-length segments.
+    position: usize,
+    segment: Segment<S>,
+    bounds: BoundingBox<S>,
+}
+
+/// Find the intersections using a sweep line moving along the x axis, so
+/// only segments which overlap horizontally are compared.
+fn sweep<'a, S: 'a, L>(
+    loops: L,
+    tolerance: Tolerance,
+) -> Vec<SelfIntersection<S>>
+where
+    L: IntoIterator<Item = &'a Polyline<S>>,
+{
+    let mut items = Vec::new();
+    let mut loop_info = Vec::new();
+    let mut index = 0;
+
+    for (loop_index, polyline) in loops.into_iter().enumerate() {
+        let mut position = 0;
+
+        for segment in polyline.segments() {
+            if segment.length() > tolerance.linear {
+                items.push(Item {
+                    index,
+                    loop_index,
+                    position,
+                    segment,
+                    bounds: segment.bounding_box(),
+                });
+                position += 1;
+            }
+            index += 1;
+        }
+
+        loop_info.push((position, polyline.is_closed()));
+    }
+
+    items.sort_by(|a, b| {
+        a.bounds
+            .min_x()
+            .partial_cmp(&b.bounds.min_x())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut found = Vec::new();
+    let mut active: Vec<&Item<S>> = Vec::new();
+
+    for item in &items {
+        active.retain(|a| {
+            a.bounds.max_x() + tolerance.linear >= item.bounds.min_x()
+        });
+
+        for &other in &active {
+            let overlaps_vertically = other.bounds.min_y()
+                <= item.bounds.max_y() + tolerance.linear
+                && item.bounds.min_y()
+                    <= other.bounds.max_y() + tolerance.linear;
+
+            if overlaps_vertically {
+                compare(other, item, &loop_info, tolerance, &mut found);
+            }
+        }
+
+        active.push(item);
+    }
+
+    found.sort_by_key(|i| (i.first, i.second));
+    found
+}
+
+fn compare<S>(
+    a: &Item<S>,
+    b: &Item<S>,
+    loop_info: &[(usize, bool)],
+    tolerance: Tolerance,
+    found: &mut Vec<SelfIntersection<S>>,
+) {
+    let (first, second) = if a.index < b.index { (a, b) } else { (b, a) };
+    let shared = shared_vertices(first, second, loop_info);
+
+    for point in first.segment.intersect_within(&second.segment, tolerance) {
+        let is_shared_vertex = shared
+            .iter()
+            .any(|vertex| vertex.approx_eq_within(&point, tolerance));
+
+        if !is_shared_vertex {
+            found.push(SelfIntersection {
+                point,
+                first: first.index,
+                second: second.index,
+            });
+        }
+    }
+}
+
+/// The vertices two neighbouring segments are expected to meet at.
+fn shared_vertices<S>(
+    first: &Item<S>,
+    second: &Item<S>,
+    loop_info: &[(usize, bool)],
+) -> Vec<Point2D<f64, S>> {
+    let mut shared = Vec::new();
+
+    if first.loop_index != second.loop_index {
+        return shared;
+    }
+
+    let (count, closed) = loop_info[first.loop_index];
+
+    if second.position == first.position + 1 {
+        shared.push(first.segment.end());
+    }
+    if closed && first.position == 0 && second.position + 1 == count {
+        shared.push(first.segment.start());
+    }
+
+    shared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Vertex;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn square(x: f64, y: f64, size: f64) -> Polyline<euclid::UnknownUnit> {
+        Polyline::from_points(vec![
+            Point::new(x, y),
+            Point::new(x + size, y),
+            Point::new(x + size, y + size),
+            Point::new(x, y + size),
+        ])
+    }
+
+    #[test]
+    fn simple_shapes_are_simple() {
+        let mut closed = square(0.0, 0.0, 1.0);
+        closed.set_closed(true);
+
+        assert!(square(0.0, 0.0, 1.0).is_simple());
+        assert!(closed.is_simple());
+    }
+
+    #[test]
+    fn two_arcs_making_a_circle() {
+        let circle = Polyline::new(
+            vec![
+                Vertex::new(Point::new(-1.0, 0.0), 1.0),
+                Vertex::new(Point::new(1.0, 0.0), 1.0),
+            ],
+            true,
+        );
+
+        assert!(circle.is_simple());
+    }
+
+    #[test]
+    fn doubling_back_is_an_intersection() {
+        let spike = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 0.0),
+        ]);
+
+        let got = spike.self_intersections();
+
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].point, Point::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn duplicate_vertices_are_ignored() {
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+        ]);
+
+        assert!(polyline.is_simple());
+    }
+
+    #[test]
+    fn overlapping_loops_in_a_region() {
+        let region =
+            Region::new(square(0.0, 0.0, 4.0), vec![square(3.0, 1.0, 2.0)]);
+
+        let got = region.self_intersections();
+
+        assert_eq!(got.len(), 2);
+        // the outer loop is segments 0-3 and the hole is segments 4-7
+        assert!(got.iter().all(|i| i.first < 4 && i.second >= 4));
+        assert!(Region::new(
+            square(0.0, 0.0, 4.0),
+            vec![square(1.0, 1.0, 2.0)]
+        )
+        .is_simple());
+    }
+
+    #[test]
+    fn the_sweep_finds_distant_pairs() {
+        // a long zig-zag where the first and last segments cross
+        let mut points = vec![Point::new(0.0, 5.0), Point::new(50.0, 5.0)];
+        for i in 0..20 {
+            let x = 50.0 - i as f64 * 2.5;
+            points.push(Point::new(x, if i % 2 == 0 { 10.0 } else { 8.0 }));
+        }
+        points.push(Point::new(1.0, 0.0));
+
+        let got = Polyline::from_points(points).self_intersections();
+
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].first, 0);
+    }
+}
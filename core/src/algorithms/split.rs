@@ -0,0 +1,440 @@
+use crate::primitives::{Arc, Line, Polyline, Segment, Vertex};
+use euclid::Point2D;
+use std::f64::consts::PI;
+
+/// Parameter values closer than this are considered to be the same.
+pub(crate) const PARAMETER_TOLERANCE: f64 = 1e-9;
+
+/// Something which can be cut into pieces at a parameter along its length.
+///
+/// Lines and arcs are parameterised from `0` at the start to `1` at the end,
+/// while the `n`'th segment of a polyline covers parameters from `n` to
+/// `n + 1`.
+pub trait Split<Space>: Sized {
+    /// Cut this object in two at `t`.
+    ///
+    /// Splitting an open curve at one of its ends (or outside it) leaves it
+    /// untouched, while splitting a closed curve opens it up so it starts
+    /// and ends at `t`.
+    fn split_at(&self, t: f64) -> Vec<Self>;
+
+    /// Remove the part of this object between `t1` and `t2`, returning the
+    /// pieces which are left.
+    ///
+    /// For closed curves the part removed goes from `t1` forwards to `t2`,
+    /// wrapping around past the end if necessary.
+    fn break_between(&self, t1: f64, t2: f64) -> Vec<Self>;
+}
+
+/// Cut something in two at the parameter, `t` (see [`Split::split_at()`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::split_at, primitives::Line};
+///
+/// let line = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+///
+/// let got = split_at(&line, 0.25);
+///
+/// assert_eq!(
+///     got,
+///     vec![
+///         Line::new(Point::new(0.0, 0.0), Point::new(1.0, 0.0)),
+///         Line::new(Point::new(1.0, 0.0), Point::new(4.0, 0.0)),
+///     ]
+/// );
+/// ```
+pub fn split_at<S, C>(geometry: &C, t: f64) -> Vec<C>
+where
+    C: Split<S>,
+{
+    geometry.split_at(t)
+}
+
+/// Remove the part of something between two parameters (see
+/// [`Split::break_between()`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::break_between, primitives::Line};
+///
+/// let line = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+///
+/// let got = break_between(&line, 0.75, 0.25);
+///
+/// assert_eq!(
+///     got,
+///     vec![
+///         Line::new(Point::new(0.0, 0.0), Point::new(1.0, 0.0)),
+///         Line::new(Point::new(3.0, 0.0), Point::new(4.0, 0.0)),
+///     ]
+/// );
+/// ```
+pub fn break_between<S, C>(geometry: &C, t1: f64, t2: f64) -> Vec<C>
+where
+    C: Split<S>,
+{
+    geometry.break_between(t1, t2)
+}
+
+impl<S> Split<S> for Line<S> {
+    fn split_at(&self, t: f64) -> Vec<Self> { split_curve(self, t) }
+
+    fn break_between(&self, t1: f64, t2: f64) -> Vec<Self> {
+        break_curve(self, t1, t2)
+    }
+}
+
+impl<S> Split<S> for Arc<S> {
+    fn split_at(&self, t: f64) -> Vec<Self> { split_curve(self, t) }
+
+    fn break_between(&self, t1: f64, t2: f64) -> Vec<Self> {
+        break_curve(self, t1, t2)
+    }
+}
+
+impl<S> Split<S> for Polyline<S> {
+    fn split_at(&self, t: f64) -> Vec<Self> {
+        if self.len() < 2 {
+            return vec![self.clone()];
+        }
+
+        split_curve(self, t)
+    }
+
+    fn break_between(&self, t1: f64, t2: f64) -> Vec<Self> {
+        if self.len() < 2 {
+            return vec![self.clone()];
+        }
+
+        break_curve(self, t1, t2)
+    }
+}
+
+fn split_curve<S, C>(curve: &C, t: f64) -> Vec<C>
+where
+    C: Parameterised<S> + Clone,
+{
+    let (start, end) = curve.parameter_range();
+
+    if curve.is_closed_curve() {
+        let t = t.rem_euclid(end - start) + start;
+        return vec![curve.portion(t, t + (end - start))];
+    }
+
+    if t <= start + PARAMETER_TOLERANCE || t >= end - PARAMETER_TOLERANCE {
+        vec![curve.clone()]
+    } else {
+        vec![curve.portion(start, t), curve.portion(t, end)]
+    }
+}
+
+fn break_curve<S, C>(curve: &C, t1: f64, t2: f64) -> Vec<C>
+where
+    C: Parameterised<S> + Clone,
+{
+    let (start, end) = curve.parameter_range();
+
+    if curve.is_closed_curve() {
+        let wrap = |t: f64| t.rem_euclid(end - start) + start;
+        let (t1, t2) = (wrap(t1), wrap(t2));
+
+        if (t1 - t2).abs() <= PARAMETER_TOLERANCE {
+            return split_curve(curve, t1);
+        }
+
+        return vec![curve.portion(t2, t1)];
+    }
+
+    let (t1, t2) = if t1 <= t2 { (t1, t2) } else { (t2, t1) };
+    let mut pieces = Vec::new();
+
+    if t1 > start + PARAMETER_TOLERANCE {
+        pieces.push(curve.portion(start, t1.min(end)));
+    }
+    if t2 < end - PARAMETER_TOLERANCE {
+        pieces.push(curve.portion(t2.max(start), end));
+    }
+
+    pieces
+}
+
+/// A curve which can be walked along using a single parameter, and cut into
+/// pieces.
+pub(crate) trait Parameterised<S>: Sized {
+    /// The parameter values at the start and end of the curve.
+    fn parameter_range(&self) -> (f64, f64);
+
+    /// Does the curve join back up with itself, so parameters wrap around?
+    fn is_closed_curve(&self) -> bool;
+
+    /// The parameter of the point on the curve closest to `point`.
+    fn parameter_of(&self, point: Point2D<f64, S>) -> f64;
+
+    /// The point at a particular parameter value.
+    fn point_at_parameter(&self, t: f64) -> Point2D<f64, S>;
+
+    /// The part of the curve between two parameters.
+    ///
+    /// For closed curves `start` may be greater than `end`, in which case
+    /// the portion wraps around past the end of the curve.
+    fn portion(&self, start: f64, end: f64) -> Self;
+}
+
+impl<S> Parameterised<S> for Line<S> {
+    fn parameter_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn is_closed_curve(&self) -> bool { false }
+
+    fn parameter_of(&self, point: Point2D<f64, S>) -> f64 {
+        let displacement = self.displacement();
+        let length_squared = displacement.square_length();
+
+        if length_squared == 0.0 {
+            0.0
+        } else {
+            ((point - self.start).dot(displacement) / length_squared)
+                .max(0.0)
+                .min(1.0)
+        }
+    }
+
+    fn point_at_parameter(&self, t: f64) -> Point2D<f64, S> {
+        self.start.lerp(self.end, t)
+    }
+
+    fn portion(&self, start: f64, end: f64) -> Self {
+        Line::new(self.point_at_parameter(start), self.point_at_parameter(end))
+    }
+}
+
+impl<S> Parameterised<S> for Arc<S> {
+    fn parameter_range(&self) -> (f64, f64) { (0.0, 1.0) }
+
+    fn is_closed_curve(&self) -> bool {
+        self.sweep_angle().radians.abs() >= 2.0 * PI - PARAMETER_TOLERANCE
+    }
+
+    fn parameter_of(&self, point: Point2D<f64, S>) -> f64 {
+        let radial = point - self.centre();
+        let sweep = self.sweep_angle().radians;
+
+        // the angle from the start, measured in the direction of travel
+        let mut angle = (radial.y.atan2(radial.x) - self.start_angle().radians)
+            .rem_euclid(2.0 * PI);
+        if sweep < 0.0 {
+            angle = (2.0 * PI - angle).rem_euclid(2.0 * PI);
+        }
+
+        let fraction = angle / sweep.abs();
+
+        if fraction <= 1.0 || self.is_closed_curve() {
+            fraction.min(1.0)
+        } else if angle - sweep.abs() < 2.0 * PI - angle {
+            // closer to the end than the start
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn point_at_parameter(&self, t: f64) -> Point2D<f64, S> {
+        self.point_at(self.sweep_angle() * t)
+    }
+
+    fn portion(&self, start: f64, end: f64) -> Self {
+        let length = if end < start {
+            end + 1.0 - start
+        } else {
+            end - start
+        };
+
+        Arc::from_centre_radius(
+            self.centre(),
+            self.radius(),
+            self.start_angle() + self.sweep_angle() * start,
+            self.sweep_angle() * length,
+        )
+    }
+}
+
+impl<S> Parameterised<S> for Polyline<S> {
+    /// The `n`'th segment covers parameters from `n` to `n + 1`.
+    fn parameter_range(&self) -> (f64, f64) {
+        (0.0, self.segments().count() as f64)
+    }
+
+    fn is_closed_curve(&self) -> bool { self.is_closed() }
+
+    fn parameter_of(&self, point: Point2D<f64, S>) -> f64 {
+        let mut best = (std::f64::INFINITY, 0.0);
+
+        for (i, segment) in self.segments().enumerate() {
+            let t = match segment {
+                Segment::Line(line) => line.parameter_of(point),
+                Segment::Arc(arc) => arc.parameter_of(point),
+            };
+            let closest = segment_point(segment, t);
+            let distance = (closest - point).length();
+
+            if distance < best.0 {
+                best = (distance, i as f64 + t);
+            }
+        }
+
+        best.1
+    }
+
+    fn point_at_parameter(&self, t: f64) -> Point2D<f64, S> {
+        let count = self.segments().count();
+        if count == 0 {
+            return self.vertices()[0].point;
+        }
+
+        let (index, fraction) = split_parameter(t, count);
+        segment_point(self.segments().nth(index).unwrap(), fraction)
+    }
+
+    fn portion(&self, start: f64, end: f64) -> Self {
+        let segments: Vec<_> = self.segments().collect();
+        let count = segments.len();
+        let end = if end < start { end + count as f64 } else { end };
+
+        let mut vertices = Vec::new();
+        let mut t = start;
+
+        while t < end - PARAMETER_TOLERANCE {
+            let whole = (t + PARAMETER_TOLERANCE).floor();
+            let next = (whole + 1.0).min(end);
+            let segment = segments[whole as usize % count];
+            let (from, to) = ((t - whole).max(0.0), next - whole);
+
+            let bulge = match segment {
+                Segment::Line(_) => 0.0,
+                Segment::Arc(arc) => {
+                    (arc.sweep_angle().radians * (to - from) / 4.0).tan()
+                },
+            };
+            vertices.push(Vertex::new(segment_point(segment, from), bulge));
+
+            t = next;
+        }
+
+        vertices.push(Vertex::straight(self.point_at_parameter(end)));
+
+        Polyline::new(vertices, false)
+    }
+}
+
+/// Turn a polyline parameter into a segment index and the fraction along
+/// that segment.
+fn split_parameter(t: f64, count: usize) -> (usize, f64) {
+    let count_f64 = count as f64;
+    let t = if t > count_f64 { t - count_f64 } else { t };
+    let index = (t.floor() as usize).min(count - 1);
+
+    (index, t - index as f64)
+}
+
+fn segment_point<S>(segment: Segment<S>, t: f64) -> Point2D<f64, S> {
+    match segment {
+        Segment::Line(line) => line.point_at_parameter(t),
+        Segment::Arc(arc) => arc.point_at_parameter(t),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn splitting_at_the_end_does_nothing() {
+        let line = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+
+        assert_eq!(line.split_at(1.0), vec![line]);
+        assert_eq!(line.split_at(-2.0), vec![line]);
+    }
+
+    #[test]
+    fn split_an_arc_in_half() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::pi(),
+        );
+
+        let got = arc.split_at(0.5);
+
+        assert_eq!(got.len(), 2);
+        assert!(got[0].end().approx_eq(&Point::new(0.0, 1.0)));
+        assert!(got[1].start().approx_eq(&Point::new(0.0, 1.0)));
+        assert!(got[1].end().approx_eq(&arc.end()));
+    }
+
+    #[test]
+    fn split_a_polyline_part_way_along_a_segment() {
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+        ]);
+
+        let got = polyline.split_at(1.5);
+
+        let points = |p: &Polyline<_>| -> Vec<Point> {
+            p.vertices().iter().map(|v| v.point).collect()
+        };
+        assert_eq!(got.len(), 2);
+        assert_eq!(
+            points(&got[0]),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(2.0, 1.0)
+            ]
+        );
+        assert_eq!(
+            points(&got[1]),
+            vec![Point::new(2.0, 1.0), Point::new(2.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn break_a_hole_in_a_circle() {
+        let circle = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        );
+
+        // remove the first quarter
+        let got = circle.break_between(0.0, 0.25);
+
+        assert_eq!(got.len(), 1);
+        assert!(got[0].start().approx_eq(&Point::new(0.0, 1.0)));
+        assert!(got[0].end().approx_eq(&Point::new(1.0, 0.0)));
+        assert!(got[0].sweep_angle().radians.approx_eq(&(1.5 * PI)));
+    }
+
+    #[test]
+    fn break_out_the_start_of_a_line() {
+        let line = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+
+        let got = line.break_between(0.0, 0.5);
+
+        assert_eq!(
+            got,
+            vec![Line::new(Point::new(2.0, 0.0), Point::new(4.0, 0.0))]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    algorithms::{intersection::arc_parameter, DEFAULT_TOLERANCE},
+    primitives::{Arc, Line},
+};
+use euclid::{Point2D, Vector2D};
+
+/// Find the lines which start at `point` and touch the `arc` at a tangent.
+///
+/// Each line goes from `point` to where it touches the arc's circle, and
+/// only tangent points which are actually on the arc are included. Points
+/// on or inside the circle don't have any tangent lines.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::tangents_from_point, primitives::Arc, Angle};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let circle = Arc::from_centre_radius(
+///     Point::zero(),
+///     1.0,
+///     Angle::zero(),
+///     Angle::two_pi(),
+/// );
+/// let point = Point::new(2.0, 0.0);
+///
+/// let got = tangents_from_point(point, circle);
+///
+/// assert_eq!(got.len(), 2);
+/// for line in got {
+///     assert_eq!(line.start, point);
+///     // the radius is perpendicular to the tangent
+///     let radius = line.end - circle.centre();
+///     assert!(radius.dot(line.displacement()).approx_eq(&0.0));
+/// }
+/// ```
+pub fn tangents_from_point<S>(
+    point: Point2D<f64, S>,
+    arc: Arc<S>,
+) -> Vec<Line<S>> {
+    let to_point = point - arc.centre();
+    let distance = to_point.length();
+    let radius = arc.radius();
+
+    if distance <= radius + DEFAULT_TOLERANCE {
+        return Vec::new();
+    }
+
+    // the tangent points are where the radius is at right angles to the
+    // line from the point
+    let along = radius * radius / distance;
+    let across = (radius * radius - along * along).max(0.0).sqrt();
+    let direction = to_point / distance;
+    let normal = Vector2D::new(-direction.y, direction.x);
+    let foot = arc.centre() + direction * along;
+
+    vec![foot + normal * across, foot - normal * across]
+        .into_iter()
+        .filter(|&touching| on_arc(arc, touching))
+        .map(|touching| Line::new(point, touching))
+        .collect()
+}
+
+/// Find the lines which touch both `first` and `second` at a tangent.
+///
+/// Each line goes from the tangent point on `first` to the tangent point on
+/// `second`, with the outer tangents (which don't cross between the
+/// circles) coming first. There are up to four solutions, depending on how
+/// the circles overlap, and only tangent points which are actually on the
+/// arcs are included.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::common_tangents, primitives::Arc, Angle};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let circle = |x: f64, radius: f64| {
+///     Arc::from_centre_radius(
+///         Point::new(x, 0.0),
+///         radius,
+///         Angle::zero(),
+///         Angle::two_pi(),
+///     )
+/// };
+///
+/// let got = common_tangents(circle(0.0, 1.0), circle(5.0, 1.0));
+///
+/// assert_eq!(got.len(), 4);
+/// // the outer tangents run along the top and bottom
+/// assert!(got[0].start.y.abs().approx_eq(&1.0));
+/// assert!(got[0].end.y.approx_eq(&got[0].start.y));
+/// ```
+pub fn common_tangents<S>(first: Arc<S>, second: Arc<S>) -> Vec<Line<S>> {
+    let between = second.centre() - first.centre();
+    let distance = between.length();
+
+    if distance <= DEFAULT_TOLERANCE {
+        // concentric circles never have a common tangent
+        return Vec::new();
+    }
+
+    let direction = between / distance;
+    let mut tangents: Vec<Line<S>> = Vec::new();
+
+    // outer tangents have both circles on the same side
+    for &side in &[1.0, -1.0] {
+        let cos = (first.radius() - side * second.radius()) / distance;
+        if cos.abs() > 1.0 + DEFAULT_TOLERANCE {
+            // one circle is inside the other (or they overlap)
+            continue;
+        }
+
+        let cos = cos.max(-1.0).min(1.0);
+        let sin = (1.0 - cos * cos).max(0.0).sqrt();
+
+        for &turn in &[1.0, -1.0] {
+            let normal = Vector2D::new(
+                direction.x * cos - turn * sin * direction.y,
+                direction.y * cos + turn * sin * direction.x,
+            );
+            let line = Line::new(
+                first.centre() + normal * first.radius(),
+                second.centre() + normal * (side * second.radius()),
+            );
+
+            let duplicate = tangents.iter().any(|existing| {
+                (existing.start - line.start).length() <= DEFAULT_TOLERANCE
+                    && (existing.end - line.end).length() <= DEFAULT_TOLERANCE
+            });
+
+            if !duplicate
+                && on_arc(first, line.start)
+                && on_arc(second, line.end)
+            {
+                tangents.push(line);
+            }
+        }
+    }
+
+    tangents
+}
+
+fn on_arc<S>(arc: Arc<S>, point: Point2D<f64, S>) -> bool {
+    arc_parameter(arc, point, DEFAULT_TOLERANCE * arc.radius().max(1.0))
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn circle(x: f64, y: f64, radius: f64) -> Arc<euclid::UnknownUnit> {
+        Arc::from_centre_radius(
+            Point::new(x, y),
+            radius,
+            Angle::zero(),
+            Angle::two_pi(),
+        )
+    }
+
+    #[test]
+    fn only_tangent_points_on_the_arc_are_used() {
+        // the top half of a unit circle
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::pi(),
+        );
+
+        let got = tangents_from_point(Point::new(2.0, 0.0), arc);
+
+        assert_eq!(got.len(), 1);
+        assert!(got[0].end.y > 0.0);
+        assert!(got[0].end.approx_eq(&Point::new(0.5, 0.75_f64.sqrt())));
+    }
+
+    #[test]
+    fn points_inside_the_circle_have_no_tangents() {
+        let got =
+            tangents_from_point(Point::new(0.5, 0.0), circle(0.0, 0.0, 1.0));
+
+        assert!(got.is_empty());
+    }
+
+    #[test]
+    fn tangents_between_different_sized_circles() {
+        let small = circle(0.0, 0.0, 1.0);
+        let large = circle(6.0, 2.0, 2.0);
+
+        let got = common_tangents(small, large);
+
+        assert_eq!(got.len(), 4);
+        for line in got {
+            let direction = line.displacement();
+            for (arc, point) in &[(small, line.start), (large, line.end)] {
+                let radius = *point - arc.centre();
+                assert!(radius.length().approx_eq(&arc.radius()));
+                assert!(radius.dot(direction).approx_eq_eps(&0.0, &1e-9));
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping_circles_only_have_outer_tangents() {
+        let got = common_tangents(circle(0.0, 0.0, 2.0), circle(3.0, 0.0, 2.0));
+
+        assert_eq!(got.len(), 2);
+        assert!(got[0].start.approx_eq(&Point::new(0.0, 2.0)));
+        assert!(got[0].end.approx_eq(&Point::new(3.0, 2.0)));
+    }
+
+    #[test]
+    fn nested_circles_dont_have_tangents() {
+        let got = common_tangents(circle(0.0, 0.0, 5.0), circle(1.0, 0.0, 1.0));
+
+        assert!(got.is_empty());
+    }
+}
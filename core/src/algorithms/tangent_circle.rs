@@ -0,0 +1,564 @@
+use crate::{
+    algorithms::DEFAULT_TOLERANCE,
+    primitives::{Arc, Circle, Line},
+};
+use euclid::{default::Vector3D, Point2D, Vector2D};
+
+/// Something a constructed [`Circle`] can touch.
+#[derive(Debug, PartialEq)]
+pub enum Tangency<S> {
+    /// The circle passes through a point.
+    Point(Point2D<f64, S>),
+    /// The circle touches a [`Line`], extended in both directions.
+    Line(Line<S>),
+    /// The circle touches another [`Circle`], from either the inside or the
+    /// outside.
+    Circle(Circle<S>),
+}
+
+impl<S> Tangency<S> {
+    /// A point we can measure everything else from.
+    fn anchor(&self) -> Point2D<f64, S> {
+        match *self {
+            Tangency::Point(point) => point,
+            Tangency::Line(line) => line.start,
+            Tangency::Circle(circle) => circle.centre,
+        }
+    }
+}
+
+impl<S> From<Point2D<f64, S>> for Tangency<S> {
+    fn from(point: Point2D<f64, S>) -> Self { Tangency::Point(point) }
+}
+
+impl<S> From<Line<S>> for Tangency<S> {
+    fn from(line: Line<S>) -> Self { Tangency::Line(line) }
+}
+
+impl<S> From<Circle<S>> for Tangency<S> {
+    fn from(circle: Circle<S>) -> Self { Tangency::Circle(circle) }
+}
+
+impl<S> From<Arc<S>> for Tangency<S> {
+    fn from(arc: Arc<S>) -> Self {
+        Tangency::Circle(Circle::new(arc.centre(), arc.radius()))
+    }
+}
+
+impl<S> Copy for Tangency<S> {}
+
+impl<S> Clone for Tangency<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Find every [`Circle`] with the given `radius` which passes through both
+/// points.
+///
+/// There are two solutions when the points are closer than the diameter,
+/// one when they are exactly a diameter apart, and none otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::algorithms::circles_through_two_points;
+/// use euclid::approxeq::ApproxEq;
+///
+/// let got = circles_through_two_points(
+///     Point::new(-1.0, 0.0),
+///     Point::new(1.0, 0.0),
+///     2.0_f64.sqrt(),
+/// );
+///
+/// assert_eq!(got.len(), 2);
+/// assert!(got.iter().any(|c| c.centre.approx_eq(&Point::new(0.0, 1.0))));
+/// assert!(got.iter().any(|c| c.centre.approx_eq(&Point::new(0.0, -1.0))));
+/// ```
+pub fn circles_through_two_points<S>(
+    first: Point2D<f64, S>,
+    second: Point2D<f64, S>,
+    radius: f64,
+) -> Vec<Circle<S>> {
+    circles_tangent_to_two(first, second, radius)
+}
+
+/// Find every [`Circle`] with the given `radius` which touches both
+/// entities.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::circles_tangent_to_two, primitives::Line};
+/// use euclid::approxeq::ApproxEq;
+///
+/// // the x and y axes
+/// let horizontal = Line::new(Point::zero(), Point::new(1.0, 0.0));
+/// let vertical = Line::new(Point::zero(), Point::new(0.0, 1.0));
+///
+/// let got = circles_tangent_to_two(horizontal, vertical, 1.0);
+///
+/// // there's one in each quadrant
+/// assert_eq!(got.len(), 4);
+/// for circle in got {
+///     assert!(circle.centre.x.abs().approx_eq(&1.0));
+///     assert!(circle.centre.y.abs().approx_eq(&1.0));
+/// }
+/// ```
+pub fn circles_tangent_to_two<S, A, B>(
+    first: A,
+    second: B,
+    radius: f64,
+) -> Vec<Circle<S>>
+where
+    A: Into<Tangency<S>>,
+    B: Into<Tangency<S>>,
+{
+    if radius <= 0.0 {
+        return Vec::new();
+    }
+
+    solve(&[first.into(), second.into()], Some(radius))
+}
+
+/// Find every [`Circle`] which touches all three entities (the [Problem of
+/// Apollonius][wiki], generalised to points and lines).
+///
+/// Depending on how the entities are arranged there may be up to 8
+/// solutions, so they are all returned and it's up to the caller to pick the
+/// one they want.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{algorithms::circles_tangent_to_three, primitives::Line};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let a = Point::new(0.0, 0.0);
+/// let b = Point::new(4.0, 0.0);
+/// let c = Point::new(0.0, 3.0);
+///
+/// let got = circles_tangent_to_three(
+///     Line::new(a, b),
+///     Line::new(b, c),
+///     Line::new(c, a),
+/// );
+///
+/// // the triangle's incircle and its three excircles
+/// assert_eq!(got.len(), 4);
+/// assert!(got.iter().any(|circle| circle.radius.approx_eq(&1.0)));
+/// ```
+///
+/// [wiki]: https://en.wikipedia.org/wiki/Problem_of_Apollonius
+pub fn circles_tangent_to_three<S, A, B, C>(
+    first: A,
+    second: B,
+    third: C,
+) -> Vec<Circle<S>>
+where
+    A: Into<Tangency<S>>,
+    B: Into<Tangency<S>>,
+    C: Into<Tangency<S>>,
+{
+    solve(&[first.into(), second.into(), third.into()], None)
+}
+
+/// A tangency condition, after choosing which side of the entity the circle
+/// should be on.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Condition {
+    /// `normal · centre - side * radius = offset`.
+    Linear { normal: Vector3D<f64>, offset: f64 },
+    /// `|centre - point|² = (radius + side * r)²`.
+    Quadratic {
+        point: Vector2D<f64, euclid::UnknownUnit>,
+        radius: f64,
+        side: f64,
+    },
+}
+
+/// Every tangency condition can be written as an equation in the unknown
+/// centre and radius, `(x, y, r)`. Equations for lines are already linear,
+/// and subtracting the equations for two circles cancels out the squared
+/// terms, so we end up with a set of linear equations plus (at most) one
+/// quadratic which can be solved directly.
+fn solve<S>(
+    entities: &[Tangency<S>],
+    fixed_radius: Option<f64>,
+) -> Vec<Circle<S>> {
+    // work relative to one of the entities to avoid losing precision
+    let origin = entities[0].anchor();
+    let mut solutions: Vec<Circle<S>> = Vec::new();
+
+    for conditions in side_combinations(entities, origin) {
+        for (centre, radius) in solve_conditions(&conditions, fixed_radius) {
+            let tolerance = DEFAULT_TOLERANCE * radius.max(1.0);
+
+            if radius <= tolerance || !radius.is_finite() {
+                continue;
+            }
+
+            let circle = Circle::new(origin + centre.cast_unit(), radius);
+            let duplicate = solutions.iter().any(|existing| {
+                (existing.centre - circle.centre).length() <= tolerance
+                    && (existing.radius - circle.radius).abs() <= tolerance
+            });
+
+            if !duplicate {
+                solutions.push(circle);
+            }
+        }
+    }
+
+    solutions
+}
+
+/// Get the conditions for every combination of sides the circle could be
+/// on.
+fn side_combinations<S>(
+    entities: &[Tangency<S>],
+    origin: Point2D<f64, S>,
+) -> Vec<Vec<Condition>> {
+    let mut combinations = vec![Vec::new()];
+
+    for entity in entities {
+        let mut next = Vec::new();
+
+        for combination in &combinations {
+            for condition in conditions_for(*entity, origin) {
+                let mut extended: Vec<Condition> = combination.clone();
+                extended.push(condition);
+                next.push(extended);
+            }
+        }
+
+        combinations = next;
+    }
+
+    combinations
+}
+
+fn conditions_for<S>(
+    entity: Tangency<S>,
+    origin: Point2D<f64, S>,
+) -> Vec<Condition> {
+    match entity {
+        Tangency::Point(point) => vec![Condition::Quadratic {
+            point: (point - origin).cast_unit(),
+            radius: 0.0,
+            side: 1.0,
+        }],
+        Tangency::Line(line) => {
+            let direction = line.direction();
+            let normal: Vector2D<f64, S> =
+                Vector2D::new(-direction.y, direction.x);
+            let offset = normal.dot(line.start - origin);
+
+            [1.0, -1.0]
+                .iter()
+                .map(|&side| Condition::Linear {
+                    normal: Vector3D::new(normal.x, normal.y, -side),
+                    offset,
+                })
+                .collect()
+        },
+        Tangency::Circle(circle) => [1.0, -1.0]
+            .iter()
+            .map(|&side| Condition::Quadratic {
+                point: (circle.centre - origin).cast_unit(),
+                radius: circle.radius,
+                side,
+            })
+            .collect(),
+    }
+}
+
+fn solve_conditions(
+    conditions: &[Condition],
+    fixed_radius: Option<f64>,
+) -> Vec<(Vector2D<f64, euclid::UnknownUnit>, f64)> {
+    let mut rows = Vec::new();
+    let mut quadratics = Vec::new();
+
+    for condition in conditions {
+        match *condition {
+            Condition::Linear { normal, offset } => rows.push((normal, offset)),
+            Condition::Quadratic {
+                point,
+                radius,
+                side,
+            } => quadratics.push((point, radius, side)),
+        }
+    }
+
+    if let Some(radius) = fixed_radius {
+        rows.push((Vector3D::new(0.0, 0.0, 1.0), radius));
+    }
+
+    // subtract the first quadratic from the others to get linear equations
+    if let Some(&(reference, reference_radius, reference_side)) =
+        quadratics.first()
+    {
+        let power = |point: Vector2D<f64, _>, radius: f64| {
+            point.square_length() - radius * radius
+        };
+
+        for &(point, radius, side) in &quadratics[1..] {
+            let difference = point - reference;
+            let row = Vector3D::new(
+                -2.0 * difference.x,
+                -2.0 * difference.y,
+                -2.0 * (side * radius - reference_side * reference_radius),
+            );
+            let rhs = power(reference, reference_radius) - power(point, radius);
+            rows.push((row, rhs));
+        }
+    }
+
+    match (rows.len(), quadratics.first()) {
+        (3, None) => solve_linear(&rows).into_iter().collect(),
+        (2, Some(&(point, radius, side))) => {
+            solve_on_line(&rows, point, radius, side)
+        },
+        // anything else is either over or under-constrained
+        _ => Vec::new(),
+    }
+}
+
+/// Solve three linear equations using Cramer's rule.
+fn solve_linear(
+    rows: &[(Vector3D<f64>, f64)],
+) -> Option<(Vector2D<f64, euclid::UnknownUnit>, f64)> {
+    let (a, b, c) = (rows[0].0, rows[1].0, rows[2].0);
+    let rhs = Vector3D::new(rows[0].1, rows[1].1, rows[2].1);
+    let determinant = a.dot(b.cross(c));
+    let scale = a.length() * b.length() * c.length();
+
+    if determinant.abs() <= DEFAULT_TOLERANCE * scale {
+        return None;
+    }
+
+    // swap the right hand side in for each column in turn
+    let columns = [
+        Vector3D::new(a.x, b.x, c.x),
+        Vector3D::new(a.y, b.y, c.y),
+        Vector3D::new(a.z, b.z, c.z),
+    ];
+    let with_rhs = |index: usize| {
+        let mut replaced = columns;
+        replaced[index] = rhs;
+        replaced[0].dot(replaced[1].cross(replaced[2])) / determinant
+    };
+
+    Some((Vector2D::new(with_rhs(0), with_rhs(1)), with_rhs(2)))
+}
+
+/// Two linear equations in `(x, y, r)` define a line of possible solutions,
+/// so we just need to find where that line satisfies the quadratic
+/// `|centre - point|² = (radius + side * r)²`.
+fn solve_on_line(
+    rows: &[(Vector3D<f64>, f64)],
+    point: Vector2D<f64, euclid::UnknownUnit>,
+    radius: f64,
+    side: f64,
+) -> Vec<(Vector2D<f64, euclid::UnknownUnit>, f64)> {
+    let ((first, first_rhs), (second, second_rhs)) = (rows[0], rows[1]);
+    let direction = first.cross(second);
+
+    if direction.length()
+        <= DEFAULT_TOLERANCE * first.length() * second.length()
+    {
+        return Vec::new();
+    }
+
+    // the solution closest to the origin is a combination of the two rows
+    let (aa, ab, bb) =
+        (first.dot(first), first.dot(second), second.dot(second));
+    let gram = aa * bb - ab * ab;
+    let start = first * ((first_rhs * bb - second_rhs * ab) / gram)
+        + second * ((second_rhs * aa - first_rhs * ab) / gram);
+
+    // substitute `start + t * direction` into the quadratic
+    let from_point = Vector2D::new(start.x, start.y) - point;
+    let along = Vector2D::new(direction.x, direction.y);
+    let target = radius + side * start.z;
+    let growth = side * direction.z;
+
+    let a = along.square_length() - growth * growth;
+    let b = 2.0 * (from_point.dot(along) - target * growth);
+    let c = from_point.square_length() - target * target;
+
+    roots(a, b, c)
+        .into_iter()
+        .map(|t| {
+            let solution = start + direction * t;
+            (Vector2D::new(solution.x, solution.y), solution.z)
+        })
+        .collect()
+}
+
+fn roots(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let scale = a.abs().max(b.abs()).max(c.abs());
+
+    if scale == 0.0 {
+        return Vec::new();
+    }
+
+    if a.abs() <= DEFAULT_TOLERANCE * scale {
+        return if b.abs() <= DEFAULT_TOLERANCE * scale {
+            Vec::new()
+        } else {
+            vec![-c / b]
+        };
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < -DEFAULT_TOLERANCE * scale * scale {
+        Vec::new()
+    } else if discriminant <= DEFAULT_TOLERANCE * scale * scale {
+        vec![-b / (2.0 * a)]
+    } else {
+        let root = discriminant.sqrt();
+        vec![(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn assert_touches(
+        circle: Circle<euclid::UnknownUnit>,
+        entity: Tangency<euclid::UnknownUnit>,
+    ) {
+        let touches = match entity {
+            Tangency::Point(point) => (point - circle.centre)
+                .length()
+                .approx_eq_eps(&circle.radius, &1e-6),
+            Tangency::Line(line) => line
+                .perpendicular_distance_to(circle.centre)
+                .get()
+                .approx_eq_eps(&circle.radius, &1e-6),
+            Tangency::Circle(other) => {
+                let distance = (other.centre - circle.centre).length();
+                distance.approx_eq_eps(&(other.radius + circle.radius), &1e-6)
+                    || distance.approx_eq_eps(
+                        &(other.radius - circle.radius).abs(),
+                        &1e-6,
+                    )
+            },
+        };
+
+        assert!(touches, "{:?} doesn't touch {:?}", circle, entity);
+    }
+
+    #[test]
+    fn points_too_far_apart() {
+        let got = circles_through_two_points(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            1.0,
+        );
+
+        assert!(got.is_empty());
+    }
+
+    #[test]
+    fn points_exactly_a_diameter_apart() {
+        let got = circles_through_two_points(
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            1.0,
+        );
+
+        assert_eq!(got.len(), 1);
+        assert!(got[0].centre.approx_eq(&Point::new(1.0, 0.0)));
+    }
+
+    #[test]
+    fn tangent_to_a_line_and_a_circle() {
+        let line: Tangency<_> =
+            Line::new(Point::new(-10.0, 0.0), Point::new(10.0, 0.0)).into();
+        let circle: Tangency<_> = Circle::new(Point::new(0.0, 2.5), 1.0).into();
+
+        let got = circles_tangent_to_two(line, circle, 1.0);
+
+        // the solutions sit on the line and either side of the circle,
+        // anything below the line would be too far away to touch it
+        assert_eq!(got.len(), 2);
+        for solution in got {
+            assert!(solution.centre.y.approx_eq(&1.0));
+            assert_touches(solution, line);
+            assert_touches(solution, circle);
+        }
+    }
+
+    #[test]
+    fn parallel_lines_with_the_wrong_radius() {
+        let got = circles_tangent_to_two(
+            Line::new(Point::new(0.0, 0.0), Point::new(1.0, 0.0)),
+            Line::new(Point::new(0.0, 5.0), Point::new(1.0, 5.0)),
+            1.0,
+        );
+
+        assert!(got.is_empty());
+    }
+
+    #[test]
+    fn three_points_is_the_circumscribed_circle() {
+        let a = Point::new(1.0, 3.0);
+        let b = Point::new(3.0, 1.0);
+        let c = Point::new(-1.0, 1.0);
+
+        let got = circles_tangent_to_three(a, b, c);
+
+        assert_eq!(got.len(), 1);
+        assert!(got[0].centre.approx_eq(&Point::new(1.0, 1.0)));
+        assert!(got[0].radius.approx_eq(&2.0));
+    }
+
+    #[test]
+    fn apollonius_with_three_separate_circles() {
+        let circles = [
+            Tangency::Circle(Circle::new(Point::new(0.0, 0.0), 1.0)),
+            Tangency::Circle(Circle::new(Point::new(10.0, 0.0), 2.0)),
+            Tangency::Circle(Circle::new(Point::new(3.0, 8.0), 1.5)),
+        ];
+
+        let got = circles_tangent_to_three(circles[0], circles[1], circles[2]);
+
+        assert_eq!(got.len(), 8);
+        for solution in got {
+            for &entity in &circles {
+                assert_touches(solution, entity);
+            }
+        }
+    }
+
+    #[test]
+    fn point_line_and_circle() {
+        let entities = [
+            Tangency::Point(Point::new(0.0, 4.0)),
+            Tangency::Line(Line::new(
+                Point::new(-10.0, 0.0),
+                Point::new(10.0, 0.0),
+            )),
+            Tangency::Circle(Circle::new(Point::new(5.0, 2.0), 1.0)),
+        ];
+
+        let got =
+            circles_tangent_to_three(entities[0], entities[1], entities[2]);
+
+        assert!(!got.is_empty());
+        for solution in got {
+            for &entity in &entities {
+                assert_touches(solution, entity);
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    algorithms::Approximate,
+    primitives::{
+        Arc, Circle, CubicBezier, Ellipse, EllipticalArc, Line, Polyline,
+        QuadraticBezier, Rectangle, Spline,
+    },
+};
+use std::f64::consts::PI;
+
+/// Convert a shape into a [`Polyline`] made entirely of straight segments.
+///
+/// This is the common representation used by anything which can only deal
+/// with straight lines (e.g. renderers, G-code or SVG path exporters, and
+/// numerical approximations of area and length).
+pub trait Tessellate<Space> {
+    /// Get a [`Polyline`] which stays within `tolerance` units of the
+    /// original shape (the chordal tolerance).
+    ///
+    /// Closed shapes produce a closed [`Polyline`], without repeating the
+    /// first point at the end.
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space>;
+}
+
+impl<Space, T: Tessellate<Space> + ?Sized> Tessellate<Space> for &T {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        (*self).tessellate(tolerance)
+    }
+}
+
+/// Turn the points from [`Approximate`] into a [`Polyline`].
+fn from_approximation<Space, A>(
+    shape: &A,
+    tolerance: f64,
+    closed: bool,
+) -> Polyline<Space>
+where
+    A: Approximate<Space> + ?Sized,
+{
+    let mut points: Vec<_> = shape.approximate(tolerance).collect();
+
+    if closed && points.len() > 1 {
+        // the approximation finishes back where it started
+        points.pop();
+    }
+
+    let mut polyline = Polyline::from_points(points);
+    polyline.set_closed(closed);
+    polyline
+}
+
+impl<Space> Tessellate<Space> for Line<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        from_approximation(self, tolerance, false)
+    }
+}
+
+impl<Space> Tessellate<Space> for Arc<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        let is_full_circle = self.sweep_angle().radians.abs() >= 2.0 * PI;
+        from_approximation(self, tolerance, is_full_circle)
+    }
+}
+
+impl<Space> Tessellate<Space> for Circle<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        self.to_arc().tessellate(tolerance)
+    }
+}
+
+impl<Space> Tessellate<Space> for Ellipse<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        from_approximation(self, tolerance, true)
+    }
+}
+
+impl<Space> Tessellate<Space> for EllipticalArc<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        let is_full_ellipse = self.sweep_angle().radians.abs() >= 2.0 * PI;
+        from_approximation(self, tolerance, is_full_ellipse)
+    }
+}
+
+impl<Space> Tessellate<Space> for Polyline<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        from_approximation(self, tolerance, self.is_closed())
+    }
+}
+
+impl<Space> Tessellate<Space> for CubicBezier<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        from_approximation(self, tolerance, false)
+    }
+}
+
+impl<Space> Tessellate<Space> for QuadraticBezier<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        from_approximation(self, tolerance, false)
+    }
+}
+
+impl<Space> Tessellate<Space> for Spline<Space> {
+    fn tessellate(&self, tolerance: f64) -> Polyline<Space> {
+        from_approximation(self, tolerance, false)
+    }
+}
+
+impl<Space> Tessellate<Space> for Rectangle<Space> {
+    fn tessellate(&self, _tolerance: f64) -> Polyline<Space> {
+        self.to_polyline()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn circles_are_closed() {
+        let circle = Circle::new(Point::new(1.0, 2.0), 5.0);
+
+        let got = circle.tessellate(0.01);
+
+        assert!(got.is_closed());
+        let first = got.vertices()[0].point;
+        let last = got.vertices()[got.len() - 1].point;
+        assert!(!first.approx_eq(&last));
+        assert!(got.vertices().iter().all(|v| v.is_straight()));
+    }
+
+    #[test]
+    fn arcs_stay_within_tolerance() {
+        let arc = Arc::from_centre_radius(
+            Point::zero(),
+            10.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+        let tolerance = 0.05;
+
+        let got = arc.tessellate(tolerance);
+
+        assert!(!got.is_closed());
+        assert!(got.vertices()[0].point.approx_eq(&arc.start()));
+        assert!(got.vertices()[got.len() - 1].point.approx_eq(&arc.end()));
+        for segment in got.segments() {
+            if let crate::primitives::Segment::Line(line) = segment {
+                let midpoint = line.start.lerp(line.end, 0.5);
+                let distance = 10.0 - midpoint.to_vector().length();
+                assert!(distance <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn bulged_polylines_are_flattened() {
+        let polyline = Polyline::new(
+            vec![
+                crate::primitives::Vertex::new(Point::new(0.0, 0.0), 1.0),
+                crate::primitives::Vertex::straight(Point::new(2.0, 0.0)),
+            ],
+            false,
+        );
+
+        let got = polyline.tessellate(0.01);
+
+        assert!(got.len() > 2);
+        assert!(got.vertices().iter().all(|v| v.is_straight()));
+        // every vertex lies on the semicircle
+        let centre = Point::new(1.0, 0.0);
+        for vertex in got.vertices() {
+            assert!((vertex.point - centre).length().approx_eq(&1.0));
+        }
+    }
+}
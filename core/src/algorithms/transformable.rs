@@ -0,0 +1,741 @@
+use crate::{
+    algorithms::DEFAULT_TOLERANCE,
+    primitives::{
+        Arc, Circle, CubicBezier, Ellipse, EllipticalArc, Line, Polyline,
+        QuadraticBezier, Ray, Rectangle, Region, Segment, Spline, Vertex,
+        XLine,
+    },
+    Angle,
+};
+use euclid::{Point2D, Transform2D, Vector2D};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// A 2×3 matrix representing an affine transformation (any combination of
+/// translation, rotation, scaling, mirroring, and shearing) within a
+/// coordinate space.
+///
+/// Transforms are composed in the order they are applied, so
+/// `a.then(b)` means "do `a`, then do `b`".
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// # type Vector = euclid::default::Vector2D<f64>;
+/// use arcs_core::{algorithms::AffineTransform, Angle};
+/// use euclid::approxeq::ApproxEq;
+///
+/// let transform = AffineTransform::rotation(Angle::frac_pi_2())
+///     .then(AffineTransform::scale(2.0, 2.0))
+///     .then(AffineTransform::translation(Vector::new(1.0, 0.0)));
+///
+/// let got = transform.transform_point(Point::new(1.0, 0.0));
+///
+/// assert!(got.approx_eq(&Point::new(1.0, 2.0)));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct AffineTransform<S> {
+    matrix: Transform2D<f64, S, S>,
+}
+
+impl<S> AffineTransform<S> {
+    /// Create an [`AffineTransform`] from its matrix elements, where a point
+    /// `(x, y)` is mapped to `(m11 x + m21 y + m31, m12 x + m22 y + m32)`.
+    pub fn row_major(
+        m11: f64,
+        m12: f64,
+        m21: f64,
+        m22: f64,
+        m31: f64,
+        m32: f64,
+    ) -> Self {
+        AffineTransform {
+            matrix: Transform2D::row_major(m11, m12, m21, m22, m31, m32),
+        }
+    }
+
+    /// The transform which leaves everything where it is.
+    pub fn identity() -> Self {
+        AffineTransform {
+            matrix: Transform2D::identity(),
+        }
+    }
+
+    /// Move everything by `displacement`.
+    pub fn translation(displacement: Vector2D<f64, S>) -> Self {
+        AffineTransform {
+            matrix: Transform2D::create_translation(
+                displacement.x,
+                displacement.y,
+            ),
+        }
+    }
+
+    /// Rotate anticlockwise around the origin.
+    pub fn rotation(angle: Angle) -> Self {
+        // built by hand because Transform2D::create_rotation() goes clockwise
+        let (sin, cos) = angle.sin_cos();
+        AffineTransform::row_major(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Rotate anticlockwise around `centre`.
+    pub fn rotation_about(centre: Point2D<f64, S>, angle: Angle) -> Self {
+        AffineTransform::about(centre, AffineTransform::rotation(angle))
+    }
+
+    /// Scale along the x and y axes, relative to the origin.
+    pub fn scale(factor_x: f64, factor_y: f64) -> Self {
+        AffineTransform {
+            matrix: Transform2D::create_scale(factor_x, factor_y),
+        }
+    }
+
+    /// Scale along the x and y axes, relative to `base_point`.
+    pub fn scale_about(
+        base_point: Point2D<f64, S>,
+        factor_x: f64,
+        factor_y: f64,
+    ) -> Self {
+        AffineTransform::about(
+            base_point,
+            AffineTransform::scale(factor_x, factor_y),
+        )
+    }
+
+    /// Reflect everything across the infinite line through `axis`, returning
+    /// [`None`] if `axis` has no length.
+    pub fn mirror(axis: Line<S>) -> Option<Self> {
+        let length = axis.length();
+        if length == 0.0 {
+            return None;
+        }
+
+        // reflecting a vector v across the unit direction d gives
+        // 2(v·d)d - v
+        let d = axis.displacement() / length;
+        let (xx, xy, yy) = (d.x * d.x, d.x * d.y, d.y * d.y);
+        let reflection = AffineTransform::row_major(
+            2.0 * xx - 1.0,
+            2.0 * xy,
+            2.0 * xy,
+            2.0 * yy - 1.0,
+            0.0,
+            0.0,
+        );
+
+        Some(AffineTransform::about(axis.start, reflection))
+    }
+
+    /// Apply `transform` as if `point` were the origin.
+    fn about(point: Point2D<f64, S>, transform: AffineTransform<S>) -> Self {
+        AffineTransform::translation(-point.to_vector())
+            .then(transform)
+            .then(AffineTransform::translation(point.to_vector()))
+    }
+
+    /// Create a transform which applies `self` and then `next`.
+    pub fn then(self, next: AffineTransform<S>) -> Self {
+        AffineTransform {
+            matrix: self.matrix.post_transform(&next.matrix),
+        }
+    }
+
+    /// The transform which undoes this one, if there is one.
+    pub fn inverse(self) -> Option<Self> {
+        self.matrix
+            .inverse()
+            .map(|matrix| AffineTransform { matrix })
+    }
+
+    /// Transform a point.
+    pub fn transform_point(self, point: Point2D<f64, S>) -> Point2D<f64, S> {
+        self.matrix.transform_point(point)
+    }
+
+    /// Transform a vector (i.e. ignoring any translation).
+    pub fn transform_vector(
+        self,
+        vector: Vector2D<f64, S>,
+    ) -> Vector2D<f64, S> {
+        self.matrix.transform_vector(vector)
+    }
+
+    /// The direction an `angle` (measured anticlockwise from the x-axis)
+    /// points in after being transformed.
+    pub fn transform_angle(self, angle: Angle) -> Angle {
+        let (sin, cos) = angle.sin_cos();
+        let direction = self.transform_vector(Vector2D::new(cos, sin));
+
+        Angle::radians(direction.y.atan2(direction.x))
+    }
+
+    /// The determinant of the matrix, telling you how much areas are scaled
+    /// by.
+    pub fn determinant(self) -> f64 { self.matrix.determinant() }
+
+    /// Does this transform mirror things (i.e. turn anticlockwise into
+    /// clockwise)?
+    pub fn is_reflection(self) -> bool { self.determinant() < 0.0 }
+
+    /// If this transform is a *similarity* (only moving, rotating, mirroring,
+    /// and scaling by the same amount in every direction), how much does it
+    /// scale lengths by?
+    pub fn uniform_scale(self) -> Option<f64> {
+        let x_axis = self.transform_vector(Vector2D::new(1.0, 0.0));
+        let y_axis = self.transform_vector(Vector2D::new(0.0, 1.0));
+        let (x_length, y_length) = (x_axis.length(), y_axis.length());
+        let tolerance = DEFAULT_TOLERANCE * x_length.max(y_length);
+
+        if x_length == 0.0
+            || (x_length - y_length).abs() > tolerance
+            || x_axis.dot(y_axis).abs() > tolerance * x_length
+        {
+            None
+        } else {
+            Some(x_length)
+        }
+    }
+
+    /// Check this transform is a similarity, returning the scale factor.
+    pub fn similarity(self) -> Result<f64, TransformError> {
+        if self.determinant() == 0.0 {
+            Err(TransformError::Degenerate)
+        } else {
+            self.uniform_scale().ok_or(TransformError::NonUniform)
+        }
+    }
+
+    /// Get the underlying [`Transform2D`].
+    pub fn to_transform2d(self) -> Transform2D<f64, S, S> { self.matrix }
+}
+
+impl<S> Default for AffineTransform<S> {
+    fn default() -> Self { AffineTransform::identity() }
+}
+
+impl<S> From<Transform2D<f64, S, S>> for AffineTransform<S> {
+    fn from(matrix: Transform2D<f64, S, S>) -> Self {
+        AffineTransform { matrix }
+    }
+}
+
+impl<S> Copy for AffineTransform<S> {}
+
+impl<S> Clone for AffineTransform<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Something which can have an [`AffineTransform`] applied to it.
+///
+/// Not every shape can be represented after every transform (e.g. squashing
+/// an [`Arc`] gives you an [`EllipticalArc`]), in which case a
+/// [`TransformError`] is returned and the shape is left unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{
+///     algorithms::{AffineTransform, TransformError, Transformable},
+///     primitives::{Arc, Line},
+///     Angle,
+/// };
+///
+/// let squash = AffineTransform::scale(1.0, 0.5);
+///
+/// let line = Line::new(Point::new(0.0, 0.0), Point::new(4.0, 4.0));
+/// let got = line.transformed_by(squash).unwrap();
+/// assert_eq!(got.end, Point::new(4.0, 2.0));
+///
+/// let arc =
+///     Arc::from_centre_radius(Point::zero(), 1.0, Angle::zero(), Angle::pi());
+/// assert_eq!(arc.transformed_by(squash), Err(TransformError::NonUniform));
+/// ```
+pub trait Transformable<Space> {
+    /// Apply a transform in-place.
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError>;
+
+    /// A convenience method for getting a transformed copy of this object.
+    fn transformed_by(
+        &self,
+        transform: AffineTransform<Space>,
+    ) -> Result<Self, TransformError>
+    where
+        Self: Sized + Clone,
+    {
+        let mut clone = self.clone();
+        clone.transform_by(transform)?;
+
+        Ok(clone)
+    }
+}
+
+impl<Space, T: Transformable<Space> + ?Sized> Transformable<Space> for &mut T {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        (**self).transform_by(transform)
+    }
+}
+
+/// The reasons a [`Transformable`] object may not be able to be transformed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TransformError {
+    /// The object can only be moved, rotated, mirrored, and scaled by the
+    /// same amount in every direction.
+    NonUniform,
+    /// The object can't be mirrored.
+    Reflection,
+    /// The transform flattens everything onto a line or a single point.
+    Degenerate,
+}
+
+impl Display for TransformError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformError::NonUniform => {
+                write!(f, "The object can only be scaled uniformly")
+            },
+            TransformError::Reflection => {
+                write!(f, "The object can't be mirrored")
+            },
+            TransformError::Degenerate => write!(
+                f,
+                "The transform flattens everything onto a line or point"
+            ),
+        }
+    }
+}
+
+impl Error for TransformError {}
+
+impl<Space> Transformable<Space> for Point2D<f64, Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        *self = transform.transform_point(*self);
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Vector2D<f64, Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        *self = transform.transform_vector(*self);
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Line<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        self.start.transform_by(transform)?;
+        self.end.transform_by(transform)
+    }
+}
+
+impl<Space> Transformable<Space> for XLine<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        self.base_point.transform_by(transform)?;
+        self.direction.transform_by(transform)
+    }
+}
+
+impl<Space> Transformable<Space> for Ray<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        self.start.transform_by(transform)?;
+        self.direction.transform_by(transform)
+    }
+}
+
+impl<Space> Transformable<Space> for CubicBezier<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        // Bézier curves are affine invariant, so we only need to transform the
+        // control points
+        self.start.transform_by(transform)?;
+        self.first_control_point.transform_by(transform)?;
+        self.second_control_point.transform_by(transform)?;
+        self.end.transform_by(transform)
+    }
+}
+
+impl<Space> Transformable<Space> for QuadraticBezier<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        self.start.transform_by(transform)?;
+        self.control_point.transform_by(transform)?;
+        self.end.transform_by(transform)
+    }
+}
+
+impl<Space> Transformable<Space> for Spline<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        // like Bézier curves, NURBS are affine invariant
+        for point in self.control_points_mut() {
+            point.transform_by(transform)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Circle<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+
+        *self = Circle::new(
+            transform.transform_point(self.centre),
+            self.radius * scale,
+        );
+
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Arc<Space> {
+    /// [`Arc`]s can only be moved, rotated, mirrored, and scaled uniformly.
+    /// Mirroring an [`Arc`] reverses its direction.
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        let scale = transform.similarity()?;
+        let sweep_angle = if transform.is_reflection() {
+            -self.sweep_angle()
+        } else {
+            self.sweep_angle()
+        };
+
+        *self = Arc::from_centre_radius(
+            transform.transform_point(self.centre()),
+            self.radius() * scale,
+            transform.transform_angle(self.start_angle()),
+            sweep_angle,
+        );
+
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Ellipse<Space> {
+    /// An affine transform always turns an [`Ellipse`] into another
+    /// [`Ellipse`], although the major and minor axes may change.
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        if transform.determinant() == 0.0 {
+            return Err(TransformError::Degenerate);
+        }
+
+        // the transformed axes are conjugate diameters of the new ellipse,
+        // so rotate them (in parameter space) until they're perpendicular
+        let p = transform.transform_vector(self.major_axis());
+        let q = transform.transform_vector(self.minor_axis());
+        let offset =
+            0.5 * (2.0 * p.dot(q)).atan2(p.square_length() - q.square_length());
+        let (sin, cos) = offset.sin_cos();
+        let major = p * cos + q * sin;
+        let minor = q * cos - p * sin;
+
+        *self = Ellipse::new(
+            transform.transform_point(self.centre()),
+            major.length(),
+            minor.length(),
+            Angle::radians(major.y.atan2(major.x)),
+        );
+
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for EllipticalArc<Space> {
+    /// Mirroring an [`EllipticalArc`] reverses its direction.
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        let ellipse = self.ellipse().transformed_by(transform)?;
+        let start = transform.transform_point(self.start());
+        let sweep_angle = if transform.is_reflection() {
+            -self.sweep_angle()
+        } else {
+            self.sweep_angle()
+        };
+
+        *self = EllipticalArc::new(
+            ellipse,
+            ellipse.parametric_angle_of(start),
+            sweep_angle,
+        );
+
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Segment<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        match self {
+            Segment::Line(line) => line.transform_by(transform),
+            Segment::Arc(arc) => arc.transform_by(transform),
+        }
+    }
+}
+
+/// Make sure the arcs in a [`Polyline`] will still be arcs afterwards.
+fn check_bulges<Space>(
+    polyline: &Polyline<Space>,
+    transform: AffineTransform<Space>,
+) -> Result<(), TransformError> {
+    if polyline.vertices().iter().all(|v| v.is_straight()) {
+        Ok(())
+    } else {
+        transform.similarity().map(|_| ())
+    }
+}
+
+impl<Space> Transformable<Space> for Polyline<Space> {
+    /// A [`Polyline`] with bulges can only be transformed in ways that keep
+    /// its arcs circular, while one made of straight lines can be
+    /// transformed any way you like.
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        check_bulges(self, transform)?;
+
+        let flip = if transform.is_reflection() { -1.0 } else { 1.0 };
+        let vertices = self
+            .vertices()
+            .iter()
+            .map(|v| {
+                Vertex::new(transform.transform_point(v.point), v.bulge * flip)
+            })
+            .collect();
+
+        *self = Polyline::new(vertices, self.is_closed());
+
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Region<Space> {
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        for boundary in self.loops() {
+            check_bulges(boundary, transform)?;
+        }
+
+        for boundary in self.loops_mut() {
+            boundary.transform_by(transform)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Space> Transformable<Space> for Rectangle<Space> {
+    /// A [`Rectangle`] can be transformed as long as its corners stay
+    /// square.
+    fn transform_by(
+        &mut self,
+        transform: AffineTransform<Space>,
+    ) -> Result<(), TransformError> {
+        if transform.determinant() == 0.0 {
+            return Err(TransformError::Degenerate);
+        }
+
+        let (sin, cos) = self.rotation.sin_cos();
+        let x_axis = transform.transform_vector(Vector2D::new(cos, sin));
+        let y_axis = transform.transform_vector(Vector2D::new(-sin, cos));
+        let tolerance = DEFAULT_TOLERANCE * x_axis.length() * y_axis.length();
+
+        if x_axis.dot(y_axis).abs() > tolerance {
+            return Err(TransformError::NonUniform);
+        }
+
+        *self = Rectangle::new(
+            transform.transform_point(self.centre),
+            self.width * x_axis.length(),
+            self.height * y_axis.length(),
+            Angle::radians(x_axis.y.atan2(x_axis.x)),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+    type Vector = euclid::default::Vector2D<f64>;
+
+    #[test]
+    fn transforms_are_applied_in_order() {
+        let rotate = AffineTransform::rotation(Angle::frac_pi_2());
+        let shift = AffineTransform::translation(Vector::new(1.0, 0.0));
+        let point = Point::new(1.0, 0.0);
+
+        let rotate_then_shift = rotate.then(shift).transform_point(point);
+        let shift_then_rotate = shift.then(rotate).transform_point(point);
+
+        assert!(rotate_then_shift.approx_eq(&Point::new(1.0, 1.0)));
+        assert!(shift_then_rotate.approx_eq(&Point::new(0.0, 2.0)));
+    }
+
+    #[test]
+    fn inverse_undoes_the_transform() {
+        let transform =
+            AffineTransform::scale_about(Point::new(1.0, 2.0), 3.0, -2.0)
+                .then(AffineTransform::rotation(Angle::degrees(30.0)));
+        let point = Point::new(-4.0, 7.0);
+
+        let there = transform.transform_point(point);
+        let back = transform.inverse().unwrap().transform_point(there);
+
+        assert!(back.approx_eq(&point));
+        assert!(AffineTransform::<euclid::UnknownUnit>::scale(0.0, 1.0)
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn similarities_have_a_uniform_scale() {
+        let similar = AffineTransform::<euclid::UnknownUnit>::rotation(
+            Angle::degrees(30.0),
+        )
+        .then(AffineTransform::scale(-2.0, 2.0));
+        let squashed = AffineTransform::<euclid::UnknownUnit>::scale(2.0, 1.0);
+
+        assert!(similar.uniform_scale().unwrap().approx_eq(&2.0));
+        assert!(similar.is_reflection());
+        assert_eq!(squashed.uniform_scale(), None);
+    }
+
+    #[test]
+    fn mirrored_arcs_change_direction() {
+        let arc = Arc::from_centre_radius(
+            Point::new(1.0, 0.0),
+            1.0,
+            Angle::zero(),
+            Angle::frac_pi_2(),
+        );
+        let mirror = AffineTransform::scale(-1.0, 1.0);
+
+        let got = arc.transformed_by(mirror).unwrap();
+
+        assert!(got.centre().approx_eq(&Point::new(-1.0, 0.0)));
+        assert!(got.start().approx_eq(&Point::new(-2.0, 0.0)));
+        assert!(got.end().approx_eq(&Point::new(-1.0, 1.0)));
+        assert!(got.is_clockwise());
+    }
+
+    #[test]
+    fn squashed_ellipses_are_still_ellipses() {
+        let ellipse = Ellipse::new(Point::zero(), 2.0, 1.0, Angle::zero());
+        let transform = AffineTransform::rotation(Angle::frac_pi_4())
+            .then(AffineTransform::scale(1.0, 0.5));
+
+        let got = ellipse.transformed_by(transform).unwrap();
+
+        // every point on the original should land on the new ellipse
+        for i in 0..8 {
+            let angle = Angle::degrees(45.0 * i as f64);
+            let expected = transform.transform_point(ellipse.point_at(angle));
+            let on_new = got.point_at(got.parametric_angle_of(expected));
+            assert!(on_new.approx_eq(&expected), "{:?}", angle);
+        }
+    }
+
+    #[test]
+    fn elliptical_arcs_keep_their_end_points() {
+        let ellipse = Ellipse::new(Point::zero(), 3.0, 1.0, Angle::zero());
+        let arc =
+            EllipticalArc::new(ellipse, Angle::frac_pi_4(), Angle::frac_pi_2());
+        let transform =
+            AffineTransform::row_major(1.0, 0.5, 0.2, -1.0, 3.0, 4.0);
+
+        let got = arc.transformed_by(transform).unwrap();
+
+        assert!(got
+            .start()
+            .approx_eq(&transform.transform_point(arc.start())));
+        assert!(got.end().approx_eq(&transform.transform_point(arc.end())));
+        assert!(got.is_clockwise());
+    }
+
+    #[test]
+    fn bulged_polylines_need_a_similarity() {
+        let polyline = Polyline::new(
+            vec![
+                Vertex::new(Point::new(0.0, 0.0), 1.0),
+                Vertex::straight(Point::new(2.0, 0.0)),
+            ],
+            false,
+        );
+        let squash = AffineTransform::scale(1.0, 0.5);
+        let mirror = AffineTransform::scale(1.0, -1.0);
+
+        assert_eq!(
+            polyline.transformed_by(squash),
+            Err(TransformError::NonUniform)
+        );
+        let mirrored = polyline.transformed_by(mirror).unwrap();
+        assert_eq!(mirrored.vertices()[0].bulge, -1.0);
+    }
+
+    #[test]
+    fn rectangles_stay_square() {
+        let rectangle =
+            Rectangle::new(Point::new(1.0, 1.0), 4.0, 2.0, Angle::zero());
+
+        let stretched = rectangle
+            .transformed_by(AffineTransform::scale(2.0, 1.0))
+            .unwrap();
+        let sheared = rectangle.transformed_by(AffineTransform::row_major(
+            1.0, 0.0, 1.0, 1.0, 0.0, 0.0,
+        ));
+
+        assert_eq!(stretched.centre, Point::new(2.0, 1.0));
+        assert_eq!(stretched.width, 8.0);
+        assert_eq!(stretched.height, 2.0);
+        assert_eq!(sheared, Err(TransformError::NonUniform));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{algorithms::AffineTransformable, primitives::Arc, BoundingBox};
+use crate::{
+    algorithms::AffineTransformable,
+    primitives::{Arc, Ellipse, EllipticalArc, Polyline, Rectangle, Region},
+    BoundingBox,
+};
 use euclid::{Transform2D, Vector2D};
 
 /// Something which can be moved around "rigidly" in *Drawing Space*.
@@ -38,6 +42,50 @@ impl<Space> Translate<Space> for Arc<Space> {
     }
 }
 
+impl<Space> Translate<Space> for Ellipse<Space> {
+    fn translate(&mut self, displacement: Vector2D<f64, Space>) {
+        *self = Ellipse::new(
+            self.centre().translated(displacement),
+            self.semi_major(),
+            self.semi_minor(),
+            self.rotation(),
+        );
+    }
+}
+
+impl<Space> Translate<Space> for EllipticalArc<Space> {
+    fn translate(&mut self, displacement: Vector2D<f64, Space>) {
+        *self = EllipticalArc::new(
+            self.ellipse().translated(displacement),
+            self.start_angle(),
+            self.sweep_angle(),
+        );
+    }
+}
+
+impl<Space> Translate<Space> for Polyline<Space> {
+    fn translate(&mut self, displacement: Vector2D<f64, Space>) {
+        for i in 0..self.len() {
+            let point = self.vertices()[i].point;
+            self.move_vertex(i, point.translated(displacement));
+        }
+    }
+}
+
+impl<Space> Translate<Space> for Rectangle<Space> {
+    fn translate(&mut self, displacement: Vector2D<f64, Space>) {
+        self.centre += displacement;
+    }
+}
+
+impl<Space> Translate<Space> for Region<Space> {
+    fn translate(&mut self, displacement: Vector2D<f64, Space>) {
+        for boundary in self.loops_mut() {
+            boundary.translate(displacement);
+        }
+    }
+}
+
 impl<Space> Translate<Space> for BoundingBox<Space> {
     fn translate(&mut self, displacement: Vector2D<f64, Space>) {
         *self = BoundingBox::new_unchecked(
@@ -0,0 +1,235 @@
+use crate::{
+    algorithms::split::{Parameterised, PARAMETER_TOLERANCE},
+    primitives::{Arc, Line, Polyline},
+};
+use euclid::Point2D;
+
+/// Something which can be cut back to a set of cutting edges.
+pub trait Trim<Space>: Sized {
+    /// Remove the section of this object which was picked.
+    ///
+    /// The `cutting_points` are where the cutting edges cross this object
+    /// (e.g. from [`crate::algorithms::Intersect`]), and the section removed
+    /// is the part between the two cutting points either side of `pick`.
+    ///
+    /// This returns the pieces which are left over, or [`None`] if there
+    /// aren't enough cutting points to trim anything (e.g. an open curve
+    /// with no cuts, or a closed curve with less than two).
+    fn trim(
+        &self,
+        cutting_points: &[Point2D<f64, Space>],
+        pick: Point2D<f64, Space>,
+    ) -> Option<Vec<Self>>;
+}
+
+impl<S> Trim<S> for Line<S> {
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{algorithms::Trim, primitives::Line};
+    ///
+    /// let line = Line::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+    /// let cuts = [Point::new(3.0, 0.0), Point::new(6.0, 0.0)];
+    ///
+    /// // trim out the middle section
+    /// let got = line.trim(&cuts, Point::new(5.0, 1.0)).unwrap();
+    ///
+    /// assert_eq!(
+    ///     got,
+    ///     vec![
+    ///         Line::new(Point::new(0.0, 0.0), Point::new(3.0, 0.0)),
+    ///         Line::new(Point::new(6.0, 0.0), Point::new(10.0, 0.0)),
+    ///     ]
+    /// );
+    /// ```
+    fn trim(
+        &self,
+        cutting_points: &[Point2D<f64, S>],
+        pick: Point2D<f64, S>,
+    ) -> Option<Vec<Self>> {
+        trim_curve(self, cutting_points, pick)
+    }
+}
+
+impl<S> Trim<S> for Arc<S> {
+    fn trim(
+        &self,
+        cutting_points: &[Point2D<f64, S>],
+        pick: Point2D<f64, S>,
+    ) -> Option<Vec<Self>> {
+        trim_curve(self, cutting_points, pick)
+    }
+}
+
+impl<S> Trim<S> for Polyline<S> {
+    fn trim(
+        &self,
+        cutting_points: &[Point2D<f64, S>],
+        pick: Point2D<f64, S>,
+    ) -> Option<Vec<Self>> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        trim_curve(self, cutting_points, pick)
+    }
+}
+
+fn trim_curve<S, C>(
+    curve: &C,
+    cutting_points: &[Point2D<f64, S>],
+    pick: Point2D<f64, S>,
+) -> Option<Vec<C>>
+where
+    C: Parameterised<S>,
+{
+    let (start, end) = curve.parameter_range();
+    let closed = curve.is_closed_curve();
+
+    let mut cuts: Vec<f64> = cutting_points
+        .iter()
+        .map(|&point| curve.parameter_of(point))
+        // cutting exactly at the ends of an open curve doesn't do anything
+        .filter(|&t| {
+            closed
+                || (t > start + PARAMETER_TOLERANCE
+                    && t < end - PARAMETER_TOLERANCE)
+        })
+        // the start and end of a closed curve are the same place
+        .map(|t| if closed && t >= end - PARAMETER_TOLERANCE { start } else { t })
+        .collect();
+    cuts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    cuts.dedup_by(|a, b| (*a - *b).abs() <= PARAMETER_TOLERANCE);
+
+    let picked = curve.parameter_of(pick);
+    let before = cuts.iter().copied().rev().find(|&t| t <= picked);
+    let after = cuts.iter().copied().find(|&t| t > picked);
+
+    if closed {
+        if cuts.len() < 2 {
+            return None;
+        }
+
+        // wrap around to find the cuts either side of the pick point
+        let before = before.unwrap_or(cuts[cuts.len() - 1]);
+        let after = after.unwrap_or(cuts[0]);
+
+        return Some(vec![curve.portion(after, before)]);
+    }
+
+    if cuts.is_empty() {
+        return None;
+    }
+
+    let mut pieces = Vec::new();
+    if let Some(before) = before {
+        pieces.push(curve.portion(start, before));
+    }
+    if let Some(after) = after {
+        pieces.push(curve.portion(after, end));
+    }
+
+    Some(pieces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn trim_the_end_off_a_line() {
+        let line = Line::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+        let cuts = [Point::new(4.0, 0.0), Point::new(10.0, 0.0)];
+
+        let got = line.trim(&cuts, Point::new(8.0, 0.0)).unwrap();
+
+        assert_eq!(
+            got,
+            vec![Line::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0))]
+        );
+        assert!(line.trim(&cuts[1..], Point::new(8.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn trim_a_circle() {
+        let circle = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        );
+        let cuts = [Point::new(0.0, 1.0), Point::new(0.0, -1.0)];
+
+        // remove the right half
+        let got = circle.trim(&cuts, Point::new(2.0, 0.0)).unwrap();
+
+        assert_eq!(got.len(), 1);
+        assert!(got[0].start().approx_eq(&Point::new(0.0, 1.0)));
+        assert!(got[0].end().approx_eq(&Point::new(0.0, -1.0)));
+        assert!(got[0]
+            .sweep_angle()
+            .radians
+            .approx_eq(&std::f64::consts::PI));
+        assert!(circle.trim(&cuts[..1], Point::new(2.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn trim_across_a_polyline_corner() {
+        let polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ]);
+        let cuts = [Point::new(5.0, 0.0), Point::new(10.0, 5.0)];
+
+        let got = polyline.trim(&cuts, Point::new(10.0, 0.0)).unwrap();
+
+        let points = |p: &Polyline<_>| -> Vec<Point> {
+            p.vertices().iter().map(|v| v.point).collect()
+        };
+        assert_eq!(got.len(), 2);
+        assert_eq!(
+            points(&got[0]),
+            vec![Point::new(0.0, 0.0), Point::new(5.0, 0.0)]
+        );
+        assert_eq!(
+            points(&got[1]),
+            vec![Point::new(10.0, 5.0), Point::new(10.0, 10.0)]
+        );
+    }
+
+    #[test]
+    fn trim_a_closed_polyline() {
+        let mut square = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ]);
+        square.set_closed(true);
+        let cuts = [Point::new(1.0, 0.0), Point::new(2.0, 1.0)];
+
+        // cut off the bottom-right corner
+        let got = square.trim(&cuts, Point::new(2.0, 0.0)).unwrap();
+
+        assert_eq!(got.len(), 1);
+        let vertices: Vec<Point> =
+            got[0].vertices().iter().map(|v| v.point).collect();
+        assert_eq!(
+            vertices,
+            vec![
+                Point::new(2.0, 1.0),
+                Point::new(2.0, 2.0),
+                Point::new(0.0, 2.0),
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+            ]
+        );
+        assert!(!got[0].is_closed());
+    }
+}
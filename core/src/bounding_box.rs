@@ -95,7 +95,10 @@ impl<S> BoundingBox<S> {
         left: BoundingBox<S>,
         right: BoundingBox<S>,
     ) -> BoundingBox<S> {
-        BoundingBox::new(left.bottom_left, right.top_right)
+        BoundingBox::new_unchecked(
+            left.bottom_left.min(right.bottom_left),
+            left.top_right.max(right.top_right),
+        )
     }
 
     /// Create a [`BoundingBox`] which fully encompasses a set of [`Bounded`]
@@ -152,8 +155,10 @@ impl<S> BoundingBox<S> {
 
     /// Do these two [`BoundingBox`]es overlap?
     pub fn intersects_with(&self, other: BoundingBox<S>) -> bool {
-        // FIXME: Actually implement this
-        self.fully_contains(other)
+        self.min_x() <= other.max_x()
+            && other.min_x() <= self.max_x()
+            && self.min_y() <= other.max_y()
+            && other.min_y() <= self.max_y()
     }
 }
 
@@ -195,4 +200,31 @@ mod tests {
 
         assert_eq!(got, original);
     }
+
+    #[test]
+    fn partially_overlapping_boxes_intersect() {
+        let first = BoundingBox::new(Point2D::zero(), Point2D::new(10.0, 10.0));
+        let second =
+            BoundingBox::new(Point2D::new(5.0, -5.0), Point2D::new(15.0, 5.0));
+        let third =
+            BoundingBox::new(Point2D::new(11.0, 0.0), Point2D::new(15.0, 5.0));
+
+        assert!(first.intersects_with(second));
+        assert!(second.intersects_with(first));
+        assert!(!first.intersects_with(third));
+    }
+
+    #[test]
+    fn merging_keeps_both_boxes() {
+        let first = BoundingBox::new(Point2D::zero(), Point2D::new(10.0, 10.0));
+        let second =
+            BoundingBox::new(Point2D::new(5.0, -5.0), Point2D::new(6.0, 6.0));
+
+        let got = BoundingBox::merge(first, second);
+
+        assert_eq!(
+            got,
+            BoundingBox::new(Point2D::new(0.0, -5.0), Point2D::new(10.0, 10.0))
+        );
+    }
 }
@@ -14,9 +14,11 @@ pub mod algorithms;
 mod bounding_box;
 mod orientation;
 pub mod primitives;
+mod tolerance;
 
 pub use bounding_box::BoundingBox;
 pub use orientation::{centre_of_three_points, Orientation};
+pub use tolerance::{ApproxEqWithin, Tolerance};
 
 /// A strongly-typed angle, useful for dealing with the pesky modular arithmetic
 /// normally associated with circles and angles.
@@ -1,4 +1,5 @@
-use euclid::{Point2D, Vector2D};
+use crate::primitives::Circle;
+use euclid::Point2D;
 
 /// How something may be oriented.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -53,29 +54,7 @@ pub fn centre_of_three_points<S>(
     second: Point2D<f64, S>,
     third: Point2D<f64, S>,
 ) -> Option<Point2D<f64, S>> {
-    // it's easier to do the math using vectors, but for semantic correctness we
-    // accept points
-    let first = first.to_vector();
-    let second = second.to_vector();
-    let third = third.to_vector();
-
-    let temp = Vector2D::dot(second, second);
-    let bc = (Vector2D::dot(first, first) - temp) / 2.0;
-    let cd = (temp - third.x * third.x - third.y * third.y) / 2.0;
-    let determinant = (first.x - second.x) * (second.y - third.y)
-        - (second.x - third.x) * (first.y - second.y);
-
-    if determinant == 0.0 {
-        // the points are collinear
-        return None;
-    }
-
-    let x =
-        (bc * (second.y - third.y) - cd * (first.y - second.y)) / determinant;
-    let y =
-        ((first.x - second.x) * cd - (second.x - third.x) * bc) / determinant;
-
-    Some(Point2D::new(x, y))
+    Circle::circumscribing(first, second, third).map(|circle| circle.centre)
 }
 
 #[cfg(test)]
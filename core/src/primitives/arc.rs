@@ -1,6 +1,6 @@
 #![allow(missing_docs)]
 
-use crate::{Angle, Orientation};
+use crate::{primitives::Circle, Angle, Orientation};
 use euclid::{Point2D, Vector2D};
 use std::f64::consts::PI;
 
@@ -54,7 +54,9 @@ impl<S> Arc<S> {
     /// assert!(got.is_anticlockwise());
     /// ```
     ///
-    /// This will fail if the three points are [`Orientation::Collinear`].
+    /// This will fail if the three points are [`Orientation::Collinear`], or so
+    /// close to collinear that the [`Arc`]'s radius would be unreasonably
+    /// large (see [`Circle::circumscribing()`]).
     ///
     /// ```rust
     /// # type Point = euclid::default::Point2D<f64>;
@@ -73,9 +75,9 @@ impl<S> Arc<S> {
         middle: Point2D<f64, S>,
         end: Point2D<f64, S>,
     ) -> Option<Self> {
-        let centre = crate::centre_of_three_points(start, middle, end)?;
-        let radius = (start - centre).length();
-        let start_angle = (start - centre).angle_from_x_axis();
+        let Circle { centre, radius } =
+            Circle::circumscribing(start, middle, end)?;
+        let start_angle = angle_of(start - centre);
         let sweep_angle = sweep_angle_from_3_points(start, middle, end, centre);
 
         Some(Arc::from_centre_radius(
@@ -114,17 +116,24 @@ impl<S> Arc<S> {
         self.centre() + Vector2D::new(r * cos, r * sin)
     }
 
+    /// Does this [`Arc`] pass through a particular angle?
+    ///
+    /// Unlike a simple range check, this accounts for angles which wrap
+    /// around (e.g. `-90°` and `270°` are treated the same).
     pub fn contains_angle(self, angle: Angle) -> bool {
-        let start_angle = self.start_angle();
-        let end_angle = self.end_angle();
+        let sweep = self.sweep_angle().radians;
+
+        if sweep.abs() >= Angle::two_pi().radians {
+            return true;
+        }
 
-        let (min, max) = if start_angle < end_angle {
-            (start_angle, end_angle)
+        let offset = if sweep >= 0.0 {
+            angle - self.start_angle()
         } else {
-            (end_angle, start_angle)
+            self.start_angle() - angle
         };
 
-        (min <= angle) && (angle <= max)
+        offset.positive().radians <= sweep.abs()
     }
 
     pub fn is_minor_arc(&self) -> bool {
@@ -140,28 +149,26 @@ fn sweep_angle_from_3_points<S>(
     end: Point2D<f64, S>,
     centre: Point2D<f64, S>,
 ) -> Angle {
-    debug_assert!(
-        Orientation::of(start, middle, end) != Orientation::Collinear
-    );
-
-    let start_ray = start - centre;
-    let end_ray = end - centre;
-    let orientation = Orientation::of(start, middle, end);
-    let angular_difference =
-        end_ray.angle_from_x_axis() - start_ray.angle_from_x_axis();
-
-    if angular_difference.radians > 0.0 && orientation == Orientation::Clockwise
-    {
-        angular_difference - Angle::two_pi()
-    } else if angular_difference.radians < 0.0
-        && orientation == Orientation::Anticlockwise
-    {
-        Angle::two_pi() - angular_difference
-    } else {
-        angular_difference
+    let difference = (angle_of(end - centre) - angle_of(start - centre))
+        .radians
+        .rem_euclid(2.0 * PI);
+
+    // the orientation of the three points tells us which way the arc goes
+    // around, so we just need to pick the matching way of getting from the
+    // start angle to the end angle
+    match Orientation::of(start, middle, end) {
+        Orientation::Clockwise if difference > 0.0 => {
+            Angle::radians(difference - 2.0 * PI)
+        },
+        _ => Angle::radians(difference),
     }
 }
 
+/// The exact angle of a vector, measured anticlockwise from the x-axis.
+fn angle_of<S>(vector: Vector2D<f64, S>) -> Angle {
+    Angle::radians(vector.y.atan2(vector.x))
+}
+
 impl<S> Copy for Arc<S> {}
 
 impl<S> Clone for Arc<S> {
@@ -205,6 +212,12 @@ mod tests {
     test_contains_angle!(inside_reverse_arc,
         Arc::from_centre_radius(Point::zero(), 1.0, Angle::frac_pi_4(), -Angle::frac_pi_4()),
         45.0 => true);
+    test_contains_angle!(negative_angle_on_full_circle,
+        Arc::from_centre_radius(Point::zero(), 1.0, Angle::zero(), Angle::two_pi()),
+        -120.0 => true);
+    test_contains_angle!(arc_which_wraps_past_zero,
+        Arc::from_centre_radius(Point::zero(), 1.0, Angle::degrees(300.0), Angle::frac_pi_2()),
+        10.0 => true);
 
     #[test]
     fn arc_from_three_points() {
@@ -240,6 +253,32 @@ mod tests {
         assert_eq!(got, expected);
     }
 
+    #[test]
+    fn major_arc_from_three_points() {
+        // start on the right, pass through the bottom and finish at the top
+        let a = Point::new(10.0, 0.0);
+        let b = Point::new(0.0, -10.0);
+        let c = Point::new(0.0, 10.0);
+
+        let got = Arc::from_three_points(a, b, c).unwrap();
+
+        assert!(got.is_clockwise());
+        assert!(got.is_major_arc());
+        assert!(got.sweep_angle().radians.approx_eq(&(-1.5 * PI)));
+        assert!(got.end().approx_eq(&c));
+    }
+
+    #[test]
+    fn nearly_collinear_points_dont_make_an_arc() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(5.0, 1e-13);
+        let c = Point::new(10.0, 0.0);
+
+        let got = Arc::from_three_points(a, b, c);
+
+        assert!(got.is_none());
+    }
+
     #[test]
     fn basic_properties() {
         let centre = Point::new(5.0, 100.0);
@@ -0,0 +1,143 @@
+use crate::{primitives::Arc, Angle};
+use euclid::{Point2D, Vector2D};
+
+/// Points are treated as collinear when the sine of the angle they make is
+/// smaller than this.
+const COLLINEAR_TOLERANCE: f64 = 1e-10;
+
+/// A full circle.
+#[derive(Debug, PartialEq)]
+pub struct Circle<S> {
+    /// The [`Circle`]'s centre point.
+    pub centre: Point2D<f64, S>,
+    /// The [`Circle`]'s radius.
+    pub radius: f64,
+}
+
+impl<S> Circle<S> {
+    /// Create a new [`Circle`].
+    pub const fn new(centre: Point2D<f64, S>, radius: f64) -> Self {
+        Circle { centre, radius }
+    }
+
+    /// Find the [`Circle`] which passes through all three points.
+    ///
+    /// This returns [`None`] when the points are collinear (or so close to
+    /// collinear that the circle would be unreasonably large), or when two
+    /// of the points are the same.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::Circle;
+    ///
+    /// let got = Circle::circumscribing(
+    ///     Point::new(1.0, 3.0),
+    ///     Point::new(3.0, 1.0),
+    ///     Point::new(-1.0, 1.0),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(got, Circle::new(Point::new(1.0, 1.0), 2.0));
+    ///
+    /// // points which are *almost* in a straight line
+    /// let nearly_collinear = Circle::circumscribing(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 1e-12),
+    ///     Point::new(2.0, 0.0),
+    /// );
+    /// assert!(nearly_collinear.is_none());
+    /// ```
+    pub fn circumscribing(
+        first: Point2D<f64, S>,
+        second: Point2D<f64, S>,
+        third: Point2D<f64, S>,
+    ) -> Option<Self> {
+        // working relative to the first point avoids losing precision when
+        // the points are a long way from the origin
+        let b = second - first;
+        let c = third - first;
+        let cross = b.cross(c);
+        let scale = b.length() * c.length();
+
+        if scale == 0.0 || cross.abs() <= COLLINEAR_TOLERANCE * scale {
+            return None;
+        }
+
+        let (b_squared, c_squared) = (b.square_length(), c.square_length());
+        let offset = Vector2D::new(
+            c.y * b_squared - b.y * c_squared,
+            b.x * c_squared - c.x * b_squared,
+        ) / (2.0 * cross);
+
+        Some(Circle::new(first + offset, offset.length()))
+    }
+
+    /// Is a point inside the [`Circle`] (or on its edge)?
+    pub fn contains(self, point: Point2D<f64, S>) -> bool {
+        (point - self.centre).length() <= self.radius
+    }
+
+    /// Get the point on the [`Circle`] at a particular angle.
+    pub fn point_at(self, angle: Angle) -> Point2D<f64, S> {
+        let (sin, cos) = angle.sin_cos();
+        self.centre + Vector2D::new(cos, sin) * self.radius
+    }
+
+    /// Convert the [`Circle`] to an [`Arc`] which goes all the way around,
+    /// anticlockwise from the positive x-axis.
+    pub fn to_arc(self) -> Arc<S> {
+        Arc::from_centre_radius(
+            self.centre,
+            self.radius,
+            Angle::zero(),
+            Angle::two_pi(),
+        )
+    }
+}
+
+impl<S> From<Circle<S>> for Arc<S> {
+    fn from(circle: Circle<S>) -> Arc<S> { circle.to_arc() }
+}
+
+impl<S> Copy for Circle<S> {}
+
+impl<S> Clone for Circle<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn far_from_the_origin() {
+        let offset = euclid::default::Vector2D::new(1e7, -1e7);
+        let points = [
+            Point::new(1.0, 0.0) + offset,
+            Point::new(0.0, 1.0) + offset,
+            Point::new(-1.0, 0.0) + offset,
+        ];
+
+        let got =
+            Circle::circumscribing(points[0], points[1], points[2]).unwrap();
+
+        assert!(got
+            .centre
+            .approx_eq_eps(&offset.to_point(), &Point::new(1e-6, 1e-6)));
+        assert!(got.radius.approx_eq_eps(&1.0, &1e-6));
+    }
+
+    #[test]
+    fn duplicate_points() {
+        let point = Point::new(1.0, 2.0);
+
+        let got = Circle::circumscribing(point, point, Point::new(5.0, 5.0));
+
+        assert!(got.is_none());
+    }
+}
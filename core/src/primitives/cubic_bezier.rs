@@ -0,0 +1,223 @@
+use euclid::{Point2D, Vector2D};
+
+/// A cubic Bézier curve, defined by its two end points and two control points.
+#[derive(Debug, PartialEq)]
+pub struct CubicBezier<S> {
+    /// Where the curve starts.
+    pub start: Point2D<f64, S>,
+    /// The control point which pulls the curve away from
+    /// [`CubicBezier::start`].
+    pub first_control_point: Point2D<f64, S>,
+    /// The control point which pulls the curve towards [`CubicBezier::end`].
+    pub second_control_point: Point2D<f64, S>,
+    /// Where the curve ends.
+    pub end: Point2D<f64, S>,
+}
+
+impl<S> CubicBezier<S> {
+    /// Create a new [`CubicBezier`].
+    pub const fn new(
+        start: Point2D<f64, S>,
+        first_control_point: Point2D<f64, S>,
+        second_control_point: Point2D<f64, S>,
+        end: Point2D<f64, S>,
+    ) -> Self {
+        CubicBezier {
+            start,
+            first_control_point,
+            second_control_point,
+            end,
+        }
+    }
+
+    /// Evaluate the curve at some `t`, where `0 <= t <= 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::CubicBezier;
+    ///
+    /// let curve = CubicBezier::new(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 1.0),
+    ///     Point::new(1.0, 1.0),
+    ///     Point::new(1.0, 0.0),
+    /// );
+    ///
+    /// assert_eq!(curve.point_at(0.0), curve.start);
+    /// assert_eq!(curve.point_at(0.5), Point::new(0.5, 0.75));
+    /// assert_eq!(curve.point_at(1.0), curve.end);
+    /// ```
+    pub fn point_at(self, t: f64) -> Point2D<f64, S> {
+        let (left, _) = self.split_at(t);
+        left.end
+    }
+
+    /// The curve's first derivative at `t`.
+    pub fn derivative_at(self, t: f64) -> Vector2D<f64, S> {
+        let p0 = self.start.to_vector();
+        let p1 = self.first_control_point.to_vector();
+        let p2 = self.second_control_point.to_vector();
+        let p3 = self.end.to_vector();
+        let u = 1.0 - t;
+
+        (p1 - p0) * (3.0 * u * u)
+            + (p2 - p1) * (6.0 * u * t)
+            + (p3 - p2) * (3.0 * t * t)
+    }
+
+    /// Split the curve in two at `t` using [de Casteljau's algorithm][wiki].
+    ///
+    /// [wiki]: https://en.wikipedia.org/wiki/De_Casteljau%27s_algorithm
+    pub fn split_at(self, t: f64) -> (Self, Self) {
+        let CubicBezier {
+            start,
+            first_control_point,
+            second_control_point,
+            end,
+        } = self;
+
+        let a = start.lerp(first_control_point, t);
+        let b = first_control_point.lerp(second_control_point, t);
+        let c = second_control_point.lerp(end, t);
+
+        let d = a.lerp(b, t);
+        let e = b.lerp(c, t);
+
+        let midpoint = d.lerp(e, t);
+
+        (
+            CubicBezier::new(start, a, d, midpoint),
+            CubicBezier::new(midpoint, e, c, end),
+        )
+    }
+
+    /// The same curve, but going from [`CubicBezier::end`] to
+    /// [`CubicBezier::start`].
+    pub fn reversed(self) -> Self {
+        CubicBezier::new(
+            self.end,
+            self.second_control_point,
+            self.first_control_point,
+            self.start,
+        )
+    }
+
+    /// The values of `t` (in the range `0 < t < 1`) where the curve reaches a
+    /// horizontal or vertical extreme.
+    pub fn extremes(self) -> impl Iterator<Item = f64> {
+        let p0 = self.start.to_vector();
+        let p1 = self.first_control_point.to_vector();
+        let p2 = self.second_control_point.to_vector();
+        let p3 = self.end.to_vector();
+
+        // the derivative is a quadratic, a*t² + b*t + c
+        let a = (p1 - p2) * 3.0 + p3 - p0;
+        let b = (p0 - p1 * 2.0 + p2) * 2.0;
+        let c = p1 - p0;
+
+        let x_roots = quadratic_roots(a.x, b.x, c.x);
+        let y_roots = quadratic_roots(a.y, b.y, c.y);
+
+        let roots: Vec<f64> = x_roots
+            .iter()
+            .chain(y_roots.iter())
+            .flatten()
+            .copied()
+            .filter(|&t| 0.0 < t && t < 1.0)
+            .collect();
+
+        roots.into_iter()
+    }
+}
+
+/// Find the real roots of `a*t² + b*t + c = 0`.
+fn quadratic_roots(a: f64, b: f64, c: f64) -> [Option<f64>; 2] {
+    const EPSILON: f64 = 1e-12;
+
+    if a.abs() < EPSILON {
+        if b.abs() < EPSILON {
+            return [None, None];
+        }
+
+        return [Some(-c / b), None];
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < 0.0 {
+        [None, None]
+    } else {
+        let root = discriminant.sqrt();
+        [Some((-b + root) / (2.0 * a)), Some((-b - root) / (2.0 * a))]
+    }
+}
+
+impl<S> Copy for CubicBezier<S> {}
+
+impl<S> Clone for CubicBezier<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn s_curve() -> CubicBezier<euclid::UnknownUnit> {
+        CubicBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+            Point::new(10.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn splitting_gives_the_same_curve() {
+        let curve = s_curve();
+
+        let (left, right) = curve.split_at(0.25);
+
+        assert_eq!(left.start, curve.start);
+        assert_eq!(right.end, curve.end);
+        assert_eq!(left.end, right.start);
+
+        for &t in &[0.0, 0.3, 0.7, 1.0] {
+            assert!(left.point_at(t).approx_eq(&curve.point_at(t * 0.25)));
+            assert!(right
+                .point_at(t)
+                .approx_eq(&curve.point_at(0.25 + t * 0.75)));
+        }
+    }
+
+    #[test]
+    fn derivative_matches_finite_difference() {
+        let curve = s_curve();
+        let t = 0.4;
+        let h = 1e-6;
+
+        let got = curve.derivative_at(t);
+
+        let expected =
+            (curve.point_at(t + h) - curve.point_at(t - h)) / (2.0 * h);
+        assert!((got - expected).length() < 1e-4);
+    }
+
+    #[test]
+    fn find_the_extremes() {
+        let curve = CubicBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 0.0),
+        );
+
+        let got: Vec<_> = curve.extremes().collect();
+
+        assert_eq!(got, vec![0.5]);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{primitives::Circle, Angle};
+use euclid::{Point2D, Vector2D};
+
+/// An ellipse, defined by its centre, the lengths of its semi-major and
+/// semi-minor axes, and the angle its major axis makes with the x-axis.
+#[derive(Debug, PartialEq)]
+pub struct Ellipse<S> {
+    centre: Point2D<f64, S>,
+    semi_major: f64,
+    semi_minor: f64,
+    rotation: Angle,
+}
+
+impl<S> Ellipse<S> {
+    /// Create a new [`Ellipse`].
+    ///
+    /// If `semi_minor` is longer than `semi_major` the two axes will be swapped
+    /// (adjusting the `rotation` accordingly) so [`Ellipse::semi_major()`] is
+    /// always the longer of the two.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{primitives::Ellipse, Angle};
+    ///
+    /// let ellipse = Ellipse::new(Point::zero(), 5.0, 10.0, Angle::zero());
+    ///
+    /// assert_eq!(ellipse.semi_major(), 10.0);
+    /// assert_eq!(ellipse.semi_minor(), 5.0);
+    /// assert_eq!(ellipse.rotation(), Angle::frac_pi_2());
+    /// ```
+    pub fn new(
+        centre: Point2D<f64, S>,
+        semi_major: f64,
+        semi_minor: f64,
+        rotation: Angle,
+    ) -> Self {
+        debug_assert!(semi_major > 0.0);
+        debug_assert!(semi_minor > 0.0);
+
+        if semi_minor > semi_major {
+            Ellipse::new(
+                centre,
+                semi_minor,
+                semi_major,
+                (rotation + Angle::frac_pi_2()).positive(),
+            )
+        } else {
+            Ellipse {
+                centre,
+                semi_major,
+                semi_minor,
+                rotation,
+            }
+        }
+    }
+
+    /// The [`Ellipse`]'s centre point.
+    pub const fn centre(self) -> Point2D<f64, S> { self.centre }
+
+    /// Half the length of the [`Ellipse`]'s longest diameter.
+    pub const fn semi_major(self) -> f64 { self.semi_major }
+
+    /// Half the length of the [`Ellipse`]'s shortest diameter.
+    pub const fn semi_minor(self) -> f64 { self.semi_minor }
+
+    /// The angle between the major axis and the x-axis.
+    pub const fn rotation(self) -> Angle { self.rotation }
+
+    /// A vector from the centre to the end of the major axis.
+    pub fn major_axis(self) -> Vector2D<f64, S> {
+        let (sin, cos) = self.rotation.sin_cos();
+        Vector2D::new(cos, sin) * self.semi_major
+    }
+
+    /// A vector from the centre to the end of the minor axis (anticlockwise
+    /// from the [`Ellipse::major_axis()`]).
+    pub fn minor_axis(self) -> Vector2D<f64, S> {
+        let (sin, cos) = self.rotation.sin_cos();
+        Vector2D::new(-sin, cos) * self.semi_minor
+    }
+
+    /// How "stretched out" the [`Ellipse`] is, where `0.0` is a circle.
+    pub fn eccentricity(self) -> f64 {
+        let ratio = self.semi_minor / self.semi_major;
+        (1.0 - ratio * ratio).sqrt()
+    }
+
+    /// Is this [`Ellipse`] actually a circle?
+    pub fn is_circle(self) -> bool { self.semi_major == self.semi_minor }
+
+    /// Get the point on the [`Ellipse`] corresponding to a particular
+    /// parametric angle (sometimes called the "eccentric anomaly"), measured
+    /// anticlockwise from the major axis.
+    ///
+    /// # Note
+    ///
+    /// The parametric angle is only the same as the polar angle when the
+    /// [`Ellipse`] is a circle.
+    pub fn point_at(self, angle: Angle) -> Point2D<f64, S> {
+        let (sin, cos) = angle.sin_cos();
+
+        self.centre + self.major_axis() * cos + self.minor_axis() * sin
+    }
+
+    /// The inverse of [`Ellipse::point_at()`], get the parametric angle
+    /// corresponding to a point on the [`Ellipse`].
+    ///
+    /// Points which aren't on the [`Ellipse`] are treated as if they had been
+    /// scaled towards or away from the centre until they were.
+    pub fn parametric_angle_of(self, point: Point2D<f64, S>) -> Angle {
+        let radial = point - self.centre;
+        let (sin, cos) = self.rotation.sin_cos();
+        let local_x = radial.x * cos + radial.y * sin;
+        let local_y = -radial.x * sin + radial.y * cos;
+
+        Angle::radians(
+            (local_y / self.semi_minor).atan2(local_x / self.semi_major),
+        )
+        .positive()
+    }
+
+    /// Is a point inside the [`Ellipse`] (or on its edge)?
+    pub fn contains(self, point: Point2D<f64, S>) -> bool {
+        let radial = point - self.centre;
+        let (sin, cos) = self.rotation.sin_cos();
+        let local_x = (radial.x * cos + radial.y * sin) / self.semi_major;
+        let local_y = (-radial.x * sin + radial.y * cos) / self.semi_minor;
+
+        local_x * local_x + local_y * local_y <= 1.0
+    }
+}
+
+impl<S> From<Circle<S>> for Ellipse<S> {
+    fn from(circle: Circle<S>) -> Ellipse<S> {
+        Ellipse::new(circle.centre, circle.radius, circle.radius, Angle::zero())
+    }
+}
+
+impl<S> Copy for Ellipse<S> {}
+
+impl<S> Clone for Ellipse<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn points_on_a_rotated_ellipse() {
+        let centre = Point::new(1.0, 2.0);
+        let ellipse = Ellipse::new(centre, 10.0, 5.0, Angle::frac_pi_2());
+
+        assert!(ellipse
+            .point_at(Angle::zero())
+            .approx_eq(&Point::new(1.0, 12.0)));
+        assert!(ellipse
+            .point_at(Angle::frac_pi_2())
+            .approx_eq(&Point::new(-4.0, 2.0)));
+        assert!(ellipse
+            .point_at(Angle::pi())
+            .approx_eq(&Point::new(1.0, -8.0)));
+    }
+
+    #[test]
+    fn parametric_angle_round_trips() {
+        let ellipse =
+            Ellipse::new(Point::new(3.0, -1.0), 7.0, 2.0, Angle::degrees(25.0));
+
+        for &degrees in &[0.0, 30.0, 90.0, 200.0, 359.0] {
+            let angle = Angle::degrees(degrees);
+            let point = ellipse.point_at(angle);
+
+            let got = ellipse.parametric_angle_of(point);
+
+            assert!(got.radians.approx_eq(&angle.radians));
+        }
+    }
+
+    #[test]
+    fn eccentricity() {
+        let circle = Ellipse::new(Point::zero(), 2.0, 2.0, Angle::zero());
+        let ellipse = Ellipse::new(Point::zero(), 5.0, 3.0, Angle::zero());
+
+        assert!(circle.is_circle());
+        assert_eq!(circle.eccentricity(), 0.0);
+        assert!(!ellipse.is_circle());
+        assert_eq!(ellipse.eccentricity(), 0.8);
+    }
+
+    #[test]
+    fn points_inside_a_rotated_ellipse() {
+        let ellipse =
+            Ellipse::new(Point::new(1.0, 1.0), 4.0, 1.0, Angle::frac_pi_2());
+
+        assert!(ellipse.contains(Point::new(1.0, 4.5)));
+        assert!(ellipse.contains(Point::new(1.5, 1.0)));
+        assert!(!ellipse.contains(Point::new(3.0, 1.0)));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    primitives::{Arc, Ellipse},
+    Angle,
+};
+use euclid::Point2D;
+
+/// A segment of an [`Ellipse`].
+///
+/// Like [`Ellipse::point_at()`], all angles are parametric angles measured
+/// anticlockwise from the ellipse's major axis.
+///
+/// [`Ellipse::point_at()`]: crate::primitives::Ellipse::point_at
+#[derive(Debug, PartialEq)]
+pub struct EllipticalArc<S> {
+    ellipse: Ellipse<S>,
+    start_angle: Angle,
+    sweep_angle: Angle,
+}
+
+impl<S> EllipticalArc<S> {
+    /// Create a new [`EllipticalArc`] which follows part of an [`Ellipse`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{
+    ///     primitives::{Ellipse, EllipticalArc},
+    ///     Angle,
+    /// };
+    ///
+    /// let ellipse = Ellipse::new(Point::zero(), 10.0, 5.0, Angle::zero());
+    /// let arc = EllipticalArc::new(ellipse, Angle::zero(), Angle::frac_pi_2());
+    ///
+    /// assert_eq!(arc.start(), Point::new(10.0, 0.0));
+    /// assert!(arc.is_anticlockwise());
+    /// ```
+    pub const fn new(
+        ellipse: Ellipse<S>,
+        start_angle: Angle,
+        sweep_angle: Angle,
+    ) -> Self {
+        EllipticalArc {
+            ellipse,
+            start_angle,
+            sweep_angle,
+        }
+    }
+
+    /// The [`Ellipse`] this [`EllipticalArc`] is a part of.
+    pub const fn ellipse(self) -> Ellipse<S> { self.ellipse }
+
+    /// The [`EllipticalArc`]'s centre point.
+    pub const fn centre(self) -> Point2D<f64, S> { self.ellipse.centre() }
+
+    /// The parametric angle this [`EllipticalArc`] starts at.
+    pub const fn start_angle(self) -> Angle { self.start_angle }
+
+    /// How far the [`EllipticalArc`] sweeps, where a positive angle is
+    /// anticlockwise.
+    pub const fn sweep_angle(self) -> Angle { self.sweep_angle }
+
+    /// The parametric angle this [`EllipticalArc`] ends at.
+    pub fn end_angle(self) -> Angle { self.start_angle() + self.sweep_angle() }
+
+    /// Does this [`EllipticalArc`] go anticlockwise?
+    pub fn is_anticlockwise(self) -> bool { self.sweep_angle > Angle::zero() }
+
+    /// Does this [`EllipticalArc`] go clockwise?
+    pub fn is_clockwise(self) -> bool { self.sweep_angle < Angle::zero() }
+
+    /// Where the [`EllipticalArc`] starts.
+    pub fn start(self) -> Point2D<f64, S> { self.point_at(Angle::zero()) }
+
+    /// Where the [`EllipticalArc`] ends.
+    pub fn end(self) -> Point2D<f64, S> { self.point_at(self.sweep_angle()) }
+
+    /// Get the point which is `angle` radians along the [`EllipticalArc`],
+    /// relative to its start.
+    pub fn point_at(self, angle: Angle) -> Point2D<f64, S> {
+        self.ellipse.point_at(self.start_angle() + angle)
+    }
+
+    /// Does this [`EllipticalArc`] pass through a particular parametric angle?
+    ///
+    /// Unlike a simple range check, this accounts for angles which wrap
+    /// around (e.g. `-90°` and `270°` are treated the same).
+    pub fn contains_angle(self, angle: Angle) -> bool {
+        let sweep = self.sweep_angle().radians;
+
+        if sweep.abs() >= Angle::two_pi().radians {
+            return true;
+        }
+
+        let offset = if sweep >= 0.0 {
+            angle - self.start_angle()
+        } else {
+            self.start_angle() - angle
+        };
+
+        offset.positive().radians <= sweep.abs()
+    }
+}
+
+impl<S> From<Arc<S>> for EllipticalArc<S> {
+    /// An [`Arc`] is just an [`EllipticalArc`] where both axes are the same
+    /// length.
+    fn from(arc: Arc<S>) -> Self {
+        let circle = Ellipse::new(
+            arc.centre(),
+            arc.radius(),
+            arc.radius(),
+            Angle::zero(),
+        );
+
+        EllipticalArc::new(circle, arc.start_angle(), arc.sweep_angle())
+    }
+}
+
+impl<S> Copy for EllipticalArc<S> {}
+
+impl<S> Clone for EllipticalArc<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::{approxeq::ApproxEq, UnknownUnit};
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn quarter(start: Angle, sweep: Angle) -> EllipticalArc<UnknownUnit> {
+        let ellipse = Ellipse::new(Point::zero(), 10.0, 5.0, Angle::zero());
+        EllipticalArc::new(ellipse, start, sweep)
+    }
+
+    #[test]
+    fn start_and_end_points() {
+        let arc = quarter(Angle::zero(), Angle::frac_pi_2());
+
+        assert!(arc.start().approx_eq(&Point::new(10.0, 0.0)));
+        assert!(arc.end().approx_eq(&Point::new(0.0, 5.0)));
+    }
+
+    #[test]
+    fn contains_angles_which_wrap_around() {
+        let arc = quarter(-Angle::frac_pi_4(), Angle::frac_pi_2());
+
+        assert!(arc.contains_angle(Angle::zero()));
+        assert!(arc.contains_angle(Angle::degrees(340.0)));
+        assert!(!arc.contains_angle(Angle::pi()));
+    }
+
+    #[test]
+    fn clockwise_arcs_contain_angles() {
+        let arc = quarter(Angle::zero(), -Angle::frac_pi_2());
+
+        assert!(arc.contains_angle(Angle::degrees(-45.0)));
+        assert!(arc.contains_angle(Angle::degrees(300.0)));
+        assert!(!arc.contains_angle(Angle::degrees(45.0)));
+    }
+}
@@ -1,7 +1,31 @@
 //! Basic geometric types which are generic over their coordinate space.
 
 mod arc;
+mod circle;
+mod cubic_bezier;
+mod ellipse;
+mod elliptical_arc;
 mod line;
+mod polygon;
+mod polyline;
+mod quadratic_bezier;
+mod ray;
+mod rectangle;
+mod region;
+mod spline;
+mod xline;
 
 pub use arc::Arc;
+pub use circle::Circle;
+pub use cubic_bezier::CubicBezier;
+pub use ellipse::Ellipse;
+pub use elliptical_arc::EllipticalArc;
 pub use line::Line;
+pub use polygon::{Polygon, PolygonRadius};
+pub use polyline::{FillRule, Polyline, Segment, Vertex};
+pub use quadratic_bezier::QuadraticBezier;
+pub use ray::Ray;
+pub use rectangle::Rectangle;
+pub use region::Region;
+pub use spline::{InvalidSpline, Spline, TangentConditions};
+pub use xline::XLine;
@@ -0,0 +1,142 @@
+use crate::{
+    primitives::{Polyline, Vertex},
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+
+/// Constructors for common polygons, which are represented as closed
+/// [`Polyline`]s.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Polygon;
+
+impl Polygon {
+    /// Create a regular polygon with `sides` sides.
+    ///
+    /// With no `rotation`, the first vertex will be directly to the right of
+    /// the `centre` (i.e. along the positive x-axis).
+    ///
+    /// # Panics
+    ///
+    /// A polygon needs at least 3 sides.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::{
+    ///     primitives::{Polygon, PolygonRadius},
+    ///     Angle,
+    /// };
+    /// use euclid::approxeq::ApproxEq;
+    ///
+    /// // a hexagon which is 10 units across the flats
+    /// let hexagon = Polygon::regular(
+    ///     Point::zero(),
+    ///     6,
+    ///     PolygonRadius::Inradius(5.0),
+    ///     Angle::zero(),
+    /// );
+    ///
+    /// assert_eq!(hexagon.len(), 6);
+    /// assert!(hexagon.is_closed());
+    /// let circumradius = 5.0 / (Angle::pi() / 6.0).radians.cos();
+    /// assert!(hexagon.vertices()[0].point.x.approx_eq(&circumradius));
+    /// ```
+    pub fn regular<S>(
+        centre: Point2D<f64, S>,
+        sides: usize,
+        radius: PolygonRadius,
+        rotation: Angle,
+    ) -> Polyline<S> {
+        assert!(sides >= 3, "A polygon needs at least 3 sides");
+
+        let circumradius = radius.circumradius(sides);
+        let step = Angle::two_pi() / sides as f64;
+
+        let vertices = (0..sides)
+            .map(|i| {
+                let (sin, cos) = (rotation + step * i as f64).sin_cos();
+                Vertex::straight(
+                    centre + Vector2D::new(cos, sin) * circumradius,
+                )
+            })
+            .collect();
+
+        Polyline::new(vertices, true)
+    }
+}
+
+/// How big a regular polygon is (see [`Polygon::regular()`]).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PolygonRadius {
+    /// The distance from the centre to each vertex (the polygon is inscribed
+    /// in a circle with this radius).
+    Circumradius(f64),
+    /// The distance from the centre to the middle of each side (the polygon
+    /// is circumscribed about a circle with this radius).
+    Inradius(f64),
+}
+
+impl PolygonRadius {
+    /// The distance from the centre to each vertex of a regular polygon with
+    /// `sides` sides.
+    pub fn circumradius(self, sides: usize) -> f64 {
+        match self {
+            PolygonRadius::Circumradius(r) => r,
+            PolygonRadius::Inradius(r) => {
+                r / (Angle::pi() / sides as f64).radians.cos()
+            },
+        }
+    }
+
+    /// The distance from the centre to the middle of each side of a regular
+    /// polygon with `sides` sides.
+    pub fn inradius(self, sides: usize) -> f64 {
+        match self {
+            PolygonRadius::Circumradius(r) => {
+                r * (Angle::pi() / sides as f64).radians.cos()
+            },
+            PolygonRadius::Inradius(r) => r,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn rotated_square() {
+        let square = Polygon::regular(
+            Point::new(1.0, 1.0),
+            4,
+            PolygonRadius::Circumradius(2.0_f64.sqrt()),
+            Angle::frac_pi_4(),
+        );
+
+        let expected = [
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+        ];
+        for (vertex, expected) in square.vertices().iter().zip(&expected) {
+            assert!(vertex.point.approx_eq(expected));
+        }
+        assert!(square.area().approx_eq(&4.0));
+    }
+
+    #[test]
+    fn radius_conversions_round_trip() {
+        let radius = PolygonRadius::Inradius(3.0);
+
+        let circumradius = radius.circumradius(5);
+
+        assert!(PolygonRadius::Circumradius(circumradius)
+            .inradius(5)
+            .approx_eq(&3.0));
+    }
+}
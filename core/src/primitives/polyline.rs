@@ -0,0 +1,651 @@
+use crate::{
+    primitives::{Arc, Line},
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+
+/// A single point in a [`Polyline`].
+#[derive(Debug, PartialEq)]
+pub struct Vertex<S> {
+    /// Where the vertex is located.
+    pub point: Point2D<f64, S>,
+    /// The bulge of the segment leaving this vertex, using the same convention
+    /// as a DXF `LWPOLYLINE`.
+    ///
+    /// The bulge is the tangent of a quarter of the segment's sweep angle, so
+    /// `0.0` is a straight line, `1.0` is an anticlockwise semicircle and
+    /// `-1.0` is a clockwise semicircle.
+    pub bulge: f64,
+}
+
+impl<S> Vertex<S> {
+    /// Create a new [`Vertex`].
+    pub const fn new(point: Point2D<f64, S>, bulge: f64) -> Self {
+        Vertex { point, bulge }
+    }
+
+    /// Create a [`Vertex`] which is followed by a straight line.
+    pub const fn straight(point: Point2D<f64, S>) -> Self {
+        Vertex::new(point, 0.0)
+    }
+
+    /// Is the segment leaving this [`Vertex`] straight?
+    pub fn is_straight(self) -> bool { self.bulge == 0.0 }
+}
+
+impl<S> Copy for Vertex<S> {}
+
+impl<S> Clone for Vertex<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// One of the pieces making up a [`Polyline`].
+#[derive(Debug, PartialEq)]
+pub enum Segment<S> {
+    /// A straight segment.
+    Line(Line<S>),
+    /// A curved segment.
+    Arc(Arc<S>),
+}
+
+impl<S> Segment<S> {
+    /// Create the [`Segment`] going from `start` to `end` with a particular
+    /// bulge (see [`Vertex::bulge`]).
+    pub fn from_bulge(
+        start: Point2D<f64, S>,
+        end: Point2D<f64, S>,
+        bulge: f64,
+    ) -> Self {
+        match BulgedSegment::new(start, end, bulge) {
+            Some(arc) => {
+                let radial = start - arc.centre;
+
+                Segment::Arc(Arc::from_centre_radius(
+                    arc.centre,
+                    arc.radius,
+                    Angle::radians(radial.y.atan2(radial.x)),
+                    Angle::radians(arc.sweep_angle),
+                ))
+            },
+            None => Segment::Line(Line::new(start, end)),
+        }
+    }
+
+    /// Where the [`Segment`] starts.
+    pub fn start(self) -> Point2D<f64, S> {
+        match self {
+            Segment::Line(line) => line.start,
+            Segment::Arc(arc) => arc.start(),
+        }
+    }
+
+    /// Where the [`Segment`] ends.
+    pub fn end(self) -> Point2D<f64, S> {
+        match self {
+            Segment::Line(line) => line.end,
+            Segment::Arc(arc) => arc.end(),
+        }
+    }
+}
+
+impl<S> From<Line<S>> for Segment<S> {
+    fn from(line: Line<S>) -> Segment<S> { Segment::Line(line) }
+}
+
+impl<S> From<Arc<S>> for Segment<S> {
+    fn from(arc: Arc<S>) -> Segment<S> { Segment::Arc(arc) }
+}
+
+impl<S> Copy for Segment<S> {}
+
+/// The arc described by a bulged segment.
+struct BulgedSegment<S> {
+    centre: Point2D<f64, S>,
+    radius: f64,
+    /// The sweep angle, in radians.
+    sweep_angle: f64,
+    /// The point halfway along the arc.
+    midpoint: Point2D<f64, S>,
+}
+
+impl<S> BulgedSegment<S> {
+    /// Calculate the arc's properties, returning [`None`] if the segment is
+    /// straight.
+    fn new(
+        start: Point2D<f64, S>,
+        end: Point2D<f64, S>,
+        bulge: f64,
+    ) -> Option<Self> {
+        let chord = end - start;
+        let chord_length = chord.length();
+
+        if bulge == 0.0 || chord_length == 0.0 {
+            return None;
+        }
+
+        let chord_midpoint = start.lerp(end, 0.5);
+        let left = Vector2D::new(-chord.y, chord.x) / chord_length;
+
+        // the centre is somewhere along the chord's perpendicular bisector,
+        // on the left for anticlockwise minor arcs, while the arc itself
+        // bulges out to the right by the sagitta
+        let offset = chord_length / 2.0 * (1.0 - bulge * bulge) / (2.0 * bulge);
+        let sagitta = bulge * chord_length / 2.0;
+
+        Some(BulgedSegment {
+            centre: chord_midpoint + left * offset,
+            radius: chord_length * (1.0 + bulge * bulge) / (4.0 * bulge.abs()),
+            sweep_angle: 4.0 * bulge.atan(),
+            midpoint: chord_midpoint - left * sagitta,
+        })
+    }
+
+    /// The signed area between the chord and the arc.
+    fn area(&self) -> f64 {
+        let theta = self.sweep_angle;
+        self.radius * self.radius * (theta - theta.sin()) / 2.0
+    }
+
+    /// The centroid of the area between the chord and the arc.
+    fn centroid(&self) -> Point2D<f64, S> {
+        let theta = self.sweep_angle.abs();
+        let distance = 4.0 * self.radius * (theta / 2.0).sin().powi(3)
+            / (3.0 * (theta - theta.sin()));
+
+        self.centre + (self.midpoint - self.centre).normalize() * distance
+    }
+
+    /// Is the point inside the area between the chord and the arc?
+    fn contains(
+        &self,
+        start: Point2D<f64, S>,
+        end: Point2D<f64, S>,
+        point: Point2D<f64, S>,
+    ) -> bool {
+        let chord = end - start;
+        let same_side_as_arc = chord.cross(point - start)
+            * chord.cross(self.midpoint - start)
+            > 0.0;
+
+        same_side_as_arc
+            && (point - self.centre).square_length()
+                <= self.radius * self.radius
+    }
+}
+
+impl<S> Clone for Segment<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// A sequence of connected line and arc segments, similar to a DXF
+/// `LWPOLYLINE`.
+#[derive(Debug, PartialEq)]
+pub struct Polyline<S> {
+    vertices: Vec<Vertex<S>>,
+    closed: bool,
+}
+
+impl<S> Polyline<S> {
+    /// Create a new [`Polyline`] from its vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::{Polyline, Vertex};
+    ///
+    /// let square = Polyline::new(
+    ///     vec![
+    ///         Vertex::straight(Point::new(0.0, 0.0)),
+    ///         Vertex::straight(Point::new(1.0, 0.0)),
+    ///         Vertex::straight(Point::new(1.0, 1.0)),
+    ///         Vertex::straight(Point::new(0.0, 1.0)),
+    ///     ],
+    ///     true,
+    /// );
+    ///
+    /// assert_eq!(square.segments().count(), 4);
+    /// assert_eq!(square.area(), 1.0);
+    /// ```
+    pub fn new(vertices: Vec<Vertex<S>>, closed: bool) -> Self {
+        Polyline { vertices, closed }
+    }
+
+    /// Create an open [`Polyline`] made of straight lines between points.
+    pub fn from_points<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = Point2D<f64, S>>,
+    {
+        Polyline::new(points.into_iter().map(Vertex::straight).collect(), false)
+    }
+
+    /// The [`Vertex`]es making up this [`Polyline`].
+    pub fn vertices(&self) -> &[Vertex<S>] { &self.vertices }
+
+    /// The number of vertices.
+    pub fn len(&self) -> usize { self.vertices.len() }
+
+    /// Does this [`Polyline`] have no vertices?
+    pub fn is_empty(&self) -> bool { self.vertices.is_empty() }
+
+    /// Does the last [`Vertex`] connect back to the first?
+    pub fn is_closed(&self) -> bool { self.closed }
+
+    /// Open or close the [`Polyline`].
+    pub fn set_closed(&mut self, closed: bool) { self.closed = closed; }
+
+    /// Add a [`Vertex`] to the end of the [`Polyline`].
+    pub fn push(&mut self, vertex: Vertex<S>) { self.vertices.push(vertex); }
+
+    /// Insert a [`Vertex`] at a particular index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, vertex: Vertex<S>) {
+        self.vertices.insert(index, vertex);
+    }
+
+    /// Remove the [`Vertex`] at a particular index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Vertex<S> {
+        self.vertices.remove(index)
+    }
+
+    /// Move a [`Vertex`] to a new location, preserving its bulge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn move_vertex(&mut self, index: usize, point: Point2D<f64, S>) {
+        self.vertices[index].point = point;
+    }
+
+    /// Change the bulge of the segment leaving a particular [`Vertex`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_bulge(&mut self, index: usize, bulge: f64) {
+        self.vertices[index].bulge = bulge;
+    }
+
+    /// Iterate over the [`Segment`]s making up this [`Polyline`].
+    ///
+    /// The bulge on the last [`Vertex`] is only used when the [`Polyline`] is
+    /// closed.
+    pub fn segments<'a>(&'a self) -> impl Iterator<Item = Segment<S>> + 'a {
+        let closing_segment = if self.closed && self.vertices.len() > 1 {
+            Some((&self.vertices[self.vertices.len() - 1], &self.vertices[0]))
+        } else {
+            None
+        };
+
+        self.vertices
+            .windows(2)
+            .map(|pair| (&pair[0], &pair[1]))
+            .chain(closing_segment)
+            .map(|(start, end)| {
+                Segment::from_bulge(start.point, end.point, start.bulge)
+            })
+    }
+
+    /// The area enclosed by the [`Polyline`], where anticlockwise polylines
+    /// have a positive area.
+    ///
+    /// Open polylines are treated as if the last vertex were joined to the
+    /// first by a straight line.
+    pub fn signed_area(&self) -> f64 {
+        let mut area = 0.0;
+        let mut segments = self.segments().peekable();
+
+        if segments.peek().is_none() {
+            return 0.0;
+        }
+
+        let first = self.vertices[0].point;
+        let mut last = first;
+
+        for segment in segments {
+            let (start, end) = (segment.start(), segment.end());
+            area += Vector2D::cross(start.to_vector(), end.to_vector()) / 2.0;
+
+            if let Segment::Arc(arc) = segment {
+                // add the area between the chord and the arc
+                let theta = arc.sweep_angle().radians;
+                area +=
+                    arc.radius() * arc.radius() * (theta - theta.sin()) / 2.0;
+            }
+
+            last = end;
+        }
+
+        area + Vector2D::cross(last.to_vector(), first.to_vector()) / 2.0
+    }
+
+    /// The area enclosed by the [`Polyline`].
+    ///
+    /// See [`Polyline::signed_area()`] for more.
+    pub fn area(&self) -> f64 { self.signed_area().abs() }
+
+    /// Iterate over each edge as `(start, end, bulge)`, including the edge
+    /// which joins the last vertex back to the first.
+    ///
+    /// As with [`Polyline::signed_area()`], open polylines are closed off
+    /// with a straight line.
+    fn edges<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (Point2D<f64, S>, Point2D<f64, S>, f64)> + 'a
+    {
+        let closing_edge = match (self.vertices.last(), self.vertices.first()) {
+            (Some(last), Some(first)) => {
+                let bulge = if self.closed { last.bulge } else { 0.0 };
+                Some((last.point, first.point, bulge))
+            },
+            _ => None,
+        };
+
+        self.vertices
+            .windows(2)
+            .map(|pair| (pair[0].point, pair[1].point, pair[0].bulge))
+            .chain(closing_edge)
+    }
+
+    /// The centre of mass of the area enclosed by this [`Polyline`], or
+    /// [`None`] if it doesn't enclose any area.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::Polyline;
+    ///
+    /// let square = Polyline::from_points(vec![
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(2.0, 0.0),
+    ///     Point::new(2.0, 2.0),
+    ///     Point::new(0.0, 2.0),
+    /// ]);
+    ///
+    /// assert_eq!(square.centroid(), Some(Point::new(1.0, 1.0)));
+    /// ```
+    pub fn centroid(&self) -> Option<Point2D<f64, S>> {
+        let mut area = 0.0;
+        let mut moment = Vector2D::zero();
+
+        for (start, end, bulge) in self.edges() {
+            // a triangle fanning out from the origin
+            let (start_vector, end_vector) =
+                (start.to_vector(), end.to_vector());
+            let cross = start_vector.cross(end_vector);
+            area += cross / 2.0;
+            moment += (start_vector + end_vector) * (cross / 6.0);
+
+            if let Some(arc) = BulgedSegment::new(start, end, bulge) {
+                let segment_area = arc.area();
+                area += segment_area;
+                moment += arc.centroid().to_vector() * segment_area;
+            }
+        }
+
+        if area.abs() <= std::f64::EPSILON {
+            None
+        } else {
+            Some((moment / area).to_point())
+        }
+    }
+
+    /// Is a point inside the area enclosed by this [`Polyline`]?
+    ///
+    /// This uses the even-odd rule, so self-intersecting polylines may have
+    /// "holes". See [`Polyline::contains_with()`] for other [`FillRule`]s.
+    pub fn contains(&self, point: Point2D<f64, S>) -> bool {
+        self.contains_with(point, FillRule::EvenOdd)
+    }
+
+    /// Is a point inside the area enclosed by this [`Polyline`], using a
+    /// particular [`FillRule`]?
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::{FillRule, Polyline};
+    ///
+    /// // a square which goes around twice
+    /// let corners = [
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 0.0),
+    ///     Point::new(1.0, 1.0),
+    ///     Point::new(0.0, 1.0),
+    /// ];
+    /// let polyline = Polyline::from_points(corners.iter().chain(&corners).copied());
+    /// let centre = Point::new(0.5, 0.5);
+    ///
+    /// assert_eq!(polyline.winding_number(centre), 2);
+    /// assert!(polyline.contains_with(centre, FillRule::NonZero));
+    /// assert!(!polyline.contains_with(centre, FillRule::EvenOdd));
+    /// ```
+    pub fn contains_with(
+        &self,
+        point: Point2D<f64, S>,
+        rule: FillRule,
+    ) -> bool {
+        rule.is_inside(self.winding_number(point))
+    }
+
+    /// How many times this [`Polyline`] goes around a point, where
+    /// anticlockwise loops count as positive.
+    ///
+    /// Open polylines are treated as if the last vertex were joined to the
+    /// first by a straight line.
+    pub fn winding_number(&self, point: Point2D<f64, S>) -> i32 {
+        let mut winding = 0;
+
+        for (start, end, bulge) in self.edges() {
+            // cast a ray to the right and count the straight chords crossing
+            // it, upwards crossings go anticlockwise around the point
+            if (start.y > point.y) != (end.y > point.y) {
+                let t = (point.y - start.y) / (end.y - start.y);
+
+                if point.x < start.x + t * (end.x - start.x) {
+                    winding += if end.y > start.y { 1 } else { -1 };
+                }
+            }
+
+            // then account for the area between the chord and the arc,
+            // which is a small loop going the same way as the arc
+            if let Some(arc) = BulgedSegment::new(start, end, bulge) {
+                if arc.contains(start, end, point) {
+                    winding += if bulge > 0.0 { 1 } else { -1 };
+                }
+            }
+        }
+
+        winding
+    }
+}
+
+/// The rules for deciding which parts of a self-intersecting or nested shape
+/// are inside it, based on its [`Polyline::winding_number()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FillRule {
+    /// A point is inside when a ray starting from it crosses the boundary an
+    /// odd number of times.
+    EvenOdd,
+    /// A point is inside when the boundary goes around it at least once.
+    NonZero,
+}
+
+impl Default for FillRule {
+    fn default() -> Self { FillRule::EvenOdd }
+}
+
+impl FillRule {
+    /// Does this rule consider a particular winding number to be inside?
+    pub fn is_inside(self, winding_number: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding_number % 2 != 0,
+            FillRule::NonZero => winding_number != 0,
+        }
+    }
+}
+
+impl<S> Clone for Polyline<S> {
+    fn clone(&self) -> Self {
+        Polyline {
+            vertices: self.vertices.clone(),
+            closed: self.closed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+    use std::f64::consts::PI;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn semicircle_from_bulge() {
+        let start = Point::new(1.0, 0.0);
+        let end = Point::new(-1.0, 0.0);
+
+        let got = Segment::from_bulge(start, end, 1.0);
+
+        match got {
+            Segment::Arc(arc) => {
+                assert!(arc.centre().approx_eq(&Point::zero()));
+                assert!(arc.radius().approx_eq(&1.0));
+                assert!(arc.is_anticlockwise());
+                assert!(arc
+                    .point_at(Angle::frac_pi_2())
+                    .approx_eq(&Point::new(0.0, 1.0)));
+                assert!(arc.end().approx_eq(&end));
+            },
+            other => panic!("Expected an arc, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn clockwise_major_arc_from_bulge() {
+        let start = Point::new(1.0, 0.0);
+        let end = Point::new(0.0, 1.0);
+
+        // three quarters of a circle going the long way around
+        let bulge = (Angle::degrees(-270.0).radians / 4.0).tan();
+        let got = Segment::from_bulge(start, end, bulge);
+
+        match got {
+            Segment::Arc(arc) => {
+                assert!(arc.centre().approx_eq(&Point::zero()));
+                assert!(arc.radius().approx_eq(&1.0));
+                assert!(arc.is_clockwise());
+                assert!(arc.is_major_arc());
+                assert!(arc.end().approx_eq(&end));
+            },
+            other => panic!("Expected an arc, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn area_of_a_rounded_shape() {
+        // a 2x1 rectangle with a semicircle on the right hand side
+        let shape = Polyline::new(
+            vec![
+                Vertex::straight(Point::new(0.0, 0.0)),
+                Vertex::new(Point::new(2.0, 0.0), 1.0),
+                Vertex::straight(Point::new(2.0, 2.0)),
+                Vertex::straight(Point::new(0.0, 2.0)),
+            ],
+            true,
+        );
+
+        let got = shape.signed_area();
+
+        assert!(got.approx_eq(&(4.0 + PI / 2.0)));
+    }
+
+    #[test]
+    fn clockwise_polylines_have_negative_area() {
+        let triangle = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 0.0),
+        ]);
+
+        assert_eq!(triangle.signed_area(), -0.5);
+    }
+
+    #[test]
+    fn edit_vertices() {
+        let mut polyline = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+        ]);
+
+        polyline.push(Vertex::straight(Point::new(1.0, 1.0)));
+        polyline.insert(1, Vertex::straight(Point::new(0.5, -1.0)));
+        polyline.set_bulge(0, 0.5);
+        polyline.move_vertex(3, Point::new(2.0, 2.0));
+        let removed = polyline.remove(2);
+
+        assert_eq!(removed.point, Point::new(1.0, 0.0));
+        assert_eq!(polyline.len(), 3);
+        assert_eq!(polyline.vertices()[0].bulge, 0.5);
+        assert_eq!(polyline.vertices()[2].point, Point::new(2.0, 2.0));
+        assert_eq!(polyline.segments().count(), 2);
+        polyline.set_closed(true);
+        assert_eq!(polyline.segments().count(), 3);
+    }
+
+    #[test]
+    fn centroid_and_containment_with_arcs() {
+        // a unit circle made from two semicircles
+        let circle = Polyline::new(
+            vec![
+                Vertex::new(Point::new(1.0, 0.0), 1.0),
+                Vertex::new(Point::new(-1.0, 0.0), 1.0),
+            ],
+            true,
+        );
+        // the top half of the circle
+        let semicircle = Polyline::new(
+            vec![
+                Vertex::new(Point::new(1.0, 0.0), 1.0),
+                Vertex::straight(Point::new(-1.0, 0.0)),
+            ],
+            true,
+        );
+
+        assert!(circle.centroid().unwrap().approx_eq(&Point::zero()));
+        assert!(circle.contains(Point::new(0.5, -0.5)));
+        assert!(!circle.contains(Point::new(0.9, 0.9)));
+        let expected = Point::new(0.0, 4.0 / (3.0 * PI));
+        assert!(semicircle.centroid().unwrap().approx_eq(&expected));
+        assert!(semicircle.contains(Point::new(0.0, 0.5)));
+        assert!(!semicircle.contains(Point::new(0.0, -0.5)));
+    }
+
+    #[test]
+    fn winding_number_of_bulged_loops() {
+        // a clockwise circle made from two semicircles
+        let circle = Polyline::new(
+            vec![
+                Vertex::new(Point::new(1.0, 0.0), -1.0),
+                Vertex::new(Point::new(-1.0, 0.0), -1.0),
+            ],
+            true,
+        );
+
+        assert_eq!(circle.winding_number(Point::new(0.2, 0.5)), -1);
+        assert_eq!(circle.winding_number(Point::new(0.2, -0.5)), -1);
+        assert_eq!(circle.winding_number(Point::new(2.0, 0.5)), 0);
+        assert!(circle.contains_with(Point::new(0.0, 0.3), FillRule::NonZero));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::primitives::CubicBezier;
+use euclid::{Point2D, Vector2D};
+
+/// A quadratic Bézier curve, defined by its two end points and a single
+/// control point.
+#[derive(Debug, PartialEq)]
+pub struct QuadraticBezier<S> {
+    /// Where the curve starts.
+    pub start: Point2D<f64, S>,
+    /// The point which pulls the curve away from the straight line between
+    /// [`QuadraticBezier::start`] and [`QuadraticBezier::end`].
+    pub control_point: Point2D<f64, S>,
+    /// Where the curve ends.
+    pub end: Point2D<f64, S>,
+}
+
+impl<S> QuadraticBezier<S> {
+    /// Create a new [`QuadraticBezier`].
+    pub const fn new(
+        start: Point2D<f64, S>,
+        control_point: Point2D<f64, S>,
+        end: Point2D<f64, S>,
+    ) -> Self {
+        QuadraticBezier {
+            start,
+            control_point,
+            end,
+        }
+    }
+
+    /// Evaluate the curve at some `t`, where `0 <= t <= 1`.
+    pub fn point_at(self, t: f64) -> Point2D<f64, S> {
+        let (left, _) = self.split_at(t);
+        left.end
+    }
+
+    /// The curve's first derivative at `t`.
+    pub fn derivative_at(self, t: f64) -> Vector2D<f64, S> {
+        (self.control_point - self.start) * (2.0 * (1.0 - t))
+            + (self.end - self.control_point) * (2.0 * t)
+    }
+
+    /// Split the curve in two at `t` using de Casteljau's algorithm.
+    pub fn split_at(self, t: f64) -> (Self, Self) {
+        let a = self.start.lerp(self.control_point, t);
+        let b = self.control_point.lerp(self.end, t);
+        let midpoint = a.lerp(b, t);
+
+        (
+            QuadraticBezier::new(self.start, a, midpoint),
+            QuadraticBezier::new(midpoint, b, self.end),
+        )
+    }
+
+    /// The same curve, but going from [`QuadraticBezier::end`] to
+    /// [`QuadraticBezier::start`].
+    pub fn reversed(self) -> Self {
+        QuadraticBezier::new(self.end, self.control_point, self.start)
+    }
+
+    /// The values of `t` (in the range `0 < t < 1`) where the curve reaches a
+    /// horizontal or vertical extreme.
+    pub fn extremes(self) -> impl Iterator<Item = f64> {
+        // the derivative is linear, so each axis has at most one root
+        let a = self.control_point - self.start;
+        let b = self.start - self.control_point * 2.0 + self.end.to_vector();
+
+        let x_root = if b.x != 0.0 { Some(-a.x / b.x) } else { None };
+        let y_root = if b.y != 0.0 { Some(-a.y / b.y) } else { None };
+
+        x_root
+            .into_iter()
+            .chain(y_root)
+            .filter(|&t| 0.0 < t && t < 1.0)
+    }
+
+    /// Convert this curve to the equivalent [`CubicBezier`] (known as *degree
+    /// elevation*).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::QuadraticBezier;
+    ///
+    /// let quadratic = QuadraticBezier::new(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(3.0, 3.0),
+    ///     Point::new(6.0, 0.0),
+    /// );
+    ///
+    /// let cubic = quadratic.to_cubic();
+    ///
+    /// assert_eq!(cubic.start, quadratic.start);
+    /// assert_eq!(cubic.first_control_point, Point::new(2.0, 2.0));
+    /// assert_eq!(cubic.second_control_point, Point::new(4.0, 2.0));
+    /// assert_eq!(cubic.end, quadratic.end);
+    /// ```
+    pub fn to_cubic(self) -> CubicBezier<S> {
+        CubicBezier::new(
+            self.start,
+            self.start.lerp(self.control_point, 2.0 / 3.0),
+            self.end.lerp(self.control_point, 2.0 / 3.0),
+            self.end,
+        )
+    }
+}
+
+impl<S> Copy for QuadraticBezier<S> {}
+
+impl<S> Clone for QuadraticBezier<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn elevated_curve_is_identical() {
+        let quadratic = QuadraticBezier::new(
+            Point::new(1.0, -2.0),
+            Point::new(5.0, 8.0),
+            Point::new(9.0, 3.0),
+        );
+
+        let cubic = quadratic.to_cubic();
+
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!(cubic.point_at(t).approx_eq(&quadratic.point_at(t)));
+        }
+    }
+
+    #[test]
+    fn find_the_extremes() {
+        let curve = QuadraticBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 2.0),
+            Point::new(2.0, 0.0),
+        );
+
+        let got: Vec<_> = curve.extremes().collect();
+
+        assert_eq!(got, vec![0.5]);
+        assert_eq!(curve.point_at(0.5), Point::new(1.0, 1.0));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    primitives::{
+        xline::{clip_parametric, crossing},
+        Line, XLine,
+    },
+    BoundingBox,
+};
+use euclid::{Point2D, Vector2D};
+
+/// A line which starts at a point and goes on forever in one direction.
+#[derive(Debug, PartialEq)]
+pub struct Ray<S> {
+    /// Where the ray starts.
+    pub start: Point2D<f64, S>,
+    /// The direction the ray goes in.
+    pub direction: Vector2D<f64, S>,
+}
+
+impl<S> Ray<S> {
+    /// Create a new [`Ray`].
+    pub const fn new(
+        start: Point2D<f64, S>,
+        direction: Vector2D<f64, S>,
+    ) -> Self {
+        Ray { start, direction }
+    }
+
+    /// Create a [`Ray`] which starts at one point and passes through
+    /// another.
+    pub fn through(start: Point2D<f64, S>, other: Point2D<f64, S>) -> Self {
+        Ray::new(start, other - start)
+    }
+
+    /// Get the point `t` [`Ray::direction`]s along the ray, where `t >= 0`.
+    pub fn point_at(self, t: f64) -> Point2D<f64, S> {
+        self.start + self.direction * t
+    }
+
+    /// The [`XLine`] this [`Ray`] lies on.
+    pub fn to_xline(self) -> XLine<S> { XLine::new(self.start, self.direction) }
+
+    /// Find the section of this [`Ray`] which lies inside a
+    /// [`BoundingBox`], if any.
+    pub fn clip_to(self, bounds: BoundingBox<S>) -> Option<Line<S>> {
+        let (start, end) = clip_parametric(
+            self.start,
+            self.direction,
+            0.0,
+            std::f64::INFINITY,
+            bounds,
+        )?;
+
+        Some(Line::new(self.point_at(start), self.point_at(end)))
+    }
+
+    /// Where does this [`Ray`] cross an [`XLine`]?
+    pub fn intersect_xline(self, xline: XLine<S>) -> Option<Point2D<f64, S>> {
+        let (t, _) = crossing(
+            self.start,
+            self.direction,
+            xline.base_point,
+            xline.direction,
+        )?;
+
+        if t >= 0.0 {
+            Some(self.point_at(t))
+        } else {
+            None
+        }
+    }
+
+    /// Where does this [`Ray`] cross another one?
+    pub fn intersect_ray(self, other: Ray<S>) -> Option<Point2D<f64, S>> {
+        let (t, u) =
+            crossing(self.start, self.direction, other.start, other.direction)?;
+
+        if t >= 0.0 && u >= 0.0 {
+            Some(self.point_at(t))
+        } else {
+            None
+        }
+    }
+
+    /// Where does this [`Ray`] cross a [`Line`] segment?
+    pub fn intersect_line(self, line: Line<S>) -> Option<Point2D<f64, S>> {
+        let (t, u) = crossing(
+            self.start,
+            self.direction,
+            line.start,
+            line.displacement(),
+        )?;
+
+        if t >= 0.0 && (0.0..=1.0).contains(&u) {
+            Some(self.point_at(t))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S> Copy for Ray<S> {}
+
+impl<S> Clone for Ray<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Point = euclid::default::Point2D<f64>;
+    type Vector = euclid::default::Vector2D<f64>;
+
+    #[test]
+    fn rays_are_only_clipped_in_front_of_the_start() {
+        let ray = Ray::new(Point::new(1.0, 1.0), Vector::new(1.0, 0.0));
+        let bounds =
+            BoundingBox::new(Point::new(0.0, 0.0), Point::new(5.0, 5.0));
+
+        let got = ray.clip_to(bounds).unwrap();
+
+        assert_eq!(got, Line::new(Point::new(1.0, 1.0), Point::new(5.0, 1.0)));
+    }
+
+    #[test]
+    fn rays_dont_intersect_behind_themselves() {
+        let ray = Ray::new(Point::new(0.0, 0.0), Vector::new(1.0, 0.0));
+        let behind = Line::new(Point::new(-1.0, -1.0), Point::new(-1.0, 1.0));
+        let in_front = Line::new(Point::new(3.0, -1.0), Point::new(3.0, 1.0));
+
+        assert_eq!(ray.intersect_line(behind), None);
+        assert_eq!(ray.intersect_line(in_front), Some(Point::new(3.0, 0.0)));
+        assert_eq!(
+            ray.intersect_ray(Ray::new(
+                Point::new(2.0, 2.0),
+                Vector::new(0.0, -1.0)
+            )),
+            Some(Point::new(2.0, 0.0))
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    primitives::{Line, Polyline, Vertex},
+    Angle,
+};
+use euclid::{Point2D, Vector2D};
+
+/// A (possibly rotated) rectangle.
+///
+/// This is mainly a convenient way to create a box, and can be turned into a
+/// closed [`Polyline`] using [`Rectangle::to_polyline()`].
+#[derive(Debug, PartialEq)]
+pub struct Rectangle<S> {
+    /// The point in the middle of the rectangle.
+    pub centre: Point2D<f64, S>,
+    /// How wide the rectangle is, measured along its rotated x-axis.
+    pub width: f64,
+    /// How tall the rectangle is, measured along its rotated y-axis.
+    pub height: f64,
+    /// How much the rectangle is rotated (anticlockwise) around its
+    /// [`Rectangle::centre`].
+    pub rotation: Angle,
+}
+
+impl<S> Rectangle<S> {
+    /// Create a new [`Rectangle`] from its centre, size and rotation.
+    pub const fn new(
+        centre: Point2D<f64, S>,
+        width: f64,
+        height: f64,
+        rotation: Angle,
+    ) -> Self {
+        Rectangle {
+            centre,
+            width,
+            height,
+            rotation,
+        }
+    }
+
+    /// Create an axis-aligned [`Rectangle`] with two opposite corners.
+    pub fn from_corners(
+        first: Point2D<f64, S>,
+        second: Point2D<f64, S>,
+    ) -> Self {
+        let size = second - first;
+
+        Rectangle::new(
+            first.lerp(second, 0.5),
+            size.x.abs(),
+            size.y.abs(),
+            Angle::zero(),
+        )
+    }
+
+    /// The rectangle's area.
+    pub fn area(self) -> f64 { self.width * self.height }
+
+    /// The distance around the outside of the rectangle.
+    pub fn perimeter(self) -> f64 { 2.0 * (self.width + self.height) }
+
+    /// The four corners, starting with what would be the bottom-left corner
+    /// if the rectangle wasn't rotated and going anticlockwise.
+    pub fn corners(self) -> [Point2D<f64, S>; 4] {
+        let (sin, cos) = self.rotation.sin_cos();
+        let half_width = Vector2D::new(cos, sin) * (self.width / 2.0);
+        let half_height = Vector2D::new(-sin, cos) * (self.height / 2.0);
+
+        [
+            self.centre - half_width - half_height,
+            self.centre + half_width - half_height,
+            self.centre + half_width + half_height,
+            self.centre - half_width + half_height,
+        ]
+    }
+
+    /// The four edges, going anticlockwise from the bottom edge.
+    pub fn edges(self) -> [Line<S>; 4] {
+        let [a, b, c, d] = self.corners();
+
+        [
+            Line::new(a, b),
+            Line::new(b, c),
+            Line::new(c, d),
+            Line::new(d, a),
+        ]
+    }
+
+    /// Is a point inside (or on the edge of) this [`Rectangle`]?
+    pub fn contains(self, point: Point2D<f64, S>) -> bool {
+        let (sin, cos) = self.rotation.sin_cos();
+        let delta = point - self.centre;
+        let x = delta.x * cos + delta.y * sin;
+        let y = -delta.x * sin + delta.y * cos;
+
+        x.abs() <= self.width / 2.0 && y.abs() <= self.height / 2.0
+    }
+
+    /// Convert the [`Rectangle`] to an equivalent closed [`Polyline`].
+    pub fn to_polyline(self) -> Polyline<S> {
+        let vertices = self
+            .corners()
+            .iter()
+            .copied()
+            .map(Vertex::straight)
+            .collect();
+
+        Polyline::new(vertices, true)
+    }
+}
+
+impl<S> From<Rectangle<S>> for Polyline<S> {
+    fn from(rectangle: Rectangle<S>) -> Polyline<S> { rectangle.to_polyline() }
+}
+
+impl<S> Copy for Rectangle<S> {}
+
+impl<S> Clone for Rectangle<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn rectangle_from_corners() {
+        let rectangle =
+            Rectangle::from_corners(Point::new(4.0, 1.0), Point::new(0.0, 3.0));
+
+        assert_eq!(rectangle.centre, Point::new(2.0, 2.0));
+        assert_eq!(rectangle.area(), 8.0);
+        assert_eq!(rectangle.perimeter(), 12.0);
+        assert_eq!(rectangle.corners()[0], Point::new(0.0, 1.0));
+        assert_eq!(rectangle.corners()[2], Point::new(4.0, 3.0));
+        assert_eq!(rectangle.to_polyline().area(), 8.0);
+    }
+
+    #[test]
+    fn rotated_rectangle() {
+        let rectangle =
+            Rectangle::new(Point::zero(), 4.0, 2.0, Angle::frac_pi_2());
+
+        let corners = rectangle.corners();
+
+        assert!(corners[0].approx_eq(&Point::new(1.0, -2.0)));
+        assert!(corners[2].approx_eq(&Point::new(-1.0, 2.0)));
+        assert!(rectangle.contains(Point::new(0.5, 1.5)));
+        assert!(!rectangle.contains(Point::new(1.5, 0.5)));
+        assert!(rectangle.edges()[1].length().approx_eq(&2.0));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::primitives::{FillRule, Polyline};
+use euclid::{Point2D, Vector2D};
+
+/// An area bounded by an outer loop, with zero or more holes cut out of it.
+///
+/// Each loop is a closed [`Polyline`], so curved boundaries can be
+/// represented using bulged vertices.
+#[derive(Debug, PartialEq)]
+pub struct Region<S> {
+    outer: Polyline<S>,
+    holes: Vec<Polyline<S>>,
+}
+
+impl<S> Region<S> {
+    /// Create a new [`Region`].
+    ///
+    /// Every loop will be closed, regardless of what it was before.
+    pub fn new(mut outer: Polyline<S>, mut holes: Vec<Polyline<S>>) -> Self {
+        outer.set_closed(true);
+        for hole in &mut holes {
+            hole.set_closed(true);
+        }
+
+        Region { outer, holes }
+    }
+
+    /// Create a [`Region`] without any holes.
+    pub fn solid(outer: Polyline<S>) -> Self { Region::new(outer, Vec::new()) }
+
+    /// The loop around the outside of this [`Region`].
+    pub fn outer(&self) -> &Polyline<S> { &self.outer }
+
+    /// The holes cut out of this [`Region`].
+    pub fn holes(&self) -> &[Polyline<S>] { &self.holes }
+
+    /// Add another hole.
+    pub fn add_hole(&mut self, mut hole: Polyline<S>) {
+        hole.set_closed(true);
+        self.holes.push(hole);
+    }
+
+    /// Iterate over every loop, starting with the [`Region::outer()`] loop.
+    pub fn loops(&self) -> impl Iterator<Item = &Polyline<S>> + '_ {
+        std::iter::once(&self.outer).chain(&self.holes)
+    }
+
+    /// Get mutable access to every loop, starting with the
+    /// [`Region::outer()`] loop.
+    pub(crate) fn loops_mut(
+        &mut self,
+    ) -> impl Iterator<Item = &mut Polyline<S>> + '_ {
+        std::iter::once(&mut self.outer).chain(&mut self.holes)
+    }
+
+    /// The area inside the outer loop, minus the area of each hole.
+    ///
+    /// Holes are assumed to lie inside the outer loop and not overlap each
+    /// other.
+    pub fn area(&self) -> f64 {
+        self.outer.area() - self.holes.iter().map(Polyline::area).sum::<f64>()
+    }
+
+    /// The centre of mass of this [`Region`], or [`None`] if it doesn't
+    /// have any area.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::{Polyline, Region};
+    ///
+    /// let square = |x: f64, y: f64, size: f64| {
+    ///     Polyline::from_points(vec![
+    ///         Point::new(x, y),
+    ///         Point::new(x + size, y),
+    ///         Point::new(x + size, y + size),
+    ///         Point::new(x, y + size),
+    ///     ])
+    /// };
+    /// // a 4x4 square with a 2x2 hole in the bottom-left corner
+    /// let region = Region::new(square(0.0, 0.0, 4.0), vec![square(0.0, 0.0, 2.0)]);
+    ///
+    /// assert_eq!(region.area(), 12.0);
+    /// let centroid = region.centroid().unwrap();
+    /// assert_eq!(centroid, Point::new(7.0 / 3.0, 7.0 / 3.0));
+    /// ```
+    pub fn centroid(&self) -> Option<Point2D<f64, S>> {
+        let area = self.area();
+
+        if area <= std::f64::EPSILON {
+            return None;
+        }
+
+        let moment = |polyline: &Polyline<S>| {
+            polyline
+                .centroid()
+                .map(|c| c.to_vector() * polyline.area())
+                .unwrap_or_else(Vector2D::zero)
+        };
+
+        let holes: Vector2D<f64, S> = self
+            .holes
+            .iter()
+            .map(moment)
+            .fold(Vector2D::zero(), |a, b| a + b);
+
+        Some(((moment(&self.outer) - holes) / area).to_point())
+    }
+
+    /// Is a point inside this [`Region`] (i.e. inside the outer loop but not
+    /// inside any of the holes)?
+    pub fn contains(&self, point: Point2D<f64, S>) -> bool {
+        self.outer.contains(point)
+            && !self.holes.iter().any(|hole| hole.contains(point))
+    }
+
+    /// Is a point inside this [`Region`], using a particular [`FillRule`]?
+    ///
+    /// The rule is applied to [`Region::winding_number()`], so overlapping
+    /// holes or an outer loop which crosses itself can be handled either
+    /// way.
+    pub fn contains_with(
+        &self,
+        point: Point2D<f64, S>,
+        rule: FillRule,
+    ) -> bool {
+        rule.is_inside(self.winding_number(point))
+    }
+
+    /// How many times the [`Region`]'s boundary goes around a point,
+    /// treating the outer loop as anticlockwise and holes as clockwise
+    /// regardless of which way they were drawn.
+    pub fn winding_number(&self, point: Point2D<f64, S>) -> i32 {
+        let oriented = |polyline: &Polyline<S>, direction: i32| {
+            let sign = if polyline.signed_area() < 0.0 { -1 } else { 1 };
+            sign * direction * polyline.winding_number(point)
+        };
+
+        oriented(&self.outer, 1)
+            + self
+                .holes
+                .iter()
+                .map(|hole| oriented(hole, -1))
+                .sum::<i32>()
+    }
+}
+
+impl<S> Clone for Region<S> {
+    fn clone(&self) -> Self {
+        Region {
+            outer: self.outer.clone(),
+            holes: self.holes.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Vertex;
+    use euclid::approxeq::ApproxEq;
+    use std::f64::consts::PI;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    fn circle(centre: Point, radius: f64) -> Polyline<euclid::UnknownUnit> {
+        let offset = euclid::default::Vector2D::new(radius, 0.0);
+
+        Polyline::new(
+            vec![
+                Vertex::new(centre + offset, 1.0),
+                Vertex::new(centre - offset, 1.0),
+            ],
+            true,
+        )
+    }
+
+    #[test]
+    fn washer() {
+        let region = Region::new(
+            circle(Point::zero(), 2.0),
+            vec![circle(Point::zero(), 1.0)],
+        );
+
+        assert!(region.area().approx_eq(&(3.0 * PI)));
+        assert!(region.centroid().unwrap().approx_eq(&Point::zero()));
+        assert!(region.contains(Point::new(0.0, 1.5)));
+        assert!(region.contains(Point::new(-1.0, -1.0)));
+        assert!(!region.contains(Point::new(0.0, 0.5)));
+        assert!(!region.contains(Point::new(0.0, -2.5)));
+    }
+
+    #[test]
+    fn loops_are_always_closed() {
+        let outer = Polyline::from_points(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+        ]);
+        let mut open = outer.clone();
+        open.set_closed(false);
+
+        let region = Region::solid(open);
+
+        assert!(region.outer().is_closed());
+        assert_eq!(region.loops().count(), 1);
+    }
+
+    #[test]
+    fn holes_are_cut_out_regardless_of_direction() {
+        let mut hole = circle(Point::zero(), 1.0);
+        hole.set_bulge(0, -1.0);
+        hole.set_bulge(1, -1.0);
+        let outer = circle(Point::zero(), 2.0);
+        let anticlockwise_hole =
+            Region::new(outer.clone(), vec![circle(Point::zero(), 1.0)]);
+        let clockwise_hole = Region::new(outer, vec![hole]);
+
+        for region in &[anticlockwise_hole, clockwise_hole] {
+            assert_eq!(region.winding_number(Point::new(0.0, 1.5)), 1);
+            assert_eq!(region.winding_number(Point::zero()), 0);
+            assert!(!region.contains_with(Point::zero(), FillRule::NonZero));
+        }
+    }
+
+    #[test]
+    fn overlapping_holes() {
+        let region = Region::new(
+            circle(Point::zero(), 5.0),
+            vec![
+                circle(Point::new(-1.0, 0.0), 2.0),
+                circle(Point::new(1.0, 0.0), 2.0),
+            ],
+        );
+        let in_both_holes = Point::new(0.0, 0.3);
+
+        // the holes cancel each other out with the even-odd rule
+        assert!(region.contains_with(in_both_holes, FillRule::EvenOdd));
+        assert!(region.contains_with(in_both_holes, FillRule::NonZero));
+        assert_eq!(region.winding_number(in_both_holes), -1);
+        assert!(!region.contains(in_both_holes));
+    }
+}
@@ -0,0 +1,712 @@
+use euclid::{Point2D, Vector2D};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// A Non-Uniform Rational B-Spline (NURBS) curve, as used by DXF `SPLINE`
+/// entities.
+///
+/// Setting all weights to `1.0` gives you a normal (non-rational) B-spline.
+#[derive(Debug, PartialEq)]
+pub struct Spline<S> {
+    degree: usize,
+    control_points: Vec<Point2D<f64, S>>,
+    knots: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl<S> Spline<S> {
+    /// Create a new [`Spline`], making sure the various parameters are
+    /// consistent with each other.
+    pub fn new(
+        degree: usize,
+        control_points: Vec<Point2D<f64, S>>,
+        knots: Vec<f64>,
+        weights: Vec<f64>,
+    ) -> Result<Self, InvalidSpline> {
+        if degree == 0 {
+            return Err(InvalidSpline::ZeroDegree);
+        }
+        if control_points.len() <= degree {
+            return Err(InvalidSpline::NotEnoughControlPoints {
+                degree,
+                found: control_points.len(),
+            });
+        }
+
+        let expected_knots = control_points.len() + degree + 1;
+        if knots.len() != expected_knots {
+            return Err(InvalidSpline::WrongNumberOfKnots {
+                expected: expected_knots,
+                found: knots.len(),
+            });
+        }
+        if knots.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(InvalidSpline::DecreasingKnots);
+        }
+        if knots[degree] == knots[control_points.len()] {
+            return Err(InvalidSpline::EmptyDomain);
+        }
+
+        if weights.len() != control_points.len() {
+            return Err(InvalidSpline::WrongNumberOfWeights {
+                expected: control_points.len(),
+                found: weights.len(),
+            });
+        }
+        if weights.iter().any(|&w| w <= 0.0 || w.is_nan()) {
+            return Err(InvalidSpline::NonPositiveWeight);
+        }
+
+        Ok(Spline {
+            degree,
+            control_points,
+            knots,
+            weights,
+        })
+    }
+
+    /// Create a non-rational [`Spline`] with a clamped, uniform knot vector.
+    ///
+    /// Clamping the knot vector means the curve will start at the first
+    /// control point and end at the last one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::Spline;
+    ///
+    /// let control_points = vec![
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 2.0),
+    ///     Point::new(3.0, 2.0),
+    ///     Point::new(4.0, 0.0),
+    /// ];
+    ///
+    /// let spline = Spline::clamped(3, control_points).unwrap();
+    ///
+    /// assert_eq!(spline.knots(), &[0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+    /// assert_eq!(spline.start(), Point::new(0.0, 0.0));
+    /// assert_eq!(spline.end(), Point::new(4.0, 0.0));
+    /// ```
+    pub fn clamped(
+        degree: usize,
+        control_points: Vec<Point2D<f64, S>>,
+    ) -> Result<Self, InvalidSpline> {
+        let n = control_points.len();
+        let interior_knots = (n + 1).saturating_sub(degree);
+
+        let mut knots = vec![0.0; degree];
+        knots.extend(
+            (0..interior_knots)
+                .map(|i| i as f64 / (interior_knots - 1).max(1) as f64),
+        );
+        knots.extend(vec![1.0; degree]);
+        let weights = vec![1.0; n];
+
+        Spline::new(degree, control_points, knots, weights)
+    }
+
+    /// Create a cubic [`Spline`] which passes through each of the `points`,
+    /// in order.
+    ///
+    /// The curve is parameterised by chord length (the distance between
+    /// consecutive points), which gives a good shape for unevenly spaced
+    /// data like survey points. Because there are two more control points
+    /// than fit points, the `tangents` say how the ends of the curve should
+    /// behave.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # type Point = euclid::default::Point2D<f64>;
+    /// use arcs_core::primitives::{Spline, TangentConditions};
+    /// use euclid::approxeq::ApproxEq;
+    ///
+    /// let points = vec![
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 2.0),
+    ///     Point::new(3.0, 3.0),
+    ///     Point::new(6.0, 1.0),
+    /// ];
+    ///
+    /// let spline =
+    ///     Spline::fit_through(&points, TangentConditions::Natural).unwrap();
+    ///
+    /// assert_eq!(spline.degree(), 3);
+    /// assert_eq!(spline.control_points().len(), points.len() + 2);
+    /// assert!(spline.start().approx_eq(&points[0]));
+    /// assert!(spline.end().approx_eq(&points[3]));
+    /// ```
+    pub fn fit_through(
+        points: &[Point2D<f64, S>],
+        tangents: TangentConditions<S>,
+    ) -> Result<Self, InvalidSpline> {
+        const DEGREE: usize = 3;
+
+        if points.len() < 2 {
+            return Err(InvalidSpline::NotEnoughFitPoints {
+                found: points.len(),
+            });
+        }
+
+        let chord_lengths: Vec<f64> = points
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).length())
+            .collect();
+        if chord_lengths
+            .iter()
+            .any(|&length| length <= std::f64::EPSILON)
+        {
+            return Err(InvalidSpline::DuplicateFitPoints);
+        }
+        let total_length: f64 = chord_lengths.iter().sum();
+
+        // the parameter value at which the curve passes through each point
+        let mut parameters = vec![0.0];
+        let mut distance = 0.0;
+        for length in &chord_lengths[..chord_lengths.len() - 1] {
+            distance += length;
+            parameters.push(distance / total_length);
+        }
+        parameters.push(1.0);
+
+        let mut knots = vec![0.0; DEGREE + 1];
+        knots.extend_from_slice(&parameters[1..parameters.len() - 1]);
+        knots.extend(vec![1.0; DEGREE + 1]);
+
+        // set up a linear system with one row per control point, A*P = b
+        let n = points.len() + 2;
+        let last = n - 1;
+        let mut matrix = vec![vec![0.0; n]; n];
+        let mut rhs = vec![Vector2D::zero(); n];
+
+        // the curve is clamped, so it starts and ends on the first and last
+        // control points
+        matrix[0][0] = 1.0;
+        rhs[0] = points[0].to_vector();
+        matrix[last][last] = 1.0;
+        rhs[last] = points[points.len() - 1].to_vector();
+
+        for (i, &u) in parameters.iter().enumerate().skip(1).take(n - 4) {
+            let span = i + DEGREE;
+            let basis = basis_functions(&knots, DEGREE, span, u);
+            matrix[i + 1][span - DEGREE..=span].copy_from_slice(&basis);
+            rhs[i + 1] = points[i].to_vector();
+        }
+
+        // the derivatives at either end depend only on the first or last
+        // couple of control points
+        let start_gap = knots[DEGREE + 1] - knots[1];
+        let end_gap = knots[last + DEGREE] - knots[last];
+
+        match tangents {
+            TangentConditions::Natural => {
+                // zero curvature at the ends means the first two (and last
+                // two) legs of the control polygon have equal derivatives
+                let next_gap = knots[DEGREE + 2] - knots[2];
+                matrix[1][0] = -1.0 / start_gap;
+                matrix[1][1] = 1.0 / start_gap + 1.0 / next_gap;
+                matrix[1][2] = -1.0 / next_gap;
+
+                let previous_gap = knots[last + DEGREE - 1] - knots[last - 1];
+                matrix[last - 1][last] = -1.0 / end_gap;
+                matrix[last - 1][last - 1] = 1.0 / end_gap + 1.0 / previous_gap;
+                matrix[last - 1][last - 2] = -1.0 / previous_gap;
+            },
+            TangentConditions::Clamped { start, end } => {
+                // C'(0) = degree * (P[1] - P[0]) / start_gap, and scaling the
+                // tangent by the total length roughly matches the speed of a
+                // chord length parameterisation
+                let factor = DEGREE as f64 / start_gap;
+                matrix[1][0] = -factor;
+                matrix[1][1] = factor;
+                rhs[1] = normalized_or_zero(start) * total_length;
+
+                let factor = DEGREE as f64 / end_gap;
+                matrix[last - 1][last - 1] = -factor;
+                matrix[last - 1][last] = factor;
+                rhs[last - 1] = normalized_or_zero(end) * total_length;
+            },
+        }
+
+        let control_points = solve(matrix, rhs)
+            .ok_or(InvalidSpline::DuplicateFitPoints)?
+            .into_iter()
+            .map(|vector| vector.to_point())
+            .collect();
+
+        Spline::new(DEGREE, control_points, knots, vec![1.0; n])
+    }
+
+    /// The polynomial degree of each piece of the curve.
+    pub fn degree(&self) -> usize { self.degree }
+
+    /// The points which "pull" the curve into shape.
+    pub fn control_points(&self) -> &[Point2D<f64, S>] { &self.control_points }
+
+    /// Mutable access to the control points.
+    ///
+    /// Moving control points around is always valid, so this doesn't need to
+    /// go through [`Spline::new()`]'s checks.
+    pub fn control_points_mut(&mut self) -> &mut [Point2D<f64, S>] {
+        &mut self.control_points
+    }
+
+    /// The knot vector.
+    pub fn knots(&self) -> &[f64] { &self.knots }
+
+    /// The weight associated with each control point.
+    pub fn weights(&self) -> &[f64] { &self.weights }
+
+    /// Does any control point have a weight other than `1.0`?
+    pub fn is_rational(&self) -> bool { self.weights.iter().any(|&w| w != 1.0) }
+
+    /// The range of parameter values the curve is defined over.
+    pub fn domain(&self) -> (f64, f64) {
+        (
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        )
+    }
+
+    /// Where the curve starts.
+    pub fn start(&self) -> Point2D<f64, S> { self.point_at(self.domain().0) }
+
+    /// Where the curve ends.
+    pub fn end(&self) -> Point2D<f64, S> { self.point_at(self.domain().1) }
+
+    /// Evaluate the curve at the parameter `u` using [de Boor's
+    /// algorithm][wiki].
+    ///
+    /// Values outside [`Spline::domain()`] are clamped.
+    ///
+    /// [wiki]: https://en.wikipedia.org/wiki/De_Boor%27s_algorithm
+    pub fn point_at(&self, u: f64) -> Point2D<f64, S> {
+        let (start, end) = self.domain();
+        let u = u.max(start).min(end);
+        let p = self.degree;
+        let k = self.span_containing(u);
+
+        // work in homogeneous coordinates, (w*x, w*y, w)
+        let mut d: Vec<[f64; 3]> = (0..=p)
+            .map(|j| {
+                let point = self.control_points[j + k - p];
+                let w = self.weights[j + k - p];
+                [point.x * w, point.y * w, w]
+            })
+            .collect();
+
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let left = self.knots[j + k - p];
+                let right = self.knots[j + 1 + k - r];
+                let alpha = (u - left) / (right - left);
+
+                let previous = d[j - 1];
+                for (value, previous) in d[j].iter_mut().zip(&previous) {
+                    *value = (1.0 - alpha) * previous + alpha * *value;
+                }
+            }
+        }
+
+        let [x, y, w] = d[p];
+        Point2D::new(x / w, y / w)
+    }
+
+    /// The parameter values where one polynomial piece ends and the next
+    /// starts, including the start and end of the [`Spline::domain()`].
+    pub fn breakpoints(&self) -> Vec<f64> {
+        let mut breakpoints: Vec<f64> =
+            self.knots[self.degree..=self.control_points.len()].to_vec();
+        breakpoints.dedup();
+        breakpoints
+    }
+
+    /// Find the index, `k`, such that `knots[k] <= u < knots[k + 1]`.
+    fn span_containing(&self, u: f64) -> usize {
+        let last_span = self.control_points.len() - 1;
+
+        (self.degree..last_span)
+            .find(|&k| self.knots[k] <= u && u < self.knots[k + 1])
+            .unwrap_or(last_span)
+    }
+}
+
+/// Evaluate the `degree + 1` basis functions which are non-zero at `u`, where
+/// `knots[span] <= u < knots[span + 1]` (Algorithm A2.2 from *The NURBS
+/// Book*).
+fn basis_functions(
+    knots: &[f64],
+    degree: usize,
+    span: usize,
+    u: f64,
+) -> Vec<f64> {
+    let mut basis = vec![0.0; degree + 1];
+    let mut left = vec![0.0; degree + 1];
+    let mut right = vec![0.0; degree + 1];
+    basis[0] = 1.0;
+
+    for j in 1..=degree {
+        left[j] = u - knots[span + 1 - j];
+        right[j] = knots[span + j] - u;
+        let mut saved = 0.0;
+
+        for r in 0..j {
+            let temp = basis[r] / (right[r + 1] + left[j - r]);
+            basis[r] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+
+        basis[j] = saved;
+    }
+
+    basis
+}
+
+fn normalized_or_zero<S>(vector: Vector2D<f64, S>) -> Vector2D<f64, S> {
+    let length = vector.length();
+
+    if length > 0.0 {
+        vector / length
+    } else {
+        Vector2D::zero()
+    }
+}
+
+/// Solve `A*x = b` using Gaussian elimination with partial pivoting,
+/// returning [`None`] if the matrix is singular.
+fn solve<S>(
+    mut matrix: Vec<Vec<f64>>,
+    mut rhs: Vec<Vector2D<f64, S>>,
+) -> Option<Vec<Vector2D<f64, S>>> {
+    let n = rhs.len();
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&a, &b| {
+            matrix[a][column]
+                .abs()
+                .partial_cmp(&matrix[b][column].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+
+        if matrix[pivot][column].abs() <= std::f64::EPSILON {
+            return None;
+        }
+
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        for row in column + 1..n {
+            let factor = matrix[row][column] / matrix[column][column];
+
+            if factor != 0.0 {
+                let (above, below) = matrix.split_at_mut(row);
+                for (value, &pivot_value) in
+                    below[0][column..].iter_mut().zip(&above[column][column..])
+                {
+                    *value -= factor * pivot_value;
+                }
+                let pivot_row = rhs[column];
+                rhs[row] -= pivot_row * factor;
+            }
+        }
+    }
+
+    for row in (0..n).rev() {
+        let mut value = rhs[row];
+        for (solved, &coefficient) in
+            rhs[row + 1..].iter().zip(&matrix[row][row + 1..])
+        {
+            value -= *solved * coefficient;
+        }
+        rhs[row] = value / matrix[row][row];
+    }
+
+    Some(rhs)
+}
+
+/// How the ends of a [`Spline`] created with [`Spline::fit_through()`]
+/// should behave.
+#[derive(Debug, PartialEq)]
+pub enum TangentConditions<S> {
+    /// Let the curve straighten out at each end (i.e. zero curvature).
+    Natural,
+    /// Make the curve leave the first point and arrive at the last point
+    /// travelling in particular directions.
+    Clamped {
+        /// The direction the curve starts in.
+        start: Vector2D<f64, S>,
+        /// The direction the curve finishes in.
+        end: Vector2D<f64, S>,
+    },
+}
+
+impl<S> Default for TangentConditions<S> {
+    fn default() -> Self { TangentConditions::Natural }
+}
+
+impl<S> Copy for TangentConditions<S> {}
+
+impl<S> Clone for TangentConditions<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<S> Clone for Spline<S> {
+    fn clone(&self) -> Self {
+        Spline {
+            degree: self.degree,
+            control_points: self.control_points.clone(),
+            knots: self.knots.clone(),
+            weights: self.weights.clone(),
+        }
+    }
+}
+
+/// The reasons a [`Spline`] may be invalid.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InvalidSpline {
+    /// The degree must be at least 1.
+    ZeroDegree,
+    /// A spline of degree `p` needs at least `p + 1` control points.
+    NotEnoughControlPoints {
+        /// The spline's degree.
+        degree: usize,
+        /// The number of control points provided.
+        found: usize,
+    },
+    /// There should be `control_points + degree + 1` knots.
+    WrongNumberOfKnots {
+        /// The number of knots required.
+        expected: usize,
+        /// The number of knots provided.
+        found: usize,
+    },
+    /// Knot values must never decrease.
+    DecreasingKnots,
+    /// The knots don't leave any room for the curve to be defined over.
+    EmptyDomain,
+    /// Each control point needs exactly one weight.
+    WrongNumberOfWeights {
+        /// The number of weights required.
+        expected: usize,
+        /// The number of weights provided.
+        found: usize,
+    },
+    /// Weights must be greater than zero.
+    NonPositiveWeight,
+    /// At least two points are needed to fit a spline through them.
+    NotEnoughFitPoints {
+        /// The number of points provided.
+        found: usize,
+    },
+    /// Consecutive fit points must be at different locations.
+    DuplicateFitPoints,
+}
+
+impl Display for InvalidSpline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidSpline::ZeroDegree => {
+                write!(f, "The degree must be at least 1")
+            },
+            InvalidSpline::NotEnoughControlPoints { degree, found } => write!(
+                f,
+                "A spline of degree {} needs at least {} control points, found {}",
+                degree,
+                degree + 1,
+                found
+            ),
+            InvalidSpline::WrongNumberOfKnots { expected, found } => {
+                write!(f, "Expected {} knots but found {}", expected, found)
+            },
+            InvalidSpline::DecreasingKnots => {
+                write!(f, "The knot vector must be non-decreasing")
+            },
+            InvalidSpline::EmptyDomain => {
+                write!(f, "The knot vector doesn't define a valid domain")
+            },
+            InvalidSpline::WrongNumberOfWeights { expected, found } => {
+                write!(f, "Expected {} weights but found {}", expected, found)
+            },
+            InvalidSpline::NonPositiveWeight => {
+                write!(f, "All weights must be greater than zero")
+            },
+            InvalidSpline::NotEnoughFitPoints { found } => write!(
+                f,
+                "At least 2 points are needed to fit a spline, found {}",
+                found
+            ),
+            InvalidSpline::DuplicateFitPoints => {
+                write!(f, "Consecutive fit points must be distinct")
+            },
+        }
+    }
+}
+
+impl Error for InvalidSpline {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::CubicBezier;
+    use euclid::approxeq::ApproxEq;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn clamped_cubic_with_four_points_is_a_bezier() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 5.0),
+            Point::new(4.0, -2.0),
+            Point::new(6.0, 1.0),
+        ];
+        let spline = Spline::clamped(3, points.clone()).unwrap();
+        let bezier =
+            CubicBezier::new(points[0], points[1], points[2], points[3]);
+
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!(spline.point_at(t).approx_eq(&bezier.point_at(t)));
+        }
+    }
+
+    #[test]
+    fn rational_quadratic_can_represent_a_circle() {
+        // a quarter circle, the classic NURBS example
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        let spline = Spline::new(
+            2,
+            vec![
+                Point::new(1.0, 0.0),
+                Point::new(1.0, 1.0),
+                Point::new(0.0, 1.0),
+            ],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            vec![1.0, w, 1.0],
+        )
+        .unwrap();
+
+        assert!(spline.is_rational());
+        for i in 0..=10 {
+            let point = spline.point_at(i as f64 / 10.0);
+            assert!(point.to_vector().length().approx_eq(&1.0));
+        }
+    }
+
+    #[test]
+    fn multiple_spans() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 0.0),
+            Point::new(3.0, 1.0),
+            Point::new(4.0, 0.0),
+        ];
+
+        let spline = Spline::clamped(2, points).unwrap();
+
+        assert_eq!(
+            spline.knots(),
+            &[0.0, 0.0, 0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0, 1.0, 1.0]
+        );
+        assert_eq!(spline.breakpoints().len(), 4);
+        assert_eq!(spline.start(), Point::new(0.0, 0.0));
+        assert_eq!(spline.end(), Point::new(4.0, 0.0));
+    }
+
+    #[test]
+    fn detect_invalid_splines() {
+        let points = vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0)];
+
+        assert_eq!(
+            Spline::clamped(2, points.clone()).unwrap_err(),
+            InvalidSpline::NotEnoughControlPoints {
+                degree: 2,
+                found: 2
+            }
+        );
+        assert_eq!(
+            Spline::new(1, points.clone(), vec![0.0, 0.0, 1.0], vec![1.0; 2])
+                .unwrap_err(),
+            InvalidSpline::WrongNumberOfKnots {
+                expected: 4,
+                found: 3
+            }
+        );
+        assert_eq!(
+            Spline::new(
+                1,
+                points.clone(),
+                vec![0.0, 1.0, 0.5, 1.0],
+                vec![1.0; 2]
+            )
+            .unwrap_err(),
+            InvalidSpline::DecreasingKnots
+        );
+        assert_eq!(
+            Spline::new(1, points, vec![0.0, 0.0, 1.0, 1.0], vec![1.0, 0.0])
+                .unwrap_err(),
+            InvalidSpline::NonPositiveWeight
+        );
+    }
+
+    #[test]
+    fn fitted_splines_pass_through_every_point() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(3.0, 4.0),
+            Point::new(7.0, 4.5),
+            Point::new(8.0, 2.0),
+        ];
+        let tangents = TangentConditions::Clamped {
+            start: euclid::default::Vector2D::new(1.0, 0.0),
+            end: euclid::default::Vector2D::new(0.0, -1.0),
+        };
+
+        for &conditions in &[TangentConditions::Natural, tangents] {
+            let spline = Spline::fit_through(&points, conditions).unwrap();
+
+            // fit points are hit at the knots
+            let mut parameters = spline.breakpoints();
+            assert_eq!(parameters.len(), points.len());
+            for (u, point) in parameters.drain(..).zip(&points) {
+                assert!(spline.point_at(u).approx_eq(point));
+            }
+        }
+
+        // a clamped start means the first leg of the control polygon points
+        // in the requested direction
+        let spline = Spline::fit_through(&points, tangents).unwrap();
+        let first_leg = spline.control_points()[1] - spline.control_points()[0];
+        assert!(first_leg.y.approx_eq(&0.0) && first_leg.x > 0.0);
+    }
+
+    #[test]
+    fn two_natural_points_make_a_straight_line() {
+        let points = [Point::new(0.0, 0.0), Point::new(3.0, 3.0)];
+
+        let spline =
+            Spline::fit_through(&points, TangentConditions::Natural).unwrap();
+
+        let midpoint = spline.point_at(0.5);
+        assert!(midpoint.approx_eq(&Point::new(1.5, 1.5)));
+        assert_eq!(
+            Spline::fit_through(&points[..1], TangentConditions::Natural)
+                .unwrap_err(),
+            InvalidSpline::NotEnoughFitPoints { found: 1 }
+        );
+        assert_eq!(
+            Spline::fit_through(
+                &[points[0], points[0]],
+                TangentConditions::Natural
+            )
+            .unwrap_err(),
+            InvalidSpline::DuplicateFitPoints
+        );
+    }
+}
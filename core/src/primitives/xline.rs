@@ -0,0 +1,184 @@
+use crate::{primitives::Line, BoundingBox};
+use euclid::{Point2D, Vector2D};
+
+/// A line which goes on forever in both directions, typically used as
+/// construction geometry.
+#[derive(Debug, PartialEq)]
+pub struct XLine<S> {
+    /// A point the line passes through.
+    pub base_point: Point2D<f64, S>,
+    /// The direction the line runs in.
+    pub direction: Vector2D<f64, S>,
+}
+
+impl<S> XLine<S> {
+    /// Create a new [`XLine`].
+    pub const fn new(
+        base_point: Point2D<f64, S>,
+        direction: Vector2D<f64, S>,
+    ) -> Self {
+        XLine {
+            base_point,
+            direction,
+        }
+    }
+
+    /// Create an [`XLine`] which passes through two points.
+    pub fn through(first: Point2D<f64, S>, second: Point2D<f64, S>) -> Self {
+        XLine::new(first, second - first)
+    }
+
+    /// Get the point `t` [`XLine::direction`]s along the line.
+    pub fn point_at(self, t: f64) -> Point2D<f64, S> {
+        self.base_point + self.direction * t
+    }
+
+    /// Find the section of this [`XLine`] which lies inside a
+    /// [`BoundingBox`], if any.
+    pub fn clip_to(self, bounds: BoundingBox<S>) -> Option<Line<S>> {
+        let (start, end) = clip_parametric(
+            self.base_point,
+            self.direction,
+            std::f64::NEG_INFINITY,
+            std::f64::INFINITY,
+            bounds,
+        )?;
+
+        Some(Line::new(self.point_at(start), self.point_at(end)))
+    }
+
+    /// Where does this [`XLine`] cross another one?
+    ///
+    /// Parallel lines never cross.
+    pub fn intersect_xline(self, other: XLine<S>) -> Option<Point2D<f64, S>> {
+        crossing(
+            self.base_point,
+            self.direction,
+            other.base_point,
+            other.direction,
+        )
+        .map(|(t, _)| self.point_at(t))
+    }
+
+    /// Where does this [`XLine`] cross a [`Line`] segment?
+    pub fn intersect_line(self, line: Line<S>) -> Option<Point2D<f64, S>> {
+        let (t, u) = crossing(
+            self.base_point,
+            self.direction,
+            line.start,
+            line.displacement(),
+        )?;
+
+        if (0.0..=1.0).contains(&u) {
+            Some(self.point_at(t))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S> Copy for XLine<S> {}
+
+impl<S> Clone for XLine<S> {
+    fn clone(&self) -> Self { *self }
+}
+
+/// Given two lines, `p + t*d` and `q + u*e`, find the `t` and `u` where they
+/// cross.
+pub(crate) fn crossing<S>(
+    p: Point2D<f64, S>,
+    d: Vector2D<f64, S>,
+    q: Point2D<f64, S>,
+    e: Vector2D<f64, S>,
+) -> Option<(f64, f64)> {
+    let denominator = d.cross(e);
+
+    if denominator.abs() <= std::f64::EPSILON * d.length() * e.length() {
+        return None;
+    }
+
+    let delta = q - p;
+    Some((delta.cross(e) / denominator, delta.cross(d) / denominator))
+}
+
+/// Clip the section of the line `origin + t*direction` where
+/// `t_min <= t <= t_max` to a [`BoundingBox`] (the [Liang-Barsky
+/// algorithm][wiki]).
+///
+/// [wiki]: https://en.wikipedia.org/wiki/Liang%E2%80%93Barsky_algorithm
+pub(crate) fn clip_parametric<S>(
+    origin: Point2D<f64, S>,
+    direction: Vector2D<f64, S>,
+    mut t_min: f64,
+    mut t_max: f64,
+    bounds: BoundingBox<S>,
+) -> Option<(f64, f64)> {
+    let edges = [
+        (-direction.x, origin.x - bounds.min_x()),
+        (direction.x, bounds.max_x() - origin.x),
+        (-direction.y, origin.y - bounds.min_y()),
+        (direction.y, bounds.max_y() - origin.y),
+    ];
+
+    for &(p, q) in &edges {
+        if p == 0.0 {
+            if q < 0.0 {
+                // parallel to, and outside of, this edge
+                return None;
+            }
+        } else {
+            let t = q / p;
+
+            if p < 0.0 {
+                t_min = t_min.max(t);
+            } else {
+                t_max = t_max.min(t);
+            }
+        }
+    }
+
+    if t_min <= t_max && t_min.is_finite() && t_max.is_finite() {
+        Some((t_min, t_max))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Point = euclid::default::Point2D<f64>;
+    type Vector = euclid::default::Vector2D<f64>;
+
+    #[test]
+    fn clip_a_diagonal_to_a_box() {
+        let xline = XLine::new(Point::new(1.0, 1.0), Vector::new(1.0, 1.0));
+        let bounds =
+            BoundingBox::new(Point::new(-2.0, 0.0), Point::new(5.0, 3.0));
+
+        let got = xline.clip_to(bounds).unwrap();
+
+        assert_eq!(got, Line::new(Point::new(0.0, 0.0), Point::new(3.0, 3.0)));
+    }
+
+    #[test]
+    fn lines_outside_the_box_are_clipped_away() {
+        let xline = XLine::new(Point::new(0.0, 10.0), Vector::new(1.0, 0.0));
+        let bounds =
+            BoundingBox::new(Point::new(-2.0, 0.0), Point::new(5.0, 3.0));
+
+        assert!(xline.clip_to(bounds).is_none());
+    }
+
+    #[test]
+    fn crossing_a_line_segment() {
+        let xline = XLine::through(Point::new(0.0, 0.0), Point::new(0.0, 1.0));
+        let hit = Line::new(Point::new(-1.0, 5.0), Point::new(1.0, 5.0));
+        let miss = Line::new(Point::new(1.0, 5.0), Point::new(2.0, 5.0));
+
+        assert_eq!(xline.intersect_line(hit), Some(Point::new(0.0, 5.0)));
+        assert_eq!(xline.intersect_line(miss), None);
+        assert_eq!(xline.intersect_xline(xline), None);
+    }
+}
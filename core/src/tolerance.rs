@@ -0,0 +1,224 @@
+use crate::{
+    algorithms::DEFAULT_TOLERANCE,
+    primitives::{Arc, Circle, Line, Segment},
+    Angle,
+};
+use euclid::{Length, Point2D, Vector2D};
+use std::f64::consts::PI;
+
+/// How close two things need to be before they are considered equal.
+///
+/// Intersections, joins and equality checks all take a [`Tolerance`] so the
+/// precision can be tuned to suit a drawing (e.g. a site plan measured in
+/// metres doesn't need the same precision as a PCB measured in mm).
+///
+/// # Examples
+///
+/// ```rust
+/// # type Point = euclid::default::Point2D<f64>;
+/// use arcs_core::{ApproxEqWithin, Tolerance};
+///
+/// let a = Point::new(1.0, 2.0);
+/// let b = Point::new(1.0, 2.0005);
+///
+/// assert!(!a.approx_eq_within(&b, Tolerance::default()));
+/// assert!(a.approx_eq_within(&b, Tolerance::from(1e-3)));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tolerance {
+    /// The largest distance between two points which are considered to be
+    /// in the same place.
+    pub linear: f64,
+    /// The largest difference between two angles which are considered to be
+    /// the same.
+    pub angular: Angle,
+}
+
+impl Tolerance {
+    /// Create a new [`Tolerance`].
+    pub fn new(linear: f64, angular: Angle) -> Self {
+        Tolerance { linear, angular }
+    }
+
+    /// Is `value` close enough to zero to be ignored?
+    pub fn is_zero(self, value: f64) -> bool { value.abs() <= self.linear }
+}
+
+impl Default for Tolerance {
+    /// Uses [`DEFAULT_TOLERANCE`] for both distances and angles (in
+    /// radians).
+    fn default() -> Self {
+        Tolerance::new(DEFAULT_TOLERANCE, Angle::radians(DEFAULT_TOLERANCE))
+    }
+}
+
+impl From<f64> for Tolerance {
+    /// Use `linear` for distances, keeping the default angular tolerance.
+    fn from(linear: f64) -> Self {
+        Tolerance {
+            linear,
+            ..Default::default()
+        }
+    }
+}
+
+/// Equality which allows for floating point error, as defined by a
+/// [`Tolerance`].
+///
+/// Unlike [`euclid::approxeq::ApproxEq`], the tolerance is chosen by the
+/// caller and distances are used as-is rather than being compared
+/// component-wise.
+pub trait ApproxEqWithin<Rhs: ?Sized = Self> {
+    /// Are `self` and `other` within `tolerance` of each other?
+    fn approx_eq_within(&self, other: &Rhs, tolerance: Tolerance) -> bool;
+}
+
+impl ApproxEqWithin for f64 {
+    fn approx_eq_within(&self, other: &f64, tolerance: Tolerance) -> bool {
+        tolerance.is_zero(self - other)
+    }
+}
+
+impl ApproxEqWithin for Angle {
+    /// Angles are compared around the circle, so `359.9°` and `0.1°` are
+    /// only `0.2°` apart.
+    fn approx_eq_within(&self, other: &Angle, tolerance: Tolerance) -> bool {
+        let difference = (self.radians - other.radians).rem_euclid(2.0 * PI);
+        difference.min(2.0 * PI - difference) <= tolerance.angular.radians
+    }
+}
+
+impl<S> ApproxEqWithin for Length<f64, S> {
+    fn approx_eq_within(
+        &self,
+        other: &Length<f64, S>,
+        tolerance: Tolerance,
+    ) -> bool {
+        self.get().approx_eq_within(&other.get(), tolerance)
+    }
+}
+
+impl<S> ApproxEqWithin for Point2D<f64, S> {
+    fn approx_eq_within(
+        &self,
+        other: &Point2D<f64, S>,
+        tolerance: Tolerance,
+    ) -> bool {
+        (*self - *other).approx_eq_within(&Vector2D::zero(), tolerance)
+    }
+}
+
+impl<S> ApproxEqWithin for Vector2D<f64, S> {
+    fn approx_eq_within(
+        &self,
+        other: &Vector2D<f64, S>,
+        tolerance: Tolerance,
+    ) -> bool {
+        tolerance.is_zero((*self - *other).length())
+    }
+}
+
+impl<S> ApproxEqWithin for Line<S> {
+    /// Lines are equal when they start and end in the same place, so the
+    /// direction matters.
+    fn approx_eq_within(&self, other: &Line<S>, tolerance: Tolerance) -> bool {
+        self.start.approx_eq_within(&other.start, tolerance)
+            && self.end.approx_eq_within(&other.end, tolerance)
+    }
+}
+
+impl<S> ApproxEqWithin for Arc<S> {
+    /// Arcs are equal when they lie on the same circle, start at the same
+    /// angle, and sweep through the same angle (so a full circle is not
+    /// equal to a zero-length arc).
+    fn approx_eq_within(&self, other: &Arc<S>, tolerance: Tolerance) -> bool {
+        let sweep_difference =
+            (self.sweep_angle().radians - other.sweep_angle().radians).abs();
+
+        self.centre().approx_eq_within(&other.centre(), tolerance)
+            && self.radius().approx_eq_within(&other.radius(), tolerance)
+            && self
+                .start_angle()
+                .approx_eq_within(&other.start_angle(), tolerance)
+            && sweep_difference <= tolerance.angular.radians
+    }
+}
+
+impl<S> ApproxEqWithin for Circle<S> {
+    fn approx_eq_within(
+        &self,
+        other: &Circle<S>,
+        tolerance: Tolerance,
+    ) -> bool {
+        self.centre.approx_eq_within(&other.centre, tolerance)
+            && self.radius.approx_eq_within(&other.radius, tolerance)
+    }
+}
+
+impl<S> ApproxEqWithin for Segment<S> {
+    fn approx_eq_within(
+        &self,
+        other: &Segment<S>,
+        tolerance: Tolerance,
+    ) -> bool {
+        match (self, other) {
+            (Segment::Line(a), Segment::Line(b)) => {
+                a.approx_eq_within(b, tolerance)
+            },
+            (Segment::Arc(a), Segment::Arc(b)) => {
+                a.approx_eq_within(b, tolerance)
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Point = euclid::default::Point2D<f64>;
+
+    #[test]
+    fn angles_wrap_around() {
+        let tolerance = Tolerance::new(0.0, Angle::degrees(0.5));
+
+        assert!(Angle::degrees(359.9)
+            .approx_eq_within(&Angle::degrees(0.1), tolerance));
+        assert!(Angle::degrees(-90.0)
+            .approx_eq_within(&Angle::degrees(270.0), tolerance));
+        assert!(!Angle::degrees(10.0)
+            .approx_eq_within(&Angle::degrees(11.0), tolerance));
+    }
+
+    #[test]
+    fn full_circles_are_not_empty_arcs() {
+        let full = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::two_pi(),
+        );
+        let empty = Arc::from_centre_radius(
+            Point::zero(),
+            1.0,
+            Angle::zero(),
+            Angle::zero(),
+        );
+
+        assert!(full.approx_eq_within(&full, Tolerance::default()));
+        assert!(!full.approx_eq_within(&empty, Tolerance::default()));
+    }
+
+    #[test]
+    fn lines_care_about_direction() {
+        let line = Line::new(Point::new(0.0, 0.0), Point::new(1.0, 1.0));
+        let nudged = Line::new(Point::new(0.0, 0.001), Point::new(1.0, 1.0));
+        let reversed = Line::new(line.end, line.start);
+        let coarse = Tolerance::from(0.01);
+
+        assert!(line.approx_eq_within(&nudged, coarse));
+        assert!(!line.approx_eq_within(&nudged, Tolerance::default()));
+        assert!(!line.approx_eq_within(&reversed, coarse));
+    }
+}